@@ -2,7 +2,7 @@
 //! 
 //! This defines the main App component and the overall layout structure.
 
-use dioxus::desktop::{use_window, use_wry_event_handler};
+use dioxus::desktop::{use_window, use_wry_event_handler, WindowCloseBehaviour};
 use dioxus::desktop::tao::event::{Event as TaoEvent, WindowEvent as TaoWindowEvent};
 use dioxus::prelude::*;
 use chrono::Utc;
@@ -10,44 +10,80 @@ use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
-use crate::core::generation::next_version_label;
+use crate::core::generation::{
+    generation_retry_delay_secs, jobs_to_start, output_version_records, GenerationError,
+};
 use crate::core::audio::decode::{decode_audio_to_f32, AudioDecodeConfig};
-use crate::core::audio::cache::{cache_matches_source, load_peak_cache, peak_cache_path};
+use crate::core::audio::cache::{peak_cache_path, shared_peak_caches};
+use crate::core::audio::mixdown::build_playback_items;
+use crate::core::audio::loudness::format_lufs;
+use crate::core::audio::meter::{step_meter, MeterChannel, METER_POLL_INTERVAL_MS};
 use crate::core::audio::playback::{AudioPlaybackEngine, PlaybackItem};
 use crate::core::audio::waveform::{
-    build_and_store_peak_cache, resolve_audio_or_video_source, resolve_audio_source, PeakBuildConfig,
+    load_shared_peak_cache, resolve_audio_or_video_source, resolve_audio_source, run_peak_cache_build,
+    PeakBuildConfig,
+};
+use crate::core::cancel::CancelHandle;
+use crate::core::export::{ExportProgress, ExportStatus};
+use crate::core::ffmpeg::{export_frame_count, export_project};
+use crate::core::media::{
+    index_project_media, probe_duration_seconds, resolve_asset_duration_seconds, spawn_asset_duration_probe,
+    spawn_video_codec_check, transcode_video_asset, create_video_proxy,
+};
+use crate::core::preview_gpu::{
+    choose_preview_backend, preview_backend_note, GpuAvailability, PreviewBackend, PreviewBounds,
+    PreviewGpuSurface,
+};
+use crate::core::provider_package::{
+    export_provider_package, import_provider_package, ProviderPackage, PROVIDER_PACKAGE_EXTENSION,
 };
-use crate::core::media::{resolve_asset_duration_seconds, spawn_asset_duration_probe, spawn_missing_duration_probes};
-use crate::core::preview_gpu::{PreviewBounds, PreviewGpuSurface};
 use crate::core::provider_store::{
-    list_global_provider_files,
-    load_global_provider_entries_or_empty,
+    list_provider_files,
+    load_merged_provider_entries_or_empty,
+    read_provider_file,
+    toggle_provider_favorite,
 };
+use crate::core::recent_projects::record_recent_project;
 use crate::core::timeline_snap::{
     best_snap_delta_frames,
+    clip_edge_targets,
     frames_from_seconds,
     seconds_from_frames,
+    next_marker_time,
+    nudge_start_times,
+    prev_marker_time,
     snap_time_to_frame,
+    step_time_by_frames,
     SnapTarget,
     SnapTargetKind,
 };
 use crate::state::{
-    GenerationJob, GenerationJobStatus, ProviderConnection, ProviderEntry, ProviderOutputType,
+    asset_display_name, GenerationJob, GenerationJobStatus, ProviderConnection, ProviderEntry, ProviderOutputType,
 };
 use crate::state::TrackType;
-use crate::providers::comfyui;
-use crate::timeline::{timeline_zoom_bounds, TimelinePanel};
-use crate::hotkeys::{handle_hotkey, HotkeyAction, HotkeyContext, HotkeyResult};
+use crate::providers::{comfyui, health, http};
+use crate::timeline::{
+    adjust_scroll_for_zoom, playhead_follow_scroll, ruler_time_at, timeline_zoom_bounds,
+    timeline_zoom_to_fit, sequential_clip_starts, timeline_zoom_to_span, zoom_focus_time,
+    TimelinePanel,
+};
+use crate::hotkeys::{handle_hotkey_with_keymap, HotkeyAction, HotkeyContext, HotkeyResult, KeyMap};
 use crate::constants::*;
+use crate::core::close_guard::{
+    active_job_count, close_after_choice, should_warn_on_close, UnsavedChoice,
+};
 use crate::components::{
-    GenerationQueuePanel, NewProjectModal, PreviewPanel,
-    ProviderBuilderModalV2, ProviderJsonEditorModal, ProvidersModalV2,
+    ExportProgressModal, ExportSettingsModal, GenerationQueuePanel, NewProjectModal, PreviewPanel,
+    ProviderBuilderModalV2, ProviderJsonEditorModal, ProvidersModalV2, RelinkModal,
     SidePanel, StartupModal, StatusBar, StartupModalMode, TitleBar, TrackContextMenu,
 };
-use crate::components::assets::AssetsPanelContent;
-use crate::components::attributes::AttributesPanelContent;
+use crate::components::assets::{
+    displayed_assets, move_asset_cursor, AssetCursorMove, AssetTypeFilter, AssetsPanelContent,
+};
+use crate::components::attributes::{generate_clips, AttributesPanelContent};
 
 
 #[derive(Clone, Copy, Serialize)]
@@ -61,11 +97,21 @@ enum PreviewCanvasMessage {
 struct TimelineViewportState {
     width: f64,
     scroll_left: f64,
+    /// Set for Ctrl/Cmd+wheel over the timeline.
+    #[serde(default)]
+    wheel_zoom: Option<TimelineWheelZoom>,
+}
+
+#[derive(Clone, Copy, Deserialize)]
+struct TimelineWheelZoom {
+    delta_y: f64,
+    /// Cursor x relative to the visible timeline area.
+    anchor_x: f64,
 }
 
 enum GenerationFailure {
     Offline(String),
-    Error(String),
+    Error(GenerationError),
 }
 
 pub(crate) fn build_audio_playback_items(
@@ -75,93 +121,26 @@ pub(crate) fn build_audio_playback_items(
     sample_cache: &Arc<Mutex<HashMap<uuid::Uuid, Arc<Vec<f32>>>>>,
     allow_decode: bool,
 ) -> (Vec<PlaybackItem>, Vec<uuid::Uuid>) {
-    let mut track_types = HashMap::new();
-    let mut track_volumes = HashMap::new();
-    for track in project.tracks.iter() {
-        track_types.insert(track.id, track.track_type.clone());
-        track_volumes.insert(track.id, track.volume);
-    }
-
-    let sample_rate = engine.sample_rate() as f64;
-    let channels = engine.channels();
-    let mut items = Vec::new();
-    let mut missing = Vec::new();
-
-    for clip in project.clips.iter() {
-        let Some(track_type) = track_types.get(&clip.track_id) else {
-            continue;
-        };
-        if *track_type != TrackType::Audio && *track_type != TrackType::Video {
-            continue;
-        }
-        let Some(asset) = project.find_asset(clip.asset_id) else {
-            continue;
-        };
-        if !asset.is_audio() && !asset.is_video() {
-            continue;
-        }
-        let Some(source_path) = resolve_audio_or_video_source(project_root, asset) else {
-            continue;
-        };
-
-        let cached = sample_cache
-            .lock()
-            .ok()
-            .and_then(|cache| cache.get(&asset.id).cloned());
-        let samples = if let Some(samples) = cached {
-            samples
-        } else if !allow_decode {
-            missing.push(asset.id);
-            continue;
-        } else {
-            let decode_config = AudioDecodeConfig {
-                target_rate: engine.sample_rate(),
-                target_channels: engine.channels(),
-            };
-            let decoded = match decode_audio_to_f32(&source_path, decode_config) {
-                Ok(decoded) => decoded,
-                Err(err) => {
-                    eprintln!(
-                        "[AUDIO ERROR] Playback decode failed asset_id={} err={}",
-                        asset.id, err
-                    );
-                    continue;
-                }
-            };
-            let samples = Arc::new(decoded.samples);
-            if let Ok(mut cache) = sample_cache.lock() {
-                cache.insert(asset.id, Arc::clone(&samples));
-            }
-            samples
-        };
+    build_playback_items(
+        project,
+        project_root,
+        engine.sample_rate(),
+        engine.channels(),
+        sample_cache,
+        allow_decode,
+    )
+}
 
-        let total_frames = (samples.len() / channels.max(1) as usize) as u64;
-        let trim_frames = (clip.trim_in_seconds.max(0.0) * sample_rate).round() as u64;
-        if trim_frames >= total_frames {
-            continue;
-        }
-        let clip_frames = (clip.duration.max(0.0) * sample_rate).round() as u64;
-        let available_frames = total_frames.saturating_sub(trim_frames);
-        let frame_count = clip_frames.min(available_frames);
-        if frame_count == 0 {
-            continue;
-        }
-        let start_frame = (clip.start_time.max(0.0) * sample_rate).round() as u64;
-        let track_volume = track_volumes.get(&clip.track_id).copied().unwrap_or(1.0);
-        let clip_volume = clip.volume;
-        let gain = (track_volume * clip_volume).max(0.0);
-
-        items.push(PlaybackItem {
-            samples,
-            start_frame,
-            sample_offset_frames: trim_frames,
-            frame_count,
-            channels,
-            gain,
-        });
+/// Decode settings for samples fed to the playback engine.
+fn playback_decode_config(
+    engine: &AudioPlaybackEngine,
+    project: &crate::state::Project,
+) -> AudioDecodeConfig {
+    AudioDecodeConfig {
+        target_rate: engine.sample_rate(),
+        target_channels: engine.channels(),
+        quality: project.settings.preview_resample_quality,
     }
-
-    (items, missing)
 }
 
 fn audio_decode_targets_for_project(
@@ -268,6 +247,81 @@ fn schedule_audio_decode_targets(
     }
 }
 
+/// Rebuild the engine's playback items from already-decoded audio, e.g. after a
+/// mute or solo change.
+fn refresh_playback_items(
+    audio_engine: &Option<Arc<AudioPlaybackEngine>>,
+    audio_sample_cache: &Arc<Mutex<HashMap<uuid::Uuid, Arc<Vec<f32>>>>>,
+    project: Signal<crate::state::Project>,
+) {
+    let Some(engine) = audio_engine.as_ref() else {
+        return;
+    };
+    let Some(project_root) = project.read().project_path.clone() else {
+        return;
+    };
+    let project_snapshot = project.read().clone();
+    let (items, _) = build_audio_playback_items(
+        &project_snapshot,
+        &project_root,
+        engine,
+        audio_sample_cache,
+        false,
+    );
+    engine.set_items(items);
+}
+
+/// Undo (or redo) one timeline edit and drop selections that no longer exist.
+fn apply_history_step(
+    mut project: Signal<crate::state::Project>,
+    mut history: Signal<crate::state::History>,
+    mut selection: Signal<crate::state::SelectionState>,
+    redo: bool,
+) -> bool {
+    let applied = {
+        let mut project_write = project.write();
+        if redo {
+            history.write().redo(&mut project_write)
+        } else {
+            history.write().undo(&mut project_write)
+        }
+    };
+    if applied {
+        let project_read = project.read();
+        let mut selection_write = selection.write();
+        selection_write
+            .clip_ids
+            .retain(|id| project_read.clips.iter().any(|clip| clip.id == *id));
+        selection_write
+            .track_ids
+            .retain(|id| project_read.tracks.iter().any(|track| track.id == *id));
+        selection_write
+            .marker_ids
+            .retain(|id| project_read.markers.iter().any(|marker| marker.id == *id));
+    }
+    applied
+}
+
+/// Drop a marker at `time` (snapped to a frame) and select it. Returns false
+/// when a marker already sits on that frame.
+fn add_marker_at_time(
+    mut project: Signal<crate::state::Project>,
+    mut history: Signal<crate::state::History>,
+    mut selection: Signal<crate::state::SelectionState>,
+    time: f64,
+) -> bool {
+    let fps = project.read().settings.fps;
+    if project.read().marker_on_frame(time, fps).is_some() {
+        return false;
+    }
+    history.write().push(&project.read());
+    let Some(id) = project.write().add_marker_at(time, fps) else {
+        return false;
+    };
+    selection.write().select_marker(id);
+    true
+}
+
 fn toggle_playback(
     audio_engine: &Option<Arc<AudioPlaybackEngine>>,
     audio_sample_cache: &Arc<Mutex<HashMap<uuid::Uuid, Arc<Vec<f32>>>>>,
@@ -298,10 +352,7 @@ fn toggle_playback(
                     let mut targets =
                         audio_decode_targets_for_project(&project_snapshot, &project_root);
                     targets.retain(|(id, _)| missing_set.contains(id));
-                    let decode_config = AudioDecodeConfig {
-                        target_rate: engine.sample_rate(),
-                        target_channels: engine.channels(),
-                    };
+                    let decode_config = playback_decode_config(&engine, &project_snapshot);
                     schedule_audio_decode_targets(
                         targets,
                         decode_config,
@@ -322,6 +373,170 @@ fn toggle_playback(
     is_playing.set(next_playing);
 }
 
+/// Preview renderer no larger than the panel can show, capped by the project settings.
+fn preview_renderer_for_bounds(
+    project_root: std::path::PathBuf,
+    settings: &crate::state::ProjectSettings,
+    bounds: Option<PreviewBounds>,
+    show_checkerboard: bool,
+) -> crate::core::preview::PreviewRenderer {
+    let limits = preview_limits_for_bounds(settings, bounds);
+    let renderer = crate::core::preview::PreviewRenderer::new_with_limits(
+        project_root,
+        settings.preview_cache_budget_bytes(),
+        limits.0,
+        limits.1,
+    );
+    renderer.set_checkerboard(checkerboard_cell(show_checkerboard, bounds));
+    renderer
+}
+
+/// Checkerboard cell size at the panel's pixel ratio; `None` when the checkerboard is off.
+fn checkerboard_cell(show_checkerboard: bool, bounds: Option<PreviewBounds>) -> Option<u32> {
+    show_checkerboard.then(|| {
+        let dpr = bounds.map(|bounds| bounds.dpr).unwrap_or(1.0);
+        crate::core::preview::checkerboard_cell_px(dpr)
+    })
+}
+
+fn preview_limits_for_bounds(
+    settings: &crate::state::ProjectSettings,
+    bounds: Option<PreviewBounds>,
+) -> (u32, u32) {
+    match bounds {
+        Some(bounds) => crate::core::preview::effective_preview_limits(
+            settings.width,
+            settings.height,
+            settings.preview_max_width,
+            settings.preview_max_height,
+            bounds.width * bounds.dpr,
+            bounds.height * bounds.dpr,
+        ),
+        None => (settings.preview_max_width, settings.preview_max_height),
+    }
+}
+
+/// Show `message` in the status bar, clearing it after `STATUS_MESSAGE_SECONDS`
+/// unless a newer message replaced it.
+fn flash_status_message(mut status_message: Signal<Option<String>>, message: String) {
+    status_message.set(Some(message.clone()));
+    spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_secs(STATUS_MESSAGE_SECONDS)).await;
+        if status_message.peek().as_deref() == Some(message.as_str()) {
+            status_message.set(None);
+        }
+    });
+}
+
+/// Save the project, bumping `save_tick` so the unsaved-changes indicator
+/// re-checks it.
+fn save_project(project: Signal<crate::state::Project>, mut save_tick: Signal<u64>) -> std::io::Result<()> {
+    let result = project.read().save();
+    *save_tick.write() += 1;
+    result
+}
+
+/// Ask whether to quit while generation jobs are queued or running. Declining
+/// keeps the app open so they can finish.
+fn confirm_abandon_jobs(active_jobs: usize) -> bool {
+    let jobs = if active_jobs == 1 {
+        "1 generation job is".to_string()
+    } else {
+        format!("{} generation jobs are", active_jobs)
+    };
+    let answer = rfd::MessageDialog::new()
+        .set_level(rfd::MessageLevel::Warning)
+        .set_title("Generation In Progress")
+        .set_description(format!(
+            "{} still queued or running and will be abandoned. Close anyway?\n\nChoose Cancel to keep the app open until they finish.",
+            jobs
+        ))
+        .set_buttons(rfd::MessageButtons::OkCancel)
+        .show();
+    matches!(answer, rfd::MessageDialogResult::Ok)
+}
+
+/// Ask whether to save before closing, via a native dialog.
+fn prompt_unsaved_changes(project_name: &str) -> UnsavedChoice {
+    let answer = rfd::MessageDialog::new()
+        .set_level(rfd::MessageLevel::Warning)
+        .set_title("Unsaved Changes")
+        .set_description(format!(
+            "Save changes to \"{}\" before closing?",
+            project_name
+        ))
+        .set_buttons(rfd::MessageButtons::YesNoCancel)
+        .show();
+    match answer {
+        rfd::MessageDialogResult::Yes => UnsavedChoice::Save,
+        rfd::MessageDialogResult::No => UnsavedChoice::Discard,
+        _ => UnsavedChoice::Cancel,
+    }
+}
+
+/// How much of an unused asset goes when it is removed.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum UnusedAssetCleanup {
+    /// Drop it from the project, keep everything on disk
+    ProjectOnly,
+    /// Also delete generative folders
+    GeneratedFiles,
+    /// Also delete imported source files inside the project folder
+    GeneratedAndSources,
+}
+
+/// Confirm removing the unused assets named in `names`, via native dialogs.
+/// Deleting imported source files gets its own warning. `None` on cancel.
+fn prompt_remove_unused_assets(names: &[String], imported: usize) -> Option<UnusedAssetCleanup> {
+    const LISTED: usize = 10;
+    let mut listing: Vec<String> = names.iter().take(LISTED).map(|name| format!("• {}", name)).collect();
+    if names.len() > LISTED {
+        listing.push(format!("…and {} more", names.len() - LISTED));
+    }
+    let count = if names.len() == 1 {
+        "1 asset".to_string()
+    } else {
+        format!("{} assets", names.len())
+    };
+    let answer = rfd::MessageDialog::new()
+        .set_level(rfd::MessageLevel::Info)
+        .set_title("Remove Unused Assets")
+        .set_description(format!(
+            "Remove {} not used by any clip?\n\n{}\n\nYes also deletes their generated files. No removes them from the project but keeps every file.",
+            count,
+            listing.join("\n")
+        ))
+        .set_buttons(rfd::MessageButtons::YesNoCancel)
+        .show();
+    match answer {
+        rfd::MessageDialogResult::Yes => {}
+        rfd::MessageDialogResult::No => return Some(UnusedAssetCleanup::ProjectOnly),
+        _ => return None,
+    }
+    if imported == 0 {
+        return Some(UnusedAssetCleanup::GeneratedFiles);
+    }
+    let sources = if imported == 1 {
+        "the source file of 1 imported asset".to_string()
+    } else {
+        format!("the source files of {} imported assets", imported)
+    };
+    let answer = rfd::MessageDialog::new()
+        .set_level(rfd::MessageLevel::Warning)
+        .set_title("Delete Source Files")
+        .set_description(format!(
+            "Also delete {} from the project folder? This can't be undone.\n\nFiles linked from outside the project folder are never deleted.",
+            sources
+        ))
+        .set_buttons(rfd::MessageButtons::YesNo)
+        .show();
+    Some(if matches!(answer, rfd::MessageDialogResult::Yes) {
+        UnusedAssetCleanup::GeneratedAndSources
+    } else {
+        UnusedAssetCleanup::GeneratedFiles
+    })
+}
+
 fn set_timeline_zoom_anchored(
     new_zoom: f64,
     duration: f64,
@@ -335,20 +550,15 @@ fn set_timeline_zoom_anchored(
         return;
     }
 
-    let anchor_x = (current_time * old_zoom) - scroll_offset();
-    let mut next_scroll = (current_time * new_zoom) - anchor_x;
-    if !next_scroll.is_finite() {
-        next_scroll = 0.0;
-    }
-    if next_scroll < 0.0 {
-        next_scroll = 0.0;
-    }
-    if let Some(width) = viewport_width {
-        let max_scroll = (duration * new_zoom - width).max(0.0);
-        if next_scroll > max_scroll {
-            next_scroll = max_scroll;
-        }
-    }
+    let focus_time = zoom_focus_time(current_time, old_zoom, scroll_offset(), viewport_width);
+    let next_scroll = adjust_scroll_for_zoom(
+        old_zoom,
+        new_zoom,
+        focus_time,
+        scroll_offset(),
+        viewport_width,
+        duration,
+    );
 
     zoom.set(new_zoom);
     scroll_offset.set(next_scroll);
@@ -365,19 +575,22 @@ async fn execute_generation_job(
 ) -> Result<String, GenerationFailure> {
     if job.output_type == ProviderOutputType::Audio {
         return Err(GenerationFailure::Error(
-            "Audio outputs are not supported in the queue yet.".to_string(),
+            "Audio outputs are not supported in the queue yet.".to_string().into(),
         ));
     }
 
     let folder_path = job.folder_path.clone();
-    let config_snapshot = project
-        .read()
-        .generative_config(job.asset_id)
-        .cloned()
-        .unwrap_or_default();
-    let version = next_version_label(&config_snapshot);
+    // Retries always re-probe so a cached offline result can't use up attempts.
+    let health = if job.attempts > 0 {
+        health::refresh_provider_health(&job.provider.connection).await
+    } else {
+        health::check_provider_health(&job.provider.connection).await
+    };
+    if let Err(err) = health {
+        return Err(GenerationFailure::Offline(err));
+    }
 
-    let output = match job.provider.connection.clone() {
+    let outputs = match job.provider.connection.clone() {
         ProviderConnection::ComfyUi {
             base_url,
             workflow_path,
@@ -386,9 +599,6 @@ async fn execute_generation_job(
         } => {
             let workflow_path = comfyui::resolve_workflow_path(workflow_path.as_deref());
             let manifest_path = comfyui::resolve_manifest_path(manifest_path.as_deref());
-            if let Err(err) = comfyui::check_health(&base_url).await {
-                return Err(GenerationFailure::Offline(err));
-            }
             comfyui::generate_output(
                 &base_url,
                 &workflow_path,
@@ -398,21 +608,29 @@ async fn execute_generation_job(
                 progress_tx.clone(),
             )
             .await
-            .map_err(|err| GenerationFailure::Error(err))
+            .map_err(GenerationFailure::Error)
+        }
+        ProviderConnection::Http(config) => {
+            http::generate_output(&config, &job.inputs, job.output_type)
+                .await
+                .map(|output| {
+                    vec![comfyui::ComfyUiOutput {
+                        bytes: output.bytes,
+                        extension: output.extension,
+                    }]
+                })
+                .map_err(GenerationFailure::Error)
         }
         _ => Err(GenerationFailure::Error(
-            "Provider connection not supported yet.".to_string(),
+            "Provider connection not supported yet.".to_string().into(),
         )),
     };
 
-    let output = match output {
-        Ok(output) => output,
+    let outputs = match outputs {
+        Ok(outputs) => outputs,
         Err(GenerationFailure::Error(err)) => {
-            if let ProviderConnection::ComfyUi { base_url, .. } = job.provider.connection.clone()
-            {
-                if let Err(health_err) = comfyui::check_health(&base_url).await {
-                    return Err(GenerationFailure::Offline(health_err));
-                }
+            if let Err(health_err) = health::refresh_provider_health(&job.provider.connection).await {
+                return Err(GenerationFailure::Offline(health_err));
             }
             return Err(GenerationFailure::Error(err));
         }
@@ -421,12 +639,31 @@ async fn execute_generation_job(
 
     std::fs::create_dir_all(&folder_path)
         .map_err(|err| {
-            GenerationFailure::Error(format!("Failed to create output folder: {}", err))
+            GenerationFailure::Error(format!("Failed to create output folder: {}", err).into())
         })?;
-    let output_path = folder_path.join(format!("{}.{}", version, output.extension));
-    std::fs::write(&output_path, &output.bytes)
-        .map_err(|err| GenerationFailure::Error(format!("Failed to save output: {}", err)))?;
+    // Read after the run so concurrent jobs on this asset don't reuse labels.
+    let config_snapshot = project
+        .read()
+        .generative_config(job.asset_id)
+        .cloned()
+        .unwrap_or_default();
+    // A batch from one run becomes consecutive versions; the first is made active.
+    let records = output_version_records(
+        &config_snapshot,
+        outputs.len(),
+        job.provider.id,
+        &job.inputs_snapshot,
+        chrono::Utc::now(),
+    );
+    for (output, record) in outputs.iter().zip(records.iter()) {
+        let output_path = folder_path.join(format!("{}.{}", record.version, output.extension));
+        std::fs::write(&output_path, &output.bytes)
+            .map_err(|err| GenerationFailure::Error(format!("Failed to save output: {}", err).into()))?;
+    }
     previewer.read().invalidate_folder(&folder_path);
+    let Some(version) = records.first().map(|record| record.version.clone()) else {
+        return Err(GenerationFailure::Error("Provider returned no outputs.".to_string().into()));
+    };
 
     {
         let mut project_write = project.write();
@@ -434,16 +671,11 @@ async fn execute_generation_job(
             config.provider_id = Some(job.provider.id);
             config.active_version = Some(version.clone());
             config.inputs = job.inputs_snapshot.clone();
-            config.versions.push(crate::state::GenerationRecord {
-                version: version.clone(),
-                timestamp: chrono::Utc::now(),
-                provider_id: job.provider.id,
-                inputs_snapshot: job.inputs_snapshot.clone(),
-            });
+            config.versions.extend(records.iter().cloned());
         });
         project_write
             .save_generative_config(job.asset_id)
-            .map_err(|err| GenerationFailure::Error(format!("Failed to save config: {}", err)))?;
+            .map_err(|err| GenerationFailure::Error(format!("Failed to save config: {}", err).into()))?;
     }
     preview_dirty.set(true);
 
@@ -468,6 +700,7 @@ pub fn App() -> Element {
     let default_settings = crate::state::ProjectSettings::default();
     let default_preview_width = default_settings.preview_max_width;
     let default_preview_height = default_settings.preview_max_height;
+    let default_preview_cache_bytes = default_settings.preview_cache_budget_bytes();
     let default_cache_root = crate::core::paths::app_cache_root().join("scratch");
     let default_cache_root_for_thumbs = default_cache_root.clone();
     let default_cache_root_for_preview = default_cache_root.clone();
@@ -496,10 +729,22 @@ pub fn App() -> Element {
     let thumbnail_refresh_tick = use_signal(|| 0_u64);
     let thumbnail_cache_buster = use_signal(|| 0_u64);
     let mut audio_waveform_cache_buster = use_signal(|| 0_u64);
+    let mut output_meter = use_signal(|| [MeterChannel::default(); 2]);
+    let mut mix_loudness = use_signal(|| None::<String>);
+    // Transient status bar message, e.g. why dropped files were skipped
+    let status_message = use_signal(|| None::<String>);
+    // Bumped on every save; saving doesn't write the project signal itself
+    let save_tick = use_signal(|| 0_u64);
+    let has_unsaved_changes = use_memo(move || {
+        save_tick.read();
+        project.read().has_unsaved_changes()
+    });
+    // Save As / bundle export in progress: (overlay title, project name, fraction done)
+    let mut folder_job_progress = use_signal(|| None::<(&'static str, String, f64)>);
     let mut previewer = use_signal(move || {
         std::sync::Arc::new(crate::core::preview::PreviewRenderer::new_with_limits(
             default_cache_root_for_preview,
-            PREVIEW_CACHE_BUDGET_BYTES,
+            default_preview_cache_bytes,
             default_preview_width,
             default_preview_height,
         ))
@@ -517,9 +762,12 @@ pub fn App() -> Element {
     let mut preview_layers =
         use_signal(|| None::<(u64, crate::core::preview::PreviewLayerStack)>);
     let mut preview_native_ready = use_signal(|| false);
+    // Whether the native GPU surface could be created; drives CPU fallback.
+    let gpu_availability = use_signal(GpuAvailability::default);
     let mut preview_native_suspended = use_signal(|| false);
     let preview_gpu = use_hook(|| Rc::new(RefCell::new(None::<PreviewGpuSurface>)));
     let mut show_preview_stats = use_signal(|| false);
+    let mut show_checkerboard = use_signal(|| false);
     let mut use_hw_decode = use_signal(|| true);
     let timeline_viewport_width = use_signal(|| None::<f64>);
     let mut timeline_viewport_eval = use_signal(|| None::<document::Eval>);
@@ -527,14 +775,66 @@ pub fn App() -> Element {
     let mut last_project_path = use_signal(|| None::<std::path::PathBuf>);
     let mut clip_cache_buckets =
         use_signal(|| std::sync::Arc::new(HashMap::<uuid::Uuid, Vec<bool>>::new()));
-    let preview_cache_tick = use_signal(|| 0_u64);
+    let mut preview_cache_tick = use_signal(|| 0_u64);
     let desktop = use_window();
     let desktop_for_bounds = desktop.clone();
     let desktop_for_events = desktop.clone();
     let desktop_for_redraw = desktop.clone();
     let mut preview_dirty = use_signal(|| true);
     let generation_queue = use_signal(|| Vec::<GenerationJob>::new());
-    let generation_active = use_signal(|| None::<uuid::Uuid>);
+    // The close button only hides the window until the handler below has
+    // decided, so it can come back if the user cancels.
+    use_hook({
+        let desktop = desktop.clone();
+        move || desktop.set_close_behavior(WindowCloseBehaviour::WindowHides)
+    });
+    use_wry_event_handler({
+        let desktop = desktop.clone();
+        move |event, _| {
+            let TaoEvent::WindowEvent {
+                window_id,
+                event: TaoWindowEvent::CloseRequested,
+                ..
+            } = event
+            else {
+                return;
+            };
+            if *window_id != desktop.window.id() {
+                return;
+            }
+            let active_jobs = active_job_count(&generation_queue.peek());
+            let dirty = project.peek().has_unsaved_changes();
+            if !should_warn_on_close(&generation_queue.peek(), dirty) {
+                desktop.set_close_behavior(WindowCloseBehaviour::WindowCloses);
+                return;
+            }
+            let mut closes = active_jobs == 0 || confirm_abandon_jobs(active_jobs);
+            if closes && dirty {
+                let project_name = project.peek().name.clone();
+                let choice = prompt_unsaved_changes(&project_name);
+                closes = close_after_choice(choice, || match save_project(project, save_tick) {
+                    Ok(()) => true,
+                    Err(err) => {
+                        eprintln!("[PROJECT SAVE ERROR] Save before closing failed: {}", err);
+                        flash_status_message(status_message, format!("Save failed: {}", err));
+                        false
+                    }
+                });
+            }
+            if closes {
+                // Dioxus handles the request right after this and now closes
+                desktop.set_close_behavior(WindowCloseBehaviour::WindowCloses);
+            } else {
+                // ...or hides the window, which has to come back
+                let desktop = desktop.clone();
+                spawn(async move {
+                    desktop.window.set_visible(true);
+                    desktop.window.set_focus();
+                });
+            }
+        }
+    });
+    let generation_active = use_signal(HashSet::<uuid::Uuid>::new);
     let generation_tick = use_signal(|| 0_u64);
     let generation_retry_tick = use_signal(|| 0_u64);
     let generation_paused = use_signal(|| false);
@@ -545,6 +845,11 @@ pub fn App() -> Element {
     // Startup Modal state - check if we have a valid project path on load
     // For MVP, we start with a dummy project, so we check if project_path is None
     let mut startup_done = use_signal(|| false);
+    // Folder currently being opened; bumping `project_load_id` abandons that load.
+    let mut project_loading = use_signal(|| None::<std::path::PathBuf>);
+    let mut project_load_id = use_signal(|| 0_u64);
+    let mut media_index_handle = use_signal(|| None::<CancelHandle>);
+    let media_index_remaining = use_signal(|| 0_usize);
     
     // Panel state
     let mut left_width = use_signal(|| PANEL_DEFAULT_WIDTH);
@@ -559,9 +864,18 @@ pub fn App() -> Element {
     let mut zoom = use_signal(|| 100.0_f64);              // Pixels per second
     let mut is_playing = use_signal(|| false);            // Playback state
     let mut scroll_offset = use_signal(|| 0.0_f64);       // Horizontal scroll position
+    let mut follow_playhead = use_signal(|| true);        // Page the view along during playback
     let mut scrub_was_playing = use_signal(|| false);
     let mut is_scrubbing = use_signal(|| false);
     let mut timeline_focused = use_signal(|| false);
+    // The assets panel was clicked last; its list takes the arrow keys
+    let mut assets_focused = use_signal(|| false);
+    let mut asset_cursor = use_signal(|| None::<uuid::Uuid>);
+    let asset_search = use_signal(String::new);
+    let asset_type_filter = use_signal(AssetTypeFilter::default);
+    let mut input_focused = use_signal(|| false);
+    // Status line under the Generate button; also set by the Ctrl+Enter hotkey
+    let generation_status = use_signal(|| None::<String>);
     
     // Derive duration/snap targets from project
     let (duration, timeline_fps, timeline_snap_targets) = {
@@ -585,19 +899,7 @@ pub fn App() -> Element {
                 true
             }
         };
-        let mut targets = Vec::new();
-
-        for clip in project_read.clips.iter() {
-            if in_view(clip.start_time) {
-                let start_frame = frames_from_seconds(clip.start_time, fps).round();
-                targets.push(SnapTarget::clip_edge(start_frame, clip.id));
-            }
-            let clip_end = clip.end_time();
-            if in_view(clip_end) {
-                let end_frame = frames_from_seconds(clip_end, fps).round();
-                targets.push(SnapTarget::clip_edge(end_frame, clip.id));
-            }
-        }
+        let mut targets = clip_edge_targets(&project_read.clips, fps, &in_view);
 
         let playhead_time = current_time();
         if in_view(playhead_time) {
@@ -651,6 +953,9 @@ pub fn App() -> Element {
     let mut dragged_asset = use_signal(|| None::<uuid::Uuid>);
     let mut mouse_pos = use_signal(|| (0.0, 0.0));
     let mut selection = use_signal(|| crate::state::SelectionState::default());
+    let mut history = use_signal(crate::state::History::default);
+    let mut clip_clipboard = use_signal(Vec::<crate::state::Clip>::new);
+    let keymap = use_hook(|| std::rc::Rc::new(KeyMap::load_user()));
     let selection_state = selection.read();
     let attributes_key = selection_state
         .primary_clip()
@@ -682,140 +987,161 @@ pub fn App() -> Element {
         }
         let mut generation_queue = generation_queue.clone();
         let mut generation_active = generation_active.clone();
-        if generation_active().is_some() {
+        let max_running = project.peek().settings.generation_max_concurrent.max(1) as usize;
+        let start_ids = jobs_to_start(&generation_queue.peek(), Utc::now(), max_running);
+        if start_ids.is_empty() {
             return;
         }
-
-        let now = Utc::now();
-        let next_job = {
+        let started: Vec<GenerationJob> = {
             let mut queue = generation_queue.write();
-            let next_index = queue
-                .iter()
-                .position(|job| job.status == GenerationJobStatus::Queued);
-            match next_index {
-                Some(index) => {
-                    let job = &mut queue[index];
-                    if let Some(next_at) = job.next_attempt_at {
-                        if next_at > now {
-                            None
-                        } else {
-                            job.status = GenerationJobStatus::Running;
-                            job.progress_overall = Some(0.0);
-                            job.progress_node = Some(0.0);
-                            job.next_attempt_at = None;
-                            Some(job.clone())
-                        }
-                    } else {
-                        job.status = GenerationJobStatus::Running;
-                        job.progress_overall = Some(0.0);
-                        job.progress_node = Some(0.0);
-                        job.next_attempt_at = None;
-                        Some(job.clone())
-                    }
-                }
-                None => None,
-            }
-        };
-
-        let Some(job) = next_job else {
-            return;
+            queue
+                .iter_mut()
+                .filter(|job| start_ids.contains(&job.id))
+                .map(|job| {
+                    job.status = GenerationJobStatus::Running;
+                    job.progress_overall = Some(0.0);
+                    job.progress_node = Some(0.0);
+                    job.next_attempt_at = None;
+                    job.clone()
+                })
+                .collect()
         };
 
-        generation_active.set(Some(job.id));
+        for job in started {
+            generation_active.write().insert(job.id);
 
-        let mut generation_queue = generation_queue.clone();
-        let mut generation_active = generation_active.clone();
-        let mut generation_tick = generation_tick.clone();
-        let generation_retry_tick = generation_retry_tick.clone();
-        let mut generation_paused = generation_paused.clone();
-        let mut generation_pause_reason = generation_pause_reason.clone();
-        let project = project.clone();
-        let previewer = previewer.clone();
-        let preview_dirty = preview_dirty.clone();
-        let thumbnailer = thumbnailer.read().clone();
-        let thumbnail_cache_buster = thumbnail_cache_buster.clone();
-        let (progress_tx, mut progress_rx) =
-            tokio::sync::mpsc::unbounded_channel::<comfyui::ComfyUiProgress>();
-        let progress_job_id = job.id;
-        let mut progress_queue = generation_queue.clone();
+            let mut generation_queue = generation_queue.clone();
+            let mut generation_active = generation_active.clone();
+            let mut generation_tick = generation_tick.clone();
+            let generation_retry_tick = generation_retry_tick.clone();
+            let mut generation_paused = generation_paused.clone();
+            let mut generation_pause_reason = generation_pause_reason.clone();
+            let project = project.clone();
+            let previewer = previewer.clone();
+            let preview_dirty = preview_dirty.clone();
+            let thumbnailer = thumbnailer.read().clone();
+            let thumbnail_cache_buster = thumbnail_cache_buster.clone();
+            let max_attempts = project.peek().settings.generation_max_attempts.max(1);
+            let (progress_tx, mut progress_rx) =
+                tokio::sync::mpsc::unbounded_channel::<comfyui::ComfyUiProgress>();
+            let progress_job_id = job.id;
+            let mut progress_queue = generation_queue.clone();
 
-        spawn(async move {
             spawn(async move {
-                while let Some(progress) = progress_rx.recv().await {
-                    let mut queue = progress_queue.write();
-                    if let Some(entry) = queue.iter_mut().find(|entry| entry.id == progress_job_id) {
-                        if entry.status == GenerationJobStatus::Running {
-                            if let Some(overall) = progress.overall {
-                                entry.progress_overall = Some(overall.clamp(0.0, 1.0));
-                            }
-                            if let Some(node) = progress.node {
-                                entry.progress_node = Some(node.clamp(0.0, 1.0));
+                spawn(async move {
+                    while let Some(progress) = progress_rx.recv().await {
+                        let mut queue = progress_queue.write();
+                        if let Some(entry) = queue.iter_mut().find(|entry| entry.id == progress_job_id) {
+                            if entry.status == GenerationJobStatus::Running {
+                                if let Some(overall) = progress.overall {
+                                    entry.progress_overall = Some(overall.clamp(0.0, 1.0));
+                                }
+                                if let Some(node) = progress.node {
+                                    entry.progress_node = Some(node.clamp(0.0, 1.0));
+                                }
                             }
                         }
                     }
-                }
-            });
+                });
 
-            let result = execute_generation_job(
-                job.clone(),
-                project,
-                previewer,
-                preview_dirty,
-                thumbnailer,
-                thumbnail_cache_buster,
-                Some(progress_tx),
-            )
-            .await;
+                let result = execute_generation_job(
+                    job.clone(),
+                    project,
+                    previewer,
+                    preview_dirty,
+                    thumbnailer,
+                    thumbnail_cache_buster,
+                    Some(progress_tx),
+                )
+                .await;
 
-            let mut queue = generation_queue.write();
-            if let Some(entry) = queue.iter_mut().find(|entry| entry.id == job.id) {
-                match &result {
-                    Ok(version) => {
-                        entry.status = GenerationJobStatus::Succeeded;
-                        entry.version = Some(version.clone());
-                        entry.progress_overall = Some(1.0);
-                        entry.progress_node = Some(1.0);
-                        entry.error = None;
-                        entry.attempts = 0;
-                        entry.next_attempt_at = None;
-                    }
-                    Err(GenerationFailure::Offline(err)) => {
-                        if entry.attempts == 0 {
-                            entry.attempts = 1;
-                            entry.status = GenerationJobStatus::Queued;
-                            entry.next_attempt_at = Some(Utc::now() + chrono::Duration::seconds(5));
-                            entry.error = Some("Provider offline, retrying in 5s".to_string());
-                            let mut generation_retry_tick = generation_retry_tick.clone();
-                            spawn(async move {
-                                tokio::time::sleep(Duration::from_secs(5)).await;
-                                generation_retry_tick.set(generation_retry_tick() + 1);
-                            });
-                        } else {
-                            entry.status = GenerationJobStatus::Queued;
+                let mut queue = generation_queue.write();
+                if let Some(entry) = queue.iter_mut().find(|entry| entry.id == job.id) {
+                    match &result {
+                        Ok(version) => {
+                            entry.status = GenerationJobStatus::Succeeded;
+                            entry.version = Some(version.clone());
+                            entry.progress_overall = Some(1.0);
+                            entry.progress_node = Some(1.0);
+                            entry.error = None;
+                            entry.attempts = 0;
                             entry.next_attempt_at = None;
-                            entry.error = Some("Provider offline, queue paused.".to_string());
-                            generation_paused.set(true);
-                            generation_pause_reason.set(Some(format!(
-                                "Provider offline: {}",
-                                err
-                            )));
                         }
-                    }
-                    Err(GenerationFailure::Error(err)) => {
-                        entry.status = GenerationJobStatus::Failed;
-                        entry.error = Some(err.clone());
-                        entry.progress_overall = None;
-                        entry.progress_node = None;
+                        Err(failure) => {
+                            let (err, offline, retryable) = match failure {
+                                GenerationFailure::Offline(err) => (err, true, true),
+                                GenerationFailure::Error(err) => (&err.message, false, err.transient),
+                            };
+                            let failed_attempts = entry.attempts.saturating_add(1);
+                            if retryable && failed_attempts < max_attempts {
+                                let delay = generation_retry_delay_secs(failed_attempts);
+                                entry.attempts = failed_attempts;
+                                entry.status = GenerationJobStatus::Queued;
+                                entry.next_attempt_at = Some(Utc::now() + chrono::Duration::seconds(delay));
+                                entry.error = Some(err.clone());
+                                entry.progress_overall = None;
+                                entry.progress_node = None;
+                                let mut generation_retry_tick = generation_retry_tick.clone();
+                                spawn(async move {
+                                    tokio::time::sleep(Duration::from_secs(delay as u64)).await;
+                                    generation_retry_tick.set(generation_retry_tick() + 1);
+                                });
+                            } else if offline {
+                                entry.status = GenerationJobStatus::Queued;
+                                entry.attempts = 0;
+                                entry.next_attempt_at = None;
+                                entry.error = Some("Provider offline, queue paused.".to_string());
+                                generation_paused.set(true);
+                                generation_pause_reason.set(Some(format!(
+                                    "Provider offline: {}",
+                                    err
+                                )));
+                            } else {
+                                entry.status = GenerationJobStatus::Failed;
+                                entry.error = Some(err.clone());
+                                entry.progress_overall = None;
+                                entry.progress_node = None;
+                            }
+                        }
                     }
                 }
-            }
 
-            if result.is_ok() {
-                generation_tick.set(generation_tick() + 1);
-            }
+                if result.is_ok() {
+                    generation_tick.set(generation_tick() + 1);
+                }
 
-            generation_active.set(None);
-        });
+                generation_active.write().remove(&job.id);
+            });
+        }
+    });
+
+    // Output VU meter: poll the engine's peaks at a throttled rate and only
+    // touch the signal while something is moving.
+    let audio_engine_for_meter = audio_engine.clone();
+    use_future(move || {
+        let audio_engine = audio_engine_for_meter.clone();
+        async move {
+            let Some(engine) = audio_engine else {
+                return;
+            };
+            let interval = Duration::from_millis(METER_POLL_INTERVAL_MS);
+            let mut last_tick = Instant::now();
+            loop {
+                tokio::time::sleep(interval).await;
+                let now = Instant::now();
+                let dt = now.saturating_duration_since(last_tick).as_secs_f32();
+                last_tick = now;
+                let peaks = engine.take_output_peaks();
+                let current = *output_meter.peek();
+                if current.iter().all(|channel| channel.is_idle()) && peaks.iter().all(|peak| *peak <= 0.0) {
+                    continue;
+                }
+                output_meter.set([
+                    step_meter(current[0], peaks[0], dt),
+                    step_meter(current[1], peaks[1], dt),
+                ]);
+            }
+        }
     });
 
     let audio_engine_for_timer = audio_engine.clone();
@@ -824,6 +1150,23 @@ pub fn App() -> Element {
         let mut is_playing = is_playing.clone();
         let project = project.clone();
         let audio_engine = audio_engine_for_timer.clone();
+        // Keep the playhead in view while playing
+        let mut follow_scroll = move |time: f64| {
+            if !*follow_playhead.peek() {
+                return;
+            }
+            let Some(width) = *timeline_viewport_width.peek() else {
+                return;
+            };
+            let zoom_value = *zoom.peek();
+            let content_width = project.read().duration() * zoom_value;
+            let current_scroll = *scroll_offset.peek();
+            if let Some(next) =
+                playhead_follow_scroll(time * zoom_value, current_scroll, width, content_width)
+            {
+                scroll_offset.set(next);
+            }
+        };
         async move {
             let mut last_tick = Instant::now();
             loop {
@@ -839,6 +1182,7 @@ pub fn App() -> Element {
                     let time = engine.playhead_seconds();
                     let snapped = snap_time_to_frame(time.min(duration), fps);
                     current_time.set(snapped);
+                    follow_scroll(snapped);
                     if time >= duration {
                         engine.pause();
                         is_playing.set(false);
@@ -852,6 +1196,7 @@ pub fn App() -> Element {
                 let next_time = (current_time() + delta.as_secs_f64()).min(duration);
                 let snapped = snap_time_to_frame(next_time, fps);
                 current_time.set(snapped);
+                follow_scroll(snapped);
 
                 if next_time >= duration {
                     is_playing.set(false);
@@ -871,6 +1216,7 @@ pub fn App() -> Element {
         let mut preview_dirty = preview_dirty.clone();
         let mut preview_cache_tick = preview_cache_tick.clone();
         let preview_native_ready = preview_native_ready.clone();
+        let gpu_availability = gpu_availability.clone();
         let use_hw_decode = use_hw_decode.clone();
         async move {
             let render_request_id = std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0));
@@ -943,7 +1289,11 @@ pub fn App() -> Element {
 
                 let project_snapshot = project.read().clone();
                 let renderer = previewer.read().clone();
-                let use_gpu = preview_native_ready();
+                let use_gpu = preview_native_ready()
+                    && choose_preview_backend(
+                        project_snapshot.settings.force_cpu_preview,
+                        &gpu_availability.peek(),
+                    ) == PreviewBackend::Gpu;
                 let decode_mode = if is_playing() {
                     crate::core::preview::PreviewDecodeMode::Sequential
                 } else {
@@ -1093,10 +1443,43 @@ pub fn App() -> Element {
         timeline_viewport_eval.set(Some(eval));
     });
 
+    // Track whether a text field has focus so hotkeys don't fire while typing.
+    use_future(move || async move {
+        let mut eval = document::eval(INPUT_FOCUS_SCRIPT);
+        while let Ok(focused) = eval.recv::<bool>().await {
+            input_focused.set(focused);
+        }
+    });
+
     use_future(move || {
         let mut preview_native_bounds = preview_native_bounds.clone();
         let preview_host_eval = preview_host_eval.clone();
         let desktop = desktop_for_bounds.clone();
+        // Re-size the renderer to the panel when the panel itself changes size
+        let mut fit_previewer = move |bounds: PreviewBounds| {
+            let (project_root, settings) = {
+                let project_read = project.read();
+                (project_read.project_path.clone(), project_read.settings.clone())
+            };
+            let Some(project_root) = project_root else {
+                return;
+            };
+            if previewer.peek().limits() == preview_limits_for_bounds(&settings, Some(bounds)) {
+                // Same size, but the pixel ratio may have moved the checkerboard cell
+                let cell = checkerboard_cell(*show_checkerboard.peek(), Some(bounds));
+                if previewer.peek().set_checkerboard(cell) {
+                    preview_dirty.set(true);
+                }
+                return;
+            }
+            previewer.set(std::sync::Arc::new(preview_renderer_for_bounds(
+                project_root,
+                &settings,
+                Some(bounds),
+                *show_checkerboard.peek(),
+            )));
+            preview_dirty.set(true);
+        };
         async move {
             loop {
                 let Some(eval) = preview_host_eval() else {
@@ -1109,6 +1492,7 @@ pub fn App() -> Element {
                         Ok(bounds) => {
                             if preview_native_bounds() != Some(bounds) {
                                 preview_native_bounds.set(Some(bounds));
+                                fit_previewer(bounds);
                                 desktop.window.request_redraw();
                             }
                         }
@@ -1123,6 +1507,7 @@ pub fn App() -> Element {
     use_future(move || {
         let mut timeline_viewport_width = timeline_viewport_width.clone();
         let mut scroll_offset = scroll_offset.clone();
+        let mut zoom = zoom.clone();
         let timeline_viewport_eval = timeline_viewport_eval.clone();
         async move {
             loop {
@@ -1139,6 +1524,33 @@ pub fn App() -> Element {
                             if timeline_viewport_width() != Some(width) {
                                 timeline_viewport_width.set(Some(width));
                             }
+                            if let Some(wheel) = state.wheel_zoom {
+                                // Keep the time under the cursor fixed while zooming
+                                let (duration, fps) = {
+                                    let project_read = project.peek();
+                                    (project_read.duration(), project_read.settings.fps)
+                                };
+                                let (min_zoom, max_zoom) =
+                                    timeline_zoom_bounds(duration, Some(width), fps);
+                                let old_zoom = *zoom.peek();
+                                let new_zoom = (old_zoom
+                                    * (-wheel.delta_y * TIMELINE_WHEEL_ZOOM_RATE).exp())
+                                .clamp(min_zoom, max_zoom);
+                                if old_zoom > 0.0 && (new_zoom - old_zoom).abs() > f64::EPSILON {
+                                    let focus_time = (scroll_left + wheel.anchor_x) / old_zoom;
+                                    let next_scroll = adjust_scroll_for_zoom(
+                                        old_zoom,
+                                        new_zoom,
+                                        focus_time,
+                                        scroll_left,
+                                        Some(width),
+                                        duration,
+                                    );
+                                    zoom.set(new_zoom);
+                                    scroll_offset.set(next_scroll);
+                                }
+                                continue;
+                            }
                             if (scroll_offset() - scroll_left).abs() > 0.5 {
                                 scroll_offset.set(scroll_left);
                             }
@@ -1183,6 +1595,7 @@ pub fn App() -> Element {
         let mut preview_gpu_upload_ms = preview_gpu_upload_ms.clone();
         let preview_layers = preview_layers.clone();
         let mut preview_native_ready = preview_native_ready.clone();
+        let mut gpu_availability = gpu_availability.clone();
         let mut preview_dirty = preview_dirty.clone();
         let preview_native_suspended = preview_native_suspended.clone();
         let desktop = desktop_for_events.clone();
@@ -1232,20 +1645,50 @@ pub fn App() -> Element {
                 return;
             }
 
+            // Forced CPU mode hides the surface; the render loop switches to
+            // CPU frames once `preview_native_ready` drops.
+            if project.peek().settings.force_cpu_preview {
+                if let Some(gpu) = preview_gpu.borrow_mut().as_mut() {
+                    gpu.clear_layers();
+                }
+                if preview_native_active() {
+                    preview_native_active.set(false);
+                }
+                if preview_native_uploaded().is_some() {
+                    preview_native_uploaded.set(None);
+                }
+                if preview_native_ready() {
+                    preview_native_ready.set(false);
+                    preview_dirty.set(true);
+                }
+                return;
+            }
+
             let mut gpu_state = preview_gpu.borrow_mut();
             if gpu_state.is_none() {
                 if preview_native_attempted() {
                     return;
                 }
                 preview_native_attempted.set(true);
-                if let Some(gpu) = PreviewGpuSurface::new(&desktop.window, target) {
-                    *gpu_state = Some(gpu);
-                    preview_native_ready.set(true);
-                    preview_dirty.set(true);
-                } else {
-                    return;
+                match PreviewGpuSurface::new(&desktop.window, target) {
+                    Ok(gpu) => {
+                        *gpu_state = Some(gpu);
+                        gpu_availability.set(GpuAvailability::Available);
+                    }
+                    Err(reason) => {
+                        eprintln!(
+                            "[PREVIEW WARN] GPU preview unavailable, compositing on the CPU: {}",
+                            reason
+                        );
+                        gpu_availability.set(GpuAvailability::Unavailable(reason));
+                        return;
+                    }
                 }
             }
+            if gpu_state.is_some() && !preview_native_ready() {
+                preview_native_ready.set(true);
+                preview_dirty.set(true);
+            }
 
             if let Some(gpu) = gpu_state.as_mut() {
                 let mut uploaded = false;
@@ -1287,6 +1730,22 @@ pub fn App() -> Element {
                     preview_gpu_upload_ms.set(Some(ms));
                 }
 
+                let guides = preview_layers
+                    .peek()
+                    .as_ref()
+                    .map(|(_, stack)| {
+                        let settings = &project.peek().settings;
+                        crate::core::preview::guide_lines(
+                            settings.preview_guides,
+                            settings.width,
+                            settings.height,
+                            stack.canvas_width,
+                            stack.canvas_height,
+                        )
+                    })
+                    .unwrap_or_default();
+                let guides_changed = gpu.set_guides(guides);
+
                 let changed = gpu.apply_bounds(bounds);
                 if gpu.over_limit() {
                     if preview_native_active() {
@@ -1297,7 +1756,7 @@ pub fn App() -> Element {
                     preview_dirty.set(true);
                     return;
                 }
-                if should_render || changed || uploaded {
+                if should_render || changed || uploaded || guides_changed {
                     gpu.render_layers();
                 }
             }
@@ -1307,6 +1766,12 @@ pub fn App() -> Element {
     //  Dialog state
     let mut show_new_project_dialog = use_signal(|| false); // Kept for "File > New" inside app
     let mut show_project_settings_dialog = use_signal(|| false);
+    let mut show_export_settings_dialog = use_signal(|| false);
+    // Opened after loading a project with missing media, or from an asset's menu.
+    let mut show_relink_dialog = use_signal(|| false);
+    // Set by the exporter while a job runs and kept until the result is dismissed.
+    let mut export_status = use_signal(|| None::<ExportStatus>);
+    let mut export_handle = use_signal(|| None::<CancelHandle>);
     
     // V2 Provider modals
     let show_providers_v2 = use_signal(|| false);
@@ -1322,23 +1787,32 @@ pub fn App() -> Element {
         let mut show_providers_v2 = show_providers_v2.clone();
         let mut provider_files_v2 = provider_files_v2.clone();
         move || {
-            provider_files_v2.set(list_global_provider_files());
+            provider_files_v2.set(list_provider_files(project.read().project_path.as_deref()));
             show_providers_v2.set(true);
         }
     };
 
-    // V2 Provider modal effects
-    let desktop_for_modal_redraw = desktop.clone();
-    let preview_gpu_for_modal = preview_gpu.clone();
-    use_effect(move || {
-        let suspended = show_providers_v2()
+    // Anything drawn over the editor: suspends the native preview surface and
+    // keeps editing hotkeys from reaching the timeline behind it.
+    let any_modal_open = move || {
+        show_providers_v2()
             || show_json_editor()
             || show_builder_v2()
             || show_new_project_dialog()
             || show_project_settings_dialog()
+            || show_export_settings_dialog()
+            || show_relink_dialog()
+            || export_status().is_some()
             || menu_open()
             || queue_open()
-            || gen_video_modal_open();
+            || gen_video_modal_open()
+    };
+
+    // V2 Provider modal effects
+    let desktop_for_modal_redraw = desktop.clone();
+    let preview_gpu_for_modal = preview_gpu.clone();
+    use_effect(move || {
+        let suspended = any_modal_open();
         if preview_native_suspended() == suspended {
             return;
         }
@@ -1396,7 +1870,7 @@ pub fn App() -> Element {
         .iter()
         .filter(|job| matches!(job.status, GenerationJobStatus::Queued | GenerationJobStatus::Running))
         .count();
-    let queue_running = generation_active().is_some();
+    let queue_running = !generation_active().is_empty();
     let queue_paused = generation_paused();
     let on_enqueue_generation = {
         let mut generation_queue = generation_queue.clone();
@@ -1448,6 +1922,342 @@ pub fn App() -> Element {
             }
         }
     };
+    // Background work every newly imported file needs: thumbnails and the codec check
+    let start_imported_asset_jobs = move |asset_id: uuid::Uuid| {
+        if let Some(asset) = project.read().find_asset(asset_id).cloned() {
+            let thumbs = thumbnailer.read().clone();
+            let mut thumbnail_cache_buster = thumbnail_cache_buster.clone();
+            spawn(async move {
+                thumbs.generate(&asset, false).await;
+                thumbnail_cache_buster.set(thumbnail_cache_buster() + 1);
+            });
+        }
+        spawn_video_codec_check(project, asset_id);
+    };
+    // After relinking, everything derived from the old file is rebuilt.
+    let mut finish_relink = move |asset_id: uuid::Uuid| {
+        let _ = save_project(project, save_tick);
+        preview_dirty.set(true);
+        spawn_asset_duration_probe(project, asset_id);
+        spawn_video_codec_check(project, asset_id);
+        if let Some(asset) = project.read().find_asset(asset_id).cloned() {
+            let thumbs = thumbnailer.read().clone();
+            let mut thumbnail_cache_buster = thumbnail_cache_buster.clone();
+            spawn(async move {
+                thumbs.generate(&asset, true).await;
+                thumbnail_cache_buster.set(thumbnail_cache_buster() + 1);
+            });
+        }
+    };
+    let mut relink_from_dialog = move |asset_id: uuid::Uuid| {
+        let Some(name) = project.read().find_asset(asset_id).map(|asset| asset.name.clone()) else {
+            return;
+        };
+        let Some(path) = rfd::FileDialog::new()
+            .set_title(format!("Relink \"{}\"", name))
+            .pick_file()
+        else {
+            return;
+        };
+        let result = project.write().relink_asset(asset_id, &path);
+        match result {
+            Ok(()) => finish_relink(asset_id),
+            Err(err) => flash_status_message(status_message, format!("Relink failed: {}", err)),
+        }
+    };
+    // Save As: copy the project folder to a new location, then switch to the copy
+    let mut start_save_as = move || {
+        if folder_job_progress.peek().is_some() {
+            return;
+        }
+        let (current_root, current_name) = {
+            let project_read = project.read();
+            let Some(root) = project_read.project_path.clone() else {
+                return;
+            };
+            (root, project_read.name.clone())
+        };
+        let start_dir = current_root
+            .parent()
+            .map(|parent| parent.to_path_buf())
+            .unwrap_or_else(|| current_root.clone());
+        let Some(dest) = rfd::FileDialog::new()
+            .set_title("Save Project As")
+            .set_directory(&start_dir)
+            .set_file_name(format!("{} copy", current_name))
+            .save_file()
+        else {
+            return;
+        };
+        let new_name = dest
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| format!("{} copy", current_name));
+        let snapshot = project.read().clone();
+        folder_job_progress.set(Some(("Saving project copy...", new_name.clone(), 0.0)));
+        spawn(async move {
+            let copied = Arc::new((AtomicU64::new(0), AtomicU64::new(0)));
+            let task = tokio::task::spawn_blocking({
+                let copied = Arc::clone(&copied);
+                let dest = dest.clone();
+                let new_name = new_name.clone();
+                move || {
+                    snapshot.duplicate_to(&dest, new_name, |done, total| {
+                        copied.0.store(done, Ordering::Relaxed);
+                        copied.1.store(total, Ordering::Relaxed);
+                    })
+                }
+            });
+            while !task.is_finished() {
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                let total = copied.1.load(Ordering::Relaxed);
+                let fraction = if total == 0 {
+                    0.0
+                } else {
+                    copied.0.load(Ordering::Relaxed) as f64 / total as f64
+                };
+                folder_job_progress.set(Some(("Saving project copy...", new_name.clone(), fraction)));
+            }
+            let result = task
+                .await
+                .map_err(|err| err.to_string())
+                .and_then(|result| result.map_err(|err| err.to_string()));
+            folder_job_progress.set(None);
+            let copy = match result {
+                Ok(copy) => copy,
+                Err(err) => {
+                    eprintln!("[PROJECT SAVE ERROR] Save As to {} failed: {}", dest.display(), err);
+                    flash_status_message(status_message, format!("Save As failed: {}", err));
+                    return;
+                }
+            };
+
+            record_recent_project(&dest);
+            thumbnailer.set(Arc::new(crate::core::thumbnailer::Thumbnailer::new(dest.clone())));
+            previewer.set(Arc::new(preview_renderer_for_bounds(
+                dest.clone(),
+                &copy.settings,
+                *preview_native_bounds.peek(),
+                *show_checkerboard.peek(),
+            )));
+            provider_entries.set(load_merged_provider_entries_or_empty(Some(dest.as_path())));
+            project.set(copy);
+            history.write().clear();
+            preview_dirty.set(true);
+            audio_waveform_cache_buster.set(audio_waveform_cache_buster() + 1);
+            flash_status_message(status_message, format!("Saved as \"{}\"", new_name));
+        });
+    };
+    // Export Bundle: zip the project folder plus the providers it uses
+    let mut start_export_bundle = move || {
+        if folder_job_progress.peek().is_some() {
+            return;
+        }
+        let (project_root, project_name) = {
+            let project_read = project.read();
+            let Some(root) = project_read.project_path.clone() else {
+                return;
+            };
+            (root, project_read.name.clone())
+        };
+        // The bundle is built from disk, so flush pending edits first
+        if let Err(err) = save_project(project, save_tick) {
+            eprintln!("[BUNDLE ERROR] Failed to save before export: {}", err);
+            flash_status_message(status_message, format!("Export Bundle failed: {}", err));
+            return;
+        }
+        let start_dir = project_root
+            .parent()
+            .map(|parent| parent.to_path_buf())
+            .unwrap_or_else(|| project_root.clone());
+        let Some(output) = rfd::FileDialog::new()
+            .set_title("Export Bundle")
+            .set_directory(&start_dir)
+            .set_file_name(format!("{}.zip", project_name))
+            .add_filter("Zip archive", &["zip"])
+            .save_file()
+        else {
+            return;
+        };
+        let snapshot = project.read().clone();
+        let providers = provider_entries.read().clone();
+        folder_job_progress.set(Some(("Exporting bundle...", project_name.clone(), 0.0)));
+        spawn(async move {
+            let written = Arc::new((AtomicU64::new(0), AtomicU64::new(0)));
+            let task = tokio::task::spawn_blocking({
+                let written = Arc::clone(&written);
+                let output = output.clone();
+                move || {
+                    let plan = crate::core::bundle::plan_project_bundle(
+                        &snapshot,
+                        &project_root,
+                        &providers,
+                        &output,
+                    )
+                    .map_err(|err| err.to_string())?;
+                    crate::core::bundle::write_project_bundle(&plan, &output, |done, total| {
+                        written.0.store(done, Ordering::Relaxed);
+                        written.1.store(total, Ordering::Relaxed);
+                    })?;
+                    Ok::<_, String>(plan)
+                }
+            });
+            while !task.is_finished() {
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                let total = written.1.load(Ordering::Relaxed);
+                let fraction = if total == 0 {
+                    0.0
+                } else {
+                    written.0.load(Ordering::Relaxed) as f64 / total as f64
+                };
+                folder_job_progress.set(Some(("Exporting bundle...", project_name.clone(), fraction)));
+            }
+            let result = task.await.map_err(|err| err.to_string()).and_then(|result| result);
+            folder_job_progress.set(None);
+            match result {
+                Ok(plan) => {
+                    for media in &plan.external_media {
+                        println!(
+                            "[BUNDLE] Not bundled (outside project): {} ({})",
+                            media.asset_name,
+                            media.path.display()
+                        );
+                    }
+                    flash_status_message(status_message, plan.summary());
+                }
+                Err(err) => {
+                    eprintln!("[BUNDLE ERROR] Export to {} failed: {}", output.display(), err);
+                    let _ = std::fs::remove_file(&output);
+                    flash_status_message(status_message, format!("Export Bundle failed: {}", err));
+                }
+            }
+        });
+    };
+    // Decode audio for a clip that was just placed and build its waveform peaks
+    let warm_placed_clip_audio = {
+        let audio_engine = audio_engine.clone();
+        let audio_sample_cache = audio_sample_cache.clone();
+        let audio_decode_in_flight = audio_decode_in_flight.clone();
+        move |asset_id: uuid::Uuid| {
+            if let Some(asset) = project.read().find_asset(asset_id).cloned() {
+                if asset.is_audio() || asset.is_video() {
+                    if let Some(project_root) = project.read().project_path.clone() {
+                        if let Some(source_path) = resolve_audio_or_video_source(&project_root, &asset) {
+                            if let Some(engine) = audio_engine.as_ref() {
+                                let project_snapshot = project.read().clone();
+                                let decode_config = playback_decode_config(&engine, &project_snapshot);
+                                schedule_audio_decode_targets(
+                                    vec![(asset.id, source_path.clone())],
+                                    decode_config,
+                                    Arc::clone(&audio_sample_cache),
+                                    Arc::clone(&audio_decode_in_flight),
+                                    project_snapshot,
+                                    project_root.clone(),
+                                    Arc::clone(engine),
+                                );
+                            }
+                            if asset.is_audio() {
+                                let mut audio_waveform_cache_buster = audio_waveform_cache_buster.clone();
+                                let peak_config = PeakBuildConfig::with_peaks_per_second(
+                                    project.read().settings.waveform_peaks_per_second,
+                                )
+                                .with_resample_quality(project.read().settings.preview_resample_quality);
+                                spawn(async move {
+                                    let needs_build = tokio::task::spawn_blocking({
+                                        let cache_path = peak_cache_path(&project_root, asset_id);
+                                        let source_path = source_path.clone();
+                                        move || {
+                                            load_shared_peak_cache(
+                                                shared_peak_caches(),
+                                                asset_id,
+                                                &cache_path,
+                                                &source_path,
+                                                &peak_config,
+                                            )
+                                            .map(|cache| cache.is_none())
+                                        }
+                                    })
+                                    .await
+                                    .ok()
+                                    .unwrap_or(Ok(true))
+                                    .unwrap_or(true);
+
+                                    if needs_build {
+                                        let _ = run_peak_cache_build(
+                                            project_root,
+                                            asset.id,
+                                            source_path,
+                                            peak_config,
+                                            None,
+                                        )
+                                        .await;
+                                        audio_waveform_cache_buster
+                                            .set(audio_waveform_cache_buster() + 1);
+                                    }
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    };
+    // Add an asset at the playhead, from its context menu or the Enter key
+    let mut add_asset_at_playhead = move |asset_id: uuid::Uuid| {
+        // Add clip at current playhead position using asset duration when available
+        let time = current_time();
+        let duration = resolve_asset_duration_seconds(project, asset_id)
+            .unwrap_or(DEFAULT_CLIP_DURATION_SECONDS);
+        history.write().push(&project.read());
+        project.write().add_clip_from_asset(asset_id, time, duration);
+        preview_dirty.set(true);
+        if let Some(asset) = project.read().find_asset(asset_id).cloned() {
+            if asset.is_audio() {
+                if let Some(project_root) = project.read().project_path.clone() {
+                    if let Some(source_path) = resolve_audio_source(&project_root, &asset) {
+                        let mut audio_waveform_cache_buster = audio_waveform_cache_buster.clone();
+                        let peak_config = PeakBuildConfig::with_peaks_per_second(
+                            project.read().settings.waveform_peaks_per_second,
+                        )
+                        .with_resample_quality(project.read().settings.preview_resample_quality);
+                        spawn(async move {
+                            let needs_build = tokio::task::spawn_blocking({
+                                let cache_path = peak_cache_path(&project_root, asset_id);
+                                let source_path = source_path.clone();
+                                move || {
+                                    load_shared_peak_cache(
+                                        shared_peak_caches(),
+                                        asset_id,
+                                        &cache_path,
+                                        &source_path,
+                                        &peak_config,
+                                    )
+                                    .map(|cache| cache.is_none())
+                                }
+                            })
+                            .await
+                            .ok()
+                            .unwrap_or(Ok(true))
+                            .unwrap_or(true);
+
+                            if needs_build {
+                                let _ = run_peak_cache_build(
+                                    project_root,
+                                    asset.id,
+                                    source_path,
+                                    peak_config,
+                                    None,
+                                )
+                                .await;
+                                audio_waveform_cache_buster
+                                    .set(audio_waveform_cache_buster() + 1);
+                            }
+                        });
+                    }
+                }
+            }
+        }
+    };
     let audio_engine_for_hotkeys = audio_engine.clone();
     let audio_sample_cache_for_hotkeys = audio_sample_cache.clone();
     let audio_decode_in_flight_for_hotkeys = audio_decode_in_flight.clone();
@@ -1506,6 +2316,7 @@ pub fn App() -> Element {
             ",
             onmousedown: move |_| {
                 timeline_focused.set(false);
+                assets_focused.set(false);
             },
             
             onmousemove: {
@@ -1534,12 +2345,13 @@ pub fn App() -> Element {
                             timeline_height.set(new_h);
                         }
                         "playhead" => {
-                            // Convert mouse x delta to time delta using zoom factor
+                            // Map the dragged position the same way the ruler click did
                             let delta_px = e.client_coordinates().x - drag_start_pos();
-                            let delta_frames = (delta_px / zoom()) * timeline_fps;
-                            let start_frames =
-                                frames_from_seconds(drag_start_size(), timeline_fps).round();
-                            let mut new_frames = start_frames + delta_frames;
+                            let drag_x = drag_start_size() * zoom() + delta_px;
+                            let drag_time =
+                                ruler_time_at(drag_x, zoom(), 0.0, duration, timeline_fps);
+                            let mut new_frames =
+                                frames_from_seconds(drag_time, timeline_fps).round();
                             let snap_enabled = !e.modifiers().alt();
                             let snap_threshold_frames = if zoom() > 0.0 {
                                 (TIMELINE_SNAP_THRESHOLD_PX / zoom()) * timeline_fps
@@ -1607,10 +2419,29 @@ pub fn App() -> Element {
                 let has_selection =
                     !selection_state.clip_ids.is_empty() || !selection_state.marker_ids.is_empty();
                 drop(selection_state);
+                let generative_selected = selection
+                    .read()
+                    .primary_clip()
+                    .and_then(|clip_id| {
+                        let project_read = project.read();
+                        let asset_id = project_read
+                            .clips
+                            .iter()
+                            .find(|clip| clip.id == clip_id)?
+                            .asset_id;
+                        project_read
+                            .find_asset(asset_id)
+                            .map(|asset| asset.is_generative())
+                    })
+                    .unwrap_or(false);
                 let hotkey_context = HotkeyContext {
                     timeline_visible: !timeline_collapsed(),
                     has_selection,
-                    input_focused: false, // TODO: track when input fields have focus
+                    input_focused: input_focused(),
+                    generative_selected,
+                    track_selected: selection.read().primary_track().is_some(),
+                    assets_focused: assets_focused() && !left_collapsed(),
+                    modal_open: any_modal_open(),
                 };
 
                 // Get modifier states
@@ -1621,7 +2452,15 @@ pub fn App() -> Element {
                 let meta = modifiers.meta();
 
                 // Dispatch the hotkey
-                match handle_hotkey(&e.key(), shift, ctrl, alt, meta, &hotkey_context) {
+                match handle_hotkey_with_keymap(
+                    &e.key(),
+                    shift,
+                    ctrl,
+                    alt,
+                    meta,
+                    &hotkey_context,
+                    &keymap,
+                ) {
                     HotkeyResult::Action(action) => {
                         if matches!(action, HotkeyAction::PlayPause) && !timeline_focused() {
                             return;
@@ -1662,6 +2501,51 @@ pub fn App() -> Element {
                                     scroll_offset_for_hotkeys.clone(),
                                 );
                             }
+                            HotkeyAction::TimelineZoomFit => {
+                                let mut zoom = zoom_for_hotkeys.clone();
+                                let mut scroll_offset = scroll_offset_for_hotkeys.clone();
+                                zoom.set(timeline_zoom_to_fit(
+                                    duration,
+                                    timeline_viewport_width(),
+                                    timeline_fps,
+                                ));
+                                scroll_offset.set(0.0);
+                            }
+                            HotkeyAction::TimelineZoomSelection => {
+                                let span = {
+                                    let project_read = project.read();
+                                    let selection_read = selection.read();
+                                    project_read
+                                        .clips
+                                        .iter()
+                                        .filter(|clip| selection_read.clip_ids.contains(&clip.id))
+                                        .fold(None, |span: Option<(f64, f64)>, clip| {
+                                            let end = clip.start_time + clip.duration;
+                                            Some(match span {
+                                                Some((start, stop)) => {
+                                                    (start.min(clip.start_time), stop.max(end))
+                                                }
+                                                None => (clip.start_time, end),
+                                            })
+                                        })
+                                };
+                                if let Some((start, end)) = span {
+                                    let (new_zoom, new_scroll) = timeline_zoom_to_span(
+                                        start,
+                                        end,
+                                        duration,
+                                        timeline_viewport_width(),
+                                        timeline_fps,
+                                    );
+                                    let mut zoom = zoom_for_hotkeys.clone();
+                                    let mut scroll_offset = scroll_offset_for_hotkeys.clone();
+                                    zoom.set(new_zoom);
+                                    scroll_offset.set(new_scroll);
+                                }
+                            }
+                            HotkeyAction::ToggleFollowPlayhead => {
+                                follow_playhead.toggle();
+                            }
                             HotkeyAction::PlayPause => {
                                 timeline_focused.set(true);
                                 toggle_playback(
@@ -1674,12 +2558,257 @@ pub fn App() -> Element {
                                 );
                             }
                             HotkeyAction::SaveProject => {
-                                if let Err(err) = project.read().save() {
+                                if let Err(err) = save_project(project, save_tick) {
                                     println!("[PROJECT SAVE] Failed: {}", err);
                                 } else {
                                     println!("[PROJECT SAVE] Saved.");
                                 }
                             }
+                            HotkeyAction::SaveProjectAs => start_save_as(),
+                            HotkeyAction::FocusGenerativeControls => {
+                                timeline_focused.set(false);
+                                right_collapsed.set(false);
+                                let _ = document::eval(FOCUS_GENERATIVE_CONTROLS_SCRIPT);
+                            }
+                            HotkeyAction::Generate => {
+                                let clip_ids = selection.read().clip_ids.clone();
+                                generate_clips(
+                                    project,
+                                    provider_entries,
+                                    &clip_ids,
+                                    generation_status,
+                                    EventHandler::new(on_enqueue_generation),
+                                );
+                            }
+                            HotkeyAction::DeleteSelection => {
+                                let (clip_ids, marker_ids) = {
+                                    let selection_read = selection.read();
+                                    (selection_read.clip_ids.clone(), selection_read.marker_ids.clone())
+                                };
+                                if clip_ids.is_empty() && marker_ids.is_empty() {
+                                    return;
+                                }
+                                history.write().push(&project.read());
+                                {
+                                    let mut project_write = project.write();
+                                    for clip_id in clip_ids.iter() {
+                                        project_write.remove_linked_clips(*clip_id);
+                                    }
+                                    for marker_id in marker_ids.iter() {
+                                        project_write.remove_marker(*marker_id);
+                                    }
+                                }
+                                selection.write().clear();
+                                preview_dirty.set(true);
+                            }
+                            HotkeyAction::GroupSelection | HotkeyAction::UngroupSelection => {
+                                let clip_ids = selection.read().clip_ids.clone();
+                                let snapshot = project.read().clone();
+                                let changed = if matches!(action, HotkeyAction::GroupSelection) {
+                                    project.write().group_clips(&clip_ids).is_some()
+                                } else {
+                                    project.write().ungroup_clips(&clip_ids)
+                                };
+                                if changed {
+                                    history.write().push(&snapshot);
+                                }
+                            }
+                            HotkeyAction::AssetPrevious
+                            | HotkeyAction::AssetNext
+                            | HotkeyAction::AssetFirst
+                            | HotkeyAction::AssetLast => {
+                                let movement = match action {
+                                    HotkeyAction::AssetPrevious => AssetCursorMove::Previous,
+                                    HotkeyAction::AssetNext => AssetCursorMove::Next,
+                                    HotkeyAction::AssetFirst => AssetCursorMove::First,
+                                    _ => AssetCursorMove::Last,
+                                };
+                                // Walk the list as displayed, search and filters applied
+                                let ids: Vec<uuid::Uuid> = {
+                                    let project_read = project.read();
+                                    displayed_assets(
+                                        &project_read.assets,
+                                        &asset_search(),
+                                        asset_type_filter(),
+                                        project_read.settings.asset_sort,
+                                    )
+                                    .into_iter()
+                                    .map(|asset| asset.id)
+                                    .collect()
+                                };
+                                let current = asset_cursor()
+                                    .and_then(|id| ids.iter().position(|other| *other == id));
+                                if let Some(index) = move_asset_cursor(current, ids.len(), movement) {
+                                    asset_cursor.set(Some(ids[index]));
+                                    document::eval(&format!(
+                                        "document.getElementById('asset-item-{}')?.scrollIntoView({{ block: 'nearest' }});",
+                                        ids[index]
+                                    ));
+                                }
+                            }
+                            HotkeyAction::AddAssetToTimeline => {
+                                let asset_id = asset_cursor()
+                                    .filter(|id| project.read().find_asset(*id).is_some());
+                                if let Some(asset_id) = asset_id {
+                                    add_asset_at_playhead(asset_id);
+                                }
+                            }
+                            HotkeyAction::NextMarker | HotkeyAction::PrevMarker => {
+                                let target = {
+                                    let project_read = project.read();
+                                    if matches!(action, HotkeyAction::NextMarker) {
+                                        next_marker_time(&project_read.markers, current_time())
+                                    } else {
+                                        prev_marker_time(&project_read.markers, current_time())
+                                    }
+                                };
+                                if let Some(time) = target {
+                                    let next_time = time.clamp(0.0, duration);
+                                    current_time.set(next_time);
+                                    if let Some(engine) = audio_engine_for_hotkeys.as_ref() {
+                                        engine.seek_seconds(next_time);
+                                    }
+                                }
+                            }
+                            HotkeyAction::AddMarker => {
+                                if add_marker_at_time(project, history, selection, current_time()) {
+                                    timeline_focused.set(true);
+                                }
+                            }
+                            HotkeyAction::StepForward
+                            | HotkeyAction::StepBackward
+                            | HotkeyAction::StepForwardTenFrames
+                            | HotkeyAction::StepBackwardTenFrames => {
+                                let frames = match action {
+                                    HotkeyAction::StepForward => 1,
+                                    HotkeyAction::StepBackward => -1,
+                                    HotkeyAction::StepForwardTenFrames => 10,
+                                    _ => -10,
+                                };
+                                let next_time =
+                                    step_time_by_frames(current_time(), frames, timeline_fps, duration);
+                                current_time.set(next_time);
+                                if let Some(engine) = audio_engine_for_hotkeys.as_ref() {
+                                    engine.seek_seconds(next_time);
+                                }
+                            }
+                            HotkeyAction::Undo | HotkeyAction::Redo => {
+                                let redo = matches!(action, HotkeyAction::Redo);
+                                if apply_history_step(project, history, selection, redo) {
+                                    preview_dirty.set(true);
+                                }
+                            }
+                            HotkeyAction::CopySelection => {
+                                let selected = selection.read().clip_ids.clone();
+                                let copied: Vec<_> = project
+                                    .read()
+                                    .clips
+                                    .iter()
+                                    .filter(|clip| selected.contains(&clip.id))
+                                    .cloned()
+                                    .collect();
+                                if !copied.is_empty() {
+                                    clip_clipboard.set(copied);
+                                }
+                            }
+                            HotkeyAction::Paste => {
+                                let copied = clip_clipboard.read().clone();
+                                if copied.is_empty() {
+                                    return;
+                                }
+                                let target_track = selection.read().primary_track();
+                                let before = crate::state::ProjectSnapshot::capture(&project.read());
+                                let pasted = project.write().paste_clips(&copied, current_time(), target_track);
+                                if !pasted.is_empty() {
+                                    history.write().push_snapshot(before, &project.read());
+                                    selection.write().select_clips(pasted);
+                                    timeline_focused.set(true);
+                                    preview_dirty.set(true);
+                                }
+                            }
+                            HotkeyAction::DuplicateSelection => {
+                                let selected = selection.read().clip_ids.clone();
+                                if selected.is_empty() {
+                                    return;
+                                }
+                                history.write().push(&project.read());
+                                let duplicates: Vec<_> = {
+                                    let mut project_write = project.write();
+                                    selected
+                                        .iter()
+                                        .filter_map(|clip_id| project_write.duplicate_clip(*clip_id))
+                                        .collect()
+                                };
+                                if !duplicates.is_empty() {
+                                    selection.write().select_clips(duplicates);
+                                    preview_dirty.set(true);
+                                }
+                            }
+                            HotkeyAction::NudgeLeft | HotkeyAction::NudgeRight => {
+                                // Linked partners ride along with the selection
+                                let selected: Vec<uuid::Uuid> = {
+                                    let project_read = project.read();
+                                    selection
+                                        .read()
+                                        .clip_ids
+                                        .iter()
+                                        .flat_map(|clip_id| project_read.linked_clip_ids(*clip_id))
+                                        .collect()
+                                };
+                                let (ids, starts): (Vec<_>, Vec<_>) = project
+                                    .read()
+                                    .clips
+                                    .iter()
+                                    .filter(|clip| selected.contains(&clip.id))
+                                    .map(|clip| (clip.id, clip.start_time))
+                                    .unzip();
+                                if ids.is_empty() {
+                                    return;
+                                }
+                                let frames = if matches!(action, HotkeyAction::NudgeLeft) { -1 } else { 1 };
+                                let nudged = nudge_start_times(&starts, frames, timeline_fps);
+                                if nudged == starts {
+                                    return;
+                                }
+                                history
+                                    .write()
+                                    .push_coalesced(&project.read(), Some("nudge".to_string()));
+                                {
+                                    let mut project_write = project.write();
+                                    for (clip_id, start) in ids.iter().zip(nudged) {
+                                        project_write.move_clip(*clip_id, start);
+                                    }
+                                }
+                                preview_dirty.set(true);
+                            }
+                            HotkeyAction::ToggleTrackMute | HotkeyAction::ToggleTrackSolo => {
+                                let Some(track_id) = selection.read().primary_track() else {
+                                    return;
+                                };
+                                let before = crate::state::ProjectSnapshot::capture(&project.read());
+                                let toggled = if matches!(action, HotkeyAction::ToggleTrackMute) {
+                                    project.write().toggle_track_mute(track_id)
+                                } else {
+                                    project.write().toggle_track_solo(track_id)
+                                };
+                                if toggled.is_some() {
+                                    history.write().push_snapshot(before, &project.read());
+                                    refresh_playback_items(
+                                        &audio_engine_for_hotkeys,
+                                        &audio_sample_cache_for_hotkeys,
+                                        project,
+                                    );
+                                    preview_dirty.set(true);
+                                }
+                            }
+                            HotkeyAction::SelectAll => {
+                                let clip_ids = project.read().clips.iter().map(|clip| clip.id).collect();
+                                selection.write().select_clips(clip_ids);
+                                timeline_focused.set(true);
+                            }
+                            HotkeyAction::DeselectAll => {
+                                selection.write().clear();
+                            }
                         }
                     }
                     HotkeyResult::NoMatch | HotkeyResult::Suppressed => {}
@@ -1705,6 +2834,8 @@ pub fn App() -> Element {
 
                 TitleBar { 
                     project_name: project.read().name.clone(),
+                    has_unsaved_changes: has_unsaved_changes(),
+                    project_path: project.read().project_path.clone(),
                     on_new_project: move |_| {
                         show_new_project_dialog.set(true);
                     },
@@ -1712,13 +2843,205 @@ pub fn App() -> Element {
                         // Since project knows its own path (if loaded/saved once), we can just save
                         // If it's effectively unsaved (default path), we might want a "Save As" flow eventually
                         // For now, MVP assumes we have a path from startup or just saves to current effective path
-                        let _ = project.read().save(); 
+                        let _ = save_project(project, save_tick); 
                     },
+                    on_save_as: move |_| start_save_as(),
+                    on_export_bundle: move |_| start_export_bundle(),
                     on_project_settings: move |_| {
                         if project.read().project_path.is_some() && startup_done() {
                             show_project_settings_dialog.set(true);
                         }
                     },
+                    on_export_settings: move |_| {
+                        if project.read().project_path.is_some() && startup_done() {
+                            show_export_settings_dialog.set(true);
+                        }
+                    },
+                    on_export_audio: move |_| {
+                        let snapshot = project.read().clone();
+                        let Some(root) = snapshot.project_path.clone() else {
+                            return;
+                        };
+                        let Some(out_path) = rfd::FileDialog::new()
+                            .set_directory(crate::core::paths::project_exports_dir(&root))
+                            .set_file_name(format!(
+                                "{}.wav",
+                                crate::core::paths::safe_file_stem(&snapshot.name, "mixdown")
+                            ))
+                            .add_filter("wav", &["wav"])
+                            .save_file()
+                        else {
+                            return;
+                        };
+                        spawn(async move {
+                            let result = tokio::task::spawn_blocking(move || {
+                                crate::core::audio::mixdown::export_wav(&snapshot, &root, &out_path)
+                            })
+                            .await;
+                            match result {
+                                Ok(Ok(())) => {}
+                                Ok(Err(err)) => eprintln!("[AUDIO ERROR] {}", err),
+                                Err(err) => eprintln!("[AUDIO ERROR] WAV export failed: {}", err),
+                            }
+                        });
+                    },
+                    on_measure_loudness: move |_| {
+                        let snapshot = project.read().clone();
+                        let Some(root) = snapshot.project_path.clone() else {
+                            return;
+                        };
+                        mix_loudness.set(Some("measuring...".to_string()));
+                        spawn(async move {
+                            let result = tokio::task::spawn_blocking(move || {
+                                crate::core::audio::mixdown::measure_mix_lufs(&snapshot, &root)
+                            })
+                            .await;
+                            match result {
+                                Ok(lufs) => mix_loudness.set(Some(format_lufs(lufs))),
+                                Err(err) => {
+                                    eprintln!("[AUDIO ERROR] Loudness measurement failed: {}", err);
+                                    mix_loudness.set(None);
+                                }
+                            }
+                        });
+                    },
+                    on_remove_unused_assets: move |_| {
+                        let (unused, names, imported) = {
+                            let project_read = project.read();
+                            let unused = project_read.unused_assets();
+                            let assets: Vec<&crate::state::Asset> = unused
+                                .iter()
+                                .filter_map(|id| project_read.find_asset(*id))
+                                .collect();
+                            let names: Vec<String> =
+                                assets.iter().map(|asset| asset_display_name(asset)).collect();
+                            let imported = assets.iter().filter(|asset| !asset.is_generative()).count();
+                            (unused, names, imported)
+                        };
+                        if unused.is_empty() {
+                            rfd::MessageDialog::new()
+                                .set_level(rfd::MessageLevel::Info)
+                                .set_title("Remove Unused Assets")
+                                .set_description("Every asset is used by a clip.")
+                                .set_buttons(rfd::MessageButtons::Ok)
+                                .show();
+                            return;
+                        }
+                        let Some(cleanup) = prompt_remove_unused_assets(&names, imported) else {
+                            return;
+                        };
+                        let thumbs = thumbnailer.read().clone();
+                        {
+                            let mut project_write = project.write();
+                            for id in unused.iter().copied() {
+                                if cleanup != UnusedAssetCleanup::ProjectOnly {
+                                    let include_sources = cleanup == UnusedAssetCleanup::GeneratedAndSources;
+                                    if let Err(err) = project_write.delete_asset_files(id, include_sources) {
+                                        eprintln!("Failed to delete files for asset {}: {}", id, err);
+                                    }
+                                }
+                                thumbs.clear_cache_for_asset(id);
+                                project_write.remove_asset(id);
+                            }
+                        }
+                        if asset_cursor().is_some_and(|id| unused.contains(&id)) {
+                            asset_cursor.set(None);
+                        }
+                        // The manifest must stop pointing at files that are gone
+                        if cleanup != UnusedAssetCleanup::ProjectOnly {
+                            if let Err(err) = save_project(project, save_tick) {
+                                eprintln!("Failed to save project after removing assets: {}", err);
+                            }
+                        }
+                        preview_dirty.set(true);
+                    },
+                    on_export_video: move |_| {
+                        if project.read().project_path.is_none()
+                            || !startup_done()
+                            || export_status().is_some()
+                        {
+                            return;
+                        }
+                        let snapshot = project.read().clone();
+                        let options = snapshot.export_options;
+                        let file_name =
+                            format!("{}.{}", snapshot.name, options.container.extension());
+                        let mut dialog = rfd::FileDialog::new()
+                            .set_file_name(&file_name)
+                            .add_filter(
+                                options.container.extension(),
+                                &[options.container.extension()],
+                            );
+                        if let Some(root) = snapshot.project_path.as_ref() {
+                            dialog = dialog.set_directory(root.join("exports"));
+                        }
+                        let Some(out_path) = dialog.save_file() else {
+                            return;
+                        };
+
+                        let handle = CancelHandle::default();
+                        export_handle.set(Some(handle.clone()));
+                        export_status.set(Some(ExportStatus::Running(ExportProgress {
+                            frame: 0,
+                            total_frames: export_frame_count(
+                                snapshot.duration(),
+                                snapshot.settings.fps,
+                            ),
+                            elapsed: Duration::ZERO,
+                        })));
+
+                        let (progress_tx, mut progress_rx) =
+                            tokio::sync::mpsc::unbounded_channel::<ExportProgress>();
+                        spawn(async move {
+                            while let Some(progress) = progress_rx.recv().await {
+                                // Don't overwrite a final status that landed first.
+                                if matches!(export_status(), Some(ExportStatus::Running(_))) {
+                                    export_status.set(Some(ExportStatus::Running(progress)));
+                                }
+                            }
+                        });
+                        spawn(async move {
+                            let status = export_project(
+                                snapshot,
+                                options,
+                                out_path,
+                                handle,
+                                move |progress| {
+                                    let _ = progress_tx.send(progress);
+                                },
+                            )
+                            .await;
+                            if let ExportStatus::Failed(err) = &status {
+                                eprintln!("[EXPORT WARN] {}", err);
+                            }
+                            export_status.set(Some(status));
+                        });
+                    },
+                    can_undo: history.read().can_undo(),
+                    can_redo: history.read().can_redo(),
+                    on_undo: move |_| {
+                        if apply_history_step(project, history, selection, false) {
+                            preview_dirty.set(true);
+                        }
+                    },
+                    on_redo: move |_| {
+                        if apply_history_step(project, history, selection, true) {
+                            preview_dirty.set(true);
+                        }
+                    },
+                    on_reveal_folder: move |folder: String| {
+                        let Some(root) = project.read().project_path.clone() else {
+                            return;
+                        };
+                        let target = if folder.is_empty() { root } else { root.join(folder) };
+                        if let Err(err) = std::fs::create_dir_all(&target) {
+                            println!("Failed to create {}: {}", target.display(), err);
+                            return;
+                        }
+                        if let Err(err) = crate::core::paths::reveal_in_file_browser(&target) {
+                            println!("Failed to reveal {}: {}", target.display(), err);
+                        }
+                    },
                     on_open_providers: move |_| {
                         open_providers_dialog();
                     },
@@ -1727,6 +3050,11 @@ pub fn App() -> Element {
                         show_preview_stats.set(!show_preview_stats());
                     },
                     use_hw_decode: use_hw_decode(),
+                    on_clear_preview_cache: move |_| {
+                        previewer.read().clear_cache();
+                        preview_cache_tick.set(preview_cache_tick() + 1);
+                        preview_dirty.set(true);
+                    },
                     on_toggle_hw_decode: move |_| {
                         use_hw_decode.set(!use_hw_decode());
                         preview_dirty.set(true);
@@ -1767,6 +3095,19 @@ pub fn App() -> Element {
                     // Assets panel content
                     AssetsPanelContent {
                         assets: project.read().assets.clone(),
+                        focused_asset: asset_cursor(),
+                        search: asset_search,
+                        type_filter: asset_type_filter,
+                        sort: project.read().settings.asset_sort,
+                        on_sort_change: move |sort| {
+                            let mut project_write = project.write();
+                            project_write.settings.asset_sort = sort;
+                            project_write.mark_edited();
+                        },
+                        on_focus: move |_| {
+                            assets_focused.set(true);
+                            timeline_focused.set(false);
+                        },
                         thumbnailer: thumbnailer.read().clone(),
                         thumbnail_cache_buster: thumbnail_cache_buster(),
                         thumbnail_refresh_tick: thumbnail_refresh_tick(),
@@ -1792,15 +3133,7 @@ pub fn App() -> Element {
                             match import_result {
                                 Ok(asset_id) => {
                                     preview_dirty.set(true);
-                                    // Trigger thumbnail generation for the new asset
-                                    if let Some(asset) = project.read().find_asset(asset_id).cloned() {
-                                        let thumbs = thumbnailer.read().clone();
-                                        let mut thumbnail_cache_buster = thumbnail_cache_buster.clone();
-                                        spawn(async move {
-                                            thumbs.generate(&asset, false).await;
-                                            thumbnail_cache_buster.set(thumbnail_cache_buster() + 1);
-                                        });
-                                    }
+                                    start_imported_asset_jobs(asset_id);
                                     spawn_asset_duration_probe(project, asset_id);
                                 },
                                 Err(e) => println!("Failed to import file {:?}: {}", path, e),
@@ -1819,43 +3152,30 @@ pub fn App() -> Element {
                                 let mut thumbnail_cache_buster = thumbnail_cache_buster.clone();
                                 let mut audio_waveform_cache_buster = audio_waveform_cache_buster.clone();
                                 let project_root = project.read().project_path.clone();
+                                let peaks_per_second = project.read().settings.waveform_peaks_per_second;
+                                let resample_quality = project.read().settings.preview_resample_quality;
                                 spawn(async move {
-                                     // Force regeneration logic could require a flag in future,
-                                     // but our `generate` function currently checks existence.
-                                     // To force it, we should probably delete the cache dir first or add a force flag.
-                                     // For now, let's just assume the user deleted the cache or we want to try again if it failed.
-                                     // To firmly force it, we'll manually nuke the cache dir for this asset here before calling generate.
-                                    
-                                     // We need to resolve the cache path to delete it.
-                                     // Thumbnailer encapsulates the path logic.
-                                     // Let's just trust `generate` or update `Thumbnailer` later to support `force`.
-                                     // Actually, user asked for manual trigger. Let's make it robust by deleting first.
-                                     // But `Thumbnailer` struct has `cache_root` private.
-                                     // Let's just call `generate` for now. If it already exists it returns silently.
-                                     // If we really want "Re-generate", we need to clear it.
-                                     // Let's updated `Thumbnailer` in a separate step or just rely on 'generate' doing a check.
-                                     // User said "what triggers re-generation... force manually".
-                                     // If `generate` returns early on existing folder, this won't do anything if folder exists.
-                                     // We should probably modify `Thumbnailer::generate` to accept a `force` boolean.
-                                     // I will do that in the next step. For now, let's wire up the UI.
-                                     if asset.is_visual() {
-                                         thumbs.generate(&asset, true).await;
-                                         thumbnail_cache_buster.set(thumbnail_cache_buster() + 1);
-                                     }
+                                    // Forced, so every tile is rebuilt from the current source
+                                    if asset.is_visual() {
+                                        thumbs.generate(&asset, true).await;
+                                        thumbnail_cache_buster.set(thumbnail_cache_buster() + 1);
+                                    }
                                     if asset.is_audio() {
                                         if let Some(project_root) = project_root {
                                             if let Some(source_path) = crate::core::audio::waveform::resolve_audio_source(
                                                 &project_root,
                                                 &asset,
                                             ) {
-                                                let _ = tokio::task::spawn_blocking(move || {
-                                                    crate::core::audio::waveform::build_and_store_peak_cache(
-                                                        &project_root,
-                                                        asset.id,
-                                                        &source_path,
-                                                        crate::core::audio::waveform::PeakBuildConfig::default(),
+                                                let _ = crate::core::audio::waveform::run_peak_cache_build(
+                                                    project_root,
+                                                    asset.id,
+                                                    source_path,
+                                                    crate::core::audio::waveform::PeakBuildConfig::with_peaks_per_second(
+                                                        peaks_per_second,
                                                     )
-                                                })
+                                                    .with_resample_quality(resample_quality),
+                                                    None,
+                                                )
                                                 .await;
                                             } else {
                                             }
@@ -1866,59 +3186,59 @@ pub fn App() -> Element {
                                 });
                             }
                         },
-                        on_delete: move |id| {
-                            project.write().remove_asset(id);
-                            preview_dirty.set(true);
+                        on_transcode: move |asset_id: uuid::Uuid| {
+                            let thumbs = thumbnailer.read().clone();
+                            let mut thumbnail_cache_buster = thumbnail_cache_buster.clone();
+                            spawn(async move {
+                                if !transcode_video_asset(project, asset_id).await {
+                                    return;
+                                }
+                                preview_dirty.set(true);
+                                spawn_asset_duration_probe(project, asset_id);
+                                if let Some(asset) = project.read().find_asset(asset_id).cloned() {
+                                    thumbs.generate(&asset, true).await;
+                                    thumbnail_cache_buster.set(thumbnail_cache_buster() + 1);
+                                }
+                            });
                         },
-                        on_add_to_timeline: move |asset_id| {
-                            // Add clip at current playhead position using asset duration when available
-                            let time = current_time();
-                            let duration = resolve_asset_duration_seconds(project, asset_id)
-                                .unwrap_or(DEFAULT_CLIP_DURATION_SECONDS);
-                            project.write().add_clip_from_asset(asset_id, time, duration);
+                        on_relink: move |asset_id| relink_from_dialog(asset_id),
+                        on_toggle_proxy: move |asset_id: uuid::Uuid| {
+                            let Some((use_proxy, has_proxy)) = project
+                                .read()
+                                .find_asset(asset_id)
+                                .map(|asset| (!asset.use_proxy, asset.proxy.is_some()))
+                            else {
+                                return;
+                            };
+                            project.write().set_asset_use_proxy(asset_id, use_proxy);
+                            let _ = save_project(project, save_tick);
                             preview_dirty.set(true);
-                            if let Some(asset) = project.read().find_asset(asset_id).cloned() {
-                                if asset.is_audio() {
-                                    if let Some(project_root) = project.read().project_path.clone() {
-                                        if let Some(source_path) = resolve_audio_source(&project_root, &asset) {
-                                            let mut audio_waveform_cache_buster = audio_waveform_cache_buster.clone();
-                                            spawn(async move {
-                                                let needs_build = tokio::task::spawn_blocking({
-                                                    let cache_path = peak_cache_path(&project_root, asset_id);
-                                                    let source_path = source_path.clone();
-                                                    move || {
-                                                            if !cache_path.exists() {
-                                                                return Ok::<bool, String>(true);
-                                                            }
-                                                        let cache = load_peak_cache(&cache_path)?;
-                                                        Ok(!cache_matches_source(&cache, &source_path)?)
-                                                    }
-                                                })
-                                                .await
-                                                .ok()
-                                                .unwrap_or(Ok(true))
-                                                .unwrap_or(true);
-
-                                                if needs_build {
-                                                    let _ = tokio::task::spawn_blocking(move || {
-                                                        build_and_store_peak_cache(
-                                                            &project_root,
-                                                            asset.id,
-                                                            &source_path,
-                                                            PeakBuildConfig::default(),
-                                                        )
-                                                    })
-                                                    .await;
-                                                    audio_waveform_cache_buster
-                                                        .set(audio_waveform_cache_buster() + 1);
-                                                }
-                                            });
-                                        }
+                            if !use_proxy || has_proxy {
+                                return;
+                            }
+                            flash_status_message(status_message, "Creating proxy...".to_string());
+                            spawn(async move {
+                                match create_video_proxy(project, asset_id).await {
+                                    Ok(()) => {
+                                        preview_dirty.set(true);
+                                        flash_status_message(status_message, "Proxy ready".to_string());
+                                    }
+                                    Err(err) => {
+                                        project.write().set_asset_use_proxy(asset_id, false);
+                                        flash_status_message(status_message, format!("Proxy failed: {}", err));
                                     }
                                 }
-                            }
+                            });
+                        },
+                        on_delete: move |id| {
+                            project.write().remove_asset(id);
+                            preview_dirty.set(true);
+                        },
+                        on_add_to_timeline: move |asset_id| add_asset_at_playhead(asset_id),
+                        on_drag_start: move |id| {
+                            asset_cursor.set(Some(id));
+                            dragged_asset.set(Some(id));
                         },
-                        on_drag_start: move |id| dragged_asset.set(Some(id)),
                     }
                 }
 
@@ -1936,6 +3256,63 @@ pub fn App() -> Element {
                         preview_gpu_upload_ms: preview_gpu_upload_ms(),
                         show_preview_stats: show_preview_stats(),
                         preview_native_active: preview_native_active(),
+                        can_save_frame: project.read().project_path.is_some() && startup_done(),
+                        show_checkerboard: show_checkerboard(),
+                        on_toggle_checkerboard: move |_| {
+                            let show = !show_checkerboard();
+                            show_checkerboard.set(show);
+                            let cell = checkerboard_cell(show, *preview_native_bounds.peek());
+                            if previewer.peek().set_checkerboard(cell) {
+                                preview_dirty.set(true);
+                            }
+                        },
+                        guides: project.read().settings.preview_guides,
+                        on_guides_change: move |guides| {
+                            let mut project_write = project.write();
+                            project_write.settings.preview_guides = guides;
+                            project_write.mark_edited();
+                            drop(project_write);
+                            preview_dirty.set(true);
+                        },
+                        on_save_frame: move |_| {
+                            let snapshot = project.read().clone();
+                            let Some(root) = snapshot.project_path.clone() else {
+                                return;
+                            };
+                            let time = current_time();
+                            let frame_index = (time * snapshot.settings.fps.max(1.0)).round() as u64;
+                            let Some(out_path) = rfd::FileDialog::new()
+                                .set_directory(crate::core::paths::project_exports_dir(&root))
+                                .set_file_name(format!(
+                                    "{}_{:06}.png",
+                                    crate::core::paths::safe_file_stem(&snapshot.name, "frame"),
+                                    frame_index
+                                ))
+                                .add_filter("png", &["png"])
+                                .save_file()
+                            else {
+                                return;
+                            };
+                            spawn(async move {
+                                // A dedicated renderer decodes at full project resolution;
+                                // the live one is capped to the preview bounds.
+                                let result = tokio::task::spawn_blocking(move || {
+                                    let renderer = crate::core::preview::PreviewRenderer::new_with_limits(
+                                        root,
+                                        snapshot.settings.preview_cache_budget_bytes(),
+                                        snapshot.settings.width,
+                                        snapshot.settings.height,
+                                    );
+                                    renderer.save_frame_png(&snapshot, time, &out_path)
+                                })
+                                .await;
+                                match result {
+                                    Ok(Ok(())) => {}
+                                    Ok(Err(err)) => eprintln!("[PREVIEW WARN] {}", err),
+                                    Err(err) => eprintln!("[PREVIEW WARN] Save frame failed: {}", err),
+                                }
+                            });
+                        },
                     }
 
                     // Timeline resize handle
@@ -1969,6 +3346,9 @@ pub fn App() -> Element {
                             clip_cache_buckets: clip_cache_buckets(),
                             project_root: project.read().project_path.clone(),
                             audio_waveform_cache_buster: audio_waveform_cache_buster,
+                            waveform_peaks_per_second: project.read().settings.waveform_peaks_per_second,
+                            resample_quality: project.read().settings.preview_resample_quality,
+                            output_meter: output_meter,
                             // Timeline state
                             current_time: current_time(),
                             duration: duration,
@@ -1988,6 +3368,10 @@ pub fn App() -> Element {
                             .1,
                             is_playing: is_playing(),
                             scroll_offset: scroll_offset(),
+                            viewport_width: timeline_viewport_width(),
+                            follow_playhead: follow_playhead(),
+                            magnetic_timeline: project.read().settings.magnetic_timeline,
+                            time_format: project.read().settings.time_format,
                             // Callbacks
                             on_seek: {
                                 let audio_engine = audio_engine.clone();
@@ -2036,6 +3420,17 @@ pub fn App() -> Element {
                                 }
                             },
                             on_scroll: move |offset: f64| scroll_offset.set(offset),
+                            on_toggle_follow: move |_| follow_playhead.toggle(),
+                            on_toggle_magnetic: move |_| {
+                                let mut project = project.write();
+                                project.settings.magnetic_timeline = !project.settings.magnetic_timeline;
+                                project.mark_edited();
+                            },
+                            on_time_format_change: move |format| {
+                                let mut project_write = project.write();
+                                project_write.settings.time_format = format;
+                                project_write.mark_edited();
+                            },
                             on_seek_start: {
                                 let audio_engine = audio_engine.clone();
                                 let audio_sample_cache = audio_sample_cache.clone();
@@ -2078,10 +3473,7 @@ pub fn App() -> Element {
                                                 targets.retain(|(id, _)| {
                                                     missing_set.contains(id)
                                                 });
-                                                let decode_config = AudioDecodeConfig {
-                                                    target_rate: engine.sample_rate(),
-                                                    target_channels: engine.channels(),
-                                                };
+                                                let decode_config = playback_decode_config(&engine, &project_snapshot);
                                                 schedule_audio_decode_targets(
                                                     targets,
                                                     decode_config,
@@ -2109,10 +3501,12 @@ pub fn App() -> Element {
                             is_seeking: dragging() == Some("playhead"),
                             // Track management
                             on_add_video_track: move |_| {
+                                history.write().push(&project.read());
                                 project.write().add_video_track();
                                 preview_dirty.set(true);
                             },
                             on_add_audio_track: move |_| {
+                                history.write().push(&project.read());
                                 project.write().add_audio_track();
                                 preview_dirty.set(true);
                             },
@@ -2124,58 +3518,164 @@ pub fn App() -> Element {
                                 selection.write().select_track(track_id);
                                 timeline_focused.set(true);
                             },
+                            on_track_toggle_collapse: move |track_id| {
+                                project.write().toggle_track_collapsed(track_id);
+                            },
+                            on_track_reorder: move |(track_id, index): (uuid::Uuid, usize)| {
+                                let snapshot = project.read().clone();
+                                if project.write().reorder_track(track_id, index) {
+                                    history.write().push(&snapshot);
+                                    preview_dirty.set(true);
+                                }
+                            },
                             // Clip operations
                             on_clip_delete: move |clip_id| {
-                                project.write().remove_clip(clip_id);
-                                selection.write().remove_clip(clip_id);
+                                history.write().push(&project.read());
+                                let removed = {
+                                    let mut project = project.write();
+                                    let track_ids = project.clip_track_ids(&project.linked_clip_ids(clip_id));
+                                    let removed = project.remove_linked_clips(clip_id);
+                                    project.arrange_tracks(&track_ids, &[]);
+                                    removed
+                                };
+                                for removed_id in removed {
+                                    selection.write().remove_clip(removed_id);
+                                }
                                 preview_dirty.set(true);
                             },
                             on_clip_move: move |(clip_id, new_start)| {
-                                project.write().move_clip(clip_id, new_start);
+                                history
+                                    .write()
+                                    .push_coalesced(&project.read(), Some(format!("move:{}", clip_id)));
+                                {
+                                    let mut project = project.write();
+                                    if project.move_clip_with_partners(clip_id, new_start) {
+                                        let moved = project.linked_clip_ids(clip_id);
+                                        let track_ids = project.clip_track_ids(&moved);
+                                        project.arrange_tracks(&track_ids, &moved);
+                                    }
+                                }
                                 preview_dirty.set(true);
                             },
                             on_clip_resize: move |(clip_id, new_start, new_duration)| {
-                                project.write().resize_clip(clip_id, new_start, new_duration);
+                                history
+                                    .write()
+                                    .push_coalesced(&project.read(), Some(format!("resize:{}", clip_id)));
+                                {
+                                    let mut project = project.write();
+                                    if project.resize_linked_clips(clip_id, new_start, new_duration) {
+                                        let resized = project.linked_clip_ids(clip_id);
+                                        let track_ids = project.clip_track_ids(&resized);
+                                        project.arrange_tracks(&track_ids, &resized);
+                                    }
+                                }
                                 preview_dirty.set(true);
                             },
                             on_clip_move_track: move |(clip_id, direction)| {
-                                if project.write().move_clip_to_adjacent_track(clip_id, direction) {
+                                history.write().push(&project.read());
+                                let moved = {
+                                    let mut project = project.write();
+                                    let mut track_ids = project.clip_track_ids(&[clip_id]);
+                                    let moved = project.move_clip_to_adjacent_track(clip_id, direction);
+                                    if moved {
+                                        track_ids.extend(project.clip_track_ids(&[clip_id]));
+                                        project.arrange_tracks(&track_ids, &[clip_id]);
+                                    }
+                                    moved
+                                };
+                                if moved {
+                                    preview_dirty.set(true);
+                                }
+                            },
+                            on_clip_link: move |clip_id: uuid::Uuid| {
+                                let mut ids = selection.read().clip_ids.clone();
+                                if !ids.contains(&clip_id) {
+                                    ids.push(clip_id);
+                                }
+                                let snapshot = project.read().clone();
+                                if project.write().link_clips(&ids).is_some() {
+                                    history.write().push(&snapshot);
+                                }
+                            },
+                            on_clip_unlink: move |clip_id| {
+                                let snapshot = project.read().clone();
+                                if project.write().unlink_clip(clip_id) {
+                                    history.write().push(&snapshot);
+                                }
+                            },
+                            on_group_selection: move |_| {
+                                let clip_ids = selection.read().clip_ids.clone();
+                                let snapshot = project.read().clone();
+                                if project.write().group_clips(&clip_ids).is_some() {
+                                    history.write().push(&snapshot);
+                                }
+                            },
+                            on_clip_ungroup: move |clip_id| {
+                                let snapshot = project.read().clone();
+                                if project.write().ungroup_clips(&[clip_id]) {
+                                    history.write().push(&snapshot);
+                                }
+                            },
+                            on_clip_set_color: move |(clip_id, color)| {
+                                // Coloring a selected clip colors the whole selection
+                                let mut clip_ids = selection.read().clip_ids.clone();
+                                if !clip_ids.contains(&clip_id) {
+                                    clip_ids = vec![clip_id];
+                                }
+                                let snapshot = project.read().clone();
+                                if project.write().set_clip_color(&clip_ids, color) {
+                                    history.write().push(&snapshot);
+                                }
+                            },
+                            on_clip_freeze_from: move |(clip_id, time)| {
+                                history.write().push(&project.read());
+                                let frozen_id = project.write().freeze_clip_from(clip_id, time);
+                                if let Some(frozen_id) = frozen_id {
+                                    selection.write().select_clip(frozen_id);
+                                    preview_dirty.set(true);
+                                }
+                            },
+                            on_clip_fade: move |(clip_id, fade_in, fade_out): (uuid::Uuid, f64, f64)| {
+                                history
+                                    .write()
+                                    .push_coalesced(&project.read(), Some(format!("fade:{}", clip_id)));
+                                if project.write().set_clip_fades(clip_id, fade_in, fade_out) {
                                     preview_dirty.set(true);
                                 }
                             },
                             selected_clips: selection.read().clip_ids.clone(),
                             on_clip_select: move |clip_id| {
-                                selection.write().select_clip(clip_id);
+                                let clip_ids = project.read().clip_selection_for(clip_id);
+                                selection.write().select_clips(clip_ids);
                                 timeline_focused.set(true);
                             },
                             on_marker_add: move |time: f64| {
-                                let snapped = snap_time_to_frame(time, timeline_fps).clamp(0.0, duration);
-                                let target_frame = frames_from_seconds(snapped, timeline_fps).round();
-                                let exists = project
-                                    .read()
-                                    .markers
-                                    .iter()
-                                    .any(|marker| {
-                                        frames_from_seconds(marker.time, timeline_fps).round()
-                                            == target_frame
-                                    });
-                                if !exists {
-                                    let marker = crate::state::Marker::new(snapped);
-                                    let id = project.write().add_marker(marker);
-                                    selection.write().select_marker(id);
+                                if add_marker_at_time(project, history, selection, time.min(duration)) {
                                     timeline_focused.set(true);
                                 }
                             },
                             on_marker_move: move |(marker_id, time)| {
                                 let snapped = snap_time_to_frame(time, timeline_fps).clamp(0.0, duration);
+                                history
+                                    .write()
+                                    .push_coalesced(&project.read(), Some(format!("marker:{}", marker_id)));
                                 project.write().move_marker(marker_id, snapped);
                                 timeline_focused.set(true);
                             },
                             on_marker_delete: move |marker_id| {
+                                history.write().push(&project.read());
                                 project.write().remove_marker(marker_id);
                                 selection.write().remove_marker(marker_id);
                                 timeline_focused.set(true);
                             },
+                            on_marker_rename: move |(marker_id, label)| {
+                                history.write().push(&project.read());
+                                project.write().set_marker_label(marker_id, label);
+                            },
+                            on_marker_recolor: move |(marker_id, color)| {
+                                history.write().push(&project.read());
+                                project.write().set_marker_color(marker_id, color);
+                            },
                             selected_markers: selection.read().marker_ids.clone(),
                             on_marker_select: move |marker_id| {
                                 selection.write().select_marker(marker_id);
@@ -2185,74 +3685,128 @@ pub fn App() -> Element {
                             // Asset Drag & Drop
                             dragged_asset: dragged_asset(),
                             on_asset_drop: {
-                                let audio_engine = audio_engine.clone();
-                                let audio_sample_cache = audio_sample_cache.clone();
-                                let audio_decode_in_flight = audio_decode_in_flight.clone();
+                                let warm_placed_clip_audio = warm_placed_clip_audio.clone();
                                 move |(track_id, time, asset_id)| {
+                                let placeholder = project.read().placeholder_at(track_id, time);
+                                if let Some(placeholder_id) = placeholder {
+                                    history.write().push(&project.read());
+                                    {
+                                        let mut project = project.write();
+                                        if project.fill_placeholder(placeholder_id, asset_id) {
+                                            project.arrange_tracks(&[track_id], &[placeholder_id]);
+                                        }
+                                    }
+                                    preview_dirty.set(true);
+                                    warm_placed_clip_audio(asset_id);
+                                    return;
+                                }
                                 let duration = resolve_asset_duration_seconds(project, asset_id)
                                     .unwrap_or(DEFAULT_CLIP_DURATION_SECONDS);
                                 let clip = crate::state::Clip::new(asset_id, track_id, time, duration);
-                                project.write().add_clip(clip);
+                                history.write().push(&project.read());
+                                {
+                                    let mut project = project.write();
+                                    let clip_id = project.add_clip(clip);
+                                    project.arrange_tracks(&[track_id], &[clip_id]);
+                                }
                                 preview_dirty.set(true);
-                                if let Some(asset) = project.read().find_asset(asset_id).cloned() {
-                                    if asset.is_audio() || asset.is_video() {
-                                        if let Some(project_root) = project.read().project_path.clone() {
-                                            if let Some(source_path) = resolve_audio_or_video_source(&project_root, &asset) {
-                                                if let Some(engine) = audio_engine.as_ref() {
-                                                    let project_snapshot = project.read().clone();
-                                                    let decode_config = AudioDecodeConfig {
-                                                        target_rate: engine.sample_rate(),
-                                                        target_channels: engine.channels(),
-                                                    };
-                                                    schedule_audio_decode_targets(
-                                                        vec![(asset.id, source_path.clone())],
-                                                        decode_config,
-                                                        Arc::clone(&audio_sample_cache),
-                                                        Arc::clone(&audio_decode_in_flight),
-                                                        project_snapshot,
-                                                        project_root.clone(),
-                                                        Arc::clone(engine),
-                                                    );
-                                                }
-                                                if asset.is_audio() {
-                                                    let mut audio_waveform_cache_buster = audio_waveform_cache_buster.clone();
-                                                    spawn(async move {
-                                                        let needs_build = tokio::task::spawn_blocking({
-                                                            let cache_path = peak_cache_path(&project_root, asset_id);
-                                                            let source_path = source_path.clone();
-                                                            move || {
-                                                                if !cache_path.exists() {
-                                                                    return Ok::<bool, String>(true);
-                                                                }
-                                                                let cache = load_peak_cache(&cache_path)?;
-                                                                Ok(!cache_matches_source(&cache, &source_path)?)
-                                                            }
-                                                        })
-                                                        .await
-                                                        .ok()
-                                                        .unwrap_or(Ok(true))
-                                                        .unwrap_or(true);
-
-                                                        if needs_build {
-                                                            let _ = tokio::task::spawn_blocking(move || {
-                                                                build_and_store_peak_cache(
-                                                                    &project_root,
-                                                                    asset.id,
-                                                                    &source_path,
-                                                                    PeakBuildConfig::default(),
-                                                                )
-                                                            })
-                                                            .await;
-                                                            audio_waveform_cache_buster
-                                                                .set(audio_waveform_cache_buster() + 1);
-                                                        }
-                                                    });
-                                                }
+                                warm_placed_clip_audio(asset_id);
+                            }
+                            },
+                            on_files_drop: {
+                                let warm_placed_clip_audio = warm_placed_clip_audio.clone();
+                                move |(track_id, time, paths): (uuid::Uuid, f64, Vec<std::path::PathBuf>)| {
+                                    let Some(track_type) = project.read().find_track(track_id).map(|track| track.track_type) else {
+                                        return;
+                                    };
+                                    if track_type == TrackType::Marker {
+                                        flash_status_message(status_message, "Media can't be dropped on a marker track".to_string());
+                                        return;
+                                    }
+                                    let mut rejected = Vec::new();
+                                    let mut mismatched = Vec::new();
+                                    let mut imported = Vec::new();
+                                    for path in paths {
+                                        let name = path
+                                            .file_name()
+                                            .map(|name| name.to_string_lossy().to_string())
+                                            .unwrap_or_else(|| path.display().to_string());
+                                        let Some(kind) = crate::state::ImportKind::from_path(&path) else {
+                                            rejected.push(name);
+                                            continue;
+                                        };
+                                        if !kind.fits_track(track_type) {
+                                            mismatched.push(name);
+                                            continue;
+                                        }
+                                        let import_result = project.write().import_file(&path);
+                                        match import_result {
+                                            Ok(asset_id) => {
+                                                start_imported_asset_jobs(asset_id);
+                                                imported.push(asset_id);
+                                            }
+                                            Err(e) => {
+                                                eprintln!("[IMPORT ERROR] Failed to import {:?}: {}", path, e);
+                                                rejected.push(name);
                                             }
                                         }
                                     }
+                                    let mut skipped = Vec::new();
+                                    if !rejected.is_empty() {
+                                        skipped.push(format!("unsupported file(s): {}", rejected.join(", ")));
+                                    }
+                                    if !mismatched.is_empty() {
+                                        let track_label = if track_type == TrackType::Audio { "an audio" } else { "a video" };
+                                        skipped.push(format!("not for {} track: {}", track_label, mismatched.join(", ")));
+                                    }
+                                    if !skipped.is_empty() {
+                                        flash_status_message(status_message, format!("Skipped {}", skipped.join("; ")));
+                                    }
+                                    if imported.is_empty() {
+                                        return;
+                                    }
+                                    preview_dirty.set(true);
+
+                                    let warm_placed_clip_audio = warm_placed_clip_audio.clone();
+                                    spawn(async move {
+                                        // Probe durations off the UI thread, then lay the clips end to end
+                                        let project_root = project.read().project_path.clone();
+                                        let mut durations = Vec::with_capacity(imported.len());
+                                        for asset_id in imported.iter().copied() {
+                                            let source = project.read().find_asset(asset_id).and_then(|asset| {
+                                                let root = project_root.as_ref()?;
+                                                resolve_audio_or_video_source(root, asset)
+                                            });
+                                            let probed = match source {
+                                                Some(path) => tokio::task::spawn_blocking(move || probe_duration_seconds(&path))
+                                                    .await
+                                                    .ok()
+                                                    .flatten(),
+                                                None => None,
+                                            };
+                                            if let Some(duration) = probed {
+                                                project.write().set_asset_duration(asset_id, Some(duration));
+                                            }
+                                            durations.push(probed.unwrap_or(DEFAULT_CLIP_DURATION_SECONDS));
+                                        }
+
+                                        let fps = project.read().settings.fps;
+                                        let starts = sequential_clip_starts(time, &durations, fps);
+                                        history.write().push(&project.read());
+                                        let mut clip_ids = Vec::with_capacity(imported.len());
+                                        for ((asset_id, start), duration) in imported.iter().zip(starts).zip(durations) {
+                                            let clip = crate::state::Clip::new(*asset_id, track_id, start, duration);
+                                            clip_ids.push(project.write().add_clip(clip));
+                                        }
+                                        project.write().arrange_tracks(&[track_id], &clip_ids);
+                                        selection.write().select_clips(clip_ids);
+                                        timeline_focused.set(true);
+                                        preview_dirty.set(true);
+                                        for asset_id in imported {
+                                            warm_placed_clip_audio(asset_id);
+                                        }
+                                    });
                                 }
-                            }
                             },
                             // Selection
                             on_deselect_all: move |_| {
@@ -2285,8 +3839,10 @@ pub fn App() -> Element {
                             key: "{attributes_key}",
                             project: project,
                             selection: selection,
+                            history: history,
                             preview_dirty: preview_dirty,
                             providers: provider_entries,
+                            gen_status: generation_status,
                             on_audio_items_refresh: {
                                 let audio_engine = audio_engine.clone();
                                 let audio_sample_cache = audio_sample_cache.clone();
@@ -2316,10 +3872,7 @@ pub fn App() -> Element {
                                                     &project_root,
                                                 );
                                                 targets.retain(|(id, _)| missing_set.contains(id));
-                                                let decode_config = AudioDecodeConfig {
-                                                    target_rate: engine.sample_rate(),
-                                                    target_channels: engine.channels(),
-                                                };
+                                                let decode_config = playback_decode_config(&engine, &project_snapshot);
                                                 schedule_audio_decode_targets(
                                                     targets,
                                                     decode_config,
@@ -2342,13 +3895,36 @@ pub fn App() -> Element {
                 }
             }
 
-            StatusBar {}
+            StatusBar {
+                preview_note: preview_backend_note(
+                    project.read().settings.force_cpu_preview,
+                    &gpu_availability(),
+                ),
+                mix_loudness: mix_loudness(),
+                status_message: status_message(),
+                overlap_count: project.read().clip_overlaps().len(),
+                on_resolve_overlaps: move |resolution| {
+                    let snapshot = project.read().clone();
+                    if project.write().resolve_clip_overlaps(resolution) {
+                        history.write().push(&snapshot);
+                        preview_dirty.set(true);
+                    }
+                },
+                current_time: current_time,
+                generation_queue: generation_queue,
+                previewer: previewer,
+                fps: project.read().settings.fps,
+                time_format: project.read().settings.time_format,
+                duration: project.read().duration(),
+            }
             
             TrackContextMenu {
                 context_menu: context_menu,
                 project: project,
                 selection: selection,
                 preview_dirty: preview_dirty,
+                current_time: current_time,
+                history: history,
             }
 
             GenerationQueuePanel {
@@ -2360,6 +3936,7 @@ pub fn App() -> Element {
                 paused: generation_paused(),
                 pause_reason: generation_pause_reason(),
                 on_resume: on_resume_generation_queue,
+                max_concurrent: project.read().settings.generation_max_concurrent.max(1) as usize,
             }
 
             // Startup Modal (Blocks everything until Project is created/loaded)
@@ -2376,21 +3953,22 @@ pub fn App() -> Element {
                         move |(parent_dir, name, settings): (std::path::PathBuf, String, crate::state::ProjectSettings)| {
                         // Create full path: parent_dir/name
                         let project_dir = parent_dir.join(&name);
-                        let preview_limits = (settings.preview_max_width, settings.preview_max_height);
                         match crate::state::Project::create_in_with_settings(&project_dir, &name, settings) {
                             Ok(new_proj) => {
+                                record_recent_project(&project_dir);
                                 // Initialize thumbnailer with new project path
                                 thumbnailer.set(std::sync::Arc::new(crate::core::thumbnailer::Thumbnailer::new(new_proj.project_path.clone().unwrap())));
-                                previewer.set(std::sync::Arc::new(
-                                    crate::core::preview::PreviewRenderer::new_with_limits(
-                                        new_proj.project_path.clone().unwrap(),
-                                        PREVIEW_CACHE_BUDGET_BYTES,
-                                        preview_limits.0,
-                                        preview_limits.1,
-                                    ),
+                                previewer.set(std::sync::Arc::new(preview_renderer_for_bounds(
+                                    new_proj.project_path.clone().unwrap(),
+                                    &new_proj.settings,
+                                    *preview_native_bounds.peek(),
+                                    *show_checkerboard.peek(),
+                                )));
+                                provider_entries.set(load_merged_provider_entries_or_empty(
+                                    new_proj.project_path.as_deref(),
                                 ));
-                                provider_entries.set(load_global_provider_entries_or_empty());
                                 project.set(new_proj);
+                                history.write().clear();
                                 preview_dirty.set(true);
                                 audio_waveform_cache_buster.set(audio_waveform_cache_buster() + 1);
                                 if let Some(engine) = audio_engine.as_ref() {
@@ -2403,10 +3981,7 @@ pub fn App() -> Element {
                                             &project_root,
                                         );
                                         if !targets.is_empty() {
-                                            let decode_config = AudioDecodeConfig {
-                                                target_rate: engine.sample_rate(),
-                                                target_channels: engine.channels(),
-                                            };
+                                            let decode_config = playback_decode_config(&engine, &project_snapshot);
                                             schedule_audio_decode_targets(
                                                 targets,
                                                 decode_config,
@@ -2419,7 +3994,9 @@ pub fn App() -> Element {
                                         }
                                     }
                                 }
-                                spawn_missing_duration_probes(project);
+                                if let Some(handle) = media_index_handle.take() {
+                                    handle.cancel();
+                                }
                                 startup_done.set(true);
                             },
                             Err(e) => println!("Error creating project: {}", e),
@@ -2431,57 +4008,107 @@ pub fn App() -> Element {
                         let audio_sample_cache = audio_sample_cache.clone();
                         let audio_decode_in_flight = audio_decode_in_flight.clone();
                         move |path: std::path::PathBuf| {
-                         match crate::state::Project::load(&path) { // path is the project folder
-                            Ok(loaded_proj) => {
-                                // Initialize thumbnailer with loaded project path
-                                thumbnailer.set(std::sync::Arc::new(crate::core::thumbnailer::Thumbnailer::new(loaded_proj.project_path.clone().unwrap())));
-                                let preview_limits = (
-                                    loaded_proj.settings.preview_max_width,
-                                    loaded_proj.settings.preview_max_height,
+                        let load_id = project_load_id() + 1;
+                        project_load_id.set(load_id);
+                        project_loading.set(Some(path.clone()));
+                        let audio_engine = audio_engine.clone();
+                        let audio_sample_cache = audio_sample_cache.clone();
+                        let audio_decode_in_flight = audio_decode_in_flight.clone();
+                        spawn(async move {
+                            // path is the project folder; parse project.json off the UI thread
+                            let loaded = tokio::task::spawn_blocking(move || {
+                                crate::state::Project::load_manifest(&path)
+                            })
+                            .await
+                            .map_err(|err| err.to_string())
+                            .and_then(|result| result.map_err(|err| err.to_string()));
+                            if project_load_id() != load_id {
+                                // Cancelled from the loading overlay.
+                                return;
+                            }
+                            project_loading.set(None);
+                            let loaded_proj = match loaded {
+                                Ok(loaded_proj) => loaded_proj,
+                                Err(e) => {
+                                    println!("Error loading project: {}", e);
+                                    return;
+                                }
+                            };
+
+                            if let Some(project_root) = loaded_proj.project_path.as_deref() {
+                                record_recent_project(project_root);
+                            }
+
+                            // Initialize thumbnailer with loaded project path
+                            thumbnailer.set(std::sync::Arc::new(crate::core::thumbnailer::Thumbnailer::new(loaded_proj.project_path.clone().unwrap())));
+                            previewer.set(std::sync::Arc::new(preview_renderer_for_bounds(
+                                loaded_proj.project_path.clone().unwrap(),
+                                &loaded_proj.settings,
+                                *preview_native_bounds.peek(),
+                                *show_checkerboard.peek(),
+                            )));
+                            provider_entries.set(load_merged_provider_entries_or_empty(
+                                loaded_proj.project_path.as_deref(),
+                            ));
+                            project.set(loaded_proj);
+                            history.write().clear();
+                            preview_dirty.set(true);
+                            audio_waveform_cache_buster.set(audio_waveform_cache_buster() + 1);
+                            startup_done.set(true);
+                            if !project.read().missing_asset_ids().is_empty() {
+                                show_relink_dialog.set(true);
+                            }
+
+                            // The timeline is up; everything below streams in behind it.
+                            if let Some(handle) = media_index_handle.take() {
+                                handle.cancel();
+                            }
+                            let handle = CancelHandle::default();
+                            media_index_handle.set(Some(handle.clone()));
+
+                            let Some(project_root) = project.read().project_path.clone() else {
+                                return;
+                            };
+                            let assets = project.read().assets.clone();
+                            let configs_root = project_root.clone();
+                            let configs = tokio::task::spawn_blocking(move || {
+                                crate::state::read_generative_configs(&configs_root, &assets)
+                            })
+                            .await
+                            .unwrap_or_default();
+                            if handle.is_cancelled() {
+                                return;
+                            }
+                            project.write().apply_generative_configs(configs);
+                            preview_dirty.set(true);
+
+                            // Cancelling only stops indexing; the audio still needs decoding for playback
+                            index_project_media(project, handle.clone(), media_index_remaining).await;
+                            if let Some(engine) = audio_engine.as_ref() {
+                                let project_snapshot = project.read().clone();
+                                let project_root = project_snapshot.project_path.clone().unwrap_or(project_root);
+                                let targets = audio_decode_targets_for_project(
+                                    &project_snapshot,
+                                    &project_root,
                                 );
-                                previewer.set(std::sync::Arc::new(
-                                    crate::core::preview::PreviewRenderer::new_with_limits(
-                                        loaded_proj.project_path.clone().unwrap(),
-                                        PREVIEW_CACHE_BUDGET_BYTES,
-                                        preview_limits.0,
-                                        preview_limits.1,
-                                    ),
-                                ));
-                                provider_entries.set(load_global_provider_entries_or_empty());
-                                project.set(loaded_proj);
-                                preview_dirty.set(true);
-                                audio_waveform_cache_buster.set(audio_waveform_cache_buster() + 1);
-                                if let Some(engine) = audio_engine.as_ref() {
-                                    let project_snapshot = project.read().clone();
-                                    if let Some(project_root) =
-                                        project_snapshot.project_path.clone()
-                                    {
-                                        let targets = audio_decode_targets_for_project(
-                                            &project_snapshot,
-                                            &project_root,
-                                        );
-                                        if !targets.is_empty() {
-                                            let decode_config = AudioDecodeConfig {
-                                                target_rate: engine.sample_rate(),
-                                                target_channels: engine.channels(),
-                                            };
-                                            schedule_audio_decode_targets(
-                                                targets,
-                                                decode_config,
-                                                Arc::clone(&audio_sample_cache),
-                                                Arc::clone(&audio_decode_in_flight),
-                                                project_snapshot,
-                                                project_root,
-                                                Arc::clone(engine),
-                                            );
-                                        }
-                                    }
+                                if !targets.is_empty() {
+                                    let decode_config = playback_decode_config(&engine, &project_snapshot);
+                                    schedule_audio_decode_targets(
+                                        targets,
+                                        decode_config,
+                                        Arc::clone(&audio_sample_cache),
+                                        Arc::clone(&audio_decode_in_flight),
+                                        project_snapshot,
+                                        project_root,
+                                        Arc::clone(engine),
+                                    );
                                 }
-                                spawn_missing_duration_probes(project);
-                                startup_done.set(true);
-                            },
-                            Err(e) => println!("Error loading project: {}", e),
-                        }
+                            }
+                            if handle.is_cancelled() {
+                                return;
+                            }
+                            media_index_handle.set(None);
+                        });
                     }
                     },
                     on_update: move |_| {},
@@ -2489,6 +4116,107 @@ pub fn App() -> Element {
                 }
             }
 
+            // Project load overlay: shown while project.json is read, cancel abandons the open
+            if let Some(loading_path) = project_loading() {
+                {
+                    let loading_name = loading_path
+                        .file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                        .unwrap_or_else(|| loading_path.display().to_string());
+                    rsx! {
+                        div {
+                            style: "
+                                position: fixed; top: 0; left: 0; right: 0; bottom: 0;
+                                background-color: rgba(0, 0, 0, 0.6); z-index: 10000;
+                                display: flex; align-items: center; justify-content: center;
+                            ",
+                            div {
+                                style: "
+                                    min-width: 280px; padding: 18px 20px;
+                                    background-color: {BG_ELEVATED}; border: 1px solid {BORDER_DEFAULT};
+                                    border-radius: 10px; box-shadow: 0 20px 50px rgba(0,0,0,0.6);
+                                    display: flex; flex-direction: column; gap: 10px; align-items: center;
+                                ",
+                                span { style: "font-size: 13px; font-weight: 600; color: {TEXT_PRIMARY};", "Opening project..." }
+                                span { style: "font-size: 11px; color: {TEXT_DIM};", "{loading_name}" }
+                                button {
+                                    class: "collapse-btn",
+                                    style: "
+                                        padding: 6px 14px; font-size: 12px;
+                                        background-color: {BG_SURFACE}; border: 1px solid {BORDER_DEFAULT};
+                                        border-radius: 6px; color: {TEXT_SECONDARY}; cursor: pointer;
+                                    ",
+                                    onclick: move |_| {
+                                        project_load_id.set(project_load_id() + 1);
+                                        project_loading.set(None);
+                                    },
+                                    "Cancel"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            // Save As / bundle export progress; large generated folders can take a while
+            if let Some((job_title, job_name, fraction)) = folder_job_progress() {
+                {
+                    let percent = (fraction * 100.0).clamp(0.0, 100.0);
+                    rsx! {
+                        div {
+                            style: "
+                                position: fixed; top: 0; left: 0; right: 0; bottom: 0;
+                                background-color: rgba(0, 0, 0, 0.6); z-index: 10000;
+                                display: flex; align-items: center; justify-content: center;
+                            ",
+                            div {
+                                style: "
+                                    min-width: 280px; padding: 18px 20px;
+                                    background-color: {BG_ELEVATED}; border: 1px solid {BORDER_DEFAULT};
+                                    border-radius: 10px; box-shadow: 0 20px 50px rgba(0,0,0,0.6);
+                                    display: flex; flex-direction: column; gap: 10px; align-items: center;
+                                ",
+                                span { style: "font-size: 13px; font-weight: 600; color: {TEXT_PRIMARY};", "{job_title}" }
+                                span { style: "font-size: 11px; color: {TEXT_DIM};", "{job_name}" }
+                                div {
+                                    style: "width: 100%; height: 4px; border-radius: 2px; background-color: {BG_SURFACE}; overflow: hidden;",
+                                    div { style: "width: {percent}%; height: 100%; background-color: {ACCENT_AUDIO};" }
+                                }
+                                span { style: "font-size: 11px; color: {TEXT_MUTED};", "{percent:.0}%" }
+                            }
+                        }
+                    }
+                }
+            }
+
+            // Background media indexing after a load; the project stays usable meanwhile
+            if media_index_handle.read().is_some() && media_index_remaining() > 0 {
+                div {
+                    style: "
+                        position: fixed; left: 12px; bottom: 12px; z-index: 2500;
+                        display: flex; align-items: center; gap: 8px;
+                        padding: 6px 10px; font-size: 11px; color: {TEXT_SECONDARY};
+                        background-color: {BG_ELEVATED}; border: 1px solid {BORDER_DEFAULT};
+                        border-radius: 6px; box-shadow: 0 4px 12px rgba(0,0,0,0.3);
+                    ",
+                    "Indexing media · {media_index_remaining()} left"
+                    button {
+                        class: "collapse-btn",
+                        style: "
+                            padding: 2px 8px; font-size: 11px;
+                            background: transparent; border: 1px solid {BORDER_DEFAULT};
+                            border-radius: 4px; color: {TEXT_SECONDARY}; cursor: pointer;
+                        ",
+                        onclick: move |_| {
+                            if let Some(handle) = media_index_handle.take() {
+                                handle.cancel();
+                            }
+                        },
+                        "Cancel"
+                    }
+                }
+            }
+
             if show_project_settings_dialog() {
                 StartupModal {
                     mode: StartupModalMode::Edit,
@@ -2498,24 +4226,29 @@ pub fn App() -> Element {
                     on_create: move |_| {},
                     on_open: move |_| {},
                     on_update: move |settings: crate::state::ProjectSettings| {
-                        let preview_limits = (settings.preview_max_width, settings.preview_max_height);
+                        let renderer_settings = settings.clone();
                         let project_path = project.read().project_path.clone();
+                        let peaks_changed = project.read().settings.waveform_peaks_per_second
+                            != settings.waveform_peaks_per_second;
                         {
                             let mut project_mut = project.write();
                             project_mut.settings = settings;
+                            project_mut.mark_edited();
+                        }
+                        if peaks_changed {
+                            // Clips reload their peaks and rebuild any cache at the old resolution.
+                            audio_waveform_cache_buster.set(audio_waveform_cache_buster() + 1);
                         }
                         if let Some(path) = project_path {
-                            previewer.set(std::sync::Arc::new(
-                                crate::core::preview::PreviewRenderer::new_with_limits(
-                                    path,
-                                    PREVIEW_CACHE_BUDGET_BYTES,
-                                    preview_limits.0,
-                                    preview_limits.1,
-                                ),
-                            ));
+                            previewer.set(std::sync::Arc::new(preview_renderer_for_bounds(
+                                path,
+                                &renderer_settings,
+                                *preview_native_bounds.peek(),
+                                *show_checkerboard.peek(),
+                            )));
                         }
                         preview_dirty.set(true);
-                        let _ = project.read().save();
+                        let _ = save_project(project, save_tick);
                     },
                     on_close: move |_| {
                         show_project_settings_dialog.set(false);
@@ -2523,10 +4256,84 @@ pub fn App() -> Element {
                 }
             }
 
+            if let Some(status) = export_status() {
+                ExportProgressModal {
+                    status: status,
+                    on_cancel: move |_| {
+                        // The job removes its partial file and reports Cancelled.
+                        if let Some(handle) = export_handle.read().as_ref() {
+                            handle.cancel();
+                        }
+                    },
+                    on_open_file: move |path: std::path::PathBuf| {
+                        if let Err(err) = crate::core::paths::open_with_default_app(&path) {
+                            println!("Failed to open {}: {}", path.display(), err);
+                        }
+                    },
+                    on_reveal_file: move |path: std::path::PathBuf| {
+                        if let Err(err) = crate::core::paths::reveal_in_file_browser(&path) {
+                            println!("Failed to reveal {}: {}", path.display(), err);
+                        }
+                    },
+                    on_close: move |_| {
+                        export_status.set(None);
+                        export_handle.set(None);
+                    },
+                }
+            }
+
+            if show_relink_dialog() {
+                RelinkModal {
+                    assets: project
+                        .read()
+                        .assets
+                        .iter()
+                        .filter(|asset| asset.is_missing())
+                        .cloned()
+                        .collect::<Vec<_>>(),
+                    on_locate: move |asset_id| relink_from_dialog(asset_id),
+                    on_search_folder: move |_| {
+                        let Some(folder) = rfd::FileDialog::new()
+                            .set_title("Search Folder for Missing Media")
+                            .pick_folder()
+                        else {
+                            return;
+                        };
+                        let missing = project.read().missing_asset_ids().len();
+                        let relinked = project.write().relink_missing_from_folder(&folder);
+                        for asset_id in relinked.iter().copied() {
+                            finish_relink(asset_id);
+                        }
+                        flash_status_message(
+                            status_message,
+                            format!("Relinked {} of {} missing files", relinked.len(), missing),
+                        );
+                    },
+                    on_close: move |_| show_relink_dialog.set(false),
+                }
+            }
+
+            if show_export_settings_dialog() {
+                ExportSettingsModal {
+                    options: project.read().export_options,
+                    on_save: move |options| {
+                        let mut project_write = project.write();
+                        project_write.export_options = options;
+                        project_write.mark_edited();
+                        drop(project_write);
+                        let _ = save_project(project, save_tick);
+                    },
+                    on_close: move |_| {
+                        show_export_settings_dialog.set(false);
+                    },
+                }
+            }
+
             NewProjectModal {
                 show: show_new_project_dialog,
                 on_go_to_wizard: move |_| {
                     project.set(crate::state::Project::default());
+                    history.write().clear();
                     startup_done.set(false);
                     show_new_project_dialog.set(false);
                 }
@@ -2536,16 +4343,26 @@ pub fn App() -> Element {
             ProvidersModalV2 {
                 show: show_providers_v2,
                 provider_files: provider_files_v2,
+                project_root: project.read().project_path.clone(),
                 on_new: move |_| {
                     edit_provider_path.set(None);
                     show_builder_v2.set(true);
                 },
                 on_reload: move |_| {
-                    provider_files_v2.set(list_global_provider_files());
+                    provider_files_v2.set(list_provider_files(project.read().project_path.as_deref()));
                 },
                 on_delete: move |path| {
                     let _ = std::fs::remove_file(&path);
-                    provider_files_v2.set(list_global_provider_files());
+                    provider_files_v2.set(list_provider_files(project.read().project_path.as_deref()));
+                },
+                on_toggle_favorite: move |path: std::path::PathBuf| {
+                    if let Err(err) = toggle_provider_favorite(&path) {
+                        println!("Failed to update provider {:?}: {}", path, err);
+                    }
+                    provider_files_v2.set(list_provider_files(project.read().project_path.as_deref()));
+                    provider_entries.set(load_merged_provider_entries_or_empty(
+                        project.read().project_path.as_deref(),
+                    ));
                 },
                 on_edit_builder: move |path| {
                     edit_provider_path.set(Some(path));
@@ -2555,6 +4372,43 @@ pub fn App() -> Element {
                     edit_provider_path.set(Some(path));
                     show_json_editor.set(true);
                 },
+                on_export: move |path: std::path::PathBuf| {
+                    let name = read_provider_file(&path)
+                        .and_then(|json| serde_json::from_str::<crate::state::ProviderEntry>(&json).ok())
+                        .map(|entry| entry.name)
+                        .unwrap_or_else(|| "provider".to_string());
+                    let Some(output) = rfd::FileDialog::new()
+                        .set_title("Export Provider")
+                        .set_file_name(format!("{}.{}", name, PROVIDER_PACKAGE_EXTENSION))
+                        .add_filter("NLA provider", &[PROVIDER_PACKAGE_EXTENSION])
+                        .save_file()
+                    else {
+                        return;
+                    };
+                    let project_root = project.read().project_path.clone();
+                    match export_provider_package(&path, project_root.as_deref(), &output) {
+                        Ok(()) => flash_status_message(status_message, format!("Exported provider \"{}\"", name)),
+                        Err(err) => {
+                            eprintln!("[PROVIDER ERROR] Export of {} failed: {}", path.display(), err);
+                            flash_status_message(status_message, format!("Provider export failed: {}", err));
+                        }
+                    }
+                },
+                on_import: move |(package, keep_id): (ProviderPackage, bool)| {
+                    match import_provider_package(&package, keep_id) {
+                        Ok(entry) => {
+                            provider_files_v2.set(list_provider_files(project.read().project_path.as_deref()));
+                            provider_entries.set(load_merged_provider_entries_or_empty(
+                                project.read().project_path.as_deref(),
+                            ));
+                            flash_status_message(status_message, format!("Imported provider \"{}\"", entry.name));
+                        }
+                        Err(err) => {
+                            eprintln!("[PROVIDER ERROR] Import failed: {}", err);
+                            flash_status_message(status_message, format!("Provider import failed: {}", err));
+                        }
+                    }
+                },
             }
 
             ProviderJsonEditorModal {
@@ -2562,18 +4416,23 @@ pub fn App() -> Element {
                 provider_path: edit_provider_path,
                 on_saved: move |_| {
                     show_json_editor.set(false);
-                    provider_files_v2.set(list_global_provider_files());
-                    provider_entries.set(load_global_provider_entries_or_empty());
+                    provider_files_v2.set(list_provider_files(project.read().project_path.as_deref()));
+                    provider_entries.set(load_merged_provider_entries_or_empty(
+                        project.read().project_path.as_deref(),
+                    ));
                 },
             }
 
             ProviderBuilderModalV2 {
                 show: show_builder_v2,
                 provider_path: edit_provider_path,
+                project_root: project.read().project_path.clone(),
                 on_saved: move |_| {
                     show_builder_v2.set(false);
-                    provider_files_v2.set(list_global_provider_files());
-                    provider_entries.set(load_global_provider_entries_or_empty());
+                    provider_files_v2.set(list_provider_files(project.read().project_path.as_deref()));
+                    provider_entries.set(load_merged_provider_entries_or_empty(
+                        project.read().project_path.as_deref(),
+                    ));
                 },
             }
         }
@@ -5,12 +5,16 @@ use crate::state::asset_display_name;
 #[component]
 pub fn AssetItem(
     asset: crate::state::Asset,
+    is_focused: bool,
     thumbnailer: std::sync::Arc<crate::core::thumbnailer::Thumbnailer>,
     thumbnail_cache_buster: u64,
     panel_width: f64,
     on_rename: EventHandler<(uuid::Uuid, String)>,
     on_delete: EventHandler<uuid::Uuid>,
     on_regenerate_thumbnails: EventHandler<uuid::Uuid>,
+    on_transcode: EventHandler<uuid::Uuid>,
+    on_relink: EventHandler<uuid::Uuid>,
+    on_toggle_proxy: EventHandler<uuid::Uuid>,
     on_add_to_timeline: EventHandler<uuid::Uuid>,
     on_drag_start: EventHandler<uuid::Uuid>,
 ) -> Element {
@@ -56,7 +60,9 @@ pub fn AssetItem(
     };
     
     // Generative assets have a subtle dashed border
-    let border_style = if asset.is_generative() {
+    let border_style = if is_focused {
+        format!("1px solid {}", BORDER_ACCENT)
+    } else if asset.is_generative() {
         format!("1px dashed {}", BORDER_DEFAULT)  // Subtle dashed, not accent-colored
     } else {
         format!("1px solid {}", BORDER_SUBTLE)
@@ -64,10 +70,32 @@ pub fn AssetItem(
 
     let asset_id = asset.id;
     let display_name = asset_display_name(&asset);
+    let issue_label = asset.issue.as_ref().map(|issue| issue.label());
+    let issue_color = match &asset.issue {
+        Some(crate::state::AssetIssue::Offline { .. }) | Some(crate::state::AssetIssue::Missing) => "#ef4444",
+        _ => ACCENT_MARKER,
+    };
+    let can_transcode = matches!(asset.kind, crate::state::AssetKind::Video { .. })
+        && matches!(
+            asset.issue,
+            Some(crate::state::AssetIssue::UnsupportedCodec { .. })
+                | Some(crate::state::AssetIssue::Offline { .. })
+        );
+    let is_missing = asset.is_missing();
+    // Visual assets rebuild their thumbnails, audio assets their waveform peaks
+    let regenerate_label = if asset.is_visual() {
+        "🔄 Regenerate Thumbnails"
+    } else {
+        "🔄 Regenerate Waveform"
+    };
+    let can_proxy = matches!(asset.kind, crate::state::AssetKind::Video { .. }) && !is_missing;
+    let proxy_label = if asset.use_proxy { "✓ Use Proxy" } else { "🪶 Use Proxy" };
+    let name_opacity = if asset.is_offline() || is_missing { "0.5" } else { "1" };
     let menu_max_x = (panel_width - 140.0).max(0.0);
     
     rsx! {
         div {
+            id: "asset-item-{asset_id}",
             style: "position: relative;",
             
             div {
@@ -180,19 +208,29 @@ pub fn AssetItem(
                         }
                     }
                 } else {
-                    span { 
-                        style: "flex: 1; min-width: 0; font-size: 12px; color: {TEXT_PRIMARY}; overflow: hidden; text-overflow: ellipsis; white-space: nowrap;",
-                        ondoubleclick: {
-                            let asset_name = asset_name.clone();
-                            let mut draft_name = draft_name.clone();
-                            let mut is_editing = is_editing.clone();
-                            move |e| {
-                                e.stop_propagation();
-                                is_editing.set(true);
-                                draft_name.set(asset_name.clone());
+                    div {
+                        style: "flex: 1; min-width: 0; display: flex; flex-direction: column; opacity: {name_opacity};",
+                        span { 
+                            style: "min-width: 0; font-size: 12px; color: {TEXT_PRIMARY}; overflow: hidden; text-overflow: ellipsis; white-space: nowrap;",
+                            ondoubleclick: {
+                                let asset_name = asset_name.clone();
+                                let mut draft_name = draft_name.clone();
+                                let mut is_editing = is_editing.clone();
+                                move |e| {
+                                    e.stop_propagation();
+                                    is_editing.set(true);
+                                    draft_name.set(asset_name.clone());
+                                }
+                            },
+                            "{display_name}"
+                        }
+                        if let Some(label) = issue_label.clone() {
+                            span {
+                                style: "font-size: 10px; color: {issue_color}; overflow: hidden; text-overflow: ellipsis; white-space: nowrap;",
+                                title: "{label}",
+                                "{label}"
                             }
-                        },
-                        "{display_name}"
+                        }
                     }
                 }
             }
@@ -230,7 +268,7 @@ pub fn AssetItem(
                                 },
                                 "➕ Add to Timeline"
                             }
-                             // Regenerate Thumbnails
+                            // Regenerate Thumbnails
                             div {
                                 style: "
                                     padding: 6px 12px; color: {TEXT_PRIMARY}; cursor: pointer;
@@ -240,7 +278,47 @@ pub fn AssetItem(
                                     on_regenerate_thumbnails.call(asset_id);
                                     show_menu.set(false);
                                 },
-                                "🔄 Refresh Media Cache"
+                                "{regenerate_label}"
+                            }
+                            if can_transcode {
+                                div {
+                                    style: "
+                                        padding: 6px 12px; color: {TEXT_PRIMARY}; cursor: pointer;
+                                        transition: background-color 0.1s ease;
+                                    ",
+                                    onclick: move |_| {
+                                        on_transcode.call(asset_id);
+                                        show_menu.set(false);
+                                    },
+                                    "🎞 Transcode to MP4"
+                                }
+                            }
+                            if can_proxy {
+                                div {
+                                    style: "
+                                        padding: 6px 12px; color: {TEXT_PRIMARY}; cursor: pointer;
+                                        transition: background-color 0.1s ease;
+                                    ",
+                                    title: "Preview from a low-resolution copy while proxies are on",
+                                    onclick: move |_| {
+                                        on_toggle_proxy.call(asset_id);
+                                        show_menu.set(false);
+                                    },
+                                    "{proxy_label}"
+                                }
+                            }
+                            if is_missing {
+                                div {
+                                    style: "
+                                        padding: 6px 12px; color: {TEXT_PRIMARY}; cursor: pointer;
+                                        transition: background-color 0.1s ease;
+                                    ",
+                                    onclick: move |_| {
+                                        on_relink.call(asset_id);
+                                        show_menu.set(false);
+                                    },
+                                    "🔗 Relink File..."
+                                }
                             }
                             // Divider
                             div {
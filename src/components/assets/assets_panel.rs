@@ -2,13 +2,120 @@ use dioxus::prelude::*;
 use crate::components::assets::{AssetItem, GenerativeVideoModal};
 use crate::constants::*;
 use crate::state::{
-    generative_video_duration_seconds, next_generative_index, DEFAULT_GENERATIVE_VIDEO_FPS,
-    DEFAULT_GENERATIVE_VIDEO_FRAME_COUNT,
+    asset_display_name, generative_video_duration_seconds, next_generative_index, sort_assets,
+    Asset, AssetKind, AssetSort, DEFAULT_GENERATIVE_VIDEO_FPS, DEFAULT_GENERATIVE_VIDEO_FRAME_COUNT,
 };
 
+/// Kind filter for the assets list. Video, Image and Audio cover imported
+/// media only; generative assets of every type fall under Generative.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AssetTypeFilter {
+    #[default]
+    All,
+    Video,
+    Image,
+    Audio,
+    Generative,
+}
+
+impl AssetTypeFilter {
+    pub const ALL: [AssetTypeFilter; 5] = [
+        AssetTypeFilter::All,
+        AssetTypeFilter::Video,
+        AssetTypeFilter::Image,
+        AssetTypeFilter::Audio,
+        AssetTypeFilter::Generative,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AssetTypeFilter::All => "All",
+            AssetTypeFilter::Video => "Video",
+            AssetTypeFilter::Image => "Image",
+            AssetTypeFilter::Audio => "Audio",
+            AssetTypeFilter::Generative => "Gen",
+        }
+    }
+
+    fn matches(self, kind: &AssetKind) -> bool {
+        match self {
+            AssetTypeFilter::All => true,
+            AssetTypeFilter::Video => matches!(kind, AssetKind::Video { .. }),
+            AssetTypeFilter::Image => matches!(kind, AssetKind::Image { .. }),
+            AssetTypeFilter::Audio => matches!(kind, AssetKind::Audio { .. }),
+            AssetTypeFilter::Generative => kind.is_generative(),
+        }
+    }
+}
+
+/// Whether `asset` is shown for the search `query` and kind `filter`. The
+/// query is a case-insensitive substring of the displayed name.
+pub fn asset_matches_filter(asset: &Asset, query: &str, filter: AssetTypeFilter) -> bool {
+    if !filter.matches(&asset.kind) {
+        return false;
+    }
+    let query = query.trim().to_lowercase();
+    query.is_empty() || asset_display_name(asset).to_lowercase().contains(&query)
+}
+
+/// The assets the panel lists, in project order.
+pub fn filter_assets<'a>(
+    assets: &'a [Asset],
+    query: &str,
+    filter: AssetTypeFilter,
+) -> Vec<&'a Asset> {
+    assets
+        .iter()
+        .filter(|asset| asset_matches_filter(asset, query, filter))
+        .collect()
+}
+
+/// The assets the panel lists, filtered and then in `sort` order.
+pub fn displayed_assets<'a>(
+    assets: &'a [Asset],
+    query: &str,
+    filter: AssetTypeFilter,
+    sort: AssetSort,
+) -> Vec<&'a Asset> {
+    let mut shown = filter_assets(assets, query, filter);
+    sort_assets(&mut shown, sort);
+    shown
+}
+
+/// A keyboard move of the highlighted asset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssetCursorMove {
+    Previous,
+    Next,
+    First,
+    Last,
+}
+
+/// Index of the asset to highlight after `movement` in a list of `len`
+/// assets, clamping at either end. With nothing highlighted yet, Next starts
+/// at the top and Previous at the bottom.
+pub fn move_asset_cursor(current: Option<usize>, len: usize, movement: AssetCursorMove) -> Option<usize> {
+    let last = len.checked_sub(1)?;
+    let index = match (movement, current) {
+        (AssetCursorMove::First, _) | (AssetCursorMove::Next, None) => 0,
+        (AssetCursorMove::Last, _) | (AssetCursorMove::Previous, None) => last,
+        (AssetCursorMove::Previous, Some(index)) => index.min(last).saturating_sub(1),
+        (AssetCursorMove::Next, Some(index)) => (index + 1).min(last),
+    };
+    Some(index)
+}
+
 #[component]
 pub fn AssetsPanelContent(
     assets: Vec<crate::state::Asset>,
+    // Asset highlighted for keyboard navigation
+    focused_asset: Option<uuid::Uuid>,
+    search: Signal<String>,
+    type_filter: Signal<AssetTypeFilter>,
+    sort: AssetSort,
+    on_sort_change: EventHandler<AssetSort>,
+    // Fired on any click inside the panel, so its keys take over
+    on_focus: EventHandler<()>,
     thumbnailer: std::sync::Arc<crate::core::thumbnailer::Thumbnailer>,
     thumbnail_cache_buster: u64,
     thumbnail_refresh_tick: u64,
@@ -19,11 +126,21 @@ pub fn AssetsPanelContent(
     on_rename: EventHandler<(uuid::Uuid, String)>,
     on_delete: EventHandler<uuid::Uuid>,
     on_regenerate_thumbnails: EventHandler<uuid::Uuid>,
+    on_transcode: EventHandler<uuid::Uuid>,
+    on_relink: EventHandler<uuid::Uuid>,
+    on_toggle_proxy: EventHandler<uuid::Uuid>,
     on_add_to_timeline: EventHandler<uuid::Uuid>,
     on_drag_start: EventHandler<uuid::Uuid>,
 ) -> Element {
     let _ = thumbnail_refresh_tick;
     let mut gen_video_modal_open = gen_video_modal_open;
+    let mut search = search;
+    let mut type_filter = type_filter;
+    let visible_assets: Vec<Asset> = displayed_assets(&assets, &search(), type_filter(), sort)
+        .into_iter()
+        .cloned()
+        .collect();
+    let is_filtered = !search().trim().is_empty() || type_filter() != AssetTypeFilter::All;
     let mut gen_video_fps = use_signal(|| DEFAULT_GENERATIVE_VIDEO_FPS.to_string());
     let mut gen_video_frames = use_signal(|| DEFAULT_GENERATIVE_VIDEO_FRAME_COUNT.to_string());
     let mut gen_video_error = use_signal(|| None::<String>);
@@ -62,6 +179,11 @@ pub fn AssetsPanelContent(
     rsx! {
         div {
             style: "display: flex; flex-direction: column; height: 100%; padding: 8px;",
+            // Handled here rather than letting the app container clear focus
+            onmousedown: move |e| {
+                e.stop_propagation();
+                on_focus.call(());
+            },
             
             // Import button
             button {
@@ -169,6 +291,72 @@ pub fn AssetsPanelContent(
                     }
                 }
             }
+            // Search and kind filters
+            div {
+                style: "display: flex; flex-direction: column; gap: 6px; margin-bottom: 8px;",
+                crate::components::common::StableTextInput {
+                    id: "asset-search-input".to_string(),
+                    value: search(),
+                    placeholder: Some("Search assets...".to_string()),
+                    style: Some(format!("
+                        width: 100%; padding: 6px 8px; font-size: 11px;
+                        background-color: {}; color: {};
+                        border: 1px solid {}; border-radius: 6px;
+                        outline: none;
+                    ", BG_SURFACE, TEXT_PRIMARY, BORDER_DEFAULT)),
+                    on_change: move |v: String| search.set(v),
+                    on_blur: move |_| {},
+                    on_keydown: move |_| {},
+                    autofocus: false,
+                }
+                div {
+                    style: "display: flex; align-items: center; gap: 6px;",
+                    span { style: "font-size: 10px; color: {TEXT_MUTED};", "Sort" }
+                    select {
+                        value: "{sort.key()}",
+                        style: "
+                            flex: 1; padding: 3px 6px; font-size: 11px;
+                            background-color: {BG_SURFACE}; color: {TEXT_PRIMARY};
+                            border: 1px solid {BORDER_DEFAULT}; border-radius: 4px;
+                            outline: none;
+                        ",
+                        onchange: move |e| {
+                            if let Some(next) = AssetSort::from_key(&e.value()) {
+                                on_sort_change.call(next);
+                            }
+                        },
+                        for value in AssetSort::ALL {
+                            option { value: "{value.key()}", "{value.label()}" }
+                        }
+                    }
+                }
+                div {
+                    style: "display: flex; gap: 4px;",
+                    for filter in AssetTypeFilter::ALL {
+                        {
+                            let active = type_filter() == filter;
+                            let (bg, color, border) = if active {
+                                (BG_HOVER, TEXT_PRIMARY, BORDER_ACCENT)
+                            } else {
+                                ("transparent", TEXT_MUTED, BORDER_SUBTLE)
+                            };
+                            rsx! {
+                                button {
+                                    key: "{filter.label()}",
+                                    style: "
+                                        flex: 1; padding: 3px 0; font-size: 10px;
+                                        background-color: {bg}; color: {color};
+                                        border: 1px solid {border}; border-radius: 4px;
+                                        cursor: pointer;
+                                    ",
+                                    onclick: move |_| type_filter.set(filter),
+                                    "{filter.label()}"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
             // Asset list
             div {
                 style: "flex: 1; overflow-y: auto;",
@@ -184,16 +372,28 @@ pub fn AssetsPanelContent(
                         "No assets yet"
                         div { style: "font-size: 10px; color: {TEXT_DIM}; margin-top: 4px;", "Import files or create generative assets" }
                     }
+                } else if visible_assets.is_empty() && is_filtered {
+                    div {
+                        style: "
+                            padding: 16px 8px; text-align: center;
+                            color: {TEXT_DIM}; font-size: 12px;
+                        ",
+                        "No matching assets"
+                    }
                 } else {
-                    for asset in assets.iter() {
+                    for asset in visible_assets.iter() {
                         AssetItem { 
                             asset: asset.clone(),
+                            is_focused: focused_asset == Some(asset.id),
                             thumbnailer: thumbnailer.clone(),
                             thumbnail_cache_buster: thumbnail_cache_buster,
                             panel_width: panel_width,
                             on_rename: move |payload| on_rename.call(payload),
                             on_delete: move |id| on_delete.call(id),
                             on_regenerate_thumbnails: move |id| on_regenerate_thumbnails.call(id),
+                            on_transcode: move |id| on_transcode.call(id),
+                            on_relink: move |id| on_relink.call(id),
+                            on_toggle_proxy: move |id| on_toggle_proxy.call(id),
                             on_add_to_timeline: move |id| on_add_to_timeline.call(id),
                             on_drag_start: move |id| on_drag_start.call(id),
                         }
@@ -253,4 +453,89 @@ pub fn AssetsPanelContent(
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_asset_type_filter_by_kind() {
+        let assets = vec![
+            Asset::new_video("Video", PathBuf::from("video/a.mp4")),
+            Asset::new_image("Image", PathBuf::from("images/a.png")),
+            Asset::new_audio("Audio", PathBuf::from("audio/a.wav")),
+            Asset::new_generative_video(
+                "Gen Video 1",
+                PathBuf::from("generated/video/a"),
+                DEFAULT_GENERATIVE_VIDEO_FPS,
+                DEFAULT_GENERATIVE_VIDEO_FRAME_COUNT,
+            ),
+            Asset::new_generative_image("Gen Image 1", PathBuf::from("generated/image/a")),
+            Asset::new_generative_audio("Gen Audio 1", PathBuf::from("generated/audio/a")),
+        ];
+        let names = |filter: AssetTypeFilter| -> Vec<String> {
+            filter_assets(&assets, "", filter)
+                .into_iter()
+                .map(|asset| asset.name.clone())
+                .collect()
+        };
+
+        assert_eq!(names(AssetTypeFilter::All).len(), assets.len());
+        // Imported kinds never include their generative counterparts
+        assert_eq!(names(AssetTypeFilter::Video), vec!["Video"]);
+        assert_eq!(names(AssetTypeFilter::Image), vec!["Image"]);
+        assert_eq!(names(AssetTypeFilter::Audio), vec!["Audio"]);
+        assert_eq!(
+            names(AssetTypeFilter::Generative),
+            vec!["Gen Video 1", "Gen Image 1", "Gen Audio 1"]
+        );
+    }
+
+    #[test]
+    fn test_asset_search_matches_display_name() {
+        let video = Asset::new_video("Interview Take", PathBuf::from("video/a.mp4"));
+        let mut gen_image = Asset::new_generative_image("Gen Image 2", PathBuf::from("generated/image/a"));
+        if let AssetKind::GenerativeImage { active_version, .. } = &mut gen_image.kind {
+            *active_version = Some("v3".to_string());
+        }
+
+        // Case-insensitive substring, surrounding whitespace ignored
+        assert!(asset_matches_filter(&video, "interview", AssetTypeFilter::All));
+        assert!(asset_matches_filter(&video, "  VIEW t ", AssetTypeFilter::All));
+        assert!(!asset_matches_filter(&video, "outtake", AssetTypeFilter::All));
+        // The active version is part of the displayed name
+        assert!(asset_matches_filter(&gen_image, "(v3)", AssetTypeFilter::All));
+        // An empty query only applies the kind filter
+        assert!(asset_matches_filter(&video, "", AssetTypeFilter::Video));
+        assert!(!asset_matches_filter(&video, "", AssetTypeFilter::Generative));
+        // Both must match
+        assert!(asset_matches_filter(&gen_image, "gen", AssetTypeFilter::Generative));
+        assert!(!asset_matches_filter(&gen_image, "gen", AssetTypeFilter::Image));
+    }
+
+    #[test]
+    fn test_asset_cursor_clamps_at_ends() {
+        use AssetCursorMove::*;
+        assert_eq!(move_asset_cursor(Some(1), 3, Next), Some(2));
+        assert_eq!(move_asset_cursor(Some(2), 3, Next), Some(2));
+        assert_eq!(move_asset_cursor(Some(1), 3, Previous), Some(0));
+        assert_eq!(move_asset_cursor(Some(0), 3, Previous), Some(0));
+        assert_eq!(move_asset_cursor(Some(1), 3, First), Some(0));
+        assert_eq!(move_asset_cursor(Some(1), 3, Last), Some(2));
+
+        // Nothing highlighted yet
+        assert_eq!(move_asset_cursor(None, 3, Next), Some(0));
+        assert_eq!(move_asset_cursor(None, 3, Previous), Some(2));
+
+        // A cursor left past the end (the list shrank) comes back inside it
+        assert_eq!(move_asset_cursor(Some(7), 3, Previous), Some(1));
+        assert_eq!(move_asset_cursor(Some(7), 3, Next), Some(2));
+
+        for movement in [Previous, Next, First, Last] {
+            assert_eq!(move_asset_cursor(None, 0, movement), None);
+            assert_eq!(move_asset_cursor(Some(0), 1, movement), Some(0));
+        }
+    }
+}
+
 
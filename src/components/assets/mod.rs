@@ -2,7 +2,9 @@ mod assets_panel;
 mod asset_item;
 mod generative_video_modal;
 
-pub use assets_panel::AssetsPanelContent;
+pub use assets_panel::{
+    displayed_assets, move_asset_cursor, AssetCursorMove, AssetTypeFilter, AssetsPanelContent,
+};
 #[allow(unused_imports)]
 pub use asset_item::AssetItem;
 pub use generative_video_modal::GenerativeVideoModal;
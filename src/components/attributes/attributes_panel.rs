@@ -6,49 +6,65 @@ use std::rc::Rc;
 use crate::components::common::{
     NumericField, ProviderTextAreaField, ProviderTextField, StableNumberInput,
 };
-use super::generative_controls::render_generative_controls;
-use super::provider_inputs::render_provider_inputs;
+use super::generative_controls::{
+    render_generative_controls, render_regenerate_version, render_version_compare, CompareAction,
+};
+use super::provider_inputs::{render_input_presets, render_provider_inputs};
 use crate::constants::*;
+use crate::core::audio::loudness::format_lufs;
+use crate::core::audio::normalize::NORMALIZE_TARGET_DBFS;
+use crate::core::input_presets::{InputPreset, InputPresets};
 use crate::core::generation::{
-    random_seed_i64, resolve_provider_inputs, resolve_seed_field, update_seed_inputs,
+    batch_generation_jobs, fan_out_generation_jobs, generative_target, regenerate_version_job, RegenerateRequest, resolve_seed_field, SkippedClip, MAX_BATCH_COUNT,
 };
-use crate::providers::comfyui;
+use crate::core::paths;
+use crate::providers::env_approvals::ProviderEnvApprovals;
+use crate::providers::health;
+use crate::providers::http::env_var_allowed;
 use crate::state::{
     asset_display_name,
+    BlendMode,
+    ClipTransform,
+    FrameFit,
     delete_all_generative_version_files,
     delete_generative_version_files,
-    input_value_as_i64,
     parse_version_index,
     GenerationJob,
-    GenerationJobStatus,
     AssetKind,
-    ProviderConnection,
     ProviderEntry,
     ProviderInputType,
     ProviderOutputType,
     DEFAULT_GENERATIVE_VIDEO_FPS,
     DEFAULT_GENERATIVE_VIDEO_FRAME_COUNT,
+    MAX_CLIP_SCALE,
+    MAX_CLIP_SPEED,
+    MIN_CLIP_SCALE,
+    MIN_CLIP_SPEED,
     generative_video_duration_seconds,
     SeedStrategy,
     TrackType,
+    VersionCompare,
+    format_seed_list,
+    parse_seed_list,
 };
 use crate::utils::parse_i64_input;
 
-const MAX_BATCH_COUNT: u32 = 50;
-
 #[component]
 pub fn AttributesPanelContent(
     project: Signal<crate::state::Project>,
     selection: Signal<crate::state::SelectionState>,
+    history: Signal<crate::state::History>,
     preview_dirty: Signal<bool>,
     providers: Signal<Vec<ProviderEntry>>,
+    mut gen_status: Signal<Option<String>>,
     on_enqueue_generation: EventHandler<GenerationJob>,
     on_audio_items_refresh: EventHandler<()>,
     previewer: Signal<std::sync::Arc<crate::core::preview::PreviewRenderer>>,
     thumbnailer: std::sync::Arc<crate::core::thumbnailer::Thumbnailer>,
     thumbnail_cache_buster: Signal<u64>,
 ) -> Element {
-    let mut gen_status = use_signal(|| None::<String>);
+    // Measured loudness for a clip; `None` while the measurement runs
+    let mut clip_loudness = use_signal(|| None::<(uuid::Uuid, Option<String>)>);
     let mut last_clip_id = use_signal(|| None::<uuid::Uuid>);
 
     let selection_state = selection.read();
@@ -85,9 +101,34 @@ pub fn AttributesPanelContent(
 
     let total_selected = selected_clip_count + selected_track_count + selected_marker_count;
     if total_selected > 1 {
+        // Several generative clips can be generated in one go, each with its own settings
+        let generative_clip_ids: Vec<uuid::Uuid> = if selected_track_count == 0 && selected_marker_count == 0 {
+            let project_read = project.read();
+            selection
+                .read()
+                .clip_ids
+                .iter()
+                .copied()
+                .filter(|clip_id| {
+                    project_read
+                        .clips
+                        .iter()
+                        .find(|clip| clip.id == *clip_id)
+                        .and_then(|clip| project_read.find_asset(clip.asset_id))
+                        .map(|asset| asset.is_generative())
+                        .unwrap_or(false)
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let generative_count = generative_clip_ids.len();
+        let on_generate_selected = move |_: MouseEvent| {
+            generate_clips(project, providers, &generative_clip_ids, gen_status, on_enqueue_generation);
+        };
         return rsx! {
             div {
-                style: "padding: 12px;",
+                style: "padding: 12px; display: flex; flex-direction: column; gap: 10px;",
                 div {
                     style: "
                         display: flex; align-items: center; justify-content: center;
@@ -96,6 +137,23 @@ pub fn AttributesPanelContent(
                     ",
                     "{total_selected} items selected"
                 }
+                if generative_count > 1 {
+                    button {
+                        class: "collapse-btn",
+                        style: "
+                            padding: 8px 12px; border-radius: 6px;
+                            border: 1px solid {BORDER_DEFAULT};
+                            background-color: {BG_SURFACE}; color: {TEXT_PRIMARY};
+                            font-size: 12px; cursor: pointer;
+                        ",
+                        title: "Generate every selected generative clip with its own provider and batch settings",
+                        onclick: on_generate_selected,
+                        "Generate {generative_count} Clips"
+                    }
+                }
+                if let Some(status) = gen_status() {
+                    span { style: "font-size: 11px; color: {TEXT_MUTED};", "{status}" }
+                }
             }
         };
     }
@@ -189,6 +247,24 @@ pub fn AttributesPanelContent(
                                     project.write().set_marker_color(marker_id, Some(value));
                                 }
                             }
+                            div {
+                                style: "display: flex; gap: 6px;",
+                                for swatch in MARKER_COLORS {
+                                    div {
+                                        key: "{swatch}",
+                                        title: "{swatch}",
+                                        style: "
+                                            width: 16px; height: 16px; border-radius: 4px; cursor: pointer;
+                                            background-color: {swatch};
+                                            border: 1px solid {if swatch.eq_ignore_ascii_case(&marker_color) { TEXT_PRIMARY } else { BORDER_DEFAULT }};
+                                        ",
+                                        onclick: move |_| {
+                                            let color = (swatch != ACCENT_MARKER).then(|| swatch.to_string());
+                                            project.write().set_marker_color(marker_id, color);
+                                        },
+                                    }
+                                }
+                            }
                         }
                         div {
                             style: "display: flex; align-items: center; gap: 8px;",
@@ -251,6 +327,9 @@ pub fn AttributesPanelContent(
                 crate::state::TrackType::Video => "Video Track",
                 crate::state::TrackType::Marker => "Marker Track",
             };
+            let [wave_r, wave_g, wave_b] = track.resolved_waveform_color();
+            let waveform_hex = format!("#{:02x}{:02x}{:02x}", wave_r, wave_g, wave_b);
+            let has_custom_waveform_color = track.waveform_color.is_some();
             let track_type_label = match track.track_type {
                 crate::state::TrackType::Audio => "Audio",
                 crate::state::TrackType::Video => "Video",
@@ -293,18 +372,56 @@ pub fn AttributesPanelContent(
                                 clamp_min: Some(0.0),
                                 clamp_max: Some(2.0),
                                 on_commit: move |value: f32| {
-                                    if let Some(track) = project.write().tracks.iter_mut().find(|track| track.id == track_id) {
+                                    project.write().update_track(track_id, |track| {
                                         track.volume = value.max(0.0);
-                                    }
+                                    });
                                     on_audio_items_refresh.call(());
                                 },
                                 on_change: move |value: f32| {
-                                    if let Some(track) = project.write().tracks.iter_mut().find(|track| track.id == track_id) {
+                                    project.write().update_track(track_id, |track| {
                                         track.volume = value.max(0.0);
-                                    }
+                                    });
                                     on_audio_items_refresh.call(());
                                 },
                             }
+                            div {
+                                style: "display: flex; align-items: center; justify-content: space-between; gap: 8px;",
+                                span { style: "font-size: 10px; color: {TEXT_MUTED};", "Waveform Color" }
+                                div {
+                                    style: "display: flex; align-items: center; gap: 6px;",
+                                    input {
+                                        r#type: "color",
+                                        value: "{waveform_hex}",
+                                        style: "width: 32px; height: 20px; padding: 0; border: 1px solid {BORDER_DEFAULT}; background: transparent; cursor: pointer;",
+                                        onchange: move |e| {
+                                            let digits = e.value();
+                                            let digits = digits.trim_start_matches('#');
+                                            let channel = |index: usize| {
+                                                digits
+                                                    .get(index..index + 2)
+                                                    .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                                            };
+                                            if let (Some(r), Some(g), Some(b)) = (channel(0), channel(2), channel(4)) {
+                                                project.write().update_track(track_id, |track| {
+                                                    track.waveform_color = Some([r, g, b]);
+                                                });
+                                            }
+                                        },
+                                    }
+                                    if has_custom_waveform_color {
+                                        button {
+                                            style: "padding: 2px 6px; font-size: 10px; background: transparent; color: {TEXT_MUTED}; border: 1px solid {BORDER_DEFAULT}; border-radius: 4px; cursor: pointer;",
+                                            title: "Use the track type's default color",
+                                            onclick: move |_| {
+                                                project.write().update_track(track_id, |track| {
+                                                    track.waveform_color = None;
+                                                });
+                                            },
+                                            "Reset"
+                                        }
+                                    }
+                                }
+                            }
                         }
                     }
                 }
@@ -363,6 +480,7 @@ pub fn AttributesPanelContent(
         .as_ref()
         .map(|asset| asset.is_audio() || asset.is_video())
         .unwrap_or(false);
+    let clip_is_video = asset.as_ref().map(|asset| asset.is_video()).unwrap_or(false);
     let project_root = project_read.project_path.clone();
     let generative_info = asset.as_ref().and_then(|asset| match &asset.kind {
         crate::state::AssetKind::GenerativeVideo { folder, .. } => {
@@ -412,6 +530,10 @@ pub fn AttributesPanelContent(
     let batch_settings = config_snapshot.batch.clone();
     let batch_count = batch_settings.count.max(1).min(MAX_BATCH_COUNT);
     let seed_strategy_value = batch_settings.seed_strategy.as_str();
+    let seed_list_value = match &batch_settings.seed_strategy {
+        SeedStrategy::FixedList(seeds) => Some(format_seed_list(seeds)),
+        _ => None,
+    };
     let seed_field_value = batch_settings.seed_field.clone().unwrap_or_default();
     let seed_field_options: Vec<(String, String)> = selected_provider
         .as_ref()
@@ -458,11 +580,14 @@ pub fn AttributesPanelContent(
         None
     };
     let batch_hint = if batch_count > 1 {
-        match batch_settings.seed_strategy {
+        match &batch_settings.seed_strategy {
             SeedStrategy::Keep => Some(
                 "Identical inputs can be cached by ComfyUI; use Increment or Random."
                     .to_string(),
             ),
+            SeedStrategy::FixedList(seeds) if seeds.is_empty() => {
+                Some("Seed list is empty; seeds will increment instead.".to_string())
+            }
             _ => {
                 if resolved_seed_field.is_none() {
                     Some(
@@ -498,10 +623,47 @@ pub fn AttributesPanelContent(
         (None, None) => b.cmp(a),
     });
     version_options.dedup();
+    // Versions from one multi-output run are labeled with their position.
+    let version_labels: Vec<(String, String)> = version_options
+        .iter()
+        .map(|version| {
+            let label = config_snapshot
+                .versions
+                .iter()
+                .find(|record| &record.version == version)
+                .and_then(|record| match (record.output_index, record.output_count) {
+                    (Some(index), Some(count)) => {
+                        Some(format!("{} · output {}/{}", version, index + 1, count))
+                    }
+                    _ => None,
+                })
+                .unwrap_or_else(|| version.clone());
+            (version.clone(), label)
+        })
+        .collect();
     let manage_versions_open = use_signal(|| false);
     let confirm_delete_current = use_signal(|| false);
     let confirm_delete_others = use_signal(|| false);
     let confirm_delete_all = use_signal(|| false);
+    let version_compare = use_signal(|| None::<VersionCompare>);
+    // The panel is keyed on the selection, so this also runs when it changes
+    use_drop({
+        let mut project = project.clone();
+        let mut preview_dirty = preview_dirty.clone();
+        move || {
+            if let Some(compare) = version_compare.peek().clone() {
+                let asset_id = compare.asset_id;
+                project.write().show_generative_version(asset_id, compare.finish());
+                preview_dirty.set(true);
+            }
+        }
+    });
+    let active_compare = version_compare().filter(|compare| compare.asset_id == clip.asset_id);
+    let regenerate_open = use_signal(|| false);
+    let regenerate_field = use_signal(String::new);
+    let regenerate_value = use_signal(String::new);
+    let input_presets = use_signal(InputPresets::load_user);
+    let preset_name = use_signal(String::new);
     let can_delete_version = !selected_version_value.trim().is_empty();
     let on_provider_change = {
         let asset_id = clip.asset_id;
@@ -527,7 +689,10 @@ pub fn AttributesPanelContent(
         let mut confirm_delete_current = confirm_delete_current.clone();
         let mut confirm_delete_others = confirm_delete_others.clone();
         let mut confirm_delete_all = confirm_delete_all.clone();
+        let mut version_compare = version_compare.clone();
         Rc::new(RefCell::new(move |e: FormEvent| {
+            // Picking a version by hand ends any comparison on it
+            version_compare.set(None);
             let value = e.value();
             let trimmed = value.trim();
             let next_version = if trimmed.is_empty() {
@@ -567,6 +732,62 @@ pub fn AttributesPanelContent(
             confirm_delete_all.set(false);
         }))
     };
+    let on_compare_action = {
+        let asset_id = clip.asset_id;
+        let mut project = project.clone();
+        let mut preview_dirty = preview_dirty.clone();
+        let previewer = previewer.clone();
+        let gen_folder_path = gen_folder_path.clone();
+        let active_version = config_snapshot.active_version.clone();
+        let mut version_compare = version_compare.clone();
+        Rc::new(RefCell::new(move |action: CompareAction| {
+            let current = version_compare().filter(|compare| compare.asset_id == asset_id);
+            // The version to make active, when the action changes it
+            let mut show = None;
+            let next = match (action, current) {
+                (CompareAction::Start, _) => Some(VersionCompare::new(asset_id, active_version.clone())),
+                (CompareAction::PinA(version), Some(mut compare)) => {
+                    show = Some(compare.pin_a(&version).map(str::to_string));
+                    Some(compare)
+                }
+                (CompareAction::PinB(version), Some(mut compare)) => {
+                    show = Some(compare.pin_b(&version).map(str::to_string));
+                    Some(compare)
+                }
+                (CompareAction::Toggle, Some(mut compare)) => {
+                    show = Some(compare.toggle().map(str::to_string));
+                    Some(compare)
+                }
+                (CompareAction::Swap, Some(mut compare)) => {
+                    compare.swap();
+                    Some(compare)
+                }
+                (CompareAction::End, Some(compare)) => {
+                    show = Some(compare.finish());
+                    None
+                }
+                (_, None) => None,
+            };
+            version_compare.set(next);
+
+            let Some(version) = show else {
+                return;
+            };
+            let shown_version = project
+                .read()
+                .find_asset(asset_id)
+                .and_then(|asset| asset.active_version().map(str::to_string));
+            if shown_version == version {
+                return;
+            }
+            // Display only: the config's active version is what gets saved
+            project.write().show_generative_version(asset_id, version);
+            if let Some(folder_path) = gen_folder_path.as_ref() {
+                previewer.read().invalidate_folder(folder_path);
+            }
+            preview_dirty.set(true);
+        }))
+    };
     let on_delete_version = {
         let gen_folder_path = gen_folder_path.clone();
         let asset_id = clip.asset_id;
@@ -885,6 +1106,101 @@ pub fn AttributesPanelContent(
         }))
     };
 
+    let provider_presets: Vec<String> = selected_provider
+        .as_ref()
+        .map(|provider| {
+            input_presets
+                .read()
+                .for_provider(provider.id)
+                .iter()
+                .map(|preset| preset.name.clone())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let on_apply_preset = {
+        let asset_id = clip.asset_id;
+        let mut project = project.clone();
+        let selected_provider = selected_provider.clone();
+        Rc::new(RefCell::new(move |e: FormEvent| {
+            let Some(provider) = selected_provider.as_ref() else {
+                return;
+            };
+            let name = e.value();
+            let Some(preset) = input_presets.read().find(provider.id, &name).cloned() else {
+                return;
+            };
+            let (values, stale) = preset.applicable_inputs(provider);
+            let mut project_write = project.write();
+            project_write.update_generative_config(asset_id, |config| {
+                for (name, value) in values {
+                    config.inputs.insert(name, crate::state::InputValue::Literal { value });
+                }
+            });
+            let _ = project_write.save_generative_config(asset_id);
+            drop(project_write);
+            if stale.is_empty() {
+                gen_status.set(Some(format!("Applied preset '{}'", preset.name)));
+            } else {
+                eprintln!(
+                    "[PRESETS WARN] Preset '{}' has inputs {} no longer defines: {}",
+                    preset.name,
+                    provider.name,
+                    stale.join(", ")
+                );
+                gen_status.set(Some(format!(
+                    "Applied preset '{}'; ignored removed inputs: {}",
+                    preset.name,
+                    stale.join(", ")
+                )));
+            }
+        }))
+    };
+
+    let on_save_preset = {
+        let selected_provider = selected_provider.clone();
+        let config_snapshot = config_snapshot.clone();
+        let mut input_presets = input_presets.clone();
+        let mut preset_name = preset_name.clone();
+        Rc::new(RefCell::new(move || {
+            let Some(provider) = selected_provider.as_ref() else {
+                return;
+            };
+            let name = preset_name().trim().to_string();
+            if name.is_empty() {
+                gen_status.set(Some("Name the preset first.".to_string()));
+                return;
+            }
+            let preset = InputPreset::from_config(&name, provider, &config_snapshot);
+            let mut presets = input_presets.read().clone();
+            presets.upsert(provider.id, preset);
+            match presets.save_user() {
+                Ok(()) => {
+                    input_presets.set(presets);
+                    preset_name.set(String::new());
+                    gen_status.set(Some(format!("Saved preset '{}'", name)));
+                }
+                Err(err) => gen_status.set(Some(format!("Failed to save preset: {}", err))),
+            }
+        }))
+    };
+
+    let on_randomize_input_change = {
+        let asset_id = clip.asset_id;
+        let mut project = project.clone();
+        Rc::new(RefCell::new(move |name: String, randomize: bool| {
+            let mut project_write = project.write();
+            project_write.update_generative_config(asset_id, |config| {
+                let randomized = &mut config.batch.randomized_inputs;
+                randomized.retain(|existing| *existing != name);
+                if randomize {
+                    randomized.push(name);
+                }
+            });
+            let _ = project_write.save_generative_config(asset_id);
+        }))
+    };
+
     let on_batch_count_change = {
         let asset_id = clip.asset_id;
         let mut project = project.clone();
@@ -900,17 +1216,49 @@ pub fn AttributesPanelContent(
 
     let on_seed_strategy_change = {
         let asset_id = clip.asset_id;
+        let seed_field = resolved_seed_field.clone();
         let mut project = project.clone();
         Rc::new(RefCell::new(move |e: FormEvent| {
             let next = SeedStrategy::from_str(&e.value());
             let mut project_write = project.write();
             project_write.update_generative_config(asset_id, |config| {
+                // Switching to a fixed list starts from the current seed value
+                let next = match next {
+                    SeedStrategy::FixedList(_) => match &config.batch.seed_strategy {
+                        SeedStrategy::FixedList(seeds) => SeedStrategy::FixedList(seeds.clone()),
+                        _ => SeedStrategy::FixedList(
+                            seed_field
+                                .as_ref()
+                                .and_then(|field| config.inputs.get(field))
+                                .and_then(|value| match value {
+                                    crate::state::InputValue::Literal { value } => value.as_i64(),
+                                    _ => None,
+                                })
+                                .into_iter()
+                                .collect(),
+                        ),
+                    },
+                    next => next,
+                };
                 config.batch.seed_strategy = next;
             });
             let _ = project_write.save_generative_config(asset_id);
         }))
     };
 
+    let on_seed_list_change = {
+        let asset_id = clip.asset_id;
+        let mut project = project.clone();
+        Rc::new(RefCell::new(move |text: String| {
+            let seeds = parse_seed_list(&text);
+            let mut project_write = project.write();
+            project_write.update_generative_config(asset_id, |config| {
+                config.batch.seed_strategy = SeedStrategy::FixedList(seeds);
+            });
+            let _ = project_write.save_generative_config(asset_id);
+        }))
+    };
+
     let on_seed_field_change = {
         let asset_id = clip.asset_id;
         let mut project = project.clone();
@@ -931,140 +1279,111 @@ pub fn AttributesPanelContent(
     };
 
     let asset_label = asset_base_label.clone();
-    let on_generate = {
+    let on_generate: Rc<RefCell<dyn FnMut()>> = Rc::new(RefCell::new(move || {
+        generate_clips(project, providers, &[clip_id], gen_status, on_enqueue_generation);
+    }));
+
+    // Inputs of the selected version as (name, label, recorded value) for the
+    // regenerate tweak picker.
+    let regenerate_record = config_snapshot
+        .versions
+        .iter()
+        .find(|record| record.version == selected_version_value)
+        .cloned();
+    let regenerate_provider = regenerate_record.as_ref().and_then(|record| {
+        providers_list
+            .iter()
+            .find(|provider| provider.id == record.provider_id)
+            .cloned()
+    });
+    let regenerate_fields: Vec<(String, String, String)> = match (&regenerate_record, &regenerate_provider) {
+        (Some(record), Some(provider)) => provider
+            .inputs
+            .iter()
+            .filter(|input| {
+                !matches!(
+                    input.input_type,
+                    ProviderInputType::Image | ProviderInputType::Video | ProviderInputType::Audio
+                )
+            })
+            .map(|input| {
+                let recorded = match record.inputs_snapshot.get(&input.name) {
+                    Some(crate::state::InputValue::Literal { value }) => match value {
+                        serde_json::Value::String(text) => text.clone(),
+                        other => other.to_string(),
+                    },
+                    _ => String::new(),
+                };
+                (input.name.clone(), input.label.clone(), recorded)
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let on_regenerate_version = {
         let gen_folder_path = gen_folder_path.clone();
-        let gen_status = gen_status.clone();
-        let selected_provider = selected_provider.clone();
         let asset_id = clip.asset_id;
         let clip_id = clip.id;
         let asset_label = asset_label.clone();
         let on_enqueue_generation = on_enqueue_generation.clone();
-        let project = project.clone();
-        Rc::new(RefCell::new(move |_evt: MouseEvent| {
-            let gen_folder_path = gen_folder_path.clone();
+        let regenerate_provider = regenerate_provider.clone();
+        let config_snapshot = config_snapshot.clone();
+        let version = selected_version_value.clone();
+        let mut regenerate_open = regenerate_open.clone();
+        Rc::new(RefCell::new(move || {
             let mut gen_status = gen_status.clone();
-            let selected_provider = selected_provider.clone();
-            let on_enqueue_generation = on_enqueue_generation.clone();
-            let mut project = project.clone();
-
-            let Some(provider) = selected_provider.clone() else {
-                gen_status.set(Some("Select a provider first.".to_string()));
+            let Some(provider) = regenerate_provider.clone() else {
+                gen_status.set(Some("The provider that made this version is gone.".to_string()));
                 return;
             };
             let Some(folder_path) = gen_folder_path.clone() else {
                 gen_status.set(Some("Missing generative folder.".to_string()));
                 return;
             };
-
-            let mut project_write = project.write();
-            project_write.update_generative_config(asset_id, |config| {
-                config.provider_id = Some(provider.id);
-            });
-            let config_snapshot = project_write
-                .generative_config(asset_id)
-                .cloned()
-                .unwrap_or_default();
-            let _ = project_write.save_generative_config(asset_id);
-
-            let resolved = resolve_provider_inputs(&provider, &config_snapshot);
-            if !resolved.missing_required.is_empty() {
-                gen_status.set(Some(format!(
-                    "Missing inputs: {}",
-                    resolved.missing_required.join(", ")
-                )));
-                return;
+            let field = regenerate_field();
+            let mut edits = std::collections::HashMap::new();
+            if let Some(input) = provider.inputs.iter().find(|input| input.name == field) {
+                match parse_tweak_value(&input.input_type, &regenerate_value()) {
+                    Ok(value) => {
+                        edits.insert(field.clone(), value);
+                    }
+                    Err(err) => {
+                        gen_status.set(Some(err));
+                        return;
+                    }
+                }
             }
-
-            let batch_settings = config_snapshot.batch.clone();
-            let batch_count = batch_settings.count.max(1).min(MAX_BATCH_COUNT);
-            let seed_field =
-                resolve_seed_field(&provider, batch_settings.seed_field.as_deref());
-            let mut seed_base = seed_field
-                .as_ref()
-                .and_then(|field| resolved.values.get(field))
-                .and_then(input_value_as_i64);
-            let mut seed_base_randomized = false;
-            if seed_base.is_none()
-                && seed_field.is_some()
-                && batch_settings.seed_strategy == SeedStrategy::Increment
-            {
-                seed_base = Some(random_seed_i64());
-                seed_base_randomized = true;
+            let job = match regenerate_version_job(RegenerateRequest {
+                provider: &provider,
+                config: &config_snapshot,
+                version: &version,
+                edits: &edits,
+                asset_id,
+                clip_id,
+                asset_label: &asset_label,
+                folder_path: &folder_path,
+            }) {
+                Ok(job) => job,
+                Err(err) => {
+                    gen_status.set(Some(err));
+                    return;
+                }
+            };
+            if !confirm_provider_env(&provider) {
+                gen_status.set(Some("Generation cancelled: environment access not approved.".to_string()));
+                return;
             }
-            let seed_strategy = batch_settings.seed_strategy;
-            let base_inputs = resolved.values.clone();
-            let base_snapshot = resolved.snapshot.clone();
-            let job_asset_label = asset_label.clone();
-
+            regenerate_open.set(false);
             gen_status.set(Some("Checking provider...".to_string()));
-
+            let version = version.clone();
+            let on_enqueue_generation = on_enqueue_generation.clone();
             spawn(async move {
-                let health = match provider.connection.clone() {
-                    ProviderConnection::ComfyUi { base_url, .. } => {
-                        comfyui::check_health(&base_url).await
-                    }
-                    _ => Err("Provider health check not supported for this adapter yet.".to_string()),
-                };
-
-                if let Err(err) = health {
+                if let Err(err) = health::check_provider_health(&provider.connection).await {
                     gen_status.set(Some(format!("Provider offline: {}", err)));
                     return;
                 }
-
-                let mut queued = 0u32;
-                for index in 0..batch_count {
-                    let (inputs, input_snapshot) = match (seed_strategy, seed_field.as_ref()) {
-                        (SeedStrategy::Keep, _) | (_, None) => {
-                            (base_inputs.clone(), base_snapshot.clone())
-                        }
-                        (SeedStrategy::Increment, Some(field)) => {
-                            let seed = seed_base.unwrap_or(0) + index as i64;
-                            update_seed_inputs(&base_inputs, &base_snapshot, field, seed)
-                        }
-                        (SeedStrategy::Random, Some(field)) => {
-                            let seed = random_seed_i64();
-                            update_seed_inputs(&base_inputs, &base_snapshot, field, seed)
-                        }
-                    };
-                    let job = GenerationJob {
-                        id: uuid::Uuid::new_v4(),
-                        created_at: chrono::Utc::now(),
-                        status: GenerationJobStatus::Queued,
-                        progress_overall: None,
-                        progress_node: None,
-                        attempts: 0,
-                        next_attempt_at: None,
-                        provider: provider.clone(),
-                        output_type: provider.output_type,
-                        asset_id,
-                        clip_id,
-                        asset_label: job_asset_label.clone(),
-                        folder_path: folder_path.clone(),
-                        inputs,
-                        inputs_snapshot: input_snapshot,
-                        version: None,
-                        error: None,
-                    };
-
-                    on_enqueue_generation.call(job);
-                    queued += 1;
-                }
-
-                let mut status = if queued > 1 {
-                    format!("Queued {} jobs", queued)
-                } else {
-                    "Queued".to_string()
-                };
-                if queued > 1 {
-                    if seed_strategy == SeedStrategy::Keep {
-                        status = format!("{} (identical inputs may be cached)", status);
-                    } else if seed_field.is_none() {
-                        status = format!("{} (no seed field detected)", status);
-                    } else if seed_base_randomized {
-                        status = format!("{} (seed missing, randomized base)", status);
-                    }
-                }
-                gen_status.set(Some(status));
+                on_enqueue_generation.call(job);
+                gen_status.set(Some(format!("Queued regeneration of {}", version)));
             });
         }))
     };
@@ -1114,6 +1433,12 @@ pub fn AttributesPanelContent(
     let transform = clip.transform;
     let clip_id = clip.id;
     let clip_label = clip.label.clone().unwrap_or_default();
+    let clip_start = clip.start_time;
+    let clip_loops = clip.loop_to_duration;
+    let loop_summary = clip.loop_length().map(|cycle| {
+        let cycles = clip.duration.max(0.0) * clip.playback_speed() / cycle;
+        format!("Plays {:.2} cycles of {:.2}s", cycles, cycle)
+    });
     let clip_track_type = project.read().find_track(clip.track_id).map(|track| track.track_type);
     let allow_clip_gain = clip_track_type == Some(TrackType::Audio)
         || clip_track_type == Some(TrackType::Video);
@@ -1149,6 +1474,7 @@ pub fn AttributesPanelContent(
                         } else {
                             Some(trimmed.to_string())
                         };
+                        history.write().push(&project.read());
                         project.write().set_clip_label(clip_id, label);
                     }
                 }
@@ -1161,8 +1487,53 @@ pub fn AttributesPanelContent(
                     border: 1px solid {BORDER_SUBTLE}; border-radius: 6px;
                 ",
                 div {
-                    style: "font-size: 10px; color: {TEXT_DIM}; text-transform: uppercase; letter-spacing: 0.5px;",
-                    "Transform"
+                    style: "display: flex; align-items: center; justify-content: space-between;",
+                    span {
+                        style: "font-size: 10px; color: {TEXT_DIM}; text-transform: uppercase; letter-spacing: 0.5px;",
+                        "Transform"
+                    }
+                    div {
+                        style: "display: flex; gap: 4px;",
+                        button {
+                            style: "
+                                padding: 2px 8px; font-size: 10px; border-radius: 4px;
+                                border: 1px solid {BORDER_DEFAULT}; background-color: {BG_SURFACE};
+                                color: {TEXT_SECONDARY}; cursor: pointer;
+                            ",
+                            title: "Scale to show the whole source inside the frame",
+                            onclick: move |_| {
+                                spawn(apply_frame_fit(project, history, preview_dirty, clip_id, FrameFit::Fit));
+                            },
+                            "Fit"
+                        }
+                        button {
+                            style: "
+                                padding: 2px 8px; font-size: 10px; border-radius: 4px;
+                                border: 1px solid {BORDER_DEFAULT}; background-color: {BG_SURFACE};
+                                color: {TEXT_SECONDARY}; cursor: pointer;
+                            ",
+                            title: "Scale to cover the whole frame, cropping the overflow",
+                            onclick: move |_| {
+                                spawn(apply_frame_fit(project, history, preview_dirty, clip_id, FrameFit::Fill));
+                            },
+                            "Fill"
+                        }
+                        button {
+                            style: "
+                                padding: 2px 8px; font-size: 10px; border-radius: 4px;
+                                border: 1px solid {BORDER_DEFAULT}; background-color: {BG_SURFACE};
+                                color: {TEXT_SECONDARY}; cursor: pointer;
+                            ",
+                            disabled: transform == ClipTransform::default(),
+                            onclick: move |_| {
+                                history.write().push(&project.read());
+                                if project.write().reset_clip_transform(clip_id) {
+                                    preview_dirty.set(true);
+                                }
+                            },
+                            "Reset Transform"
+                        }
+                    }
                 }
                 div {
                     style: "display: grid; grid-template-columns: repeat(auto-fit, minmax(70px, 1fr)); gap: 8px;",
@@ -1173,8 +1544,15 @@ pub fn AttributesPanelContent(
                         step: "1",
                         clamp_min: None,
                         clamp_max: None,
+                        default_value: Some(0.0),
                         on_commit: move |value| {
-                            update_clip_transform(project, clip_id, |transform| {
+                            update_clip_transform(project, history, clip_id, |transform| {
+                                transform.position_x = value;
+                            });
+                            preview_dirty.set(true);
+                        },
+                        on_change: move |value: f32| {
+                            update_clip_transform(project, history, clip_id, |transform| {
                                 transform.position_x = value;
                             });
                             preview_dirty.set(true);
@@ -1187,8 +1565,15 @@ pub fn AttributesPanelContent(
                         step: "1",
                         clamp_min: None,
                         clamp_max: None,
+                        default_value: Some(0.0),
                         on_commit: move |value| {
-                            update_clip_transform(project, clip_id, |transform| {
+                            update_clip_transform(project, history, clip_id, |transform| {
+                                transform.position_y = value;
+                            });
+                            preview_dirty.set(true);
+                        },
+                        on_change: move |value: f32| {
+                            update_clip_transform(project, history, clip_id, |transform| {
                                 transform.position_y = value;
                             });
                             preview_dirty.set(true);
@@ -1199,10 +1584,17 @@ pub fn AttributesPanelContent(
                         label: "Scale X",
                         value: transform.scale_x,
                         step: "0.01",
-                        clamp_min: Some(0.01),
-                        clamp_max: None,
+                        clamp_min: Some(MIN_CLIP_SCALE),
+                        clamp_max: Some(MAX_CLIP_SCALE),
+                        default_value: Some(1.0),
                         on_commit: move |value| {
-                            update_clip_transform(project, clip_id, |transform| {
+                            update_clip_transform(project, history, clip_id, |transform| {
+                                transform.scale_x = value;
+                            });
+                            preview_dirty.set(true);
+                        },
+                        on_change: move |value: f32| {
+                            update_clip_transform(project, history, clip_id, |transform| {
                                 transform.scale_x = value;
                             });
                             preview_dirty.set(true);
@@ -1213,10 +1605,17 @@ pub fn AttributesPanelContent(
                         label: "Scale Y",
                         value: transform.scale_y,
                         step: "0.01",
-                        clamp_min: Some(0.01),
-                        clamp_max: None,
+                        clamp_min: Some(MIN_CLIP_SCALE),
+                        clamp_max: Some(MAX_CLIP_SCALE),
+                        default_value: Some(1.0),
                         on_commit: move |value| {
-                            update_clip_transform(project, clip_id, |transform| {
+                            update_clip_transform(project, history, clip_id, |transform| {
+                                transform.scale_y = value;
+                            });
+                            preview_dirty.set(true);
+                        },
+                        on_change: move |value: f32| {
+                            update_clip_transform(project, history, clip_id, |transform| {
                                 transform.scale_y = value;
                             });
                             preview_dirty.set(true);
@@ -1229,8 +1628,15 @@ pub fn AttributesPanelContent(
                         step: "1",
                         clamp_min: None,
                         clamp_max: None,
+                        default_value: Some(0.0),
                         on_commit: move |value| {
-                            update_clip_transform(project, clip_id, |transform| {
+                            update_clip_transform(project, history, clip_id, |transform| {
+                                transform.rotation_deg = value;
+                            });
+                            preview_dirty.set(true);
+                        },
+                        on_change: move |value: f32| {
+                            update_clip_transform(project, history, clip_id, |transform| {
                                 transform.rotation_deg = value;
                             });
                             preview_dirty.set(true);
@@ -1243,13 +1649,234 @@ pub fn AttributesPanelContent(
                         step: "0.05",
                         clamp_min: Some(0.0),
                         clamp_max: Some(1.0),
+                        default_value: Some(1.0),
                         on_commit: move |value| {
-                            update_clip_transform(project, clip_id, |transform| {
+                            update_clip_transform(project, history, clip_id, |transform| {
                                 transform.opacity = value;
                             });
                             preview_dirty.set(true);
                         }
                     }
+                    div {
+                        style: "display: flex; flex-direction: column; gap: 4px; min-width: 0;",
+                        span { style: "font-size: 10px; color: {TEXT_MUTED};", "Blend" }
+                        select {
+                            value: "{transform.blend_mode.as_str()}",
+                            style: "
+                                width: 100%; min-width: 0; box-sizing: border-box;
+                                padding: 6px 8px; font-size: 12px;
+                                background-color: {BG_SURFACE}; color: {TEXT_PRIMARY};
+                                border: 1px solid {BORDER_DEFAULT}; border-radius: 4px;
+                                outline: none;
+                            ",
+                            onchange: move |e| {
+                                let Some(mode) = BlendMode::from_id(&e.value()) else {
+                                    return;
+                                };
+                                update_clip_transform(project, history, clip_id, |transform| {
+                                    transform.blend_mode = mode;
+                                });
+                                preview_dirty.set(true);
+                            },
+                            for mode in BlendMode::ALL {
+                                option {
+                                    value: "{mode.as_str()}",
+                                    selected: mode == transform.blend_mode,
+                                    "{mode.label()}"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            if clip_has_audio {
+                div {
+                    style: "
+                        display: flex; flex-direction: column; gap: 10px;
+                        padding: 10px; background-color: {BG_SURFACE};
+                        border: 1px solid {BORDER_SUBTLE}; border-radius: 6px;
+                    ",
+                    div {
+                        style: "font-size: 10px; color: {TEXT_DIM}; text-transform: uppercase; letter-spacing: 0.5px;",
+                        "Playback"
+                    }
+                    label {
+                        style: "display: flex; align-items: center; gap: 6px; font-size: 11px; color: {TEXT_SECONDARY}; cursor: pointer;",
+                        input {
+                            r#type: "checkbox",
+                            checked: clip.reversed,
+                            onchange: move |_| {
+                                history.write().push(&project.read());
+                                if let Some(clip) = project.write().clips.iter_mut().find(|clip| clip.id == clip_id) {
+                                    clip.reversed = !clip.reversed;
+                                }
+                                on_audio_items_refresh.call(());
+                                preview_dirty.set(true);
+                            },
+                        }
+                        "Reverse"
+                    }
+                    NumericField {
+                        key: "{clip_id}-speed",
+                        label: "Speed",
+                        value: clip.playback_speed() as f32,
+                        step: "0.05",
+                        clamp_min: Some(MIN_CLIP_SPEED as f32),
+                        clamp_max: Some(MAX_CLIP_SPEED as f32),
+                        default_value: Some(1.0),
+                        on_commit: move |value: f32| {
+                            history.write().push_coalesced(&project.read(), Some(format!("speed:{}", clip_id)));
+                            if project.write().set_clip_speed(clip_id, value as f64) {
+                                on_audio_items_refresh.call(());
+                                preview_dirty.set(true);
+                            }
+                        }
+                    }
+                    label {
+                        style: "display: flex; align-items: center; gap: 6px; font-size: 11px; color: {TEXT_SECONDARY}; cursor: pointer;",
+                        title: "Repeat the clip's source to fill a longer duration",
+                        input {
+                            r#type: "checkbox",
+                            checked: clip.loop_to_duration,
+                            onchange: move |_| {
+                                history.write().push(&project.read());
+                                let enabled = !clip_loops;
+                                if project.write().set_clip_loop(clip_id, enabled) {
+                                    on_audio_items_refresh.call(());
+                                    preview_dirty.set(true);
+                                }
+                            },
+                        }
+                        "Loop to fill duration"
+                    }
+                    if let Some(loop_summary) = loop_summary {
+                        NumericField {
+                            key: "{clip_id}-loop-duration",
+                            label: "Loop Duration (s)",
+                            value: clip.duration as f32,
+                            step: "0.1",
+                            clamp_min: Some(0.1),
+                            clamp_max: None,
+                            on_commit: move |value: f32| {
+                                history.write().push_coalesced(&project.read(), Some(format!("loop:{}", clip_id)));
+                                if project.write().resize_clip(clip_id, clip_start, value as f64) {
+                                    on_audio_items_refresh.call(());
+                                    preview_dirty.set(true);
+                                }
+                            }
+                        }
+                        div {
+                            style: "font-size: 10px; color: {TEXT_MUTED};",
+                            "{loop_summary}"
+                        }
+                    }
+                    label {
+                        style: "display: flex; align-items: center; gap: 6px; font-size: 11px; color: {TEXT_SECONDARY}; cursor: pointer;",
+                        title: "Scale the clip so its loudest peak reaches {NORMALIZE_TARGET_DBFS} dBFS",
+                        input {
+                            r#type: "checkbox",
+                            checked: clip.normalize,
+                            onchange: move |_| {
+                                history.write().push(&project.read());
+                                if let Some(clip) = project.write().clips.iter_mut().find(|clip| clip.id == clip_id) {
+                                    clip.normalize = !clip.normalize;
+                                }
+                                on_audio_items_refresh.call(());
+                            },
+                        }
+                        "Normalize"
+                    }
+                    {
+                        let loudness = match clip_loudness() {
+                            Some((id, Some(text))) if id == clip_id => text,
+                            Some((id, None)) if id == clip_id => "Measuring...".to_string(),
+                            _ => "--".to_string(),
+                        };
+                        rsx! {
+                            div {
+                                style: "display: flex; align-items: center; gap: 8px; font-size: 11px; color: {TEXT_SECONDARY};",
+                                span { style: "font-size: 10px; color: {TEXT_MUTED};", "Loudness" }
+                                span { style: "flex: 1; font-family: 'SF Mono', Consolas, monospace;", "{loudness}" }
+                                button {
+                                    style: "padding: 2px 8px; font-size: 10px; background: transparent; color: {TEXT_MUTED}; border: 1px solid {BORDER_DEFAULT}; border-radius: 4px; cursor: pointer;",
+                                    title: "Integrated loudness (ITU-R BS.1770) of the clip as it plays",
+                                    onclick: move |_| {
+                                        let snapshot = project.read().clone();
+                                        let Some(root) = snapshot.project_path.clone() else {
+                                            return;
+                                        };
+                                        clip_loudness.set(Some((clip_id, None)));
+                                        spawn(async move {
+                                            let result = tokio::task::spawn_blocking(move || {
+                                                crate::core::audio::mixdown::measure_clip_lufs(&snapshot, &root, clip_id)
+                                            })
+                                            .await
+                                            .unwrap_or_else(|err| Err(err.to_string()));
+                                            let text = match result {
+                                                Ok(lufs) => format_lufs(lufs),
+                                                Err(err) => {
+                                                    eprintln!("[AUDIO ERROR] Clip loudness failed: {}", err);
+                                                    "--".to_string()
+                                                }
+                                            };
+                                            clip_loudness.set(Some((clip_id, Some(text))));
+                                        });
+                                    },
+                                    "Measure"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            if clip_is_video {
+                div {
+                    style: "
+                        display: flex; flex-direction: column; gap: 10px;
+                        padding: 10px; background-color: {BG_SURFACE};
+                        border: 1px solid {BORDER_SUBTLE}; border-radius: 6px;
+                    ",
+                    div {
+                        style: "font-size: 10px; color: {TEXT_DIM}; text-transform: uppercase; letter-spacing: 0.5px;",
+                        "Freeze Frame"
+                    }
+                    label {
+                        style: "display: flex; align-items: center; gap: 6px; font-size: 11px; color: {TEXT_SECONDARY}; cursor: pointer;",
+                        input {
+                            r#type: "checkbox",
+                            checked: clip.is_frozen(),
+                            onchange: move |_| {
+                                history.write().push(&project.read());
+                                if let Some(clip) = project.write().clips.iter_mut().find(|clip| clip.id == clip_id) {
+                                    clip.freeze_at = match clip.freeze_at {
+                                        Some(_) => None,
+                                        None => Some(clip.trim_in_seconds),
+                                    };
+                                }
+                                preview_dirty.set(true);
+                            },
+                        }
+                        "Hold a single frame"
+                    }
+                    if let Some(freeze_at) = clip.freeze_at {
+                        NumericField {
+                            key: "{clip_id}-freeze-at",
+                            label: "Hold At (s)",
+                            value: freeze_at as f32,
+                            step: "0.04",
+                            clamp_min: Some(0.0),
+                            clamp_max: None,
+                            on_commit: move |value: f32| {
+                                history.write().push_coalesced(&project.read(), Some(format!("freeze:{}", clip_id)));
+                                if let Some(clip) = project.write().clips.iter_mut().find(|clip| clip.id == clip_id) {
+                                    clip.freeze_at = Some(value.max(0.0) as f64);
+                                }
+                                preview_dirty.set(true);
+                            }
+                        }
+                    }
                 }
             }
 
@@ -1272,12 +1899,14 @@ pub fn AttributesPanelContent(
                         clamp_min: Some(0.0),
                         clamp_max: Some(2.0),
                         on_commit: move |value: f32| {
+                            history.write().push_coalesced(&project.read(), Some(format!("volume:{}", clip_id)));
                             if let Some(clip) = project.write().clips.iter_mut().find(|clip| clip.id == clip_id) {
                                 clip.volume = value.max(0.0);
                             }
                             on_audio_items_refresh.call(());
                         },
                         on_change: move |value: f32| {
+                            history.write().push_coalesced(&project.read(), Some(format!("volume:{}", clip_id)));
                             if let Some(clip) = project.write().clips.iter_mut().find(|clip| clip.id == clip_id) {
                                 clip.volume = value.max(0.0);
                             }
@@ -1331,6 +1960,7 @@ pub fn AttributesPanelContent(
                 }
                 {render_generative_controls(
                     &version_options,
+                    &version_labels,
                     &selected_version_value,
                     manage_versions_open,
                     confirm_delete_current,
@@ -1353,6 +1983,8 @@ pub fn AttributesPanelContent(
                     on_batch_count_change,
                     seed_strategy_value,
                     on_seed_strategy_change,
+                    seed_list_value,
+                    on_seed_list_change,
                     seed_field_value.as_str(),
                     &seed_field_options,
                     on_seed_field_change,
@@ -1361,12 +1993,38 @@ pub fn AttributesPanelContent(
                     batch_hint.clone(),
                     confirm_delete_all,
                 )}
+                if !regenerate_fields.is_empty() {
+                    {render_regenerate_version(
+                        &selected_version_value,
+                        &regenerate_fields,
+                        regenerate_open,
+                        regenerate_field,
+                        regenerate_value,
+                        on_regenerate_version,
+                    )}
+                }
+                if version_options.len() > 1 {
+                    {render_version_compare(
+                        active_compare,
+                        &version_labels,
+                        on_compare_action,
+                    )}
+                }
+                if selected_provider.is_some() {
+                    {render_input_presets(
+                        &provider_presets,
+                        preset_name,
+                        on_apply_preset,
+                        on_save_preset,
+                    )}
+                }
                 {render_provider_inputs(
                     selected_provider,
                     show_missing_provider,
                     &config_snapshot,
                     &selected_version_value,
                     set_input_value.clone(),
+                    on_randomize_input_change,
                 )}
             }
 
@@ -1374,16 +2032,374 @@ pub fn AttributesPanelContent(
     }
 }
 
+/// "Name (reason), ..." for the status line.
+/// A typed-in replacement value for a provider input.
+fn parse_tweak_value(input_type: &ProviderInputType, text: &str) -> Result<serde_json::Value, String> {
+    let trimmed = text.trim();
+    match input_type {
+        ProviderInputType::Integer => trimmed
+            .parse::<i64>()
+            .map(serde_json::Value::from)
+            .map_err(|_| format!("'{}' is not a whole number.", trimmed)),
+        ProviderInputType::Number => trimmed
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(serde_json::Value::Number)
+            .ok_or_else(|| format!("'{}' is not a number.", trimmed)),
+        ProviderInputType::Boolean => trimmed
+            .parse::<bool>()
+            .map(serde_json::Value::Bool)
+            .map_err(|_| format!("'{}' is not true or false.", trimmed)),
+        ProviderInputType::Text | ProviderInputType::Enum { .. } => {
+            Ok(serde_json::Value::String(text.to_string()))
+        }
+        ProviderInputType::Image | ProviderInputType::Video | ProviderInputType::Audio => {
+            Err("Media inputs can't be tweaked here.".to_string())
+        }
+    }
+}
+
+/// Queue generation for the generative clips among `clip_ids`. A lone clip runs
+/// its batch settings; several fan out, each with its own provider and settings.
+/// Shared by the Generate buttons and the Ctrl+Enter hotkey.
+pub fn generate_clips(
+    project: Signal<crate::state::Project>,
+    providers: Signal<Vec<ProviderEntry>>,
+    clip_ids: &[uuid::Uuid],
+    mut gen_status: Signal<Option<String>>,
+    on_enqueue_generation: EventHandler<GenerationJob>,
+) {
+    let generative_clip_ids: Vec<uuid::Uuid> = {
+        let project_read = project.read();
+        clip_ids
+            .iter()
+            .copied()
+            .filter(|clip_id| {
+                project_read
+                    .clips
+                    .iter()
+                    .find(|clip| clip.id == *clip_id)
+                    .and_then(|clip| project_read.find_asset(clip.asset_id))
+                    .map(|asset| asset.is_generative())
+                    .unwrap_or(false)
+            })
+            .collect()
+    };
+    match generative_clip_ids.as_slice() {
+        [] => {}
+        [clip_id] => generate_clip(project, providers, *clip_id, gen_status, on_enqueue_generation),
+        _ => generate_fan_out(project, providers, &generative_clip_ids, gen_status, on_enqueue_generation),
+    }
+}
+
+fn generate_clip(
+    mut project: Signal<crate::state::Project>,
+    providers: Signal<Vec<ProviderEntry>>,
+    clip_id: uuid::Uuid,
+    mut gen_status: Signal<Option<String>>,
+    on_enqueue_generation: EventHandler<GenerationJob>,
+) {
+    let project_read = project.read();
+    let Some(asset) = project_read
+        .clips
+        .iter()
+        .find(|clip| clip.id == clip_id)
+        .and_then(|clip| project_read.find_asset(clip.asset_id))
+    else {
+        return;
+    };
+    let asset_id = asset.id;
+    let asset_label = asset.name.clone();
+    let target = generative_target(&asset.kind).map(|(folder, output)| (folder.to_path_buf(), output));
+    let provider_id = project_read.generative_config(asset_id).and_then(|config| config.provider_id);
+    let folder_path = project_read
+        .project_path
+        .as_ref()
+        .zip(target.as_ref())
+        .map(|(root, (folder, _))| root.join(folder));
+    drop(project_read);
+
+    let provider = provider_id.zip(target.as_ref()).and_then(|(id, (_, output))| {
+        providers
+            .read()
+            .iter()
+            .find(|entry| entry.id == id && entry.output_type == *output)
+            .cloned()
+    });
+    let Some(provider) = provider else {
+        gen_status.set(Some("Select a provider first.".to_string()));
+        return;
+    };
+    let Some(folder_path) = folder_path else {
+        gen_status.set(Some("Missing generative folder.".to_string()));
+        return;
+    };
+    if !confirm_provider_env(&provider) {
+        gen_status.set(Some("Generation cancelled: environment access not approved.".to_string()));
+        return;
+    }
+
+    let mut project_write = project.write();
+    project_write.update_generative_config(asset_id, |config| {
+        config.provider_id = Some(provider.id);
+    });
+    let config_snapshot = project_write
+        .generative_config(asset_id)
+        .cloned()
+        .unwrap_or_default();
+    let _ = project_write.save_generative_config(asset_id);
+    drop(project_write);
+
+    let batch = match batch_generation_jobs(
+        &provider,
+        &config_snapshot,
+        asset_id,
+        clip_id,
+        &asset_label,
+        &folder_path,
+    ) {
+        Ok(batch) => batch,
+        Err(err) => {
+            gen_status.set(Some(err));
+            return;
+        }
+    };
+    let keep_seed = config_snapshot.batch.seed_strategy == SeedStrategy::Keep;
+
+    gen_status.set(Some("Checking provider...".to_string()));
+
+    spawn(async move {
+        let health = health::check_provider_health(&provider.connection).await;
+
+        if let Err(err) = health {
+            gen_status.set(Some(format!("Provider offline: {}", err)));
+            return;
+        }
+
+        let queued = batch.jobs.len();
+        for job in batch.jobs {
+            on_enqueue_generation.call(job);
+        }
+
+        let mut status = if queued > 1 {
+            format!("Queued {} jobs", queued)
+        } else {
+            "Queued".to_string()
+        };
+        if queued > 1 {
+            if keep_seed {
+                status = format!("{} (identical inputs may be cached)", status);
+            } else if batch.seed_field.is_none() {
+                status = format!("{} (no seed field detected)", status);
+            } else if batch.seed_base_randomized {
+                status = format!("{} (seed missing, randomized base)", status);
+            }
+        }
+        gen_status.set(Some(status));
+    });
+}
+
+fn generate_fan_out(
+    project: Signal<crate::state::Project>,
+    providers: Signal<Vec<ProviderEntry>>,
+    clip_ids: &[uuid::Uuid],
+    mut gen_status: Signal<Option<String>>,
+    on_enqueue_generation: EventHandler<GenerationJob>,
+) {
+    let fan_out = fan_out_generation_jobs(&project.read(), clip_ids, &providers.read());
+    if fan_out.jobs.is_empty() {
+        gen_status.set(Some(format!(
+            "Nothing queued: {}",
+            skipped_clips_summary(&fan_out.skipped)
+        )));
+        return;
+    }
+    // Ask once per provider before it first reads environment variables
+    let mut declined = std::collections::HashSet::new();
+    let mut confirmed = std::collections::HashSet::new();
+    for job in fan_out.jobs.iter() {
+        if declined.contains(&job.provider.id) || !confirmed.insert(job.provider.id) {
+            continue;
+        }
+        if !confirm_provider_env(&job.provider) {
+            declined.insert(job.provider.id);
+        }
+    }
+    gen_status.set(Some("Checking providers...".to_string()));
+    spawn(async move {
+        // One health check per provider; clips on an offline provider are skipped
+        let mut offline = std::collections::HashMap::new();
+        for job in fan_out.jobs.iter() {
+            if offline.contains_key(&job.provider.id) || declined.contains(&job.provider.id) {
+                continue;
+            }
+            let health = health::check_provider_health(&job.provider.connection).await;
+            offline.insert(job.provider.id, health.err());
+        }
+        let mut skipped = fan_out.skipped;
+        let mut queued_clips = std::collections::HashSet::new();
+        let mut queued = 0;
+        for job in fan_out.jobs {
+            if declined.contains(&job.provider.id) {
+                if !skipped.iter().any(|skip| skip.clip_id == job.clip_id) {
+                    skipped.push(SkippedClip {
+                        clip_id: job.clip_id,
+                        label: job.asset_label.clone(),
+                        reason: "Environment access not approved".to_string(),
+                    });
+                }
+                continue;
+            }
+            if let Some(Some(err)) = offline.get(&job.provider.id) {
+                if !skipped.iter().any(|skip| skip.clip_id == job.clip_id) {
+                    skipped.push(SkippedClip {
+                        clip_id: job.clip_id,
+                        label: job.asset_label.clone(),
+                        reason: format!("Provider offline: {}", err),
+                    });
+                }
+                continue;
+            }
+            queued_clips.insert(job.clip_id);
+            on_enqueue_generation.call(job);
+            queued += 1;
+        }
+        let mut status = format!(
+            "Queued {} jobs for {} clips",
+            queued,
+            queued_clips.len()
+        );
+        if !skipped.is_empty() {
+            status = format!("{}; skipped {}", status, skipped_clips_summary(&skipped));
+        }
+        gen_status.set(Some(status));
+    });
+}
+
+/// Ask before a provider reads environment variables the user hasn't approved
+/// for it yet. The answer is remembered per provider.
+fn confirm_provider_env(provider: &ProviderEntry) -> bool {
+    let mut approvals = ProviderEnvApprovals::load_user();
+    let vars = approvals.unapproved(provider);
+    if vars.is_empty() {
+        return true;
+    }
+    let listing = vars
+        .iter()
+        .map(|var| {
+            if env_var_allowed(var) {
+                format!("• {}", var)
+            } else {
+                format!("• {} (not readable: only NLA_ variables are resolved)", var)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let answer = rfd::MessageDialog::new()
+        .set_level(rfd::MessageLevel::Warning)
+        .set_title("Provider Environment Access")
+        .set_description(format!(
+            "\"{}\" sends these environment variables with its requests:\n\n{}\n\nAllow it to read them?",
+            provider.name, listing
+        ))
+        .set_buttons(rfd::MessageButtons::OkCancel)
+        .show();
+    if !matches!(answer, rfd::MessageDialogResult::Ok) {
+        return false;
+    }
+    approvals.approve(provider.id, &vars);
+    if let Err(err) = approvals.save(&paths::provider_env_approvals_path()) {
+        eprintln!("[PROVIDER WARN] Failed to save env approvals: {}", err);
+    }
+    true
+}
+
+fn skipped_clips_summary(skipped: &[SkippedClip]) -> String {
+    skipped
+        .iter()
+        .map(|skip| format!("{} ({})", skip.label, skip.reason))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 fn update_clip_transform(
     mut project: Signal<crate::state::Project>,
+    mut history: Signal<crate::state::History>,
     clip_id: uuid::Uuid,
     update: impl FnOnce(&mut crate::state::ClipTransform),
 ) {
+    history
+        .write()
+        .push_coalesced(&project.read(), Some(format!("transform:{}", clip_id)));
     if let Some(clip) = project.write().clips.iter_mut().find(|clip| clip.id == clip_id) {
         update(&mut clip.transform);
     }
 }
 
+/// Scale a clip to fit or fill the project frame. The source size is probed
+/// off the UI thread the first time and cached on file-backed assets.
+async fn apply_frame_fit(
+    mut project: Signal<crate::state::Project>,
+    mut history: Signal<crate::state::History>,
+    mut preview_dirty: Signal<bool>,
+    clip_id: uuid::Uuid,
+    mode: FrameFit,
+) {
+    let (asset_id, cached, source, frame) = {
+        let project_read = project.read();
+        let Some(clip) = project_read.clips.iter().find(|clip| clip.id == clip_id) else {
+            return;
+        };
+        let Some(asset) = project_read.find_asset(clip.asset_id) else {
+            return;
+        };
+        let source = project_read
+            .project_path
+            .as_deref()
+            .and_then(|root| crate::core::preview::visual_source_path(root, asset));
+        let frame = (project_read.settings.width, project_read.settings.height);
+        (asset.id, asset.source_dimensions(), source, frame)
+    };
+
+    let dimensions = match cached {
+        Some(dimensions) => Some(dimensions),
+        None => {
+            let Some((path, is_video)) = source else {
+                return;
+            };
+            let probed = tokio::task::spawn_blocking(move || {
+                crate::core::media::probe_media_dimensions(&path, is_video)
+            })
+            .await
+            .ok()
+            .flatten();
+            if let Some((width, height)) = probed {
+                project.write().set_asset_dimensions(asset_id, width, height);
+            }
+            probed
+        }
+    };
+    let Some(dimensions) = dimensions else {
+        eprintln!("[TRANSFORM ERROR] Could not read the source size for clip {}", clip_id);
+        return;
+    };
+
+    let Some(fitted) = project
+        .read()
+        .clips
+        .iter()
+        .find(|clip| clip.id == clip_id)
+        .and_then(|clip| clip.transform.fitted_to_frame(dimensions, frame, mode))
+    else {
+        return;
+    };
+    history.write().push(&project.read());
+    if project.write().set_clip_transform(clip_id, fitted) {
+        preview_dirty.set(true);
+    }
+}
+
 fn update_generative_video_asset(
     project: &mut crate::state::Project,
     asset_id: uuid::Uuid,
@@ -1391,6 +2407,7 @@ fn update_generative_video_asset(
     frame_count: u32,
 ) {
     let duration = generative_video_duration_seconds(fps, frame_count);
+    project.mark_edited();
     if let Some(asset) = project.assets.iter_mut().find(|asset| asset.id == asset_id) {
         if let AssetKind::GenerativeVideo {
             fps: stored_fps,
@@ -1409,10 +2426,11 @@ fn update_generative_video_asset(
     };
 
     for clip in project.clips.iter_mut().filter(|clip| clip.asset_id == asset_id) {
-        if clip.duration > duration {
-            clip.duration = duration.max(0.0);
+        let max_duration = clip.timeline_duration_for_source(duration);
+        if clip.duration > max_duration {
+            clip.duration = max_duration.max(0.0);
         }
-        let max_trim = (duration - clip.duration).max(0.0);
+        let max_trim = (duration - clip.source_duration()).max(0.0);
         if clip.trim_in_seconds > max_trim {
             clip.trim_in_seconds = max_trim;
         }
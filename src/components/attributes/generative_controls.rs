@@ -3,12 +3,23 @@ use std::rc::Rc;
 
 use dioxus::prelude::*;
 
-use crate::components::common::ProviderIntegerField;
+use crate::components::common::{ProviderIntegerField, ProviderTextField, StableTextInput};
 use crate::constants::*;
-use crate::state::ProviderEntry;
+use crate::state::{group_providers_by_category, CompareSide, ProviderEntry, ProviderScope, VersionCompare};
+
+/// Steps of an A/B version comparison, handled by the attributes panel.
+pub(super) enum CompareAction {
+    Start,
+    PinA(String),
+    PinB(String),
+    Toggle,
+    Swap,
+    End,
+}
 
 pub(super) fn render_generative_controls(
     version_options: &[String],
+    version_labels: &[(String, String)],
     selected_version_value: &str,
     mut manage_versions_open: Signal<bool>,
     mut confirm_delete_current: Signal<bool>,
@@ -23,7 +34,7 @@ pub(super) fn render_generative_controls(
     on_provider_change: Rc<RefCell<dyn FnMut(FormEvent)>>,
     show_missing_provider: bool,
     providers_path_label: &str,
-    on_generate: Rc<RefCell<dyn FnMut(MouseEvent)>>,
+    on_generate: Rc<RefCell<dyn FnMut()>>,
     gen_status: Signal<Option<String>>,
     generate_label: &str,
     generate_opacity: &str,
@@ -31,6 +42,8 @@ pub(super) fn render_generative_controls(
     on_batch_count_change: Rc<RefCell<dyn FnMut(i64)>>,
     seed_strategy_value: &str,
     on_seed_strategy_change: Rc<RefCell<dyn FnMut(FormEvent)>>,
+    seed_list_value: Option<String>,
+    on_seed_list_change: Rc<RefCell<dyn FnMut(String)>>,
     seed_field_value: &str,
     seed_field_options: &[(String, String)],
     on_seed_field_change: Rc<RefCell<dyn FnMut(FormEvent)>>,
@@ -50,6 +63,27 @@ pub(super) fn render_generative_controls(
     let current_opacity = if can_delete_current { "1.0" } else { "0.4" };
     let others_opacity = if has_other_versions { "1.0" } else { "0.4" };
     let all_opacity = if can_delete_all { "1.0" } else { "0.4" };
+    let selected_scope = compatible_providers
+        .iter()
+        .find(|provider| provider.id.to_string() == selected_provider_value)
+        .map(|provider| provider.scope);
+    // Favorites get their own group at the top; everything else groups by category.
+    let mut favorite_providers: Vec<ProviderEntry> = compatible_providers
+        .iter()
+        .filter(|provider| provider.favorite)
+        .cloned()
+        .collect();
+    favorite_providers.sort_by_key(|provider| provider.name.to_lowercase());
+    let other_providers: Vec<ProviderEntry> = compatible_providers
+        .iter()
+        .filter(|provider| !provider.favorite)
+        .cloned()
+        .collect();
+    let provider_groups = group_providers_by_category(&other_providers);
+    let scope_color = match selected_scope {
+        Some(ProviderScope::Project) => ACCENT_PRIMARY,
+        _ => TEXT_DIM,
+    };
     rsx! {
         div {
             style: "
@@ -105,8 +139,8 @@ pub(super) fn render_generative_controls(
                     if version_options.is_empty() {
                         option { value: "", "No versions yet" }
                     } else {
-                        for version in version_options.iter() {
-                            option { value: "{version}", "{version}" }
+                        for (version, label) in version_labels.iter() {
+                            option { value: "{version}", "{label}" }
                         }
                     }
                 }
@@ -278,7 +312,21 @@ pub(super) fn render_generative_controls(
             }
             div {
                 style: "display: flex; flex-direction: column; gap: 6px;",
-                span { style: "font-size: 10px; color: {TEXT_MUTED};", "Provider" }
+                div {
+                    style: "display: flex; align-items: center; justify-content: space-between;",
+                    span { style: "font-size: 10px; color: {TEXT_MUTED};", "Provider" }
+                    if let Some(scope) = selected_scope {
+                        span {
+                            style: "
+                                font-size: 9px; color: {scope_color};
+                                border: 1px solid {scope_color}; border-radius: 3px;
+                                padding: 0 4px;
+                            ",
+                            title: "Where this provider is stored",
+                            "{scope.label()}"
+                        }
+                    }
+                }
                 select {
                     value: "{selected_provider_value}",
                     style: "
@@ -292,27 +340,42 @@ pub(super) fn render_generative_controls(
                         move |e| on_provider_change.borrow_mut()(e)
                     },
                     option { value: "", "None selected" }
-                    for provider in compatible_providers.iter() {
-                        option { value: "{provider.id}", "{provider.name}" }
+                    if !favorite_providers.is_empty() {
+                        optgroup {
+                            label: "★ Favorites",
+                            for provider in favorite_providers.iter() {
+                                option { value: "{provider.id}", "★ {provider.name} ({provider.scope.label()})" }
+                            }
+                        }
+                    }
+                    for (category, members) in provider_groups.iter() {
+                        optgroup {
+                            label: "{category}",
+                            for provider in members.iter() {
+                                option { value: "{provider.id}", "{provider.name} ({provider.scope.label()})" }
+                            }
+                        }
                     }
                 }
             }
             if show_missing_provider {
                 div {
                     style: "font-size: 11px; color: #f97316;",
-                    "Selected provider missing from global providers."
+                    "Selected provider missing from global and project providers."
                 }
             }
             if compatible_providers.is_empty() {
                 div {
                     style: "font-size: 11px; color: {TEXT_DIM};",
-                    "No providers configured. Add JSON files under {providers_path_label}."
+                    "No providers configured. Add JSON files under {providers_path_label} or the project's .providers folder."
                 }
             }
             div {
                 style: "display: flex; flex-direction: column; gap: 6px;",
                 button {
+                    id: "generative-generate-button",
                     class: "collapse-btn",
+                    title: "Generate (Ctrl+Enter)",
                     style: "
                         width: 100%; padding: 8px 10px;
                         background-color: {ACCENT_VIDEO};
@@ -322,7 +385,7 @@ pub(super) fn render_generative_controls(
                     ",
                     onclick: {
                         let on_generate = on_generate.clone();
-                        move |e| on_generate.borrow_mut()(e)
+                        move |_| on_generate.borrow_mut()()
                     },
                     "{generate_label}"
                 }
@@ -368,6 +431,7 @@ pub(super) fn render_generative_controls(
                             option { value: "increment", "Increment" }
                             option { value: "random", "Random" }
                             option { value: "keep", "Keep" }
+                            option { value: "fixed_list", "Fixed List" }
                         }
                     }
                     div {
@@ -392,6 +456,16 @@ pub(super) fn render_generative_controls(
                         }
                     }
                 }
+                if let Some(seeds) = seed_list_value {
+                    ProviderTextField {
+                        label: "Seeds (comma separated)".to_string(),
+                        value: seeds,
+                        on_commit: {
+                            let on_seed_list_change = on_seed_list_change.clone();
+                            move |next| on_seed_list_change.borrow_mut()(next)
+                        }
+                    }
+                }
                 if let Some(hint) = seed_hint.as_ref() {
                     if seed_hint_is_warning {
                         div { style: "font-size: 10px; color: #f97316;", "{hint}" }
@@ -406,3 +480,236 @@ pub(super) fn render_generative_controls(
         }
     }
 }
+
+/// A/B compare row: pin two versions and flip the preview between them.
+pub(super) fn render_version_compare(
+    compare: Option<VersionCompare>,
+    version_labels: &[(String, String)],
+    on_action: Rc<RefCell<dyn FnMut(CompareAction)>>,
+) -> Element {
+    let button_style = format!(
+        "padding: 6px 10px; background-color: {}; border: 1px solid {}; border-radius: 6px; color: {}; font-size: 11px; cursor: pointer;",
+        BG_SURFACE, BORDER_DEFAULT, TEXT_PRIMARY
+    );
+    let select_style = format!(
+        "flex: 1; min-width: 0; padding: 6px 8px; font-size: 12px; background-color: {}; color: {}; border: 1px solid {}; border-radius: 4px; outline: none;",
+        BG_SURFACE, TEXT_PRIMARY, BORDER_DEFAULT
+    );
+    let Some(compare) = compare else {
+        return rsx! {
+            button {
+                class: "collapse-btn",
+                title: "Pin two versions and flip between them in the preview",
+                style: "{button_style}",
+                onclick: move |_| on_action.borrow_mut()(CompareAction::Start),
+                "Compare A/B"
+            }
+        };
+    };
+    let pinned_a = compare.a.clone().unwrap_or_default();
+    let pinned_b = compare.b.clone().unwrap_or_default();
+    let shown_label = |version: &str| {
+        version_labels
+            .iter()
+            .find(|(value, _)| value == version)
+            .map(|(_, label)| label.clone())
+            .unwrap_or_else(|| version.to_string())
+    };
+    let showing = match (compare.showing, compare.shown_version()) {
+        (CompareSide::A, Some(version)) => format!("Showing A: {}", shown_label(version)),
+        (CompareSide::B, Some(version)) => format!("Showing B: {}", shown_label(version)),
+        (_, None) => "Pick a version".to_string(),
+    };
+    let ready = compare.is_ready();
+    let toggle_opacity = if ready { "1.0" } else { "0.4" };
+    let side_color = |side: CompareSide| if compare.showing == side { ACCENT_PRIMARY } else { TEXT_MUTED };
+    let a_color = side_color(CompareSide::A);
+    let b_color = side_color(CompareSide::B);
+    rsx! {
+        div {
+            style: "
+                display: flex; flex-direction: column; gap: 6px;
+                padding: 8px; border: 1px solid {BORDER_DEFAULT};
+                border-radius: 8px; background-color: {BG_ELEVATED};
+            ",
+            div {
+                style: "display: flex; gap: 6px; align-items: center;",
+                span { style: "width: 14px; font-size: 11px; font-weight: 600; color: {a_color};", "A" }
+                select {
+                    value: "{pinned_a}",
+                    style: "{select_style}",
+                    onchange: {
+                        let on_action = on_action.clone();
+                        move |e: FormEvent| on_action.borrow_mut()(CompareAction::PinA(e.value()))
+                    },
+                    if pinned_a.is_empty() {
+                        option { value: "", disabled: true, "Pick a version" }
+                    }
+                    for (version, label) in version_labels.iter() {
+                        option { value: "{version}", "{label}" }
+                    }
+                }
+            }
+            div {
+                style: "display: flex; gap: 6px; align-items: center;",
+                span { style: "width: 14px; font-size: 11px; font-weight: 600; color: {b_color};", "B" }
+                select {
+                    value: "{pinned_b}",
+                    style: "{select_style}",
+                    onchange: {
+                        let on_action = on_action.clone();
+                        move |e: FormEvent| on_action.borrow_mut()(CompareAction::PinB(e.value()))
+                    },
+                    if pinned_b.is_empty() {
+                        option { value: "", disabled: true, "Pick a version" }
+                    }
+                    for (version, label) in version_labels.iter() {
+                        option { value: "{version}", "{label}" }
+                    }
+                }
+            }
+            div { style: "font-size: 10px; color: {TEXT_DIM};", "{showing}" }
+            div {
+                style: "display: flex; gap: 6px;",
+                button {
+                    class: "collapse-btn",
+                    style: "{button_style} flex: 1; opacity: {toggle_opacity};",
+                    disabled: !ready,
+                    onclick: {
+                        let on_action = on_action.clone();
+                        move |_| on_action.borrow_mut()(CompareAction::Toggle)
+                    },
+                    "Toggle A/B"
+                }
+                button {
+                    class: "collapse-btn",
+                    title: "Exchange the A and B pins",
+                    style: "{button_style}",
+                    onclick: {
+                        let on_action = on_action.clone();
+                        move |_| on_action.borrow_mut()(CompareAction::Swap)
+                    },
+                    "Swap"
+                }
+                button {
+                    class: "collapse-btn",
+                    title: "Stop comparing and restore the version that was active",
+                    style: "{button_style}",
+                    onclick: {
+                        let on_action = on_action.clone();
+                        move |_| on_action.borrow_mut()(CompareAction::End)
+                    },
+                    "Done"
+                }
+            }
+        }
+    }
+}
+
+/// "Regenerate from this version": rerun the selected version with its
+/// recorded inputs and seed, optionally changing one field first.
+pub(super) fn render_regenerate_version(
+    version: &str,
+    fields: &[(String, String, String)],
+    mut open: Signal<bool>,
+    mut field: Signal<String>,
+    mut value: Signal<String>,
+    on_regenerate: Rc<RefCell<dyn FnMut()>>,
+) -> Element {
+    let button_style = format!(
+        "padding: 6px 10px; background-color: {}; border: 1px solid {}; border-radius: 6px; color: {}; font-size: 11px; cursor: pointer;",
+        BG_SURFACE, BORDER_DEFAULT, TEXT_PRIMARY
+    );
+    if !open() {
+        let fields = fields.to_vec();
+        return rsx! {
+            button {
+                class: "collapse-btn",
+                title: "Queue {version} again with the same seed, changing one input",
+                style: "{button_style}",
+                onclick: move |_| {
+                    // Start from the first field's recorded value
+                    if let Some((name, _, recorded)) = fields.first() {
+                        field.set(name.clone());
+                        value.set(recorded.clone());
+                    }
+                    open.set(true);
+                },
+                "Regenerate from {version}..."
+            }
+        };
+    }
+    let selected_field = field();
+    let field_value = value();
+    let field_options = fields.to_vec();
+    rsx! {
+        div {
+            style: "
+                display: flex; flex-direction: column; gap: 6px;
+                padding: 8px; border: 1px solid {BORDER_DEFAULT};
+                border-radius: 8px; background-color: {BG_ELEVATED};
+            ",
+            div {
+                style: "font-size: 10px; color: {TEXT_DIM};",
+                "Regenerate {version} with the same seed, changing:"
+            }
+            select {
+                value: "{selected_field}",
+                style: "
+                    width: 100%; padding: 6px 8px; font-size: 12px;
+                    background-color: {BG_SURFACE}; color: {TEXT_PRIMARY};
+                    border: 1px solid {BORDER_DEFAULT}; border-radius: 4px;
+                    outline: none;
+                ",
+                onchange: move |e: FormEvent| {
+                    let name = e.value();
+                    let recorded = field_options
+                        .iter()
+                        .find(|(option, _, _)| *option == name)
+                        .map(|(_, _, recorded)| recorded.clone())
+                        .unwrap_or_default();
+                    field.set(name);
+                    value.set(recorded);
+                },
+                for (name, label, _) in fields.iter() {
+                    option { value: "{name}", "{label}" }
+                }
+            }
+            StableTextInput {
+                id: "generative-regenerate-value".to_string(),
+                value: field_value,
+                placeholder: Some("New value".to_string()),
+                style: None,
+                on_change: move |next: String| value.set(next),
+                on_blur: move |_| {},
+                on_keydown: {
+                    let on_regenerate = on_regenerate.clone();
+                    move |e: KeyboardEvent| {
+                        if e.key() == Key::Enter {
+                            on_regenerate.borrow_mut()();
+                        }
+                    }
+                },
+                autofocus: false,
+            }
+            div {
+                style: "display: flex; gap: 6px;",
+                button {
+                    class: "collapse-btn",
+                    style: "{button_style} flex: 1; background-color: {ACCENT_VIDEO}; border: none; color: white;",
+                    onclick: {
+                        let on_regenerate = on_regenerate.clone();
+                        move |_| on_regenerate.borrow_mut()()
+                    },
+                    "Queue"
+                }
+                button {
+                    class: "collapse-btn",
+                    style: "{button_style}",
+                    onclick: move |_| open.set(false),
+                    "Cancel"
+                }
+            }
+        }
+    }
+}
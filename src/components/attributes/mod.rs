@@ -2,4 +2,4 @@ mod attributes_panel;
 mod generative_controls;
 mod provider_inputs;
 
-pub use attributes_panel::AttributesPanelContent;
+pub use attributes_panel::{generate_clips, AttributesPanelContent};
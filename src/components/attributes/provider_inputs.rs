@@ -5,11 +5,12 @@ use dioxus::prelude::*;
 
 use crate::components::common::{
     ProviderFloatField, ProviderIntegerField, ProviderTextAreaField, ProviderTextField,
+    StableTextInput,
 };
 use crate::constants::*;
 use crate::state::{
     input_value_as_bool, input_value_as_f64, input_value_as_i64, input_value_as_string,
-    GenerativeConfig, ProviderEntry, ProviderInputType,
+    GenerativeConfig, ProviderEntry, ProviderInputField, ProviderInputType,
 };
 
 pub(super) fn render_provider_inputs(
@@ -18,6 +19,7 @@ pub(super) fn render_provider_inputs(
     config_snapshot: &GenerativeConfig,
     version_key: &str,
     set_input_value: Rc<RefCell<dyn FnMut(String, serde_json::Value)>>,
+    on_randomize_input_change: Rc<RefCell<dyn FnMut(String, bool)>>,
 ) -> Element {
     let version_key = if version_key.trim().is_empty() {
         "current"
@@ -26,6 +28,7 @@ pub(super) fn render_provider_inputs(
     };
     rsx! {
         div {
+            id: "generative-provider-inputs",
             style: "
                 display: flex; flex-direction: column; gap: 10px;
                 padding: 10px; background-color: {BG_SURFACE};
@@ -60,6 +63,11 @@ pub(super) fn render_provider_inputs(
                             let input_type = input.input_type.clone();
                             let field_key = format!("{}::{}", version_key, input.name);
                             let set_input_value = set_input_value.clone();
+                            let randomize_toggle = render_randomize_toggle(
+                                input,
+                                config_snapshot.batch.randomized_inputs.contains(&input.name),
+                                on_randomize_input_change.clone(),
+                            );
                             match input_type {
                                 ProviderInputType::Text => {
                                     let value = current_value
@@ -102,17 +110,23 @@ pub(super) fn render_provider_inputs(
                                         .and_then(input_value_as_f64)
                                         .unwrap_or(0.0);
                                     rsx! {
-                                        ProviderFloatField {
+                                        div {
                                             key: "{field_key}",
-                                            label: label.clone(),
-                                            value,
-                                            step: "0.1",
-                                            on_commit: move |next| {
-                                                if let Some(number) = serde_json::Number::from_f64(next) {
-                                                    set_input_value
-                                                        .borrow_mut()(input_name.clone(), serde_json::Value::Number(number));
+                                            style: "display: flex; flex-direction: column; gap: 4px;",
+                                            ProviderFloatField {
+                                                label: label.clone(),
+                                                value,
+                                                step: "0.1",
+                                                min: input.ui.as_ref().and_then(|ui| ui.min),
+                                                max: input.ui.as_ref().and_then(|ui| ui.max),
+                                                on_commit: move |next| {
+                                                    if let Some(number) = serde_json::Number::from_f64(next) {
+                                                        set_input_value
+                                                            .borrow_mut()(input_name.clone(), serde_json::Value::Number(number));
+                                                    }
                                                 }
                                             }
+                                            {randomize_toggle}
                                         }
                                     }
                                 }
@@ -122,14 +136,18 @@ pub(super) fn render_provider_inputs(
                                         .and_then(input_value_as_i64)
                                         .unwrap_or(0);
                                     rsx! {
-                                        ProviderIntegerField {
+                                        div {
                                             key: "{field_key}",
-                                            label: label.clone(),
-                                            value,
-                                            on_commit: move |next: i64| {
-                                                set_input_value
-                                                    .borrow_mut()(input_name.clone(), serde_json::Value::Number(next.into()));
+                                            style: "display: flex; flex-direction: column; gap: 4px;",
+                                            ProviderIntegerField {
+                                                label: label.clone(),
+                                                value,
+                                                on_commit: move |next: i64| {
+                                                    set_input_value
+                                                        .borrow_mut()(input_name.clone(), serde_json::Value::Number(next.into()));
+                                                }
                                             }
+                                            {randomize_toggle}
                                         }
                                     }
                                 }
@@ -211,3 +229,118 @@ pub(super) fn render_provider_inputs(
         }
     }
 }
+
+/// Preset picker and "save current inputs as" row above the provider inputs.
+pub(super) fn render_input_presets(
+    preset_names: &[String],
+    mut preset_name: Signal<String>,
+    on_apply_preset: Rc<RefCell<dyn FnMut(FormEvent)>>,
+    on_save_preset: Rc<RefCell<dyn FnMut()>>,
+) -> Element {
+    let has_presets = !preset_names.is_empty();
+    rsx! {
+        div {
+            style: "
+                display: flex; flex-direction: column; gap: 6px;
+                padding: 10px; background-color: {BG_SURFACE};
+                border: 1px solid {BORDER_SUBTLE}; border-radius: 6px;
+            ",
+            div {
+                style: "font-size: 10px; color: {TEXT_DIM}; text-transform: uppercase; letter-spacing: 0.5px;",
+                "Presets"
+            }
+            if has_presets {
+                select {
+                    // Always shows the placeholder so picking the same preset again re-applies it
+                    value: "",
+                    style: "
+                        width: 100%; padding: 6px 8px; font-size: 12px;
+                        background-color: {BG_SURFACE}; color: {TEXT_PRIMARY};
+                        border: 1px solid {BORDER_DEFAULT}; border-radius: 4px;
+                        outline: none;
+                    ",
+                    onchange: {
+                        let on_apply_preset = on_apply_preset.clone();
+                        move |e| on_apply_preset.borrow_mut()(e)
+                    },
+                    option { value: "", disabled: true, "Apply preset..." }
+                    for name in preset_names.iter() {
+                        option { value: "{name}", "{name}" }
+                    }
+                }
+            }
+            div {
+                style: "display: flex; gap: 6px; align-items: center;",
+                div {
+                    style: "flex: 1; min-width: 0;",
+                    StableTextInput {
+                        id: "generative-preset-name".to_string(),
+                        value: preset_name(),
+                        placeholder: Some("Preset name".to_string()),
+                        style: None,
+                        on_change: move |next: String| preset_name.set(next),
+                        on_blur: move |_| {},
+                        on_keydown: {
+                            let on_save_preset = on_save_preset.clone();
+                            move |e: KeyboardEvent| {
+                                if e.key() == Key::Enter {
+                                    on_save_preset.borrow_mut()();
+                                }
+                            }
+                        },
+                        autofocus: false,
+                    }
+                }
+                button {
+                    class: "collapse-btn",
+                    title: "Save the current input values as a preset for this provider",
+                    style: "
+                        padding: 6px 10px;
+                        background-color: {BG_SURFACE};
+                        border: 1px solid {BORDER_DEFAULT};
+                        border-radius: 4px;
+                        color: {TEXT_PRIMARY}; font-size: 11px; cursor: pointer;
+                    ",
+                    onclick: {
+                        let on_save_preset = on_save_preset.clone();
+                        move |_| on_save_preset.borrow_mut()()
+                    },
+                    "Save"
+                }
+            }
+        }
+    }
+}
+
+/// "Randomize in batch" switch under a numeric input, shown only when the
+/// input has a range to sample from.
+fn render_randomize_toggle(
+    input: &ProviderInputField,
+    randomized: bool,
+    on_change: Rc<RefCell<dyn FnMut(String, bool)>>,
+) -> Element {
+    let Some((min, max, _)) = input.randomization_bounds() else {
+        return rsx! {};
+    };
+    let input_name = input.name.clone();
+    let color = if randomized { ACCENT_PRIMARY } else { TEXT_DIM };
+    rsx! {
+        div {
+            style: "display: flex; align-items: center; justify-content: space-between; gap: 8px;",
+            span { style: "font-size: 10px; color: {TEXT_DIM};", "Randomize in batch ({min}-{max})" }
+            button {
+                class: "collapse-btn",
+                title: "Draw a new value for each job in a batch",
+                style: "
+                    padding: 2px 8px;
+                    background-color: {BG_SURFACE};
+                    border: 1px solid {BORDER_DEFAULT};
+                    border-radius: 999px;
+                    color: {color}; font-size: 10px; cursor: pointer;
+                ",
+                onclick: move |_| on_change.borrow_mut()(input_name.clone(), !randomized),
+                if randomized { "On" } else { "Off" }
+            }
+        }
+    }
+}
@@ -196,6 +196,8 @@ pub fn StableNumberInput(
     on_change: EventHandler<String>,
     on_blur: EventHandler<FocusEvent>,
     on_keydown: EventHandler<KeyboardEvent>,
+    /// Wheel over the hovered input; when set, the page does not scroll underneath it.
+    #[props(default = None)] on_wheel: Option<EventHandler<WheelEvent>>,
 ) -> Element {
     let mut last_prop_value = use_signal(|| value.clone());
     let mut key_gen = use_signal(|| 0u32);
@@ -254,6 +256,13 @@ pub fn StableNumberInput(
             },
             onblur: move |e| on_blur.call(e),
             onkeydown: move |e| on_keydown.call(e),
+            onwheel: move |e| {
+                if let Some(handler) = on_wheel.as_ref() {
+                    e.prevent_default();
+                    e.stop_propagation();
+                    handler.call(e);
+                }
+            },
         }
     }
 }
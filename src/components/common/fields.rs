@@ -5,6 +5,129 @@ use crate::components::common::{StableNumberInput, StableTextArea, StableTextInp
 use crate::constants::*;
 use crate::utils::{parse_f32_input, parse_f64_input, parse_i64_input};
 
+/// Signed amount one wheel notch moves a numeric field by, or `None` for an empty delta.
+/// Scrolling up increments; holding Shift moves by ten steps.
+pub(crate) fn wheel_step_delta(e: &WheelEvent, step: f64) -> Option<f64> {
+    let delta = e.delta().strip_units();
+    // Shift+wheel is reported as horizontal scrolling on some platforms.
+    let amount = if delta.y != 0.0 { delta.y } else { delta.x };
+    wheel_step_amount(amount, step, e.modifiers().shift())
+}
+
+fn wheel_step_amount(amount: f64, step: f64, shift: bool) -> Option<f64> {
+    if amount == 0.0 {
+        return None;
+    }
+    let multiplier = if shift { 10.0 } else { 1.0 };
+    Some(-amount.signum() * step * multiplier)
+}
+
+/// Signed amount an Up/Down arrow press moves a numeric field by, or `None` for
+/// other keys. Shift moves ten steps and Alt a tenth of a step.
+pub(crate) fn arrow_step_delta(e: &KeyboardEvent, step: f64) -> Option<f64> {
+    let direction = match e.key() {
+        Key::ArrowUp => 1.0,
+        Key::ArrowDown => -1.0,
+        _ => return None,
+    };
+    let modifiers = e.modifiers();
+    Some(arrow_step_amount(direction, step, modifiers.shift(), modifiers.alt()))
+}
+
+fn arrow_step_amount(direction: f64, step: f64, shift: bool, alt: bool) -> f64 {
+    let multiplier = if shift {
+        10.0
+    } else if alt {
+        0.1
+    } else {
+        1.0
+    };
+    direction * step * multiplier
+}
+
+/// `value` moved by `delta` and clamped. Rounded to 1e-6 so repeated
+/// fractional steps don't accumulate float noise.
+pub(crate) fn step_numeric_value(value: f64, delta: f64, clamp_min: Option<f64>, clamp_max: Option<f64>) -> f64 {
+    let mut next = ((value + delta) * 1e6).round() / 1e6;
+    if let Some(min) = clamp_min {
+        next = next.max(min);
+    }
+    if let Some(max) = clamp_max {
+        next = next.min(max);
+    }
+    next
+}
+
+/// Horizontal drag distance that moves a scrubbed field by one step.
+const SCRUB_PIXELS_PER_STEP: f64 = 2.0;
+
+/// A drag on a numeric field's label that scrubs its value.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct ScrubDrag {
+    last_x: f64,
+    value: f64,
+    moved: bool,
+}
+
+impl ScrubDrag {
+    pub(crate) fn new(start_x: f64, start_value: f64) -> Self {
+        Self {
+            last_x: start_x,
+            value: start_value,
+            moved: false,
+        }
+    }
+
+    /// Follow the pointer to `x`. Movement is measured from the previous
+    /// position, so changing modifiers mid-drag changes the speed without a
+    /// jump. Returns the new value, or `None` when it didn't change.
+    pub(crate) fn update(
+        &mut self,
+        x: f64,
+        step: f64,
+        shift: bool,
+        alt: bool,
+        clamp_min: Option<f64>,
+        clamp_max: Option<f64>,
+    ) -> Option<f64> {
+        let steps = (x - self.last_x) / SCRUB_PIXELS_PER_STEP;
+        self.last_x = x;
+        let next = step_numeric_value(
+            self.value,
+            arrow_step_amount(steps, step, shift, alt),
+            clamp_min,
+            clamp_max,
+        );
+        if next == self.value {
+            return None;
+        }
+        self.value = next;
+        self.moved = true;
+        Some(next)
+    }
+
+    /// End the drag. Returns the value to commit, or `None` for a plain click.
+    pub(crate) fn finish(self) -> Option<f64> {
+        self.moved.then_some(self.value)
+    }
+}
+
+/// Re-focus an input after a committed value remounts it, so held arrow keys keep stepping.
+fn refocus_input(id: &str) {
+    let _ = document::eval(&format!(
+        "setTimeout(() => document.getElementById('{}')?.focus(), 0);",
+        id
+    ));
+}
+
+fn parse_step(step: &str) -> f64 {
+    step.trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|value| *value > 0.0)
+        .unwrap_or(1.0)
+}
+
 #[component]
 pub fn NumericField(
     label: &'static str,
@@ -14,6 +137,8 @@ pub fn NumericField(
     clamp_max: Option<f32>,
     on_commit: EventHandler<f32>,
     #[props(default = None)] on_change: Option<EventHandler<f32>>,
+    /// When set, a reset button (and double-clicking the label) restores this value.
+    #[props(default = None)] default_value: Option<f32>,
 ) -> Element {
     let mut text = use_signal(|| format!("{:.2}", value));
     let mut last_prop_value = use_signal(|| value);
@@ -51,12 +176,84 @@ pub fn NumericField(
         commit_on_blur();
     };
 
+    let step_value = parse_step(step);
+    let input_id = format!("numeric-field-{}", label.replace(' ', "-"));
+    let keydown_input_id = input_id.clone();
     let on_keydown = move |e: KeyboardEvent| {
         if e.key() == Key::Enter {
             commit_on_key();
+            return;
         }
+        let Some(delta) = arrow_step_delta(&e, step_value) else {
+            return;
+        };
+        e.prevent_default();
+        let current = parse_f32_input(&text(), last_prop_value()) as f64;
+        let next = step_numeric_value(
+            current,
+            delta,
+            clamp_min.map(f64::from),
+            clamp_max.map(f64::from),
+        ) as f32;
+        text.set(format!("{:.2}", next));
+        last_prop_value.set(next);
+        on_commit.call(next);
+        refocus_input(&keydown_input_id);
     };
     let on_change_handler = on_change.clone();
+    let mut scrub = use_signal(|| None::<ScrubDrag>);
+    let on_scrub_start = move |e: MouseEvent| {
+        if !e.trigger_button().is_some_and(|btn| format!("{:?}", btn) == "Primary") {
+            return;
+        }
+        e.prevent_default();
+        let current = parse_f32_input(&text(), last_prop_value()) as f64;
+        scrub.set(Some(ScrubDrag::new(e.client_coordinates().x, current)));
+    };
+    let on_scrub_move = move |e: MouseEvent| {
+        let Some(mut drag) = scrub() else {
+            return;
+        };
+        let modifiers = e.modifiers();
+        let next = drag.update(
+            e.client_coordinates().x,
+            step_value,
+            modifiers.shift(),
+            modifiers.alt(),
+            clamp_min.map(f64::from),
+            clamp_max.map(f64::from),
+        );
+        scrub.set(Some(drag));
+        if let Some(next) = next {
+            let next = next as f32;
+            text.set(format!("{:.2}", next));
+            // Live updates go through on_change; undo is recorded by the commit on release
+            if let Some(handler) = on_change_handler.as_ref() {
+                handler.call(next);
+            }
+        }
+    };
+    let on_scrub_end = move |_| {
+        let Some(drag) = scrub() else {
+            return;
+        };
+        scrub.set(None);
+        if let Some(value) = drag.finish() {
+            let value = value as f32;
+            text.set(format!("{:.2}", value));
+            last_prop_value.set(value);
+            on_commit.call(value);
+        }
+    };
+    let mut reset_to_default = move || {
+        let Some(default) = default_value else {
+            return;
+        };
+        text.set(format!("{:.2}", default));
+        last_prop_value.set(default);
+        on_commit.call(default);
+    };
+    let is_default = default_value.map_or(true, |default| (value - default).abs() <= 0.0001);
     let on_change = move |next_value: String| {
         text.set(next_value.clone());
         if let Some(handler) = on_change_handler.as_ref() {
@@ -70,9 +267,23 @@ pub fn NumericField(
             handler.call(parsed);
         }
     };
+    let on_wheel = move |e: WheelEvent| {
+        let Some(delta) = wheel_step_delta(&e, step_value) else {
+            return;
+        };
+        let current = parse_f32_input(&text(), last_prop_value()) as f64;
+        let next = step_numeric_value(
+            current,
+            delta,
+            clamp_min.map(f64::from),
+            clamp_max.map(f64::from),
+        ) as f32;
+        text.set(format!("{:.2}", next));
+        last_prop_value.set(next);
+        on_commit.call(next);
+    };
 
     let text_value = text();
-    let input_id = format!("numeric-field-{}", label.replace(' ', "-"));
     let input_style = format!(
         "
             width: 100%; min-width: 0; box-sizing: border-box;
@@ -88,7 +299,35 @@ pub fn NumericField(
     rsx! {
         div {
             style: "display: flex; flex-direction: column; gap: 4px; min-width: 0;",
-            span { style: "font-size: 10px; color: {TEXT_MUTED};", "{label}" }
+            div {
+                style: "display: flex; align-items: center; gap: 4px; min-width: 0;",
+                span {
+                    style: "font-size: 10px; color: {TEXT_MUTED}; cursor: ew-resize; user-select: none;",
+                    title: "Drag to scrub (Shift: faster, Alt: finer)",
+                    onmousedown: on_scrub_start,
+                    ondoubleclick: move |_| reset_to_default(),
+                    "{label}"
+                }
+                if !is_default {
+                    button {
+                        style: "
+                            margin-left: auto; padding: 0 4px; font-size: 10px; line-height: 14px;
+                            background: transparent; color: {TEXT_DIM};
+                            border: none; cursor: pointer;
+                        ",
+                        title: "Reset to default",
+                        onclick: move |_| reset_to_default(),
+                        "↺"
+                    }
+                }
+            }
+            if scrub().is_some() {
+                div {
+                    style: "position: fixed; top: 0; left: 0; right: 0; bottom: 0; z-index: 9999; cursor: ew-resize;",
+                    onmousemove: on_scrub_move,
+                    onmouseup: on_scrub_end,
+                }
+            }
             StableNumberInput {
                 id: input_id,
                 value: text_value,
@@ -100,6 +339,7 @@ pub fn NumericField(
                 on_change: on_change,
                 on_blur: on_blur,
                 on_keydown: on_keydown,
+                on_wheel: on_wheel,
             }
         }
     }
@@ -247,6 +487,9 @@ pub fn ProviderFloatField(
     label: String,
     value: f64,
     step: &'static str,
+    /// Bounds for wheel and arrow steps
+    #[props(default = None)] min: Option<f64>,
+    #[props(default = None)] max: Option<f64>,
     on_commit: EventHandler<f64>,
 ) -> Element {
     let mut text = use_signal(|| format!("{:.2}", value));
@@ -274,7 +517,18 @@ pub fn ProviderFloatField(
 
     let mut commit_on_blur = make_commit();
     let mut commit_on_key = make_commit();
+    let step_value = parse_step(step);
+    let on_wheel = move |e: WheelEvent| {
+        let Some(delta) = wheel_step_delta(&e, step_value) else {
+            return;
+        };
+        let next = step_numeric_value(parse_f64_input(&text(), last_prop_value()), delta, min, max);
+        text.set(format!("{:.2}", next));
+        last_prop_value.set(next);
+        on_commit.call(next);
+    };
     let input_id = format!("provider-float-field-{}", label.replace(' ', "-").to_lowercase());
+    let keydown_input_id = input_id.clone();
     let input_style = format!(
         "
             width: 100%; min-width: 0; box-sizing: border-box;
@@ -297,16 +551,27 @@ pub fn ProviderFloatField(
                 value: text_value,
                 placeholder: None,
                 style: Some(input_style),
-                min: None,
-                max: None,
+                min: min.map(|v| v.to_string()),
+                max: max.map(|v| v.to_string()),
                 step: Some(step.to_string()),
                 on_change: move |v| text.set(v),
                 on_blur: move |_| commit_on_blur(),
                 on_keydown: move |e: KeyboardEvent| {
                     if e.key() == Key::Enter {
                         commit_on_key();
+                        return;
                     }
+                    let Some(delta) = arrow_step_delta(&e, step_value) else {
+                        return;
+                    };
+                    e.prevent_default();
+                    let next = step_numeric_value(parse_f64_input(&text(), last_prop_value()), delta, min, max);
+                    text.set(format!("{:.2}", next));
+                    last_prop_value.set(next);
+                    on_commit.call(next);
+                    refocus_input(&keydown_input_id);
                 },
+                on_wheel: on_wheel,
             }
         }
     }
@@ -343,7 +608,17 @@ pub fn ProviderIntegerField(
 
     let mut commit_on_blur = make_commit();
     let mut commit_on_key = make_commit();
+    let on_wheel = move |e: WheelEvent| {
+        let Some(delta) = wheel_step_delta(&e, 1.0) else {
+            return;
+        };
+        let next = parse_i64_input(&text(), last_prop_value()).saturating_add(delta as i64);
+        text.set(next.to_string());
+        last_prop_value.set(next);
+        on_commit.call(next);
+    };
     let input_id = format!("provider-integer-field-{}", label.replace(' ', "-").to_lowercase());
+    let keydown_input_id = input_id.clone();
     let input_style = format!(
         "
             width: 100%; min-width: 0; box-sizing: border-box;
@@ -374,9 +649,98 @@ pub fn ProviderIntegerField(
                 on_keydown: move |e: KeyboardEvent| {
                     if e.key() == Key::Enter {
                         commit_on_key();
+                        return;
                     }
+                    let Some(delta) = arrow_step_delta(&e, 1.0) else {
+                        return;
+                    };
+                    e.prevent_default();
+                    // Alt's tenth of a step still moves an integer by one
+                    let delta = if delta.abs() < 1.0 { delta.signum() } else { delta.round() };
+                    let next = parse_i64_input(&text(), last_prop_value()).saturating_add(delta as i64);
+                    text.set(next.to_string());
+                    last_prop_value.set(next);
+                    on_commit.call(next);
+                    refocus_input(&keydown_input_id);
                 },
+                on_wheel: on_wheel,
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arrow_step_modifiers() {
+        assert_eq!(arrow_step_amount(1.0, 0.5, false, false), 0.5);
+        assert_eq!(arrow_step_amount(-1.0, 0.5, false, false), -0.5);
+        assert_eq!(arrow_step_amount(1.0, 0.5, true, false), 5.0);
+        assert!((arrow_step_amount(-1.0, 0.5, false, true) + 0.05).abs() < 1e-12);
+        // Shift wins when both are held
+        assert_eq!(arrow_step_amount(1.0, 1.0, true, true), 10.0);
+    }
+
+    #[test]
+    fn test_step_numeric_value_clamps() {
+        assert_eq!(step_numeric_value(1.0, 0.5, None, None), 1.5);
+        assert_eq!(step_numeric_value(0.95, 0.1, Some(0.0), Some(1.0)), 1.0);
+        assert_eq!(step_numeric_value(0.05, -0.1, Some(0.0), Some(1.0)), 0.0);
+        // Repeated tenth steps land on clean values
+        let mut value = 0.0;
+        for _ in 0..3 {
+            value = step_numeric_value(value, 0.1, None, None);
+        }
+        assert_eq!(value, 0.3);
+    }
+
+    #[test]
+    fn test_wheel_step_clamps_to_bounds() {
+        // Scrolling up (negative delta) increments
+        assert_eq!(wheel_step_amount(-100.0, 0.1, false), Some(0.1));
+        assert_eq!(wheel_step_amount(3.0, 0.1, false), Some(-0.1));
+        assert_eq!(wheel_step_amount(-1.0, 0.1, true), Some(1.0));
+        assert_eq!(wheel_step_amount(0.0, 0.1, false), None);
+
+        let step = |value: f64, amount: f64, shift: bool| {
+            let delta = wheel_step_amount(amount, 0.1, shift).unwrap();
+            step_numeric_value(value, delta, Some(0.0), Some(1.0))
+        };
+        assert_eq!(step(0.5, -1.0, false), 0.6);
+        assert_eq!(step(0.95, -1.0, false), 1.0);
+        assert_eq!(step(0.5, -1.0, true), 1.0);
+        assert_eq!(step(0.05, 1.0, false), 0.0);
+        assert_eq!(step(0.5, 1.0, true), 0.0);
+    }
+
+    #[test]
+    fn test_scrub_pixels_to_value() {
+        let mut drag = ScrubDrag::new(100.0, 1.0);
+        // Two pixels per step
+        assert_eq!(drag.update(110.0, 0.5, false, false, None, None), Some(3.5));
+        // Shift is ten times faster, Alt ten times finer
+        assert_eq!(drag.update(112.0, 0.5, true, false, None, None), Some(8.5));
+        assert_eq!(drag.update(102.0, 0.5, false, true, None, None), Some(8.25));
+        // Clamped at the bounds, and no change reports nothing
+        assert_eq!(drag.update(200.0, 0.5, false, false, None, Some(10.0)), Some(10.0));
+        assert_eq!(drag.update(240.0, 0.5, false, false, None, Some(10.0)), None);
+    }
+
+    #[test]
+    fn test_scrub_commits_once_on_release() {
+        let mut drag = ScrubDrag::new(0.0, 0.0);
+        for x in [4.0, 8.0, 12.0] {
+            assert!(drag.update(x, 1.0, false, false, Some(0.0), None).is_some());
+        }
+        assert_eq!(drag.finish(), Some(6.0));
+
+        // A click without movement commits nothing
+        let drag = ScrubDrag::new(50.0, 2.0);
+        assert_eq!(drag.finish(), None);
+        let mut drag = ScrubDrag::new(50.0, 0.0);
+        assert_eq!(drag.update(40.0, 1.0, false, false, Some(0.0), None), None);
+        assert_eq!(drag.finish(), None);
+    }
+}
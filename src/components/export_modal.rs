@@ -0,0 +1,325 @@
+use dioxus::prelude::*;
+
+use crate::components::common::StableNumberInput;
+use crate::constants::*;
+use crate::state::{
+    export_presets, matching_export_preset, ExportAudioCodec, ExportContainer, ExportOptions,
+    ExportRateControl, ExportVideoCodec,
+};
+
+const CUSTOM_PRESET_KEY: &str = "custom";
+
+/// Export settings: preset picker plus container, codec, and rate controls.
+#[component]
+pub fn ExportSettingsModal(
+    options: ExportOptions,
+    on_save: EventHandler<ExportOptions>,
+    on_close: EventHandler<MouseEvent>,
+) -> Element {
+    let mut container = use_signal(|| options.container);
+    let mut video_codec = use_signal(|| options.video_codec);
+    let mut audio_codec = use_signal(|| options.audio_codec);
+    let mut use_bitrate = use_signal(|| matches!(options.rate_control, ExportRateControl::Bitrate(_)));
+    let mut rate_value = use_signal(|| match options.rate_control {
+        ExportRateControl::Crf(crf) => crf.to_string(),
+        ExportRateControl::Bitrate(kbps) => kbps.to_string(),
+    });
+    let mut audio_bitrate = use_signal(|| options.audio_bitrate_kbps.to_string());
+
+    let mut apply_options = move |next: ExportOptions| {
+        container.set(next.container);
+        video_codec.set(next.video_codec);
+        audio_codec.set(next.audio_codec);
+        match next.rate_control {
+            ExportRateControl::Crf(crf) => {
+                use_bitrate.set(false);
+                rate_value.set(crf.to_string());
+            }
+            ExportRateControl::Bitrate(kbps) => {
+                use_bitrate.set(true);
+                rate_value.set(kbps.to_string());
+            }
+        }
+        audio_bitrate.set(next.audio_bitrate_kbps.to_string());
+    };
+
+    let parsed = parse_export_options(
+        container(),
+        video_codec(),
+        audio_codec(),
+        use_bitrate(),
+        &rate_value(),
+        &audio_bitrate(),
+    )
+    .and_then(|options| options.validate().map(|_| options));
+    let preset_key = parsed
+        .as_ref()
+        .ok()
+        .and_then(matching_export_preset)
+        .unwrap_or(CUSTOM_PRESET_KEY);
+    let (error, ffmpeg_args) = match parsed.as_ref() {
+        Ok(options) => (None, options.ffmpeg_codec_args().join(" ")),
+        Err(err) => (Some(err.clone()), String::new()),
+    };
+    let save_enabled = parsed.is_ok();
+    let save_opacity = if save_enabled { "1" } else { "0.5" };
+    let save_cursor = if save_enabled { "pointer" } else { "default" };
+    let rate_label = if use_bitrate() { "Video Bitrate (kbps)" } else { "Quality (CRF)" };
+    let rate_mode_key = if use_bitrate() { "bitrate" } else { "crf" };
+    let rate_disabled = video_codec() == ExportVideoCodec::ProRes;
+    let audio_bitrate_disabled = !audio_codec().uses_bitrate();
+
+    let select_style = format!(
+        "
+            width: 100%; padding: 6px 8px; font-size: 11px;
+            background-color: {}; color: {};
+            border: 1px solid {}; border-radius: 6px;
+        ",
+        BG_SURFACE, TEXT_PRIMARY, BORDER_DEFAULT
+    );
+    let input_style = select_style.clone();
+
+    rsx! {
+        div {
+            style: "
+                position: fixed; inset: 0;
+                background: rgba(0, 0, 0, 0.45);
+                backdrop-filter: blur(6px);
+                -webkit-backdrop-filter: blur(6px);
+                z-index: 2000;
+            ",
+            onclick: move |e| on_close.call(e),
+        }
+        div {
+            style: "
+                position: fixed; top: 50%; left: 50%;
+                transform: translate(-50%, -50%);
+                width: 420px;
+                padding: 14px;
+                background-color: {BG_ELEVATED};
+                border: 1px solid {BORDER_DEFAULT};
+                border-radius: 10px;
+                box-shadow: 0 14px 30px rgba(0,0,0,0.45);
+                display: flex; flex-direction: column; gap: 12px;
+                z-index: 2001;
+            ",
+            onclick: move |e| e.stop_propagation(),
+            div {
+                style: "display: flex; flex-direction: column; gap: 4px;",
+                span { style: "font-size: 13px; color: {TEXT_PRIMARY};", "Export Settings" }
+                span { style: "font-size: 10px; color: {TEXT_DIM};", "Saved with the project and used for timeline exports." }
+            }
+            div {
+                style: "display: flex; flex-direction: column; gap: 6px;",
+                span { style: "font-size: 10px; color: {TEXT_MUTED};", "Preset" }
+                select {
+                    value: "{preset_key}",
+                    style: "{select_style}",
+                    onchange: move |e| {
+                        let key = e.value();
+                        if let Some(preset) = export_presets().into_iter().find(|preset| preset.name == key) {
+                            apply_options(preset.options);
+                        }
+                    },
+                    for preset in export_presets() {
+                        option { value: "{preset.name}", "{preset.name}" }
+                    }
+                    option { value: CUSTOM_PRESET_KEY, "Custom" }
+                }
+            }
+            div {
+                style: "display: grid; grid-template-columns: 1fr 1fr; gap: 10px;",
+                div {
+                    style: "display: flex; flex-direction: column; gap: 6px;",
+                    span { style: "font-size: 10px; color: {TEXT_MUTED};", "Container" }
+                    select {
+                        value: "{container().key()}",
+                        style: "{select_style}",
+                        onchange: move |e| {
+                            if let Some(next) = ExportContainer::from_key(&e.value()) {
+                                container.set(next);
+                            }
+                        },
+                        for value in ExportContainer::ALL {
+                            option { value: "{value.key()}", "{value.label()}" }
+                        }
+                    }
+                }
+                div {
+                    style: "display: flex; flex-direction: column; gap: 6px;",
+                    span { style: "font-size: 10px; color: {TEXT_MUTED};", "Video Codec" }
+                    select {
+                        value: "{video_codec().key()}",
+                        style: "{select_style}",
+                        onchange: move |e| {
+                            if let Some(next) = ExportVideoCodec::from_key(&e.value()) {
+                                video_codec.set(next);
+                            }
+                        },
+                        for value in ExportVideoCodec::ALL {
+                            option { value: "{value.key()}", "{value.label()}" }
+                        }
+                    }
+                }
+                div {
+                    style: "display: flex; flex-direction: column; gap: 6px;",
+                    span { style: "font-size: 10px; color: {TEXT_MUTED};", "Rate Control" }
+                    select {
+                        value: "{rate_mode_key}",
+                        style: "{select_style}",
+                        disabled: rate_disabled,
+                        onchange: move |e| {
+                            let next = e.value() == "bitrate";
+                            if next != use_bitrate() {
+                                use_bitrate.set(next);
+                                rate_value.set(if next { "8000".to_string() } else { "20".to_string() });
+                            }
+                        },
+                        option { value: "crf", "Constant Quality" }
+                        option { value: "bitrate", "Target Bitrate" }
+                    }
+                }
+                div {
+                    style: "display: flex; flex-direction: column; gap: 6px;",
+                    span { style: "font-size: 10px; color: {TEXT_MUTED};", "{rate_label}" }
+                    if rate_disabled {
+                        span { style: "font-size: 10px; color: {TEXT_DIM}; padding: 7px 0;", "Set by ProRes profile" }
+                    } else {
+                        StableNumberInput {
+                            id: "export-rate-value".to_string(),
+                            value: rate_value(),
+                            placeholder: None,
+                            style: Some(input_style.clone()),
+                            min: Some("0".to_string()),
+                            max: None,
+                            step: Some("1".to_string()),
+                            on_change: move |v: String| rate_value.set(v),
+                            on_blur: move |_| {},
+                            on_keydown: move |_| {},
+                        }
+                    }
+                }
+                div {
+                    style: "display: flex; flex-direction: column; gap: 6px;",
+                    span { style: "font-size: 10px; color: {TEXT_MUTED};", "Audio Codec" }
+                    select {
+                        value: "{audio_codec().key()}",
+                        style: "{select_style}",
+                        onchange: move |e| {
+                            if let Some(next) = ExportAudioCodec::from_key(&e.value()) {
+                                audio_codec.set(next);
+                            }
+                        },
+                        for value in ExportAudioCodec::ALL {
+                            option { value: "{value.key()}", "{value.label()}" }
+                        }
+                    }
+                }
+                div {
+                    style: "display: flex; flex-direction: column; gap: 6px;",
+                    span { style: "font-size: 10px; color: {TEXT_MUTED};", "Audio Bitrate (kbps)" }
+                    if audio_bitrate_disabled {
+                        span { style: "font-size: 10px; color: {TEXT_DIM}; padding: 7px 0;", "Uncompressed" }
+                    } else {
+                        StableNumberInput {
+                            id: "export-audio-bitrate".to_string(),
+                            value: audio_bitrate(),
+                            placeholder: None,
+                            style: Some(input_style.clone()),
+                            min: Some("1".to_string()),
+                            max: None,
+                            step: Some("16".to_string()),
+                            on_change: move |v: String| audio_bitrate.set(v),
+                            on_blur: move |_| {},
+                            on_keydown: move |_| {},
+                        }
+                    }
+                }
+            }
+            if let Some(message) = error.as_ref() {
+                div { style: "font-size: 10px; color: #fca5a5;", "{message}" }
+            } else {
+                div {
+                    style: "
+                        font-size: 10px; color: {TEXT_DIM}; font-family: monospace;
+                        word-break: break-all;
+                    ",
+                    "ffmpeg {ffmpeg_args}"
+                }
+            }
+            div {
+                style: "display: flex; justify-content: flex-end; gap: 8px;",
+                button {
+                    class: "collapse-btn",
+                    style: "
+                        padding: 6px 10px; font-size: 11px;
+                        background-color: {BG_SURFACE};
+                        border: 1px solid {BORDER_DEFAULT};
+                        border-radius: 6px; color: {TEXT_PRIMARY};
+                        cursor: pointer;
+                    ",
+                    onclick: move |e| on_close.call(e),
+                    "Cancel"
+                }
+                button {
+                    class: "collapse-btn",
+                    style: "
+                        padding: 6px 12px; font-size: 11px;
+                        background-color: {ACCENT_PRIMARY};
+                        border: none; border-radius: 6px;
+                        color: white; font-weight: 600;
+                        cursor: {save_cursor}; opacity: {save_opacity};
+                    ",
+                    disabled: !save_enabled,
+                    onclick: move |e| {
+                        if let Ok(options) = parsed.clone() {
+                            on_save.call(options);
+                            on_close.call(e);
+                        }
+                    },
+                    "Save"
+                }
+            }
+        }
+    }
+}
+
+fn parse_export_options(
+    container: ExportContainer,
+    video_codec: ExportVideoCodec,
+    audio_codec: ExportAudioCodec,
+    use_bitrate: bool,
+    rate_value: &str,
+    audio_bitrate: &str,
+) -> Result<ExportOptions, String> {
+    let rate_control = if video_codec == ExportVideoCodec::ProRes {
+        ExportRateControl::default()
+    } else if use_bitrate {
+        let kbps = rate_value
+            .trim()
+            .parse::<u32>()
+            .map_err(|_| "Enter a whole-number video bitrate.".to_string())?;
+        ExportRateControl::Bitrate(kbps)
+    } else {
+        let crf = rate_value
+            .trim()
+            .parse::<u8>()
+            .map_err(|_| "Enter a whole-number CRF.".to_string())?;
+        ExportRateControl::Crf(crf)
+    };
+    let audio_bitrate_kbps = if audio_codec.uses_bitrate() {
+        audio_bitrate
+            .trim()
+            .parse::<u32>()
+            .map_err(|_| "Enter a whole-number audio bitrate.".to_string())?
+    } else {
+        0
+    };
+    Ok(ExportOptions {
+        container,
+        video_codec,
+        rate_control,
+        audio_codec,
+        audio_bitrate_kbps,
+    })
+}
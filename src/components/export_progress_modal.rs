@@ -0,0 +1,165 @@
+use dioxus::prelude::*;
+use std::path::PathBuf;
+
+use crate::constants::*;
+use crate::core::export::{format_export_duration, ExportStatus};
+
+/// Progress for a running export, then its outcome with follow-up actions.
+#[component]
+pub fn ExportProgressModal(
+    status: ExportStatus,
+    on_cancel: EventHandler<MouseEvent>,
+    on_open_file: EventHandler<PathBuf>,
+    on_reveal_file: EventHandler<PathBuf>,
+    on_close: EventHandler<MouseEvent>,
+) -> Element {
+    let title = match &status {
+        ExportStatus::Running(_) => "Exporting...",
+        ExportStatus::Finished(_) => "Export Complete",
+        ExportStatus::Cancelled => "Export Cancelled",
+        ExportStatus::Failed(_) => "Export Failed",
+    };
+    let button_style = format!(
+        "
+            padding: 6px 10px; font-size: 11px;
+            background-color: {};
+            border: 1px solid {};
+            border-radius: 6px; color: {};
+            cursor: pointer;
+        ",
+        BG_SURFACE, BORDER_DEFAULT, TEXT_PRIMARY
+    );
+
+    rsx! {
+        div {
+            style: "
+                position: fixed; inset: 0;
+                background: rgba(0, 0, 0, 0.45);
+                backdrop-filter: blur(6px);
+                -webkit-backdrop-filter: blur(6px);
+                z-index: 2000;
+            ",
+        }
+        div {
+            style: "
+                position: fixed; top: 50%; left: 50%;
+                transform: translate(-50%, -50%);
+                width: 380px;
+                padding: 14px;
+                background-color: {BG_ELEVATED};
+                border: 1px solid {BORDER_DEFAULT};
+                border-radius: 10px;
+                box-shadow: 0 14px 30px rgba(0,0,0,0.45);
+                display: flex; flex-direction: column; gap: 12px;
+                z-index: 2001;
+            ",
+            span { style: "font-size: 13px; color: {TEXT_PRIMARY};", "{title}" }
+            {
+                match status.clone() {
+                    ExportStatus::Running(progress) => {
+                        let percent = progress.fraction() * 100.0;
+                        let percent_label = format!("{:.0}", percent);
+                        let elapsed = format_export_duration(progress.elapsed);
+                        let eta = progress
+                            .eta()
+                            .map(format_export_duration)
+                            .unwrap_or_else(|| "--".to_string());
+                        rsx! {
+                            div {
+                                style: "
+                                    height: 6px; border-radius: 3px; overflow: hidden;
+                                    background-color: {BG_SURFACE}; border: 1px solid {BORDER_SUBTLE};
+                                ",
+                                div {
+                                    style: "height: 100%; width: {percent}%; background-color: {ACCENT_PRIMARY};",
+                                }
+                            }
+                            div {
+                                style: "display: flex; justify-content: space-between; font-size: 10px; color: {TEXT_MUTED};",
+                                span { "{percent_label}% · frame {progress.frame} / {progress.total_frames}" }
+                                span { "{elapsed} elapsed · {eta} left" }
+                            }
+                            div {
+                                style: "display: flex; justify-content: flex-end;",
+                                button {
+                                    class: "collapse-btn",
+                                    style: "{button_style}",
+                                    onclick: move |e| on_cancel.call(e),
+                                    "Cancel"
+                                }
+                            }
+                        }
+                    }
+                    ExportStatus::Finished(path) => {
+                        let path_label = path.to_string_lossy().to_string();
+                        let open_path = path.clone();
+                        let reveal_path = path.clone();
+                        rsx! {
+                            span {
+                                style: "font-size: 10px; color: {TEXT_DIM}; word-break: break-all;",
+                                "{path_label}"
+                            }
+                            div {
+                                style: "display: flex; justify-content: flex-end; gap: 8px;",
+                                button {
+                                    class: "collapse-btn",
+                                    style: "{button_style}",
+                                    onclick: move |_| on_reveal_file.call(reveal_path.clone()),
+                                    "Reveal in Folder"
+                                }
+                                button {
+                                    class: "collapse-btn",
+                                    style: "{button_style}",
+                                    onclick: move |_| on_open_file.call(open_path.clone()),
+                                    "Open File"
+                                }
+                                button {
+                                    class: "collapse-btn",
+                                    style: "
+                                        padding: 6px 12px; font-size: 11px;
+                                        background-color: {ACCENT_PRIMARY};
+                                        border: none; border-radius: 6px;
+                                        color: white; font-weight: 600;
+                                        cursor: pointer;
+                                    ",
+                                    onclick: move |e| on_close.call(e),
+                                    "Done"
+                                }
+                            }
+                        }
+                    }
+                    ExportStatus::Cancelled | ExportStatus::Failed(_) => {
+                        let message = match &status {
+                            ExportStatus::Failed(err) => err.clone(),
+                            _ => "The partial file was removed.".to_string(),
+                        };
+                        let message_color = if matches!(status, ExportStatus::Failed(_)) {
+                            "#fca5a5"
+                        } else {
+                            TEXT_DIM
+                        };
+                        rsx! {
+                            div {
+                                style: "
+                                    font-size: 10px; color: {message_color};
+                                    white-space: pre-wrap; word-break: break-word;
+                                    max-height: 160px; overflow-y: auto;
+                                ",
+                                "{message}"
+                            }
+                            div {
+                                style: "display: flex; justify-content: flex-end;",
+                                button {
+                                    class: "collapse-btn",
+                                    style: "{button_style}",
+                                    onclick: move |e| on_close.call(e),
+                                    "Close"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use dioxus::prelude::*;
 
 use crate::constants::*;
+use crate::core::generation::batch_progress;
 use crate::state::{GenerationJob, GenerationJobStatus, ProviderOutputType};
 
 #[component]
@@ -13,21 +14,34 @@ pub fn GenerationQueuePanel(
     paused: bool,
     pause_reason: Option<String>,
     on_resume: EventHandler<MouseEvent>,
+    max_concurrent: usize,
 ) -> Element {
     if !open {
         return rsx! {};
     }
 
     let mut context_menu = use_signal(|| None::<(f64, f64, uuid::Uuid)>);
+    let running_count = jobs
+        .iter()
+        .filter(|job| job.status == GenerationJobStatus::Running)
+        .count();
+    let queued_count = jobs
+        .iter()
+        .filter(|job| job.status == GenerationJobStatus::Queued)
+        .count();
     let count_label = if jobs.is_empty() {
         "Empty".to_string()
     } else {
-        format!("{}", jobs.len())
+        format!(
+            "{} of {} running · {} queued",
+            running_count, max_concurrent, queued_count
+        )
     };
     let has_clearable = jobs
         .iter()
         .any(|job| job.status != GenerationJobStatus::Running);
     let clear_opacity = if has_clearable { "0.9" } else { "0.4" };
+    let batches = batch_progress(&jobs);
 
     rsx! {
         div {
@@ -115,6 +129,43 @@ pub fn GenerationQueuePanel(
                         }
                     }
                 }
+                for batch in batches.iter() {
+                    {
+                        let finished = batch.succeeded + batch.failed;
+                        let percent = (batch.fraction.clamp(0.0, 1.0) * 100.0).round() as u32;
+                        let failed_label = (batch.failed > 0).then(|| format!(" · {} failed", batch.failed));
+                        rsx! {
+                            div {
+                                key: "batch-{batch.batch_id}",
+                                style: "
+                                    display: flex; flex-direction: column; gap: 6px;
+                                    padding: 10px; background-color: {BG_SURFACE};
+                                    border: 1px solid {BORDER_DEFAULT}; border-radius: 8px;
+                                ",
+                                div {
+                                    style: "display: flex; align-items: center; justify-content: space-between; gap: 8px;",
+                                    span { style: "font-size: 12px; color: {TEXT_PRIMARY};", "Multi-clip batch" }
+                                    span {
+                                        style: "font-size: 10px; color: {TEXT_MUTED};",
+                                        "{finished} of {batch.total} done"
+                                        if let Some(label) = failed_label {
+                                            span { style: "color: #fca5a5;", "{label}" }
+                                        }
+                                    }
+                                }
+                                div {
+                                    style: "
+                                        height: 6px; border-radius: 999px;
+                                        background-color: {BG_BASE}; overflow: hidden;
+                                    ",
+                                    div {
+                                        style: "height: 100%; width: {percent}%; background-color: {ACCENT_VIDEO};",
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
                 if jobs.is_empty() {
                     div {
                         style: "
@@ -146,6 +197,9 @@ pub fn GenerationQueuePanel(
                                 .map(|progress| (progress.clamp(0.0, 1.0) * 100.0).round() as u32)
                                 .unwrap_or(0);
                             let job_id = job.id;
+                            let retry_at = job
+                                .next_attempt_at
+                                .filter(|_| job.status == GenerationJobStatus::Queued);
                             rsx! {
                                 div {
                                     key: "{job.id}",
@@ -212,6 +266,9 @@ pub fn GenerationQueuePanel(
                                             }
                                         }
                                     }
+                                    if let Some(next_at) = retry_at {
+                                        RetryCountdown { attempt: job.attempts + 1, next_at }
+                                    }
                                     if let Some(error) = job.error.as_ref() {
                                         span { style: "font-size: 10px; color: #fca5a5;", "{error}" }
                                     }
@@ -285,3 +342,21 @@ pub fn GenerationQueuePanel(
         }
     }
 }
+
+/// Only mounted while a retry is scheduled, so the once-a-second re-render
+/// stops as soon as no job is waiting.
+#[component]
+fn RetryCountdown(attempt: u8, next_at: chrono::DateTime<chrono::Utc>) -> Element {
+    let mut now = use_signal(chrono::Utc::now);
+    use_future(move || async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+            now.set(chrono::Utc::now());
+        }
+    });
+    let seconds = (next_at - now()).num_seconds().max(0);
+
+    rsx! {
+        span { style: "font-size: 10px; color: #fdba74;", "Retry {attempt} in {seconds}s" }
+    }
+}
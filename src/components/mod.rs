@@ -14,6 +14,9 @@ mod provider_builder_modal_v2;
 mod new_project_modal;
 mod track_context_menu;
 mod generation_queue_panel;
+mod export_modal;
+mod export_progress_modal;
+mod relink_modal;
 
 pub use startup_modal::{StartupModal, StartupModalMode};
 pub use title_bar::TitleBar;
@@ -26,3 +29,6 @@ pub use provider_builder_modal_v2::ProviderBuilderModalV2;
 pub use new_project_modal::NewProjectModal;
 pub use track_context_menu::TrackContextMenu;
 pub use generation_queue_panel::GenerationQueuePanel;
+pub use export_modal::ExportSettingsModal;
+pub use export_progress_modal::ExportProgressModal;
+pub use relink_modal::RelinkModal;
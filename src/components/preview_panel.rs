@@ -1,5 +1,6 @@
 use dioxus::prelude::*;
 use crate::constants::*;
+use crate::state::PreviewGuides;
 
 #[component]
 pub fn PreviewPanel(
@@ -11,7 +12,15 @@ pub fn PreviewPanel(
     preview_gpu_upload_ms: Option<f64>,
     show_preview_stats: bool,
     preview_native_active: bool,
+    can_save_frame: bool,
+    on_save_frame: EventHandler<MouseEvent>,
+    show_checkerboard: bool,
+    on_toggle_checkerboard: EventHandler<MouseEvent>,
+    // Overlay guides; view only, drawn over the frame and never exported
+    guides: PreviewGuides,
+    on_guides_change: EventHandler<PreviewGuides>,
 ) -> Element {
+    let mut guides_menu_open = use_signal(|| false);
     let fps_label = format!("{:.0}", fps);
     let has_frame = preview_frame.is_some();
     let canvas_visibility = if preview_native_active {
@@ -55,6 +64,7 @@ pub fn PreviewPanel(
                 lines.push(format!("gpu {:.1}ms", gpu_ms));
             }
             lines.push(format!("hit {:.0}%", hit_ratio));
+            lines.push(format!("  scrub {}", stats.scrub_ring_hits));
             lines.push(format!("layers {}", stats.layers));
             lines.join("\n")
         })
@@ -63,6 +73,32 @@ pub fn PreviewPanel(
     };
     let stats_text = stats_text.unwrap_or_default();
     let show_stats_overlay = show_preview_stats && !stats_text.is_empty();
+    let save_frame_color = if can_save_frame { TEXT_MUTED } else { TEXT_DIM };
+    let save_frame_cursor = if can_save_frame { "pointer" } else { "default" };
+    let checkerboard_color = if show_checkerboard { TEXT_PRIMARY } else { TEXT_DIM };
+    let checkerboard_bg = if show_checkerboard { BG_HOVER } else { "transparent" };
+    let guides_color = if guides.any() { TEXT_PRIMARY } else { TEXT_DIM };
+    let guides_bg = if guides.any() || guides_menu_open() { BG_HOVER } else { "transparent" };
+    let guide_options: [(&'static str, bool, fn(&mut PreviewGuides, bool)); 4] = [
+        ("Title safe (90%)", guides.title_safe, |g, on| g.title_safe = on),
+        ("Action safe (93%)", guides.action_safe, |g, on| g.action_safe = on),
+        ("Rule of thirds", guides.thirds, |g, on| g.thirds = on),
+        ("Center cross", guides.center, |g, on| g.center = on),
+    ];
+    // The HTML canvas path draws guides as SVG over the canvas; the native GPU
+    // surface draws its own.
+    let guide_overlay = preview_frame
+        .filter(|_| !preview_native_active && guides.any())
+        .map(|frame| {
+            let lines = crate::core::preview::guide_lines(
+                guides,
+                width,
+                height,
+                frame.width,
+                frame.height,
+            );
+            (frame.width, frame.height, lines)
+        });
     rsx! {
         div {
             style: "display: flex; flex-direction: column; flex: 1; min-height: 0; background-color: {BG_DEEPEST};",
@@ -88,6 +124,58 @@ pub fn PreviewPanel(
                 }
                 div {
                     style: "grid-column: 3; justify-self: end; display: flex; align-items: center; gap: 6px; font-family: 'SF Mono', Consolas, monospace; font-size: 11px; color: {TEXT_DIM};",
+                    button {
+                        class: "collapse-btn",
+                        style: "padding: 0 6px; height: 20px; border: none; border-radius: 3px; background: {checkerboard_bg}; color: {checkerboard_color}; font-size: 10px; cursor: pointer; display: flex; align-items: center; justify-content: center;",
+                        title: "Show a checkerboard behind transparent areas",
+                        onclick: move |e| on_toggle_checkerboard.call(e),
+                        "Alpha"
+                    }
+                    div {
+                        style: "position: relative;",
+                        button {
+                            class: "collapse-btn",
+                            style: "padding: 0 6px; height: 20px; border: none; border-radius: 3px; background: {guides_bg}; color: {guides_color}; font-size: 10px; cursor: pointer; display: flex; align-items: center; justify-content: center;",
+                            title: "Safe-area and composition guides",
+                            onclick: move |_| guides_menu_open.set(!guides_menu_open()),
+                            "Guides"
+                        }
+                        if guides_menu_open() {
+                            div {
+                                style: "
+                                    position: absolute; top: 24px; right: 0; z-index: 20;
+                                    display: flex; flex-direction: column; gap: 6px;
+                                    padding: 8px 10px; min-width: 150px;
+                                    background-color: {BG_ELEVATED}; border: 1px solid {BORDER_DEFAULT};
+                                    border-radius: 6px; box-shadow: 0 4px 12px rgba(0,0,0,0.4);
+                                ",
+                                onmouseleave: move |_| guides_menu_open.set(false),
+                                for (option_label, enabled, apply) in guide_options {
+                                    label {
+                                        style: "display: flex; align-items: center; gap: 6px; font-family: inherit; font-size: 11px; color: {TEXT_SECONDARY}; cursor: pointer; white-space: nowrap;",
+                                        input {
+                                            r#type: "checkbox",
+                                            checked: enabled,
+                                            onchange: move |_| {
+                                                let mut next = guides;
+                                                apply(&mut next, !enabled);
+                                                on_guides_change.call(next);
+                                            },
+                                        }
+                                        "{option_label}"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    button {
+                        class: "collapse-btn",
+                        style: "padding: 0 6px; height: 20px; border: none; border-radius: 3px; background: transparent; color: {save_frame_color}; font-size: 10px; cursor: {save_frame_cursor}; display: flex; align-items: center; justify-content: center;",
+                        title: "Save the current frame as a PNG",
+                        disabled: !can_save_frame,
+                        onclick: move |e| on_save_frame.call(e),
+                        "Save Frame"
+                    }
                     span { "{width} x {height}" }
                     span { style: "color: {TEXT_MUTED};", "@" }
                     span { "{fps_label}" }
@@ -108,6 +196,27 @@ pub fn PreviewPanel(
                         height: "1",
                         style: "position: relative; z-index: 1; max-width: 100%; max-height: 100%; width: auto; height: auto; border: none; border-radius: 0; background-color: #000; visibility: {canvas_visibility};",
                     }
+                    if let Some((canvas_w, canvas_h, lines)) = guide_overlay {
+                        // Same intrinsic size and max constraints as the canvas, centered
+                        // the same way, so the viewBox lines up with the frame.
+                        svg {
+                            width: "{canvas_w}",
+                            height: "{canvas_h}",
+                            view_box: "0 0 {canvas_w} {canvas_h}",
+                            style: "position: absolute; inset: 0; margin: auto; max-width: 100%; max-height: 100%; width: auto; height: auto; z-index: 2; pointer-events: none;",
+                            for line in lines {
+                                line {
+                                    x1: "{line.x0}",
+                                    y1: "{line.y0}",
+                                    x2: "{line.x1}",
+                                    y2: "{line.y1}",
+                                    stroke: "rgba(255, 255, 255, 0.6)",
+                                    stroke_width: "1",
+                                    vector_effect: "non-scaling-stroke",
+                                }
+                            }
+                        }
+                    }
                     if show_placeholder {
                         div {
                             style: "position: absolute; inset: 0; display: flex; flex-direction: column; align-items: center; justify-content: center; gap: 12px; color: {TEXT_DIM}; z-index: 2;",
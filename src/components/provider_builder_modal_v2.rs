@@ -3,14 +3,29 @@ use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
 use crate::constants::*;
-use crate::core::comfyui_workflow::ComfyWorkflowNode;
-use crate::core::provider_store::{provider_path_for_entry, read_provider_file, write_provider_file};
+use crate::core::comfyui_workflow::{
+    hash_workflow_file, validate_bindings, workflow_drifted, ComfyWorkflowNode,
+};
+use crate::core::generation::{parse_transform_expression, transform_expression};
+use crate::core::provider_duplicates::{find_duplicate_provider, DuplicateProvider, ProviderFingerprint};
+use crate::core::provider_store::{
+    list_provider_files, manifest_path_for_scope, portable_provider_entry, provider_path_in_scope,
+    provider_scope_for_path, read_provider_file, write_provider_file,
+};
 use crate::state::{
     ComfyOutputSelector, ComfyWorkflowRef, InputBinding, ManifestInput, NodeSelector,
     ProviderConnection, ProviderEntry, ProviderInputField, ProviderInputType, ProviderManifest,
-    ProviderOutputType, InputUi,
+    ProviderOutputType, ProviderScope, InputUi, RandomRange,
 };
 
+/// What to do when saving would duplicate an existing provider.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum DuplicateResolution {
+    /// Save over the existing provider, taking its id and file.
+    Overwrite,
+    KeepBoth,
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum BuilderMode {
     Inputs,
@@ -41,6 +56,10 @@ struct BuilderInput {
     enum_options: String,
     tag: String,
     multiline: bool,
+    /// Transform shorthand, see `parse_transform_expression`.
+    transform_text: String,
+    /// Not editable here; kept so re-saving doesn't drop a hand-written range.
+    random_range: Option<RandomRange>,
     selector: NodeSelectorDraft,
 }
 
@@ -48,26 +67,39 @@ struct BuilderInput {
 pub fn ProviderBuilderModalV2(
     show: Signal<bool>,
     provider_path: Signal<Option<PathBuf>>,
+    project_root: Option<PathBuf>,
     on_saved: EventHandler<PathBuf>,
 ) -> Element {
     // All the state signals - no "initialized" flag needed!
     let mut workflow_path = use_signal(|| None::<PathBuf>);
     let mut workflow_nodes = use_signal(Vec::<ComfyWorkflowNode>::new);
     let mut workflow_error = use_signal(|| None::<String>);
+    // Set when the manifest's stored hash doesn't match the workflow on disk.
+    let mut workflow_drift = use_signal(|| false);
     let mut workflow_search = use_signal(String::new);
+    let mut paste_open = use_signal(|| false);
+    let mut paste_text = use_signal(String::new);
+    let mut paste_error = use_signal(|| None::<String>);
     let mut selected_node_id = use_signal(|| None::<String>);
 
     let mut provider_name = use_signal(|| "New Provider".to_string());
     let mut provider_id = use_signal(Uuid::new_v4);
     let mut base_url = use_signal(|| "http://127.0.0.1:8188".to_string());
+    let mut category = use_signal(String::new);
+    let mut favorite = use_signal(|| false);
+    // Not edited here; kept so saving doesn't drop a value set in the JSON.
+    let mut max_concurrent = use_signal(|| None::<u32>);
     let mut output_type = use_signal(|| ProviderOutputType::Image);
     let mut output_key = use_signal(|| "images".to_string());
     let mut output_tag = use_signal(String::new);
     let mut output_node = use_signal(|| None::<OutputNodeDraft>);
     let mut builder_mode = use_signal(|| BuilderMode::Inputs);
+    let mut save_scope = use_signal(|| ProviderScope::Global);
 
     let mut exposed_inputs = use_signal(Vec::<BuilderInput>::new);
     let mut builder_error = use_signal(|| None::<String>);
+    // Existing provider the last save attempt matched; the user picks what to do
+    let mut duplicate_of = use_signal(|| None::<DuplicateProvider>);
     let mut manifest_path = use_signal(|| None::<PathBuf>);
     let mut loaded_path = use_signal(|| None::<PathBuf>); // Track what we loaded
     let mut loaded_new = use_signal(|| false);
@@ -90,6 +122,9 @@ pub fn ProviderBuilderModalV2(
             provider_name.set("New Provider".to_string());
             provider_id.set(Uuid::new_v4());
             base_url.set("http://127.0.0.1:8188".to_string());
+            category.set(String::new());
+            favorite.set(false);
+            max_concurrent.set(None);
             output_type.set(ProviderOutputType::Image);
             output_key.set("images".to_string());
             output_tag.set(String::new());
@@ -98,8 +133,16 @@ pub fn ProviderBuilderModalV2(
             workflow_path.set(None);
             workflow_nodes.set(Vec::new());
             workflow_error.set(None);
+            workflow_drift.set(false);
             manifest_path.set(None);
             builder_error.set(None);
+            duplicate_of.set(None);
+            save_scope.set(
+                current_path
+                    .as_deref()
+                    .map(provider_scope_for_path)
+                    .unwrap_or(ProviderScope::Global),
+            );
             
             if let Some(ref path) = current_path {
                 // Load and parse provider JSON
@@ -108,6 +151,9 @@ pub fn ProviderBuilderModalV2(
                         // PRESERVE EXISTING UUID!
                         provider_id.set(entry.id);
                         provider_name.set(entry.name.clone());
+                        category.set(entry.category.clone().unwrap_or_default());
+                        favorite.set(entry.favorite);
+                        max_concurrent.set(entry.max_concurrent);
                         output_type.set(entry.output_type);
                         
                         if let ProviderConnection::ComfyUi {
@@ -138,7 +184,15 @@ pub fn ProviderBuilderModalV2(
                                 
                                 if let Ok(man_json) = std::fs::read_to_string(&man_path_buf) {
                                     if let Ok(manifest) = serde_json::from_str::<ProviderManifest>(&man_json) {
-                                        if let ProviderManifest::ComfyUi { inputs, output, .. } = manifest {
+                                        if let ProviderManifest::ComfyUi { workflow, inputs, output, .. } = manifest {
+                                            if let Some(wf_path) = workflow_path() {
+                                                if let Ok(current_hash) = hash_workflow_file(&wf_path) {
+                                                    workflow_drift.set(workflow_drifted(
+                                                        workflow.workflow_hash.as_deref(),
+                                                        &current_hash,
+                                                    ));
+                                                }
+                                            }
                                             // Populate inputs from manifest
                                             let mut next_inputs = Vec::new();
                                             for input in inputs {
@@ -154,6 +208,13 @@ pub fn ProviderBuilderModalV2(
                                                     enum_options,
                                                     tag: input.bind.selector.tag.unwrap_or_default(),
                                                     multiline: input.ui.as_ref().map(|ui| ui.multiline).unwrap_or(false),
+                                                    transform_text: input
+                                                        .bind
+                                                        .transform
+                                                        .as_ref()
+                                                        .map(transform_expression)
+                                                        .unwrap_or_default(),
+                                                    random_range: input.random_range,
                                                     selector: NodeSelectorDraft {
                                                         class_type: input.bind.selector.class_type,
                                                         input_key: input.bind.selector.input_key,
@@ -218,6 +279,7 @@ pub fn ProviderBuilderModalV2(
                     workflow_path.set(Some(path));
                     workflow_nodes.set(nodes);
                     workflow_error.set(None);
+                    workflow_drift.set(false);
                     selected_node_id.set(None);
                 }
                 Err(err) => {
@@ -229,11 +291,71 @@ pub fn ProviderBuilderModalV2(
         }
     };
 
+    // Pasted API-format JSON is saved next to the project's other workflows
+    // (or the global workflows folder) so the manifest can reference a file.
+    let project_root_for_paste = project_root.clone();
+    let import_pasted_workflow = move |_| {
+        let value = match crate::core::comfyui_workflow::parse_api_workflow(&paste_text()) {
+            Ok(value) => value,
+            Err(err) => {
+                paste_error.set(Some(err));
+                return;
+            }
+        };
+        let nodes = match crate::core::comfyui_workflow::parse_workflow_nodes(&value) {
+            Ok(nodes) => nodes,
+            Err(err) => {
+                paste_error.set(Some(err));
+                return;
+            }
+        };
+        let dir = match project_root_for_paste.as_ref() {
+            Some(root) => root.join("workflows"),
+            None => crate::core::paths::resource_dir("workflows")
+                .unwrap_or_else(|| crate::core::paths::app_config_root().join("workflows")),
+        };
+        match crate::core::comfyui_workflow::save_pasted_workflow(&dir, &provider_name(), &value) {
+            Ok(path) => {
+                workflow_path.set(Some(path));
+                workflow_nodes.set(nodes);
+                workflow_error.set(None);
+                workflow_drift.set(false);
+                selected_node_id.set(None);
+                paste_text.set(String::new());
+                paste_error.set(None);
+                paste_open.set(false);
+            }
+            Err(err) => paste_error.set(Some(err)),
+        }
+    };
+
+    // Re-read the workflow from disk so the binding warnings reflect its
+    // current contents; saving afterwards records the new hash.
+    let revalidate_workflow = move |_| {
+        let Some(path) = workflow_path() else {
+            return;
+        };
+        match crate::core::comfyui_workflow::load_workflow_nodes(&path) {
+            Ok(nodes) => {
+                workflow_nodes.set(nodes);
+                workflow_error.set(None);
+                workflow_drift.set(false);
+            }
+            Err(err) => {
+                workflow_error.set(Some(err));
+                workflow_nodes.set(Vec::new());
+            }
+        }
+        selected_node_id.set(None);
+    };
+
     // NOTE: Keeping the rest of the builder UI mostly the same,
     // just the initialization logic changed. Let me add the save handler
     // then render the rest of the UI...
     
-    let save_provider = move |_| {
+    let project_root_for_save = project_root.clone();
+    let mut save_provider = move |resolution: Option<DuplicateResolution>| {
+        duplicate_of.set(None);
         let Some(wf_path) = workflow_path() else {
             builder_error.set(Some("Select a workflow first".to_string()));
             return;
@@ -258,6 +380,13 @@ pub fn ProviderBuilderModalV2(
         
         // Build manifest and provider entry
         let workflow_path_str = wf_path.to_string_lossy().to_string();
+        let workflow_hash = match hash_workflow_file(&wf_path) {
+            Ok(hash) => hash,
+            Err(err) => {
+                builder_error.set(Some(err));
+                return;
+            }
+        };
         let manifest_path_value = manifest_path()
             .unwrap_or_else(|| derive_manifest_path(&wf_path));
         let manifest_path_str = manifest_path_value.to_string_lossy().to_string();
@@ -291,6 +420,13 @@ pub fn ProviderBuilderModalV2(
             };
             
             let input_ui = build_input_ui(input);
+            let transform = match parse_transform_expression(&input.transform_text) {
+                Ok(transform) => transform,
+                Err(err) => {
+                    builder_error.set(Some(format!("Input {}: {}", input.name, err)));
+                    return;
+                }
+            };
             
             manifest_inputs.push(ManifestInput {
                 name: input.name.clone(),
@@ -299,9 +435,10 @@ pub fn ProviderBuilderModalV2(
                 required: input.required,
                 default: default_value.clone(),
                 ui: input_ui.clone(),
+                random_range: input.random_range,
                 bind: InputBinding {
                     selector,
-                    transform: None,
+                    transform,
                 },
             });
             
@@ -312,6 +449,7 @@ pub fn ProviderBuilderModalV2(
                 required: input.required,
                 default: default_value,
                 ui: input_ui,
+                random_range: input.random_range,
             });
         }
         
@@ -330,7 +468,7 @@ pub fn ProviderBuilderModalV2(
             output_type: output_type(),
             workflow: ComfyWorkflowRef {
                 workflow_path: workflow_path_str.clone(),
-                workflow_hash: None,
+                workflow_hash: Some(workflow_hash),
             },
             inputs: manifest_inputs,
             output: ComfyOutputSelector {
@@ -340,7 +478,7 @@ pub fn ProviderBuilderModalV2(
         };
         
         // CRITICAL: Use existing provider_id if we loaded one, don't generate new!
-        let entry = ProviderEntry {
+        let mut entry = ProviderEntry {
             id: provider_id(), // ← PRESERVES UUID
             name,
             output_type: output_type(),
@@ -350,7 +488,42 @@ pub fn ProviderBuilderModalV2(
                 workflow_path: Some(workflow_path_str),
                 manifest_path: Some(manifest_path_str),
             },
+            category: Some(category().trim().to_string()).filter(|value| !value.is_empty()),
+            favorite: favorite(),
+            max_concurrent: max_concurrent(),
+            scope: save_scope(),
+        };
+
+        // Warn before adding a second copy of a provider that already exists
+        let duplicate = if resolution == Some(DuplicateResolution::KeepBoth) {
+            None
+        } else {
+            let fingerprint = ProviderFingerprint::new(&entry, Some(&manifest), manifest_workflow_hash(&manifest));
+            let files = list_provider_files(project_root_for_save.as_deref());
+            find_duplicate_provider(&fingerprint, &files, project_root_for_save.as_deref(), Some(entry.id))
         };
+        let mut target_scope = save_scope();
+        let mut overwrite_path = None;
+        if let Some(duplicate) = duplicate {
+            if resolution != Some(DuplicateResolution::Overwrite) {
+                duplicate_of.set(Some(duplicate));
+                return;
+            }
+            entry.id = duplicate.id;
+            target_scope = provider_scope_for_path(&duplicate.path);
+            entry.scope = target_scope;
+            overwrite_path = Some(duplicate.path);
+        }
+
+        // The manifest follows the provider when it moves between scopes
+        let manifest_path_value = manifest_path_for_scope(
+            &manifest_path_value,
+            target_scope,
+            project_root_for_save.as_deref(),
+        );
+        if let ProviderConnection::ComfyUi { manifest_path: stored, .. } = &mut entry.connection {
+            *stored = Some(manifest_path_value.to_string_lossy().to_string());
+        }
         
         // Write manifest
         let manifest_json = match serde_json::to_string_pretty(&manifest) {
@@ -369,9 +542,14 @@ pub fn ProviderBuilderModalV2(
             builder_error.set(Some(format!("Failed to write manifest: {}", err)));
             return;
         }
+        let previous_manifest = manifest_path().filter(|previous| *previous != manifest_path_value);
         
-        // Write provider
-        let provider_json = match serde_json::to_string_pretty(&entry) {
+        // Write provider; project providers keep paths inside the project relative
+        let stored_entry = match (target_scope, project_root_for_save.as_deref()) {
+            (ProviderScope::Project, Some(root)) => portable_provider_entry(&entry, root),
+            _ => entry.clone(),
+        };
+        let provider_json = match serde_json::to_string_pretty(&stored_entry) {
             Ok(json) => json,
             Err(err) => {
                 builder_error.set(Some(format!("Failed to serialize provider: {}", err)));
@@ -379,17 +557,36 @@ pub fn ProviderBuilderModalV2(
             }
         };
         
-        let save_path = provider_path()
-            .unwrap_or_else(|| provider_path_for_entry(&entry));
+        // Keep the existing file unless the user moved the provider to the other scope
+        let existing_path = overwrite_path.or_else(|| {
+            provider_path().filter(|path| provider_scope_for_path(path) == target_scope)
+        });
+        let Some(save_path) = existing_path.or_else(|| {
+            provider_path_in_scope(&entry, target_scope, project_root_for_save.as_deref())
+        }) else {
+            builder_error.set(Some("Open a project to save project providers".to_string()));
+            return;
+        };
         
         if let Err(err) = write_provider_file(&save_path, &provider_json) {
             builder_error.set(Some(format!("Failed to save provider: {}", err)));
             return;
         }
+        if let Some(previous_path) = provider_path() {
+            if previous_path != save_path {
+                let _ = std::fs::remove_file(&previous_path);
+            }
+        }
+        if let Some(previous_manifest) = previous_manifest {
+            let _ = std::fs::remove_file(&previous_manifest);
+        }
         
         manifest_path.set(Some(manifest_path_value));
+        provider_id.set(entry.id);
         on_saved.call(save_path);
     };
+    let mut save_overwriting = save_provider.clone();
+    let mut save_keeping_both = save_provider.clone();
 
     // Shortened version of rest of UI - keeping interactive parts
     let query = workflow_search().trim().to_lowercase();
@@ -421,6 +618,41 @@ pub fn ProviderBuilderModalV2(
             .find(|node| node.id == id)
     });
 
+    // Bindings that no longer resolve, e.g. after swapping the workflow file
+    let binding_warnings: Vec<String> = if workflow_nodes().is_empty() {
+        Vec::new()
+    } else {
+        let input_selectors: Vec<(String, NodeSelector)> = exposed_inputs()
+            .iter()
+            .map(|input| {
+                let tag = input.tag.trim();
+                (
+                    input.name.clone(),
+                    NodeSelector {
+                        tag: if tag.is_empty() { None } else { Some(tag.to_string()) },
+                        class_type: input.selector.class_type.clone(),
+                        input_key: input.selector.input_key.clone(),
+                        title: input.selector.title.clone(),
+                    },
+                )
+            })
+            .collect();
+        let output_selector = output_node().map(|node| {
+            let tag = output_tag();
+            let tag = tag.trim();
+            NodeSelector {
+                tag: if tag.is_empty() { None } else { Some(tag.to_string()) },
+                class_type: node.class_type,
+                input_key: output_key(),
+                title: node.title,
+            }
+        });
+        validate_bindings(&workflow_nodes(), &input_selectors, output_selector.as_ref())
+            .iter()
+            .map(|warning| warning.to_string())
+            .collect()
+    };
+
     // UI helper values
     let inputs_active = builder_mode() == BuilderMode::Inputs;
     let input_tab_bg = if inputs_active { BG_HOVER } else { BG_SURFACE };
@@ -460,6 +692,8 @@ pub fn ProviderBuilderModalV2(
                 enum_options: String::new(),
                 tag: String::new(),
                 multiline: false,
+                transform_text: String::new(),
+                random_range: None,
                 selector,
             };
             let mut next = exposed_inputs();
@@ -549,6 +783,19 @@ pub fn ProviderBuilderModalV2(
                                 onclick: pick_workflow,
                                 "Choose Workflow..."
                             }
+                            button {
+                                class: "collapse-btn",
+                                style: "
+                                    background: {BG_SURFACE}; border: 1px solid {BORDER_DEFAULT};
+                                    color: {TEXT_PRIMARY}; font-size: 11px; cursor: pointer;
+                                    padding: 6px 10px; border-radius: 6px;
+                                ",
+                                onclick: move |_| {
+                                    paste_open.set(!paste_open());
+                                    paste_error.set(None);
+                                },
+                                "Paste Workflow JSON..."
+                            }
                             button {
                                 class: "collapse-btn",
                                 style: "
@@ -561,6 +808,57 @@ pub fn ProviderBuilderModalV2(
                         }
                     }
 
+                    if paste_open() {
+                        div {
+                            style: "
+                                padding: 10px 18px; display: flex; flex-direction: column; gap: 6px;
+                                border-bottom: 1px solid {BORDER_DEFAULT}; background-color: {BG_BASE};
+                            ",
+                            span {
+                                style: "font-size: 10px; color: {TEXT_DIM};",
+                                "Paste the JSON from ComfyUI's \"Save (API Format)\". It will be saved to the workflows folder."
+                            }
+                            crate::components::common::StableTextArea {
+                                id: "workflow-paste-textarea".to_string(),
+                                value: paste_text(),
+                                placeholder: Some("{ \"3\": { \"class_type\": \"KSampler\", \"inputs\": { ... } } }".to_string()),
+                                style: None,
+                                rows: Some(8),
+                                on_change: move |v: String| paste_text.set(v),
+                                on_focus: move |_| {},
+                                on_blur: move |_| {},
+                            }
+                            if let Some(error) = paste_error() {
+                                div { style: "font-size: 11px; color: #f97316;", "{error}" }
+                            }
+                            div {
+                                style: "display: flex; gap: 8px; justify-content: flex-end;",
+                                button {
+                                    class: "collapse-btn",
+                                    style: "
+                                        background: transparent; border: none; color: {TEXT_SECONDARY};
+                                        font-size: 11px; cursor: pointer; padding: 6px 10px; border-radius: 6px;
+                                    ",
+                                    onclick: move |_| {
+                                        paste_open.set(false);
+                                        paste_error.set(None);
+                                    },
+                                    "Cancel"
+                                }
+                                button {
+                                    class: "collapse-btn",
+                                    style: "
+                                        background: {BG_SURFACE}; border: 1px solid {BORDER_ACCENT};
+                                        color: {TEXT_PRIMARY}; font-size: 11px; cursor: pointer;
+                                        padding: 6px 10px; border-radius: 6px;
+                                    ",
+                                    onclick: import_pasted_workflow,
+                                    "Import"
+                                }
+                            }
+                        }
+                    }
+
                     // Errors
                     if let Some(error) = workflow_error() {
                         div { style: "padding: 8px 18px; font-size: 11px; color: #f97316;", "{error}" }
@@ -568,6 +866,66 @@ pub fn ProviderBuilderModalV2(
                     if let Some(error) = builder_error() {
                         div { style: "padding: 8px 18px; font-size: 11px; color: #f97316;", "{error}" }
                     }
+                    if let Some(duplicate) = duplicate_of() {
+                        div {
+                            style: "padding: 6px 18px; font-size: 11px; color: #eab308; display: flex; align-items: center; gap: 8px;",
+                            span {
+                                style: "flex: 1;",
+                                title: "{duplicate.path.display()}",
+                                "\"{duplicate.name}\" already runs this workflow with the same inputs and output."
+                            }
+                            button {
+                                style: "
+                                    padding: 2px 8px; font-size: 11px; border-radius: 4px;
+                                    border: 1px solid {BORDER_DEFAULT}; background-color: {BG_SURFACE};
+                                    color: {TEXT_PRIMARY}; cursor: pointer;
+                                ",
+                                onclick: move |_| save_overwriting(Some(DuplicateResolution::Overwrite)),
+                                "Overwrite"
+                            }
+                            button {
+                                style: "
+                                    padding: 2px 8px; font-size: 11px; border-radius: 4px;
+                                    border: 1px solid {BORDER_DEFAULT}; background-color: {BG_SURFACE};
+                                    color: {TEXT_PRIMARY}; cursor: pointer;
+                                ",
+                                onclick: move |_| save_keeping_both(Some(DuplicateResolution::KeepBoth)),
+                                "Keep Both"
+                            }
+                            button {
+                                style: "
+                                    padding: 2px 8px; font-size: 11px; border-radius: 4px;
+                                    border: 1px solid {BORDER_DEFAULT}; background-color: transparent;
+                                    color: {TEXT_SECONDARY}; cursor: pointer;
+                                ",
+                                onclick: move |_| duplicate_of.set(None),
+                                "Cancel"
+                            }
+                        }
+                    }
+                    if workflow_drift() {
+                        div {
+                            style: "padding: 6px 18px; font-size: 11px; color: #eab308; display: flex; align-items: center; gap: 8px;",
+                            span { "The workflow file changed since this provider was saved; node bindings may no longer match." }
+                            button {
+                                style: "
+                                    padding: 2px 8px; font-size: 11px; border-radius: 4px;
+                                    border: 1px solid {BORDER_DEFAULT}; background-color: {BG_SURFACE};
+                                    color: {TEXT_PRIMARY}; cursor: pointer;
+                                ",
+                                onclick: revalidate_workflow,
+                                "Re-validate bindings"
+                            }
+                        }
+                    }
+                    if !binding_warnings.is_empty() {
+                        div {
+                            style: "padding: 6px 18px; font-size: 11px; color: #eab308; display: flex; flex-direction: column; gap: 2px;",
+                            for warning in binding_warnings.iter() {
+                                div { "⚠ {warning}" }
+                            }
+                        }
+                    }
 
                     // Main content
                     div {
@@ -836,6 +1194,32 @@ pub fn ProviderBuilderModalV2(
                                         on_keydown: move |_| {},
                                         autofocus: false,
                                     }
+                                    div {
+                                        style: "display: flex; gap: 8px; align-items: center;",
+                                        crate::components::common::StableTextInput {
+                                            id: "provider-category-input".to_string(),
+                                            value: category(),
+                                            placeholder: Some("Category (optional)".to_string()),
+                                            style: Some(format!("
+                                                flex: 1; padding: 6px 8px; font-size: 11px;
+                                                background-color: {}; color: {};
+                                                border: 1px solid {}; border-radius: 6px;
+                                            ", BG_ELEVATED, TEXT_PRIMARY, BORDER_DEFAULT)),
+                                            on_change: move |v: String| category.set(v),
+                                            on_blur: move |_| {},
+                                            on_keydown: move |_| {},
+                                            autofocus: false,
+                                        }
+                                        label {
+                                            style: "display: flex; align-items: center; gap: 4px; font-size: 11px; color: {TEXT_SECONDARY}; cursor: pointer;",
+                                            input {
+                                                r#type: "checkbox",
+                                                checked: favorite(),
+                                                onchange: move |_| favorite.set(!favorite()),
+                                            }
+                                            "★ Favorite"
+                                        }
+                                    }
                                 }
 
                                 // Inputs or Output config
@@ -1075,6 +1459,30 @@ pub fn ProviderBuilderModalV2(
                                                                     autofocus: false,
                                                                 }
                                                             }
+                                                            crate::components::common::StableTextInput {
+                                                                id: format!("input-transform-{}", input.id),
+                                                                value: input.transform_text.clone(),
+                                                                placeholder: Some("Transform (e.g. * 24 | round)".to_string()),
+                                                                style: Some(format!("
+                                                                    width: 100%; padding: 4px 6px; font-size: 10px;
+                                                                    font-family: 'SF Mono', Consolas, monospace;
+                                                                    background-color: {}; color: {};
+                                                                    border: 1px solid {}; border-radius: 4px;
+                                                                ", BG_SURFACE, TEXT_PRIMARY, BORDER_DEFAULT)),
+                                                                on_change: move |v: String| {
+                                                                    let mut next = exposed_inputs();
+                                                                    if let Some(target) = next.get_mut(index) {
+                                                                        target.transform_text = v;
+                                                                    }
+                                                                    exposed_inputs.set(next);
+                                                                },
+                                                                on_blur: move |_| {},
+                                                                on_keydown: move |_| {},
+                                                                autofocus: false,
+                                                            }
+                                                            if let Err(err) = parse_transform_expression(&input.transform_text) {
+                                                                div { style: "font-size: 9px; color: #fca5a5;", "{err}" }
+                                                            }
                                                             div {
                                                                 style: "font-size: 9px; color: {TEXT_DIM};",
                                                                 "→ {input_clone.selector.class_type}.{input_clone.selector.input_key}"
@@ -1115,9 +1523,39 @@ pub fn ProviderBuilderModalV2(
                                     }
                                 }
 
-                                // Save button
+                                // Save scope + Save button
                                 div {
-                                    style: "display: flex; justify-content: flex-end; gap: 8px;",
+                                    style: "display: flex; justify-content: flex-end; align-items: center; gap: 8px;",
+                                    span { style: "font-size: 11px; color: {TEXT_DIM};", "Save to" }
+                                    for scope in [ProviderScope::Global, ProviderScope::Project] {
+                                        {
+                                            let available = scope == ProviderScope::Global || project_root.is_some();
+                                            let is_active = save_scope() == scope;
+                                            let scope_bg = if is_active { BG_HOVER } else { BG_SURFACE };
+                                            let scope_border = if is_active { BORDER_ACCENT } else { BORDER_DEFAULT };
+                                            let scope_opacity = if available { "1" } else { "0.4" };
+                                            let scope_title = match scope {
+                                                ProviderScope::Global => "Available to every project",
+                                                ProviderScope::Project => "Stored in this project's .providers folder",
+                                            };
+                                            rsx! {
+                                                button {
+                                                    class: "collapse-btn",
+                                                    style: "
+                                                        padding: 6px 10px; font-size: 11px;
+                                                        background-color: {scope_bg};
+                                                        border: 1px solid {scope_border}; border-radius: 6px;
+                                                        color: {TEXT_PRIMARY}; cursor: pointer;
+                                                        opacity: {scope_opacity};
+                                                    ",
+                                                    title: "{scope_title}",
+                                                    disabled: !available,
+                                                    onclick: move |_| save_scope.set(scope),
+                                                    "{scope.label()}"
+                                                }
+                                            }
+                                        }
+                                    }
                                     button {
                                         class: "collapse-btn",
                                         style: "
@@ -1127,7 +1565,7 @@ pub fn ProviderBuilderModalV2(
                                             color: white; font-weight: 600;
                                             cursor: pointer;
                                         ",
-                                        onclick: save_provider,
+                                        onclick: move |_| save_provider(None),
                                         "Save Provider"
                                     }
                                 }
@@ -1141,6 +1579,13 @@ pub fn ProviderBuilderModalV2(
 }
 
 // Helper functions (same as V1)
+fn manifest_workflow_hash(manifest: &ProviderManifest) -> Option<String> {
+    match manifest {
+        ProviderManifest::ComfyUi { workflow, .. } => workflow.workflow_hash.clone(),
+        _ => None,
+    }
+}
+
 fn derive_manifest_path(workflow_path: &Path) -> PathBuf {
     let mut path = workflow_path.to_path_buf();
     let old_name = path.file_stem().and_then(|s| s.to_str()).unwrap_or("workflow");
@@ -1,24 +1,172 @@
 use dioxus::prelude::*;
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::time::Duration;
 
 use crate::constants::*;
-use crate::core::provider_store::read_provider_file;
+use crate::core::provider_duplicates::{find_duplicate_provider, DuplicateProvider, ProviderFingerprint};
+use crate::core::provider_package::{read_provider_package, ProviderPackage, PROVIDER_PACKAGE_EXTENSION};
+use crate::core::provider_store::{list_global_provider_files, provider_scope_for_path, read_provider_file};
+use crate::providers::health::{
+    check_provider_health, health_cache_key, invalidate_provider_health, ProviderHealth,
+};
+use crate::state::{
+    provider_category_label, ProviderConnection, ProviderScope, UNCATEGORIZED_PROVIDER_CATEGORY,
+};
+
+/// How often the open modal re-reads provider health (served from the cache
+/// until it expires).
+const HEALTH_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A provider file as shown in the list, read loosely so broken files still appear.
+struct ProviderListRow {
+    path: PathBuf,
+    name: String,
+    category: String,
+    favorite: bool,
+    connection: Option<ProviderConnection>,
+}
+
+fn provider_list_rows(paths: &[PathBuf]) -> Vec<ProviderListRow> {
+    let mut rows: Vec<ProviderListRow> = paths
+        .iter()
+        .map(|path| {
+            let value = read_provider_file(path)
+                .and_then(|json| serde_json::from_str::<serde_json::Value>(&json).ok());
+            let field = |key: &str| {
+                value
+                    .as_ref()
+                    .and_then(|v| v.get(key))
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string())
+            };
+            ProviderListRow {
+                path: path.clone(),
+                name: field("name").unwrap_or_else(|| "Unnamed".to_string()),
+                category: provider_category_label(field("category").as_deref()).to_string(),
+                favorite: value
+                    .as_ref()
+                    .and_then(|v| v.get("favorite"))
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false),
+                connection: value
+                    .as_ref()
+                    .and_then(|v| v.get("connection"))
+                    .and_then(|v| serde_json::from_value(v.clone()).ok()),
+            }
+        })
+        .collect();
+    rows.sort_by(|a, b| {
+        (a.category == UNCATEGORIZED_PROVIDER_CATEGORY, a.category.to_lowercase())
+            .cmp(&(b.category == UNCATEGORIZED_PROVIDER_CATEGORY, b.category.to_lowercase()))
+            .then_with(|| b.favorite.cmp(&a.favorite))
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+    rows
+}
+
+/// Check every distinct endpoint among `connections`, keyed by health cache key.
+async fn poll_provider_health(
+    connections: Vec<ProviderConnection>,
+) -> HashMap<String, Result<ProviderHealth, String>> {
+    let mut results = HashMap::new();
+    for connection in connections {
+        let key = health_cache_key(&connection);
+        if results.contains_key(&key) {
+            continue;
+        }
+        let result = check_provider_health(&connection).await;
+        results.insert(key, result);
+    }
+    results
+}
+
+fn provider_connections(paths: &[PathBuf]) -> Vec<ProviderConnection> {
+    provider_list_rows(paths)
+        .into_iter()
+        .filter_map(|row| row.connection)
+        .collect()
+}
 
 #[component]
 pub fn ProvidersModalV2(
     show: Signal<bool>,
     provider_files: Signal<Vec<PathBuf>>,
+    project_root: Option<PathBuf>,
     on_new: EventHandler<()>,
     on_reload: EventHandler<()>,
     on_delete: EventHandler<PathBuf>,
+    on_toggle_favorite: EventHandler<PathBuf>,
     on_edit_builder: EventHandler<PathBuf>,
     on_edit_json: EventHandler<PathBuf>,
+    // Provider file to package as a .nlaprovider
+    on_export: EventHandler<PathBuf>,
+    // Validated package and whether to keep its provider id
+    on_import: EventHandler<(ProviderPackage, bool)>,
 ) -> Element {
     let mut selected_provider = use_signal(|| None::<PathBuf>);
+    let mut pending_import = use_signal(|| None::<ProviderPackage>);
+    let mut import_error = use_signal(|| None::<String>);
+    // Global provider the pending import would duplicate
+    let mut import_duplicate = use_signal(|| None::<DuplicateProvider>);
+    let mut provider_health = use_signal(HashMap::<String, Result<ProviderHealth, String>>::new);
+
+    use_future(move || async move {
+        loop {
+            if *show.peek() {
+                let connections = provider_connections(&provider_files.peek());
+                let results = poll_provider_health(connections).await;
+                provider_health.set(results);
+            }
+            tokio::time::sleep(HEALTH_POLL_INTERVAL).await;
+        }
+    });
+
+    let refresh_health = move |_| {
+        invalidate_provider_health();
+        spawn(async move {
+            let connections = provider_connections(&provider_files.peek());
+            let results = poll_provider_health(connections).await;
+            provider_health.set(results);
+        });
+    };
     
+    let pick_import = move |_| {
+        let Some(path) = rfd::FileDialog::new()
+            .set_title("Import Provider")
+            .add_filter("NLA provider", &[PROVIDER_PACKAGE_EXTENSION])
+            .pick_file()
+        else {
+            return;
+        };
+        match read_provider_package(&path) {
+            Ok(package) => {
+                selected_provider.set(None);
+                import_error.set(None);
+                import_duplicate.set(find_duplicate_provider(
+                    &ProviderFingerprint::from_package(&package),
+                    &list_global_provider_files(),
+                    None,
+                    None,
+                ));
+                pending_import.set(Some(package));
+            }
+            Err(err) => {
+                pending_import.set(None);
+                import_error.set(Some(err));
+            }
+        }
+    };
+
     let providers_root = crate::core::provider_store::global_providers_root()
         .display()
         .to_string();
+    let provider_rows = provider_list_rows(&provider_files());
+    let project_providers_root = project_root.as_ref().map(|root| {
+        crate::core::provider_store::project_providers_root(root)
+            .display()
+            .to_string()
+    });
     
     rsx! {
         if !show() {
@@ -66,11 +214,17 @@ pub fn ProvidersModalV2(
                             style: "display: flex; flex-direction: column; gap: 4px;",
                             span { 
                                 style: "font-size: 13px; font-weight: 600; color: {TEXT_PRIMARY};", 
-                                "AI Providers (Global)" 
+                                "AI Providers" 
                             }
                             span { 
                                 style: "font-size: 10px; color: {TEXT_DIM};", 
-                                "{providers_root}" 
+                                "Global: {providers_root}" 
+                            }
+                            if let Some(project_providers_root) = project_providers_root.clone() {
+                                span {
+                                    style: "font-size: 10px; color: {TEXT_DIM};",
+                                    "Project: {project_providers_root}"
+                                }
                             }
                         }
                         button {
@@ -124,6 +278,32 @@ pub fn ProvidersModalV2(
                                     onclick: move |_| on_reload.call(()),
                                     "Reload"
                                 }
+                                button {
+                                    class: "collapse-btn",
+                                    style: "
+                                        flex: 1; padding: 6px 8px;
+                                        background-color: {BG_SURFACE};
+                                        border: 1px solid {BORDER_DEFAULT};
+                                        border-radius: 6px;
+                                        color: {TEXT_SECONDARY}; font-size: 11px; cursor: pointer;
+                                    ",
+                                    title: "Import a shared .nlaprovider file",
+                                    onclick: pick_import,
+                                    "Import"
+                                }
+                                button {
+                                    class: "collapse-btn",
+                                    style: "
+                                        flex: 1; padding: 6px 8px;
+                                        background-color: {BG_SURFACE};
+                                        border: 1px solid {BORDER_DEFAULT};
+                                        border-radius: 6px;
+                                        color: {TEXT_SECONDARY}; font-size: 11px; cursor: pointer;
+                                    ",
+                                    title: "Re-check whether each provider is reachable",
+                                    onclick: refresh_health,
+                                    "Refresh Status"
+                                }
                             }
                             
                             // Provider list
@@ -136,7 +316,7 @@ pub fn ProvidersModalV2(
                                     padding: 6px;
                                 ",
                                 
-                                if provider_files().is_empty() {
+                                if provider_rows.is_empty() {
                                     div {
                                         style: "
                                             padding: 10px; font-size: 11px; color: {TEXT_DIM};
@@ -145,29 +325,62 @@ pub fn ProvidersModalV2(
                                         "No providers yet"
                                     }
                                 } else {
-                                    for path in provider_files().iter() {
+                                    for (index, row) in provider_rows.iter().enumerate() {
                                         {
-                                            let file_name = path
+                                            let show_header = index == 0
+                                                || provider_rows[index - 1].category != row.category;
+                                            let category = row.category.clone();
+                                            let file_name = row.path
                                                 .file_name()
                                                 .and_then(|n| n.to_str())
-                                                .unwrap_or("provider.json");
-                                            let path_clone = path.clone();
+                                                .unwrap_or("provider.json")
+                                                .to_string();
+                                            let path_clone = row.path.clone();
+                                            let path_for_favorite = row.path.clone();
                                             let is_selected = selected_provider()
                                                 .as_ref()
-                                                .map(|s| s == path)
+                                                .map(|s| s == &row.path)
                                                 .unwrap_or(false);
                                             let item_bg = if is_selected { BG_HOVER } else { "transparent" };
                                             let item_border = if is_selected { BORDER_ACCENT } else { BORDER_SUBTLE };
-                                            
-                                            // Try to get provider name from JSON
-                                            let provider_name = read_provider_file(&path_clone)
-                                                .and_then(|json| serde_json::from_str::<serde_json::Value>(&json).ok())
-                                                .and_then(|v| v.get("name").and_then(|n| n.as_str()).map(|s| s.to_string()))
-                                                .unwrap_or_else(|| "Unnamed".to_string());
+                                            let scope = provider_scope_for_path(&row.path);
+                                            let scope_label = scope.label();
+                                            let scope_color = match scope {
+                                                ProviderScope::Global => TEXT_DIM,
+                                                ProviderScope::Project => ACCENT_PRIMARY,
+                                            };
+                                            let provider_name = row.name.clone();
+                                            let health = row.connection.as_ref().and_then(|connection| {
+                                                provider_health().get(&health_cache_key(connection)).cloned()
+                                            });
+                                            let (health_color, health_title) = match health {
+                                                Some(Ok(ProviderHealth::Online)) => ("#22c55e", "Online".to_string()),
+                                                Some(Ok(ProviderHealth::Unchecked)) => {
+                                                    (TEXT_DIM, "No health check; assumed online".to_string())
+                                                }
+                                                Some(Err(err)) => ("#ef4444", format!("Offline: {}", err)),
+                                                None => (TEXT_DIM, "Status unknown".to_string()),
+                                            };
+                                            let (star, star_color, star_title) = if row.favorite {
+                                                ("★", ACCENT_MARKER, "Remove from favorites")
+                                            } else {
+                                                ("☆", TEXT_DIM, "Add to favorites")
+                                            };
                                             
                                             rsx! {
+                                                if show_header {
+                                                    div {
+                                                        key: "header-{category}-{index}",
+                                                        style: "
+                                                            font-size: 9px; color: {TEXT_DIM};
+                                                            text-transform: uppercase; letter-spacing: 0.5px;
+                                                            padding: 4px 2px;
+                                                        ",
+                                                        "{category}"
+                                                    }
+                                                }
                                                 div {
-                                                    key: "{path.display()}",
+                                                    key: "{row.path.display()}",
                                                     class: "collapse-btn",
                                                     style: "
                                                         padding: 8px; margin-bottom: 6px;
@@ -177,11 +390,42 @@ pub fn ProvidersModalV2(
                                                         cursor: pointer;
                                                         display: flex; flex-direction: column; gap: 2px;
                                                     ",
-                                                    onclick: move |_| selected_provider.set(Some(path_clone.clone())),
+                                                    onclick: move |_| {
+                                                        pending_import.set(None);
+                                                        import_error.set(None);
+                                                        selected_provider.set(Some(path_clone.clone()));
+                                                    },
                                                     
-                                                    span { 
-                                                        style: "font-size: 11px; font-weight: 600; color: {TEXT_PRIMARY};", 
-                                                        "{provider_name}" 
+                                                    div {
+                                                        style: "display: flex; align-items: center; gap: 6px;",
+                                                        span {
+                                                            style: "font-size: 12px; color: {star_color}; cursor: pointer;",
+                                                            title: "{star_title}",
+                                                            onclick: move |e| {
+                                                                e.stop_propagation();
+                                                                on_toggle_favorite.call(path_for_favorite.clone());
+                                                            },
+                                                            "{star}"
+                                                        }
+                                                        span {
+                                                            style: "
+                                                                width: 7px; height: 7px; border-radius: 50%;
+                                                                background-color: {health_color}; flex-shrink: 0;
+                                                            ",
+                                                            title: "{health_title}",
+                                                        }
+                                                        span { 
+                                                            style: "flex: 1; min-width: 0; font-size: 11px; font-weight: 600; color: {TEXT_PRIMARY};", 
+                                                            "{provider_name}" 
+                                                        }
+                                                        span {
+                                                            style: "
+                                                                font-size: 9px; color: {scope_color};
+                                                                border: 1px solid {scope_color}; border-radius: 3px;
+                                                                padding: 0 4px; flex-shrink: 0;
+                                                            ",
+                                                            "{scope_label}"
+                                                        }
                                                     }
                                                     span { 
                                                         style: "font-size: 9px; color: {TEXT_DIM};", 
@@ -225,7 +469,103 @@ pub fn ProvidersModalV2(
                                 gap: 16px;
                             ",
                             
-                            if let Some(path) = selected_provider() {
+                            if let Some(package) = pending_import() {
+                                {
+                                    let provider_name = package.provider.name.clone();
+                                    let duplicate = import_duplicate();
+                                    let package_for_keep = match duplicate.as_ref() {
+                                        // Overwriting takes over the existing provider's id and file
+                                        Some(duplicate) => {
+                                            let mut package = package.clone();
+                                            package.provider.id = duplicate.id;
+                                            package
+                                        }
+                                        None => package.clone(),
+                                    };
+                                    let package_for_new = package.clone();
+                                    let (hint, keep_label, new_label) = match duplicate.as_ref() {
+                                        Some(duplicate) => (
+                                            format!(
+                                                "\"{}\" already runs this workflow with the same inputs and output.",
+                                                duplicate.name
+                                            ),
+                                            format!("Overwrite \"{}\"", duplicate.name),
+                                            "Keep Both".to_string(),
+                                        ),
+                                        None => (
+                                            "Keeping the ID replaces a provider you imported before; a new ID adds a separate copy."
+                                                .to_string(),
+                                            "Keep Provider ID".to_string(),
+                                            "Import as New Provider".to_string(),
+                                        ),
+                                    };
+                                    let hint_color = if duplicate.is_some() { "#eab308" } else { TEXT_DIM };
+                                    rsx! {
+                                        div {
+                                            style: "text-align: center; margin-bottom: 12px;",
+                                            div {
+                                                style: "font-size: 14px; font-weight: 600; color: {TEXT_PRIMARY}; margin-bottom: 4px;",
+                                                "Import \"{provider_name}\""
+                                            }
+                                            div {
+                                                style: "font-size: 10px; color: {hint_color}; max-width: 320px;",
+                                                "{hint}"
+                                            }
+                                        }
+                                        button {
+                                            class: "collapse-btn",
+                                            style: "
+                                                width: 240px; padding: 12px 16px;
+                                                background-color: {BG_SURFACE};
+                                                border: 1px solid {BORDER_DEFAULT};
+                                                border-radius: 8px;
+                                                color: {TEXT_PRIMARY}; font-size: 12px; font-weight: 600;
+                                                cursor: pointer;
+                                            ",
+                                            onclick: move |_| {
+                                                on_import.call((package_for_keep.clone(), true));
+                                                pending_import.set(None);
+                                                import_duplicate.set(None);
+                                            },
+                                            "{keep_label}"
+                                        }
+                                        button {
+                                            class: "collapse-btn",
+                                            style: "
+                                                width: 240px; padding: 12px 16px;
+                                                background-color: {BG_SURFACE};
+                                                border: 1px solid {BORDER_DEFAULT};
+                                                border-radius: 8px;
+                                                color: {TEXT_PRIMARY}; font-size: 12px; font-weight: 600;
+                                                cursor: pointer;
+                                            ",
+                                            onclick: move |_| {
+                                                on_import.call((package_for_new.clone(), false));
+                                                pending_import.set(None);
+                                                import_duplicate.set(None);
+                                            },
+                                            "{new_label}"
+                                        }
+                                        button {
+                                            class: "collapse-btn",
+                                            style: "
+                                                background: transparent; border: none; color: {TEXT_SECONDARY};
+                                                font-size: 11px; cursor: pointer; padding: 4px 8px;
+                                            ",
+                                            onclick: move |_| {
+                                                pending_import.set(None);
+                                                import_duplicate.set(None);
+                                            },
+                                            "Cancel"
+                                        }
+                                    }
+                                }
+                            } else if let Some(err) = import_error() {
+                                div {
+                                    style: "font-size: 11px; color: #ef4444; text-align: center; max-width: 340px;",
+                                    "Import failed: {err}"
+                                }
+                            } else if let Some(path) = selected_provider() {
                                 // Get provider info
                                 {
                                     let provider_name = read_provider_file(&path)
@@ -249,6 +589,7 @@ pub fn ProvidersModalV2(
                                         {
                                             let path_for_builder = path.clone();
                                             let path_for_json = path.clone();
+                                            let path_for_export = path.clone();
                                             rsx! {
                                                 button {
                                                     class: "collapse-btn",
@@ -277,6 +618,21 @@ pub fn ProvidersModalV2(
                                                     onclick: move |_| on_edit_json.call(path_for_json.clone()),
                                                     "Edit as JSON"
                                                 }
+
+                                                button {
+                                                    class: "collapse-btn",
+                                                    style: "
+                                                        width: 240px; padding: 12px 16px;
+                                                        background-color: {BG_SURFACE};
+                                                        border: 1px solid {BORDER_DEFAULT};
+                                                        border-radius: 8px;
+                                                        color: {TEXT_PRIMARY}; font-size: 12px; font-weight: 600;
+                                                        cursor: pointer;
+                                                    ",
+                                                    title: "Save this provider with its workflow and manifest as one file",
+                                                    onclick: move |_| on_export.call(path_for_export.clone()),
+                                                    "Export..."
+                                                }
                                             }
                                         }
                                     }
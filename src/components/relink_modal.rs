@@ -0,0 +1,115 @@
+use dioxus::prelude::*;
+use uuid::Uuid;
+
+use crate::constants::*;
+use crate::state::Asset;
+
+/// Lists assets whose source file wasn't found, with a way to point each at
+/// its new location or to search a folder for all of them by file name.
+#[component]
+pub fn RelinkModal(
+    assets: Vec<Asset>,
+    on_locate: EventHandler<Uuid>,
+    on_search_folder: EventHandler<MouseEvent>,
+    on_close: EventHandler<MouseEvent>,
+) -> Element {
+    let button_style = format!(
+        "
+            padding: 4px 10px; font-size: 11px;
+            background-color: {};
+            border: 1px solid {};
+            border-radius: 6px; color: {};
+            cursor: pointer; flex-shrink: 0;
+        ",
+        BG_SURFACE, BORDER_DEFAULT, TEXT_PRIMARY
+    );
+    let summary = match assets.len() {
+        0 => "All media files were found.".to_string(),
+        1 => "1 media file was not found.".to_string(),
+        count => format!("{} media files were not found.", count),
+    };
+
+    rsx! {
+        div {
+            style: "
+                position: fixed; inset: 0;
+                background: rgba(0, 0, 0, 0.45);
+                z-index: 2000;
+            ",
+            onclick: move |e| on_close.call(e),
+        }
+        div {
+            style: "
+                position: fixed; top: 50%; left: 50%;
+                transform: translate(-50%, -50%);
+                width: 460px; max-height: 70vh;
+                padding: 14px;
+                background-color: {BG_ELEVATED};
+                border: 1px solid {BORDER_DEFAULT};
+                border-radius: 10px;
+                box-shadow: 0 14px 30px rgba(0,0,0,0.45);
+                display: flex; flex-direction: column; gap: 10px;
+                z-index: 2001;
+            ",
+            span { style: "font-size: 13px; color: {TEXT_PRIMARY};", "Relink Media" }
+            span {
+                style: "font-size: 11px; color: {TEXT_MUTED};",
+                "{summary} Clips using them stay on the timeline but show nothing until relinked."
+            }
+            div {
+                style: "display: flex; flex-direction: column; gap: 4px; overflow-y: auto;",
+                for asset in assets.iter() {
+                    {
+                        let asset_id = asset.id;
+                        let stored_path = asset
+                            .source_file()
+                            .map(|path| path.display().to_string())
+                            .unwrap_or_default();
+                        rsx! {
+                            div {
+                                key: "{asset_id}",
+                                style: "
+                                    display: flex; align-items: center; gap: 8px;
+                                    padding: 6px 8px; border-radius: 6px;
+                                    background-color: {BG_SURFACE}; border: 1px solid {BORDER_SUBTLE};
+                                ",
+                                div {
+                                    style: "flex: 1; min-width: 0; display: flex; flex-direction: column;",
+                                    span {
+                                        style: "font-size: 12px; color: {TEXT_PRIMARY}; overflow: hidden; text-overflow: ellipsis; white-space: nowrap;",
+                                        "{asset.name}"
+                                    }
+                                    span {
+                                        style: "font-size: 10px; color: #ef4444; overflow: hidden; text-overflow: ellipsis; white-space: nowrap;",
+                                        title: "{stored_path}",
+                                        "{stored_path}"
+                                    }
+                                }
+                                button {
+                                    style: "{button_style}",
+                                    onclick: move |_| on_locate.call(asset_id),
+                                    "Locate..."
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            div {
+                style: "display: flex; justify-content: flex-end; gap: 8px;",
+                if !assets.is_empty() {
+                    button {
+                        style: "{button_style}",
+                        onclick: move |e| on_search_folder.call(e),
+                        "Search Folder..."
+                    }
+                }
+                button {
+                    style: "{button_style}",
+                    onclick: move |e| on_close.call(e),
+                    "Close"
+                }
+            }
+        }
+    }
+}
@@ -1,7 +1,15 @@
 use dioxus::prelude::*;
 use std::path::PathBuf;
 use crate::constants::*;
-use crate::state::ProjectSettings;
+use crate::core::recent_projects::{forget_recent_project, RecentProjects};
+use crate::state::{ProjectSettings, ResampleQuality};
+
+/// Waveform detail presets as (label, peaks per second).
+const WAVEFORM_DETAIL_PRESETS: [(&str, f64); 3] = [
+    ("Fast", 46.875),
+    ("Standard", 187.5),
+    ("Detailed", 750.0),
+];
 
 #[derive(Clone, Copy, PartialEq)]
 pub enum StartupModalMode {
@@ -37,6 +45,27 @@ pub fn StartupModal(
     let mut duration = use_signal(|| (seed_settings.duration_seconds / 60.0).to_string());
     let mut preview_max_width = use_signal(|| seed_settings.preview_max_width.to_string());
     let mut preview_max_height = use_signal(|| seed_settings.preview_max_height.to_string());
+    let preview_cache_default = seed_settings.preview_cache_mb;
+    let mut preview_cache_mb = use_signal(|| seed_settings.preview_cache_mb.to_string());
+    let mut waveform_peaks_per_second = use_signal(|| seed_settings.waveform_peaks_per_second);
+    let mut clamp_pasted_clips = use_signal(|| seed_settings.clamp_pasted_clips);
+    let mut force_cpu_preview = use_signal(|| seed_settings.force_cpu_preview);
+    let mut use_proxies = use_signal(|| seed_settings.use_proxies);
+    let mut preview_resample_quality = use_signal(|| seed_settings.preview_resample_quality);
+    let mut allow_duplicate_overlap = use_signal(|| seed_settings.allow_duplicate_overlap);
+    let mut magnetic_timeline = use_signal(|| seed_settings.magnetic_timeline);
+    let time_format = seed_settings.time_format;
+    let preview_guides = seed_settings.preview_guides;
+    let asset_sort = seed_settings.asset_sort;
+    let [bg_r, bg_g, bg_b, bg_a] = seed_settings.background_color;
+    let mut background_hex = use_signal(|| format!("#{:02x}{:02x}{:02x}", bg_r, bg_g, bg_b));
+    let mut background_alpha = use_signal(|| bg_a.to_string());
+    let max_attempts_default = seed_settings.generation_max_attempts;
+    let mut generation_max_attempts =
+        use_signal(|| seed_settings.generation_max_attempts.to_string());
+    let max_concurrent_default = seed_settings.generation_max_concurrent;
+    let mut generation_max_concurrent =
+        use_signal(|| seed_settings.generation_max_concurrent.to_string());
     let header_title = if is_edit {
         "Project Settings"
     } else {
@@ -85,6 +114,21 @@ pub fn StartupModal(
             .unwrap_or(default)
     }
 
+    // `#rrggbb` from the color picker plus a 0-255 alpha field.
+    fn parse_background(hex: &str, alpha: &str, default: [u8; 4]) -> [u8; 4] {
+        let digits = hex.trim().trim_start_matches('#');
+        let channel = |index: usize| {
+            digits
+                .get(index..index + 2)
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+        };
+        let (Some(r), Some(g), Some(b)) = (channel(0), channel(2), channel(4)) else {
+            return default;
+        };
+        let a = alpha.trim().parse::<u8>().unwrap_or(default[3]);
+        [r, g, b, a]
+    }
+
     fn parse_f64(value: &str, default: f64, min: f64) -> f64 {
         value
             .trim()
@@ -94,10 +138,18 @@ pub fn StartupModal(
             .unwrap_or(default)
     }
     
-    // Scan for existing projects (folders containing project.json)
+    // Recently opened projects first (newest first, wherever they live), then
+    // any other project in the default folder.
     // Re-runs when refresh_counter changes
     let _ = refresh_counter(); // Subscribe to changes
-    let existing_projects: Vec<(String, std::path::PathBuf)> = if projects_folder_for_scan.exists() {
+    let mut recent_projects = RecentProjects::load_user();
+    recent_projects.prune_missing();
+    let recent_entries: Vec<(String, std::path::PathBuf)> = recent_projects
+        .entries()
+        .iter()
+        .map(|entry| (entry.display_name(), entry.path.clone()))
+        .collect();
+    let scanned_projects: Vec<(String, std::path::PathBuf)> = if projects_folder_for_scan.exists() {
         std::fs::read_dir(&projects_folder_for_scan)
             .map(|entries| {
                 entries
@@ -118,6 +170,15 @@ pub fn StartupModal(
     } else {
         Vec::new()
     };
+    let mut existing_projects = recent_entries;
+    for (name, path) in scanned_projects {
+        let already_listed = existing_projects
+            .iter()
+            .any(|(_, listed)| listed == &path);
+        if !already_listed {
+            existing_projects.push((name, path));
+        }
+    }
 
     rsx! {
         div {
@@ -367,6 +428,50 @@ pub fn StartupModal(
                                 }
                             }
 
+                            // Background color section
+                            div {
+                                label {
+                                    style: "
+                                        display: block; font-size: 11px; font-weight: 500;
+                                        color: {TEXT_MUTED}; margin-bottom: 8px;
+                                        text-transform: uppercase; letter-spacing: 0.5px;
+                                    ",
+                                    "Background"
+                                }
+                                div {
+                                    style: "display: flex; align-items: center; gap: 8px; font-size: 12px; color: {TEXT_SECONDARY};",
+                                    input {
+                                        r#type: "color",
+                                        value: "{background_hex}",
+                                        style: "
+                                            width: 40px; height: 28px; padding: 0;
+                                            background: {BG_BASE}; border: 1px solid {BORDER_DEFAULT};
+                                            border-radius: 4px; cursor: pointer;
+                                        ",
+                                        oninput: move |e| background_hex.set(e.value()),
+                                    }
+                                    span { style: "font-family: 'SF Mono', Consolas, monospace; color: {TEXT_DIM};", "{background_hex}" }
+                                    "Alpha"
+                                    crate::components::common::StableNumberInput {
+                                        id: "background-alpha-input".to_string(),
+                                        value: background_alpha(),
+                                        placeholder: None,
+                                        style: Some(format!("
+                                            width: 56px; padding: 4px 6px; background: {};
+                                            border: 1px solid {}; border-radius: 4px;
+                                            color: {}; font-size: 12px; outline: none;
+                                            text-align: center; user-select: text;
+                                        ", BG_BASE, BORDER_DEFAULT, TEXT_PRIMARY)),
+                                        min: Some("0".to_string()),
+                                        max: Some("255".to_string()),
+                                        step: Some("1".to_string()),
+                                        on_change: move |v: String| background_alpha.set(v),
+                                        on_blur: move |_| {},
+                                        on_keydown: move |_| {},
+                                    }
+                                }
+                            }
+
                             // Preview downsample section
                             div {
                                 div {
@@ -448,6 +553,146 @@ pub fn StartupModal(
                                         on_keydown: move |_| {},
                                     }
                                 }
+                                div {
+                                    style: "display: flex; align-items: center; gap: 8px; margin-top: 8px; font-size: 12px; color: {TEXT_SECONDARY};",
+                                    "Keep up to"
+                                    crate::components::common::StableNumberInput {
+                                        id: "preview-cache-mb-input".to_string(),
+                                        value: preview_cache_mb(),
+                                        placeholder: None,
+                                        style: Some(format!("
+                                            width: 72px; padding: 4px 6px; background: {};
+                                            border: 1px solid {}; border-radius: 4px;
+                                            color: {}; font-size: 12px; outline: none;
+                                            text-align: center; user-select: text;
+                                        ", BG_BASE, BORDER_DEFAULT, TEXT_PRIMARY)),
+                                        min: Some("256".to_string()),
+                                        max: None,
+                                        step: Some("256".to_string()),
+                                        on_change: move |v: String| preview_cache_mb.set(v),
+                                        on_blur: move |_| {},
+                                        on_keydown: move |_| {},
+                                    }
+                                    "MB of decoded frames in memory"
+                                }
+                                label {
+                                    style: "display: flex; align-items: center; gap: 6px; margin-top: 8px; font-size: 12px; color: {TEXT_SECONDARY}; cursor: pointer;",
+                                    input {
+                                        r#type: "checkbox",
+                                        checked: force_cpu_preview(),
+                                        onchange: move |_| force_cpu_preview.set(!force_cpu_preview()),
+                                    }
+                                    "Composite the preview on the CPU (skip GPU acceleration)"
+                                }
+                                label {
+                                    style: "display: flex; align-items: center; gap: 6px; margin-top: 6px; font-size: 12px; color: {TEXT_SECONDARY}; cursor: pointer;",
+                                    input {
+                                        r#type: "checkbox",
+                                        checked: use_proxies(),
+                                        onchange: move |_| use_proxies.set(!use_proxies()),
+                                    }
+                                    "Preview from proxies where assets have them (exports use originals)"
+                                }
+                            }
+
+                            // Waveform detail section
+                            div {
+                                div {
+                                    style: "display: flex; align-items: center; gap: 6px; margin-bottom: 8px;",
+                                    label {
+                                        style: "
+                                            display: block; font-size: 11px; font-weight: 500;
+                                            color: {TEXT_MUTED};
+                                            text-transform: uppercase; letter-spacing: 0.5px;
+                                        ",
+                                        "Waveform Detail"
+                                    }
+                                    div {
+                                        class: "info-tooltip",
+                                        style: "
+                                            position: relative; width: 14px; height: 14px;
+                                            border-radius: 50%; border: 1px solid {TEXT_DIM};
+                                            display: flex; align-items: center; justify-content: center;
+                                            font-size: 9px; color: {TEXT_DIM}; cursor: help;
+                                        ",
+                                        "!"
+                                        div {
+                                            style: "
+                                                position: absolute; left: 20px; top: -8px;
+                                                background: {BG_ELEVATED}; border: 1px solid {BORDER_DEFAULT};
+                                                border-radius: 6px; padding: 8px 12px;
+                                                font-size: 11px; color: {TEXT_SECONDARY};
+                                                white-space: nowrap; pointer-events: none;
+                                                opacity: 0; transition: opacity 0.2s ease;
+                                                box-shadow: 0 4px 12px rgba(0,0,0,0.3);
+                                                z-index: 1000;
+                                            ",
+                                            class: "tooltip-content",
+                                            "Lower detail builds waveforms faster. Changing it rebuilds audio caches."
+                                        }
+                                    }
+                                }
+                                div {
+                                    style: "display: flex; gap: 6px; align-items: center;",
+                                    for (label, peaks) in WAVEFORM_DETAIL_PRESETS {
+                                        {
+                                            let is_active = (waveform_peaks_per_second() - peaks).abs() < f64::EPSILON;
+                                            let border_color = if is_active { ACCENT_VIDEO } else { BORDER_DEFAULT };
+                                            rsx! {
+                                                button {
+                                                    key: "{label}",
+                                                    style: "
+                                                        padding: 6px 12px; border-radius: 6px; font-size: 11px;
+                                                        border: 1px solid {border_color}; cursor: pointer;
+                                                        background: {BG_SURFACE}; color: {TEXT_SECONDARY};
+                                                        transition: all 0.15s ease;
+                                                    ",
+                                                    onclick: move |_| waveform_peaks_per_second.set(peaks),
+                                                    "{label}"
+                                                }
+                                            }
+                                        }
+                                    }
+                                    span {
+                                        style: "margin-left: 4px; color: {TEXT_DIM}; font-size: 11px;",
+                                        "{waveform_peaks_per_second().round()} peaks/s"
+                                    }
+                                }
+                            }
+
+                            // Resample quality section
+                            div {
+                                label {
+                                    style: "
+                                        display: block; font-size: 11px; font-weight: 500;
+                                        color: {TEXT_MUTED}; margin-bottom: 8px;
+                                        text-transform: uppercase; letter-spacing: 0.5px;
+                                    ",
+                                    title: "Used when decoding audio for playback and waveforms. Exports always use Sinc.",
+                                    "Playback Resampling"
+                                }
+                                div {
+                                    style: "display: flex; gap: 6px; align-items: center;",
+                                    for quality in ResampleQuality::ALL {
+                                        {
+                                            let is_active = preview_resample_quality() == quality;
+                                            let border_color = if is_active { ACCENT_VIDEO } else { BORDER_DEFAULT };
+                                            rsx! {
+                                                button {
+                                                    key: "{quality.as_str()}",
+                                                    style: "
+                                                        padding: 6px 12px; border-radius: 6px; font-size: 11px;
+                                                        border: 1px solid {border_color}; cursor: pointer;
+                                                        background: {BG_SURFACE}; color: {TEXT_SECONDARY};
+                                                        transition: all 0.15s ease;
+                                                    ",
+                                                    onclick: move |_| preview_resample_quality.set(quality),
+                                                    "{quality.label()}"
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
                             }
 
                             // FPS & Duration row
@@ -533,6 +778,91 @@ pub fn StartupModal(
                                 }
                             }
 
+                            // Editing behaviour
+                            if is_edit {
+                                div {
+                                    label {
+                                        style: "
+                                            display: block; font-size: 11px; font-weight: 500;
+                                            color: {TEXT_MUTED}; margin-bottom: 8px;
+                                            text-transform: uppercase; letter-spacing: 0.5px;
+                                        ",
+                                        "Editing"
+                                    }
+                                    label {
+                                        style: "display: flex; align-items: center; gap: 6px; font-size: 12px; color: {TEXT_SECONDARY}; cursor: pointer;",
+                                        input {
+                                            r#type: "checkbox",
+                                            checked: clamp_pasted_clips(),
+                                            onchange: move |_| clamp_pasted_clips.set(!clamp_pasted_clips()),
+                                        }
+                                        "Keep pasted clips inside the project duration"
+                                    }
+                                    label {
+                                        style: "display: flex; align-items: center; gap: 6px; margin-top: 6px; font-size: 12px; color: {TEXT_SECONDARY}; cursor: pointer;",
+                                        input {
+                                            r#type: "checkbox",
+                                            checked: allow_duplicate_overlap(),
+                                            onchange: move |_| allow_duplicate_overlap.set(!allow_duplicate_overlap()),
+                                        }
+                                        "Let duplicates overlap the next clip"
+                                    }
+                                    label {
+                                        style: "display: flex; align-items: center; gap: 6px; margin-top: 6px; font-size: 12px; color: {TEXT_SECONDARY}; cursor: pointer;",
+                                        input {
+                                            r#type: "checkbox",
+                                            checked: magnetic_timeline(),
+                                            onchange: move |_| magnetic_timeline.set(!magnetic_timeline()),
+                                        }
+                                        "Magnetic timeline (close gaps when clips move)"
+                                    }
+                                    div {
+                                        style: "display: flex; align-items: center; gap: 8px; margin-top: 8px; font-size: 12px; color: {TEXT_SECONDARY};",
+                                        "Try failed generations up to"
+                                        crate::components::common::StableNumberInput {
+                                            id: "generation-max-attempts-input".to_string(),
+                                            value: generation_max_attempts(),
+                                            placeholder: None,
+                                            style: Some(format!("
+                                                width: 48px; padding: 4px 6px; background: {};
+                                                border: 1px solid {}; border-radius: 4px;
+                                                color: {}; font-size: 12px; outline: none;
+                                                text-align: center; user-select: text;
+                                            ", BG_BASE, BORDER_DEFAULT, TEXT_PRIMARY)),
+                                            min: Some("1".to_string()),
+                                            max: Some("10".to_string()),
+                                            step: Some("1".to_string()),
+                                            on_change: move |v: String| generation_max_attempts.set(v),
+                                            on_blur: move |_| {},
+                                            on_keydown: move |_| {},
+                                        }
+                                        "times"
+                                    }
+                                    div {
+                                        style: "display: flex; align-items: center; gap: 8px; margin-top: 8px; font-size: 12px; color: {TEXT_SECONDARY};",
+                                        "Run up to"
+                                        crate::components::common::StableNumberInput {
+                                            id: "generation-max-concurrent-input".to_string(),
+                                            value: generation_max_concurrent(),
+                                            placeholder: None,
+                                            style: Some(format!("
+                                                width: 48px; padding: 4px 6px; background: {};
+                                                border: 1px solid {}; border-radius: 4px;
+                                                color: {}; font-size: 12px; outline: none;
+                                                text-align: center; user-select: text;
+                                            ", BG_BASE, BORDER_DEFAULT, TEXT_PRIMARY)),
+                                            min: Some("1".to_string()),
+                                            max: Some("16".to_string()),
+                                            step: Some("1".to_string()),
+                                            on_change: move |v: String| generation_max_concurrent.set(v),
+                                            on_blur: move |_| {},
+                                            on_keydown: move |_| {},
+                                        }
+                                        "generations at once"
+                                    }
+                                }
+                            }
+
                             // Divider
                             div { 
                                 style: "height: 1px; background: linear-gradient(90deg, {BORDER_SUBTLE} 0%, transparent 100%); margin: 8px 0;" 
@@ -648,6 +978,38 @@ pub fn StartupModal(
                                                 preview_default_height,
                                                 1,
                                             ),
+                                            waveform_peaks_per_second: waveform_peaks_per_second(),
+                                            clamp_pasted_clips: clamp_pasted_clips(),
+                                            allow_duplicate_overlap: allow_duplicate_overlap(),
+                                            magnetic_timeline: magnetic_timeline(),
+                                            time_format,
+                                            generation_max_attempts: parse_u32(
+                                                &generation_max_attempts(),
+                                                max_attempts_default as u32,
+                                                1,
+                                            )
+                                            .min(10) as u8,
+                                            generation_max_concurrent: parse_u32(
+                                                &generation_max_concurrent(),
+                                                max_concurrent_default as u32,
+                                                1,
+                                            )
+                                            .min(16) as u8,
+                                            preview_cache_mb: parse_u32(
+                                                &preview_cache_mb(),
+                                                preview_cache_default,
+                                                256,
+                                            ),
+                                            force_cpu_preview: force_cpu_preview(),
+                                            use_proxies: use_proxies(),
+                                            preview_resample_quality: preview_resample_quality(),
+                                            background_color: parse_background(
+                                                &background_hex(),
+                                                &background_alpha(),
+                                                [bg_r, bg_g, bg_b, bg_a],
+                                            ),
+                                            preview_guides,
+                                            asset_sort,
                                         };
                                         on_update.call(settings);
                                         on_close.call(e);
@@ -685,6 +1047,38 @@ pub fn StartupModal(
                                                 preview_default_height,
                                                 1,
                                             ),
+                                            waveform_peaks_per_second: waveform_peaks_per_second(),
+                                            clamp_pasted_clips: clamp_pasted_clips(),
+                                            allow_duplicate_overlap: allow_duplicate_overlap(),
+                                            magnetic_timeline: magnetic_timeline(),
+                                            time_format,
+                                            generation_max_attempts: parse_u32(
+                                                &generation_max_attempts(),
+                                                max_attempts_default as u32,
+                                                1,
+                                            )
+                                            .min(10) as u8,
+                                            generation_max_concurrent: parse_u32(
+                                                &generation_max_concurrent(),
+                                                max_concurrent_default as u32,
+                                                1,
+                                            )
+                                            .min(16) as u8,
+                                            preview_cache_mb: parse_u32(
+                                                &preview_cache_mb(),
+                                                preview_cache_default,
+                                                256,
+                                            ),
+                                            force_cpu_preview: force_cpu_preview(),
+                                            use_proxies: use_proxies(),
+                                            preview_resample_quality: preview_resample_quality(),
+                                            background_color: parse_background(
+                                                &background_hex(),
+                                                &background_alpha(),
+                                                [bg_r, bg_g, bg_b, bg_a],
+                                            ),
+                                            preview_guides,
+                                            asset_sort,
                                         };
                                         on_create.call((parent_dir(), n, settings));
                                     }
@@ -765,6 +1159,7 @@ pub fn StartupModal(
                                                 div {
                                                     class: "collapse-btn",
                                                     key: "{proj_path.display()}",
+                                                    title: "{proj_path.display()}",
                                                     style: "
                                                         padding: 12px 14px; cursor: pointer;
                                                         border-bottom: 1px solid {BORDER_SUBTLE};
@@ -880,6 +1275,7 @@ pub fn StartupModal(
                             } else {
                                 println!("Deleted project: {:?}", proj_path);
                             }
+                            forget_recent_project(&proj_path);
                             // Close menu and refresh list
                             context_menu.set(None);
                             refresh_counter.set(refresh_counter() + 1);
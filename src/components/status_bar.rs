@@ -1,17 +1,178 @@
 use dioxus::prelude::*;
+use std::sync::Arc;
 use crate::constants::*;
+use crate::core::preview::PreviewRenderer;
+use crate::core::timecode::format_time;
+use crate::state::{GenerationJob, GenerationJobStatus, OverlapResolution, TimeFormat};
+
+/// How often the preview cache readout is refreshed.
+const CACHE_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
 
 #[component]
-pub fn StatusBar() -> Element {
+pub fn StatusBar(
+    // Why the preview is composited on the CPU, when it is
+    preview_note: Option<String>,
+    // Last measured integrated loudness of the mixdown
+    mix_loudness: Option<String>,
+    // Short-lived notice, e.g. files a drop skipped
+    #[props(default = None)]
+    status_message: Option<String>,
+    // Overlapping clip pairs across all tracks
+    overlap_count: usize,
+    on_resolve_overlaps: EventHandler<OverlapResolution>,
+    // Read here rather than passed as values so playback and queue updates
+    // re-render only the status bar
+    current_time: Signal<f64>,
+    generation_queue: Signal<Vec<GenerationJob>>,
+    previewer: Signal<Arc<PreviewRenderer>>,
+    fps: f64,
+    time_format: TimeFormat,
+    duration: f64,
+) -> Element {
+    // The frame cache lives behind a lock in the renderer, so it is polled.
+    let mut cache_usage = use_signal(|| (0_usize, 0_usize));
+    use_future(move || async move {
+        loop {
+            let usage = previewer.peek().cache_usage();
+            if *cache_usage.peek() != usage {
+                cache_usage.set(usage);
+            }
+            tokio::time::sleep(CACHE_POLL_INTERVAL).await;
+        }
+    });
+    let (cache_used, cache_budget) = cache_usage();
+    let cache_label = format!(
+        "Cache {} / {}",
+        format_bytes(cache_used as u64),
+        format_bytes(cache_budget as u64)
+    );
+    let job_summary = {
+        let jobs = generation_queue.read();
+        let count = |status: GenerationJobStatus| jobs.iter().filter(|job| job.status == status).count();
+        job_count_summary(count(GenerationJobStatus::Running), count(GenerationJobStatus::Queued))
+    };
+    let playhead_label = format_time(current_time(), fps, time_format);
+    let duration_label = format_time(duration, fps, time_format);
+    let fps_label = (fps * 100.0).round() / 100.0;
+    let overlap_label = if overlap_count == 1 {
+        "1 overlap".to_string()
+    } else {
+        format!("{} overlaps", overlap_count)
+    };
     rsx! {
         div {
             style: "display: flex; align-items: center; justify-content: space-between; height: 22px; padding: 0 14px; background-color: {BG_SURFACE}; border-top: 1px solid {BORDER_DEFAULT}; font-size: 11px; color: {TEXT_DIM};",
-            span { "Ready" }
+            div {
+                style: "display: flex; align-items: center; gap: 12px; min-width: 0;",
+                if let Some(message) = status_message {
+                    span {
+                        style: "color: {TEXT_SECONDARY}; white-space: nowrap; overflow: hidden; text-overflow: ellipsis;",
+                        title: "{message}",
+                        "{message}"
+                    }
+                } else {
+                    span { "Ready" }
+                }
+                if overlap_count > 0 {
+                    span {
+                        style: "display: flex; align-items: center; gap: 6px; color: #ef4444; white-space: nowrap;",
+                        title: "Clips on the same track overlap; only the top one is visible or audible where they do",
+                        "⚠ {overlap_label}"
+                        button {
+                            style: "padding: 0 6px; font-size: 10px; background: transparent; color: {TEXT_SECONDARY}; border: 1px solid {BORDER_DEFAULT}; border-radius: 3px; cursor: pointer;",
+                            title: "Push overlapping clips later until they touch",
+                            onclick: move |_| on_resolve_overlaps.call(OverlapResolution::Ripple),
+                            "Ripple"
+                        }
+                        button {
+                            style: "padding: 0 6px; font-size: 10px; background: transparent; color: {TEXT_SECONDARY}; border: 1px solid {BORDER_DEFAULT}; border-radius: 3px; cursor: pointer;",
+                            title: "Shorten clips so they end where the next one starts",
+                            onclick: move |_| on_resolve_overlaps.call(OverlapResolution::Trim),
+                            "Trim"
+                        }
+                    }
+                }
+                if let Some(note) = preview_note {
+                    span {
+                        style: "color: {TEXT_MUTED}; white-space: nowrap; overflow: hidden; text-overflow: ellipsis;",
+                        title: "{note}",
+                        "{note}"
+                    }
+                }
+            }
             div {
                 style: "display: flex; gap: 16px; font-family: 'SF Mono', Consolas, monospace;",
-                span { "60 fps" }
-                span { "00:00 / 00:00" }
+                if let Some(summary) = job_summary {
+                    span {
+                        style: "color: {ACCENT_MARKER};",
+                        title: "Generation jobs in the queue",
+                        "{summary}"
+                    }
+                }
+                span {
+                    title: "Decoded preview frames held in memory, out of the cache budget",
+                    "{cache_label}"
+                }
+                if let Some(loudness) = mix_loudness {
+                    span {
+                        title: "Integrated loudness of the full mixdown (YouTube normalizes to -14 LUFS)",
+                        "Mix {loudness}"
+                    }
+                }
+                span { "{fps_label} fps" }
+                span { "{playhead_label} / {duration_label}" }
             }
         }
     }
 }
+
+/// Byte count in the largest unit that keeps it at or above one, e.g. `512 B`,
+/// `1.5 KB`, `240.0 MB`, `2.00 GB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const KB: f64 = 1024.0;
+    const MB: f64 = KB * 1024.0;
+    const GB: f64 = MB * 1024.0;
+    let value = bytes as f64;
+    if value >= GB {
+        format!("{:.2} GB", value / GB)
+    } else if value >= MB {
+        format!("{:.1} MB", value / MB)
+    } else if value >= KB {
+        format!("{:.1} KB", value / KB)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
+/// Status bar summary of the generation queue, or `None` when it is idle.
+pub fn job_count_summary(running: usize, queued: usize) -> Option<String> {
+    match (running, queued) {
+        (0, 0) => None,
+        (running, 0) => Some(format!("{} generating", running)),
+        (0, queued) => Some(format!("{} queued", queued)),
+        (running, queued) => Some(format!("{} generating · {} queued", running, queued)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes_units() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1024), "1.0 KB");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(240 * 1024 * 1024), "240.0 MB");
+        assert_eq!(format_bytes(2 * 1024 * 1024 * 1024), "2.00 GB");
+    }
+
+    #[test]
+    fn test_job_count_summary() {
+        assert_eq!(job_count_summary(0, 0), None);
+        assert_eq!(job_count_summary(1, 0).as_deref(), Some("1 generating"));
+        assert_eq!(job_count_summary(0, 4).as_deref(), Some("4 queued"));
+        assert_eq!(job_count_summary(2, 3).as_deref(), Some("2 generating · 3 queued"));
+    }
+}
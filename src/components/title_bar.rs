@@ -1,6 +1,15 @@
 use dioxus::prelude::*;
 use crate::constants::*;
 
+/// Project subfolders offered by File > Reveal, as (label, path relative to the project root).
+const REVEAL_FOLDERS: [(&str, &str); 5] = [
+    ("Reveal Project Folder", ""),
+    ("Reveal Video Folder", "video"),
+    ("Reveal Audio Folder", "audio"),
+    ("Reveal Images Folder", "images"),
+    ("Reveal Generated Folder", "generated"),
+];
+
 /// Menu item with label and optional hotkey hint
 #[derive(Clone, PartialEq)]
 pub struct MenuItem {
@@ -39,12 +48,27 @@ impl MenuItem {
 #[component]
 pub fn TitleBar(
     project_name: String,
+    project_path: Option<std::path::PathBuf>,
+    has_unsaved_changes: bool,
     on_new_project: EventHandler<MouseEvent>,
     on_save: EventHandler<MouseEvent>,
+    on_save_as: EventHandler<MouseEvent>,
+    on_export_bundle: EventHandler<MouseEvent>,
     on_project_settings: EventHandler<MouseEvent>,
+    on_export_settings: EventHandler<MouseEvent>,
+    on_export_video: EventHandler<MouseEvent>,
+    on_export_audio: EventHandler<MouseEvent>,
+    on_measure_loudness: EventHandler<MouseEvent>,
+    on_remove_unused_assets: EventHandler<MouseEvent>,
+    can_undo: bool,
+    can_redo: bool,
+    on_undo: EventHandler<MouseEvent>,
+    on_redo: EventHandler<MouseEvent>,
+    on_reveal_folder: EventHandler<String>,
     on_open_providers: EventHandler<MouseEvent>,
     show_preview_stats: bool,
     on_toggle_preview_stats: EventHandler<MouseEvent>,
+    on_clear_preview_cache: EventHandler<MouseEvent>,
     use_hw_decode: bool,
     on_toggle_hw_decode: EventHandler<MouseEvent>,
     queue_count: usize,
@@ -62,6 +86,58 @@ pub fn TitleBar(
     } else {
         MenuItem::new("Project Settings...").disabled()
     };
+    let export_settings_item = if project_loaded {
+        MenuItem::new("Export Settings...")
+    } else {
+        MenuItem::new("Export Settings...").disabled()
+    };
+    let export_video_item = if project_loaded {
+        MenuItem::new("Export Video...")
+    } else {
+        MenuItem::new("Export Video...").disabled()
+    };
+    let export_audio_item = if project_loaded {
+        MenuItem::new("Export Audio (WAV)...")
+    } else {
+        MenuItem::new("Export Audio (WAV)...").disabled()
+    };
+    let save_as_item = if project_loaded {
+        MenuItem::new("Save As...").with_hotkey("Ctrl+Shift+S")
+    } else {
+        MenuItem::new("Save As...").with_hotkey("Ctrl+Shift+S").disabled()
+    };
+    let export_bundle_item = if project_loaded {
+        MenuItem::new("Export Bundle...")
+    } else {
+        MenuItem::new("Export Bundle...").disabled()
+    };
+    let measure_loudness_item = if project_loaded {
+        MenuItem::new("Measure Mix Loudness")
+    } else {
+        MenuItem::new("Measure Mix Loudness").disabled()
+    };
+
+    let remove_unused_item = if project_loaded {
+        MenuItem::new("Remove Unused Assets...")
+    } else {
+        MenuItem::new("Remove Unused Assets...").disabled()
+    };
+
+    let undo_item = if can_undo {
+        MenuItem::new("Undo").with_hotkey("Ctrl+Z")
+    } else {
+        MenuItem::new("Undo").with_hotkey("Ctrl+Z").disabled()
+    };
+    let redo_item = if can_redo {
+        MenuItem::new("Redo").with_hotkey("Ctrl+Y")
+    } else {
+        MenuItem::new("Redo").with_hotkey("Ctrl+Y").disabled()
+    };
+
+    let mut path_copied = use_signal(|| false);
+    let project_path_label = project_path
+        .as_ref()
+        .map(|path| path.to_string_lossy().to_string());
 
     // Close menu on any click outside
     let close_menus = move |_: MouseEvent| {
@@ -117,6 +193,55 @@ pub fn TitleBar(
                                 on_project_settings.call(e);
                             },
                         }
+                        MenuItemButton {
+                            item: export_settings_item.clone(),
+                            on_click: move |e| {
+                                active_menu.set(None); on_menu_open.call(false);
+                                on_export_settings.call(e);
+                            },
+                        }
+                        MenuItemButton {
+                            item: export_video_item.clone(),
+                            on_click: move |e| {
+                                active_menu.set(None); on_menu_open.call(false);
+                                on_export_video.call(e);
+                            },
+                        }
+                        MenuItemButton {
+                            item: export_audio_item.clone(),
+                            on_click: move |e| {
+                                active_menu.set(None); on_menu_open.call(false);
+                                on_export_audio.call(e);
+                            },
+                        }
+                        MenuItemButton {
+                            item: measure_loudness_item.clone(),
+                            on_click: move |e| {
+                                active_menu.set(None); on_menu_open.call(false);
+                                on_measure_loudness.call(e);
+                            },
+                        }
+                        MenuItemButton {
+                            item: remove_unused_item.clone(),
+                            on_click: move |e| {
+                                active_menu.set(None); on_menu_open.call(false);
+                                on_remove_unused_assets.call(e);
+                            },
+                        }
+                        MenuDivider {}
+                        for (label, folder) in REVEAL_FOLDERS {
+                            MenuItemButton {
+                                item: if project_loaded {
+                                    MenuItem::new(label)
+                                } else {
+                                    MenuItem::new(label).disabled()
+                                },
+                                on_click: move |_| {
+                                    active_menu.set(None); on_menu_open.call(false);
+                                    on_reveal_folder.call(folder.to_string());
+                                },
+                            }
+                        }
                         MenuDivider {}
                         MenuItemButton {
                             item: MenuItem::new("Save").with_hotkey("Ctrl+S"),
@@ -126,8 +251,18 @@ pub fn TitleBar(
                             },
                         }
                         MenuItemButton {
-                            item: MenuItem::new("Save As...").with_hotkey("Ctrl+Shift+S").disabled(),
-                            on_click: move |_| {},
+                            item: save_as_item,
+                            on_click: move |e| {
+                                active_menu.set(None); on_menu_open.call(false);
+                                on_save_as.call(e);
+                            },
+                        }
+                        MenuItemButton {
+                            item: export_bundle_item,
+                            on_click: move |e| {
+                                active_menu.set(None); on_menu_open.call(false);
+                                on_export_bundle.call(e);
+                            },
                         }
                         MenuDivider {}
                         MenuItemButton {
@@ -155,12 +290,18 @@ pub fn TitleBar(
                     },
                     MenuDropdown {
                         MenuItemButton {
-                            item: MenuItem::new("Undo").with_hotkey("Ctrl+Z").disabled(),
-                            on_click: move |_| {},
+                            item: undo_item,
+                            on_click: move |e| {
+                                active_menu.set(None); on_menu_open.call(false);
+                                on_undo.call(e);
+                            },
                         }
                         MenuItemButton {
-                            item: MenuItem::new("Redo").with_hotkey("Ctrl+Y").disabled(),
-                            on_click: move |_| {},
+                            item: redo_item,
+                            on_click: move |e| {
+                                active_menu.set(None); on_menu_open.call(false);
+                                on_redo.call(e);
+                            },
                         }
                         MenuDivider {}
                         MenuItemButton {
@@ -211,6 +352,13 @@ pub fn TitleBar(
                                 on_toggle_preview_stats.call(e);
                             },
                         }
+                        MenuItemButton {
+                            item: MenuItem::new("Clear Preview Cache"),
+                            on_click: move |e| {
+                                active_menu.set(None); on_menu_open.call(false);
+                                on_clear_preview_cache.call(e);
+                            },
+                        }
                         MenuDivider {}
                         MenuItemButton {
                             item: MenuItem::new("Zoom In").with_hotkey("Num +").disabled(),
@@ -301,13 +449,50 @@ pub fn TitleBar(
                 }
             }
 
-            // Center: Project name
-            span { 
+            // Center: Project name and path (click the path to copy it)
+            div {
                 style: "
-                    font-size: 12px; color: {TEXT_MUTED};
+                    display: flex; align-items: baseline; gap: 8px; max-width: 50%;
                     position: absolute; left: 50%; transform: translateX(-50%);
-                ", 
-                "{project_name}" 
+                ",
+                span {
+                    style: "font-size: 12px; color: {TEXT_MUTED}; white-space: nowrap;",
+                    "{project_name}"
+                    if has_unsaved_changes {
+                        span {
+                            style: "margin-left: 4px; color: {TEXT_PRIMARY};",
+                            title: "Unsaved changes",
+                            "●"
+                        }
+                    }
+                }
+                if let Some(path_label) = project_path_label.clone() {
+                    span {
+                        style: "
+                            font-size: 11px; color: {TEXT_DIM}; cursor: pointer;
+                            overflow: hidden; text-overflow: ellipsis; white-space: nowrap;
+                        ",
+                        title: "Click to copy project path",
+                        onclick: move |_| {
+                            let literal = serde_json::to_string(&path_label)
+                                .unwrap_or_else(|_| "''".to_string());
+                            let _ = document::eval(&format!(
+                                "navigator.clipboard.writeText({});",
+                                literal
+                            ));
+                            path_copied.set(true);
+                            spawn(async move {
+                                tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
+                                path_copied.set(false);
+                            });
+                        },
+                        if path_copied() {
+                            "Copied"
+                        } else {
+                            "{path_label}"
+                        }
+                    }
+                }
             }
 
             // Right side: Quick toggles (compact)
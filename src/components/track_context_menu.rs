@@ -1,7 +1,10 @@
 use dioxus::prelude::*;
 
 use crate::constants::*;
-use crate::state::{Project, SelectionState, TrackType};
+use crate::state::{History, Project, SelectionState, TrackType};
+
+/// Length of a placeholder added from the menu, in seconds.
+const PLACEHOLDER_SECONDS: f64 = 5.0;
 
 #[component]
 pub fn TrackContextMenu(
@@ -9,6 +12,8 @@ pub fn TrackContextMenu(
     project: Signal<Project>,
     selection: Signal<SelectionState>,
     preview_dirty: Signal<bool>,
+    current_time: Signal<f64>,
+    history: Signal<History>,
 ) -> Element {
     rsx! {
         if let Some((x, y, track_id)) = context_menu() {
@@ -66,6 +71,29 @@ pub fn TrackContextMenu(
                             "🗑 Delete \"{track_name}\""
                         }
 
+                        div {
+                            style: "
+                                padding: 6px 12px; color: {TEXT_PRIMARY}; cursor: pointer;
+                                transition: background-color 0.1s ease;
+                            ",
+                            onmouseenter: move |_| {},
+                            onclick: move |_| {
+                                let time = current_time();
+                                history.write().push(&project.read());
+                                {
+                                    let mut project = project.write();
+                                    if let Some(clip_id) =
+                                        project.add_placeholder(track_id, time, PLACEHOLDER_SECONDS, None)
+                                    {
+                                        project.arrange_tracks(&[track_id], &[clip_id]);
+                                    }
+                                }
+                                preview_dirty.set(true);
+                                context_menu.set(None);
+                            },
+                            "▭ Add Placeholder at Playhead"
+                        }
+
                         div {
                             style: "height: 1px; background-color: {BORDER_SUBTLE}; margin: 2px 0;",
                         }
@@ -21,6 +21,8 @@ pub const ACCENT_PRIMARY: &str = "#3b82f6";
 pub const ACCENT_AUDIO: &str = "#3b82f6";
 pub const ACCENT_MARKER: &str = "#f97316";
 pub const ACCENT_VIDEO: &str = "#22c55e";
+/// Swatches offered when recoloring a marker (the first is the default marker color).
+pub const MARKER_COLORS: [&str; 6] = ["#f97316", "#ef4444", "#eab308", "#22c55e", "#3b82f6", "#a855f7"];
 
 pub const PANEL_MIN_WIDTH: f64 = 180.0;
 pub const PANEL_MAX_WIDTH: f64 = 400.0;
@@ -31,9 +33,10 @@ pub const TIMELINE_MAX_HEIGHT: f64 = 500.0;
 pub const TIMELINE_DEFAULT_HEIGHT: f64 = 220.0;
 pub const TIMELINE_COLLAPSED_HEIGHT: f64 = 32.0;
 pub const DEFAULT_CLIP_DURATION_SECONDS: f64 = 2.0;
+/// How long a transient status bar message stays up.
+pub const STATUS_MESSAGE_SECONDS: u64 = 5;
 pub const PREVIEW_FPS: u64 = 24;
 pub const PREVIEW_FRAME_INTERVAL_MS: u64 = 1000 / PREVIEW_FPS;
-pub const PREVIEW_CACHE_BUDGET_BYTES: usize = 8usize * 1024 * 1024 * 1024;
 pub const PREVIEW_PREFETCH_SCRUB_SECONDS: f64 = 0.5;
 pub const PREVIEW_PREFETCH_PLAYBACK_SECONDS: f64 = 3.0;
 pub const PREVIEW_IDLE_PREFETCH_DELAY_MS: u64 = 800;
@@ -43,6 +46,11 @@ pub const SHOW_CACHE_TICKS: bool = false;
 pub const TIMELINE_MIN_ZOOM_FLOOR: f64 = 0.1;
 pub const TIMELINE_MAX_PX_PER_FRAME: f64 = 8.0;
 pub const TIMELINE_SNAP_THRESHOLD_PX: f64 = 6.0;
+pub const TIMELINE_FOLLOW_MARGIN_PX: f64 = 48.0;
+/// Zoom change per wheel delta unit for Ctrl+wheel (100 units is about x1.22).
+pub const TIMELINE_WHEEL_ZOOM_RATE: f64 = 0.002;
+/// Empty space kept on each side when zooming to the selected clips.
+pub const TIMELINE_ZOOM_SELECTION_PADDING_PX: f64 = 24.0;
 
 pub const PREVIEW_CANVAS_SCRIPT: &str = r#"
 let canvas = null;
@@ -226,6 +234,23 @@ function attach() {
         lastUserScrollMs = performance.now();
         sendWidth();
     }, { passive: true });
+    host.addEventListener("wheel", (event) => {
+        if (!(event.ctrlKey || event.metaKey)) {
+            return;
+        }
+        event.preventDefault();
+        const rect = host.getBoundingClientRect();
+        // Line/page deltas (mode 1/2) are scaled to roughly pixel units
+        const scale = event.deltaMode === 1 ? 33 : event.deltaMode === 2 ? 400 : 1;
+        dioxus.send({
+            width: host.clientWidth || 0,
+            scroll_left: host.scrollLeft || 0,
+            wheel_zoom: {
+                delta_y: event.deltaY * scale,
+                anchor_x: event.clientX - rect.left,
+            },
+        });
+    }, { passive: false });
     window.addEventListener("resize", sendWidth, { passive: true });
     const initialValue = readScrollLeftAttr(host);
     if (initialValue !== null) {
@@ -245,3 +270,55 @@ while (true) {
     }
 }
 "#;
+
+pub const INPUT_FOCUS_SCRIPT: &str = r#"
+const nonTextInputs = ["checkbox", "radio", "button", "submit", "reset", "range", "color", "file"];
+let last = null;
+
+function isEditable(el) {
+    if (!el) {
+        return false;
+    }
+    if (el.tagName === "TEXTAREA" || el.tagName === "SELECT") {
+        return true;
+    }
+    if (el.tagName === "INPUT") {
+        return !nonTextInputs.includes((el.type || "text").toLowerCase());
+    }
+    return !!el.isContentEditable;
+}
+
+function sendFocus() {
+    const next = isEditable(document.activeElement);
+    if (next === last) {
+        return;
+    }
+    last = next;
+    dioxus.send(next);
+}
+
+document.addEventListener("focusin", sendFocus);
+document.addEventListener("focusout", () => setTimeout(sendFocus, 0));
+sendFocus();
+await new Promise(() => {});
+"#;
+
+pub const FOCUS_GENERATIVE_CONTROLS_SCRIPT: &str = r#"
+let attempts = 0;
+
+function focusControls() {
+    const inputs = document.getElementById("generative-provider-inputs");
+    const target = (inputs && inputs.querySelector("textarea, input:not([type]), input[type='text']"))
+        || document.getElementById("generative-generate-button");
+    if (!target) {
+        if (attempts++ < 20) {
+            setTimeout(focusControls, 50);
+        }
+        return;
+    }
+    target.scrollIntoView({ block: "nearest" });
+    target.focus();
+}
+
+focusControls();
+"#;
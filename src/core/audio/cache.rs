@@ -2,9 +2,11 @@
 
 #![allow(dead_code)]
 
+use std::collections::HashMap;
 use std::fs::{self, File};
 use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use uuid::Uuid;
@@ -141,6 +143,59 @@ pub fn cache_matches_source(cache: &PeakCache, source_path: &Path) -> Result<boo
     Ok(cache.source_size == size && cache.source_mtime == mtime)
 }
 
+/// Loaded peak caches shared by every clip of an asset, so redrawing or
+/// reselecting a clip doesn't re-read the cache file. Entries are checked
+/// against the source file on each lookup and dropped once it changes.
+#[derive(Default)]
+pub struct PeakCacheRegistry {
+    entries: Mutex<HashMap<Uuid, Arc<PeakCache>>>,
+}
+
+impl PeakCacheRegistry {
+    /// The cache for `asset_id` if it is still current for `source_path`.
+    pub fn get_current(&self, asset_id: Uuid, source_path: &Path) -> Option<Arc<PeakCache>> {
+        let mut entries = self.entries.lock().ok()?;
+        let cache = entries.get(&asset_id)?.clone();
+        if cache_matches_source(&cache, source_path).unwrap_or(false) {
+            Some(cache)
+        } else {
+            entries.remove(&asset_id);
+            None
+        }
+    }
+
+    /// Store `cache` for `asset_id`, replacing any older entry.
+    pub fn insert(&self, asset_id: Uuid, cache: PeakCache) -> Arc<PeakCache> {
+        let cache = Arc::new(cache);
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.insert(asset_id, Arc::clone(&cache));
+        }
+        cache
+    }
+
+    pub fn invalidate(&self, asset_id: Uuid) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.remove(&asset_id);
+        }
+    }
+
+    pub fn clear(&self) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.clear();
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().map(|entries| entries.len()).unwrap_or(0)
+    }
+}
+
+/// Process-wide peak cache registry.
+pub fn shared_peak_caches() -> &'static PeakCacheRegistry {
+    static REGISTRY: OnceLock<PeakCacheRegistry> = OnceLock::new();
+    REGISTRY.get_or_init(PeakCacheRegistry::default)
+}
+
 pub fn source_identity(path: &Path) -> Result<(u64, u64), String> {
     let meta = fs::metadata(path).map_err(|err| err.to_string())?;
     let size = meta.len();
@@ -197,3 +252,51 @@ fn write_i16(file: &mut File, value: i16) -> Result<(), String> {
     file.write_all(&value.to_le_bytes())
         .map_err(|err| err.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache_for(source_path: &Path) -> PeakCache {
+        let (source_size, source_mtime) = source_identity(source_path).unwrap();
+        PeakCache {
+            sample_rate: 100,
+            channels: 2,
+            source_size,
+            source_mtime,
+            levels: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_registry_reuses_and_invalidates() {
+        let dir = std::env::temp_dir().join(format!("nla-peak-registry-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("voice.wav");
+        fs::write(&source, [0_u8; 64]).unwrap();
+
+        let registry = PeakCacheRegistry::default();
+        let asset_id = Uuid::new_v4();
+        let stored = registry.insert(asset_id, cache_for(&source));
+        assert_eq!(registry.len(), 1);
+
+        // Two clips of the asset get the same allocation
+        let first = registry.get_current(asset_id, &source).unwrap();
+        let second = registry.get_current(asset_id, &source).unwrap();
+        assert!(Arc::ptr_eq(&first, &stored));
+        assert!(Arc::ptr_eq(&first, &second));
+
+        // A source with a different size no longer matches and is evicted
+        fs::write(&source, [0_u8; 96]).unwrap();
+        assert!(registry.get_current(asset_id, &source).is_none());
+        assert_eq!(registry.len(), 0);
+
+        // So does one with the same size but a different modification time
+        let mut touched = cache_for(&source);
+        touched.source_mtime = touched.source_mtime.saturating_sub(1);
+        registry.insert(asset_id, touched);
+        assert!(registry.get_current(asset_id, &source).is_none());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -12,12 +12,15 @@ use ffmpeg::format;
 use ffmpeg::frame;
 use ffmpeg::media;
 
-use super::resample::{frame_to_f32_interleaved, AudioResampleConfig, AudioResampler};
+use super::resample::{
+    frame_to_f32_interleaved, AudioResampleConfig, AudioResampler, ResampleQuality,
+};
 
 #[derive(Clone, Copy, Debug)]
 pub struct AudioDecodeConfig {
     pub target_rate: u32,
     pub target_channels: u16,
+    pub quality: ResampleQuality,
 }
 
 impl Default for AudioDecodeConfig {
@@ -25,6 +28,7 @@ impl Default for AudioDecodeConfig {
         Self {
             target_rate: 48_000,
             target_channels: 2,
+            quality: ResampleQuality::default(),
         }
     }
 }
@@ -58,9 +62,24 @@ pub fn decode_audio_to_f32(
 pub fn decode_audio_chunks<F>(
     path: &Path,
     config: AudioDecodeConfig,
+    on_samples: F,
+) -> Result<AudioDecodeMeta, String>
+where
+    F: FnMut(&[f32]) -> bool,
+{
+    decode_audio_chunks_with_meta(path, config, |_| {}, on_samples)
+}
+
+/// Like `decode_audio_chunks`, but hands the stream metadata to `on_meta`
+/// before the first chunk is decoded (useful for progress reporting).
+pub fn decode_audio_chunks_with_meta<M, F>(
+    path: &Path,
+    config: AudioDecodeConfig,
+    on_meta: M,
     mut on_samples: F,
 ) -> Result<AudioDecodeMeta, String>
 where
+    M: FnOnce(&AudioDecodeMeta),
     F: FnMut(&[f32]) -> bool,
 {
     init_ffmpeg()?;
@@ -109,6 +128,7 @@ where
         AudioResampleConfig {
             target_rate: config.target_rate,
             target_channels: config.target_channels,
+            quality: config.quality,
         },
     )?;
 
@@ -120,6 +140,7 @@ where
         target_channels: resampler.target_channels(),
         duration_seconds,
     };
+    on_meta(&meta);
 
     let mut decoded = frame::Audio::empty();
     let mut total_samples = 0_usize;
@@ -131,23 +152,29 @@ where
         decoder
             .send_packet(&packet)
             .map_err(|err| err.to_string())?;
-        drain_decoder(
+        let keep_going = drain_decoder(
             &mut decoder,
             &mut resampler,
             &mut decoded,
             &mut on_samples,
             &mut total_samples,
         )?;
+        if !keep_going {
+            return Ok(meta);
+        }
     }
 
     decoder.send_eof().map_err(|err| err.to_string())?;
-    drain_decoder(
+    let keep_going = drain_decoder(
         &mut decoder,
         &mut resampler,
         &mut decoded,
         &mut on_samples,
         &mut total_samples,
     )?;
+    if !keep_going {
+        return Ok(meta);
+    }
     flush_resampler(&mut resampler, &mut on_samples, &mut total_samples)?;
 
     Ok(meta)
@@ -159,7 +186,7 @@ fn drain_decoder<F>(
     decoded: &mut frame::Audio,
     on_samples: &mut F,
     total_samples: &mut usize,
-) -> Result<(), String>
+) -> Result<bool, String>
 where
     F: FnMut(&[f32]) -> bool,
 {
@@ -171,10 +198,10 @@ where
         let buffer = frame_to_f32_interleaved(&resampled)?;
         *total_samples = total_samples.saturating_add(buffer.len());
         if !on_samples(&buffer) {
-            return Ok(());
+            return Ok(false);
         }
     }
-    Ok(())
+    Ok(true)
 }
 
 fn flush_resampler<F>(
@@ -0,0 +1,302 @@
+//! Integrated loudness (LUFS) per ITU-R BS.1770-4.
+//!
+//! Each channel is K-weighted, mean-squared over 400 ms blocks with 75%
+//! overlap, and the blocks are gated at -70 LUFS and then 10 LU below the
+//! ungated mean before averaging.
+
+use std::collections::VecDeque;
+
+/// Gating block length.
+const BLOCK_SECONDS: f64 = 0.4;
+/// Blocks start every 100 ms (75% overlap).
+const BLOCK_STEP_SECONDS: f64 = 0.1;
+const ABSOLUTE_GATE_LUFS: f64 = -70.0;
+const RELATIVE_GATE_LU: f64 = -10.0;
+/// Offset in the BS.1770 loudness formula that puts a 0 dBFS 1 kHz stereo tone at 0 LUFS.
+const LOUDNESS_OFFSET: f64 = -0.691;
+
+/// Streaming integrated loudness: feed interleaved frames with [`push`] in
+/// any block size, then read the result with [`integrated`]. Only the last
+/// 400 ms of weighted power and one value per 100 ms step are kept, so a
+/// long mix never has to be held in memory.
+///
+/// [`push`]: LoudnessMeter::push
+/// [`integrated`]: LoudnessMeter::integrated
+pub struct LoudnessMeter {
+    filters: Vec<KWeighting>,
+    weights: Vec<f64>,
+    block_frames: usize,
+    step_frames: usize,
+    /// Channel-weighted K-filtered power of the most recent frames, at most
+    /// one block long.
+    window: VecDeque<f64>,
+    window_sum: f64,
+    frames: usize,
+    /// Mean power of every completed gating block.
+    blocks: Vec<f64>,
+}
+
+impl LoudnessMeter {
+    pub fn new(channels: u16, sample_rate: u32) -> Self {
+        let channels = channels.max(1) as usize;
+        let block_frames = (BLOCK_SECONDS * sample_rate as f64).round() as usize;
+        Self {
+            filters: vec![KWeighting::new(sample_rate.max(1) as f64); channels],
+            weights: (0..channels)
+                .map(|channel| channel_weight(channel, channels))
+                .collect(),
+            block_frames,
+            step_frames: (BLOCK_STEP_SECONDS * sample_rate as f64).round() as usize,
+            window: VecDeque::with_capacity(block_frames + 1),
+            window_sum: 0.0,
+            frames: 0,
+            blocks: Vec::new(),
+        }
+    }
+
+    /// Add interleaved `samples`; a trailing partial frame is ignored.
+    pub fn push(&mut self, samples: &[f32]) {
+        if self.block_frames == 0 || self.step_frames == 0 {
+            return;
+        }
+        for frame in samples.chunks_exact(self.filters.len()) {
+            let mut power = 0.0_f64;
+            for (channel, sample) in frame.iter().enumerate() {
+                let weight = self.weights[channel];
+                if weight == 0.0 {
+                    continue;
+                }
+                let filtered = self.filters[channel].process(*sample as f64);
+                power += weight * filtered * filtered;
+            }
+            self.window.push_back(power);
+            self.window_sum += power;
+            if self.window.len() > self.block_frames {
+                self.window_sum -= self.window.pop_front().unwrap_or(0.0);
+            }
+            self.frames += 1;
+            if self.window.len() == self.block_frames
+                && (self.frames - self.block_frames) % self.step_frames == 0
+            {
+                self.blocks
+                    .push(self.window_sum.max(0.0) / self.block_frames as f64);
+            }
+        }
+    }
+
+    /// Integrated loudness of everything pushed so far. `f64::NEG_INFINITY`
+    /// for silence or less than one 400 ms block.
+    pub fn integrated(&self) -> f64 {
+        gated_loudness(&self.blocks)
+    }
+}
+
+/// Loudness for display, e.g. "-14.2 LUFS".
+pub fn format_lufs(lufs: f64) -> String {
+    if lufs.is_finite() {
+        format!("{:.1} LUFS", lufs)
+    } else {
+        "-inf LUFS".to_string()
+    }
+}
+
+/// Integrated loudness from per-block weighted mean squares.
+fn gated_loudness(blocks: &[f64]) -> f64 {
+    let above_absolute: Vec<f64> = blocks
+        .iter()
+        .copied()
+        .filter(|power| block_loudness(*power) > ABSOLUTE_GATE_LUFS)
+        .collect();
+    if above_absolute.is_empty() {
+        return f64::NEG_INFINITY;
+    }
+    let relative_gate = block_loudness(mean(&above_absolute)) + RELATIVE_GATE_LU;
+    let gated: Vec<f64> = above_absolute
+        .into_iter()
+        .filter(|power| block_loudness(*power) > relative_gate)
+        .collect();
+    if gated.is_empty() {
+        return f64::NEG_INFINITY;
+    }
+    block_loudness(mean(&gated))
+}
+
+fn block_loudness(power: f64) -> f64 {
+    if power <= 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    LOUDNESS_OFFSET + 10.0 * power.log10()
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+/// BS.1770 channel weights: 1.0 for front channels, 1.41 for the 5.1
+/// surrounds, and the LFE is left out.
+fn channel_weight(channel: usize, channels: usize) -> f64 {
+    if channels == 6 {
+        return match channel {
+            3 => 0.0,
+            4 | 5 => 1.41,
+            _ => 1.0,
+        };
+    }
+    1.0
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Biquad {
+    b0: f64,
+    b1: f64,
+    b2: f64,
+    a1: f64,
+    a2: f64,
+    z1: f64,
+    z2: f64,
+}
+
+impl Biquad {
+    fn process(&mut self, input: f64) -> f64 {
+        // Transposed direct form II
+        let output = self.b0 * input + self.z1;
+        self.z1 = self.b1 * input - self.a1 * output + self.z2;
+        self.z2 = self.b2 * input - self.a2 * output;
+        output
+    }
+}
+
+/// The two-stage K-weighting pre-filter: a high shelf modelling the head,
+/// then the RLB high-pass. Coefficients are derived for any sample rate so
+/// 48 kHz matches the values tabulated in BS.1770.
+#[derive(Clone, Copy, Debug)]
+struct KWeighting {
+    shelf: Biquad,
+    high_pass: Biquad,
+}
+
+impl KWeighting {
+    fn new(sample_rate: f64) -> Self {
+        let shelf = {
+            let f0 = 1681.974_450_955_533;
+            let gain_db = 3.999_843_853_973_347;
+            let q = 0.707_175_236_955_419_6;
+            let k = (std::f64::consts::PI * f0 / sample_rate).tan();
+            let vh = 10.0_f64.powf(gain_db / 20.0);
+            let vb = vh.powf(0.499_666_774_154_541_6);
+            let a0 = 1.0 + k / q + k * k;
+            Biquad {
+                b0: (vh + vb * k / q + k * k) / a0,
+                b1: 2.0 * (k * k - vh) / a0,
+                b2: (vh - vb * k / q + k * k) / a0,
+                a1: 2.0 * (k * k - 1.0) / a0,
+                a2: (1.0 - k / q + k * k) / a0,
+                z1: 0.0,
+                z2: 0.0,
+            }
+        };
+        let high_pass = {
+            let f0 = 38.135_470_876_024_44;
+            let q = 0.500_327_037_323_877_3;
+            let k = (std::f64::consts::PI * f0 / sample_rate).tan();
+            let a0 = 1.0 + k / q + k * k;
+            Biquad {
+                b0: 1.0,
+                b1: -2.0,
+                b2: 1.0,
+                a1: 2.0 * (k * k - 1.0) / a0,
+                a2: (1.0 - k / q + k * k) / a0,
+                z1: 0.0,
+                z2: 0.0,
+            }
+        };
+        Self { shelf, high_pass }
+    }
+
+    fn process(&mut self, input: f64) -> f64 {
+        self.high_pass.process(self.shelf.process(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measure_lufs(samples: &[f32], sample_rate: u32) -> f64 {
+        measure_lufs_interleaved(samples, 1, sample_rate)
+    }
+
+    fn measure_lufs_interleaved(samples: &[f32], channels: u16, sample_rate: u32) -> f64 {
+        let mut meter = LoudnessMeter::new(channels, sample_rate);
+        meter.push(samples);
+        meter.integrated()
+    }
+
+    /// Interleaved 1 kHz sine with `amplitude_dbfs` peak on every channel.
+    fn tone(amplitude_dbfs: f64, seconds: f64, channels: u16, sample_rate: u32) -> Vec<f32> {
+        let amplitude = 10.0_f64.powf(amplitude_dbfs / 20.0);
+        let frames = (seconds * sample_rate as f64) as usize;
+        let mut samples = Vec::with_capacity(frames * channels as usize);
+        for frame in 0..frames {
+            let t = frame as f64 / sample_rate as f64;
+            let value = (amplitude * (t * 1000.0 * std::f64::consts::TAU).sin()) as f32;
+            for _ in 0..channels {
+                samples.push(value);
+            }
+        }
+        samples
+    }
+
+    #[test]
+    fn test_stereo_reference_tone() {
+        // EBU Tech 3341 case 1: a -23 dBFS 1 kHz stereo tone reads -23 LUFS
+        let samples = tone(-23.0, 20.0, 2, 48_000);
+        let lufs = measure_lufs_interleaved(&samples, 2, 48_000);
+        assert!((lufs + 23.0).abs() < 0.1, "{}", lufs);
+
+        // ...and -33 dBFS reads -33 LUFS, at 44.1 kHz too
+        let samples = tone(-33.0, 20.0, 2, 44_100);
+        let lufs = measure_lufs_interleaved(&samples, 2, 44_100);
+        assert!((lufs + 33.0).abs() < 0.1, "{}", lufs);
+    }
+
+    #[test]
+    fn test_mono_tone_is_one_channel() {
+        // A single channel carries half the power of the same tone in stereo
+        let samples = tone(-20.0, 5.0, 1, 48_000);
+        let lufs = measure_lufs(&samples, 48_000);
+        assert!((lufs - (-20.0 - 3.01)).abs() < 0.1, "{}", lufs);
+    }
+
+    #[test]
+    fn test_gating_ignores_silence_and_quiet_passages() {
+        let mut samples = tone(-20.0, 10.0, 2, 48_000);
+        samples.extend(std::iter::repeat(0.0).take(48_000 * 2 * 10));
+        let lufs = measure_lufs_interleaved(&samples, 2, 48_000);
+        assert!((lufs + 20.0).abs() < 0.1, "{}", lufs);
+
+        // A passage 30 LU down falls under the relative gate
+        let mut samples = tone(-20.0, 10.0, 2, 48_000);
+        samples.extend(tone(-50.0, 10.0, 2, 48_000));
+        let lufs = measure_lufs_interleaved(&samples, 2, 48_000);
+        assert!((lufs + 20.0).abs() < 0.2, "{}", lufs);
+    }
+
+    #[test]
+    fn test_silence_and_short_input() {
+        assert_eq!(measure_lufs(&vec![0.0; 48_000], 48_000), f64::NEG_INFINITY);
+        assert_eq!(measure_lufs(&tone(-20.0, 0.2, 1, 48_000), 48_000), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_block_size_does_not_change_the_result() {
+        let mut samples = tone(-20.0, 6.0, 2, 48_000);
+        samples.extend(tone(-26.0, 6.0, 2, 48_000));
+        let whole = measure_lufs_interleaved(&samples, 2, 48_000);
+        let mut meter = LoudnessMeter::new(2, 48_000);
+        for chunk in samples.chunks(4096 * 2) {
+            meter.push(chunk);
+        }
+        assert!((meter.integrated() - whole).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,142 @@
+//! Output level metering for the playback VU meter.
+//!
+//! The audio callback only records the loudest sample per channel; the UI polls
+//! those peaks at a throttled rate and runs them through `step_meter` for the
+//! ballistics (smooth fall, peak hold, clip hold).
+
+/// How often the UI reads the engine's output peaks.
+pub const METER_POLL_INTERVAL_MS: u64 = 33;
+/// Bottom of the meter scale.
+pub const METER_FLOOR_DB: f32 = -60.0;
+/// How fast the bar falls once the signal drops.
+const METER_FALL_DB_PER_SECOND: f32 = 24.0;
+/// How long the peak marker stays put before it starts falling.
+pub const PEAK_HOLD_SECONDS: f32 = 1.0;
+/// Fall rate of the peak marker after its hold expires.
+const PEAK_FALL_DB_PER_SECOND: f32 = 12.0;
+/// How long the clip indicator stays red after a sample reaches full scale.
+pub const CLIP_HOLD_SECONDS: f32 = 1.5;
+
+/// Display state of one meter channel. Levels are linear (1.0 = 0 dBFS).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MeterChannel {
+    pub level: f32,
+    pub peak: f32,
+    /// Seconds left before the peak marker starts falling.
+    pub peak_hold: f32,
+    /// Seconds left on the clip indicator.
+    pub clip_hold: f32,
+}
+
+impl MeterChannel {
+    pub fn is_clipping(&self) -> bool {
+        self.clip_hold > 0.0
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.level <= 0.0 && self.peak <= 0.0 && self.clip_hold <= 0.0
+    }
+}
+
+/// Advance `state` by `dt_seconds` given the loudest sample seen since the last step.
+pub fn step_meter(state: MeterChannel, input_peak: f32, dt_seconds: f32) -> MeterChannel {
+    let input_peak = if input_peak.is_finite() { input_peak.abs() } else { 0.0 };
+    let dt_seconds = dt_seconds.max(0.0);
+    let floor = db_to_linear(METER_FLOOR_DB);
+
+    let fallen_level = state.level * db_to_linear(-METER_FALL_DB_PER_SECOND * dt_seconds);
+    let mut level = input_peak.max(fallen_level);
+    if level < floor {
+        level = 0.0;
+    }
+
+    let (mut peak, peak_hold) = if input_peak >= state.peak {
+        (input_peak, PEAK_HOLD_SECONDS)
+    } else if state.peak_hold > dt_seconds {
+        (state.peak, state.peak_hold - dt_seconds)
+    } else {
+        // Only the part of the step past the hold counts toward the fall
+        let falling = dt_seconds - state.peak_hold.max(0.0);
+        let fallen = state.peak * db_to_linear(-PEAK_FALL_DB_PER_SECOND * falling);
+        (fallen.max(level), 0.0)
+    };
+    if peak < floor {
+        peak = 0.0;
+    }
+
+    let clip_hold = if input_peak >= 1.0 {
+        CLIP_HOLD_SECONDS
+    } else {
+        (state.clip_hold - dt_seconds).max(0.0)
+    };
+
+    MeterChannel {
+        level,
+        peak,
+        peak_hold,
+        clip_hold,
+    }
+}
+
+/// Position of a linear level on the meter scale, 0.0 at the floor and 1.0 at 0 dBFS.
+pub fn meter_fraction(level: f32) -> f32 {
+    if level <= 0.0 || !level.is_finite() {
+        return 0.0;
+    }
+    let db = 20.0 * level.log10();
+    ((db - METER_FLOOR_DB) / -METER_FLOOR_DB).clamp(0.0, 1.0)
+}
+
+fn db_to_linear(db: f32) -> f32 {
+    10.0_f32.powf(db / 20.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_peak_hold_then_decay() {
+        let mut state = step_meter(MeterChannel::default(), 0.5, 0.033);
+        assert_eq!(state.peak, 0.5);
+        assert_eq!(state.peak_hold, PEAK_HOLD_SECONDS);
+
+        // Quieter input: the bar falls but the peak marker holds
+        state = step_meter(state, 0.1, 0.5);
+        assert!(state.level < 0.5);
+        assert_eq!(state.peak, 0.5);
+        assert!((state.peak_hold - 0.5).abs() < 1e-6);
+
+        // Half a second past the hold it has fallen 6 dB (12 dB/s)
+        state = step_meter(state, 0.0, 1.0);
+        assert_eq!(state.peak_hold, 0.0);
+        assert!((state.peak - 0.5 * db_to_linear(-6.0)).abs() < 1e-4, "{}", state.peak);
+
+        // A louder hit resets the hold
+        state = step_meter(state, 0.8, 0.033);
+        assert_eq!(state.peak, 0.8);
+        assert_eq!(state.peak_hold, PEAK_HOLD_SECONDS);
+    }
+
+    #[test]
+    fn test_clip_indicator_holds_then_clears() {
+        let mut state = step_meter(MeterChannel::default(), 1.2, 0.033);
+        assert!(state.is_clipping());
+        state = step_meter(state, 0.2, CLIP_HOLD_SECONDS - 0.1);
+        assert!(state.is_clipping());
+        state = step_meter(state, 0.2, 0.2);
+        assert!(!state.is_clipping());
+    }
+
+    #[test]
+    fn test_meter_falls_to_idle() {
+        let mut state = step_meter(MeterChannel::default(), 1.0, 0.033);
+        for _ in 0..200 {
+            state = step_meter(state, 0.0, 0.033);
+        }
+        assert!(state.is_idle());
+        assert_eq!(meter_fraction(0.0), 0.0);
+        assert_eq!(meter_fraction(1.0), 1.0);
+        assert!((meter_fraction(db_to_linear(-30.0)) - 0.5).abs() < 1e-4);
+    }
+}
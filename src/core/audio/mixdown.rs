@@ -0,0 +1,486 @@
+//! Timeline audio mixdown: turns clips into mixer items and renders them offline.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use uuid::Uuid;
+
+use super::decode::{decode_audio_to_f32, AudioDecodeConfig};
+use super::loudness::LoudnessMeter;
+use super::normalize::{clip_normalize_gain_from_disk, clip_normalize_gain_from_samples};
+use super::playback::{mix_items_into, PlaybackItem};
+use super::resample::ResampleQuality;
+use super::waveform::resolve_audio_or_video_source;
+use crate::state::{Clip, Project, TrackType};
+
+/// Frames mixed per block when rendering offline.
+const MIXDOWN_BLOCK_FRAMES: usize = 4096;
+/// Sample rate of standalone WAV mixdowns.
+pub const WAV_SAMPLE_RATE: u32 = 48_000;
+/// WAV mixdowns are always stereo.
+pub const WAV_CHANNELS: u16 = 2;
+
+/// Mixer items for every audible clip. Assets whose samples aren't in
+/// `sample_cache` are decoded when `allow_decode` is set and reported in the
+/// second list otherwise.
+pub fn build_playback_items(
+    project: &Project,
+    project_root: &Path,
+    sample_rate: u32,
+    channels: u16,
+    sample_cache: &Mutex<HashMap<Uuid, Arc<Vec<f32>>>>,
+    allow_decode: bool,
+) -> (Vec<PlaybackItem>, Vec<Uuid>) {
+    let mut track_types = HashMap::new();
+    let mut track_volumes = HashMap::new();
+    for track in project.tracks.iter() {
+        track_types.insert(track.id, track.track_type);
+        track_volumes.insert(track.id, track.volume);
+    }
+
+    let target_rate = sample_rate;
+    let mut items = Vec::new();
+    let mut missing = Vec::new();
+
+    for clip in project.clips.iter() {
+        let Some(track_type) = track_types.get(&clip.track_id) else {
+            continue;
+        };
+        if !project.track_is_active(clip.track_id) {
+            continue;
+        }
+        if *track_type != TrackType::Audio && *track_type != TrackType::Video {
+            continue;
+        }
+        let Some(asset) = project.find_asset(clip.asset_id) else {
+            continue;
+        };
+        if !asset.is_audio() && !asset.is_video() {
+            continue;
+        }
+        let Some(source_path) = resolve_audio_or_video_source(project_root, asset) else {
+            continue;
+        };
+
+        let cached = sample_cache
+            .lock()
+            .ok()
+            .and_then(|cache| cache.get(&asset.id).cloned());
+        let samples = if let Some(samples) = cached {
+            samples
+        } else if !allow_decode {
+            missing.push(asset.id);
+            continue;
+        } else {
+            // Only offline mixdowns decode here, so always take the best resampler
+            let decode_config = AudioDecodeConfig {
+                target_rate,
+                target_channels: channels,
+                quality: ResampleQuality::Sinc,
+            };
+            let decoded = match decode_audio_to_f32(&source_path, decode_config) {
+                Ok(decoded) => decoded,
+                Err(err) => {
+                    eprintln!(
+                        "[AUDIO ERROR] Playback decode failed asset_id={} err={}",
+                        asset.id, err
+                    );
+                    continue;
+                }
+            };
+            let samples = Arc::new(decoded.samples);
+            if let Ok(mut cache) = sample_cache.lock() {
+                cache.insert(asset.id, Arc::clone(&samples));
+            }
+            samples
+        };
+
+        let track_volume = track_volumes.get(&clip.track_id).copied().unwrap_or(1.0);
+        let normalize_gain = if clip.normalize {
+            clip_normalize_gain_from_disk(clip, project_root, asset.id, &source_path).unwrap_or_else(
+                || clip_normalize_gain_from_samples(clip, &samples, channels, target_rate),
+            )
+        } else {
+            1.0
+        };
+        if let Some(item) =
+            clip_playback_item(clip, track_volume * normalize_gain, samples, channels, target_rate)
+        {
+            items.push(item);
+        }
+    }
+
+    (items, missing)
+}
+
+/// Mixer item for `clip` playing decoded `samples` (interleaved at
+/// `sample_rate`), or `None` when nothing of it is audible. `track_gain` is the
+/// track volume times any normalize gain.
+pub fn clip_playback_item(
+    clip: &Clip,
+    track_gain: f32,
+    samples: Arc<Vec<f32>>,
+    channels: u16,
+    sample_rate: u32,
+) -> Option<PlaybackItem> {
+    let sample_rate = sample_rate as f64;
+    let total_frames = (samples.len() / channels.max(1) as usize) as u64;
+    let trim_frames = (clip.trim_in_seconds.max(0.0) * sample_rate).round() as u64;
+    if trim_frames >= total_frames {
+        return None;
+    }
+    let speed = clip.playback_speed();
+    let clip_frames = (clip.duration.max(0.0) * sample_rate).round() as u64;
+    let source_frames = total_frames.saturating_sub(trim_frames);
+    let loop_frames = clip
+        .loop_length()
+        .map(|cycle| ((cycle * sample_rate).round() as u64).min(source_frames))
+        .unwrap_or(0);
+    let frame_count = if loop_frames > 0 {
+        clip_frames
+    } else {
+        // Output frames the rest of the source lasts at the clip's speed
+        clip_frames.min((source_frames as f64 / speed).floor() as u64)
+    };
+    if frame_count == 0 {
+        return None;
+    }
+    let start_frame = (clip.start_time.max(0.0) * sample_rate).round() as u64;
+    let gain = (track_gain * clip.volume).max(0.0);
+    let (fade_in, fade_out) = clip.clamped_fades();
+
+    Some(PlaybackItem {
+        samples,
+        start_frame,
+        sample_offset_frames: trim_frames,
+        frame_count,
+        channels,
+        gain,
+        reversed: clip.reversed,
+        speed,
+        loop_frames,
+        fade_in_frames: (fade_in * sample_rate).round() as u64,
+        fade_out_frames: (fade_out * sample_rate).round() as u64,
+    })
+}
+
+/// Mix `items` one block at a time, handing each clamped interleaved block to
+/// `on_block` in order, so writers never hold the whole mix in memory.
+pub fn render_mixdown_blocks<E>(
+    items: &[PlaybackItem],
+    total_frames: u64,
+    channels: u16,
+    mut on_block: impl FnMut(&[f32]) -> Result<(), E>,
+) -> Result<(), E> {
+    let channels = channels.max(1);
+    let mut block = vec![0.0_f32; MIXDOWN_BLOCK_FRAMES * channels as usize];
+    let mut start_frame = 0_u64;
+    while start_frame < total_frames {
+        let frames = (total_frames - start_frame).min(MIXDOWN_BLOCK_FRAMES as u64) as usize;
+        let chunk = &mut block[..frames * channels as usize];
+        chunk.fill(0.0);
+        mix_items_into(items, start_frame, channels, chunk);
+        for sample in chunk.iter_mut() {
+            *sample = sample.clamp(-1.0, 1.0);
+        }
+        on_block(chunk)?;
+        start_frame += frames as u64;
+    }
+    Ok(())
+}
+
+/// Size of the `data` chunk for `sample_count` 16-bit samples. Plain WAV
+/// sizes are 32-bit, so longer mixes are refused rather than written with a
+/// wrapped header.
+fn wav_data_len(sample_count: usize) -> Result<u32, String> {
+    sample_count
+        .checked_mul(2)
+        .and_then(|len| u32::try_from(len).ok())
+        .filter(|len| len.checked_add(36).is_some())
+        .ok_or_else(|| "The mix is too long for a WAV file (4 GB limit).".to_string())
+}
+
+/// 16-bit PCM WAV file bytes for interleaved `samples`.
+pub fn encode_wav(samples: &[f32], sample_rate: u32, channels: u16) -> Result<Vec<u8>, String> {
+    let data_len = wav_data_len(samples.len())?;
+    let mut out = Vec::with_capacity(44 + data_len as usize);
+    out.extend_from_slice(&wav_header(data_len, sample_rate, channels));
+    out.extend(samples.iter().flat_map(|sample| wav_sample(*sample).to_le_bytes()));
+    Ok(out)
+}
+
+/// The 44-byte header of a 16-bit PCM WAV whose `data` chunk is `data_len` bytes.
+fn wav_header(data_len: u32, sample_rate: u32, channels: u16) -> Vec<u8> {
+    let channels = channels.max(1);
+    let bytes_per_sample = 2u16;
+    let block_align = channels * bytes_per_sample;
+    let byte_rate = sample_rate * block_align as u32;
+
+    let mut out = Vec::with_capacity(44);
+    out.extend_from_slice(b"RIFF");
+    out.extend_from_slice(&(36 + data_len).to_le_bytes());
+    out.extend_from_slice(b"WAVE");
+    out.extend_from_slice(b"fmt ");
+    out.extend_from_slice(&16u32.to_le_bytes());
+    out.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    out.extend_from_slice(&channels.to_le_bytes());
+    out.extend_from_slice(&sample_rate.to_le_bytes());
+    out.extend_from_slice(&byte_rate.to_le_bytes());
+    out.extend_from_slice(&block_align.to_le_bytes());
+    out.extend_from_slice(&(bytes_per_sample * 8).to_le_bytes());
+    out.extend_from_slice(b"data");
+    out.extend_from_slice(&data_len.to_le_bytes());
+    out
+}
+
+fn wav_sample(sample: f32) -> i16 {
+    (sample.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16
+}
+
+/// Mix the project down and write it to `path` as a stereo 48 kHz WAV. The
+/// mix is written block by block as it renders; a failed write removes the
+/// partial file.
+pub fn export_wav(project: &Project, project_root: &Path, path: &Path) -> Result<(), String> {
+    let cache = Mutex::new(HashMap::new());
+    let (items, _) =
+        build_playback_items(project, project_root, WAV_SAMPLE_RATE, WAV_CHANNELS, &cache, true);
+    let total_frames =
+        (project.settings.duration_seconds.max(0.0) * WAV_SAMPLE_RATE as f64).round() as u64;
+    let data_len = wav_data_len(total_frames as usize * WAV_CHANNELS as usize)?;
+
+    let result = (|| -> std::io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(&wav_header(data_len, WAV_SAMPLE_RATE, WAV_CHANNELS))?;
+        let mut bytes = Vec::with_capacity(MIXDOWN_BLOCK_FRAMES * WAV_CHANNELS as usize * 2);
+        render_mixdown_blocks(&items, total_frames, WAV_CHANNELS, |block| {
+            bytes.clear();
+            bytes.extend(block.iter().flat_map(|sample| wav_sample(*sample).to_le_bytes()));
+            writer.write_all(&bytes)
+        })?;
+        writer.flush()
+    })();
+    result.map_err(|err| {
+        let _ = std::fs::remove_file(path);
+        format!("Failed to write {}: {}", path.display(), err)
+    })
+}
+
+/// Integrated loudness (LUFS) of the whole timeline mixdown, measured block
+/// by block as it renders.
+pub fn measure_mix_lufs(project: &Project, project_root: &Path) -> f64 {
+    let cache = Mutex::new(HashMap::new());
+    let (items, _) =
+        build_playback_items(project, project_root, WAV_SAMPLE_RATE, WAV_CHANNELS, &cache, true);
+    let total_frames =
+        (project.settings.duration_seconds.max(0.0) * WAV_SAMPLE_RATE as f64).round() as u64;
+    let mut meter = LoudnessMeter::new(WAV_CHANNELS, WAV_SAMPLE_RATE);
+    let _ = render_mixdown_blocks(&items, total_frames, WAV_CHANNELS, |block| {
+        meter.push(block);
+        Ok::<(), ()>(())
+    });
+    meter.integrated()
+}
+
+/// Integrated loudness (LUFS) of one clip as it plays, with its gain, fades and
+/// normalize applied. Track mute/solo is ignored so any clip can be measured.
+pub fn measure_clip_lufs(project: &Project, project_root: &Path, clip_id: Uuid) -> Result<f64, String> {
+    let clip = project
+        .clips
+        .iter()
+        .find(|clip| clip.id == clip_id)
+        .cloned()
+        .ok_or_else(|| "Clip not found.".to_string())?;
+    let mut isolated = project.clone();
+    isolated.settings.duration_seconds = clip.duration;
+    isolated.clips = vec![Clip {
+        start_time: 0.0,
+        ..clip
+    }];
+    for track in isolated.tracks.iter_mut() {
+        track.muted = false;
+        track.soloed = false;
+    }
+    Ok(measure_mix_lufs(&isolated, project_root))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Mix `items` into one interleaved buffer covering `total_frames` frames.
+    fn render_mixdown(items: &[PlaybackItem], total_frames: u64, channels: u16) -> Vec<f32> {
+        let mut samples = Vec::with_capacity(total_frames as usize * channels.max(1) as usize);
+        let _ = render_mixdown_blocks(items, total_frames, channels, |block| {
+            samples.extend_from_slice(block);
+            Ok::<(), ()>(())
+        });
+        samples
+    }
+
+    fn item(start_frame: u64, frames: u64, value: f32) -> PlaybackItem {
+        PlaybackItem {
+            samples: Arc::new(vec![value; frames as usize * 2]),
+            start_frame,
+            sample_offset_frames: 0,
+            frame_count: frames,
+            channels: 2,
+            gain: 1.0,
+            reversed: false,
+            speed: 1.0,
+            loop_frames: 0,
+            fade_in_frames: 0,
+            fade_out_frames: 0,
+        }
+    }
+
+    #[test]
+    fn test_mixdown_spans_blocks_and_clamps() {
+        let total = MIXDOWN_BLOCK_FRAMES as u64 * 2 + 10;
+        let items = [
+            item(10, MIXDOWN_BLOCK_FRAMES as u64 * 2, 0.75),
+            item(MIXDOWN_BLOCK_FRAMES as u64, 5, 0.5),
+        ];
+        let mixed = render_mixdown(&items, total, 2);
+        assert_eq!(mixed.len(), total as usize * 2);
+        assert_eq!(mixed[0], 0.0);
+        assert_eq!(mixed[20], 0.75);
+        // Overlap of both items clips at full scale
+        assert_eq!(mixed[MIXDOWN_BLOCK_FRAMES * 2], 1.0);
+        assert_eq!(mixed[(total as usize - 1) * 2], 0.75);
+    }
+
+    fn clip(start_time: f64, duration: f64, volume: f32) -> Clip {
+        let mut clip = Clip::new(Uuid::new_v4(), Uuid::new_v4(), start_time, duration);
+        clip.volume = volume;
+        clip
+    }
+
+    /// One second of constant `value` in stereo at 100 Hz.
+    fn tone(value: f32) -> Arc<Vec<f32>> {
+        Arc::new(vec![value; 200])
+    }
+
+    #[test]
+    fn test_overlapping_clips_sum() {
+        let items = [
+            clip_playback_item(&clip(0.0, 1.0, 1.0), 1.0, tone(0.25), 2, 100).unwrap(),
+            clip_playback_item(&clip(0.5, 1.0, 1.0), 1.0, tone(0.5), 2, 100).unwrap(),
+        ];
+        let mixed = render_mixdown(&items, 200, 2);
+        assert_eq!(mixed[10 * 2], 0.25);
+        // 0.5s..1.0s has both clips
+        assert_eq!(mixed[60 * 2], 0.75);
+        assert_eq!(mixed[60 * 2 + 1], 0.75);
+        assert_eq!(mixed[120 * 2], 0.5);
+        assert_eq!(mixed[160 * 2], 0.0);
+    }
+
+    #[test]
+    fn test_clip_and_track_volume_scale_samples() {
+        let item = clip_playback_item(&clip(0.0, 1.0, 0.5), 0.8, tone(0.5), 2, 100).unwrap();
+        assert!((item.gain - 0.4).abs() < 1e-6);
+        let mixed = render_mixdown(&[item], 100, 2);
+        assert!(mixed.iter().all(|sample| (sample - 0.2).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_clip_speed_resamples_the_trimmed_source() {
+        // Mono ramp: frame n holds n / 100
+        let ramp = Arc::new((0..100).map(|frame| frame as f32 / 100.0).collect::<Vec<f32>>());
+        let mut fast = Clip::new(Uuid::new_v4(), Uuid::new_v4(), 0.0, 0.25);
+        fast.trim_in_seconds = 0.1;
+        fast.speed = 2.0;
+        let item = clip_playback_item(&fast, 1.0, Arc::clone(&ramp), 1, 100).unwrap();
+        assert_eq!(item.frame_count, 25);
+        let mixed = render_mixdown(&[item], 25, 1);
+        assert!((mixed[0] - 0.10).abs() < 1e-6);
+        assert!((mixed[5] - 0.20).abs() < 1e-6);
+        assert!((mixed[24] - 0.58).abs() < 1e-6);
+
+        // Half speed interpolates between source frames and lasts as long as the source allows
+        let mut slow = Clip::new(Uuid::new_v4(), Uuid::new_v4(), 0.0, 4.0);
+        slow.trim_in_seconds = 0.5;
+        slow.speed = 0.5;
+        let item = clip_playback_item(&slow, 1.0, Arc::clone(&ramp), 1, 100).unwrap();
+        assert_eq!(item.frame_count, 100);
+        let mixed = render_mixdown(&[item], 100, 1);
+        assert!((mixed[1] - 0.505).abs() < 1e-6);
+        assert!((mixed[10] - 0.55).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_reversed_clip_plays_trimmed_range_backward() {
+        let ramp = Arc::new((0..100).map(|frame| frame as f32 / 100.0).collect::<Vec<f32>>());
+        let mut clip = Clip::new(Uuid::new_v4(), Uuid::new_v4(), 0.0, 0.2);
+        clip.trim_in_seconds = 0.1;
+        clip.reversed = true;
+        let item = clip_playback_item(&clip, 1.0, Arc::clone(&ramp), 1, 100).unwrap();
+        let mixed = render_mixdown(&[item], 20, 1);
+        assert!((mixed[0] - 0.29).abs() < 1e-6);
+        assert!((mixed[19] - 0.10).abs() < 1e-6);
+
+        // At double speed it covers twice the source, still ending on the trim point
+        clip.speed = 2.0;
+        let item = clip_playback_item(&clip, 1.0, Arc::clone(&ramp), 1, 100).unwrap();
+        let mixed = render_mixdown(&[item], 20, 1);
+        assert!((mixed[0] - 0.49).abs() < 1e-6);
+        assert!((mixed[19] - 0.11).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_looping_clip_repeats_its_cycle() {
+        let ramp = Arc::new((0..100).map(|frame| frame as f32 / 100.0).collect::<Vec<f32>>());
+        let mut clip = Clip::new(Uuid::new_v4(), Uuid::new_v4(), 0.0, 0.25);
+        clip.trim_in_seconds = 0.2;
+        clip.loop_to_duration = true;
+        clip.loop_source_seconds = 0.1;
+        let item = clip_playback_item(&clip, 1.0, Arc::clone(&ramp), 1, 100).unwrap();
+        assert_eq!(item.frame_count, 25);
+        let mixed = render_mixdown(&[item], 25, 1);
+        assert!((mixed[0] - 0.20).abs() < 1e-6);
+        assert!((mixed[9] - 0.29).abs() < 1e-6);
+        assert!((mixed[10] - 0.20).abs() < 1e-6);
+        // The last cycle stops halfway through
+        assert!((mixed[24] - 0.24).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_mixdown_trims_clip_past_duration() {
+        // Project is 0.5s long; the clip runs to 1.0s
+        let item = clip_playback_item(&clip(0.25, 0.75, 1.0), 1.0, tone(0.5), 2, 100).unwrap();
+        let mixed = render_mixdown(&[item], 50, 2);
+        assert_eq!(mixed.len(), 100);
+        assert_eq!(mixed[49 * 2], 0.5);
+    }
+
+    #[test]
+    fn test_encode_wav_header_and_samples() {
+        let bytes = encode_wav(&[0.0, 1.0, -1.0, 0.5], 48_000, 2).unwrap();
+        assert_eq!(bytes.len(), 44 + 8);
+        assert_eq!(&bytes[0..4], b"RIFF");
+        assert_eq!(u32::from_le_bytes(bytes[4..8].try_into().unwrap()), 36 + 8);
+        assert_eq!(&bytes[8..16], b"WAVEfmt ");
+        assert_eq!(u16::from_le_bytes([bytes[22], bytes[23]]), 2);
+        assert_eq!(u32::from_le_bytes(bytes[24..28].try_into().unwrap()), 48_000);
+        assert_eq!(u32::from_le_bytes(bytes[28..32].try_into().unwrap()), 48_000 * 4);
+        assert_eq!(u16::from_le_bytes([bytes[34], bytes[35]]), 16);
+        assert_eq!(&bytes[36..40], b"data");
+        let sample = |index: usize| i16::from_le_bytes([bytes[44 + index * 2], bytes[45 + index * 2]]);
+        assert_eq!(sample(0), 0);
+        assert_eq!(sample(1), i16::MAX);
+        assert_eq!(sample(2), -i16::MAX);
+        assert_eq!(sample(3), 16384);
+    }
+
+    #[test]
+    fn test_wav_data_len_refuses_oversized_mixes() {
+        assert_eq!(wav_data_len(4), Ok(8));
+        let largest = ((u32::MAX - 36) / 2) as usize;
+        assert_eq!(wav_data_len(largest), Ok(largest as u32 * 2));
+        assert!(wav_data_len(largest + 1).is_err());
+        assert!(wav_data_len(usize::MAX).is_err());
+    }
+}
@@ -1,7 +1,11 @@
-//! Audio core modules (decode, playback, waveform, cache).
+//! Audio core modules (decode, mixdown, playback, waveform, cache).
 
 pub mod cache;
 pub mod decode;
+pub mod loudness;
+pub mod meter;
+pub mod mixdown;
+pub mod normalize;
 pub mod playback;
 pub mod resample;
 pub mod waveform;
@@ -0,0 +1,174 @@
+//! Per-clip peak normalization.
+//!
+//! The gain comes from the clip's loudest peak over its trimmed source range.
+//! Peak caches answer that without decoding; decoded samples are the fallback
+//! when no current cache exists yet.
+
+use std::path::Path;
+
+use uuid::Uuid;
+
+use super::cache::{
+    cache_matches_source, load_peak_cache, peak_cache_path, shared_peak_caches, PeakCache,
+};
+use crate::state::Clip;
+
+/// Level the loudest peak of a normalized clip is raised or lowered to.
+pub const NORMALIZE_TARGET_DBFS: f32 = -1.0;
+/// Cap on the boost so near-silent clips don't turn into amplified noise (+24 dB).
+pub const NORMALIZE_MAX_GAIN: f32 = 16.0;
+/// Peaks below this are treated as silence and left alone.
+const NORMALIZE_SILENCE_PEAK: f32 = 1.0e-4;
+
+/// Linear gain that brings `peak` (0.0..=1.0) to `NORMALIZE_TARGET_DBFS`.
+pub fn normalize_gain_for_peak(peak: f32) -> f32 {
+    if !peak.is_finite() || peak < NORMALIZE_SILENCE_PEAK {
+        return 1.0;
+    }
+    let target = 10.0_f32.powf(NORMALIZE_TARGET_DBFS / 20.0);
+    (target / peak).min(NORMALIZE_MAX_GAIN)
+}
+
+/// Loudest absolute peak between `start_seconds` and `end_seconds` of the
+/// source, read from the finest level of `cache`. `None` when the cache holds
+/// no peaks for that range.
+pub fn peak_in_range(cache: &PeakCache, start_seconds: f64, end_seconds: f64) -> Option<f32> {
+    let level = cache.levels.first()?;
+    if level.block_size == 0 || cache.sample_rate == 0 {
+        return None;
+    }
+    let sample_rate = cache.sample_rate as f64;
+    let start_frame = (start_seconds.max(0.0) * sample_rate).floor() as usize;
+    let end_frame = (end_seconds.max(0.0) * sample_rate).ceil() as usize;
+    let start_index = start_frame / level.block_size;
+    let end_index = end_frame.div_ceil(level.block_size).min(level.peaks.len());
+    if start_index >= end_index {
+        return None;
+    }
+    let max = level.peaks[start_index..end_index]
+        .iter()
+        .flat_map(|peak| [peak.min_l, peak.max_l, peak.min_r, peak.max_r])
+        .map(|value| value.unsigned_abs())
+        .max()
+        .unwrap_or(0);
+    Some((max as f32 / i16::MAX as f32).min(1.0))
+}
+
+/// Normalize gain for `clip` from its source's peak cache.
+pub fn clip_normalize_gain_from_cache(clip: &Clip, cache: &PeakCache) -> Option<f32> {
+    let start = clip.trim_in_seconds.max(0.0);
+    let peak = peak_in_range(cache, start, start + clip.source_duration())?;
+    Some(normalize_gain_for_peak(peak))
+}
+
+/// Normalize gain for `clip` from decoded interleaved `samples`.
+pub fn clip_normalize_gain_from_samples(
+    clip: &Clip,
+    samples: &[f32],
+    channels: u16,
+    sample_rate: u32,
+) -> f32 {
+    let channels = channels.max(1) as usize;
+    let sample_rate = sample_rate as f64;
+    let total_frames = samples.len() / channels;
+    let start_frame = ((clip.trim_in_seconds.max(0.0) * sample_rate).round() as usize).min(total_frames);
+    let end_frame = (start_frame + (clip.source_duration() * sample_rate).round() as usize).min(total_frames);
+    let peak = samples[start_frame * channels..end_frame * channels]
+        .iter()
+        .fold(0.0_f32, |peak, sample| peak.max(sample.abs()));
+    normalize_gain_for_peak(peak.min(1.0))
+}
+
+/// Normalize gain for `clip` from the peak cache of `asset_id` (shared in
+/// memory, else on disk), if it is current for `source_path`.
+pub fn clip_normalize_gain_from_disk(
+    clip: &Clip,
+    project_root: &Path,
+    asset_id: Uuid,
+    source_path: &Path,
+) -> Option<f32> {
+    if let Some(cache) = shared_peak_caches().get_current(asset_id, source_path) {
+        return clip_normalize_gain_from_cache(clip, &cache);
+    }
+    let cache_path = peak_cache_path(project_root, asset_id);
+    if !cache_path.exists() {
+        return None;
+    }
+    let cache = load_peak_cache(&cache_path).ok()?;
+    if !cache_matches_source(&cache, source_path).unwrap_or(false) {
+        return None;
+    }
+    clip_normalize_gain_from_cache(clip, &cache)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::audio::cache::{PeakLevel, PeakPair};
+
+    fn cache_with_peaks(block_size: usize, peaks: &[i16]) -> PeakCache {
+        PeakCache {
+            sample_rate: 100,
+            channels: 2,
+            source_size: 0,
+            source_mtime: 0,
+            levels: vec![PeakLevel {
+                block_size,
+                peaks: peaks
+                    .iter()
+                    .map(|&peak| PeakPair {
+                        min_l: -peak / 2,
+                        max_l: peak / 2,
+                        min_r: -peak,
+                        max_r: peak / 4,
+                    })
+                    .collect(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_normalize_gain_from_peak_cache() {
+        // Ten blocks of 10 frames at 100 Hz: one block per 0.1 s
+        let cache = cache_with_peaks(10, &[1000, 2000, 16384, 4000, 8192, 8192, 8192, 8192, 8192, 8192]);
+        let mut clip = Clip::new(Uuid::new_v4(), Uuid::new_v4(), 0.0, 1.0);
+
+        // The loudest peak (the right channel's minimum) sits at half scale
+        let gain = clip_normalize_gain_from_cache(&clip, &cache).unwrap();
+        let target = 10.0_f32.powf(NORMALIZE_TARGET_DBFS / 20.0);
+        assert!((gain - target / (16384.0 / 32767.0)).abs() < 1e-4, "{}", gain);
+
+        // Trimming past the loud block only considers the quarter-scale tail
+        clip.trim_in_seconds = 0.4;
+        clip.duration = 0.6;
+        let gain = clip_normalize_gain_from_cache(&clip, &cache).unwrap();
+        assert!((gain - target / (8192.0 / 32767.0)).abs() < 1e-3, "{}", gain);
+
+        // A range the cache doesn't cover yet has no answer
+        clip.trim_in_seconds = 2.0;
+        assert_eq!(clip_normalize_gain_from_cache(&clip, &cache), None);
+    }
+
+    #[test]
+    fn test_normalize_gain_limits() {
+        // Silence is left alone and quiet clips are capped
+        assert_eq!(normalize_gain_for_peak(0.0), 1.0);
+        assert_eq!(normalize_gain_for_peak(0.001), NORMALIZE_MAX_GAIN);
+        // Clipping sources are pulled down to the target
+        assert!(normalize_gain_for_peak(1.0) < 1.0);
+
+        let cache = cache_with_peaks(10, &[i16::MIN.saturating_add(1); 4]);
+        let clip = Clip::new(Uuid::new_v4(), Uuid::new_v4(), 0.0, 0.4);
+        let gain = clip_normalize_gain_from_cache(&clip, &cache).unwrap();
+        assert!((gain - normalize_gain_for_peak(1.0)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_normalize_gain_from_samples_matches_cache() {
+        let mut samples = vec![0.0_f32; 200];
+        samples[41] = -0.5;
+        let clip = Clip::new(Uuid::new_v4(), Uuid::new_v4(), 0.0, 1.0);
+        let gain = clip_normalize_gain_from_samples(&clip, &samples, 2, 100);
+        assert!((gain - normalize_gain_for_peak(0.5)).abs() < 1e-6);
+    }
+}
@@ -3,7 +3,7 @@
 #![allow(dead_code)]
 
 use std::sync::{
-    atomic::{AtomicBool, AtomicU64, Ordering},
+    atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering},
     Arc, Mutex,
 };
 
@@ -18,6 +18,18 @@ pub struct PlaybackItem {
     pub frame_count: u64,
     pub channels: u16,
     pub gain: f32,
+    /// Read the item's sample range back to front.
+    pub reversed: bool,
+    /// Source frames played per output frame. Rates other than 1.0 are
+    /// resampled, so the pitch moves with the speed.
+    pub speed: f64,
+    /// Source frames in one loop cycle, repeated to fill the item; 0 plays
+    /// the range once.
+    pub loop_frames: u64,
+    /// Linear gain ramp length at the start of the item, in frames.
+    pub fade_in_frames: u64,
+    /// Linear gain ramp length at the end of the item, in frames.
+    pub fade_out_frames: u64,
 }
 
 impl PlaybackItem {
@@ -28,6 +40,141 @@ impl PlaybackItem {
     pub fn end_frame(&self) -> u64 {
         self.start_frame + self.frames()
     }
+
+    /// Gain for the frame `local_frame` frames into the item, fades included.
+    fn gain_at(&self, local_frame: u64) -> f32 {
+        let mut fade = 1.0_f32;
+        if self.fade_in_frames > 0 {
+            fade = fade.min(local_frame as f32 / self.fade_in_frames as f32);
+        }
+        if self.fade_out_frames > 0 {
+            let remaining = self.frame_count.saturating_sub(local_frame);
+            fade = fade.min(remaining as f32 / self.fade_out_frames as f32);
+        }
+        self.gain * fade.clamp(0.0, 1.0)
+    }
+}
+
+/// Add the items overlapping `[start_frame, start_frame + out frames)` into `out`
+/// (interleaved, `channels` wide). Items with a different channel count are skipped.
+pub fn mix_items_into(items: &[PlaybackItem], start_frame: u64, channels: u16, out: &mut [f32]) {
+    let frames = out.len() / channels.max(1) as usize;
+    let end_frame = start_frame + frames as u64;
+    for item in items.iter() {
+        if item.channels != channels {
+            continue;
+        }
+        let item_start = item.start_frame;
+        let item_end = item.end_frame();
+        if item_end <= start_frame || item_start >= end_frame {
+            continue;
+        }
+
+        let overlap_start = start_frame.max(item_start);
+        let overlap_end = end_frame.min(item_end);
+        let overlap_frames = (overlap_end - overlap_start) as usize;
+        let buffer_offset =
+            (overlap_start - start_frame) as usize * channels as usize;
+        if (item.speed - 1.0).abs() > 1e-9 || item.loop_frames > 0 {
+            mix_resampled_item(
+                item,
+                overlap_start - item_start,
+                overlap_frames,
+                channels,
+                &mut out[buffer_offset..],
+            );
+            continue;
+        }
+        if item.reversed {
+            let last_frame = item.sample_offset_frames + item.frame_count - 1;
+            if (last_frame as usize + 1) * channels as usize > item.samples.len() {
+                continue;
+            }
+            let local_start = overlap_start - item_start;
+            for frame in 0..overlap_frames {
+                let local_frame = local_start + frame as u64;
+                let source_frame = last_frame - local_frame;
+                let source_offset = source_frame as usize * channels as usize;
+                let out_offset = buffer_offset + frame * channels as usize;
+                let gain = item.gain_at(local_frame);
+                for ch in 0..channels as usize {
+                    out[out_offset + ch] +=
+                        item.samples[source_offset + ch] * gain;
+                }
+            }
+            continue;
+        }
+
+        let item_offset_frames =
+            (overlap_start - item_start) + item.sample_offset_frames;
+        let item_offset = item_offset_frames as usize * channels as usize;
+
+        let slice_end = item_offset + overlap_frames * channels as usize;
+        if slice_end > item.samples.len() {
+            continue;
+        }
+
+        if item.fade_in_frames == 0 && item.fade_out_frames == 0 {
+            for i in 0..(overlap_frames * channels as usize) {
+                out[buffer_offset + i] +=
+                    item.samples[item_offset + i] * item.gain;
+            }
+            continue;
+        }
+        let local_start = overlap_start - item_start;
+        for frame in 0..overlap_frames {
+            let gain = item.gain_at(local_start + frame as u64);
+            let offset = frame * channels as usize;
+            for ch in 0..channels as usize {
+                out[buffer_offset + offset + ch] +=
+                    item.samples[item_offset + offset + ch] * gain;
+            }
+        }
+    }
+}
+
+/// Add `frames` frames of an item playing at a speed other than 1.0 or
+/// looping, starting `local_start` frames into it. Source positions between
+/// frames are linearly interpolated.
+fn mix_resampled_item(
+    item: &PlaybackItem,
+    local_start: u64,
+    frames: usize,
+    channels: u16,
+    out: &mut [f32],
+) {
+    let channels = channels as usize;
+    let available_frames = item.samples.len() / channels;
+    if available_frames == 0 {
+        return;
+    }
+    let first = item.sample_offset_frames as f64;
+    let span = if item.loop_frames > 0 {
+        item.loop_frames as f64
+    } else {
+        item.frame_count as f64 * item.speed
+    };
+    let last = first + (span - 1.0).max(0.0);
+    for frame in 0..frames {
+        let local_frame = local_start + frame as u64;
+        let mut offset = local_frame as f64 * item.speed;
+        if item.loop_frames > 0 {
+            offset = offset.rem_euclid(span);
+        }
+        let position = if item.reversed { last - offset } else { first + offset };
+        let index = position.max(0.0).floor() as usize;
+        if index >= available_frames {
+            continue;
+        }
+        let next = (index + 1).min(available_frames - 1);
+        let fraction = (position.max(0.0) - index as f64) as f32;
+        let gain = item.gain_at(local_frame);
+        for ch in 0..channels {
+            let current = item.samples[index * channels + ch];
+            let following = item.samples[next * channels + ch];
+            out[frame * channels + ch] += (current + (following - current) * fraction) * gain;
+        }
+    }
 }
 
 pub struct AudioPlaybackEngine {
@@ -37,6 +184,7 @@ pub struct AudioPlaybackEngine {
     playhead_frames: Arc<AtomicU64>,
     scrub_hold: Arc<AtomicBool>,
     scrub_preview_frames: Arc<AtomicU64>,
+    output_peaks: Arc<OutputPeaks>,
     sample_rate: u32,
     channels: u16,
     sample_format: SampleFormat,
@@ -57,6 +205,7 @@ impl AudioPlaybackEngine {
         let playhead_frames = Arc::new(AtomicU64::new(0));
         let scrub_hold = Arc::new(AtomicBool::new(false));
         let scrub_preview_frames = Arc::new(AtomicU64::new(0));
+        let output_peaks = Arc::new(OutputPeaks::default());
 
         let channels_for_cb = channels;
 
@@ -70,6 +219,7 @@ impl AudioPlaybackEngine {
                 Arc::clone(&playhead_frames),
                 Arc::clone(&scrub_hold),
                 Arc::clone(&scrub_preview_frames),
+                Arc::clone(&output_peaks),
                 channels_for_cb,
             )?,
             SampleFormat::I16 => build_output_stream::<i16>(
@@ -80,6 +230,7 @@ impl AudioPlaybackEngine {
                 Arc::clone(&playhead_frames),
                 Arc::clone(&scrub_hold),
                 Arc::clone(&scrub_preview_frames),
+                Arc::clone(&output_peaks),
                 channels_for_cb,
             )?,
             SampleFormat::U16 => build_output_stream::<u16>(
@@ -90,6 +241,7 @@ impl AudioPlaybackEngine {
                 Arc::clone(&playhead_frames),
                 Arc::clone(&scrub_hold),
                 Arc::clone(&scrub_preview_frames),
+                Arc::clone(&output_peaks),
                 channels_for_cb,
             )?,
             SampleFormat::I32 => build_output_stream::<i32>(
@@ -100,6 +252,7 @@ impl AudioPlaybackEngine {
                 Arc::clone(&playhead_frames),
                 Arc::clone(&scrub_hold),
                 Arc::clone(&scrub_preview_frames),
+                Arc::clone(&output_peaks),
                 channels_for_cb,
             )?,
             SampleFormat::U32 => build_output_stream::<u32>(
@@ -110,6 +263,7 @@ impl AudioPlaybackEngine {
                 Arc::clone(&playhead_frames),
                 Arc::clone(&scrub_hold),
                 Arc::clone(&scrub_preview_frames),
+                Arc::clone(&output_peaks),
                 channels_for_cb,
             )?,
             SampleFormat::F64 => build_output_stream::<f64>(
@@ -120,6 +274,7 @@ impl AudioPlaybackEngine {
                 Arc::clone(&playhead_frames),
                 Arc::clone(&scrub_hold),
                 Arc::clone(&scrub_preview_frames),
+                Arc::clone(&output_peaks),
                 channels_for_cb,
             )?,
             SampleFormat::I8 => build_output_stream::<i8>(
@@ -130,6 +285,7 @@ impl AudioPlaybackEngine {
                 Arc::clone(&playhead_frames),
                 Arc::clone(&scrub_hold),
                 Arc::clone(&scrub_preview_frames),
+                Arc::clone(&output_peaks),
                 channels_for_cb,
             )?,
             SampleFormat::U8 => build_output_stream::<u8>(
@@ -140,6 +296,7 @@ impl AudioPlaybackEngine {
                 Arc::clone(&playhead_frames),
                 Arc::clone(&scrub_hold),
                 Arc::clone(&scrub_preview_frames),
+                Arc::clone(&output_peaks),
                 channels_for_cb,
             )?,
             other => {
@@ -159,6 +316,7 @@ impl AudioPlaybackEngine {
             playhead_frames,
             scrub_hold,
             scrub_preview_frames,
+            output_peaks,
             sample_rate,
             channels,
             sample_format: output.sample_format,
@@ -214,6 +372,42 @@ impl AudioPlaybackEngine {
     pub fn is_playing(&self) -> bool {
         self.playing.load(Ordering::Relaxed)
     }
+
+    /// Loudest left/right output sample since the last call (before clamping,
+    /// so values above 1.0 mean the mix clipped).
+    pub fn take_output_peaks(&self) -> [f32; 2] {
+        self.output_peaks.take()
+    }
+}
+
+/// Per-channel output peaks written by the audio callback and drained by the
+/// UI meter. Stored as `f32` bits: for non-negative floats the bit patterns
+/// order the same way as the values, so `fetch_max` works directly.
+#[derive(Default)]
+struct OutputPeaks {
+    left: AtomicU32,
+    right: AtomicU32,
+}
+
+impl OutputPeaks {
+    fn record(&self, buffer: &[f32], channels: u16) {
+        let channels = channels.max(1) as usize;
+        let mut left = 0.0_f32;
+        let mut right = 0.0_f32;
+        for frame in buffer.chunks_exact(channels) {
+            left = left.max(frame[0].abs());
+            right = right.max(frame[channels.min(2) - 1].abs());
+        }
+        self.left.fetch_max(left.to_bits(), Ordering::Relaxed);
+        self.right.fetch_max(right.to_bits(), Ordering::Relaxed);
+    }
+
+    fn take(&self) -> [f32; 2] {
+        [
+            f32::from_bits(self.left.swap(0, Ordering::Relaxed)),
+            f32::from_bits(self.right.swap(0, Ordering::Relaxed)),
+        ]
+    }
 }
 
 struct OutputConfig {
@@ -268,6 +462,7 @@ fn build_output_stream<T>(
     playhead: Arc<AtomicU64>,
     scrub_hold: Arc<AtomicBool>,
     scrub_preview_frames: Arc<AtomicU64>,
+    output_peaks: Arc<OutputPeaks>,
     channels: u16,
 ) -> Result<cpal::Stream, String>
 where
@@ -297,35 +492,7 @@ where
                 let end_frame = start_frame + frames as u64;
 
                 if let Ok(items) = items.lock() {
-                    for item in items.iter() {
-                        if item.channels != channels {
-                            continue;
-                        }
-                        let item_start = item.start_frame;
-                        let item_end = item.end_frame();
-                        if item_end <= start_frame || item_start >= end_frame {
-                            continue;
-                        }
-
-                        let overlap_start = start_frame.max(item_start);
-                        let overlap_end = end_frame.min(item_end);
-                        let overlap_frames = (overlap_end - overlap_start) as usize;
-                        let buffer_offset =
-                            (overlap_start - start_frame) as usize * channels as usize;
-                        let item_offset_frames =
-                            (overlap_start - item_start) + item.sample_offset_frames;
-                        let item_offset = item_offset_frames as usize * channels as usize;
-
-                        let slice_end = item_offset + overlap_frames * channels as usize;
-                        if slice_end > item.samples.len() {
-                            continue;
-                        }
-
-                        for i in 0..(overlap_frames * channels as usize) {
-                            mix_buffer[buffer_offset + i] +=
-                                item.samples[item_offset + i] * item.gain;
-                        }
-                    }
+                    mix_items_into(&items, start_frame, channels, &mut mix_buffer);
                 }
 
                 if scrub_hold.load(Ordering::Relaxed) {
@@ -340,6 +507,7 @@ where
                     scrub_preview_frames.store(consumed, Ordering::Relaxed);
                 }
 
+                output_peaks.record(&mix_buffer, channels);
                 for (out, sample) in data.iter_mut().zip(mix_buffer.iter()) {
                     *out = T::from_sample(sample.clamp(-1.0, 1.0));
                 }
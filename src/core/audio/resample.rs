@@ -8,11 +8,15 @@ use ffmpeg::channel_layout::ChannelLayout;
 use ffmpeg::format::{sample, Sample};
 use ffmpeg::frame;
 use ffmpeg::software::resampling::context::Context as ResampleContext;
+use ffmpeg::Dictionary;
+
+pub use crate::state::ResampleQuality;
 
 #[derive(Clone, Copy, Debug)]
 pub struct AudioResampleConfig {
     pub target_rate: u32,
     pub target_channels: u16,
+    pub quality: ResampleQuality,
 }
 
 impl Default for AudioResampleConfig {
@@ -20,8 +24,29 @@ impl Default for AudioResampleConfig {
         Self {
             target_rate: 48_000,
             target_channels: 2,
+            quality: ResampleQuality::default(),
+        }
+    }
+}
+
+/// swresample options for `quality`. `Sinc` sets nothing, so it stays on
+/// FFmpeg's default windowed-sinc filter (32 taps).
+fn resample_options(quality: ResampleQuality) -> Dictionary<'static> {
+    let mut options = Dictionary::new();
+    match quality {
+        ResampleQuality::Linear => {
+            // Two taps with interpolation between a few filter phases
+            options.set("filter_size", "2");
+            options.set("phase_shift", "4");
+            options.set("linear_interp", "1");
+        }
+        ResampleQuality::Cubic => {
+            options.set("filter_type", "cubic");
+            options.set("filter_size", "4");
         }
+        ResampleQuality::Sinc => {}
     }
+    options
 }
 
 pub struct AudioResampler {
@@ -39,13 +64,14 @@ impl AudioResampler {
     ) -> Result<Self, String> {
         let target_layout = channel_layout_for_channels(config.target_channels);
         let target_format = Sample::F32(sample::Type::Packed);
-        let ctx = ResampleContext::get(
+        let ctx = ResampleContext::get_with(
             input_format,
             input_layout,
             input_rate,
             target_format,
             target_layout,
             config.target_rate,
+            resample_options(config.quality),
         )
         .map_err(|err| err.to_string())?;
 
@@ -150,3 +176,81 @@ pub fn frame_to_f32_interleaved(frame: &frame::Audio) -> Result<Vec<f32>, String
     let samples: &[f32] = bytemuck::cast_slice(data);
     Ok(samples[..expected_samples].to_vec())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// One second of a 1 kHz mono sine at `rate`, resampled to 48 kHz stereo
+    /// with `quality`. Returns the interleaved output.
+    fn resample_tone(rate: u32, quality: ResampleQuality) -> Vec<f32> {
+        let format = Sample::F32(sample::Type::Packed);
+        let mut input = frame::Audio::new(format, rate as usize, ChannelLayout::MONO);
+        input.set_rate(rate);
+        {
+            let data: &mut [f32] = bytemuck::cast_slice_mut(input.data_mut(0));
+            for (index, sample) in data.iter_mut().take(rate as usize).enumerate() {
+                let t = index as f32 / rate as f32;
+                *sample = (t * 1000.0 * std::f32::consts::TAU).sin() * 0.5;
+            }
+        }
+
+        let config = AudioResampleConfig {
+            target_rate: 48_000,
+            target_channels: 2,
+            quality,
+        };
+        let mut resampler = AudioResampler::new(format, rate, ChannelLayout::MONO, config).unwrap();
+        let mut output = frame_to_f32_interleaved(&resampler.resample(&input).unwrap()).unwrap();
+        while let Some(tail) = resampler.flush().unwrap() {
+            output.extend(frame_to_f32_interleaved(&tail).unwrap());
+        }
+        output
+    }
+
+    #[test]
+    fn test_resample_output_length_across_qualities() {
+        for quality in ResampleQuality::ALL {
+            let output = resample_tone(44_100, quality);
+            let frames = output.len() / 2;
+            assert!(
+                frames.abs_diff(48_000) <= 2,
+                "{:?}: expected ~48000 frames, got {}",
+                quality,
+                frames
+            );
+        }
+    }
+
+    #[test]
+    fn test_resample_preserves_frequency_across_qualities() {
+        for quality in ResampleQuality::ALL {
+            let output = resample_tone(44_100, quality);
+            let left: Vec<f32> = output.chunks_exact(2).map(|frame| frame[0]).collect();
+            // Skip the filter's ramp at both ends and count zero crossings:
+            // 1 kHz over 0.9 s crosses zero about 1800 times
+            let window = &left[2_400..45_600];
+            let crossings = window
+                .windows(2)
+                .filter(|pair| (pair[0] < 0.0) != (pair[1] < 0.0))
+                .count();
+            assert!(
+                crossings.abs_diff(1_800) <= 4,
+                "{:?}: {} zero crossings",
+                quality,
+                crossings
+            );
+            let peak = window.iter().fold(0.0_f32, |peak, sample| peak.max(sample.abs()));
+            assert!((peak - 0.5).abs() < 0.05, "{:?}: peak {}", quality, peak);
+        }
+    }
+
+    #[test]
+    fn test_default_quality_keeps_ffmpeg_defaults() {
+        assert_eq!(AudioResampleConfig::default().quality, ResampleQuality::Sinc);
+        assert_eq!(resample_options(ResampleQuality::Sinc).iter().count(), 0);
+        assert!(resample_options(ResampleQuality::Cubic)
+            .iter()
+            .any(|(key, value)| key == "filter_type" && value == "cubic"));
+    }
+}
@@ -2,18 +2,40 @@
 
 #![allow(dead_code)]
 
+use std::cell::Cell;
 use std::path::Path;
+use std::sync::{Arc, OnceLock};
 
+use tokio::sync::{mpsc, Semaphore};
 use tokio::task;
 use uuid::Uuid;
 
-use super::cache::{peak_cache_path, source_identity, write_peak_cache, PeakCache, PeakLevel, PeakPair};
-use super::decode::{decode_audio_chunks, AudioDecodeConfig};
+use super::cache::{
+    cache_matches_source, load_peak_cache, peak_cache_path, source_identity, write_peak_cache,
+    shared_peak_caches, PeakCache, PeakCacheRegistry, PeakLevel, PeakPair,
+};
+use super::decode::{decode_audio_chunks_with_meta, AudioDecodeConfig};
+use super::resample::ResampleQuality;
 use crate::state::{Asset, AssetKind};
 
 const PEAK_BASE_BLOCK: usize = 256;
 const PEAK_LEVEL_FACTOR: usize = 4;
 const PEAK_MAX_LEVELS: usize = 8;
+const PEAK_MIN_BASE_BLOCK: usize = 16;
+const PEAK_MAX_BASE_BLOCK: usize = 8192;
+/// Seconds of decoded audio between progress reports.
+const PEAK_PROGRESS_INTERVAL_SECONDS: f64 = 15.0;
+/// Hard ceiling on concurrent peak builds, regardless of core count.
+const PEAK_BUILD_MAX_CONCURRENT: usize = 4;
+
+/// Incremental update emitted while a peak cache is being built.
+#[derive(Clone, Debug)]
+pub struct PeakBuildProgress {
+    /// Completion in 0.0..=1.0, when the source duration is known.
+    pub fraction: Option<f32>,
+    /// Peaks computed so far; levels cover only the decoded prefix.
+    pub partial: PeakCache,
+}
 
 #[derive(Clone, Copy, Debug)]
 pub struct PeakBuildConfig {
@@ -22,6 +44,7 @@ pub struct PeakBuildConfig {
     pub max_levels: usize,
     pub target_rate: u32,
     pub target_channels: u16,
+    pub resample_quality: ResampleQuality,
 }
 
 impl Default for PeakBuildConfig {
@@ -32,26 +55,141 @@ impl Default for PeakBuildConfig {
             max_levels: PEAK_MAX_LEVELS,
             target_rate: 48_000,
             target_channels: 2,
+            resample_quality: ResampleQuality::default(),
+        }
+    }
+}
+
+impl PeakBuildConfig {
+    /// Config whose finest level holds roughly `peaks_per_second` peaks per second.
+    pub fn with_peaks_per_second(peaks_per_second: f64) -> Self {
+        let mut config = Self::default();
+        if peaks_per_second.is_finite() && peaks_per_second > 0.0 {
+            let block = (config.target_rate as f64 / peaks_per_second).round() as usize;
+            config.base_block = block.clamp(PEAK_MIN_BASE_BLOCK, PEAK_MAX_BASE_BLOCK);
+        }
+        config
+    }
+
+    pub fn with_resample_quality(mut self, quality: ResampleQuality) -> Self {
+        self.resample_quality = quality;
+        self
+    }
+
+    /// Whether `cache` was built at this config's resolution.
+    pub fn matches_cache(&self, cache: &PeakCache) -> bool {
+        cache.sample_rate == self.target_rate
+            && cache.levels.first().map(|level| level.block_size) == Some(self.base_block)
+    }
+}
+
+/// Load the peak cache for `source_path` if it is current for both the source and `config`.
+pub fn load_current_peak_cache(
+    cache_path: &Path,
+    source_path: &Path,
+    config: &PeakBuildConfig,
+) -> Result<Option<PeakCache>, String> {
+    if !cache_path.exists() {
+        return Ok(None);
+    }
+    let cache = load_peak_cache(cache_path)?;
+    if cache_matches_source(&cache, source_path)? && config.matches_cache(&cache) {
+        Ok(Some(cache))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Like [`load_current_peak_cache`], but served from `registry` when another
+/// clip of the asset already loaded it. Disk loads are added to the registry.
+pub fn load_shared_peak_cache(
+    registry: &PeakCacheRegistry,
+    asset_id: Uuid,
+    cache_path: &Path,
+    source_path: &Path,
+    config: &PeakBuildConfig,
+) -> Result<Option<Arc<PeakCache>>, String> {
+    if let Some(cache) = registry.get_current(asset_id, source_path) {
+        if config.matches_cache(&cache) {
+            return Ok(Some(cache));
         }
+        registry.invalidate(asset_id);
     }
+    Ok(load_current_peak_cache(cache_path, source_path, config)?
+        .map(|cache| registry.insert(asset_id, cache)))
 }
 
 pub fn build_peak_cache(source_path: &Path, config: PeakBuildConfig) -> Result<PeakCache, String> {
+    build_peak_cache_with_progress(source_path, config, |_| true)
+}
+
+/// Build a peak cache, reporting partial results every few seconds of decoded audio.
+///
+/// Returning `false` from `on_progress` aborts the build with an error.
+pub fn build_peak_cache_with_progress<F>(
+    source_path: &Path,
+    config: PeakBuildConfig,
+    mut on_progress: F,
+) -> Result<PeakCache, String>
+where
+    F: FnMut(PeakBuildProgress) -> bool,
+{
     let (source_size, source_mtime) = source_identity(source_path)?;
     let mut accumulator = PeakAccumulator::new(config.base_block);
+    let expected_frames = Cell::new(None::<f64>);
+    let report_interval_frames =
+        ((PEAK_PROGRESS_INTERVAL_SECONDS * config.target_rate as f64) as usize).max(1);
+    let mut next_report_frame = report_interval_frames;
+    let mut cancelled = false;
 
-    decode_audio_chunks(
+    decode_audio_chunks_with_meta(
         source_path,
         AudioDecodeConfig {
             target_rate: config.target_rate,
             target_channels: config.target_channels,
+            quality: config.resample_quality,
+        },
+        |meta| {
+            expected_frames.set(
+                meta.duration_seconds
+                    .filter(|duration| *duration > 0.0)
+                    .map(|duration| duration * meta.target_rate as f64),
+            );
         },
         |chunk| {
             accumulator.push_interleaved(chunk);
+            let frames = accumulator.frames_seen();
+            if frames < next_report_frame {
+                return true;
+            }
+            next_report_frame = frames + report_interval_frames;
+            let fraction = expected_frames
+                .get()
+                .map(|expected| (frames as f64 / expected).clamp(0.0, 1.0) as f32);
+            let partial = PeakCache {
+                sample_rate: config.target_rate,
+                channels: config.target_channels,
+                source_size,
+                source_mtime,
+                levels: build_levels(
+                    accumulator.snapshot(),
+                    config.base_block,
+                    config.level_factor,
+                    config.max_levels,
+                ),
+            };
+            if !on_progress(PeakBuildProgress { fraction, partial }) {
+                cancelled = true;
+                return false;
+            }
             true
         },
     )?;
 
+    if cancelled {
+        return Err("Peak cache build cancelled.".to_string());
+    }
+
     let base_peaks = accumulator.finish();
     let levels = build_levels(base_peaks, config.base_block, config.level_factor, config.max_levels);
 
@@ -76,13 +214,56 @@ pub fn build_and_store_peak_cache(
     Ok(cache_path)
 }
 
+/// Build and store a peak cache on the blocking pool, limited by the shared build gate.
+///
+/// When `progress_tx` is set, partial caches are streamed to it while decoding;
+/// dropping the receiver cancels the build.
+pub async fn run_peak_cache_build(
+    project_root: std::path::PathBuf,
+    asset_id: Uuid,
+    source_path: std::path::PathBuf,
+    config: PeakBuildConfig,
+    progress_tx: Option<mpsc::UnboundedSender<PeakBuildProgress>>,
+) -> Result<std::path::PathBuf, String> {
+    let _permit = peak_build_gate()
+        .acquire()
+        .await
+        .map_err(|err| err.to_string())?;
+    task::spawn_blocking(move || {
+        let cache = build_peak_cache_with_progress(&source_path, config, |progress| {
+            match progress_tx.as_ref() {
+                Some(tx) => tx.send(progress).is_ok(),
+                None => true,
+            }
+        })?;
+        let cache_path = peak_cache_path(&project_root, asset_id);
+        write_peak_cache(&cache_path, &cache)?;
+        // Every clip of the asset picks the fresh peaks up without a disk read
+        shared_peak_caches().insert(asset_id, cache);
+        Ok(cache_path)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
 pub fn spawn_peak_cache_build(
     project_root: std::path::PathBuf,
     asset_id: Uuid,
     source_path: std::path::PathBuf,
     config: PeakBuildConfig,
 ) -> task::JoinHandle<Result<std::path::PathBuf, String>> {
-    task::spawn_blocking(move || build_and_store_peak_cache(&project_root, asset_id, &source_path, config))
+    task::spawn(run_peak_cache_build(project_root, asset_id, source_path, config, None))
+}
+
+/// Semaphore shared by all peak builds so a project full of audio doesn't saturate the CPU.
+fn peak_build_gate() -> &'static Semaphore {
+    static GATE: OnceLock<Semaphore> = OnceLock::new();
+    GATE.get_or_init(|| {
+        let cores = std::thread::available_parallelism()
+            .map(|count| count.get())
+            .unwrap_or(2);
+        Semaphore::new((cores / 2).clamp(1, PEAK_BUILD_MAX_CONCURRENT))
+    })
 }
 
 pub fn resolve_audio_source(project_root: &Path, asset: &Asset) -> Option<std::path::PathBuf> {
@@ -179,6 +360,7 @@ fn combine_peaks(peaks: &[PeakPair], factor: usize) -> Vec<PeakPair> {
 struct PeakAccumulator {
     block_size: usize,
     count: usize,
+    frames_seen: usize,
     min_l: f32,
     max_l: f32,
     min_r: f32,
@@ -191,6 +373,7 @@ impl PeakAccumulator {
         Self {
             block_size: block_size.max(1),
             count: 0,
+            frames_seen: 0,
             min_l: 1.0,
             max_l: -1.0,
             min_r: 1.0,
@@ -211,12 +394,22 @@ impl PeakAccumulator {
         self.min_r = self.min_r.min(right);
         self.max_r = self.max_r.max(right);
         self.count += 1;
+        self.frames_seen += 1;
 
         if self.count >= self.block_size {
             self.flush_block();
         }
     }
 
+    fn frames_seen(&self) -> usize {
+        self.frames_seen
+    }
+
+    /// Copy of the completed blocks so far (the in-progress block is left out).
+    fn snapshot(&self) -> Vec<PeakPair> {
+        self.peaks.clone()
+    }
+
     fn finish(mut self) -> Vec<PeakPair> {
         if self.count > 0 {
             self.flush_block();
@@ -311,3 +504,53 @@ fn resolve_generative_video_source(
 
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clips_of_one_asset_share_a_loaded_cache() {
+        let dir = std::env::temp_dir().join(format!("nla-shared-peaks-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("music.wav");
+        std::fs::write(&source, [0_u8; 128]).unwrap();
+
+        let config = PeakBuildConfig::default();
+        let (source_size, source_mtime) = source_identity(&source).unwrap();
+        let asset_id = Uuid::new_v4();
+        let cache_path = peak_cache_path(&dir, asset_id);
+        let cache = PeakCache {
+            sample_rate: config.target_rate,
+            channels: 2,
+            source_size,
+            source_mtime,
+            levels: vec![PeakLevel {
+                block_size: config.base_block,
+                peaks: Vec::new(),
+            }],
+        };
+        write_peak_cache(&cache_path, &cache).unwrap();
+
+        let registry = PeakCacheRegistry::default();
+        let first = load_shared_peak_cache(&registry, asset_id, &cache_path, &source, &config)
+            .unwrap()
+            .unwrap();
+
+        // The second clip is served from memory even with the file gone
+        std::fs::remove_file(&cache_path).unwrap();
+        let second = load_shared_peak_cache(&registry, asset_id, &cache_path, &source, &config)
+            .unwrap()
+            .unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+
+        // A different peak resolution isn't served from the shared entry
+        let finer = PeakBuildConfig::with_peaks_per_second(1000.0);
+        assert!(load_shared_peak_cache(&registry, asset_id, &cache_path, &source, &finer)
+            .unwrap()
+            .is_none());
+        assert_eq!(registry.len(), 0);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
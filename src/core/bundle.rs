@@ -0,0 +1,423 @@
+//! Export a project as a self-contained `.zip` bundle.
+//!
+//! Planning walks the project folder and decides what goes into the archive;
+//! writing streams each file into the zip in chunks, so large generated
+//! videos are never held in memory. Regenerable caches and rendered exports
+//! are left out. Providers the project uses are bundled with their ComfyUI
+//! workflow and manifest files, rewritten to point inside the bundle. Media
+//! outside the project folder isn't copied; it is listed in the report and in
+//! `external_media.json` inside the archive.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use uuid::Uuid;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::core::paths::resolve_resource_path;
+use crate::core::provider_store::portable_provider_entry;
+use crate::state::{resolve_project_path, Project, ProviderConnection, ProviderEntry, ProviderScope};
+
+/// Top-level folders that are rebuilt on demand and not worth shipping.
+const SKIPPED_FOLDERS: [&str; 2] = [".cache", "exports"];
+const PROVIDERS_FOLDER: &str = ".providers";
+/// Where workflows referenced from outside the project land in the bundle.
+const BUNDLED_WORKFLOWS_FOLDER: &str = "workflows";
+const EXTERNAL_MEDIA_MANIFEST: &str = "external_media.json";
+const WRITE_CHUNK_BYTES: usize = 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BundleSource {
+    File(PathBuf),
+    /// Generated content, e.g. a provider config rewritten for the bundle.
+    Bytes(Vec<u8>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BundleEntry {
+    /// `/`-separated path inside the archive, relative to the bundle folder.
+    pub archive_path: String,
+    pub source: BundleSource,
+    pub size: u64,
+}
+
+/// A media asset that lives outside the project folder and wasn't bundled.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExternalMedia {
+    pub asset_id: Uuid,
+    pub asset_name: String,
+    pub path: PathBuf,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BundlePlan {
+    /// Folder name every entry is placed under inside the archive.
+    pub root_name: String,
+    pub entries: Vec<BundleEntry>,
+    pub external_media: Vec<ExternalMedia>,
+}
+
+impl BundlePlan {
+    pub fn total_bytes(&self) -> u64 {
+        self.entries.iter().map(|entry| entry.size).sum()
+    }
+
+    pub fn contains(&self, archive_path: &str) -> bool {
+        self.entries.iter().any(|entry| entry.archive_path == archive_path)
+    }
+
+    /// One-line summary for the status bar.
+    pub fn summary(&self) -> String {
+        let mut summary = format!("Bundled {} files", self.entries.len());
+        if !self.external_media.is_empty() {
+            let names: Vec<&str> = self
+                .external_media
+                .iter()
+                .map(|media| media.asset_name.as_str())
+                .collect();
+            summary.push_str(&format!(
+                "; {} external asset(s) not bundled: {}",
+                names.len(),
+                names.join(", ")
+            ));
+        }
+        summary
+    }
+}
+
+/// Decide what goes into the bundle for `project`, saved at `project_root`.
+/// `providers` is the merged provider list; project providers and any global
+/// provider a generative asset uses are bundled. `output` is skipped in case
+/// the zip is being written inside the project.
+pub fn plan_project_bundle(
+    project: &Project,
+    project_root: &Path,
+    providers: &[ProviderEntry],
+    output: &Path,
+) -> io::Result<BundlePlan> {
+    let root_name = project_root
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| project.name.clone());
+    let mut plan = BundlePlan {
+        root_name,
+        ..BundlePlan::default()
+    };
+
+    let used_provider_ids: HashSet<Uuid> = project
+        .generative_configs
+        .values()
+        .flat_map(|config| {
+            config
+                .provider_id
+                .into_iter()
+                .chain(config.versions.iter().map(|record| record.provider_id))
+        })
+        .collect();
+    let bundled_providers: Vec<&ProviderEntry> = providers
+        .iter()
+        .filter(|entry| entry.scope == ProviderScope::Project || used_provider_ids.contains(&entry.id))
+        .collect();
+    let rewritten_provider_files: HashSet<String> = bundled_providers
+        .iter()
+        .map(|entry| provider_archive_path(entry.id))
+        .collect();
+
+    let mut files = Vec::new();
+    collect_project_files(project_root, Path::new(""), &mut files)?;
+    for (relative, size) in files {
+        let source = project_root.join(&relative);
+        if source == output {
+            continue;
+        }
+        let archive_path = archive_path_for(&relative);
+        if rewritten_provider_files.contains(&archive_path) {
+            continue;
+        }
+        plan.entries.push(BundleEntry {
+            archive_path,
+            source: BundleSource::File(source),
+            size,
+        });
+    }
+
+    for entry in bundled_providers {
+        let mut bundled = entry.clone();
+        if let ProviderConnection::ComfyUi {
+            workflow_path,
+            manifest_path,
+            ..
+        } = &mut bundled.connection
+        {
+            for path in [workflow_path, manifest_path].into_iter().flatten() {
+                if let Some(archive_path) = bundle_provider_file(&mut plan, project_root, entry.id, path.as_str()) {
+                    *path = archive_path;
+                }
+            }
+        }
+        let json = serde_json::to_vec_pretty(&portable_provider_entry(&bundled, project_root))
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        plan.entries.push(BundleEntry {
+            archive_path: provider_archive_path(entry.id),
+            size: json.len() as u64,
+            source: BundleSource::Bytes(json),
+        });
+    }
+
+    for asset in project.assets.iter().filter(|asset| !asset.is_generative()) {
+        let path = resolve_project_path(asset.stored_path(), project_root);
+        if !path.starts_with(project_root) {
+            plan.external_media.push(ExternalMedia {
+                asset_id: asset.id,
+                asset_name: asset.name.clone(),
+                path,
+            });
+        }
+    }
+    if !plan.external_media.is_empty() {
+        let json = serde_json::to_vec_pretty(&plan.external_media)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        plan.entries.push(BundleEntry {
+            archive_path: EXTERNAL_MEDIA_MANIFEST.to_string(),
+            size: json.len() as u64,
+            source: BundleSource::Bytes(json),
+        });
+    }
+
+    Ok(plan)
+}
+
+/// Stream every entry of `plan` into a zip at `output`. `on_progress` gets
+/// (bytes written, total bytes).
+pub fn write_project_bundle(
+    plan: &BundlePlan,
+    output: &Path,
+    mut on_progress: impl FnMut(u64, u64),
+) -> Result<(), String> {
+    if let Some(parent) = output.parent() {
+        fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    let file = fs::File::create(output).map_err(|err| err.to_string())?;
+    let mut zip = ZipWriter::new(io::BufWriter::new(file));
+    let total = plan.total_bytes();
+    let mut written = 0_u64;
+    on_progress(written, total);
+
+    let mut buffer = vec![0_u8; WRITE_CHUNK_BYTES];
+    for entry in &plan.entries {
+        let name = format!("{}/{}", plan.root_name, entry.archive_path);
+        let options = SimpleFileOptions::default()
+            .compression_method(compression_for(&entry.archive_path))
+            .large_file(entry.size >= u32::MAX as u64);
+        zip.start_file(name.as_str(), options).map_err(|err| err.to_string())?;
+        match &entry.source {
+            BundleSource::Bytes(bytes) => {
+                zip.write_all(bytes).map_err(|err| err.to_string())?;
+                written += bytes.len() as u64;
+                on_progress(written, total);
+            }
+            BundleSource::File(path) => {
+                let mut reader = fs::File::open(path)
+                    .map_err(|err| format!("{}: {}", path.display(), err))?;
+                loop {
+                    let read = reader.read(&mut buffer).map_err(|err| err.to_string())?;
+                    if read == 0 {
+                        break;
+                    }
+                    zip.write_all(&buffer[..read]).map_err(|err| err.to_string())?;
+                    written += read as u64;
+                    on_progress(written, total);
+                }
+            }
+        }
+    }
+    zip.finish().map_err(|err| err.to_string())?;
+    Ok(())
+}
+
+/// Every file under the project except the skipped top-level folders, as
+/// (path relative to `root`, size).
+fn collect_project_files(root: &Path, relative: &Path, files: &mut Vec<(PathBuf, u64)>) -> io::Result<()> {
+    for entry in fs::read_dir(root.join(relative))? {
+        let entry = entry?;
+        let entry_relative = relative.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            let skipped = relative.as_os_str().is_empty()
+                && SKIPPED_FOLDERS
+                    .iter()
+                    .any(|folder| entry.file_name() == std::ffi::OsStr::new(folder));
+            if !skipped {
+                collect_project_files(root, &entry_relative, files)?;
+            }
+        } else if file_type.is_file() {
+            files.push((entry_relative, entry.metadata()?.len()));
+        }
+    }
+    Ok(())
+}
+
+/// Add a provider's workflow or manifest file to the bundle when it lives
+/// outside the project. Returns the path the bundled provider should use.
+fn bundle_provider_file(
+    plan: &mut BundlePlan,
+    project_root: &Path,
+    provider_id: Uuid,
+    stored: &str,
+) -> Option<String> {
+    let path = resolve_project_path(Path::new(stored), project_root);
+    let path = if path.exists() {
+        path
+    } else {
+        resolve_resource_path(Path::new(stored))
+    };
+    if path.starts_with(project_root) || !path.is_file() {
+        // Already travels with the folder, or there is nothing to copy
+        return None;
+    }
+    let file_name = path.file_name()?.to_string_lossy().to_string();
+    let archive_path = format!("{}/{}/{}", BUNDLED_WORKFLOWS_FOLDER, provider_id, file_name);
+    if !plan.contains(&archive_path) {
+        let size = fs::metadata(&path).ok()?.len();
+        plan.entries.push(BundleEntry {
+            archive_path: archive_path.clone(),
+            source: BundleSource::File(path),
+            size,
+        });
+    }
+    Some(archive_path)
+}
+
+fn provider_archive_path(provider_id: Uuid) -> String {
+    format!("{}/{}.json", PROVIDERS_FOLDER, provider_id)
+}
+
+fn archive_path_for(relative: &Path) -> String {
+    relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().to_string())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Text is worth deflating; media is already compressed.
+fn compression_for(archive_path: &str) -> CompressionMethod {
+    let text = ["json", "txt", "md"]
+        .iter()
+        .any(|ext| archive_path.to_ascii_lowercase().ends_with(&format!(".{}", ext)));
+    if text {
+        CompressionMethod::Deflated
+    } else {
+        CompressionMethod::Stored
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{Asset, GenerativeConfig, ProviderOutputType};
+
+    fn write(path: &Path, contents: &[u8]) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    fn comfy_provider(workflow: &Path, scope: ProviderScope) -> ProviderEntry {
+        let mut entry = ProviderEntry::new(
+            "Comfy",
+            ProviderOutputType::Image,
+            ProviderConnection::ComfyUi {
+                base_url: "http://127.0.0.1:8188".to_string(),
+                workflow_path: Some(workflow.to_string_lossy().to_string()),
+                manifest_path: None,
+            },
+        );
+        entry.scope = scope;
+        entry
+    }
+
+    #[test]
+    fn test_bundle_collects_project_files_and_reports_external_media() {
+        let base = std::env::temp_dir().join(format!("nla-bundle-{}", Uuid::new_v4()));
+        let root = base.join("Short Film");
+        let mut project = Project::create_in(&root, "Short Film").unwrap();
+        write(&root.join("video").join("clip.mp4"), b"video");
+        write(&root.join("generated/image/gen_001/v1.png"), b"png");
+        write(&root.join(".cache/thumbnails/a/0.jpg"), b"thumb");
+        write(&root.join("exports/final.mp4"), b"render");
+        write(&base.join("shared").join("music.wav"), b"wav");
+        project.add_asset(Asset::new_video("clip", PathBuf::from("video/clip.mp4")));
+        let music = Asset::new_audio("music", base.join("shared").join("music.wav"));
+        let music_id = project.add_asset(music);
+
+        // One global provider is used by a generative asset; another is not
+        let outside_workflow = base.join("workflows").join("flow_API.json");
+        write(&outside_workflow, b"{}");
+        let used = comfy_provider(&outside_workflow, ProviderScope::Global);
+        let unused = comfy_provider(&outside_workflow, ProviderScope::Global);
+        let generative_id = project.add_asset(Asset::new_generative_image(
+            "gen",
+            PathBuf::from("generated/image/gen_001"),
+        ));
+        project.generative_configs.insert(
+            generative_id,
+            GenerativeConfig {
+                provider_id: Some(used.id),
+                ..GenerativeConfig::default()
+            },
+        );
+        project.save().unwrap();
+
+        let output = root.join("Short Film.zip");
+        let plan = plan_project_bundle(&project, &root, &[used.clone(), unused.clone()], &output).unwrap();
+
+        assert_eq!(plan.root_name, "Short Film");
+        assert!(plan.contains("project.json"));
+        assert!(plan.contains("video/clip.mp4"));
+        assert!(plan.contains("generated/image/gen_001/v1.png"));
+        // Caches and rendered exports stay behind
+        assert!(!plan.entries.iter().any(|entry| entry.archive_path.starts_with(".cache/")));
+        assert!(!plan.contains("exports/final.mp4"));
+
+        // The used provider and its outside workflow are bundled, pointing inside the bundle
+        let workflow_in_bundle = format!("workflows/{}/flow_API.json", used.id);
+        assert!(plan.contains(&workflow_in_bundle));
+        assert!(!plan.contains(&provider_archive_path(unused.id)));
+        let provider_entry = plan
+            .entries
+            .iter()
+            .find(|entry| entry.archive_path == provider_archive_path(used.id))
+            .unwrap();
+        let BundleSource::Bytes(json) = &provider_entry.source else {
+            panic!("provider should be rewritten");
+        };
+        let bundled: ProviderEntry = serde_json::from_slice(json).unwrap();
+        match bundled.connection {
+            ProviderConnection::ComfyUi { workflow_path, .. } => {
+                assert_eq!(workflow_path.as_deref(), Some(workflow_in_bundle.as_str()))
+            }
+            other => panic!("unexpected connection {:?}", other),
+        }
+
+        // Media outside the folder is reported and listed, not copied
+        assert_eq!(plan.external_media.len(), 1);
+        assert_eq!(plan.external_media[0].asset_id, music_id);
+        assert!(plan.contains(EXTERNAL_MEDIA_MANIFEST));
+        assert!(!plan.entries.iter().any(|entry| entry.archive_path.ends_with("music.wav")));
+        assert!(plan.summary().contains("music"));
+
+        // Writing streams everything, skipping the zip itself on a second plan
+        let mut progress = (0, 0);
+        write_project_bundle(&plan, &output, |done, total| progress = (done, total)).unwrap();
+        assert_eq!(progress.0, progress.1);
+        assert_eq!(progress.1, plan.total_bytes());
+        let replanned = plan_project_bundle(&project, &root, &[used], &output).unwrap();
+        assert!(!replanned.contains("Short Film.zip"));
+
+        let _ = fs::remove_dir_all(&base);
+    }
+}
@@ -0,0 +1,113 @@
+//! Deciding whether closing the window needs the user's confirmation.
+
+use crate::state::{GenerationJob, GenerationJobStatus};
+
+/// The user's answer to the unsaved-changes prompt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnsavedChoice {
+    Save,
+    Discard,
+    Cancel,
+}
+
+/// Generation jobs that would be abandoned by quitting now.
+pub fn active_job_count(jobs: &[GenerationJob]) -> usize {
+    jobs.iter()
+        .filter(|job| matches!(job.status, GenerationJobStatus::Queued | GenerationJobStatus::Running))
+        .count()
+}
+
+/// Whether closing the window should ask first: queued or running jobs would
+/// be abandoned, or the project has unsaved changes.
+pub fn should_warn_on_close(jobs: &[GenerationJob], dirty: bool) -> bool {
+    dirty || active_job_count(jobs) > 0
+}
+
+/// Whether the window may close once the user answered the prompt. `save`
+/// only runs for [`UnsavedChoice::Save`]; if it fails the window stays open
+/// so the edits aren't lost.
+pub fn close_after_choice(choice: UnsavedChoice, save: impl FnOnce() -> bool) -> bool {
+    match choice {
+        UnsavedChoice::Save => save(),
+        UnsavedChoice::Discard => true,
+        UnsavedChoice::Cancel => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{ProviderConnection, ProviderEntry, ProviderOutputType};
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn job(status: GenerationJobStatus) -> GenerationJob {
+        let provider = ProviderEntry::new(
+            "Test",
+            ProviderOutputType::Image,
+            ProviderConnection::ComfyUi {
+                base_url: "http://localhost:8188".to_string(),
+                workflow_path: None,
+                manifest_path: None,
+            },
+        );
+        GenerationJob {
+            id: Uuid::new_v4(),
+            created_at: chrono::Utc::now(),
+            status,
+            progress_overall: None,
+            progress_node: None,
+            attempts: 0,
+            next_attempt_at: None,
+            provider,
+            output_type: ProviderOutputType::Image,
+            asset_id: Uuid::new_v4(),
+            clip_id: Uuid::new_v4(),
+            batch_id: None,
+            asset_label: "Gen".to_string(),
+            folder_path: std::path::PathBuf::new(),
+            inputs: HashMap::new(),
+            inputs_snapshot: HashMap::new(),
+            version: None,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_should_warn_on_close() {
+        use GenerationJobStatus::*;
+        // Idle and saved: close right away
+        assert!(!should_warn_on_close(&[], false));
+        // Finished jobs have nothing left to lose
+        let finished = vec![job(Succeeded), job(Failed)];
+        assert_eq!(active_job_count(&finished), 0);
+        assert!(!should_warn_on_close(&finished, false));
+        assert!(should_warn_on_close(&finished, true));
+
+        for status in [Queued, Running] {
+            let jobs = vec![job(Succeeded), job(status)];
+            assert_eq!(active_job_count(&jobs), 1);
+            assert!(should_warn_on_close(&jobs, false));
+            assert!(should_warn_on_close(&jobs, true));
+        }
+        assert_eq!(active_job_count(&[job(Running), job(Queued), job(Queued)]), 3);
+    }
+
+    #[test]
+    fn test_close_after_choice() {
+        assert!(close_after_choice(UnsavedChoice::Save, || true));
+        // A failed save keeps the window open
+        assert!(!close_after_choice(UnsavedChoice::Save, || false));
+        // Only saving actually saves
+        let mut saved = false;
+        assert!(close_after_choice(UnsavedChoice::Discard, || {
+            saved = true;
+            true
+        }));
+        assert!(!close_after_choice(UnsavedChoice::Cancel, || {
+            saved = true;
+            true
+        }));
+        assert!(!saved);
+    }
+}
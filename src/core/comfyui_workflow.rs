@@ -1,14 +1,48 @@
 use serde_json::Value;
-use std::path::Path;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+use crate::state::NodeSelector;
 
 #[derive(Debug, Clone)]
 pub struct ComfyWorkflowNode {
     pub id: String,
     pub class_type: String,
     pub title: Option<String>,
+    /// `_meta.nla_tag`, used by selectors to pin a node.
+    pub tag: Option<String>,
     pub inputs: Vec<String>,
 }
 
+/// A manifest binding that no longer resolves against the loaded workflow.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BindingWarning {
+    MissingClass { binding: String, class_type: String },
+    MissingInputKey { binding: String, class_type: String, input_key: String },
+    Ambiguous { binding: String, node_ids: Vec<String> },
+}
+
+impl std::fmt::Display for BindingWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BindingWarning::MissingClass { binding, class_type } => {
+                write!(f, "{}: no {} node in this workflow", binding, class_type)
+            }
+            BindingWarning::MissingInputKey {
+                binding,
+                class_type,
+                input_key,
+            } => write!(f, "{}: {} has no input '{}'", binding, class_type, input_key),
+            BindingWarning::Ambiguous { binding, node_ids } => write!(
+                f,
+                "{}: matches several nodes ({}); set a title or tag",
+                binding,
+                node_ids.join(", ")
+            ),
+        }
+    }
+}
+
 pub fn load_workflow_nodes(path: &Path) -> Result<Vec<ComfyWorkflowNode>, String> {
     let json = std::fs::read_to_string(path)
         .map_err(|err| format!("Failed to read workflow: {}", err))?;
@@ -17,6 +51,101 @@ pub fn load_workflow_nodes(path: &Path) -> Result<Vec<ComfyWorkflowNode>, String
     parse_workflow_nodes(&value)
 }
 
+/// Stable fingerprint of a workflow, stored in the manifest so the builder can
+/// tell when the file was edited after the bindings were authored.
+///
+/// `serde_json` objects are key-sorted, so re-serializing the parsed value
+/// canonicalizes key order and whitespace before hashing.
+pub fn workflow_hash(value: &Value) -> String {
+    let digest = Sha256::digest(value.to_string().as_bytes());
+    let hex: String = digest.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!("sha256:{}", hex)
+}
+
+pub fn hash_workflow_file(path: &Path) -> Result<String, String> {
+    let json = std::fs::read_to_string(path)
+        .map_err(|err| format!("Failed to read workflow: {}", err))?;
+    let value: Value = serde_json::from_str(&json)
+        .map_err(|err| format!("Invalid workflow JSON: {}", err))?;
+    Ok(workflow_hash(&value))
+}
+
+/// True when the manifest recorded a hash and the workflow no longer matches
+/// it. Manifests written before hashes were stored never report drift.
+pub fn workflow_drifted(stored_hash: Option<&str>, current_hash: &str) -> bool {
+    match stored_hash {
+        Some(stored) if !stored.trim().is_empty() => stored.trim() != current_hash,
+        _ => false,
+    }
+}
+
+/// Parse workflow JSON pasted into the builder. Only the API format (node ids
+/// mapped to `{ class_type, inputs }`) can be queued, so the editor graph that
+/// ComfyUI's regular "Save" writes is rejected with a hint.
+pub fn parse_api_workflow(text: &str) -> Result<Value, String> {
+    let value: Value = serde_json::from_str(text.trim())
+        .map_err(|err| format!("Invalid workflow JSON: {}", err))?;
+    let Some(map) = value.as_object() else {
+        return Err("Workflow JSON must be an object.".to_string());
+    };
+    if map.get("nodes").map(Value::is_array).unwrap_or(false)
+        && (map.contains_key("links") || map.contains_key("last_node_id"))
+    {
+        return Err(
+            "This is ComfyUI's UI-format graph. Export it with \"Save (API Format)\" \
+             (enable dev mode options in ComfyUI settings) and paste that instead."
+                .to_string(),
+        );
+    }
+    if map.is_empty() {
+        return Err("Workflow JSON has no nodes.".to_string());
+    }
+    for (node_id, node_value) in map.iter() {
+        let is_api_id = !node_id.is_empty()
+            && node_id
+                .split(':')
+                .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()));
+        let has_class = node_value
+            .get("class_type")
+            .map(Value::is_string)
+            .unwrap_or(false);
+        if !is_api_id || !has_class {
+            return Err(format!(
+                "Entry \"{}\" is not an API-format node (expected a numeric id with a class_type).",
+                node_id
+            ));
+        }
+    }
+    Ok(value)
+}
+
+/// Write a pasted workflow into `dir` as `<name>_API.json`, adding a numeric
+/// suffix instead of overwriting an existing file.
+pub fn save_pasted_workflow(dir: &Path, name: &str, value: &Value) -> Result<PathBuf, String> {
+    std::fs::create_dir_all(dir)
+        .map_err(|err| format!("Failed to create workflows folder: {}", err))?;
+    let stem: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    let stem = if stem.trim_matches('_').is_empty() {
+        "pasted_workflow".to_string()
+    } else {
+        stem
+    };
+    let mut path = dir.join(format!("{}_API.json", stem));
+    let mut suffix = 2;
+    while path.exists() {
+        path = dir.join(format!("{}_{}_API.json", stem, suffix));
+        suffix += 1;
+    }
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|err| format!("Failed to serialize workflow: {}", err))?;
+    std::fs::write(&path, json).map_err(|err| format!("Failed to write workflow: {}", err))?;
+    Ok(path)
+}
+
 pub fn parse_workflow_nodes(value: &Value) -> Result<Vec<ComfyWorkflowNode>, String> {
     let Some(map) = value.as_object() else {
         return Err("Workflow JSON must be an object.".to_string());
@@ -37,6 +166,11 @@ pub fn parse_workflow_nodes(value: &Value) -> Result<Vec<ComfyWorkflowNode>, Str
             .and_then(|meta| meta.get("title"))
             .and_then(|value| value.as_str())
             .map(|value| value.to_string());
+        let tag = node_obj
+            .get("_meta")
+            .and_then(|meta| meta.get("nla_tag"))
+            .and_then(|value| value.as_str())
+            .map(|value| value.to_string());
         let mut inputs = Vec::new();
         if let Some(input_map) = node_obj.get("inputs").and_then(|value| value.as_object()) {
             for key in input_map.keys() {
@@ -49,6 +183,7 @@ pub fn parse_workflow_nodes(value: &Value) -> Result<Vec<ComfyWorkflowNode>, Str
             id: node_id.clone(),
             class_type,
             title,
+            tag,
             inputs,
         });
     }
@@ -56,3 +191,203 @@ pub fn parse_workflow_nodes(value: &Value) -> Result<Vec<ComfyWorkflowNode>, Str
     nodes.sort_by(|a, b| a.id.cmp(&b.id));
     Ok(nodes)
 }
+
+/// Check that each input selector (and the output node, whose `input_key` is
+/// the output key rather than a node input) still resolves the way the ComfyUI
+/// adapter will resolve it.
+pub fn validate_bindings(
+    nodes: &[ComfyWorkflowNode],
+    inputs: &[(String, NodeSelector)],
+    output: Option<&NodeSelector>,
+) -> Vec<BindingWarning> {
+    let mut warnings = Vec::new();
+    for (name, selector) in inputs {
+        if let Some(warning) = check_selector(nodes, name, selector, true) {
+            warnings.push(warning);
+        }
+    }
+    if let Some(selector) = output {
+        if let Some(warning) = check_selector(nodes, "output", selector, false) {
+            warnings.push(warning);
+        }
+    }
+    warnings
+}
+
+fn check_selector(
+    nodes: &[ComfyWorkflowNode],
+    binding: &str,
+    selector: &NodeSelector,
+    require_input_key: bool,
+) -> Option<BindingWarning> {
+    let tag = selector.tag.as_deref().filter(|tag| !tag.is_empty());
+    let class_matches: Vec<&ComfyWorkflowNode> = nodes
+        .iter()
+        .filter(|node| node.class_type == selector.class_type)
+        .filter(|node| tag.is_none() || node.tag.as_deref() == tag)
+        .collect();
+    if class_matches.is_empty() {
+        return Some(BindingWarning::MissingClass {
+            binding: binding.to_string(),
+            class_type: selector.class_type.clone(),
+        });
+    }
+
+    let mut candidates = class_matches;
+    if require_input_key {
+        candidates.retain(|node| node.inputs.contains(&selector.input_key));
+        if candidates.is_empty() {
+            return Some(BindingWarning::MissingInputKey {
+                binding: binding.to_string(),
+                class_type: selector.class_type.clone(),
+                input_key: selector.input_key.clone(),
+            });
+        }
+    }
+    if let Some(title) = selector.title.as_ref() {
+        let titled: Vec<&ComfyWorkflowNode> = candidates
+            .iter()
+            .copied()
+            .filter(|node| node.title.as_ref() == Some(title))
+            .collect();
+        if !titled.is_empty() {
+            candidates = titled;
+        }
+    }
+    if candidates.len() > 1 {
+        return Some(BindingWarning::Ambiguous {
+            binding: binding.to_string(),
+            node_ids: candidates.iter().map(|node| node.id.clone()).collect(),
+        });
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn selector(class_type: &str, input_key: &str, title: Option<&str>) -> NodeSelector {
+        NodeSelector {
+            tag: None,
+            class_type: class_type.to_string(),
+            input_key: input_key.to_string(),
+            title: title.map(str::to_string),
+        }
+    }
+
+    fn nodes() -> Vec<ComfyWorkflowNode> {
+        parse_workflow_nodes(&json!({
+            "3": { "class_type": "KSampler", "inputs": { "seed": 1, "steps": 20 } },
+            "6": { "class_type": "CLIPTextEncode", "inputs": { "text": "a" }, "_meta": { "title": "Positive" } },
+            "7": { "class_type": "CLIPTextEncode", "inputs": { "text": "b" }, "_meta": { "title": "Negative" } },
+            "9": { "class_type": "SaveImage", "inputs": { "images": ["8", 0] } }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_valid_bindings_have_no_warnings() {
+        let inputs = vec![
+            ("seed".to_string(), selector("KSampler", "seed", None)),
+            ("prompt".to_string(), selector("CLIPTextEncode", "text", Some("Positive"))),
+        ];
+        let output = selector("SaveImage", "images", None);
+        assert!(validate_bindings(&nodes(), &inputs, Some(&output)).is_empty());
+    }
+
+    #[test]
+    fn test_missing_class_and_input_key() {
+        let inputs = vec![
+            ("cfg".to_string(), selector("KSamplerAdvanced", "cfg", None)),
+            ("denoise".to_string(), selector("KSampler", "denoise", None)),
+        ];
+        let output = selector("VHS_VideoCombine", "gifs", None);
+        assert_eq!(
+            validate_bindings(&nodes(), &inputs, Some(&output)),
+            vec![
+                BindingWarning::MissingClass {
+                    binding: "cfg".to_string(),
+                    class_type: "KSamplerAdvanced".to_string(),
+                },
+                BindingWarning::MissingInputKey {
+                    binding: "denoise".to_string(),
+                    class_type: "KSampler".to_string(),
+                    input_key: "denoise".to_string(),
+                },
+                BindingWarning::MissingClass {
+                    binding: "output".to_string(),
+                    class_type: "VHS_VideoCombine".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_workflow_hash_ignores_key_order_and_whitespace() {
+        let a: Value = serde_json::from_str(
+            r#"{"3": {"class_type": "KSampler", "inputs": {"seed": 1, "steps": 20}}}"#,
+        )
+        .unwrap();
+        let b: Value =
+            serde_json::from_str(r#"{"3":{"inputs":{"steps":20,"seed":1},"class_type":"KSampler"}}"#)
+                .unwrap();
+        assert_eq!(workflow_hash(&a), workflow_hash(&b));
+        assert_eq!(workflow_hash(&a), workflow_hash(&a.clone()));
+
+        let edited = json!({ "3": { "class_type": "KSampler", "inputs": { "seed": 1, "steps": 30 } } });
+        assert_ne!(workflow_hash(&a), workflow_hash(&edited));
+    }
+
+    #[test]
+    fn test_workflow_drift_detection() {
+        let current = workflow_hash(&json!({ "1": { "class_type": "SaveImage", "inputs": {} } }));
+        assert!(!workflow_drifted(Some(&current), &current));
+        assert!(workflow_drifted(Some("sha256:0000"), &current));
+        // Older manifests without a hash never warn
+        assert!(!workflow_drifted(None, &current));
+        assert!(!workflow_drifted(Some(""), &current));
+    }
+
+    #[test]
+    fn test_parse_api_workflow_accepts_api_format() {
+        let text = r#"{
+            "3": { "class_type": "KSampler", "inputs": { "seed": 1 } },
+            "9": { "class_type": "SaveImage", "inputs": {}, "_meta": { "title": "Save" } }
+        }"#;
+        let value = parse_api_workflow(text).unwrap();
+        assert_eq!(parse_workflow_nodes(&value).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_parse_api_workflow_rejects_ui_format() {
+        let text = r#"{
+            "last_node_id": 9,
+            "last_link_id": 9,
+            "nodes": [{ "id": 3, "type": "KSampler", "widgets_values": [1, "fixed"] }],
+            "links": [],
+            "version": 0.4
+        }"#;
+        let err = parse_api_workflow(text).unwrap_err();
+        assert!(err.contains("UI-format"), "{}", err);
+        assert!(err.contains("Save (API Format)"), "{}", err);
+
+        let err = parse_api_workflow(r#"{ "3": { "inputs": {} } }"#).unwrap_err();
+        assert!(err.contains("\"3\""), "{}", err);
+        assert!(parse_api_workflow("[1, 2]").is_err());
+        assert!(parse_api_workflow("{}").is_err());
+    }
+
+    #[test]
+    fn test_ambiguous_selector_without_title() {
+        let inputs = vec![("prompt".to_string(), selector("CLIPTextEncode", "text", None))];
+        assert_eq!(
+            validate_bindings(&nodes(), &inputs, None),
+            vec![BindingWarning::Ambiguous {
+                binding: "prompt".to_string(),
+                node_ids: vec!["6".to_string(), "7".to_string()],
+            }]
+        );
+    }
+}
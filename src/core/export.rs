@@ -0,0 +1,256 @@
+//! Export job plumbing: ffmpeg child management, progress, and cancellation.
+//!
+//! The timeline exporter drives an [`FfmpegExportProcess`]; the UI watches
+//! [`ExportStatus`] and cancels through a [`CancelHandle`].
+#![allow(dead_code)]
+
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::core::cancel::CancelHandle;
+
+/// How many trailing stderr lines to keep for error messages.
+const STDERR_TAIL_LINES: usize = 12;
+const PROGRESS_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Snapshot of a running export.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExportProgress {
+    /// Frames encoded so far.
+    pub frame: u64,
+    pub total_frames: u64,
+    pub elapsed: Duration,
+}
+
+impl ExportProgress {
+    pub fn fraction(&self) -> f64 {
+        if self.total_frames == 0 {
+            return 0.0;
+        }
+        (self.frame as f64 / self.total_frames as f64).clamp(0.0, 1.0)
+    }
+
+    /// Remaining time extrapolated from the average rate so far.
+    pub fn eta(&self) -> Option<Duration> {
+        if self.frame == 0 || self.total_frames == 0 {
+            return None;
+        }
+        let per_frame = self.elapsed.as_secs_f64() / self.frame as f64;
+        let remaining = self.total_frames.saturating_sub(self.frame) as f64;
+        Some(Duration::from_secs_f64(per_frame * remaining))
+    }
+}
+
+/// Where an export job stands, as shown by the progress modal.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportStatus {
+    Running(ExportProgress),
+    Finished(PathBuf),
+    Cancelled,
+    Failed(String),
+}
+
+/// An ffmpeg encode writing to `out_path`, reporting frames via `-progress`.
+pub struct FfmpegExportProcess {
+    child: Child,
+    stdin: Option<ChildStdin>,
+    out_path: PathBuf,
+    frames: Receiver<u64>,
+    last_frame: u64,
+    stderr_tail: Arc<Mutex<VecDeque<String>>>,
+}
+
+impl FfmpegExportProcess {
+    /// Start `ffmpeg -y <args> <out_path>` with stdin piped for raw frames.
+    pub fn spawn(args: &[String], out_path: &Path) -> Result<Self, String> {
+        let mut child = Command::new("ffmpeg")
+            .args(["-hide_banner", "-nostats", "-y", "-progress", "pipe:1"])
+            .args(args)
+            .arg(out_path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| format!("Failed to start ffmpeg: {}", err))?;
+
+        let stdin = child.stdin.take();
+        let stdout = child.stdout.take();
+        let stderr = child.stderr.take();
+
+        let (frame_tx, frames) = mpsc::channel();
+        if let Some(stdout) = stdout {
+            std::thread::spawn(move || {
+                for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                    if let Some(frame) = parse_progress_frame(&line) {
+                        if frame_tx.send(frame).is_err() {
+                            break;
+                        }
+                    }
+                }
+            });
+        }
+
+        let stderr_tail = Arc::new(Mutex::new(VecDeque::with_capacity(STDERR_TAIL_LINES)));
+        if let Some(stderr) = stderr {
+            let stderr_tail = stderr_tail.clone();
+            std::thread::spawn(move || {
+                for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                    if let Ok(mut tail) = stderr_tail.lock() {
+                        if tail.len() >= STDERR_TAIL_LINES {
+                            tail.pop_front();
+                        }
+                        tail.push_back(line);
+                    }
+                }
+            });
+        }
+
+        Ok(Self {
+            child,
+            stdin,
+            out_path: out_path.to_path_buf(),
+            frames,
+            last_frame: 0,
+            stderr_tail,
+        })
+    }
+
+    /// Raw input for encoders reading from `pipe:0`.
+    pub fn stdin(&mut self) -> Option<&mut ChildStdin> {
+        self.stdin.as_mut()
+    }
+
+    /// Latest frame count ffmpeg has reported, without blocking.
+    pub fn poll_frame(&mut self) -> u64 {
+        while let Ok(frame) = self.frames.try_recv() {
+            self.last_frame = frame;
+        }
+        self.last_frame
+    }
+
+    /// Kill the encoder and remove the partial output.
+    pub fn cancel(mut self) {
+        self.stdin = None;
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        let _ = std::fs::remove_file(&self.out_path);
+    }
+
+    /// Close stdin and wait for ffmpeg; a failed encode removes the partial output.
+    pub fn finish(mut self) -> Result<PathBuf, String> {
+        self.stdin = None;
+        let status = self
+            .child
+            .wait()
+            .map_err(|err| format!("Failed to wait for ffmpeg: {}", err))?;
+        if status.success() {
+            return Ok(self.out_path);
+        }
+        let _ = std::fs::remove_file(&self.out_path);
+        let tail = self
+            .stderr_tail
+            .lock()
+            .map(|tail| tail.iter().cloned().collect::<Vec<_>>().join("\n"))
+            .unwrap_or_default();
+        if tail.trim().is_empty() {
+            Err(format!("ffmpeg exited with {}", status))
+        } else {
+            Err(tail)
+        }
+    }
+
+    /// Wait for an encode that needs no piped input, reporting progress until it
+    /// exits or `handle` is cancelled.
+    pub fn wait_with_progress(
+        mut self,
+        total_frames: u64,
+        handle: &CancelHandle,
+        mut on_progress: impl FnMut(ExportProgress),
+    ) -> ExportStatus {
+        self.stdin = None;
+        let started = Instant::now();
+        loop {
+            if handle.is_cancelled() {
+                self.cancel();
+                return ExportStatus::Cancelled;
+            }
+            match self.frames.recv_timeout(PROGRESS_POLL_INTERVAL) {
+                Ok(frame) => {
+                    self.last_frame = frame;
+                    on_progress(ExportProgress {
+                        frame,
+                        total_frames,
+                        elapsed: started.elapsed(),
+                    });
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                // stdout closed: ffmpeg is done one way or another.
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+        match self.finish() {
+            Ok(path) => ExportStatus::Finished(path),
+            Err(err) => ExportStatus::Failed(err),
+        }
+    }
+}
+
+/// Pull the frame count out of a `-progress` line such as `frame=120`.
+fn parse_progress_frame(line: &str) -> Option<u64> {
+    line.trim().strip_prefix("frame=")?.trim().parse().ok()
+}
+
+/// `m:ss` (or `h:mm:ss`) for elapsed/ETA labels.
+pub fn format_export_duration(duration: Duration) -> String {
+    let total = duration.as_secs();
+    let hours = total / 3600;
+    let minutes = (total % 3600) / 60;
+    let seconds = total % 60;
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_progress_frame() {
+        assert_eq!(parse_progress_frame("frame=120"), Some(120));
+        assert_eq!(parse_progress_frame("  frame= 7 \n"), Some(7));
+        assert_eq!(parse_progress_frame("fps=29.97"), None);
+        assert_eq!(parse_progress_frame("frame=N/A"), None);
+        assert_eq!(parse_progress_frame("progress=end"), None);
+    }
+
+    #[test]
+    fn test_eta_extrapolates_average_rate() {
+        let progress = ExportProgress {
+            frame: 100,
+            total_frames: 400,
+            elapsed: Duration::from_secs(25),
+        };
+        assert_eq!(progress.eta(), Some(Duration::from_secs(75)));
+        assert_eq!(progress.fraction(), 0.25);
+
+        // Nothing to extrapolate from yet
+        let starting = ExportProgress { frame: 0, ..progress };
+        assert_eq!(starting.eta(), None);
+        let unknown = ExportProgress { total_frames: 0, ..progress };
+        assert_eq!(unknown.eta(), None);
+        assert_eq!(unknown.fraction(), 0.0);
+
+        // ffmpeg can report past the expected total
+        let over = ExportProgress { frame: 210, ..progress };
+        assert_eq!(over.eta(), Some(Duration::ZERO));
+        assert_eq!(over.fraction(), 1.0);
+    }
+}
@@ -0,0 +1,268 @@
+//! Timeline export: composites every frame at project resolution and pipes the
+//! raw RGBA stream to ffmpeg alongside the audio mixdown.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crate::core::audio::mixdown::{build_playback_items, render_mixdown_blocks};
+use crate::core::cancel::CancelHandle;
+use crate::core::export::{ExportProgress, ExportStatus, FfmpegExportProcess};
+use crate::core::preview::PreviewRenderer;
+use crate::state::{ExportOptions, Project};
+
+/// Sample rate of the exported audio track.
+pub const EXPORT_SAMPLE_RATE: u32 = 48_000;
+/// Channel count of the exported audio track.
+pub const EXPORT_CHANNELS: u16 = 2;
+/// Decoded-frame cache for the export renderer; frames are mostly read once.
+const EXPORT_CACHE_BUDGET_BYTES: usize = 512 * 1024 * 1024;
+
+/// Number of frames needed to cover `duration_seconds` at `fps`. A trailing
+/// partial frame still gets rendered.
+pub fn export_frame_count(duration_seconds: f64, fps: f64) -> u64 {
+    let fps = fps.max(1.0);
+    let duration_seconds = duration_seconds.max(0.0);
+    // Epsilon so a duration sitting on a frame boundary doesn't add a frame.
+    (duration_seconds * fps - 1e-6).ceil().max(0.0) as u64
+}
+
+/// Timeline time of frame `index`.
+pub fn export_frame_time(index: u64, fps: f64) -> f64 {
+    index as f64 / fps.max(1.0)
+}
+
+/// Render `project` to `out_path` with `options`, calling `on_progress` after
+/// every frame. Runs on a blocking thread; cancel through `handle`.
+pub async fn export_project(
+    project: Project,
+    options: ExportOptions,
+    out_path: PathBuf,
+    handle: CancelHandle,
+    on_progress: impl FnMut(ExportProgress) + Send + 'static,
+) -> ExportStatus {
+    let job = tokio::task::spawn_blocking(move || {
+        run_export(&project, &options, &out_path, &handle, on_progress)
+    });
+    match job.await {
+        Ok(status) => status,
+        Err(err) => ExportStatus::Failed(format!("Export task failed: {}", err)),
+    }
+}
+
+fn run_export(
+    project: &Project,
+    options: &ExportOptions,
+    out_path: &Path,
+    handle: &CancelHandle,
+    on_progress: impl FnMut(ExportProgress),
+) -> ExportStatus {
+    if let Err(err) = options.validate() {
+        return ExportStatus::Failed(err);
+    }
+    let Some(project_root) = project.project_path.clone() else {
+        return ExportStatus::Failed("Save the project before exporting.".to_string());
+    };
+
+    let settings = &project.settings;
+    let fps = settings.fps.max(1.0);
+    let duration = project.duration();
+    let total_frames = export_frame_count(duration, fps);
+    if total_frames == 0 {
+        return ExportStatus::Failed("The timeline is empty.".to_string());
+    }
+
+    // ffmpeg reads video from stdin, so the audio goes through a temp file.
+    let audio_path = std::env::temp_dir().join(format!("nla-export-{}.f32", uuid::Uuid::new_v4()));
+    let status = write_mixdown(project, &project_root, duration, &audio_path).and_then(|_| {
+        FfmpegExportProcess::spawn(&ffmpeg_input_args(project, options, &audio_path), out_path)
+    });
+    let status = match status {
+        Ok(mut process) => {
+            let renderer = PreviewRenderer::new_with_limits(
+                project_root,
+                EXPORT_CACHE_BUDGET_BYTES,
+                settings.width,
+                settings.height,
+            );
+            let result = drive_export(total_frames, fps, handle, on_progress, |time| {
+                let frame = renderer.render_export_frame(project, time);
+                let stdin = process
+                    .stdin()
+                    .ok_or_else(|| "ffmpeg closed its input".to_string())?;
+                stdin.write_all(frame.as_raw()).map_err(|err| err.to_string())
+            });
+            match result {
+                Ok(true) => {
+                    process.cancel();
+                    ExportStatus::Cancelled
+                }
+                // A broken pipe means ffmpeg died; its stderr says why.
+                Ok(false) | Err(_) => match (process.finish(), result) {
+                    (Ok(path), Ok(_)) => ExportStatus::Finished(path),
+                    (Err(err), _) | (Ok(_), Err(err)) => ExportStatus::Failed(err),
+                },
+            }
+        }
+        Err(err) => ExportStatus::Failed(err),
+    };
+    let _ = std::fs::remove_file(&audio_path);
+    status
+}
+
+/// Render frames `0..total_frames` in order, reporting progress after each.
+/// Returns `Ok(true)` when cancelled part way.
+fn drive_export(
+    total_frames: u64,
+    fps: f64,
+    handle: &CancelHandle,
+    mut on_progress: impl FnMut(ExportProgress),
+    mut render: impl FnMut(f64) -> Result<(), String>,
+) -> Result<bool, String> {
+    let started = Instant::now();
+    for index in 0..total_frames {
+        if handle.is_cancelled() {
+            return Ok(true);
+        }
+        render(export_frame_time(index, fps))?;
+        on_progress(ExportProgress {
+            frame: index + 1,
+            total_frames,
+            elapsed: started.elapsed(),
+        });
+    }
+    Ok(false)
+}
+
+/// Decode and mix every audible clip into raw little-endian f32 samples,
+/// streamed to `path` block by block.
+fn write_mixdown(
+    project: &Project,
+    project_root: &Path,
+    duration: f64,
+    path: &Path,
+) -> Result<(), String> {
+    let cache = Mutex::new(HashMap::new());
+    let (items, _) = build_playback_items(
+        project,
+        project_root,
+        EXPORT_SAMPLE_RATE,
+        EXPORT_CHANNELS,
+        &cache,
+        true,
+    );
+    let total_frames = (duration.max(0.0) * EXPORT_SAMPLE_RATE as f64).round() as u64;
+    let result = (|| -> std::io::Result<()> {
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+        let mut bytes = Vec::new();
+        render_mixdown_blocks(&items, total_frames, EXPORT_CHANNELS, |block| {
+            bytes.clear();
+            bytes.extend(block.iter().flat_map(|sample| sample.to_le_bytes()));
+            writer.write_all(&bytes)
+        })?;
+        writer.flush()
+    })();
+    result.map_err(|err| format!("Failed to write audio mixdown: {}", err))
+}
+
+/// Inputs (raw video on stdin, mixdown file) followed by the encoder flags.
+fn ffmpeg_input_args(project: &Project, options: &ExportOptions, audio_path: &Path) -> Vec<String> {
+    let settings = &project.settings;
+    let mut args: Vec<String> = vec![
+        "-f".into(),
+        "rawvideo".into(),
+        "-pix_fmt".into(),
+        "rgba".into(),
+        "-s".into(),
+        format!("{}x{}", settings.width.max(1), settings.height.max(1)),
+        "-r".into(),
+        format!("{}", settings.fps.max(1.0)),
+        "-i".into(),
+        "pipe:0".into(),
+        "-f".into(),
+        "f32le".into(),
+        "-ar".into(),
+        EXPORT_SAMPLE_RATE.to_string(),
+        "-ac".into(),
+        EXPORT_CHANNELS.to_string(),
+        "-i".into(),
+        audio_path.to_string_lossy().to_string(),
+        "-map".into(),
+        "0:v".into(),
+        "-map".into(),
+        "1:a".into(),
+    ];
+    args.extend(options.ffmpeg_codec_args());
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::export::ExportProgress;
+
+    #[test]
+    fn test_frame_count_covers_duration() {
+        assert_eq!(export_frame_count(10.0, 30.0), 300);
+        assert_eq!(export_frame_count(1.0 / 60.0, 60.0), 1);
+        // A partial trailing frame is still rendered
+        assert_eq!(export_frame_count(1.01, 24.0), 25);
+        assert_eq!(export_frame_count(0.0, 24.0), 0);
+        assert_eq!(export_frame_count(60.0, 30000.0 / 1001.0), 1799);
+        assert_eq!(export_frame_time(30, 30.0), 1.0);
+    }
+
+    #[test]
+    fn test_progress_sequence() {
+        let handle = CancelHandle::default();
+        let mut reported: Vec<ExportProgress> = Vec::new();
+        let mut rendered = Vec::new();
+        let result = drive_export(
+            4,
+            2.0,
+            &handle,
+            |progress| reported.push(progress),
+            |time| {
+                rendered.push(time);
+                Ok(())
+            },
+        );
+        assert_eq!(result, Ok(false));
+        assert_eq!(rendered, vec![0.0, 0.5, 1.0, 1.5]);
+        let frames: Vec<u64> = reported.iter().map(|progress| progress.frame).collect();
+        assert_eq!(frames, vec![1, 2, 3, 4]);
+        assert!(reported.iter().all(|progress| progress.total_frames == 4));
+        assert_eq!(reported.last().map(|progress| progress.fraction()), Some(1.0));
+    }
+
+    #[test]
+    fn test_cancel_and_render_error_stop_the_export() {
+        let handle = CancelHandle::default();
+        let mut reported = 0;
+        let result = drive_export(
+            10,
+            30.0,
+            &handle,
+            |_| reported += 1,
+            |time| {
+                if time >= 2.0 / 30.0 {
+                    handle.cancel();
+                }
+                Ok(())
+            },
+        );
+        assert_eq!(result, Ok(true));
+        assert_eq!(reported, 3);
+
+        let result = drive_export(
+            10,
+            30.0,
+            &CancelHandle::default(),
+            |_| {},
+            |_| Err("Broken pipe".to_string()),
+        );
+        assert_eq!(result, Err("Broken pipe".to_string()));
+    }
+}
@@ -1,12 +1,18 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
 
+use chrono::{DateTime, Utc};
 use serde_json::Value;
 use uuid::Uuid;
 
 use crate::state::{
-    GenerativeConfig, InputValue, ProviderEntry, ProviderInputField, ProviderInputType,
+    input_value_as_f64, input_value_as_i64, input_value_as_string, AssetKind, BindingTransform, GenerationJob, GenerationJobStatus, GenerationRecord, GenerativeConfig, InputValue, Project, ProviderConnection,
+    ProviderEntry, ProviderInputField, ProviderInputType, ProviderOutputType, SeedStrategy,
 };
 
+/// Most jobs one Generate press queues for a single clip.
+pub const MAX_BATCH_COUNT: u32 = 50;
+
 #[derive(Debug, Clone)]
 pub struct ResolvedInputs {
     pub values: HashMap<String, Value>,
@@ -59,6 +65,37 @@ pub fn next_version_label(config: &GenerativeConfig) -> String {
     format!("v{}", max_version + 1)
 }
 
+/// Records for a run that produced `output_count` outputs: one version each,
+/// numbered consecutively after the config's latest version.
+pub fn output_version_records(
+    config: &GenerativeConfig,
+    output_count: usize,
+    provider_id: Uuid,
+    inputs_snapshot: &HashMap<String, InputValue>,
+    timestamp: DateTime<Utc>,
+) -> Vec<GenerationRecord> {
+    let mut scratch = GenerativeConfig {
+        versions: config.versions.clone(),
+        active_version: config.active_version.clone(),
+        ..GenerativeConfig::default()
+    };
+    let multi = output_count > 1;
+    let mut records = Vec::with_capacity(output_count);
+    for index in 0..output_count {
+        let record = GenerationRecord {
+            version: next_version_label(&scratch),
+            timestamp,
+            provider_id,
+            inputs_snapshot: inputs_snapshot.clone(),
+            output_index: multi.then_some(index as u32),
+            output_count: multi.then_some(output_count as u32),
+        };
+        scratch.versions.push(record.clone());
+        records.push(record);
+    }
+    records
+}
+
 fn literal_input_value(config: &GenerativeConfig, name: &str) -> Option<Value> {
     config.inputs.get(name).and_then(|input| match input {
         InputValue::Literal { value } => Some(value.clone()),
@@ -112,6 +149,41 @@ pub fn update_seed_inputs(
     (values, snapshot)
 }
 
+/// Value of `input` at `unit` (0..1) through its randomization bounds, on a
+/// step boundary when the input has one. `None` when it can't be randomized.
+pub fn sample_random_input(input: &ProviderInputField, unit: f64) -> Option<Value> {
+    let (min, max, step) = input.randomization_bounds()?;
+    let unit = unit.clamp(0.0, 1.0);
+    match input.input_type {
+        ProviderInputType::Integer => {
+            let (low, high) = (min.ceil() as i64, max.floor() as i64);
+            if low > high {
+                return None;
+            }
+            let step = step.map(|step| step.round() as i64).filter(|step| *step > 0).unwrap_or(1);
+            let slots = (high - low) / step + 1;
+            let slot = ((unit * slots as f64) as i64).min(slots - 1);
+            Some(Value::from(low + slot * step))
+        }
+        _ => {
+            let value = match step.filter(|step| *step > 0.0) {
+                Some(step) => {
+                    let slots = ((max - min) / step + 1e-9).floor() as i64 + 1;
+                    let slot = ((unit * slots as f64) as i64).min(slots - 1);
+                    min + slot as f64 * step
+                }
+                None => min + (max - min) * unit,
+            };
+            serde_json::Number::from_f64(value).map(Value::Number)
+        }
+    }
+}
+
+/// Uniform random number in 0..1.
+fn random_unit_f64() -> f64 {
+    (Uuid::new_v4().as_u128() >> 75) as f64 / (1u64 << 53) as f64
+}
+
 /// Generate a random seed suitable for numeric seed inputs.
 pub fn random_seed_i64() -> i64 {
     let raw = Uuid::new_v4().as_u128();
@@ -126,3 +198,1098 @@ fn seed_like(name: &str, label: &str) -> bool {
 fn is_seed_candidate(input: &ProviderInputField) -> bool {
     matches!(input.input_type, ProviderInputType::Integer | ProviderInputType::Number)
 }
+
+/// One clip's batch of jobs, plus how the seed was handled for the status line.
+#[derive(Debug, Clone)]
+pub struct BatchJobs {
+    pub jobs: Vec<GenerationJob>,
+    pub seed_field: Option<String>,
+    /// No seed value was set, so incrementing started from a random one.
+    pub seed_base_randomized: bool,
+}
+
+/// Queued jobs generating `asset_id` (selected as `clip_id`) with `provider`,
+/// following the config's batch count, seed strategy, and randomized inputs. Fails when required
+/// inputs have no value.
+pub fn batch_generation_jobs(
+    provider: &ProviderEntry,
+    config: &GenerativeConfig,
+    asset_id: Uuid,
+    clip_id: Uuid,
+    asset_label: &str,
+    folder_path: &Path,
+) -> Result<BatchJobs, String> {
+    let resolved = resolve_provider_inputs(provider, config);
+    if !resolved.missing_required.is_empty() {
+        return Err(format!("Missing inputs: {}", resolved.missing_required.join(", ")));
+    }
+
+    let batch_count = config.batch.count.clamp(1, MAX_BATCH_COUNT);
+    let seed_strategy = config.batch.seed_strategy.resolved();
+    let seed_field = resolve_seed_field(provider, config.batch.seed_field.as_deref());
+    let mut seed_base = seed_field
+        .as_ref()
+        .and_then(|field| resolved.values.get(field))
+        .and_then(input_value_as_i64);
+    let mut seed_base_randomized = false;
+    if seed_base.is_none() && seed_field.is_some() && seed_strategy == SeedStrategy::Increment {
+        seed_base = Some(random_seed_i64());
+        seed_base_randomized = true;
+    }
+
+    let randomized: Vec<&ProviderInputField> = provider
+        .inputs
+        .iter()
+        .filter(|input| config.batch.randomized_inputs.contains(&input.name))
+        .collect();
+
+    let jobs = (0..batch_count)
+        .map(|index| {
+            let seed = seed_field
+                .as_ref()
+                .zip(seed_strategy.seed_at(seed_base, index, random_seed_i64));
+            let (mut inputs, mut inputs_snapshot) = match seed {
+                Some((field, seed)) => update_seed_inputs(&resolved.values, &resolved.snapshot, field, seed),
+                None => (resolved.values.clone(), resolved.snapshot.clone()),
+            };
+            for input in randomized.iter() {
+                // The seed strategy owns the seed field
+                if seed.is_some_and(|(field, _)| *field == input.name) {
+                    continue;
+                }
+                if let Some(value) = sample_random_input(input, random_unit_f64()) {
+                    inputs.insert(input.name.clone(), value.clone());
+                    inputs_snapshot.insert(input.name.clone(), InputValue::Literal { value });
+                }
+            }
+            queued_job(provider, asset_id, clip_id, asset_label, folder_path, inputs, inputs_snapshot)
+        })
+        .collect();
+
+    Ok(BatchJobs {
+        jobs,
+        seed_field,
+        seed_base_randomized,
+    })
+}
+
+/// What [`regenerate_version_job`] re-runs, and for which clip.
+pub struct RegenerateRequest<'a> {
+    pub provider: &'a ProviderEntry,
+    pub config: &'a GenerativeConfig,
+    pub version: &'a str,
+    /// Inputs to change from the recorded ones, by name.
+    pub edits: &'a HashMap<String, Value>,
+    pub asset_id: Uuid,
+    pub clip_id: Uuid,
+    pub asset_label: &'a str,
+    pub folder_path: &'a Path,
+}
+
+/// One job re-running `version` with the inputs it was generated from, except
+/// for `edits`. The recorded seed is kept unless `edits` changes it, so the
+/// result only differs by the tweak.
+pub fn regenerate_version_job(request: RegenerateRequest) -> Result<GenerationJob, String> {
+    let RegenerateRequest {
+        provider,
+        config,
+        version,
+        edits,
+        asset_id,
+        clip_id,
+        asset_label,
+        folder_path,
+    } = request;
+    let record = config
+        .versions
+        .iter()
+        .find(|record| record.version == version)
+        .ok_or_else(|| format!("Version {} not found.", version))?;
+    let mut base = GenerativeConfig {
+        provider_id: Some(provider.id),
+        inputs: record.inputs_snapshot.clone(),
+        ..GenerativeConfig::default()
+    };
+    for (name, value) in edits {
+        base.inputs.insert(name.clone(), InputValue::Literal { value: value.clone() });
+    }
+    let resolved = resolve_provider_inputs(provider, &base);
+    if !resolved.missing_required.is_empty() {
+        return Err(format!("Missing inputs: {}", resolved.missing_required.join(", ")));
+    }
+
+    let seed = resolve_seed_field(provider, config.batch.seed_field.as_deref()).and_then(|field| {
+        let seed = resolved.values.get(&field).and_then(input_value_as_i64)?;
+        Some((field, seed))
+    });
+    let (inputs, inputs_snapshot) = match seed {
+        Some((field, seed)) => update_seed_inputs(&resolved.values, &resolved.snapshot, &field, seed),
+        None => (resolved.values, resolved.snapshot),
+    };
+    Ok(queued_job(provider, asset_id, clip_id, asset_label, folder_path, inputs, inputs_snapshot))
+}
+
+fn queued_job(
+    provider: &ProviderEntry,
+    asset_id: Uuid,
+    clip_id: Uuid,
+    asset_label: &str,
+    folder_path: &Path,
+    inputs: HashMap<String, Value>,
+    inputs_snapshot: HashMap<String, InputValue>,
+) -> GenerationJob {
+    GenerationJob {
+        id: Uuid::new_v4(),
+        created_at: Utc::now(),
+        status: GenerationJobStatus::Queued,
+        progress_overall: None,
+        progress_node: None,
+        attempts: 0,
+        next_attempt_at: None,
+        provider: provider.clone(),
+        output_type: provider.output_type,
+        asset_id,
+        clip_id,
+        batch_id: None,
+        asset_label: asset_label.to_string(),
+        folder_path: folder_path.to_path_buf(),
+        inputs,
+        inputs_snapshot,
+        version: None,
+        error: None,
+    }
+}
+
+/// A selected clip that a multi-clip generation left out.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkippedClip {
+    pub clip_id: Uuid,
+    pub label: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GenerationFanOut {
+    pub jobs: Vec<GenerationJob>,
+    pub skipped: Vec<SkippedClip>,
+}
+
+/// Jobs for every generative clip in `clip_ids`, each using its own config,
+/// provider, batch count, and seed strategy. All jobs share one `batch_id` so
+/// the queue can show their combined progress. Non-generative clips are
+/// ignored; clips without a usable provider or with missing inputs are
+/// reported in `skipped`. Clips of an asset already covered by an earlier
+/// clip are left out since they would write into the same folder.
+pub fn fan_out_generation_jobs(
+    project: &Project,
+    clip_ids: &[Uuid],
+    providers: &[ProviderEntry],
+) -> GenerationFanOut {
+    let batch_id = Uuid::new_v4();
+    let mut fan_out = GenerationFanOut::default();
+    let mut seen_assets = HashSet::new();
+    for &clip_id in clip_ids {
+        let Some(clip) = project.clips.iter().find(|clip| clip.id == clip_id) else {
+            continue;
+        };
+        let Some(asset) = project.find_asset(clip.asset_id) else {
+            continue;
+        };
+        let Some((folder, output_type)) = generative_target(&asset.kind) else {
+            continue;
+        };
+        if !seen_assets.insert(asset.id) {
+            continue;
+        }
+        let mut skip = |reason: &str| {
+            fan_out.skipped.push(SkippedClip {
+                clip_id,
+                label: asset.name.clone(),
+                reason: reason.to_string(),
+            });
+        };
+        let Some(root) = project.project_path.as_ref() else {
+            skip("Project is not saved");
+            continue;
+        };
+        let config = project.generative_config(asset.id).cloned().unwrap_or_default();
+        let Some(provider_id) = config.provider_id else {
+            skip("No provider selected");
+            continue;
+        };
+        let Some(provider) = providers
+            .iter()
+            .find(|provider| provider.id == provider_id && provider.output_type == output_type)
+        else {
+            skip("Provider not found");
+            continue;
+        };
+        match batch_generation_jobs(provider, &config, asset.id, clip_id, &asset.name, &root.join(folder)) {
+            Ok(batch) => fan_out.jobs.extend(batch.jobs.into_iter().map(|mut job| {
+                job.batch_id = Some(batch_id);
+                job
+            })),
+            Err(err) => skip(&err),
+        }
+    }
+    fan_out
+}
+
+/// Folder and output type of a generative asset; `None` for imported media.
+pub fn generative_target(kind: &AssetKind) -> Option<(&Path, ProviderOutputType)> {
+    match kind {
+        AssetKind::GenerativeVideo { folder, .. } => Some((folder, ProviderOutputType::Video)),
+        AssetKind::GenerativeImage { folder, .. } => Some((folder, ProviderOutputType::Image)),
+        AssetKind::GenerativeAudio { folder, .. } => Some((folder, ProviderOutputType::Audio)),
+        _ => None,
+    }
+}
+
+/// Combined state of jobs queued together from a multi-clip generation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchProgress {
+    pub batch_id: Uuid,
+    pub total: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    /// Finished jobs plus the running ones' progress, over the total.
+    pub fraction: f32,
+}
+
+/// Progress of each batch with more than one job in `jobs`, in queue order.
+pub fn batch_progress(jobs: &[GenerationJob]) -> Vec<BatchProgress> {
+    let mut batches: Vec<BatchProgress> = Vec::new();
+    let mut done: HashMap<Uuid, f32> = HashMap::new();
+    for job in jobs {
+        let Some(batch_id) = job.batch_id else {
+            continue;
+        };
+        let index = match batches.iter().position(|batch| batch.batch_id == batch_id) {
+            Some(index) => index,
+            None => {
+                batches.push(BatchProgress {
+                    batch_id,
+                    total: 0,
+                    succeeded: 0,
+                    failed: 0,
+                    fraction: 0.0,
+                });
+                batches.len() - 1
+            }
+        };
+        let batch = &mut batches[index];
+        batch.total += 1;
+        let progress = match job.status {
+            GenerationJobStatus::Succeeded => {
+                batch.succeeded += 1;
+                1.0
+            }
+            GenerationJobStatus::Failed => {
+                batch.failed += 1;
+                1.0
+            }
+            GenerationJobStatus::Running => job.progress_overall.unwrap_or(0.0).clamp(0.0, 1.0),
+            GenerationJobStatus::Queued => 0.0,
+        };
+        *done.entry(batch_id).or_insert(0.0) += progress;
+    }
+    batches.retain(|batch| batch.total > 1);
+    for batch in batches.iter_mut() {
+        batch.fraction = done.get(&batch.batch_id).copied().unwrap_or(0.0) / batch.total as f32;
+    }
+    batches
+}
+
+/// Longest wait between automatic retries of a failed generation.
+pub const GENERATION_RETRY_MAX_DELAY_SECS: i64 = 60;
+
+/// Wait before retrying after the `failed_attempts`-th failure: 2, 4, 8, ...
+/// seconds, capped at `GENERATION_RETRY_MAX_DELAY_SECS`.
+pub fn generation_retry_delay_secs(failed_attempts: u8) -> i64 {
+    let exponent = failed_attempts.clamp(1, 16) as u32;
+    (1_i64 << exponent).min(GENERATION_RETRY_MAX_DELAY_SECS)
+}
+
+/// A failed generation, tagged by the provider with whether retrying could
+/// succeed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenerationError {
+    pub message: String,
+    /// Network failures, timeouts and 5xx/408/429 responses. Anything else
+    /// (bad workflow, missing nodes, other 4xx) will fail the same way again.
+    pub transient: bool,
+}
+
+impl GenerationError {
+    pub fn permanent(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            transient: false,
+        }
+    }
+
+    pub fn transient(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            transient: true,
+        }
+    }
+
+    /// Classify an error response by its HTTP status code.
+    pub fn from_status(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            transient: is_transient_status(status),
+        }
+    }
+}
+
+impl From<String> for GenerationError {
+    fn from(message: String) -> Self {
+        Self::permanent(message)
+    }
+}
+
+impl std::fmt::Display for GenerationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Server errors, request timeouts and rate limiting are worth retrying.
+pub fn is_transient_status(status: u16) -> bool {
+    (500..600).contains(&status) || status == 408 || status == 429
+}
+
+/// Apply a manifest binding transform to an input value before it is written
+/// into the workflow node.
+pub fn apply_binding_transform(
+    value: &Value,
+    transform: Option<&BindingTransform>,
+) -> Result<Value, String> {
+    match transform {
+        Some(transform) => eval_transform(value.clone(), transform),
+        None => Ok(value.clone()),
+    }
+}
+
+fn eval_transform(value: Value, transform: &BindingTransform) -> Result<Value, String> {
+    let number = |value: &Value| {
+        input_value_as_f64(value)
+            .or_else(|| value.as_str().and_then(|text| text.trim().parse::<f64>().ok()))
+            .ok_or_else(|| format!("Expected a number for transform, got {}", value))
+    };
+    match transform {
+        BindingTransform::Clamp { min, max } => number_value(number(&value)?.max(*min).min(*max)),
+        BindingTransform::Multiply { factor } => number_value(number(&value)? * factor),
+        BindingTransform::Offset { amount } => number_value(number(&value)? + amount),
+        BindingTransform::Round => {
+            let rounded = number(&value)?.round();
+            if rounded.is_finite() && rounded.abs() < i64::MAX as f64 {
+                Ok(Value::Number((rounded as i64).into()))
+            } else {
+                Err("Rounded value is out of range.".to_string())
+            }
+        }
+        BindingTransform::Format { template } => {
+            let text = input_value_as_string(&value).unwrap_or_else(|| value.to_string());
+            Ok(Value::String(template.replace("{value}", &text)))
+        }
+        BindingTransform::JsonPointer { pointer } => {
+            let document = match &value {
+                Value::String(text) => serde_json::from_str(text)
+                    .map_err(|err| format!("Input is not JSON: {}", err))?,
+                other => other.clone(),
+            };
+            document
+                .pointer(pointer)
+                .cloned()
+                .ok_or_else(|| format!("JSON pointer {} not found in input.", pointer))
+        }
+        BindingTransform::Chain { steps } => steps
+            .iter()
+            .try_fold(value, |value, step| eval_transform(value, step)),
+    }
+}
+
+fn number_value(number: f64) -> Result<Value, String> {
+    serde_json::Number::from_f64(number)
+        .map(Value::Number)
+        .ok_or_else(|| "Transformed value is not a valid number.".to_string())
+}
+
+/// Parse the builder's transform shorthand: steps separated by `|`, each one of
+/// `* 24`, `+ 1`, `- 1`, `/ 2`, `round`, `clamp 0, 1`, `format frame_{value}`,
+/// or `pointer /a/0`. Blank text means no transform.
+pub fn parse_transform_expression(text: &str) -> Result<Option<BindingTransform>, String> {
+    let mut steps = Vec::new();
+    for step in text.split('|').map(str::trim).filter(|step| !step.is_empty()) {
+        steps.push(parse_transform_step(step)?);
+    }
+    Ok(match steps.len() {
+        0 => None,
+        1 => steps.pop(),
+        _ => Some(BindingTransform::Chain { steps }),
+    })
+}
+
+fn parse_transform_step(step: &str) -> Result<BindingTransform, String> {
+    let number = |text: &str| {
+        text.trim()
+            .parse::<f64>()
+            .map_err(|_| format!("Transform '{}' needs a number.", step))
+    };
+    let (head, rest) = match step.char_indices().next() {
+        Some((_, op @ ('*' | '+' | '-' | '/'))) => (op.to_string(), &step[1..]),
+        _ => match step.split_once(char::is_whitespace) {
+            Some((word, rest)) => (word.to_ascii_lowercase(), rest),
+            None => (step.to_ascii_lowercase(), ""),
+        },
+    };
+    match head.as_str() {
+        "*" => Ok(BindingTransform::Multiply { factor: number(rest)? }),
+        "/" => {
+            let divisor = number(rest)?;
+            if divisor == 0.0 {
+                return Err("Cannot divide by zero.".to_string());
+            }
+            Ok(BindingTransform::Multiply { factor: 1.0 / divisor })
+        }
+        "+" => Ok(BindingTransform::Offset { amount: number(rest)? }),
+        "-" => Ok(BindingTransform::Offset { amount: -number(rest)? }),
+        "round" => Ok(BindingTransform::Round),
+        "clamp" => {
+            let (min, max) = rest
+                .split_once(',')
+                .ok_or_else(|| "Clamp needs 'min, max'.".to_string())?;
+            Ok(BindingTransform::Clamp { min: number(min)?, max: number(max)? })
+        }
+        "format" => Ok(BindingTransform::Format { template: rest.trim().to_string() }),
+        "pointer" => Ok(BindingTransform::JsonPointer { pointer: rest.trim().to_string() }),
+        _ => Err(format!("Unknown transform '{}'.", step)),
+    }
+}
+
+/// Inverse of `parse_transform_expression`, for showing a saved transform.
+pub fn transform_expression(transform: &BindingTransform) -> String {
+    match transform {
+        BindingTransform::Clamp { min, max } => format!("clamp {}, {}", min, max),
+        BindingTransform::Multiply { factor } => format!("* {}", factor),
+        BindingTransform::Offset { amount } if *amount < 0.0 => format!("- {}", -amount),
+        BindingTransform::Offset { amount } => format!("+ {}", amount),
+        BindingTransform::Round => "round".to_string(),
+        BindingTransform::Format { template } => format!("format {}", template),
+        BindingTransform::JsonPointer { pointer } => format!("pointer {}", pointer),
+        BindingTransform::Chain { steps } => steps
+            .iter()
+            .map(transform_expression)
+            .collect::<Vec<_>>()
+            .join(" | "),
+    }
+}
+
+/// Machine a provider sends work to (`scheme://host:port`). Providers that
+/// share one are throttled together.
+pub fn provider_endpoint_key(connection: &ProviderConnection) -> String {
+    let url = match connection {
+        ProviderConnection::ComfyUi { base_url, .. }
+        | ProviderConnection::CustomHttp { base_url, .. } => base_url.as_str(),
+        ProviderConnection::Http(config) => config.request.url.as_str(),
+    };
+    let url = url.trim().to_ascii_lowercase();
+    match url.split_once("://") {
+        Some((scheme, rest)) => {
+            let host = rest.split(['/', '?', '#']).next().unwrap_or(rest);
+            format!("{}://{}", scheme, host)
+        }
+        None => url.trim_end_matches('/').to_string(),
+    }
+}
+
+/// Queued jobs to start now, in queue order. Jobs waiting on a retry are
+/// skipped; at most `max_running` jobs run in total, and each endpoint runs
+/// at most its provider's `max_concurrent` (one when unset).
+pub fn jobs_to_start(jobs: &[GenerationJob], now: DateTime<Utc>, max_running: usize) -> Vec<Uuid> {
+    let mut running_total = 0;
+    let mut running_per_endpoint: HashMap<String, usize> = HashMap::new();
+    for job in jobs.iter().filter(|job| job.status == GenerationJobStatus::Running) {
+        running_total += 1;
+        *running_per_endpoint
+            .entry(provider_endpoint_key(&job.provider.connection))
+            .or_insert(0) += 1;
+    }
+
+    let mut start = Vec::new();
+    for job in jobs.iter().filter(|job| job.status == GenerationJobStatus::Queued) {
+        if running_total >= max_running {
+            break;
+        }
+        if job.next_attempt_at.is_some_and(|next_at| next_at > now) {
+            continue;
+        }
+        let limit = job.provider.max_concurrent.unwrap_or(1).max(1) as usize;
+        let running = running_per_endpoint
+            .entry(provider_endpoint_key(&job.provider.connection))
+            .or_insert(0);
+        if *running >= limit {
+            continue;
+        }
+        *running += 1;
+        running_total += 1;
+        start.push(job.id);
+    }
+    start
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_version_records_fan_out() {
+        let provider_id = Uuid::new_v4();
+        let now = Utc::now();
+        let mut config = GenerativeConfig::default();
+        config.versions = output_version_records(&config, 1, provider_id, &HashMap::new(), now);
+        assert_eq!(config.versions[0].version, "v1");
+        assert_eq!(config.versions[0].output_index, None);
+        assert_eq!(config.versions[0].output_count, None);
+
+        config.active_version = Some("v4".to_string());
+        let records = output_version_records(&config, 3, provider_id, &HashMap::new(), now);
+        let versions: Vec<&str> = records.iter().map(|record| record.version.as_str()).collect();
+        assert_eq!(versions, vec!["v5", "v6", "v7"]);
+        let indices: Vec<Option<u32>> = records.iter().map(|record| record.output_index).collect();
+        assert_eq!(indices, vec![Some(0), Some(1), Some(2)]);
+        assert!(records.iter().all(|record| record.output_count == Some(3)));
+        assert!(output_version_records(&config, 0, provider_id, &HashMap::new(), now).is_empty());
+    }
+
+    fn seeded_provider(output_type: ProviderOutputType) -> ProviderEntry {
+        let mut provider = ProviderEntry::new(
+            "Seeded",
+            output_type,
+            ProviderConnection::ComfyUi {
+                base_url: "http://127.0.0.1:8188".to_string(),
+                workflow_path: None,
+                manifest_path: None,
+            },
+        );
+        provider.inputs = vec![ProviderInputField {
+            name: "seed".to_string(),
+            label: "Seed".to_string(),
+            input_type: ProviderInputType::Integer,
+            required: false,
+            default: None,
+            ui: None,
+            random_range: None,
+        }];
+        provider
+    }
+
+    fn generative_clip(
+        project: &mut Project,
+        asset: crate::state::Asset,
+        config: Option<GenerativeConfig>,
+    ) -> (Uuid, Uuid) {
+        let asset_id = project.add_asset(asset);
+        if let Some(config) = config {
+            project.generative_configs.insert(asset_id, config);
+        }
+        let clip = crate::state::Clip::new(asset_id, Uuid::new_v4(), 0.0, 1.0);
+        let clip_id = clip.id;
+        project.clips.push(clip);
+        (asset_id, clip_id)
+    }
+
+    fn batch_config(provider_id: Uuid, count: u32, seed: Option<i64>) -> GenerativeConfig {
+        let mut config = GenerativeConfig {
+            provider_id: Some(provider_id),
+            ..GenerativeConfig::default()
+        };
+        config.batch.count = count;
+        config.batch.seed_strategy = SeedStrategy::Increment;
+        if let Some(seed) = seed {
+            config.inputs.insert(
+                "seed".to_string(),
+                InputValue::Literal { value: Value::from(seed) },
+            );
+        }
+        config
+    }
+
+    #[test]
+    fn test_fan_out_queues_each_clip_with_its_own_batch() {
+        use crate::state::Asset;
+        let image_provider = seeded_provider(ProviderOutputType::Image);
+        let video_provider = seeded_provider(ProviderOutputType::Video);
+        let mut project = Project::new("Fan Out");
+        project.project_path = Some(std::path::PathBuf::from("project"));
+
+        let (image_asset, image_clip) = generative_clip(
+            &mut project,
+            Asset::new_generative_image("Still", std::path::PathBuf::from("generated/image/still")),
+            Some(batch_config(image_provider.id, 3, Some(10))),
+        );
+        let (video_asset, video_clip) = generative_clip(
+            &mut project,
+            Asset::new_generative_video("Shot", std::path::PathBuf::from("generated/video/shot"), 24.0, 48),
+            Some(batch_config(video_provider.id, 2, Some(0))),
+        );
+        let (_, orphan_clip) = generative_clip(
+            &mut project,
+            Asset::new_generative_image("Orphan", std::path::PathBuf::from("generated/image/orphan")),
+            Some(batch_config(Uuid::new_v4(), 4, None)),
+        );
+        let (_, unset_clip) = generative_clip(
+            &mut project,
+            Asset::new_generative_image("Unset", std::path::PathBuf::from("generated/image/unset")),
+            None,
+        );
+        let (_, plain_clip) = generative_clip(
+            &mut project,
+            Asset::new_video("Plain", std::path::PathBuf::from("video/plain.mp4")),
+            None,
+        );
+        // A second clip of the same asset would write into the same folder
+        let repeat_clip = crate::state::Clip::new(image_asset, Uuid::new_v4(), 2.0, 1.0);
+        let repeat_clip_id = repeat_clip.id;
+        project.clips.push(repeat_clip);
+
+        let providers = vec![image_provider.clone(), video_provider.clone()];
+        let fan_out = fan_out_generation_jobs(
+            &project,
+            &[image_clip, orphan_clip, video_clip, plain_clip, unset_clip, repeat_clip_id],
+            &providers,
+        );
+
+        let pairs: Vec<(Uuid, Uuid)> = fan_out.jobs.iter().map(|job| (job.asset_id, job.clip_id)).collect();
+        assert_eq!(
+            pairs,
+            vec![
+                (image_asset, image_clip),
+                (image_asset, image_clip),
+                (image_asset, image_clip),
+                (video_asset, video_clip),
+                (video_asset, video_clip),
+            ]
+        );
+        // Each clip keeps its own provider, folder, and seed sequence
+        assert!(fan_out.jobs[..3].iter().all(|job| job.provider.id == image_provider.id));
+        assert!(fan_out.jobs[3..].iter().all(|job| job.provider.id == video_provider.id));
+        assert_eq!(
+            fan_out.jobs[0].folder_path,
+            std::path::Path::new("project").join("generated/image/still")
+        );
+        let seeds: Vec<Value> = fan_out.jobs.iter().map(|job| job.inputs["seed"].clone()).collect();
+        assert_eq!(seeds, vec![Value::from(10), Value::from(11), Value::from(12), Value::from(0), Value::from(1)]);
+        // All jobs share one batch
+        let batch_id = fan_out.jobs[0].batch_id;
+        assert!(batch_id.is_some());
+        assert!(fan_out.jobs.iter().all(|job| job.batch_id == batch_id));
+
+        // Missing providers are reported and the rest still queue
+        let skipped: Vec<Uuid> = fan_out.skipped.iter().map(|skip| skip.clip_id).collect();
+        assert_eq!(skipped, vec![orphan_clip, unset_clip]);
+        assert_eq!(fan_out.skipped[0].reason, "Provider not found");
+        assert_eq!(fan_out.skipped[1].reason, "No provider selected");
+    }
+
+    #[test]
+    fn test_batch_progress_combines_jobs_of_a_batch() {
+        let batch_id = Uuid::new_v4();
+        let mut jobs: Vec<GenerationJob> = [
+            GenerationJobStatus::Succeeded,
+            GenerationJobStatus::Failed,
+            GenerationJobStatus::Running,
+            GenerationJobStatus::Queued,
+        ]
+        .into_iter()
+        .map(|status| {
+            let mut job = job("http://a:8188", status, None);
+            job.batch_id = Some(batch_id);
+            job
+        })
+        .collect();
+        jobs[2].progress_overall = Some(0.5);
+        // Single-clip jobs aren't grouped
+        jobs.push(job("http://a:8188", GenerationJobStatus::Running, None));
+
+        let batches = batch_progress(&jobs);
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].total, 4);
+        assert_eq!((batches[0].succeeded, batches[0].failed), (1, 1));
+        assert!((batches[0].fraction - 2.5 / 4.0).abs() < 1e-6);
+    }
+
+    fn batch_seeds(config: &GenerativeConfig, provider: &ProviderEntry) -> Vec<Value> {
+        let batch = batch_generation_jobs(
+            provider,
+            config,
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            "Seeded",
+            std::path::Path::new("generated"),
+        )
+        .unwrap();
+        batch.jobs.iter().map(|job| job.inputs["seed"].clone()).collect()
+    }
+
+    #[test]
+    fn test_fixed_seed_list_cycles_across_a_longer_batch() {
+        let provider = seeded_provider(ProviderOutputType::Image);
+        let mut config = batch_config(provider.id, 5, Some(100));
+        config.batch.seed_strategy = SeedStrategy::FixedList(vec![7, -3]);
+        assert_eq!(
+            batch_seeds(&config, &provider),
+            vec![Value::from(7), Value::from(-3), Value::from(7), Value::from(-3), Value::from(7)]
+        );
+
+        // The list survives a save and reload of the config
+        let json = serde_json::to_string(&config.batch).unwrap();
+        let reloaded: crate::state::BatchSettings = serde_json::from_str(&json).unwrap();
+        assert_eq!(reloaded.seed_strategy, SeedStrategy::FixedList(vec![7, -3]));
+        let legacy: crate::state::BatchSettings =
+            serde_json::from_str(r#"{"count": 2, "seed_strategy": "random"}"#).unwrap();
+        assert_eq!(legacy.seed_strategy, SeedStrategy::Random);
+    }
+
+    fn numeric_input(
+        name: &str,
+        input_type: ProviderInputType,
+        ui_range: Option<(f64, f64, f64)>,
+        random_range: Option<(f64, f64)>,
+    ) -> ProviderInputField {
+        ProviderInputField {
+            name: name.to_string(),
+            label: name.to_string(),
+            input_type,
+            required: false,
+            default: None,
+            ui: ui_range.map(|(min, max, step)| crate::state::InputUi {
+                min: Some(min),
+                max: Some(max),
+                step: Some(step),
+                placeholder: None,
+                multiline: false,
+                group: None,
+                advanced: false,
+                unit: None,
+            }),
+            random_range: random_range.map(|(min, max)| crate::state::RandomRange {
+                min: Some(min),
+                max: Some(max),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_randomized_inputs_vary_within_range_per_job() {
+        let mut provider = seeded_provider(ProviderOutputType::Image);
+        provider.inputs.extend([
+            numeric_input("cfg", ProviderInputType::Number, Some((4.0, 9.0, 0.5)), None),
+            // The batch range narrows the slider range
+            numeric_input("steps", ProviderInputType::Integer, Some((1.0, 100.0, 1.0)), Some((20.0, 30.0))),
+            numeric_input("denoise", ProviderInputType::Number, None, Some((0.3, 0.7))),
+            numeric_input("width", ProviderInputType::Integer, Some((64.0, 2048.0, 64.0)), None),
+        ]);
+        let mut config = batch_config(provider.id, 20, Some(5));
+        config.batch.randomized_inputs = vec!["cfg".to_string(), "steps".to_string(), "denoise".to_string()];
+        config.inputs.insert(
+            "width".to_string(),
+            InputValue::Literal { value: Value::from(512) },
+        );
+        let batch = batch_generation_jobs(
+            &provider,
+            &config,
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            "Random",
+            std::path::Path::new("generated"),
+        )
+        .unwrap();
+
+        let values = |name: &str| -> Vec<f64> {
+            batch.jobs.iter().map(|job| job.inputs[name].as_f64().unwrap()).collect()
+        };
+        let cfg = values("cfg");
+        assert!(cfg.iter().all(|value| (4.0..=9.0).contains(value) && (value * 2.0).fract() == 0.0), "{:?}", cfg);
+        let steps = values("steps");
+        assert!(steps.iter().all(|value| (20.0..=30.0).contains(value) && value.fract() == 0.0), "{:?}", steps);
+        let denoise = values("denoise");
+        assert!(denoise.iter().all(|value| (0.3..=0.7).contains(value)), "{:?}", denoise);
+        for sampled in [&cfg, &steps, &denoise] {
+            assert!(sampled.iter().any(|value| *value != sampled[0]), "{:?}", sampled);
+        }
+        // Sampled values are recorded in the snapshot used for the version record
+        assert!(batch.jobs.iter().all(|job| matches!(
+            &job.inputs_snapshot["cfg"],
+            InputValue::Literal { value } if *value == job.inputs["cfg"]
+        )));
+
+        // Unflagged inputs and the seed sequence are untouched
+        assert!(values("width").iter().all(|value| *value == 512.0));
+        assert_eq!(values("seed"), (5..25).map(|seed| seed as f64).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_regenerate_version_keeps_snapshot_except_edit() {
+        let mut provider = seeded_provider(ProviderOutputType::Image);
+        provider.inputs.extend([
+            numeric_input("cfg", ProviderInputType::Number, None, None),
+            ProviderInputField {
+                name: "prompt".to_string(),
+                label: "Prompt".to_string(),
+                input_type: ProviderInputType::Text,
+                required: true,
+                default: None,
+                ui: None,
+                random_range: None,
+            },
+        ]);
+        let snapshot: HashMap<String, InputValue> = [
+            ("seed", Value::from(1234)),
+            ("cfg", Value::from(5.0)),
+            ("prompt", Value::from("a lighthouse at dusk")),
+        ]
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), InputValue::Literal { value }))
+        .collect();
+        // The current inputs and seed strategy have moved on since v2
+        let mut config = batch_config(provider.id, 4, Some(99));
+        config.batch.seed_strategy = SeedStrategy::Random;
+        config.inputs.insert(
+            "prompt".to_string(),
+            InputValue::Literal { value: Value::from("something else") },
+        );
+        config.versions = output_version_records(&config, 2, provider.id, &snapshot, Utc::now());
+        let regenerate = |edits: &[(&str, Value)]| {
+            let edits: HashMap<String, Value> =
+                edits.iter().map(|(name, value)| (name.to_string(), value.clone())).collect();
+            regenerate_version_job(RegenerateRequest {
+                provider: &provider,
+                config: &config,
+                version: "v2",
+                edits: &edits,
+                asset_id: Uuid::new_v4(),
+                clip_id: Uuid::new_v4(),
+                asset_label: "Lighthouse",
+                folder_path: std::path::Path::new("generated"),
+            })
+        };
+
+        let job = regenerate(&[("cfg", Value::from(6.5))]).unwrap();
+        let mut expected = snapshot.clone();
+        expected.insert("cfg".to_string(), InputValue::Literal { value: Value::from(6.5) });
+        assert_eq!(job.inputs_snapshot, expected);
+        assert_eq!(job.inputs["seed"], Value::from(1234));
+        assert_eq!(job.inputs["prompt"], Value::from("a lighthouse at dusk"));
+        assert_eq!(job.inputs["cfg"], Value::from(6.5));
+        assert_eq!(job.batch_id, None);
+
+        // Editing the seed itself is honoured
+        let job = regenerate(&[("seed", Value::from(7))]).unwrap();
+        assert_eq!(job.inputs["seed"], Value::from(7));
+        assert_eq!(job.inputs["cfg"], Value::from(5.0));
+
+        assert!(regenerate_version_job(RegenerateRequest {
+            provider: &provider,
+            config: &config,
+            version: "v9",
+            edits: &HashMap::new(),
+            asset_id: Uuid::new_v4(),
+            clip_id: Uuid::new_v4(),
+            asset_label: "Lighthouse",
+            folder_path: std::path::Path::new("generated"),
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_sample_random_input_stays_on_bounds_and_steps() {
+        let cfg = numeric_input("cfg", ProviderInputType::Number, Some((4.0, 9.0, 0.5)), None);
+        assert_eq!(sample_random_input(&cfg, 0.0), Some(Value::from(4.0)));
+        assert_eq!(sample_random_input(&cfg, 0.999_999), Some(Value::from(9.0)));
+        let width = numeric_input("width", ProviderInputType::Integer, Some((64.0, 2048.0, 64.0)), None);
+        assert_eq!(sample_random_input(&width, 0.5), Some(Value::from(1088)));
+        // Unbounded and non-numeric inputs aren't randomized
+        assert_eq!(sample_random_input(&numeric_input("x", ProviderInputType::Number, None, None), 0.5), None);
+        assert_eq!(sample_random_input(&numeric_input("x", ProviderInputType::Text, None, Some((0.0, 1.0))), 0.5), None);
+    }
+
+    #[test]
+    fn test_empty_fixed_seed_list_falls_back_to_increment() {
+        let provider = seeded_provider(ProviderOutputType::Image);
+        let mut config = batch_config(provider.id, 3, Some(40));
+        config.batch.seed_strategy = SeedStrategy::FixedList(Vec::new());
+        assert_eq!(
+            batch_seeds(&config, &provider),
+            vec![Value::from(40), Value::from(41), Value::from(42)]
+        );
+        assert_eq!(crate::state::parse_seed_list("1, 2,x 3\n-4"), vec![1, 2, 3, -4]);
+        assert_eq!(crate::state::format_seed_list(&[1, 2]), "1, 2");
+    }
+
+    fn job(base_url: &str, status: GenerationJobStatus, max_concurrent: Option<u32>) -> GenerationJob {
+        let mut provider = ProviderEntry::new(
+            "Test",
+            crate::state::ProviderOutputType::Image,
+            ProviderConnection::ComfyUi {
+                base_url: base_url.to_string(),
+                workflow_path: None,
+                manifest_path: None,
+            },
+        );
+        provider.max_concurrent = max_concurrent;
+        GenerationJob {
+            id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            status,
+            progress_overall: None,
+            progress_node: None,
+            attempts: 0,
+            next_attempt_at: None,
+            provider,
+            output_type: crate::state::ProviderOutputType::Image,
+            asset_id: Uuid::new_v4(),
+            clip_id: Uuid::new_v4(),
+            batch_id: None,
+            asset_label: "Gen".to_string(),
+            folder_path: std::path::PathBuf::new(),
+            inputs: HashMap::new(),
+            inputs_snapshot: HashMap::new(),
+            version: None,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_scheduler_respects_global_limit() {
+        use GenerationJobStatus::*;
+        let now = Utc::now();
+        let jobs = vec![
+            job("http://a:8188", Running, Some(4)),
+            job("http://a:8188", Queued, Some(4)),
+            job("http://a:8188", Queued, Some(4)),
+            job("http://a:8188", Queued, Some(4)),
+        ];
+        assert_eq!(jobs_to_start(&jobs, now, 1), Vec::<Uuid>::new());
+        assert_eq!(jobs_to_start(&jobs, now, 3), vec![jobs[1].id, jobs[2].id]);
+
+        // A job waiting on a retry is passed over, not blocking the rest
+        let mut jobs = jobs;
+        jobs[1].next_attempt_at = Some(now + chrono::Duration::seconds(10));
+        assert_eq!(jobs_to_start(&jobs, now, 3), vec![jobs[2].id, jobs[3].id]);
+        jobs[1].next_attempt_at = Some(now - chrono::Duration::seconds(1));
+        assert_eq!(jobs_to_start(&jobs, now, 2), vec![jobs[1].id]);
+    }
+
+    #[test]
+    fn test_scheduler_groups_by_endpoint() {
+        use GenerationJobStatus::*;
+        let now = Utc::now();
+        let jobs = vec![
+            job("http://gpu-a:8188", Running, None),
+            job("http://GPU-A:8188/", Queued, None),
+            job("http://gpu-b:8188", Queued, None),
+            job("http://gpu-b:8188", Queued, None),
+            job("http://gpu-c:8188", Queued, Some(2)),
+            job("http://gpu-c:8188", Queued, Some(2)),
+        ];
+        // gpu-a is busy, gpu-b takes one, gpu-c takes two
+        assert_eq!(
+            jobs_to_start(&jobs, now, 8),
+            vec![jobs[2].id, jobs[4].id, jobs[5].id]
+        );
+        assert_eq!(jobs_to_start(&jobs, now, 2), vec![jobs[2].id]);
+        assert_eq!(
+            provider_endpoint_key(&jobs[1].provider.connection),
+            "http://gpu-a:8188"
+        );
+    }
+
+    #[test]
+    fn test_binding_transforms() {
+        use serde_json::json;
+        let apply = |value: Value, transform: BindingTransform| {
+            apply_binding_transform(&value, Some(&transform))
+        };
+        assert_eq!(apply(json!(2.5), BindingTransform::Multiply { factor: 4.0 }), Ok(json!(10.0)));
+        assert_eq!(apply(json!(10), BindingTransform::Offset { amount: -3.0 }), Ok(json!(7.0)));
+        assert_eq!(apply(json!("7"), BindingTransform::Clamp { min: 0.0, max: 5.0 }), Ok(json!(5.0)));
+        assert_eq!(apply(json!(2.5), BindingTransform::Round), Ok(json!(3)));
+        assert_eq!(
+            apply(json!(12), BindingTransform::Format { template: "frame_{value}.png".to_string() }),
+            Ok(json!("frame_12.png"))
+        );
+        assert_eq!(
+            apply(
+                json!("{\"size\": {\"w\": 512}}"),
+                BindingTransform::JsonPointer { pointer: "/size/w".to_string() }
+            ),
+            Ok(json!(512))
+        );
+        assert!(apply(json!({"a": 1}), BindingTransform::JsonPointer { pointer: "/b".to_string() }).is_err());
+        assert!(apply(json!("abc"), BindingTransform::Multiply { factor: 2.0 }).is_err());
+        assert_eq!(apply_binding_transform(&json!("as is"), None), Ok(json!("as is")));
+
+        // Duration in seconds fed to a node that wants a frame count
+        let seconds_to_frames = BindingTransform::Chain {
+            steps: vec![BindingTransform::Multiply { factor: 24.0 }, BindingTransform::Round],
+        };
+        assert_eq!(apply(json!(2.51), seconds_to_frames.clone()), Ok(json!(60)));
+
+        let legacy: BindingTransform =
+            serde_json::from_value(json!({ "type": "scale", "factor": 2.0 })).unwrap();
+        assert_eq!(legacy, BindingTransform::Multiply { factor: 2.0 });
+    }
+
+    #[test]
+    fn test_transform_expression_round_trip() {
+        let parsed = parse_transform_expression("* 24 | round").unwrap();
+        assert_eq!(
+            parsed,
+            Some(BindingTransform::Chain {
+                steps: vec![BindingTransform::Multiply { factor: 24.0 }, BindingTransform::Round],
+            })
+        );
+        assert_eq!(transform_expression(parsed.as_ref().unwrap()), "* 24 | round");
+        assert_eq!(
+            parse_transform_expression("- 1").unwrap(),
+            Some(BindingTransform::Offset { amount: -1.0 })
+        );
+        assert_eq!(
+            parse_transform_expression("Clamp 0, 1.5").unwrap(),
+            Some(BindingTransform::Clamp { min: 0.0, max: 1.5 })
+        );
+        assert_eq!(
+            parse_transform_expression("format clip {value}").unwrap(),
+            Some(BindingTransform::Format { template: "clip {value}".to_string() })
+        );
+        assert_eq!(parse_transform_expression("  ").unwrap(), None);
+        assert!(parse_transform_expression("/ 0").is_err());
+        assert!(parse_transform_expression("sqrt").is_err());
+    }
+
+    #[test]
+    fn test_retry_backoff_schedule() {
+        let delays: Vec<i64> = (1..=7).map(generation_retry_delay_secs).collect();
+        assert_eq!(delays, vec![2, 4, 8, 16, 32, 60, 60]);
+        assert_eq!(generation_retry_delay_secs(0), 2);
+        assert_eq!(generation_retry_delay_secs(u8::MAX), GENERATION_RETRY_MAX_DELAY_SECS);
+    }
+
+    #[test]
+    fn test_transient_vs_permanent_errors() {
+        for status in [500, 502, 503, 504, 408, 429] {
+            assert!(GenerationError::from_status(status, "failed").transient, "{}", status);
+        }
+        for status in [400, 401, 403, 404, 422] {
+            assert!(!GenerationError::from_status(status, "failed").transient, "{}", status);
+        }
+        let error: GenerationError = "Workflow node not found".to_string().into();
+        assert!(!error.transient);
+        assert_eq!(error.to_string(), "Workflow node not found");
+        assert!(GenerationError::transient("Timed out waiting for ComfyUI output.").transient);
+    }
+}
@@ -0,0 +1,226 @@
+//! Named sets of provider input values, saved per provider.
+//!
+//! Presets live in one `presets.json` beside the global providers folder,
+//! keyed by provider UUID, so global and project providers share the file.
+//! It sits outside the folder because every `.json` in there is read as a
+//! provider.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::core::provider_store::global_providers_root;
+use crate::state::{GenerativeConfig, InputValue, ProviderEntry};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InputPreset {
+    pub name: String,
+    #[serde(default)]
+    pub inputs: BTreeMap<String, Value>,
+}
+
+impl InputPreset {
+    /// The literal values `config` holds for inputs `provider` defines.
+    pub fn from_config(name: &str, provider: &ProviderEntry, config: &GenerativeConfig) -> Self {
+        let inputs = provider
+            .inputs
+            .iter()
+            .filter_map(|input| match config.inputs.get(&input.name) {
+                Some(InputValue::Literal { value }) => Some((input.name.clone(), value.clone())),
+                _ => None,
+            })
+            .collect();
+        Self {
+            name: name.trim().to_string(),
+            inputs,
+        }
+    }
+
+    /// Split the preset into values `provider` still has an input for and the
+    /// names of inputs it no longer defines.
+    pub fn applicable_inputs(&self, provider: &ProviderEntry) -> (Vec<(String, Value)>, Vec<String>) {
+        let mut values = Vec::new();
+        let mut stale = Vec::new();
+        for (name, value) in &self.inputs {
+            if provider.inputs.iter().any(|input| &input.name == name) {
+                values.push((name.clone(), value.clone()));
+            } else {
+                stale.push(name.clone());
+            }
+        }
+        (values, stale)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InputPresets {
+    #[serde(default)]
+    providers: BTreeMap<Uuid, Vec<InputPreset>>,
+}
+
+impl InputPresets {
+    /// Load the user's presets, or none when the file is missing or unreadable.
+    pub fn load_user() -> Self {
+        Self::load(&input_presets_path())
+    }
+
+    pub fn load(path: &Path) -> Self {
+        let json = match std::fs::read_to_string(path) {
+            Ok(json) => json,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Self::default(),
+            Err(err) => {
+                eprintln!("[PRESETS WARN] Failed to read {}: {}", path.display(), err);
+                return Self::default();
+            }
+        };
+        serde_json::from_str(&json).unwrap_or_else(|err| {
+            eprintln!("[PRESETS WARN] Ignoring malformed presets file: {}", err);
+            Self::default()
+        })
+    }
+
+    pub fn save_user(&self) -> Result<(), String> {
+        self.save(&input_presets_path())
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|err| err.to_string())?;
+        std::fs::write(path, json).map_err(|err| err.to_string())
+    }
+
+    /// Presets saved for `provider_id`, in the order they were first saved.
+    pub fn for_provider(&self, provider_id: Uuid) -> &[InputPreset] {
+        self.providers
+            .get(&provider_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn find(&self, provider_id: Uuid, name: &str) -> Option<&InputPreset> {
+        self.for_provider(provider_id)
+            .iter()
+            .find(|preset| preset.name == name)
+    }
+
+    /// Add `preset`, replacing one of the same name for the provider.
+    pub fn upsert(&mut self, provider_id: Uuid, preset: InputPreset) {
+        let presets = self.providers.entry(provider_id).or_default();
+        match presets.iter_mut().find(|existing| existing.name == preset.name) {
+            Some(existing) => *existing = preset,
+            None => presets.push(preset),
+        }
+    }
+
+    pub fn remove(&mut self, provider_id: Uuid, name: &str) -> bool {
+        let Some(presets) = self.providers.get_mut(&provider_id) else {
+            return false;
+        };
+        let before = presets.len();
+        presets.retain(|preset| preset.name != name);
+        let removed = presets.len() != before;
+        if presets.is_empty() {
+            self.providers.remove(&provider_id);
+        }
+        removed
+    }
+}
+
+pub fn input_presets_path() -> PathBuf {
+    global_providers_root().with_file_name("presets.json")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{ProviderConnection, ProviderInputField, ProviderInputType, ProviderOutputType};
+
+    fn provider(input_names: &[&str]) -> ProviderEntry {
+        let mut provider = ProviderEntry::new(
+            "Presets",
+            ProviderOutputType::Image,
+            ProviderConnection::ComfyUi {
+                base_url: "http://127.0.0.1:8188".to_string(),
+                workflow_path: None,
+                manifest_path: None,
+            },
+        );
+        provider.inputs = input_names
+            .iter()
+            .map(|name| ProviderInputField {
+                name: name.to_string(),
+                label: name.to_string(),
+                input_type: ProviderInputType::Text,
+                required: false,
+                default: None,
+                ui: None,
+                random_range: None,
+            })
+            .collect();
+        provider
+    }
+
+    fn literal(value: Value) -> InputValue {
+        InputValue::Literal { value }
+    }
+
+    #[test]
+    fn test_presets_round_trip_per_provider() {
+        let portrait = provider(&["prompt", "cfg"]);
+        let mut config = GenerativeConfig::default();
+        config.inputs.insert("prompt".to_string(), literal(Value::from("a red fox")));
+        config.inputs.insert("cfg".to_string(), literal(Value::from(6.5)));
+        // Values for inputs the provider doesn't define aren't captured
+        config.inputs.insert("leftover".to_string(), literal(Value::from(1)));
+
+        let mut presets = InputPresets::default();
+        presets.upsert(portrait.id, InputPreset::from_config(" Fox ", &portrait, &config));
+        config.inputs.insert("cfg".to_string(), literal(Value::from(3.0)));
+        presets.upsert(portrait.id, InputPreset::from_config("Low CFG", &portrait, &config));
+        let other = Uuid::new_v4();
+        presets.upsert(other, InputPreset::from_config("Empty", &portrait, &GenerativeConfig::default()));
+
+        let fox = presets.find(portrait.id, "Fox").unwrap();
+        assert_eq!(fox.inputs.len(), 2);
+        assert_eq!(fox.inputs["cfg"], Value::from(6.5));
+
+        // Saving under an existing name replaces it in place
+        config.inputs.insert("prompt".to_string(), literal(Value::from("a grey fox")));
+        presets.upsert(portrait.id, InputPreset::from_config("Fox", &portrait, &config));
+        let names: Vec<&str> = presets.for_provider(portrait.id).iter().map(|preset| preset.name.as_str()).collect();
+        assert_eq!(names, vec!["Fox", "Low CFG"]);
+
+        let root = std::env::temp_dir().join(format!("nla-presets-{}", Uuid::new_v4()));
+        let path = root.join("presets.json");
+        presets.save(&path).unwrap();
+        let mut loaded = InputPresets::load(&path);
+        assert_eq!(loaded, presets);
+        assert_eq!(loaded.find(portrait.id, "Fox").unwrap().inputs["prompt"], Value::from("a grey fox"));
+
+        assert!(loaded.remove(other, "Empty"));
+        assert!(loaded.for_provider(other).is_empty());
+        assert!(InputPresets::load(&root.join("missing.json")).for_provider(portrait.id).is_empty());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_preset_inputs_missing_from_provider_are_ignored() {
+        let before = provider(&["prompt", "cfg", "sampler"]);
+        let mut config = GenerativeConfig::default();
+        config.inputs.insert("prompt".to_string(), literal(Value::from("dusk")));
+        config.inputs.insert("cfg".to_string(), literal(Value::from(7)));
+        config.inputs.insert("sampler".to_string(), literal(Value::from("euler")));
+        let preset = InputPreset::from_config("Dusk", &before, &config);
+
+        // The provider was edited and no longer exposes the sampler or cfg
+        let after = provider(&["prompt", "steps"]);
+        let (values, stale) = preset.applicable_inputs(&after);
+        assert_eq!(values, vec![("prompt".to_string(), Value::from("dusk"))]);
+        assert_eq!(stale, vec!["cfg".to_string(), "sampler".to_string()]);
+    }
+}
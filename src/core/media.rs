@@ -1,7 +1,14 @@
 use dioxus::prelude::{spawn, ReadableExt, Signal, WritableExt};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+use crate::core::cancel::CancelHandle;
+use crate::core::video_decode::{probe_video_codec_support, VideoCodecSupport};
+use crate::state::{AssetIssue, AssetProxy};
+
+/// Proxies are scaled down to this height.
+pub const PROXY_HEIGHT: u32 = 540;
+
 /// Probe media duration in seconds using ffprobe.
 pub fn probe_duration_seconds(path: &Path) -> Option<f64> {
     let output = Command::new("ffprobe")
@@ -28,10 +35,43 @@ pub fn probe_duration_seconds(path: &Path) -> Option<f64> {
     duration_str.parse::<f64>().ok()
 }
 
+/// Pixel size of an image, or of the first video stream of a video file.
+pub fn probe_media_dimensions(path: &Path, is_video: bool) -> Option<(u32, u32)> {
+    if !is_video {
+        return image::image_dimensions(path).ok();
+    }
+    let output = Command::new("ffprobe")
+        .arg("-v")
+        .arg("error")
+        .arg("-select_streams")
+        .arg("v:0")
+        .arg("-show_entries")
+        .arg("stream=width,height")
+        .arg("-of")
+        .arg("csv=s=x:p=0")
+        .arg(path)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (width, height) = stdout.trim().split_once('x')?;
+    let width = width.trim().parse::<u32>().ok()?;
+    let height = height.trim().parse::<u32>().ok()?;
+    (width > 0 && height > 0).then_some((width, height))
+}
+
 pub fn spawn_asset_duration_probe(
-    mut project: Signal<crate::state::Project>,
+    project: Signal<crate::state::Project>,
     asset_id: uuid::Uuid,
 ) {
+    spawn(probe_asset_duration(project, asset_id));
+}
+
+async fn probe_asset_duration(mut project: Signal<crate::state::Project>, asset_id: uuid::Uuid) {
     let (project_root, asset_path, needs_probe) = {
         let project_read = project.read();
         let project_root = project_read.project_path.clone();
@@ -54,31 +94,62 @@ pub fn spawn_asset_duration_probe(
     }
 
     let absolute_path = project_root.join(asset_path);
+    let duration = tokio::task::spawn_blocking(move || probe_duration_seconds(&absolute_path))
+        .await
+        .ok()
+        .flatten();
 
-    spawn(async move {
-        let duration = tokio::task::spawn_blocking(move || probe_duration_seconds(&absolute_path))
-            .await
-            .ok()
-            .flatten();
-
-        if let Some(duration) = duration {
-            project.write().set_asset_duration(asset_id, Some(duration));
-        }
-    });
+    if let Some(duration) = duration {
+        project.write().set_asset_duration(asset_id, Some(duration));
+    }
 }
 
-pub fn spawn_missing_duration_probes(project: Signal<crate::state::Project>) {
-    let asset_ids: Vec<uuid::Uuid> = project
-        .read()
-        .assets
-        .iter()
-        .filter(|asset| asset.duration_seconds.is_none() && (asset.is_video() || asset.is_audio()))
-        .map(|asset| asset.id)
-        .collect();
+/// Probe missing durations and re-check flagged video codecs (a transcode may have been
+/// interrupted, or the file replaced) for every asset, one at a time so a huge project
+/// doesn't flood the blocking pool. `remaining` counts down as
+/// assets finish; the loop stops at the next asset once `handle` is cancelled.
+pub async fn index_project_media(
+    project: Signal<crate::state::Project>,
+    handle: CancelHandle,
+    mut remaining: Signal<usize>,
+) {
+    let (duration_ids, codec_ids): (Vec<uuid::Uuid>, Vec<uuid::Uuid>) = {
+        let project_read = project.read();
+        let duration_ids = project_read
+            .assets
+            .iter()
+            .filter(|asset| asset.duration_seconds.is_none() && (asset.is_video() || asset.is_audio()))
+            .map(|asset| asset.id)
+            .collect();
+        let codec_ids = project_read
+            .assets
+            .iter()
+            .filter(|asset| {
+                asset.issue.is_some()
+                    && !asset.is_missing()
+                    && matches!(asset.kind, crate::state::AssetKind::Video { .. })
+            })
+            .map(|asset| asset.id)
+            .collect();
+        (duration_ids, codec_ids)
+    };
 
-    for asset_id in asset_ids {
-        spawn_asset_duration_probe(project, asset_id);
+    remaining.set(duration_ids.len() + codec_ids.len());
+    for asset_id in duration_ids {
+        if handle.is_cancelled() {
+            break;
+        }
+        probe_asset_duration(project, asset_id).await;
+        remaining.set(remaining().saturating_sub(1));
+    }
+    for asset_id in codec_ids {
+        if handle.is_cancelled() {
+            break;
+        }
+        check_video_codec(project, asset_id).await;
+        remaining.set(remaining().saturating_sub(1));
     }
+    remaining.set(0);
 }
 
 pub fn resolve_asset_duration_seconds(
@@ -121,3 +192,307 @@ pub fn resolve_asset_duration_seconds(
 
     None
 }
+
+/// Check whether an `ffmpeg` binary is on the PATH.
+pub fn ffmpeg_available() -> bool {
+    Command::new("ffmpeg")
+        .arg("-version")
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+/// Transcode a video to H.264/AAC MP4 next to the original.
+/// Returns the new path relative to the project root.
+pub fn transcode_video_for_editing(project_root: &Path, relative_path: &Path) -> Result<PathBuf, String> {
+    if !ffmpeg_available() {
+        return Err("FFmpeg not found; install it to transcode this file".to_string());
+    }
+
+    let source = project_root.join(relative_path);
+    if !source.exists() {
+        return Err("Source file is missing".to_string());
+    }
+    let stem = relative_path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("video");
+    let relative_dir = relative_path.parent().map(Path::to_path_buf).unwrap_or_default();
+
+    let mut file_name = format!("{}_transcoded.mp4", stem);
+    let mut counter = 1;
+    while project_root.join(&relative_dir).join(&file_name).exists() {
+        file_name = format!("{}_transcoded_{}.mp4", stem, counter);
+        counter += 1;
+    }
+    let relative_output = relative_dir.join(&file_name);
+    let output_path = project_root.join(&relative_output);
+
+    let output = Command::new("ffmpeg")
+        .arg("-v")
+        .arg("error")
+        .arg("-y")
+        .arg("-i")
+        .arg(&source)
+        .arg("-c:v")
+        .arg("libx264")
+        .arg("-preset")
+        .arg("veryfast")
+        .arg("-crf")
+        .arg("18")
+        .arg("-pix_fmt")
+        .arg("yuv420p")
+        .arg("-c:a")
+        .arg("aac")
+        .arg("-b:a")
+        .arg("192k")
+        .arg(&output_path)
+        .output()
+        .map_err(|err| err.to_string())?;
+
+    if !output.status.success() {
+        let _ = std::fs::remove_file(&output_path);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().last().unwrap_or("Transcode failed").trim();
+        return Err(if reason.is_empty() {
+            "Transcode failed".to_string()
+        } else {
+            reason.to_string()
+        });
+    }
+
+    Ok(relative_output)
+}
+
+/// Size of the proxy for a `width` x `height` source: `PROXY_HEIGHT` tall with
+/// the same aspect ratio, with even sides for the H.264 encoder. `None` when
+/// the source is already that small.
+pub fn proxy_dimensions(width: u32, height: u32) -> Option<(u32, u32)> {
+    if width == 0 || height <= PROXY_HEIGHT {
+        return None;
+    }
+    let scaled_width = (width as f64 * PROXY_HEIGHT as f64 / height as f64).round() as u32;
+    let even_width = (scaled_width / 2 * 2).max(2);
+    Some((even_width, PROXY_HEIGHT))
+}
+
+/// Encode a `width` x `height` editing proxy of `relative_path` into
+/// `.cache/proxies`. Returns the proxy path relative to `project_root`.
+pub fn transcode_video_proxy(
+    project_root: &Path,
+    relative_path: &Path,
+    asset_id: uuid::Uuid,
+    width: u32,
+    height: u32,
+) -> Result<PathBuf, String> {
+    if !ffmpeg_available() {
+        return Err("FFmpeg not found; install it to create proxies".to_string());
+    }
+    let source = crate::state::resolve_project_path(relative_path, project_root);
+    if !source.exists() {
+        return Err("Source file is missing".to_string());
+    }
+    let relative_output = PathBuf::from(".cache")
+        .join("proxies")
+        .join(format!("{}.mp4", asset_id));
+    let output_path = project_root.join(&relative_output);
+    if let Some(parent) = output_path.parent() {
+        std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+
+    // Short keyframe interval so scrubbing the proxy seeks quickly; preview
+    // audio still comes from the original.
+    let output = Command::new("ffmpeg")
+        .arg("-v")
+        .arg("error")
+        .arg("-y")
+        .arg("-i")
+        .arg(&source)
+        .arg("-vf")
+        .arg(format!("scale={}:{}", width, height))
+        .arg("-c:v")
+        .arg("libx264")
+        .arg("-preset")
+        .arg("veryfast")
+        .arg("-crf")
+        .arg("23")
+        .arg("-g")
+        .arg("12")
+        .arg("-pix_fmt")
+        .arg("yuv420p")
+        .arg("-an")
+        .arg(&output_path)
+        .output()
+        .map_err(|err| err.to_string())?;
+
+    if !output.status.success() {
+        let _ = std::fs::remove_file(&output_path);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().last().unwrap_or("Proxy encode failed").trim();
+        return Err(if reason.is_empty() {
+            "Proxy encode failed".to_string()
+        } else {
+            reason.to_string()
+        });
+    }
+
+    Ok(relative_output)
+}
+
+/// Create (or recreate) the editing proxy of a video asset, probing its size
+/// first if it isn't known yet.
+pub async fn create_video_proxy(
+    mut project: Signal<crate::state::Project>,
+    asset_id: uuid::Uuid,
+) -> Result<(), String> {
+    let (project_root, asset_path, known_size) = {
+        let project_read = project.read();
+        let asset = project_read
+            .find_asset(asset_id)
+            .ok_or_else(|| "Asset not found".to_string())?;
+        let crate::state::AssetKind::Video { path } = &asset.kind else {
+            return Err("Only video files can have proxies".to_string());
+        };
+        let project_root = project_read
+            .project_path
+            .clone()
+            .ok_or_else(|| "Save the project first".to_string())?;
+        (project_root, path.clone(), asset.source_dimensions())
+    };
+
+    let (width, height) = match known_size {
+        Some(size) => size,
+        None => {
+            let absolute_path = crate::state::resolve_project_path(&asset_path, &project_root);
+            let size = tokio::task::spawn_blocking(move || probe_media_dimensions(&absolute_path, true))
+                .await
+                .ok()
+                .flatten()
+                .ok_or_else(|| "Couldn't read the video's size".to_string())?;
+            project.write().set_asset_dimensions(asset_id, size.0, size.1);
+            size
+        }
+    };
+    let (proxy_width, proxy_height) = proxy_dimensions(width, height)
+        .ok_or_else(|| "The video is already small enough to edit directly".to_string())?;
+
+    let relative_output = tokio::task::spawn_blocking(move || {
+        transcode_video_proxy(&project_root, &asset_path, asset_id, proxy_width, proxy_height)
+    })
+    .await
+    .unwrap_or_else(|err| Err(err.to_string()))?;
+
+    project.write().set_asset_proxy(
+        asset_id,
+        Some(AssetProxy {
+            path: relative_output,
+            width: proxy_width,
+            height: proxy_height,
+        }),
+    );
+    let _ = project.read().save();
+    Ok(())
+}
+
+/// Probe a video asset's codec in the background and flag it if it can't be decoded.
+pub fn spawn_video_codec_check(
+    project: Signal<crate::state::Project>,
+    asset_id: uuid::Uuid,
+) {
+    spawn(check_video_codec(project, asset_id));
+}
+
+async fn check_video_codec(mut project: Signal<crate::state::Project>, asset_id: uuid::Uuid) {
+    let (project_root, asset_path) = {
+        let project_read = project.read();
+        let asset_path = project_read.find_asset(asset_id).and_then(|asset| match &asset.kind {
+            crate::state::AssetKind::Video { path } => Some(path.clone()),
+            _ => None,
+        });
+        (project_read.project_path.clone(), asset_path)
+    };
+
+    let Some(project_root) = project_root else { return; };
+    let Some(asset_path) = asset_path else { return; };
+    let absolute_path = project_root.join(asset_path);
+
+    let support = tokio::task::spawn_blocking(move || probe_video_codec_support(&absolute_path))
+        .await
+        .unwrap_or(VideoCodecSupport::Supported);
+
+    let issue = match support {
+        VideoCodecSupport::Supported => None,
+        VideoCodecSupport::Unsupported { codec } => Some(AssetIssue::UnsupportedCodec { codec }),
+        VideoCodecSupport::Unreadable(reason) => Some(AssetIssue::Offline { reason }),
+    };
+    let changed = project
+        .read()
+        .find_asset(asset_id)
+        .map(|asset| asset.issue != issue)
+        .unwrap_or(false);
+    if changed {
+        project.write().set_asset_issue(asset_id, issue);
+        let _ = project.read().save();
+    }
+}
+
+/// Transcode an unsupported video asset and switch it to the transcoded copy.
+/// Marks the asset offline when transcoding isn't possible. Returns true on success.
+pub async fn transcode_video_asset(
+    mut project: Signal<crate::state::Project>,
+    asset_id: uuid::Uuid,
+) -> bool {
+    let (project_root, asset_path) = {
+        let project_read = project.read();
+        let asset_path = project_read.find_asset(asset_id).and_then(|asset| match &asset.kind {
+            crate::state::AssetKind::Video { path } => Some(path.clone()),
+            _ => None,
+        });
+        (project_read.project_path.clone(), asset_path)
+    };
+    let (Some(project_root), Some(asset_path)) = (project_root, asset_path) else {
+        return false;
+    };
+
+    project.write().set_asset_issue(asset_id, Some(AssetIssue::Transcoding));
+
+    let result = tokio::task::spawn_blocking(move || {
+        transcode_video_for_editing(&project_root, &asset_path)
+    })
+    .await
+    .unwrap_or_else(|err| Err(err.to_string()));
+
+    match result {
+        Ok(relative_output) => {
+            project.write().replace_video_source(asset_id, relative_output);
+            let _ = project.read().save();
+            true
+        }
+        Err(reason) => {
+            println!("Transcode failed for {}: {}", asset_id, reason);
+            project
+                .write()
+                .set_asset_issue(asset_id, Some(AssetIssue::Offline { reason }));
+            let _ = project.read().save();
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proxy_dimensions_keep_aspect_at_proxy_height() {
+        assert_eq!(proxy_dimensions(3840, 2160), Some((960, 540)));
+        assert_eq!(proxy_dimensions(1920, 1080), Some((960, 540)));
+        // Portrait and odd aspect ratios round to an even width
+        assert_eq!(proxy_dimensions(1080, 1920), Some((304, 540)));
+        assert_eq!(proxy_dimensions(2048, 858), Some((1288, 540)));
+        // Already small enough: no proxy
+        assert_eq!(proxy_dimensions(960, 540), None);
+        assert_eq!(proxy_dimensions(640, 360), None);
+        assert_eq!(proxy_dimensions(0, 1080), None);
+    }
+}
@@ -1,13 +1,22 @@
 pub mod thumbnailer;
 pub mod media;
+pub mod cancel;
 pub mod preview;
 pub mod preview_store;
 pub mod preview_gpu;
 pub mod provider_store;
+pub mod provider_package;
+pub mod provider_duplicates;
+pub mod input_presets;
 pub mod generation;
 pub mod comfyui_workflow;
+pub mod bundle;
 pub mod paths;
+pub mod recent_projects;
+pub mod close_guard;
 pub mod timeline_snap;
+pub mod timecode;
 mod video_decode;
 pub mod audio;
-// pub mod ffmpeg; // Placeholder for future imports
+pub mod export;
+pub mod ffmpeg;
@@ -49,3 +49,142 @@ pub fn app_cache_root() -> PathBuf {
         .unwrap_or_else(|_| std::env::temp_dir());
     base.join("NLA-AI-VideoCreator").join("cache")
 }
+
+/// Per-user settings folder (keymap and other preferences).
+pub fn app_config_root() -> PathBuf {
+    let base = std::env::var("APPDATA")
+        .or_else(|_| std::env::var("XDG_CONFIG_HOME"))
+        .map(PathBuf::from)
+        .or_else(|_| std::env::var("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .unwrap_or_else(|_| std::env::temp_dir());
+    base.join("NLA-AI-VideoCreator")
+}
+
+/// The recently opened projects list.
+pub fn recent_projects_path() -> PathBuf {
+    app_config_root().join("recent.json")
+}
+
+/// Environment variables the user let each HTTP provider read.
+pub fn provider_env_approvals_path() -> PathBuf {
+    app_config_root().join("provider_env.json")
+}
+
+/// Default folder for exported videos and stills: the project's `exports`
+/// folder when it exists, otherwise the project root.
+pub fn project_exports_dir(project_root: &Path) -> PathBuf {
+    let exports = project_root.join("exports");
+    if exports.is_dir() {
+        exports
+    } else {
+        project_root.to_path_buf()
+    }
+}
+
+/// `name` made safe to use as (part of) a file name: path separators, `:` and
+/// the other characters Windows rejects become `_`, and leading or trailing
+/// dots and spaces are dropped so `..` can't name a parent folder. Falls back
+/// to `fallback` when nothing is left.
+pub fn safe_file_stem(name: &str, fallback: &str) -> String {
+    let replaced: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    let trimmed = replaced.trim_matches(|c: char| c == '.' || c.is_whitespace());
+    if trimmed.is_empty() {
+        fallback.to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Open a folder (or a file's containing folder) in the OS file browser.
+pub fn reveal_in_file_browser(path: &Path) -> Result<(), String> {
+    if !path.exists() {
+        return Err(format!("{} does not exist", path.display()));
+    }
+
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut command = std::process::Command::new("explorer");
+        if path.is_file() {
+            command.arg(format!("/select,{}", path.display()));
+        } else {
+            command.arg(path);
+        }
+        command
+    };
+
+    #[cfg(target_os = "macos")]
+    let mut command = {
+        let mut command = std::process::Command::new("open");
+        if path.is_file() {
+            command.arg("-R");
+        }
+        command.arg(path);
+        command
+    };
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let mut command = {
+        let mut command = std::process::Command::new("xdg-open");
+        let folder = if path.is_file() {
+            path.parent().unwrap_or(path)
+        } else {
+            path
+        };
+        command.arg(folder);
+        command
+    };
+
+    command.spawn().map(|_| ()).map_err(|err| err.to_string())
+}
+
+/// Open a file with the OS default application.
+pub fn open_with_default_app(path: &Path) -> Result<(), String> {
+    if !path.exists() {
+        return Err(format!("{} does not exist", path.display()));
+    }
+
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut command = std::process::Command::new("cmd");
+        command.args(["/C", "start", ""]).arg(path);
+        command
+    };
+
+    #[cfg(target_os = "macos")]
+    let mut command = {
+        let mut command = std::process::Command::new("open");
+        command.arg(path);
+        command
+    };
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let mut command = {
+        let mut command = std::process::Command::new("xdg-open");
+        command.arg(path);
+        command
+    };
+
+    command.spawn().map(|_| ()).map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_safe_file_stem() {
+        assert_eq!(safe_file_stem("My Project", "frame"), "My Project");
+        assert_eq!(safe_file_stem("../../etc/passwd", "frame"), "_.._etc_passwd");
+        assert_eq!(safe_file_stem("C:\\Users\\me", "frame"), "C__Users_me");
+        assert_eq!(safe_file_stem("Take 1: final?", "frame"), "Take 1_ final_");
+        assert_eq!(safe_file_stem("..", "frame"), "frame");
+        assert_eq!(safe_file_stem("  ", "frame"), "frame");
+    }
+}
@@ -36,6 +36,36 @@ impl FrameCache {
         }
     }
 
+    /// Bytes of decoded frames currently held.
+    pub fn total_bytes(&self) -> usize {
+        self.total_bytes
+    }
+
+    pub fn max_bytes(&self) -> usize {
+        self.max_bytes
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Change the budget, evicting least-recently-used frames if now over it.
+    pub fn set_max_bytes(&mut self, max_bytes: usize) {
+        self.max_bytes = max_bytes;
+        self.evict_if_needed();
+        self.compact_lru_if_needed();
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.lru_order.clear();
+        self.asset_index.clear();
+        self.total_bytes = 0;
+    }
 
     pub(crate) fn get(&mut self, key: &FrameKey) -> Option<CachedFrame> {
         let (image, source_width, source_height) = {
@@ -108,6 +138,9 @@ impl FrameCache {
                 self.total_bytes = self.total_bytes.saturating_sub(entry.size_bytes);
             }
         }
+        // Queue entries for the removed frames are skipped by stamp on
+        // eviction; compacting just keeps the queue from growing.
+        self.compact_lru_if_needed();
     }
 
     pub(crate) fn invalidate_folder(&mut self, folder: &Path) {
@@ -167,3 +200,87 @@ impl FrameCache {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(path: &str, frame_index: i64) -> FrameKey {
+        FrameKey {
+            path: PathBuf::from(path),
+            frame_index,
+        }
+    }
+
+    /// 10x10 RGBA = 400 bytes.
+    fn frame() -> Arc<RgbaImage> {
+        Arc::new(RgbaImage::new(10, 10))
+    }
+
+    #[test]
+    fn test_byte_accounting() {
+        let mut cache = FrameCache::new(10_000);
+        cache.insert(key("a.mp4", 0), frame(), 10, 10);
+        cache.insert(key("a.mp4", 1), frame(), 10, 10);
+        assert_eq!(cache.total_bytes(), 800);
+        assert_eq!(cache.len(), 2);
+
+        // Replacing a key doesn't double count
+        cache.insert(key("a.mp4", 1), Arc::new(RgbaImage::new(20, 10)), 20, 10);
+        assert_eq!(cache.total_bytes(), 1200);
+
+        cache.invalidate_path(Path::new("a.mp4"));
+        assert_eq!(cache.total_bytes(), 0);
+        assert!(cache.is_empty());
+
+        // Frames larger than the whole budget are never cached
+        let mut small = FrameCache::new(100);
+        small.insert(key("a.mp4", 0), frame(), 10, 10);
+        assert!(small.is_empty());
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used_first() {
+        let mut cache = FrameCache::new(1200);
+        cache.insert(key("a.mp4", 0), frame(), 10, 10);
+        cache.insert(key("a.mp4", 1), frame(), 10, 10);
+        cache.insert(key("a.mp4", 2), frame(), 10, 10);
+        // Touch frame 0 so frame 1 becomes the oldest
+        assert!(cache.get(&key("a.mp4", 0)).is_some());
+
+        cache.insert(key("a.mp4", 3), frame(), 10, 10);
+        assert_eq!(cache.total_bytes(), 1200);
+        assert!(cache.get(&key("a.mp4", 1)).is_none());
+        assert!(cache.get(&key("a.mp4", 0)).is_some());
+        assert!(cache.get(&key("a.mp4", 2)).is_some());
+        assert!(cache.get(&key("a.mp4", 3)).is_some());
+
+        // The reads above left 0 as the oldest; shrinking the budget drops it
+        cache.set_max_bytes(800);
+        assert!(cache.get(&key("a.mp4", 0)).is_none());
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.total_bytes(), 800);
+    }
+
+    #[test]
+    fn test_invalidation_keeps_lru_consistent() {
+        let mut cache = FrameCache::new(1200);
+        cache.insert(key("gen/v1.png", 0), frame(), 10, 10);
+        cache.insert(key("b.mp4", 0), frame(), 10, 10);
+        cache.invalidate_folder(Path::new("gen"));
+        assert_eq!(cache.total_bytes(), 400);
+
+        // Re-inserting the invalidated frame gets a fresh stamp, so it is
+        // not evicted ahead of older frames because of its stale queue entry
+        cache.insert(key("gen/v1.png", 0), frame(), 10, 10);
+        cache.insert(key("c.mp4", 0), frame(), 10, 10);
+        cache.insert(key("d.mp4", 0), frame(), 10, 10);
+        assert!(cache.get(&key("b.mp4", 0)).is_none());
+        assert!(cache.get(&key("gen/v1.png", 0)).is_some());
+        assert_eq!(cache.total_bytes(), 1200);
+
+        cache.clear();
+        assert_eq!(cache.total_bytes(), 0);
+        assert!(cache.asset_index.is_empty());
+    }
+}
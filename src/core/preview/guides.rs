@@ -0,0 +1,156 @@
+//! Safe-area and composition guides drawn over the preview.
+//!
+//! Guides are laid out in project pixels and mapped onto the downsampled
+//! preview canvas, so they land on the same content whatever the preview cap.
+
+use crate::state::PreviewGuides;
+
+/// Title-safe box as a fraction of the frame.
+pub const TITLE_SAFE_FRACTION: f32 = 0.90;
+/// Action-safe box as a fraction of the frame.
+pub const ACTION_SAFE_FRACTION: f32 = 0.93;
+/// Half-length of the center cross arms, as a fraction of the frame height.
+const CENTER_CROSS_FRACTION: f32 = 0.04;
+/// Most lines `guide_lines` can return (two boxes, the grid, and the cross).
+pub const MAX_GUIDE_LINES: usize = 14;
+
+/// Axis-aligned guide segment in preview canvas pixels.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GuideLine {
+    pub x0: f32,
+    pub y0: f32,
+    pub x1: f32,
+    pub y1: f32,
+}
+
+/// Lines for the enabled `guides` on a `canvas_w` x `canvas_h` preview of a
+/// `project_w` x `project_h` project.
+pub fn guide_lines(
+    guides: PreviewGuides,
+    project_w: u32,
+    project_h: u32,
+    canvas_w: u32,
+    canvas_h: u32,
+) -> Vec<GuideLine> {
+    let mut lines = Vec::new();
+    if project_w == 0 || project_h == 0 || canvas_w == 0 || canvas_h == 0 {
+        return lines;
+    }
+    let project_w = project_w as f32;
+    let project_h = project_h as f32;
+    // Per-axis so rounding in the canvas size keeps guides flush with its edges.
+    let scale_x = canvas_w as f32 / project_w;
+    let scale_y = canvas_h as f32 / project_h;
+    let mut push = |x0: f32, y0: f32, x1: f32, y1: f32| {
+        lines.push(GuideLine {
+            x0: x0 * scale_x,
+            y0: y0 * scale_y,
+            x1: x1 * scale_x,
+            y1: y1 * scale_y,
+        });
+    };
+
+    let mut safe_box = |fraction: f32| {
+        let left = project_w * (1.0 - fraction) * 0.5;
+        let top = project_h * (1.0 - fraction) * 0.5;
+        let right = project_w - left;
+        let bottom = project_h - top;
+        push(left, top, right, top);
+        push(left, bottom, right, bottom);
+        push(left, top, left, bottom);
+        push(right, top, right, bottom);
+    };
+    if guides.action_safe {
+        safe_box(ACTION_SAFE_FRACTION);
+    }
+    if guides.title_safe {
+        safe_box(TITLE_SAFE_FRACTION);
+    }
+    if guides.thirds {
+        for third in [1.0 / 3.0, 2.0 / 3.0] {
+            push(project_w * third, 0.0, project_w * third, project_h);
+            push(0.0, project_h * third, project_w, project_h * third);
+        }
+    }
+    if guides.center {
+        let center_x = project_w * 0.5;
+        let center_y = project_h * 0.5;
+        let arm = project_h * CENTER_CROSS_FRACTION;
+        push(center_x - arm, center_y, center_x + arm, center_y);
+        push(center_x, center_y - arm, center_x, center_y + arm);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::preview::layers::preview_canvas_size;
+
+    fn all_guides() -> PreviewGuides {
+        PreviewGuides {
+            title_safe: true,
+            action_safe: true,
+            thirds: true,
+            center: true,
+        }
+    }
+
+    fn assert_line(line: GuideLine, expected: [f32; 4]) {
+        let actual = [line.x0, line.y0, line.x1, line.y1];
+        for (a, e) in actual.iter().zip(expected.iter()) {
+            assert!((a - e).abs() < 0.01, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_guides_map_through_preview_downsample() {
+        // 1080p project previewed at the default 960x540 cap
+        let (canvas_w, canvas_h, _) = preview_canvas_size(1920, 1080, 960, 540);
+        assert_eq!((canvas_w, canvas_h), (960, 540));
+        let guides = PreviewGuides {
+            title_safe: true,
+            thirds: true,
+            ..PreviewGuides::default()
+        };
+        let lines = guide_lines(guides, 1920, 1080, canvas_w, canvas_h);
+        assert_eq!(lines.len(), 8);
+        // Title safe: 5% in from each edge
+        assert_line(lines[0], [48.0, 27.0, 912.0, 27.0]);
+        assert_line(lines[1], [48.0, 513.0, 912.0, 513.0]);
+        assert_line(lines[2], [48.0, 27.0, 48.0, 513.0]);
+        assert_line(lines[3], [912.0, 27.0, 912.0, 513.0]);
+        // Thirds
+        assert_line(lines[4], [320.0, 0.0, 320.0, 540.0]);
+        assert_line(lines[5], [0.0, 180.0, 960.0, 180.0]);
+        assert_line(lines[6], [640.0, 0.0, 640.0, 540.0]);
+        assert_line(lines[7], [0.0, 360.0, 960.0, 360.0]);
+    }
+
+    #[test]
+    fn test_guides_follow_portrait_aspect() {
+        // Portrait project: the height limits the preview and the width rounds
+        let (canvas_w, canvas_h, _) = preview_canvas_size(1080, 1920, 960, 540);
+        assert_eq!((canvas_w, canvas_h), (304, 540));
+        let guides = PreviewGuides {
+            action_safe: true,
+            center: true,
+            ..PreviewGuides::default()
+        };
+        let lines = guide_lines(guides, 1080, 1920, canvas_w, canvas_h);
+        assert_eq!(lines.len(), 6);
+        // Action safe: 3.5% in from each edge of the canvas
+        assert_line(lines[0], [10.64, 18.9, 293.36, 18.9]);
+        assert_line(lines[3], [293.36, 18.9, 293.36, 521.1]);
+        // Cross arms are sized off the frame height and centered on the canvas
+        assert_line(lines[4], [130.38, 270.0, 173.62, 270.0]);
+        assert_line(lines[5], [152.0, 248.4, 152.0, 291.6]);
+    }
+
+    #[test]
+    fn test_guide_line_count() {
+        assert!(guide_lines(PreviewGuides::default(), 1920, 1080, 960, 540).is_empty());
+        assert_eq!(guide_lines(all_guides(), 1920, 1080, 960, 540).len(), MAX_GUIDE_LINES);
+        assert!(guide_lines(all_guides(), 0, 1080, 960, 540).is_empty());
+    }
+}
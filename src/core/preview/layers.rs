@@ -3,10 +3,10 @@ use std::path::PathBuf;
 use std::sync::Arc;
 
 use image::{Rgba, RgbaImage};
-use image::imageops::{overlay, resize, FilterType};
+use image::imageops::{crop_imm, overlay, resize, FilterType};
 use imageproc::geometric_transformations::{rotate_about_center, Interpolation};
 
-use crate::state::ClipTransform;
+use crate::state::{BlendMode, ClipTransform, MAX_CLIP_SCALE, MIN_CLIP_SCALE};
 
 use super::types::{FrameKey, PreviewLayerPlacement};
 
@@ -18,6 +18,9 @@ pub(crate) struct PendingDecode {
     pub(crate) cache_key: FrameKey,
     pub(crate) transform: ClipTransform,
     pub(crate) lane_id: u64,
+    pub(crate) scrub_focus: Option<uuid::Uuid>,
+    /// Original size to lay a proxy's frames out at
+    pub(crate) source_size: Option<(u32, u32)>,
 }
 
 pub(crate) struct DecodedFrame {
@@ -55,6 +58,85 @@ pub(crate) fn preview_canvas_size(
     (canvas_w, canvas_h, scale)
 }
 
+/// Checkerboard cell edge in CSS pixels; scaled by the display's pixel ratio.
+const CHECKERBOARD_CELL_CSS_PX: f64 = 8.0;
+const CHECKERBOARD_LIGHT: Rgba<u8> = Rgba([58, 58, 58, 255]);
+const CHECKERBOARD_DARK: Rgba<u8> = Rgba([38, 38, 38, 255]);
+
+/// Checker cell size in canvas pixels for a display with `device_pixel_ratio`.
+pub fn checkerboard_cell_px(device_pixel_ratio: f64) -> u32 {
+    let dpr = if device_pixel_ratio.is_finite() && device_pixel_ratio > 0.0 {
+        device_pixel_ratio
+    } else {
+        1.0
+    };
+    (CHECKERBOARD_CELL_CSS_PX * dpr).round().max(1.0) as u32
+}
+
+/// Opaque checkerboard drawn behind the clip layers so transparency shows.
+pub(crate) fn checkerboard_image(width: u32, height: u32, cell_px: u32) -> RgbaImage {
+    let cell = cell_px.max(1);
+    RgbaImage::from_fn(width, height, |x, y| {
+        if (x / cell + y / cell) % 2 == 0 {
+            CHECKERBOARD_LIGHT
+        } else {
+            CHECKERBOARD_DARK
+        }
+    })
+}
+
+/// Fractions of the configured preview cap that the renderer may drop to.
+const PREVIEW_LIMIT_STEPS: [f32; 4] = [0.25, 0.5, 0.75, 1.0];
+
+/// Pick preview render limits for a panel of `panel_width` x `panel_height` physical pixels.
+///
+/// Returns the smallest step of the cap whose canvas still covers what the panel can
+/// display, so small panels render small. Snapping to steps keeps resizes from
+/// rebuilding the renderer on every pixel.
+pub fn effective_preview_limits(
+    project_width: u32,
+    project_height: u32,
+    cap_width: u32,
+    cap_height: u32,
+    panel_width: f64,
+    panel_height: f64,
+) -> (u32, u32) {
+    let cap_width = cap_width.max(1);
+    let cap_height = cap_height.max(1);
+    if panel_width < 1.0 || panel_height < 1.0 {
+        return (cap_width, cap_height);
+    }
+
+    let (display_w, display_h, _) = preview_canvas_size(
+        project_width,
+        project_height,
+        panel_width.round() as u32,
+        panel_height.round() as u32,
+    );
+    for step in PREVIEW_LIMIT_STEPS {
+        let limit_w = ((cap_width as f32 * step).round() as u32).max(1);
+        let limit_h = ((cap_height as f32 * step).round() as u32).max(1);
+        let (canvas_w, canvas_h, _) =
+            preview_canvas_size(project_width, project_height, limit_w, limit_h);
+        if canvas_w >= display_w || canvas_h >= display_h {
+            return (limit_w, limit_h);
+        }
+    }
+    (cap_width, cap_height)
+}
+
+/// Largest side a transformed layer may be resized to in full on the CPU.
+///
+/// Bigger layers are cropped to the canvas before resizing (or skipped when rotated),
+/// so an extreme zoom never allocates a buffer far larger than the preview itself.
+const MAX_LAYER_DIMENSION_PX: f32 = 8192.0;
+
+/// True when a `width` x `height` layer is larger than `max_side` on either
+/// side, or isn't a finite size at all.
+pub fn layer_oversized(width: f32, height: f32, max_side: f32) -> bool {
+    !width.is_finite() || !height.is_finite() || width > max_side || height > max_side
+}
+
 pub(crate) fn composite_layer(
     canvas: &mut RgbaImage,
     image: &RgbaImage,
@@ -89,13 +171,23 @@ pub(crate) fn composite_layer(
         return;
     }
 
+    if layer_oversized(placement.scaled_w, placement.scaled_h, MAX_LAYER_DIMENSION_PX) {
+        if placement.rotation_deg.abs() > 0.01 {
+            // Rotated crops are not worth the complexity; skip rather than hang.
+            return;
+        }
+        composite_cropped_layer(canvas, image.as_ref(), &placement);
+        return;
+    }
+
     let resized = resize(image.as_ref(), scaled_w, scaled_h, FilterType::Triangle);
     if placement.rotation_deg.abs() <= 0.01 {
-        overlay(
+        overlay_blended(
             canvas,
             &resized,
             placement.offset_x.round() as i64,
             placement.offset_y.round() as i64,
+            placement.blend_mode,
         );
         return;
     }
@@ -105,7 +197,121 @@ pub(crate) fn composite_layer(
     let center_y = placement.offset_y + placement.scaled_h * 0.5;
     let dest_x = (center_x - rotated.width() as f32 * 0.5).round() as i64;
     let dest_y = (center_y - rotated.height() as f32 * 0.5).round() as i64;
-    overlay(canvas, &rotated, dest_x, dest_y);
+    overlay_blended(canvas, &rotated, dest_x, dest_y, placement.blend_mode);
+}
+
+/// Resize only the part of an oversized, unrotated layer that lands on the canvas.
+fn composite_cropped_layer(
+    canvas: &mut RgbaImage,
+    image: &RgbaImage,
+    placement: &PreviewLayerPlacement,
+) {
+    let canvas_w = canvas.width() as f32;
+    let canvas_h = canvas.height() as f32;
+    let visible_x0 = (-placement.offset_x).max(0.0);
+    let visible_y0 = (-placement.offset_y).max(0.0);
+    let visible_x1 = (canvas_w - placement.offset_x).min(placement.scaled_w);
+    let visible_y1 = (canvas_h - placement.offset_y).min(placement.scaled_h);
+    if visible_x1 <= visible_x0 || visible_y1 <= visible_y0 {
+        return;
+    }
+
+    let src_per_px_x = image.width() as f32 / placement.scaled_w;
+    let src_per_px_y = image.height() as f32 / placement.scaled_h;
+    let src_x = (visible_x0 * src_per_px_x).floor() as u32;
+    let src_y = (visible_y0 * src_per_px_y).floor() as u32;
+    let src_x1 = ((visible_x1 * src_per_px_x).ceil() as u32).min(image.width());
+    let src_y1 = ((visible_y1 * src_per_px_y).ceil() as u32).min(image.height());
+    if src_x1 <= src_x || src_y1 <= src_y {
+        return;
+    }
+
+    // Map the whole source pixels back to canvas space so the crop stays aligned.
+    let dest_x0 = placement.offset_x + src_x as f32 / src_per_px_x;
+    let dest_y0 = placement.offset_y + src_y as f32 / src_per_px_y;
+    let dest_w = ((src_x1 - src_x) as f32 / src_per_px_x).round() as u32;
+    let dest_h = ((src_y1 - src_y) as f32 / src_per_px_y).round() as u32;
+    if dest_w == 0 || dest_h == 0 {
+        return;
+    }
+    // Whole source pixels can still overhang the canvas; give up if that gets silly
+    // (a tiny image blown up so far that one pixel covers several canvases).
+    if dest_w as f32 > canvas_w * 4.0 || dest_h as f32 > canvas_h * 4.0 {
+        return;
+    }
+
+    let cropped = crop_imm(image, src_x, src_y, src_x1 - src_x, src_y1 - src_y).to_image();
+    let resized = resize(&cropped, dest_w, dest_h, FilterType::Triangle);
+    overlay_blended(
+        canvas,
+        &resized,
+        dest_x0.round() as i64,
+        dest_y0.round() as i64,
+        placement.blend_mode,
+    );
+}
+
+/// `imageops::overlay` with a blend mode. Normal goes straight to `overlay` so
+/// existing projects composite exactly as before.
+fn overlay_blended(canvas: &mut RgbaImage, top: &RgbaImage, x: i64, y: i64, mode: BlendMode) {
+    if mode == BlendMode::Normal {
+        overlay(canvas, top, x, y);
+        return;
+    }
+    let x0 = x.max(0);
+    let y0 = y.max(0);
+    let x1 = (x + top.width() as i64).min(canvas.width() as i64);
+    let y1 = (y + top.height() as i64).min(canvas.height() as i64);
+    for canvas_y in y0..y1 {
+        for canvas_x in x0..x1 {
+            let src = *top.get_pixel((canvas_x - x) as u32, (canvas_y - y) as u32);
+            let dst = canvas.get_pixel_mut(canvas_x as u32, canvas_y as u32);
+            *dst = blend_pixel(*dst, src, mode);
+        }
+    }
+}
+
+/// Blend one straight-alpha pixel over another.
+pub(crate) fn blend_pixel(dst: Rgba<u8>, src: Rgba<u8>, mode: BlendMode) -> Rgba<u8> {
+    let src_alpha = src.0[3] as f32 / 255.0;
+    if src_alpha <= 0.0 {
+        return dst;
+    }
+    let dst_alpha = dst.0[3] as f32 / 255.0;
+    let mut out = [0u8; 4];
+    for channel in 0..3 {
+        let value = blend_channel(
+            src.0[channel] as f32 / 255.0,
+            dst.0[channel] as f32 / 255.0,
+            src_alpha,
+            mode,
+        );
+        out[channel] = (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    }
+    let alpha = src_alpha + dst_alpha * (1.0 - src_alpha);
+    out[3] = (alpha.clamp(0.0, 1.0) * 255.0).round() as u8;
+    Rgba(out)
+}
+
+/// One colour channel of `src` (coverage `src_alpha`) blended onto `dst`, all in 0..1.
+///
+/// Add, Multiply and Screen are written so they equal the GPU's fixed-function
+/// blend states (see `preview_gpu::blend`); keep the two in sync.
+pub(crate) fn blend_channel(src: f32, dst: f32, src_alpha: f32, mode: BlendMode) -> f32 {
+    match mode {
+        BlendMode::Normal => src * src_alpha + dst * (1.0 - src_alpha),
+        BlendMode::Add => (dst + src * src_alpha).min(1.0),
+        BlendMode::Multiply => dst + (src * dst - dst) * src_alpha,
+        BlendMode::Screen => dst + src * (1.0 - dst) * src_alpha,
+        BlendMode::Overlay => {
+            let blended = if dst < 0.5 {
+                2.0 * src * dst
+            } else {
+                1.0 - 2.0 * (1.0 - src) * (1.0 - dst)
+            };
+            dst + (blended - dst) * src_alpha
+        }
+    }
 }
 
 pub(crate) fn rotate_rgba(image: &RgbaImage, rotation_deg: f32) -> RgbaImage {
@@ -158,9 +364,9 @@ pub(crate) fn compute_layer_placement(
 
     let base_scale_x = (source_w * preview_scale) / decoded_w;
     let base_scale_y = (source_h * preview_scale) / decoded_h;
-    let scaled_w = decoded_w * base_scale_x * transform.scale_x.max(0.01);
-    let scaled_h = decoded_h * base_scale_y * transform.scale_y.max(0.01);
-    if scaled_w <= 0.0 || scaled_h <= 0.0 {
+    let scaled_w = decoded_w * base_scale_x * transform.scale_x.clamp(MIN_CLIP_SCALE, MAX_CLIP_SCALE);
+    let scaled_h = decoded_h * base_scale_y * transform.scale_y.clamp(MIN_CLIP_SCALE, MAX_CLIP_SCALE);
+    if !scaled_w.is_finite() || !scaled_h.is_finite() || scaled_w <= 0.0 || scaled_h <= 0.0 {
         return None;
     }
 
@@ -175,6 +381,7 @@ pub(crate) fn compute_layer_placement(
         scaled_h,
         opacity,
         rotation_deg: transform.rotation_deg,
+        blend_mode: transform.blend_mode,
     })
 }
 
@@ -184,3 +391,111 @@ pub(crate) fn apply_opacity(image: &mut RgbaImage, opacity: f32) {
         pixel.0[3] = alpha;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layer_oversized() {
+        assert!(!layer_oversized(1920.0, 1080.0, MAX_LAYER_DIMENSION_PX));
+        assert!(!layer_oversized(MAX_LAYER_DIMENSION_PX, MAX_LAYER_DIMENSION_PX, MAX_LAYER_DIMENSION_PX));
+        assert!(layer_oversized(MAX_LAYER_DIMENSION_PX + 1.0, 10.0, MAX_LAYER_DIMENSION_PX));
+        assert!(layer_oversized(10.0, 192_000.0, MAX_LAYER_DIMENSION_PX));
+        assert!(layer_oversized(f32::INFINITY, 10.0, MAX_LAYER_DIMENSION_PX));
+        assert!(layer_oversized(10.0, f32::NAN, MAX_LAYER_DIMENSION_PX));
+    }
+
+    #[test]
+    fn test_extreme_scale_composites_within_the_canvas() {
+        let mut canvas = RgbaImage::from_pixel(64, 36, Rgba([0, 0, 0, 255]));
+        let image = RgbaImage::from_pixel(1600, 900, Rgba([255, 0, 0, 255]));
+        let transform = ClipTransform {
+            scale_x: MAX_CLIP_SCALE,
+            scale_y: MAX_CLIP_SCALE,
+            ..ClipTransform::default()
+        };
+        // 16000 x 9000 is past the limit, so only the visible crop is resized
+        composite_layer(&mut canvas, &image, 1600, 900, transform, 0.1);
+        assert_eq!(canvas.get_pixel(32, 18), &Rgba([255, 0, 0, 255]));
+    }
+
+    #[test]
+    fn test_checkerboard_shows_through_transparent_frame() {
+        let checker = checkerboard_image(32, 18, 4);
+        assert_eq!(checker.get_pixel(0, 0), &CHECKERBOARD_LIGHT);
+        assert_eq!(checker.get_pixel(4, 0), &CHECKERBOARD_DARK);
+        assert_eq!(checker.get_pixel(4, 4), &CHECKERBOARD_LIGHT);
+
+        let mut canvas = checker.clone();
+        let transparent = RgbaImage::from_pixel(32, 18, Rgba([255, 0, 0, 0]));
+        composite_layer(&mut canvas, &transparent, 32, 18, ClipTransform::default(), 1.0);
+        assert_eq!(canvas, checker);
+    }
+
+    #[test]
+    fn test_opaque_frame_covers_checkerboard() {
+        let mut canvas = checkerboard_image(32, 18, 4);
+        let opaque = RgbaImage::from_pixel(32, 18, Rgba([200, 100, 50, 255]));
+        composite_layer(&mut canvas, &opaque, 32, 18, ClipTransform::default(), 1.0);
+        assert!(canvas.pixels().all(|pixel| *pixel == Rgba([200, 100, 50, 255])));
+    }
+
+    fn blended(dst: [u8; 4], src: [u8; 4], mode: BlendMode) -> [u8; 4] {
+        blend_pixel(Rgba(dst), Rgba(src), mode).0
+    }
+
+    #[test]
+    fn test_blend_modes_on_known_pixels() {
+        let dst = [128, 64, 255, 255];
+        let src = [128, 255, 0, 255];
+        assert_eq!(blended(dst, src, BlendMode::Normal), [128, 255, 0, 255]);
+        assert_eq!(blended(dst, src, BlendMode::Add), [255, 255, 255, 255]);
+        assert_eq!(blended(dst, src, BlendMode::Multiply), [64, 64, 0, 255]);
+        assert_eq!(blended(dst, src, BlendMode::Screen), [192, 255, 255, 255]);
+        // Overlay keys off the destination: dark channels multiply, light ones screen.
+        assert_eq!(blended(dst, src, BlendMode::Overlay), [128, 128, 255, 255]);
+        assert_eq!(blended([64, 0, 0, 255], [128, 0, 0, 255], BlendMode::Overlay)[0], 64);
+        assert_eq!(blended([192, 0, 0, 255], [128, 0, 0, 255], BlendMode::Overlay)[0], 192);
+    }
+
+    #[test]
+    fn test_blend_modes_respect_source_alpha() {
+        let dst = [100, 100, 100, 255];
+        for mode in BlendMode::ALL {
+            assert_eq!(blended(dst, [255, 0, 10, 0], mode), dst);
+        }
+        // Half coverage lands halfway between the backdrop and the full blend.
+        assert_eq!(blended(dst, [200, 200, 200, 128], BlendMode::Add), [200, 200, 200, 255]);
+        assert_eq!(blended(dst, [0, 0, 0, 128], BlendMode::Multiply), [50, 50, 50, 255]);
+    }
+
+    #[test]
+    fn test_composite_layer_applies_blend_mode() {
+        let mut canvas = RgbaImage::from_pixel(8, 8, Rgba([100, 150, 200, 255]));
+        let layer = RgbaImage::from_pixel(4, 4, Rgba([255, 255, 255, 255]));
+        let transform = ClipTransform {
+            blend_mode: BlendMode::Multiply,
+            ..ClipTransform::default()
+        };
+        composite_layer(&mut canvas, &layer, 4, 4, transform, 1.0);
+        // White multiplies to the backdrop everywhere
+        assert!(canvas.pixels().all(|pixel| *pixel == Rgba([100, 150, 200, 255])));
+
+        let transform = ClipTransform {
+            blend_mode: BlendMode::Screen,
+            ..ClipTransform::default()
+        };
+        composite_layer(&mut canvas, &layer, 4, 4, transform, 1.0);
+        assert_eq!(canvas.get_pixel(4, 4), &Rgba([255, 255, 255, 255]));
+        assert_eq!(canvas.get_pixel(0, 0), &Rgba([100, 150, 200, 255]));
+    }
+
+    #[test]
+    fn test_checkerboard_cell_follows_pixel_ratio() {
+        assert_eq!(checkerboard_cell_px(1.0), 8);
+        assert_eq!(checkerboard_cell_px(2.0), 16);
+        assert_eq!(checkerboard_cell_px(1.25), 10);
+        assert_eq!(checkerboard_cell_px(0.0), 8);
+    }
+}
@@ -4,11 +4,18 @@
 
 mod renderer;
 mod cache;
+mod scrub_ring;
 mod layers;
+mod guides;
 mod types;
 mod utils;
 
 pub use renderer::PreviewRenderer;
+pub use guides::{guide_lines, GuideLine, MAX_GUIDE_LINES};
+pub use layers::{checkerboard_cell_px, effective_preview_limits, layer_oversized};
+#[cfg(test)]
+pub(crate) use layers::blend_channel;
 #[allow(unused_imports)]
 pub use cache::FrameCache;
 pub use types::*;
+pub use utils::visual_source_path;
@@ -1,28 +1,31 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
 use image::{Rgba, RgbaImage};
 
 use crate::core::media::probe_duration_seconds;
+use crate::core::preview_gpu::supports_blend_mode;
 use crate::core::preview_store;
 use crate::core::video_decode::{DecodeMode, VideoDecodeWorker};
-use crate::state::{Asset, AssetKind, Project, TrackType};
+use crate::state::{Asset, AssetKind, BlendMode, Project, TrackType};
 
 use super::{
     cache::FrameCache,
     layers::{
-        composite_layer, compute_layer_placement, preview_canvas_size, DecodedFrame, PendingDecode,
+        checkerboard_image, composite_layer, compute_layer_placement, preview_canvas_size, DecodedFrame, PendingDecode,
         PreviewLayer,
     },
+    scrub_ring::ScrubRing,
     types::{
-        FrameKey, PlateCache, PreviewDecodeMode, PreviewFrameInfo, PreviewLayerGpu,
+        CachedFrame, FrameKey, PlateCache, PreviewDecodeMode, PreviewFrameInfo, PreviewLayerGpu,
         PreviewLayerPlacement, PreviewLayerStack, PreviewStats, RenderOutput, MAX_CACHE_BUCKETS,
-        PLATE_BORDER_COLOR, PLATE_BORDER_WIDTH,
+        PLATE_BORDER_COLOR, PLATE_BORDER_WIDTH, SCRUB_RING_FRAMES,
     },
     utils::{
-        clamp_time, draw_border, elapsed_ms, frame_index_to_time, resolve_asset_source,
+        clamp_time, draw_border, elapsed_ms, frame_index_to_time, resolve_preview_source,
         scale_image_to_fit, time_to_frame_index, track_lane_id,
     },
 };
@@ -34,8 +37,22 @@ pub struct PreviewRenderer {
     max_height: u32,
     video_decoder: VideoDecodeWorker,
     frame_cache: Mutex<FrameCache>,
+    scrub_ring: Mutex<ScrubRing>,
     duration_cache: Mutex<HashMap<PathBuf, Option<f64>>>,
     plate_cache: Mutex<Option<PlateCache>>,
+    /// Checker cell size behind the preview composite; 0 draws the project background.
+    checkerboard_cell: AtomicU32,
+}
+
+/// Which frame of the timeline to collect layers for, and how to decode it.
+struct LayerRequest<'a> {
+    project: &'a Project,
+    project_root: &'a Path,
+    time_seconds: f64,
+    fps: f64,
+    decode_mode: PreviewDecodeMode,
+    allow_hw_decode: bool,
+    use_proxies: bool,
 }
 
 impl PreviewRenderer {
@@ -54,8 +71,30 @@ impl PreviewRenderer {
             max_height,
             video_decoder: VideoDecodeWorker::new(max_width, max_height),
             frame_cache: Mutex::new(FrameCache::new(max_cache_bytes)),
+            scrub_ring: Mutex::new(ScrubRing::new(SCRUB_RING_FRAMES)),
             duration_cache: Mutex::new(HashMap::new()),
             plate_cache: Mutex::new(None),
+            checkerboard_cell: AtomicU32::new(0),
+        }
+    }
+
+    /// Preview bounds this renderer scales decoded frames into.
+    pub fn limits(&self) -> (u32, u32) {
+        (self.max_width, self.max_height)
+    }
+
+    /// Show a checkerboard with `cell_px` cells behind the preview instead of
+    /// the project background. Returns true when the setting changed. Exports
+    /// always use the background color.
+    pub fn set_checkerboard(&self, cell_px: Option<u32>) -> bool {
+        let cell = cell_px.unwrap_or(0);
+        self.checkerboard_cell.swap(cell, Ordering::Relaxed) != cell
+    }
+
+    fn preview_background(&self, width: u32, height: u32, background: [u8; 4]) -> RgbaImage {
+        match self.checkerboard_cell.load(Ordering::Relaxed) {
+            0 => RgbaImage::from_pixel(width, height, Rgba(background)),
+            cell => checkerboard_image(width, height, cell),
         }
     }
 
@@ -63,6 +102,27 @@ impl PreviewRenderer {
         if let Ok(mut cache) = self.frame_cache.lock() {
             cache.invalidate_folder(folder);
         }
+        if let Ok(mut ring) = self.scrub_ring.lock() {
+            ring.clear();
+        }
+    }
+
+    /// Drop every cached decoded frame.
+    pub fn clear_cache(&self) {
+        if let Ok(mut cache) = self.frame_cache.lock() {
+            cache.clear();
+        }
+        if let Ok(mut ring) = self.scrub_ring.lock() {
+            ring.clear();
+        }
+    }
+
+    /// Decoded-frame cache usage as `(used_bytes, budget_bytes)`.
+    pub fn cache_usage(&self) -> (usize, usize) {
+        self.frame_cache
+            .lock()
+            .map(|cache| (cache.total_bytes(), cache.max_bytes()))
+            .unwrap_or((0, 0))
     }
 
     fn cached_video_duration(&self, path: &Path) -> Option<f64> {
@@ -121,12 +181,15 @@ impl PreviewRenderer {
         let fps = project.settings.fps.max(1.0);
         let collect_start = Instant::now();
         let layers = self.collect_layers(
-            project,
-            project_root,
-            time_seconds,
-            fps,
-            decode_mode,
-            allow_hw_decode,
+            LayerRequest {
+                project,
+                project_root,
+                time_seconds,
+                fps,
+                decode_mode,
+                allow_hw_decode,
+                use_proxies: project.settings.use_proxies,
+            },
             &mut stats,
         );
         stats.collect_ms = elapsed_ms(collect_start);
@@ -148,7 +211,8 @@ impl PreviewRenderer {
             };
         }
 
-        let mut canvas = RgbaImage::from_pixel(canvas_w, canvas_h, Rgba([0, 0, 0, 255]));
+        let mut canvas =
+            self.preview_background(canvas_w, canvas_h, project.settings.background_color);
 
         let composite_start = Instant::now();
         for layer in layers {
@@ -182,6 +246,62 @@ impl PreviewRenderer {
         }
     }
 
+    /// Composite one frame at full project resolution for export. Unlike
+    /// `render_frame` this skips the preview store and plate border, and always
+    /// returns a frame (the project background where nothing is on screen).
+    pub fn render_export_frame(&self, project: &Project, time_seconds: f64) -> RgbaImage {
+        let mut stats = PreviewStats::default();
+        let project_root = project
+            .project_path
+            .as_ref()
+            .unwrap_or(&self.project_root);
+        let width = project.settings.width.max(1);
+        let height = project.settings.height.max(1);
+        let (canvas_w, canvas_h, scale) = preview_canvas_size(width, height, width, height);
+
+        let fps = project.settings.fps.max(1.0);
+        let layers = self.collect_layers(
+            LayerRequest {
+                project,
+                project_root,
+                time_seconds,
+                fps,
+                decode_mode: PreviewDecodeMode::Sequential,
+                allow_hw_decode: false,
+                // Exports always render from the originals
+                use_proxies: false,
+            },
+            &mut stats,
+        );
+        let mut canvas =
+            RgbaImage::from_pixel(canvas_w, canvas_h, Rgba(project.settings.background_color));
+        for layer in layers {
+            composite_layer(
+                &mut canvas,
+                &layer.image,
+                layer.source_width,
+                layer.source_height,
+                layer.transform,
+                scale,
+            );
+        }
+        canvas
+    }
+
+    /// Composite the frame at `time_seconds` at project resolution and write it
+    /// to `path` as a PNG.
+    pub fn save_frame_png(
+        &self,
+        project: &Project,
+        time_seconds: f64,
+        path: &Path,
+    ) -> Result<(), String> {
+        let frame = self.render_export_frame(project, time_seconds);
+        frame
+            .save_with_format(path, image::ImageFormat::Png)
+            .map_err(|err| format!("Failed to write {}: {}", path.display(), err))
+    }
+
     /// Render the per-layer stack for GPU compositing.
     pub fn render_layers(
         &self,
@@ -207,12 +327,15 @@ impl PreviewRenderer {
         let fps = project.settings.fps.max(1.0);
         let collect_start = Instant::now();
         let layers = self.collect_layers(
-            project,
-            project_root,
-            time_seconds,
-            fps,
-            decode_mode,
-            allow_hw_decode,
+            LayerRequest {
+                project,
+                project_root,
+                time_seconds,
+                fps,
+                decode_mode,
+                allow_hw_decode,
+                use_proxies: project.settings.use_proxies,
+            },
             &mut stats,
         );
         stats.collect_ms = elapsed_ms(collect_start);
@@ -234,17 +357,58 @@ impl PreviewRenderer {
             };
         }
 
-        let mut gpu_layers = Vec::new();
-        // Add the black fill plate as the first layer (canvas background)
-        if let Some((plate_fill, _border)) = self.plate_images(canvas_w, canvas_h) {
-            let placement = PreviewLayerPlacement {
-                offset_x: 0.0,
-                offset_y: 0.0,
-                scaled_w: canvas_w as f32,
-                scaled_h: canvas_h as f32,
-                opacity: 1.0,
-                rotation_deg: 0.0,
+        let full_canvas = PreviewLayerPlacement {
+            offset_x: 0.0,
+            offset_y: 0.0,
+            scaled_w: canvas_w as f32,
+            scaled_h: canvas_h as f32,
+            opacity: 1.0,
+            rotation_deg: 0.0,
+            blend_mode: BlendMode::Normal,
+        };
+
+        // Modes the GPU can't blend with fixed-function state (Overlay reads the
+        // backdrop per channel) flatten the whole stack on the CPU instead, so the
+        // two preview paths always agree.
+        if layers
+            .iter()
+            .any(|layer| !supports_blend_mode(layer.transform.blend_mode))
+        {
+            let composite_start = Instant::now();
+            let mut canvas =
+            self.preview_background(canvas_w, canvas_h, project.settings.background_color);
+            for layer in layers {
+                composite_layer(
+                    &mut canvas,
+                    &layer.image,
+                    layer.source_width,
+                    layer.source_height,
+                    layer.transform,
+                    preview_scale,
+                );
+            }
+            stats.composite_ms = elapsed_ms(composite_start);
+            stats.total_ms = elapsed_ms(render_start);
+            return RenderOutput {
+                frame: None,
+                layers: Some(PreviewLayerStack {
+                    canvas_width: canvas_w,
+                    canvas_height: canvas_h,
+                    layers: vec![PreviewLayerGpu {
+                        image: Arc::new(canvas),
+                        placement: full_canvas,
+                    }],
+                }),
+                stats,
             };
+        }
+
+        let mut gpu_layers = Vec::new();
+        // Add the fill plate (black or checkerboard) as the first layer (canvas background)
+        if let Some((plate_fill, _border)) =
+            self.plate_images(canvas_w, canvas_h, project.settings.background_color)
+        {
+            let placement = full_canvas;
             gpu_layers.push(PreviewLayerGpu {
                 image: plate_fill,
                 placement,
@@ -283,21 +447,23 @@ impl PreviewRenderer {
         }
     }
 
-    fn collect_layers(
-        &self,
-        project: &Project,
-        project_root: &Path,
-        time_seconds: f64,
-        fps: f64,
-        decode_mode: PreviewDecodeMode,
-        allow_hw_decode: bool,
-        stats: &mut PreviewStats,
-    ) -> Vec<PreviewLayer> {
+    fn collect_layers(&self, request: LayerRequest, stats: &mut PreviewStats) -> Vec<PreviewLayer> {
+        let LayerRequest {
+            project,
+            project_root,
+            time_seconds,
+            fps,
+            decode_mode,
+            allow_hw_decode,
+            use_proxies,
+        } = request;
         let mut track_order: HashMap<uuid::Uuid, usize> = HashMap::new();
         let mut video_tracks = 0;
         for track in project.tracks.iter() {
             if track.track_type == TrackType::Video {
-                track_order.insert(track.id, video_tracks);
+                if project.track_is_active(track.id) {
+                    track_order.insert(track.id, video_tracks);
+                }
                 video_tracks += 1;
             }
         }
@@ -307,6 +473,21 @@ impl PreviewRenderer {
             PreviewDecodeMode::Sequential => DecodeMode::Sequential,
         };
 
+        // The top-most video clip under the playhead owns the scrub ring.
+        let scrub_focus = project
+            .clips
+            .iter()
+            .filter(|clip| time_seconds >= clip.start_time && time_seconds < clip.end_time())
+            .filter(|clip| {
+                project
+                    .find_asset(clip.asset_id)
+                    .map(|asset| asset.is_video())
+                    .unwrap_or(false)
+            })
+            .filter_map(|clip| track_order.get(&clip.track_id).map(|index| (*index, clip.id)))
+            .min_by_key(|(index, _)| *index)
+            .map(|(_, clip_id)| clip_id);
+
         let mut layers = Vec::new();
         let mut pending = Vec::new();
         for clip in project.clips.iter() {
@@ -324,13 +505,11 @@ impl PreviewRenderer {
                 _ => continue,
             };
 
-            let source_time = (time_seconds - clip.start_time + clip.trim_in_seconds).max(0.0);
-            let Some((path, is_video, duration)) = resolve_asset_source(
-                project_root,
-                asset,
-                &["png", "jpg", "jpeg", "webp"],
-                &["mp4", "mov", "mkv", "webm"],
-            ) else {
+            let source_time = clip.source_time_at(time_seconds);
+            let clip_transform = clip.transform_at(time_seconds);
+            let Some((path, is_video, duration, source_size)) =
+                resolve_preview_source(project_root, asset, use_proxies)
+            else {
                 continue;
             };
 
@@ -350,14 +529,37 @@ impl PreviewRenderer {
                 frame_index,
             };
 
+            let is_scrub_focus = is_video && scrub_focus == Some(clip.id);
+            if is_scrub_focus {
+                if let Ok(mut ring) = self.scrub_ring.lock() {
+                    ring.focus(clip.id, &path, clip.transform);
+                    if let Some(cached) = ring.get(frame_index) {
+                        stats.scrub_ring_hits += 1;
+                        stats.cache_hits += 1;
+                        layers.push(PreviewLayer {
+                            track_index,
+                            start_time: clip.start_time,
+                            image: cached.image,
+                            transform: clip_transform,
+                            source_width: cached.source_width,
+                            source_height: cached.source_height,
+                        });
+                        continue;
+                    }
+                }
+            }
+
             if let Ok(mut cache) = self.frame_cache.lock() {
                 if let Some(cached) = cache.get(&cache_key) {
                     stats.cache_hits += 1;
+                    if is_scrub_focus {
+                        self.remember_scrub_frame(clip.id, frame_index, &cached);
+                    }
                     layers.push(PreviewLayer {
                         track_index,
                         start_time: clip.start_time,
                         image: cached.image,
-                        transform: clip.transform,
+                        transform: clip_transform,
                         source_width: cached.source_width,
                         source_height: cached.source_height,
                     });
@@ -374,21 +576,18 @@ impl PreviewRenderer {
                 stats.still_load_ms += decode_ms;
                 if let Some(decoded) = decoded {
                     let image = Arc::new(decoded.image);
+                    let (source_width, source_height) =
+                        source_size.unwrap_or((decoded.source_width, decoded.source_height));
                     if let Ok(mut cache) = self.frame_cache.lock() {
-                        cache.insert(
-                            cache_key,
-                            Arc::clone(&image),
-                            decoded.source_width,
-                            decoded.source_height,
-                        );
+                        cache.insert(cache_key, Arc::clone(&image), source_width, source_height);
                     }
                     layers.push(PreviewLayer {
                         track_index,
                         start_time: clip.start_time,
                         image,
-                        transform: clip.transform,
-                        source_width: decoded.source_width,
-                        source_height: decoded.source_height,
+                        transform: clip_transform,
+                        source_width,
+                        source_height,
                     });
                 }
                 continue;
@@ -400,8 +599,10 @@ impl PreviewRenderer {
                 path,
                 frame_time,
                 cache_key,
-                transform: clip.transform,
+                transform: clip_transform,
                 lane_id: track_lane_id(clip.track_id),
+                scrub_focus: is_scrub_focus.then_some(clip.id),
+                source_size,
             });
         }
 
@@ -430,14 +631,23 @@ impl PreviewRenderer {
                     stats.video_decode_copy_ms += timings.copy_ms;
                     if let Some(image) = response.image {
                         let image = Arc::new(image);
-                        if let Ok(mut cache) = self.frame_cache.lock() {
-                            cache.insert(
-                                item.cache_key,
-                                Arc::clone(&image),
-                                response.source_width,
-                                response.source_height,
+                        let (source_width, source_height) = item
+                            .source_size
+                            .unwrap_or((response.source_width, response.source_height));
+                        if let Some(clip_id) = item.scrub_focus {
+                            self.remember_scrub_frame(
+                                clip_id,
+                                item.cache_key.frame_index,
+                                &CachedFrame {
+                                    image: Arc::clone(&image),
+                                    source_width,
+                                    source_height,
+                                },
                             );
                         }
+                        if let Ok(mut cache) = self.frame_cache.lock() {
+                            cache.insert(item.cache_key, Arc::clone(&image), source_width, source_height);
+                        }
                         if response.used_hw {
                             stats.hw_decode_frames += 1;
                         } else {
@@ -448,8 +658,8 @@ impl PreviewRenderer {
                             start_time: item.start_time,
                             image,
                             transform: item.transform,
-                            source_width: response.source_width,
-                            source_height: response.source_height,
+                            source_width,
+                            source_height,
                         });
                     }
                 }
@@ -465,6 +675,14 @@ impl PreviewRenderer {
         layers
     }
 
+    fn remember_scrub_frame(&self, clip_id: uuid::Uuid, frame_index: i64, frame: &CachedFrame) {
+        if let Ok(mut ring) = self.scrub_ring.lock() {
+            if ring.is_focused(clip_id) {
+                ring.insert(frame_index, frame.clone());
+            }
+        }
+    }
+
     pub fn prefetch_frames(
         &self,
         project: &Project,
@@ -496,13 +714,16 @@ impl PreviewRenderer {
                 if frame_time < clip.start_time || frame_time >= clip.end_time() {
                     continue;
                 }
+                if !project.track_is_active(clip.track_id) {
+                    continue;
+                }
 
                 let asset = match project.find_asset(clip.asset_id) {
                     Some(asset) if asset.is_visual() => asset,
                     _ => continue,
                 };
 
-                let source_time = (frame_time - clip.start_time + clip.trim_in_seconds).max(0.0);
+                let source_time = clip.source_time_at(frame_time);
                 let _ = self.load_clip_frame(
                     project_root,
                     asset,
@@ -511,6 +732,7 @@ impl PreviewRenderer {
                     decode_mode,
                     track_lane_id(clip.track_id),
                     allow_hw_decode,
+                    project.settings.use_proxies,
                     None,
                 );
             }
@@ -543,12 +765,9 @@ impl PreviewRenderer {
                 continue;
             }
 
-            let Some((path, is_video, _duration)) = resolve_asset_source(
-                project_root,
-                asset,
-                &["png", "jpg", "jpeg", "webp"],
-                &["mp4", "mov", "mkv", "webm"],
-            ) else {
+            let Some((path, is_video, _duration, _)) =
+                resolve_preview_source(project_root, asset, project.settings.use_proxies)
+            else {
                 continue;
             };
 
@@ -567,8 +786,13 @@ impl PreviewRenderer {
                 continue;
             };
 
-            if !is_video {
-                let cached = asset_frames.contains(&0);
+            if !is_video || clip.is_frozen() {
+                let frame_index = if is_video {
+                    time_to_frame_index(clip.freeze_at.unwrap_or_default(), fps)
+                } else {
+                    0
+                };
+                let cached = asset_frames.contains(&frame_index);
                 for bucket in buckets.iter_mut() {
                     *bucket = cached;
                 }
@@ -577,16 +801,26 @@ impl PreviewRenderer {
             }
 
             let clip_start = clip.trim_in_seconds.max(0.0);
-            let clip_end = clip_start + clip_duration;
+            let clip_end = clip_start + clip.source_duration();
             for frame_index in asset_frames.iter() {
                 let frame_time = frame_index_to_time(*frame_index, fps);
                 if frame_time < clip_start || frame_time > clip_end {
                     continue;
                 }
-                let time_in_clip = (frame_time - clip_start).max(0.0);
-                let bucket_index = (time_in_clip / bucket_seconds).floor() as usize;
-                if let Some(bucket) = buckets.get_mut(bucket_index) {
-                    *bucket = true;
+                let mut time_in_clip = clip.timeline_duration_for_source((frame_time - clip_start).max(0.0));
+                // A looping clip shows the same source frame once per cycle
+                let cycle = clip.loop_length().map(|cycle| clip.timeline_duration_for_source(cycle));
+                loop {
+                    let bucket_index = (time_in_clip / bucket_seconds).floor() as usize;
+                    if let Some(bucket) = buckets.get_mut(bucket_index) {
+                        *bucket = true;
+                    }
+                    match cycle {
+                        Some(cycle) if cycle > 0.0 && time_in_clip + cycle < clip.duration => {
+                            time_in_clip += cycle;
+                        }
+                        _ => break,
+                    }
                 }
             }
 
@@ -605,10 +839,11 @@ impl PreviewRenderer {
         decode_mode: PreviewDecodeMode,
         lane_id: u64,
         allow_hw_decode: bool,
+        use_proxies: bool,
         mut stats: Option<&mut PreviewStats>,
     ) -> Option<Arc<RgbaImage>> {
-        let (path, is_video, duration) =
-            resolve_asset_source(project_root, asset, &["png", "jpg", "jpeg", "webp"], &["mp4", "mov", "mkv", "webm"])?;
+        let (path, is_video, duration, source_size) =
+            resolve_preview_source(project_root, asset, use_proxies)?;
 
         let (frame_index, frame_time) = if is_video {
             let (mapped_time, clamp_duration) =
@@ -690,13 +925,10 @@ impl PreviewRenderer {
         };
 
         let image = Arc::new(decoded.image);
+        let (source_width, source_height) =
+            source_size.unwrap_or((decoded.source_width, decoded.source_height));
         if let Ok(mut cache) = self.frame_cache.lock() {
-            cache.insert(
-                cache_key,
-                Arc::clone(&image),
-                decoded.source_width,
-                decoded.source_height,
-            );
+            cache.insert(cache_key, Arc::clone(&image), source_width, source_height);
         }
 
         Some(image)
@@ -718,23 +950,29 @@ impl PreviewRenderer {
 }
 
 impl PreviewRenderer {
-    fn plate_images(&self, width: u32, height: u32) -> Option<(Arc<RgbaImage>, Arc<RgbaImage>)> {
+    fn plate_images(
+        &self,
+        width: u32,
+        height: u32,
+        background: [u8; 4],
+    ) -> Option<(Arc<RgbaImage>, Arc<RgbaImage>)> {
         if width == 0 || height == 0 {
             return None;
         }
 
+        let checker_cell = self.checkerboard_cell.load(Ordering::Relaxed);
         if let Ok(mut cache) = self.plate_cache.lock() {
             if let Some(entry) = cache.as_ref() {
-                if entry.width == width && entry.height == height {
+                if entry.width == width
+                    && entry.height == height
+                    && entry.checker_cell == checker_cell
+                    && entry.background == background
+                {
                     return Some((Arc::clone(&entry.fill), Arc::clone(&entry.border)));
                 }
             }
 
-            let fill = Arc::new(RgbaImage::from_pixel(
-                width,
-                height,
-                Rgba([0, 0, 0, 255]),
-            ));
+            let fill = Arc::new(self.preview_background(width, height, background));
             let mut border = RgbaImage::from_pixel(width, height, Rgba([0, 0, 0, 0]));
             draw_border(&mut border, PLATE_BORDER_COLOR, PLATE_BORDER_WIDTH);
 
@@ -742,6 +980,8 @@ impl PreviewRenderer {
             *cache = Some(PlateCache {
                 width,
                 height,
+                checker_cell,
+                background,
                 fill: Arc::clone(&fill),
                 border: Arc::clone(&border),
             });
@@ -751,3 +991,41 @@ impl PreviewRenderer {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_frame_matches_project_resolution() {
+        let mut project = Project::default();
+        project.settings.width = 64;
+        project.settings.height = 36;
+        let renderer = PreviewRenderer::new_with_limits(std::env::temp_dir(), 1024 * 1024, 64, 36);
+
+        let frame = renderer.render_export_frame(&project, 0.0);
+        assert_eq!(frame.dimensions(), (64, 36));
+        assert_eq!(frame.as_raw().len(), 64 * 36 * 4);
+        // Empty timelines still produce an opaque frame
+        assert!(frame.pixels().all(|pixel| pixel.0[3] == 255));
+    }
+
+    #[test]
+    fn test_empty_timeline_renders_background_color() {
+        let mut project = Project::default();
+        project.settings.width = 32;
+        project.settings.height = 18;
+        project.settings.background_color = [0, 177, 64, 255];
+        let renderer = PreviewRenderer::new_with_limits(std::env::temp_dir(), 1024 * 1024, 32, 18);
+
+        let frame = renderer.render_export_frame(&project, 0.0);
+        assert!(frame.pixels().all(|pixel| pixel.0 == [0, 177, 64, 255]));
+
+        // The preview plate uses it too, unless the checkerboard is on
+        let (fill, _) = renderer.plate_images(32, 18, project.settings.background_color).unwrap();
+        assert!(fill.pixels().all(|pixel| pixel.0 == [0, 177, 64, 255]));
+        renderer.set_checkerboard(Some(4));
+        let (fill, _) = renderer.plate_images(32, 18, project.settings.background_color).unwrap();
+        assert_ne!(fill.get_pixel(0, 0).0, [0, 177, 64, 255]);
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+
+use crate::state::ClipTransform;
+
+use super::CachedFrame;
+
+/// The clip a scrub ring currently holds frames for.
+#[derive(Clone, Debug, PartialEq)]
+struct ScrubOwner {
+    clip_id: uuid::Uuid,
+    path: PathBuf,
+    transform: ClipTransform,
+}
+
+/// Small window of recently decoded frames around the playhead for the focused clip.
+///
+/// Sits in front of the shared frame cache so back-and-forth scrubbing inside one clip
+/// never waits on the decoder for frames it has just shown. The window is dropped
+/// whenever focus moves to another clip or the focused clip's source/transform changes.
+pub(crate) struct ScrubRing {
+    capacity: usize,
+    owner: Option<ScrubOwner>,
+    center: i64,
+    frames: VecDeque<(i64, CachedFrame)>,
+}
+
+impl ScrubRing {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            owner: None,
+            center: 0,
+            frames: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Point the ring at a clip, evicting everything if it differs from the current owner.
+    pub(crate) fn focus(&mut self, clip_id: uuid::Uuid, path: &Path, transform: ClipTransform) {
+        let matches = self.owner.as_ref().is_some_and(|owner| {
+            owner.clip_id == clip_id && owner.path == path && owner.transform == transform
+        });
+        if matches {
+            return;
+        }
+        self.frames.clear();
+        self.owner = Some(ScrubOwner {
+            clip_id,
+            path: path.to_path_buf(),
+            transform,
+        });
+    }
+
+    pub(crate) fn is_focused(&self, clip_id: uuid::Uuid) -> bool {
+        self.owner
+            .as_ref()
+            .is_some_and(|owner| owner.clip_id == clip_id)
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.frames.clear();
+        self.owner = None;
+    }
+
+    pub(crate) fn get(&mut self, frame_index: i64) -> Option<CachedFrame> {
+        self.center = frame_index;
+        self.frames
+            .iter()
+            .find(|(index, _)| *index == frame_index)
+            .map(|(_, frame)| frame.clone())
+    }
+
+    /// Store a frame, evicting whichever held frame is farthest from the playhead.
+    pub(crate) fn insert(&mut self, frame_index: i64, frame: CachedFrame) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.center = frame_index;
+        if let Some(slot) = self
+            .frames
+            .iter_mut()
+            .find(|(index, _)| *index == frame_index)
+        {
+            slot.1 = frame;
+            return;
+        }
+        if self.frames.len() >= self.capacity {
+            let center = self.center;
+            if let Some(farthest) = self
+                .frames
+                .iter()
+                .enumerate()
+                .max_by_key(|(_, (index, _))| (index - center).abs())
+                .map(|(position, _)| position)
+            {
+                self.frames.remove(farthest);
+            }
+        }
+        self.frames.push_back((frame_index, frame));
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.frames.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::RgbaImage;
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    fn frame() -> CachedFrame {
+        CachedFrame {
+            image: Arc::new(RgbaImage::new(4, 4)),
+            source_width: 4,
+            source_height: 4,
+        }
+    }
+
+    #[test]
+    fn test_focus_change_evicts() {
+        let clip = uuid::Uuid::new_v4();
+        let path = PathBuf::from("video/a.mp4");
+        let mut ring = ScrubRing::new(8);
+        ring.focus(clip, &path, ClipTransform::default());
+        ring.insert(10, frame());
+        assert!(ring.get(10).is_some());
+
+        // Same clip and transform keeps frames.
+        ring.focus(clip, &path, ClipTransform::default());
+        assert!(ring.get(10).is_some());
+
+        let moved = ClipTransform {
+            position_x: 12.0,
+            ..ClipTransform::default()
+        };
+        ring.focus(clip, &path, moved);
+        assert!(ring.get(10).is_none());
+
+        ring.insert(10, frame());
+        ring.focus(uuid::Uuid::new_v4(), &path, moved);
+        assert!(ring.get(10).is_none());
+    }
+
+    #[test]
+    fn test_evicts_farthest_from_playhead() {
+        let mut ring = ScrubRing::new(3);
+        ring.focus(uuid::Uuid::new_v4(), Path::new("a.mp4"), ClipTransform::default());
+        ring.insert(0, frame());
+        ring.insert(1, frame());
+        ring.insert(2, frame());
+        ring.insert(3, frame());
+        assert_eq!(ring.len(), 3);
+        assert!(ring.get(0).is_none());
+        assert!(ring.get(3).is_some());
+        assert!(ring.get(1).is_some());
+    }
+
+    /// Benchmark-style scrub: sweep back and forth over a short range with a decoder
+    /// that costs a fixed amount per frame, with and without the ring in front of it.
+    #[test]
+    #[ignore = "timing benchmark; run with --ignored"]
+    fn test_scrub_bench_back_and_forth() {
+        const WINDOW: i64 = 24;
+        const PASSES: usize = 6;
+        let decode_cost = Duration::from_millis(1);
+        let clip = uuid::Uuid::new_v4();
+        let path = PathBuf::from("video/scrub.mp4");
+
+        let sweep: Vec<i64> = (0..PASSES)
+            .flat_map(|pass| {
+                let forward: Vec<i64> = (0..WINDOW).collect();
+                if pass % 2 == 0 {
+                    forward
+                } else {
+                    forward.into_iter().rev().collect()
+                }
+            })
+            .collect();
+
+        let decode = |decodes: &mut usize| {
+            *decodes += 1;
+            std::thread::sleep(decode_cost);
+            frame()
+        };
+
+        let mut uncached_decodes = 0;
+        let uncached_start = Instant::now();
+        for _ in sweep.iter() {
+            let _ = decode(&mut uncached_decodes);
+        }
+        let uncached = uncached_start.elapsed();
+
+        let mut ring = ScrubRing::new(WINDOW as usize * 2);
+        let mut ring_decodes = 0;
+        let ring_start = Instant::now();
+        for index in sweep.iter().copied() {
+            ring.focus(clip, &path, ClipTransform::default());
+            if ring.get(index).is_none() {
+                let frame = decode(&mut ring_decodes);
+                ring.insert(index, frame);
+            }
+        }
+        let with_ring = ring_start.elapsed();
+
+        assert_eq!(ring_decodes, WINDOW as usize);
+        assert_eq!(uncached_decodes, sweep.len());
+        assert!(with_ring < uncached);
+    }
+}
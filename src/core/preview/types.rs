@@ -3,8 +3,12 @@ use std::sync::Arc;
 
 use image::{Rgba, RgbaImage};
 
+use crate::state::BlendMode;
+
 pub const FFMPEG_TIME_EPSILON: f64 = 0.001;
 pub const MAX_CACHE_BUCKETS: usize = 120;
+/// Decoded frames kept around the playhead for the focused clip while scrubbing.
+pub const SCRUB_RING_FRAMES: usize = 48;
 pub const PLATE_BORDER_WIDTH: u32 = 1;
 pub const PLATE_BORDER_COLOR: Rgba<u8> = Rgba([0x27, 0x27, 0x2a, 255]);
 
@@ -26,6 +30,7 @@ pub struct PreviewStats {
     pub layers: usize,
     pub cache_hits: usize,
     pub cache_misses: usize,
+    pub scrub_ring_hits: usize,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -49,6 +54,7 @@ pub struct PreviewLayerPlacement {
     pub scaled_h: f32,
     pub opacity: f32,
     pub rotation_deg: f32,
+    pub blend_mode: BlendMode,
 }
 
 #[derive(Clone, Debug)]
@@ -87,6 +93,10 @@ pub struct CachedFrame {
 pub(crate) struct PlateCache {
     pub width: u32,
     pub height: u32,
+    /// Checker cell size the fill was drawn with, 0 for the solid background.
+    pub checker_cell: u32,
+    /// Project background color the fill was drawn with.
+    pub background: [u8; 4],
     pub fill: Arc<RgbaImage>,
     pub border: Arc<RgbaImage>,
 }
@@ -4,7 +4,7 @@ use std::time::Instant;
 use image::{Rgba, RgbaImage};
 use image::imageops::{resize, FilterType};
 
-use crate::state::{Asset, AssetKind};
+use crate::state::{resolve_project_path, Asset, AssetKind};
 
 use super::types::FFMPEG_TIME_EPSILON;
 
@@ -160,3 +160,96 @@ pub(crate) fn resolve_asset_source(
         _ => None,
     }
 }
+
+/// The proxy to preview `asset` from, and the original's pixel size to lay
+/// out its smaller frames at. `None` (use the original) unless proxies are
+/// on, the asset opted in, its size is known and the proxy file exists.
+pub(crate) fn preview_proxy(
+    project_root: &Path,
+    asset: &Asset,
+    use_proxies: bool,
+) -> Option<(std::path::PathBuf, (u32, u32))> {
+    if !use_proxies || !asset.use_proxy {
+        return None;
+    }
+    let proxy = asset.proxy.as_ref()?;
+    let size = asset.source_dimensions()?;
+    let path = resolve_project_path(&proxy.path, project_root);
+    path.is_file().then_some((path, size))
+}
+
+/// [`resolve_asset_source`] for the preview: the file to decode (the proxy
+/// when [`preview_proxy`] picks one), whether it is a video, its duration,
+/// and the size to lay a proxy's frames out at.
+pub(crate) fn resolve_preview_source(
+    project_root: &Path,
+    asset: &Asset,
+    use_proxies: bool,
+) -> Option<(std::path::PathBuf, bool, Option<f64>, Option<(u32, u32)>)> {
+    let (path, is_video, duration) = resolve_asset_source(
+        project_root,
+        asset,
+        &["png", "jpg", "jpeg", "webp"],
+        &["mp4", "mov", "mkv", "webm"],
+    )?;
+    match preview_proxy(project_root, asset, use_proxies) {
+        Some((proxy_path, size)) => Some((proxy_path, is_video, duration, Some(size))),
+        None => Some((path, is_video, duration, None)),
+    }
+}
+
+/// File currently shown by a visual asset (the active version for generative
+/// assets), with whether it is a video.
+pub fn visual_source_path(project_root: &Path, asset: &Asset) -> Option<(std::path::PathBuf, bool)> {
+    resolve_asset_source(project_root, asset, &["png", "jpg", "jpeg", "webp"], &["mp4", "mov", "mkv", "webm"])
+        .map(|(path, is_video, _)| (path, is_video))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::AssetProxy;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_preview_prefers_enabled_existing_proxy() {
+        let root = std::env::temp_dir().join(format!("nla-proxy-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(root.join(".cache").join("proxies")).unwrap();
+        let mut asset = Asset::new_video("shot", PathBuf::from("video/shot.mp4"));
+        asset.source_width = Some(3840);
+        asset.source_height = Some(2160);
+        let original = root.join("video").join("shot.mp4");
+        let source = |asset: &Asset, use_proxies: bool| {
+            resolve_preview_source(&root, asset, use_proxies).map(|(path, _, _, size)| (path, size))
+        };
+
+        // No proxy yet
+        asset.use_proxy = true;
+        assert_eq!(source(&asset, true), Some((original.clone(), None)));
+
+        // A recorded proxy whose file is gone isn't used
+        let proxy_path = root.join(".cache").join("proxies").join("shot.mp4");
+        asset.proxy = Some(AssetProxy {
+            path: PathBuf::from(".cache/proxies/shot.mp4"),
+            width: 960,
+            height: 540,
+        });
+        assert_eq!(source(&asset, true), Some((original.clone(), None)));
+
+        // Present and enabled: the proxy, laid out at the original's size
+        std::fs::write(&proxy_path, b"mp4").unwrap();
+        assert_eq!(source(&asset, true), Some((proxy_path.clone(), Some((3840, 2160)))));
+
+        // Turned off globally or for the asset: the original
+        assert_eq!(source(&asset, false), Some((original.clone(), None)));
+        asset.use_proxy = false;
+        assert_eq!(source(&asset, true), Some((original.clone(), None)));
+
+        // Without the original's size the proxy can't be placed
+        asset.use_proxy = true;
+        asset.source_width = None;
+        assert_eq!(source(&asset, true), Some((original, None)));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
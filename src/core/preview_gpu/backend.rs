@@ -0,0 +1,71 @@
+//! Choosing between the native GPU preview surface and CPU compositing.
+
+/// Outcome of trying to create the GPU preview surface.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub enum GpuAvailability {
+    /// Surface creation hasn't been attempted yet.
+    #[default]
+    Unknown,
+    Available,
+    /// Creation failed; holds the reason for logs and the status bar.
+    Unavailable(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PreviewBackend {
+    /// Layers are uploaded to the native wgpu surface.
+    Gpu,
+    /// Frames are composited by `PreviewRenderer` and drawn to the HTML canvas.
+    Cpu,
+}
+
+/// The GPU surface is only used once it exists and the user hasn't forced CPU mode.
+pub fn choose_preview_backend(force_cpu: bool, gpu: &GpuAvailability) -> PreviewBackend {
+    match gpu {
+        GpuAvailability::Available if !force_cpu => PreviewBackend::Gpu,
+        _ => PreviewBackend::Cpu,
+    }
+}
+
+/// Short status-bar note when the preview is running on the CPU for a reason
+/// the user may not expect.
+pub fn preview_backend_note(force_cpu: bool, gpu: &GpuAvailability) -> Option<String> {
+    if force_cpu {
+        return Some("CPU preview (forced in settings)".to_string());
+    }
+    match gpu {
+        GpuAvailability::Unavailable(reason) => {
+            Some(format!("CPU preview: GPU unavailable ({})", reason))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gpu_unavailable_selects_cpu() {
+        let unavailable = GpuAvailability::Unavailable("No compatible GPU adapter".to_string());
+        assert_eq!(choose_preview_backend(false, &unavailable), PreviewBackend::Cpu);
+        assert_eq!(
+            preview_backend_note(false, &unavailable).as_deref(),
+            Some("CPU preview: GPU unavailable (No compatible GPU adapter)")
+        );
+        // Not tried yet also stays on the CPU
+        assert_eq!(choose_preview_backend(false, &GpuAvailability::Unknown), PreviewBackend::Cpu);
+        assert_eq!(preview_backend_note(false, &GpuAvailability::Unknown), None);
+    }
+
+    #[test]
+    fn test_force_cpu_overrides_available_gpu() {
+        assert_eq!(
+            choose_preview_backend(false, &GpuAvailability::Available),
+            PreviewBackend::Gpu
+        );
+        assert_eq!(choose_preview_backend(true, &GpuAvailability::Available), PreviewBackend::Cpu);
+        assert!(preview_backend_note(true, &GpuAvailability::Available).is_some());
+        assert_eq!(preview_backend_note(false, &GpuAvailability::Available), None);
+    }
+}
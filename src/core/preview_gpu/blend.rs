@@ -0,0 +1,138 @@
+//! Fixed-function blend states for per-clip blend modes.
+//!
+//! Each state is chosen so the GPU produces the same result as
+//! `core::preview::layers::blend_channel`. Like the Normal path, sRGB surfaces
+//! apply these in linear light.
+
+use crate::state::BlendMode;
+
+/// Fragment entry point that outputs straight (non-premultiplied) colour.
+pub(crate) const STRAIGHT_ALPHA_ENTRY: &str = "fs_main";
+/// Fragment entry point that outputs colour premultiplied by alpha.
+pub(crate) const PREMULTIPLIED_ENTRY: &str = "fs_premultiplied";
+
+const ALPHA_OVER: wgpu::BlendComponent = wgpu::BlendComponent {
+    src_factor: wgpu::BlendFactor::One,
+    dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+    operation: wgpu::BlendOperation::Add,
+};
+
+/// Fragment entry point and blend state for a layer drawn with `mode`.
+///
+/// Returns `None` for Overlay: it switches formula on the backdrop value, which
+/// fixed-function blending can't read, so those stacks are composited on the CPU.
+pub(crate) fn layer_blend(mode: BlendMode) -> Option<(&'static str, wgpu::BlendState)> {
+    let (entry_point, color) = match mode {
+        BlendMode::Normal => return Some((STRAIGHT_ALPHA_ENTRY, wgpu::BlendState::ALPHA_BLENDING)),
+        // dst + src * a
+        BlendMode::Add => (
+            STRAIGHT_ALPHA_ENTRY,
+            wgpu::BlendComponent {
+                src_factor: wgpu::BlendFactor::SrcAlpha,
+                dst_factor: wgpu::BlendFactor::One,
+                operation: wgpu::BlendOperation::Add,
+            },
+        ),
+        // dst * (src * a) + dst * (1 - a)
+        BlendMode::Multiply => (
+            PREMULTIPLIED_ENTRY,
+            wgpu::BlendComponent {
+                src_factor: wgpu::BlendFactor::Dst,
+                dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+                operation: wgpu::BlendOperation::Add,
+            },
+        ),
+        // (src * a) * (1 - dst) + dst
+        BlendMode::Screen => (
+            PREMULTIPLIED_ENTRY,
+            wgpu::BlendComponent {
+                src_factor: wgpu::BlendFactor::OneMinusDst,
+                dst_factor: wgpu::BlendFactor::One,
+                operation: wgpu::BlendOperation::Add,
+            },
+        ),
+        BlendMode::Overlay => return None,
+    };
+    Some((
+        entry_point,
+        wgpu::BlendState {
+            color,
+            alpha: ALPHA_OVER,
+        },
+    ))
+}
+
+/// Whether the GPU preview can draw a layer with `mode` directly.
+pub fn supports_blend_mode(mode: BlendMode) -> bool {
+    layer_blend(mode).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::preview::blend_channel;
+
+    /// What the blend unit computes for one colour channel. `src` is the
+    /// fragment output, `src_alpha` its alpha, `dst` the framebuffer value.
+    fn evaluate(component: wgpu::BlendComponent, src: f32, src_alpha: f32, dst: f32) -> f32 {
+        let factor = |factor: wgpu::BlendFactor| match factor {
+            wgpu::BlendFactor::Zero => 0.0,
+            wgpu::BlendFactor::One => 1.0,
+            wgpu::BlendFactor::Src => src,
+            wgpu::BlendFactor::OneMinusSrc => 1.0 - src,
+            wgpu::BlendFactor::SrcAlpha => src_alpha,
+            wgpu::BlendFactor::OneMinusSrcAlpha => 1.0 - src_alpha,
+            wgpu::BlendFactor::Dst => dst,
+            wgpu::BlendFactor::OneMinusDst => 1.0 - dst,
+            other => panic!("unexpected blend factor {:?}", other),
+        };
+        assert_eq!(component.operation, wgpu::BlendOperation::Add);
+        let value = src * factor(component.src_factor) + dst * factor(component.dst_factor);
+        // Unorm targets clamp on write
+        value.clamp(0.0, 1.0)
+    }
+
+    #[test]
+    fn test_gpu_blend_states_match_cpu_formulas() {
+        let samples = [0.0, 0.1, 0.25, 0.5, 0.6, 0.9, 1.0];
+        for mode in BlendMode::ALL {
+            let Some((entry_point, state)) = layer_blend(mode) else {
+                assert_eq!(mode, BlendMode::Overlay);
+                continue;
+            };
+            for &src in &samples {
+                for &dst in &samples {
+                    for &alpha in &samples {
+                        let shader_out = if entry_point == PREMULTIPLIED_ENTRY {
+                            src * alpha
+                        } else {
+                            src
+                        };
+                        let gpu = evaluate(state.color, shader_out, alpha, dst);
+                        let cpu = blend_channel(src, dst, alpha, mode).clamp(0.0, 1.0);
+                        assert!(
+                            (gpu - cpu).abs() < 1e-5,
+                            "{:?}: src {} dst {} alpha {} -> gpu {} cpu {}",
+                            mode,
+                            src,
+                            dst,
+                            alpha,
+                            gpu,
+                            cpu
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_blend_states_keep_alpha_over() {
+        for mode in BlendMode::ALL {
+            if let Some((_, state)) = layer_blend(mode) {
+                assert_eq!(state.alpha, ALPHA_OVER, "{:?}", mode);
+            }
+        }
+        assert!(!supports_blend_mode(BlendMode::Overlay));
+    }
+}
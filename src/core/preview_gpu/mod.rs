@@ -6,6 +6,10 @@ mod surface;
 mod shaders;
 mod types;
 mod layers;
+mod blend;
+mod backend;
 
 pub use surface::PreviewGpuSurface;
 pub use types::PreviewBounds;
+pub use blend::supports_blend_mode;
+pub use backend::{choose_preview_backend, preview_backend_note, GpuAvailability, PreviewBackend};
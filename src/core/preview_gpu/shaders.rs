@@ -59,13 +59,24 @@ var layer_tex: texture_2d<f32>;
 @group(0) @binding(1)
 var layer_sampler: sampler;
 
-@fragment
-fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+fn sample_layer(input: VertexOutput) -> vec4<f32> {
     let uv = vec2<f32>(input.uv.x, 1.0 - input.uv.y);
     var color = textureSample(layer_tex, layer_sampler, uv);
     color.a = color.a * layer.rotation_opacity.z;
     return color;
 }
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    return sample_layer(input);
+}
+
+// Multiply and Screen blend states expect premultiplied colour.
+@fragment
+fn fs_premultiplied(input: VertexOutput) -> @location(0) vec4<f32> {
+    let color = sample_layer(input);
+    return vec4<f32>(color.rgb * color.a, color.a);
+}
 "#;
 
 #[cfg(target_os = "windows")]
@@ -106,3 +117,7 @@ fn fs_main(_input: VertexOutput) -> @location(0) vec4<f32> {
 // #27 = 39/255 = 0.153 sRGB -> ~0.0201 linear
 // #2a = 42/255 = 0.165 sRGB -> ~0.0231 linear
 pub(crate) const BORDER_COLOR_LINEAR: [f32; 4] = [0.0201, 0.0201, 0.0231, 1.0];
+
+#[cfg(target_os = "windows")]
+// Preview guides: light grey at 60% so they read over both dark and bright footage.
+pub(crate) const GUIDE_COLOR_LINEAR: [f32; 4] = [0.6, 0.6, 0.6, 0.6];
@@ -1,11 +1,17 @@
 #[cfg(target_os = "windows")]
-use crate::core::preview::PreviewLayerStack;
+use crate::core::preview::{layer_oversized, GuideLine, PreviewLayerStack, MAX_GUIDE_LINES};
 #[cfg(not(target_os = "windows"))]
-use crate::core::preview::PreviewLayerStack;
+use crate::core::preview::{GuideLine, PreviewLayerStack};
+#[cfg(target_os = "windows")]
+use super::blend::layer_blend;
 #[cfg(target_os = "windows")]
 use super::layers::{align_to, compute_layer_uniform, create_layer};
 #[cfg(target_os = "windows")]
-use super::shaders::{BORDER_COLOR_LINEAR, BORDER_SHADER, PREVIEW_CLEAR_COLOR, PREVIEW_SHADER};
+use crate::state::BlendMode;
+#[cfg(target_os = "windows")]
+use super::shaders::{
+    BORDER_COLOR_LINEAR, BORDER_SHADER, GUIDE_COLOR_LINEAR, PREVIEW_CLEAR_COLOR, PREVIEW_SHADER,
+};
 #[cfg(target_os = "windows")]
 use super::types::{BorderUniform, GpuLayer, LayerUniform, PreviewBounds, QUAD_VERTICES, Vertex};
 #[cfg(not(target_os = "windows"))]
@@ -40,7 +46,7 @@ pub struct PreviewGpuSurface {
     sampler: wgpu::Sampler,
     texture_bind_group_layout: wgpu::BindGroupLayout,
     uniform_bind_group_layout: wgpu::BindGroupLayout,
-    pipeline: wgpu::RenderPipeline,
+    pipelines: Vec<(BlendMode, wgpu::RenderPipeline)>,
     vertex_buffer: wgpu::Buffer,
     layers: Vec<GpuLayer>,
     canvas_size: (u32, u32),
@@ -52,6 +58,10 @@ pub struct PreviewGpuSurface {
     border_bind_group_layout: wgpu::BindGroupLayout,
     border_uniform_buffers: [wgpu::Buffer; 4],
     border_bind_groups: [wgpu::BindGroup; 4],
+    // Overlay guides, drawn with the border pipeline (one buffer per line)
+    guides: Vec<GuideLine>,
+    guide_uniform_buffers: Vec<wgpu::Buffer>,
+    guide_bind_groups: Vec<wgpu::BindGroup>,
 }
 
 #[cfg(target_os = "windows")]
@@ -59,7 +69,7 @@ impl PreviewGpuSurface {
     pub fn new<T>(
         parent: &dioxus::desktop::tao::window::Window,
         target: &dioxus::desktop::tao::event_loop::EventLoopWindowTarget<T>,
-    ) -> Option<Self> {
+    ) -> Result<Self, String> {
         use dioxus::desktop::tao::dpi::LogicalSize;
         use dioxus::desktop::tao::platform::windows::{WindowBuilderExtWindows, WindowExtWindows};
         use dioxus::desktop::tao::window::WindowBuilder;
@@ -71,17 +81,22 @@ impl PreviewGpuSurface {
             .with_inner_size(LogicalSize::new(1.0, 1.0))
             .with_parent_window(parent.hwnd());
 
-        let window = builder.build(target).ok()?;
+        let window = builder
+            .build(target)
+            .map_err(|err| format!("Failed to create preview window: {}", err))?;
 
         let instance = wgpu::Instance::default();
-        let surface_target = unsafe { wgpu::SurfaceTargetUnsafe::from_window(&window) }.ok()?;
+        let surface_target = unsafe { wgpu::SurfaceTargetUnsafe::from_window(&window) }
+            .map_err(|err| format!("No surface target for the preview window: {}", err))?;
         let surface: wgpu::Surface<'static> =
-            unsafe { instance.create_surface_unsafe(surface_target) }.ok()?;
+            unsafe { instance.create_surface_unsafe(surface_target) }
+                .map_err(|err| format!("Failed to create wgpu surface: {}", err))?;
         let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
             power_preference: wgpu::PowerPreference::HighPerformance,
             compatible_surface: Some(&surface),
             force_fallback_adapter: false,
-        }))?;
+        }))
+        .ok_or_else(|| "No compatible GPU adapter".to_string())?;
 
         let adapter_limits = adapter.limits();
         let mut requested_limits = wgpu::Limits::downlevel_defaults();
@@ -107,10 +122,13 @@ impl PreviewGpuSurface {
                 None,
             ))
         })
-        .ok()?;
+        .map_err(|err| format!("Failed to open GPU device: {}", err))?;
         let max_surface_size = device.limits().max_texture_dimension_2d.max(1);
 
         let surface_caps = surface.get_capabilities(&adapter);
+        if surface_caps.formats.is_empty() || surface_caps.alpha_modes.is_empty() {
+            return Err("GPU adapter cannot present to the preview window".to_string());
+        }
         let format = surface_caps
             .formats
             .iter()
@@ -190,38 +208,47 @@ impl PreviewGpuSurface {
             bind_group_layouts: &[&texture_bind_group_layout, &uniform_bind_group_layout],
             push_constant_ranges: &[],
         });
-        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("preview_gpu_pipeline"),
-            layout: Some(&pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &shader,
-                entry_point: "vs_main",
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-                buffers: &[Vertex::desc()],
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &shader,
-                entry_point: "fs_main",
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: config.format,
-                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Ccw,
-                cull_mode: None,
-                polygon_mode: wgpu::PolygonMode::Fill,
-                unclipped_depth: false,
-                conservative: false,
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState::default(),
-            multiview: None,
-        });
+        // One pipeline per blend mode the GPU can draw; Overlay stacks arrive
+        // pre-flattened from the renderer.
+        let pipelines = BlendMode::ALL
+            .into_iter()
+            .filter_map(|mode| {
+                let (entry_point, blend) = layer_blend(mode)?;
+                let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                    label: Some(&format!("preview_gpu_pipeline_{}", mode.as_str())),
+                    layout: Some(&pipeline_layout),
+                    vertex: wgpu::VertexState {
+                        module: &shader,
+                        entry_point: "vs_main",
+                        compilation_options: wgpu::PipelineCompilationOptions::default(),
+                        buffers: &[Vertex::desc()],
+                    },
+                    fragment: Some(wgpu::FragmentState {
+                        module: &shader,
+                        entry_point,
+                        compilation_options: wgpu::PipelineCompilationOptions::default(),
+                        targets: &[Some(wgpu::ColorTargetState {
+                            format: config.format,
+                            blend: Some(blend),
+                            write_mask: wgpu::ColorWrites::ALL,
+                        })],
+                    }),
+                    primitive: wgpu::PrimitiveState {
+                        topology: wgpu::PrimitiveTopology::TriangleList,
+                        strip_index_format: None,
+                        front_face: wgpu::FrontFace::Ccw,
+                        cull_mode: None,
+                        polygon_mode: wgpu::PolygonMode::Fill,
+                        unclipped_depth: false,
+                        conservative: false,
+                    },
+                    depth_stencil: None,
+                    multisample: wgpu::MultisampleState::default(),
+                    multiview: None,
+                });
+                Some((mode, pipeline))
+            })
+            .collect::<Vec<_>>();
 
         let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("preview_gpu_vertex_buffer"),
@@ -271,6 +298,34 @@ impl PreviewGpuSurface {
             })
         });
 
+        let guide_uniform = BorderUniform {
+            rect: [0.0, 0.0, 0.0, 0.0],
+            color: GUIDE_COLOR_LINEAR,
+        };
+        let guide_uniform_buffers: Vec<wgpu::Buffer> = (0..MAX_GUIDE_LINES)
+            .map(|i| {
+                device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some(&format!("guide_uniform_buffer_{}", i)),
+                    contents: bytemuck::bytes_of(&guide_uniform),
+                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                })
+            })
+            .collect();
+        let guide_bind_groups: Vec<wgpu::BindGroup> = guide_uniform_buffers
+            .iter()
+            .enumerate()
+            .map(|(i, buffer)| {
+                device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: Some(&format!("guide_bind_group_{}", i)),
+                    layout: &border_bind_group_layout,
+                    entries: &[wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: buffer.as_entire_binding(),
+                    }],
+                })
+            })
+            .collect();
+
         let border_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("border_shader"),
             source: wgpu::ShaderSource::Wgsl(BORDER_SHADER.into()),
@@ -329,7 +384,7 @@ impl PreviewGpuSurface {
             );
         }
 
-        Some(Self {
+        Ok(Self {
             window,
             surface,
             device,
@@ -342,7 +397,7 @@ impl PreviewGpuSurface {
             sampler,
             texture_bind_group_layout,
             uniform_bind_group_layout,
-            pipeline,
+            pipelines,
             vertex_buffer,
             layers: Vec::new(),
             canvas_size: (1, 1),
@@ -352,6 +407,9 @@ impl PreviewGpuSurface {
             border_bind_group_layout,
             border_uniform_buffers,
             border_bind_groups,
+            guides: Vec::new(),
+            guide_uniform_buffers,
+            guide_bind_groups,
         })
     }
 
@@ -424,17 +482,27 @@ impl PreviewGpuSurface {
             stack.canvas_height.max(1),
         );
 
-        if stack.layers.is_empty() {
+        // Layers too big for one texture are left out instead of failing the upload
+        let max_side = self.device.limits().max_texture_dimension_2d as f32;
+        let layers: Vec<_> = stack
+            .layers
+            .iter()
+            .filter(|layer| {
+                !layer_oversized(layer.image.width() as f32, layer.image.height() as f32, max_side)
+            })
+            .collect();
+
+        if layers.is_empty() {
             self.layers.clear();
             return true;
         }
 
-        if self.layers.len() > stack.layers.len() {
-            self.layers.truncate(stack.layers.len());
+        if self.layers.len() > layers.len() {
+            self.layers.truncate(layers.len());
         }
 
         let mut uploaded = false;
-        for (index, layer) in stack.layers.iter().enumerate() {
+        for (index, layer) in layers.into_iter().enumerate() {
             let width = layer.image.width().max(1);
             let height = layer.image.height().max(1);
 
@@ -533,6 +601,15 @@ impl PreviewGpuSurface {
         self.over_limit
     }
 
+    /// Replace the overlay guides (in canvas pixels). Returns true if they changed.
+    pub fn set_guides(&mut self, guides: Vec<GuideLine>) -> bool {
+        if self.guides == guides {
+            return false;
+        }
+        self.guides = guides;
+        true
+    }
+
     pub fn render_layers(&mut self) {
         if self.over_limit {
             return;
@@ -606,6 +683,36 @@ impl PreviewGpuSurface {
             }
         }
 
+        // Guides map from canvas pixels to screen pixels like the layers, then
+        // draw as 1 pixel rects.
+        let guide_count = if should_draw_border {
+            self.guides.len().min(self.guide_uniform_buffers.len())
+        } else {
+            0
+        };
+        for (i, guide) in self.guides.iter().take(guide_count).enumerate() {
+            let x0 = offset_x + guide.x0.min(guide.x1) * preview_scale;
+            let y0 = offset_y + guide.y0.min(guide.y1) * preview_scale;
+            let x1 = offset_x + guide.x0.max(guide.x1) * preview_scale;
+            let y1 = offset_y + guide.y0.max(guide.y1) * preview_scale;
+            let w = (x1 - x0).max(1.0);
+            let h = (y1 - y0).max(1.0);
+            let uniform = BorderUniform {
+                rect: [
+                    (x0.round() / surface_w) * 2.0 - 1.0,
+                    1.0 - ((y0.round() + h) / surface_h) * 2.0,
+                    w / surface_w * 2.0,
+                    h / surface_h * 2.0,
+                ],
+                color: GUIDE_COLOR_LINEAR,
+            };
+            self.queue.write_buffer(
+                &self.guide_uniform_buffers[i],
+                0,
+                bytemuck::bytes_of(&uniform),
+            );
+        }
+
         {
             let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("preview_gpu_pass"),
@@ -631,16 +738,26 @@ impl PreviewGpuSurface {
                 let scissor_h = preview_h.round().max(1.0) as u32;
                 pass.set_scissor_rect(scissor_x, scissor_y, scissor_w, scissor_h);
 
-                pass.set_pipeline(&self.pipeline);
                 pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
 
                 let canvas_size = self.canvas_size;
+                let mut bound_mode = None;
                 for layer in &self.layers {
                     let Some(uniform) =
                         compute_layer_uniform(self.size, layer.placement, canvas_size)
                     else {
                         continue;
                     };
+                    let mode = layer.placement.blend_mode;
+                    if bound_mode != Some(mode) {
+                        let Some((_, pipeline)) =
+                            self.pipelines.iter().find(|(pipeline_mode, _)| *pipeline_mode == mode)
+                        else {
+                            continue;
+                        };
+                        pass.set_pipeline(pipeline);
+                        bound_mode = Some(mode);
+                    }
                     self.queue.write_buffer(
                         &layer.uniform_buffer,
                         0,
@@ -665,6 +782,11 @@ impl PreviewGpuSurface {
                     pass.set_bind_group(0, &self.border_bind_groups[i], &[]);
                     pass.draw(0..QUAD_VERTICES.len() as u32, 0..1);
                 }
+
+                for bind_group in self.guide_bind_groups.iter().take(guide_count) {
+                    pass.set_bind_group(0, bind_group, &[]);
+                    pass.draw(0..QUAD_VERTICES.len() as u32, 0..1);
+                }
             }
         }
 
@@ -695,8 +817,8 @@ impl PreviewGpuSurface {
     pub fn new<T>(
         _parent: &dioxus::desktop::tao::window::Window,
         _target: &dioxus::desktop::tao::event_loop::EventLoopWindowTarget<T>,
-    ) -> Option<Self> {
-        None
+    ) -> Result<Self, String> {
+        Err("GPU preview is only supported on Windows".to_string())
     }
 
     pub fn apply_bounds(&mut self, _bounds: PreviewBounds) -> bool {
@@ -709,5 +831,9 @@ impl PreviewGpuSurface {
 
     pub fn clear_layers(&mut self) {}
 
+    pub fn set_guides(&mut self, _guides: Vec<GuideLine>) -> bool {
+        false
+    }
+
     pub fn render_layers(&mut self) {}
 }
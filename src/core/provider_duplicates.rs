@@ -0,0 +1,263 @@
+//! Spot providers that would run the same graph the same way.
+//!
+//! Users with many similar ComfyUI graphs tend to import or save the same
+//! provider twice under different names. Two ComfyUI providers count as
+//! duplicates when their workflows hash the same and their manifests bind the
+//! same inputs and output; names, categories, and the server URL are ignored.
+
+use std::path::{Path, PathBuf};
+
+use uuid::Uuid;
+
+use crate::core::comfyui_workflow::{hash_workflow_file, workflow_hash};
+use crate::core::paths::resolve_resource_path;
+use crate::core::provider_package::ProviderPackage;
+use crate::core::provider_store::{provider_scope_for_path, read_provider_file, resolve_provider_entry_paths};
+use crate::state::{
+    ComfyOutputSelector, NodeSelector, ProviderConnection, ProviderEntry, ProviderManifest,
+    ProviderOutputType, ProviderScope,
+};
+
+/// What a provider does, without what it is called.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderFingerprint {
+    output_type: ProviderOutputType,
+    graph: ProviderGraph,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ProviderGraph {
+    ComfyUi {
+        workflow_hash: Option<String>,
+        /// Sorted by input name.
+        inputs: Vec<(String, NodeSelector)>,
+        output: Option<ComfyOutputSelector>,
+    },
+    /// Non-ComfyUI providers compare by their whole connection.
+    Connection(ProviderConnection),
+}
+
+/// An existing provider file that matches the one being saved or imported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateProvider {
+    pub path: PathBuf,
+    pub id: Uuid,
+    pub name: String,
+}
+
+impl ProviderFingerprint {
+    /// `workflow_hash` is the hash of the workflow the entry runs, when known.
+    pub fn new(
+        entry: &ProviderEntry,
+        manifest: Option<&ProviderManifest>,
+        workflow_hash: Option<String>,
+    ) -> Self {
+        let graph = match &entry.connection {
+            ProviderConnection::ComfyUi { .. } => {
+                let (mut inputs, output, stored_hash) = match manifest {
+                    Some(ProviderManifest::ComfyUi {
+                        inputs,
+                        output,
+                        workflow,
+                        ..
+                    }) => (
+                        inputs
+                            .iter()
+                            .map(|input| (input.name.clone(), input.bind.selector.clone()))
+                            .collect::<Vec<_>>(),
+                        Some(output.clone()),
+                        workflow.workflow_hash.clone(),
+                    ),
+                    _ => (Vec::new(), None, None),
+                };
+                inputs.sort_by(|a, b| a.0.cmp(&b.0));
+                ProviderGraph::ComfyUi {
+                    workflow_hash: workflow_hash.or(stored_hash),
+                    inputs,
+                    output,
+                }
+            }
+            connection => ProviderGraph::Connection(connection.clone()),
+        };
+        Self {
+            output_type: entry.output_type,
+            graph,
+        }
+    }
+
+    /// Fingerprint an entry whose paths are resolved, reading its manifest
+    /// and workflow from disk. Missing files just weaken the comparison.
+    pub fn load(entry: &ProviderEntry) -> Self {
+        let (workflow_path, manifest_path) = match &entry.connection {
+            ProviderConnection::ComfyUi {
+                workflow_path,
+                manifest_path,
+                ..
+            } => (workflow_path.as_deref(), manifest_path.as_deref()),
+            _ => (None, None),
+        };
+        let manifest = manifest_path
+            .and_then(|path| read_provider_file(&resolve_resource_path(Path::new(path))))
+            .and_then(|json| serde_json::from_str::<ProviderManifest>(&json).ok());
+        let hash = workflow_path.and_then(|path| hash_workflow_file(&resolve_resource_path(Path::new(path))).ok());
+        Self::new(entry, manifest.as_ref(), hash)
+    }
+
+    pub fn from_package(package: &ProviderPackage) -> Self {
+        Self::new(
+            &package.provider,
+            package.manifest.as_ref(),
+            package.workflow.as_ref().map(workflow_hash),
+        )
+    }
+
+    /// True when both providers would produce the same output from the same
+    /// inputs. ComfyUI providers whose workflow couldn't be hashed never match.
+    pub fn is_duplicate_of(&self, other: &Self) -> bool {
+        if self != other {
+            return false;
+        }
+        match &self.graph {
+            ProviderGraph::ComfyUi { workflow_hash, .. } => workflow_hash.is_some(),
+            ProviderGraph::Connection(_) => true,
+        }
+    }
+}
+
+/// First provider among `files` matching `fingerprint`, skipping `ignore_id`
+/// (the provider being edited). Project files are resolved against
+/// `project_root`.
+pub fn find_duplicate_provider(
+    fingerprint: &ProviderFingerprint,
+    files: &[PathBuf],
+    project_root: Option<&Path>,
+    ignore_id: Option<Uuid>,
+) -> Option<DuplicateProvider> {
+    files.iter().find_map(|path| {
+        let json = read_provider_file(path)?;
+        let mut entry: ProviderEntry = serde_json::from_str(&json).ok()?;
+        if Some(entry.id) == ignore_id {
+            return None;
+        }
+        if let (ProviderScope::Project, Some(root)) = (provider_scope_for_path(path), project_root) {
+            resolve_provider_entry_paths(&mut entry, root);
+        }
+        ProviderFingerprint::load(&entry)
+            .is_duplicate_of(fingerprint)
+            .then(|| DuplicateProvider {
+                path: path.clone(),
+                id: entry.id,
+                name: entry.name,
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{ComfyWorkflowRef, InputBinding, ManifestInput, ProviderInputType};
+
+    fn selector(class_type: &str, input_key: &str) -> NodeSelector {
+        NodeSelector {
+            tag: None,
+            class_type: class_type.to_string(),
+            input_key: input_key.to_string(),
+            title: None,
+        }
+    }
+
+    fn comfy_entry(name: &str) -> ProviderEntry {
+        ProviderEntry::new(
+            name,
+            ProviderOutputType::Image,
+            ProviderConnection::ComfyUi {
+                base_url: "http://127.0.0.1:8188".to_string(),
+                workflow_path: Some(format!("workflows/{}_API.json", name)),
+                manifest_path: None,
+            },
+        )
+    }
+
+    fn manifest(input_names: &[&str], output_key: &str) -> ProviderManifest {
+        ProviderManifest::ComfyUi {
+            schema_version: 1,
+            name: None,
+            output_type: ProviderOutputType::Image,
+            workflow: ComfyWorkflowRef {
+                workflow_path: "workflow_API.json".to_string(),
+                workflow_hash: None,
+            },
+            inputs: input_names
+                .iter()
+                .map(|name| ManifestInput {
+                    name: name.to_string(),
+                    label: name.to_string(),
+                    input_type: ProviderInputType::Text,
+                    required: false,
+                    default: None,
+                    ui: None,
+                    random_range: None,
+                    bind: InputBinding {
+                        selector: selector("CLIPTextEncode", name),
+                        transform: None,
+                    },
+                })
+                .collect(),
+            output: ComfyOutputSelector {
+                selector: selector("SaveImage", output_key),
+                index: None,
+            },
+        }
+    }
+
+    fn fingerprint(entry: &ProviderEntry, manifest: &ProviderManifest, hash: &str) -> ProviderFingerprint {
+        ProviderFingerprint::new(entry, Some(manifest), Some(hash.to_string()))
+    }
+
+    #[test]
+    fn test_identical_and_renamed_providers_are_duplicates() {
+        let original = comfy_entry("Portrait");
+        let base = fingerprint(&original, &manifest(&["prompt", "negative"], "images"), "sha256:a");
+        assert!(base.is_duplicate_of(&fingerprint(
+            &original,
+            &manifest(&["prompt", "negative"], "images"),
+            "sha256:a"
+        )));
+
+        // Renamed, recategorized, pointed at another server, inputs listed in another order
+        let mut renamed = comfy_entry("Portrait v2");
+        renamed.category = Some("Faces".to_string());
+        renamed.favorite = true;
+        renamed.connection = ProviderConnection::ComfyUi {
+            base_url: "http://gpu-box:8188".to_string(),
+            workflow_path: Some("elsewhere/copy_API.json".to_string()),
+            manifest_path: None,
+        };
+        let renamed_fingerprint = fingerprint(&renamed, &manifest(&["negative", "prompt"], "images"), "sha256:a");
+        assert!(base.is_duplicate_of(&renamed_fingerprint));
+    }
+
+    #[test]
+    fn test_different_providers_are_not_duplicates() {
+        let entry = comfy_entry("Portrait");
+        let base = fingerprint(&entry, &manifest(&["prompt"], "images"), "sha256:a");
+
+        // Edited workflow
+        assert!(!base.is_duplicate_of(&fingerprint(&entry, &manifest(&["prompt"], "images"), "sha256:b")));
+        // Another input exposed
+        assert!(!base.is_duplicate_of(&fingerprint(
+            &entry,
+            &manifest(&["prompt", "seed"], "images"),
+            "sha256:a"
+        )));
+        // Different output selector
+        assert!(!base.is_duplicate_of(&fingerprint(&entry, &manifest(&["prompt"], "gifs"), "sha256:a")));
+        // Different output type
+        let mut video = entry.clone();
+        video.output_type = ProviderOutputType::Video;
+        assert!(!base.is_duplicate_of(&fingerprint(&video, &manifest(&["prompt"], "images"), "sha256:a")));
+        // Workflows that couldn't be read never match, even with each other
+        let unknown = ProviderFingerprint::new(&entry, None, None);
+        assert!(!unknown.is_duplicate_of(&unknown.clone()));
+    }
+}
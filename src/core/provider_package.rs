@@ -0,0 +1,347 @@
+//! Share configured providers as single `.nlaprovider` files.
+//!
+//! A package is one JSON document holding the provider entry, its ComfyUI
+//! manifest, and the API workflow it runs, so it can be handed to another
+//! machine without chasing down the files it points at. Importing writes the
+//! workflow and manifest next to the target providers root and points the
+//! entry at the new copies.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::core::comfyui_workflow::{parse_api_workflow, parse_workflow_nodes, save_pasted_workflow};
+use crate::core::paths::resolve_resource_path;
+use crate::core::provider_store::{
+    global_providers_root, provider_scope_for_path, save_provider_entry_to, resolve_provider_entry_paths,
+};
+use crate::state::{ProviderConnection, ProviderEntry, ProviderManifest, ProviderScope};
+
+pub const PROVIDER_PACKAGE_EXTENSION: &str = "nlaprovider";
+const PROVIDER_PACKAGE_VERSION: u32 = 1;
+/// Imported workflows and manifests live here, under the providers root.
+const IMPORTED_WORKFLOWS_FOLDER: &str = "workflows";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProviderPackage {
+    pub package_version: u32,
+    pub provider: ProviderEntry,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manifest: Option<ProviderManifest>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workflow: Option<Value>,
+    /// Original workflow file name, reused when importing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workflow_file_name: Option<String>,
+}
+
+impl ProviderPackage {
+    /// Gather `entry` with the manifest and workflow files it references.
+    /// Paths must already be resolved (absolute or app-resource relative).
+    pub fn from_entry(entry: &ProviderEntry) -> Result<Self, String> {
+        let mut package = Self {
+            package_version: PROVIDER_PACKAGE_VERSION,
+            provider: entry.clone(),
+            manifest: None,
+            workflow: None,
+            workflow_file_name: None,
+        };
+        package.provider.favorite = false;
+        if let ProviderConnection::ComfyUi {
+            workflow_path,
+            manifest_path,
+            ..
+        } = &mut package.provider.connection
+        {
+            if let Some(path) = workflow_path.take() {
+                let path = resolve_resource_path(Path::new(&path));
+                let json = fs::read_to_string(&path)
+                    .map_err(|err| format!("Failed to read workflow {}: {}", path.display(), err))?;
+                package.workflow = Some(parse_api_workflow(&json)?);
+                package.workflow_file_name = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string());
+            }
+            if let Some(path) = manifest_path.take() {
+                let path = resolve_resource_path(Path::new(&path));
+                let json = fs::read_to_string(&path)
+                    .map_err(|err| format!("Failed to read manifest {}: {}", path.display(), err))?;
+                package.manifest = Some(
+                    serde_json::from_str(&json)
+                        .map_err(|err| format!("Invalid manifest {}: {}", path.display(), err))?,
+                );
+            }
+        }
+        Ok(package)
+    }
+
+    /// Parse a package and check that its workflow is a usable API workflow.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let package: Self =
+            serde_json::from_str(json).map_err(|err| format!("Invalid provider package: {}", err))?;
+        if package.package_version > PROVIDER_PACKAGE_VERSION {
+            return Err(format!(
+                "Provider package version {} is newer than this app supports.",
+                package.package_version
+            ));
+        }
+        if let Some(workflow) = package.workflow.as_ref() {
+            parse_api_workflow(&workflow.to_string())?;
+            parse_workflow_nodes(workflow)?;
+        }
+        Ok(package)
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|err| format!("Failed to serialize provider: {}", err))
+    }
+
+    /// Write the workflow, manifest, and entry under `providers_root`. With
+    /// `keep_id` the entry replaces any provider sharing its UUID; otherwise it
+    /// gets a fresh one and lands alongside.
+    pub fn install(&self, providers_root: &Path, keep_id: bool) -> Result<ProviderEntry, String> {
+        let mut entry = self.provider.clone();
+        entry.scope = ProviderScope::Global;
+        if !keep_id {
+            entry.id = Uuid::new_v4();
+        }
+        let workflows_dir = providers_root.join(IMPORTED_WORKFLOWS_FOLDER);
+        let installed_workflow = match self.workflow.as_ref() {
+            Some(workflow) => {
+                let stem = self
+                    .workflow_file_name
+                    .as_deref()
+                    .and_then(|name| Path::new(name).file_stem())
+                    .map(|stem| stem.to_string_lossy().to_string())
+                    .map(|stem| stem.trim_end_matches("_API").to_string())
+                    .unwrap_or_else(|| entry.name.clone());
+                Some(save_pasted_workflow(&workflows_dir, &stem, workflow)?)
+            }
+            None => None,
+        };
+        let installed_manifest = match self.manifest.clone() {
+            Some(mut manifest) => {
+                if let (ProviderManifest::ComfyUi { workflow, .. }, Some(path)) =
+                    (&mut manifest, installed_workflow.as_ref())
+                {
+                    workflow.workflow_path = path.to_string_lossy().to_string();
+                }
+                let path = manifest_path_for(&workflows_dir, installed_workflow.as_deref(), entry.id);
+                let json = serde_json::to_string_pretty(&manifest)
+                    .map_err(|err| format!("Failed to serialize manifest: {}", err))?;
+                fs::write(&path, json).map_err(|err| format!("Failed to write manifest: {}", err))?;
+                Some(path)
+            }
+            None => None,
+        };
+        if let ProviderConnection::ComfyUi {
+            workflow_path,
+            manifest_path,
+            ..
+        } = &mut entry.connection
+        {
+            *workflow_path = installed_workflow.map(|path| path.to_string_lossy().to_string());
+            *manifest_path = installed_manifest.map(|path| path.to_string_lossy().to_string());
+        }
+        save_provider_entry_to(providers_root, &entry)
+            .map_err(|err| format!("Failed to save provider: {}", err))?;
+        Ok(entry)
+    }
+}
+
+/// Package the provider stored at `provider_file` and write it to `output`.
+/// Project providers are resolved against `project_root` first.
+pub fn export_provider_package(
+    provider_file: &Path,
+    project_root: Option<&Path>,
+    output: &Path,
+) -> Result<(), String> {
+    let json = fs::read_to_string(provider_file)
+        .map_err(|err| format!("Failed to read provider: {}", err))?;
+    let mut entry: ProviderEntry =
+        serde_json::from_str(&json).map_err(|err| format!("Invalid provider config: {}", err))?;
+    if let (ProviderScope::Project, Some(root)) = (provider_scope_for_path(provider_file), project_root) {
+        resolve_provider_entry_paths(&mut entry, root);
+    }
+    let package = ProviderPackage::from_entry(&entry)?;
+    fs::write(output, package.to_json()?).map_err(|err| format!("Failed to write package: {}", err))
+}
+
+/// Read and validate a package file, e.g. to show what is being imported.
+pub fn read_provider_package(path: &Path) -> Result<ProviderPackage, String> {
+    let json = fs::read_to_string(path).map_err(|err| format!("Failed to read package: {}", err))?;
+    ProviderPackage::from_json(&json)
+}
+
+/// Install `package` into the global providers root.
+pub fn import_provider_package(package: &ProviderPackage, keep_id: bool) -> Result<ProviderEntry, String> {
+    package.install(&global_providers_root(), keep_id)
+}
+
+/// `<workflow stem>_manifest.json` beside the imported workflow, matching the
+/// builder's naming; falls back to the provider id without a workflow.
+fn manifest_path_for(dir: &Path, workflow: Option<&Path>, provider_id: Uuid) -> PathBuf {
+    let stem = workflow
+        .and_then(|path| path.file_stem())
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| provider_id.to_string());
+    dir.join(format!("{}_manifest.json", stem))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{
+        ComfyOutputSelector, ComfyWorkflowRef, InputBinding, ManifestInput, NodeSelector,
+        ProviderInputField, ProviderInputType, ProviderOutputType,
+    };
+
+    fn selector(class_type: &str, input_key: &str) -> NodeSelector {
+        NodeSelector {
+            tag: None,
+            class_type: class_type.to_string(),
+            input_key: input_key.to_string(),
+            title: Some("Main".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_provider_package_round_trip_preserves_inputs_and_output() {
+        let base = std::env::temp_dir().join(format!("nla-provider-package-{}", Uuid::new_v4()));
+        let source = base.join("source");
+        fs::create_dir_all(&source).unwrap();
+        let workflow = serde_json::json!({
+            "3": { "class_type": "KSampler", "inputs": { "seed": 1 } },
+            "9": { "class_type": "SaveImage", "inputs": { "images": ["3", 0] } }
+        });
+        let workflow_path = source.join("portrait_API.json");
+        fs::write(&workflow_path, workflow.to_string()).unwrap();
+        let manifest = ProviderManifest::ComfyUi {
+            schema_version: 1,
+            name: Some("Portrait".to_string()),
+            output_type: ProviderOutputType::Image,
+            workflow: ComfyWorkflowRef {
+                workflow_path: workflow_path.to_string_lossy().to_string(),
+                workflow_hash: None,
+            },
+            inputs: vec![ManifestInput {
+                name: "seed".to_string(),
+                label: "Seed".to_string(),
+                input_type: ProviderInputType::Integer,
+                required: false,
+                default: Some(serde_json::json!(7)),
+                ui: None,
+                random_range: None,
+                bind: InputBinding {
+                    selector: selector("KSampler", "seed"),
+                    transform: None,
+                },
+            }],
+            output: ComfyOutputSelector {
+                selector: selector("SaveImage", "images"),
+                index: Some(1),
+            },
+        };
+        let manifest_path = source.join("portrait_API_manifest.json");
+        fs::write(&manifest_path, serde_json::to_string(&manifest).unwrap()).unwrap();
+        let mut entry = ProviderEntry::new(
+            "Portrait",
+            ProviderOutputType::Image,
+            ProviderConnection::ComfyUi {
+                base_url: "http://127.0.0.1:8188".to_string(),
+                workflow_path: Some(workflow_path.to_string_lossy().to_string()),
+                manifest_path: Some(manifest_path.to_string_lossy().to_string()),
+            },
+        );
+        entry.inputs = vec![ProviderInputField {
+            name: "seed".to_string(),
+            label: "Seed".to_string(),
+            input_type: ProviderInputType::Integer,
+            required: false,
+            default: Some(serde_json::json!(7)),
+            ui: None,
+            random_range: None,
+        }];
+
+        let json = ProviderPackage::from_entry(&entry).unwrap().to_json().unwrap();
+        let package = ProviderPackage::from_json(&json).unwrap();
+        let target = base.join("providers");
+
+        // Keeping the id replaces in place; a fresh id installs a second copy
+        let kept = package.install(&target, true).unwrap();
+        assert_eq!(kept.id, entry.id);
+        let copy = package.install(&target, false).unwrap();
+        assert_ne!(copy.id, entry.id);
+
+        for installed in [&kept, &copy] {
+            assert_eq!(installed.inputs, entry.inputs);
+            let ProviderConnection::ComfyUi {
+                workflow_path,
+                manifest_path,
+                ..
+            } = &installed.connection
+            else {
+                panic!("expected a ComfyUI provider");
+            };
+            let workflow_path = PathBuf::from(workflow_path.clone().unwrap());
+            assert!(workflow_path.starts_with(&target));
+            let written: Value = serde_json::from_str(&fs::read_to_string(&workflow_path).unwrap()).unwrap();
+            assert_eq!(written, workflow);
+
+            let manifest_path = PathBuf::from(manifest_path.clone().unwrap());
+            let installed_manifest: ProviderManifest =
+                serde_json::from_str(&fs::read_to_string(&manifest_path).unwrap()).unwrap();
+            let ProviderManifest::ComfyUi {
+                workflow: workflow_ref,
+                inputs,
+                output,
+                ..
+            } = installed_manifest
+            else {
+                panic!("expected a ComfyUI manifest");
+            };
+            assert_eq!(Path::new(&workflow_ref.workflow_path), workflow_path.as_path());
+            let ProviderManifest::ComfyUi {
+                inputs: original_inputs,
+                output: original_output,
+                ..
+            } = &manifest
+            else {
+                unreachable!();
+            };
+            assert_eq!(&inputs, original_inputs);
+            assert_eq!(&output, original_output);
+        }
+        // The two imports don't share workflow files
+        assert_ne!(kept.connection, copy.connection);
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn test_provider_package_rejects_ui_format_workflow() {
+        let mut package = ProviderPackage {
+            package_version: PROVIDER_PACKAGE_VERSION,
+            provider: ProviderEntry::new(
+                "Broken",
+                ProviderOutputType::Image,
+                ProviderConnection::ComfyUi {
+                    base_url: "http://127.0.0.1:8188".to_string(),
+                    workflow_path: None,
+                    manifest_path: None,
+                },
+            ),
+            manifest: None,
+            workflow: Some(serde_json::json!({ "nodes": [], "links": [] })),
+            workflow_file_name: None,
+        };
+        let json = package.to_json().unwrap();
+        assert!(ProviderPackage::from_json(&json).is_err());
+
+        package.workflow = None;
+        assert!(ProviderPackage::from_json(&package.to_json().unwrap()).is_ok());
+    }
+}
@@ -5,14 +5,50 @@ use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
 
-use crate::state::ProviderEntry;
+use crate::state::{portable_path, resolve_project_path, ProviderConnection, ProviderEntry, ProviderScope};
 
 pub fn load_provider_entries(project_root: &Path) -> io::Result<Vec<ProviderEntry>> {
-    load_provider_entries_from(&providers_root(project_root))
+    let mut entries = load_provider_entries_from(&providers_root(project_root), ProviderScope::Project)?;
+    for entry in entries.iter_mut() {
+        resolve_provider_entry_paths(entry, project_root);
+    }
+    Ok(entries)
 }
 
 pub fn load_global_provider_entries() -> io::Result<Vec<ProviderEntry>> {
-    load_provider_entries_from(&global_providers_root())
+    load_provider_entries_from(&global_providers_root(), ProviderScope::Global)
+}
+
+/// Load global providers merged with the project's own providers.
+///
+/// Project-local entries replace global entries that share the same UUID.
+pub fn load_merged_provider_entries_or_empty(project_root: Option<&Path>) -> Vec<ProviderEntry> {
+    let entries = load_global_provider_entries_or_empty();
+    let Some(project_root) = project_root else {
+        return entries;
+    };
+    match load_provider_entries(project_root) {
+        Ok(project_entries) => merge_provider_entries(entries, project_entries),
+        Err(err) => {
+            println!("Failed to load project provider entries: {}", err);
+            entries
+        }
+    }
+}
+
+/// `global` with each project entry replacing the global one of the same UUID,
+/// or appended when there is none.
+pub fn merge_provider_entries(
+    mut global: Vec<ProviderEntry>,
+    project: Vec<ProviderEntry>,
+) -> Vec<ProviderEntry> {
+    for project_entry in project {
+        match global.iter_mut().find(|entry| entry.id == project_entry.id) {
+            Some(existing) => *existing = project_entry,
+            None => global.push(project_entry),
+        }
+    }
+    global
 }
 
 pub fn load_global_provider_entries_or_empty() -> Vec<ProviderEntry> {
@@ -26,7 +62,47 @@ pub fn load_global_provider_entries_or_empty() -> Vec<ProviderEntry> {
 }
 
 pub fn save_provider_entry(project_root: &Path, entry: &ProviderEntry) -> io::Result<PathBuf> {
-    save_provider_entry_to(&providers_root(project_root), entry)
+    save_provider_entry_to(&providers_root(project_root), &portable_provider_entry(entry, project_root))
+}
+
+/// `entry` as a project provider file stores it: workflow and manifest paths
+/// inside the project are made relative so the folder can be moved.
+pub fn portable_provider_entry(entry: &ProviderEntry, project_root: &Path) -> ProviderEntry {
+    let mut entry = entry.clone();
+    for path in comfy_paths_mut(&mut entry) {
+        if let Some(relative) = portable_path(Path::new(path.as_str()), project_root) {
+            *path = relative.to_string_lossy().to_string();
+        }
+    }
+    entry
+}
+
+/// Undo `portable_provider_entry` after loading a project provider. Relative
+/// paths that don't exist in the project are left for the app resource
+/// lookup (e.g. the bundled `workflows/` folder).
+pub(crate) fn resolve_provider_entry_paths(entry: &mut ProviderEntry, project_root: &Path) {
+    for path in comfy_paths_mut(entry) {
+        let stored = Path::new(path.as_str());
+        if stored.is_absolute() {
+            continue;
+        }
+        let resolved = resolve_project_path(stored, project_root);
+        if resolved.exists() {
+            *path = resolved.to_string_lossy().to_string();
+        }
+    }
+}
+
+fn comfy_paths_mut(entry: &mut ProviderEntry) -> impl Iterator<Item = &mut String> {
+    let paths = match &mut entry.connection {
+        ProviderConnection::ComfyUi {
+            workflow_path,
+            manifest_path,
+            ..
+        } => vec![workflow_path, manifest_path],
+        _ => Vec::new(),
+    };
+    paths.into_iter().flatten()
 }
 
 pub fn save_global_provider_entry(entry: &ProviderEntry) -> io::Result<PathBuf> {
@@ -41,10 +117,35 @@ pub fn global_providers_root() -> PathBuf {
     base.join("NLA-AI-VideoCreator").join("providers")
 }
 
+pub fn project_providers_root(project_root: &Path) -> PathBuf {
+    providers_root(project_root)
+}
+
 pub fn list_global_provider_files() -> Vec<PathBuf> {
-    let root = global_providers_root();
+    list_provider_files_in(&global_providers_root())
+}
+
+/// List global provider files followed by the project's provider files (if any).
+pub fn list_provider_files(project_root: Option<&Path>) -> Vec<PathBuf> {
+    let mut files = list_global_provider_files();
+    if let Some(project_root) = project_root {
+        files.extend(list_provider_files_in(&providers_root(project_root)));
+    }
+    files
+}
+
+/// Scope of a provider file, judged by which providers root it lives under.
+pub fn provider_scope_for_path(path: &Path) -> ProviderScope {
+    if path.starts_with(global_providers_root()) {
+        ProviderScope::Global
+    } else {
+        ProviderScope::Project
+    }
+}
+
+fn list_provider_files_in(root: &Path) -> Vec<PathBuf> {
     let mut files = Vec::new();
-    let read_dir = match fs::read_dir(&root) {
+    let read_dir = match fs::read_dir(root) {
         Ok(read_dir) => read_dir,
         Err(_) => return files,
     };
@@ -70,10 +171,69 @@ pub fn write_provider_file(path: &Path, contents: &str) -> io::Result<()> {
     Ok(())
 }
 
+/// Flip the `favorite` flag in a provider file, leaving the rest of the JSON untouched.
+pub fn toggle_provider_favorite(path: &Path) -> io::Result<bool> {
+    let json = fs::read_to_string(path)?;
+    let mut value: serde_json::Value = serde_json::from_str(&json)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    let Some(object) = value.as_object_mut() else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "provider config is not a JSON object",
+        ));
+    };
+    let favorite = !object
+        .get("favorite")
+        .and_then(|flag| flag.as_bool())
+        .unwrap_or(false);
+    object.insert("favorite".to_string(), serde_json::Value::Bool(favorite));
+    let json = serde_json::to_string_pretty(&value)
+        .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+    fs::write(path, json)?;
+    Ok(favorite)
+}
+
 pub fn provider_path_for_entry(entry: &ProviderEntry) -> PathBuf {
     global_providers_root().join(format!("{}.json", entry.id))
 }
 
+/// Path for an entry saved in `scope`; project scope needs a project root.
+pub fn provider_path_in_scope(
+    entry: &ProviderEntry,
+    scope: ProviderScope,
+    project_root: Option<&Path>,
+) -> Option<PathBuf> {
+    match scope {
+        ProviderScope::Global => Some(provider_path_for_entry(entry)),
+        ProviderScope::Project => project_root
+            .map(|root| providers_root(root).join(format!("{}.json", entry.id))),
+    }
+}
+
+/// Where a provider's manifest belongs once saved in `scope`. A manifest already
+/// on that side (inside the project for project providers, outside it for
+/// global ones) stays put; otherwise it moves under that scope's providers
+/// folder so it travels with the provider.
+pub fn manifest_path_for_scope(
+    manifest_path: &Path,
+    scope: ProviderScope,
+    project_root: Option<&Path>,
+) -> PathBuf {
+    let inside_project = project_root.is_some_and(|root| manifest_path.starts_with(root));
+    let file_name = manifest_path.file_name().unwrap_or_default();
+    match (scope, project_root) {
+        (ProviderScope::Project, Some(root)) if !inside_project => {
+            providers_root(root).join(MANIFESTS_DIR).join(file_name)
+        }
+        (ProviderScope::Global, _) if inside_project => {
+            global_providers_root().join(MANIFESTS_DIR).join(file_name)
+        }
+        _ => manifest_path.to_path_buf(),
+    }
+}
+
+const MANIFESTS_DIR: &str = "manifests";
+
 pub fn default_provider_entry() -> ProviderEntry {
     let mut entry = ProviderEntry::new(
         "New Provider",
@@ -92,7 +252,7 @@ fn providers_root(project_root: &Path) -> PathBuf {
     project_root.join(".providers")
 }
 
-fn load_provider_entries_from(root: &Path) -> io::Result<Vec<ProviderEntry>> {
+fn load_provider_entries_from(root: &Path, scope: ProviderScope) -> io::Result<Vec<ProviderEntry>> {
     if !root.exists() {
         return Ok(Vec::new());
     }
@@ -117,20 +277,21 @@ fn load_provider_entries_from(root: &Path) -> io::Result<Vec<ProviderEntry>> {
                 continue;
             }
         };
-        let provider: ProviderEntry = match serde_json::from_str(&json) {
+        let mut provider: ProviderEntry = match serde_json::from_str(&json) {
             Ok(provider) => provider,
             Err(err) => {
                 println!("Failed to parse provider config {:?}: {}", path, err);
                 continue;
             }
         };
+        provider.scope = scope;
         entries.push(provider);
     }
 
     Ok(entries)
 }
 
-fn save_provider_entry_to(root: &Path, entry: &ProviderEntry) -> io::Result<PathBuf> {
+pub(crate) fn save_provider_entry_to(root: &Path, entry: &ProviderEntry) -> io::Result<PathBuf> {
     fs::create_dir_all(root)?;
     let path = root.join(format!("{}.json", entry.id));
     let json = serde_json::to_string_pretty(entry)
@@ -145,3 +306,102 @@ fn is_json_file(path: &Path) -> bool {
         .map(|ext| ext.eq_ignore_ascii_case("json"))
         .unwrap_or(false)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::ProviderOutputType;
+
+    fn comfy_entry(workflow_path: &Path, manifest_path: &Path) -> ProviderEntry {
+        ProviderEntry::new(
+            "Comfy",
+            ProviderOutputType::Image,
+            ProviderConnection::ComfyUi {
+                base_url: "http://127.0.0.1:8188".to_string(),
+                workflow_path: Some(workflow_path.to_string_lossy().to_string()),
+                manifest_path: Some(manifest_path.to_string_lossy().to_string()),
+            },
+        )
+    }
+
+    fn comfy_paths(entry: &ProviderEntry) -> (String, String) {
+        match &entry.connection {
+            ProviderConnection::ComfyUi {
+                workflow_path,
+                manifest_path,
+                ..
+            } => (workflow_path.clone().unwrap(), manifest_path.clone().unwrap()),
+            other => panic!("unexpected connection {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_project_entry_overrides_global_entry() {
+        let shared_manifest = Path::new("global").join("flow_manifest.json");
+        let project_manifest = Path::new("project").join("flow_manifest.json");
+        let global = comfy_entry(Path::new("flow_API.json"), &shared_manifest);
+        let global_only = comfy_entry(Path::new("other_API.json"), &shared_manifest);
+        let mut project = comfy_entry(Path::new("flow_API.json"), &project_manifest);
+        project.id = global.id;
+        project.scope = ProviderScope::Project;
+        let project_only = ProviderEntry {
+            scope: ProviderScope::Project,
+            ..comfy_entry(Path::new("new_API.json"), &project_manifest)
+        };
+
+        let merged = merge_provider_entries(
+            vec![global.clone(), global_only.clone()],
+            vec![project.clone(), project_only.clone()],
+        );
+        assert_eq!(merged, vec![project.clone(), global_only, project_only]);
+        let (_, manifest) = comfy_paths(&merged[0]);
+        assert_eq!(Path::new(&manifest), project_manifest.as_path());
+    }
+
+    #[test]
+    fn test_manifest_moves_with_provider_scope() {
+        let root = std::env::temp_dir().join("nla-scope").join("project");
+        let outside = std::env::temp_dir().join("nla-scope").join("shared").join("flow_manifest.json");
+        let inside = root.join("workflows").join("flow_manifest.json");
+
+        // Moving to the project pulls an outside manifest into .providers
+        let moved_in = manifest_path_for_scope(&outside, ProviderScope::Project, Some(&root));
+        assert_eq!(moved_in, root.join(".providers").join("manifests").join("flow_manifest.json"));
+        assert_eq!(manifest_path_for_scope(&inside, ProviderScope::Project, Some(&root)), inside);
+
+        // Moving to global takes a project manifest out of the project
+        let moved_out = manifest_path_for_scope(&inside, ProviderScope::Global, Some(&root));
+        assert_eq!(moved_out, global_providers_root().join("manifests").join("flow_manifest.json"));
+        assert_eq!(manifest_path_for_scope(&outside, ProviderScope::Global, Some(&root)), outside);
+        assert_eq!(manifest_path_for_scope(&inside, ProviderScope::Global, None), inside);
+    }
+
+    #[test]
+    fn test_project_provider_paths_round_trip() {
+        let base = std::env::temp_dir().join(format!("nla-provider-paths-{}", uuid::Uuid::new_v4()));
+        let root = base.join("project");
+        let workflow = root.join("workflows").join("flow_API.json");
+        let outside_manifest = base.join("shared").join("flow.manifest.json");
+        fs::create_dir_all(workflow.parent().unwrap()).unwrap();
+        fs::write(&workflow, "{}").unwrap();
+
+        let entry = comfy_entry(&workflow, &outside_manifest);
+        let path = save_provider_entry(&root, &entry).unwrap();
+
+        // On disk the workflow inside the project is relative, the outside manifest absolute
+        let stored: ProviderEntry = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        let (stored_workflow, stored_manifest) = comfy_paths(&stored);
+        assert_eq!(Path::new(&stored_workflow), Path::new("workflows").join("flow_API.json"));
+        assert_eq!(Path::new(&stored_manifest), outside_manifest.as_path());
+
+        // Loading from a moved folder resolves against the new root
+        let moved = base.join("moved");
+        fs::rename(&root, &moved).unwrap();
+        let loaded = load_provider_entries(&moved).unwrap();
+        let (loaded_workflow, loaded_manifest) = comfy_paths(&loaded[0]);
+        assert_eq!(Path::new(&loaded_workflow), moved.join("workflows").join("flow_API.json"));
+        assert_eq!(Path::new(&loaded_manifest), outside_manifest.as_path());
+
+        let _ = fs::remove_dir_all(&base);
+    }
+}
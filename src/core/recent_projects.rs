@@ -0,0 +1,215 @@
+//! Recently opened projects, kept in `recent.json` in the app config folder.
+//!
+//! Every project that is created or opened is recorded with a timestamp, wherever
+//! it lives on disk, so projects opened through Browse show up on the startup
+//! screen next time.
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// How many projects the list remembers.
+pub const MAX_RECENT_PROJECTS: usize = 12;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecentProject {
+    /// Project folder (the one holding `project.json`).
+    pub path: PathBuf,
+    pub last_opened: DateTime<Utc>,
+}
+
+impl RecentProject {
+    /// Folder name shown in the list.
+    pub fn display_name(&self) -> String {
+        self.path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("Unknown")
+            .to_string()
+    }
+}
+
+/// Recent projects, most recently opened first.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecentProjects {
+    #[serde(default)]
+    entries: Vec<RecentProject>,
+}
+
+impl RecentProjects {
+    /// Load the user's list, or an empty one when the file is missing or unreadable.
+    pub fn load_user() -> Self {
+        Self::load(&crate::core::paths::recent_projects_path())
+    }
+
+    pub fn load(path: &Path) -> Self {
+        let json = match std::fs::read_to_string(path) {
+            Ok(json) => json,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Self::default(),
+            Err(err) => {
+                eprintln!("[RECENT WARN] Failed to read {}: {}", path.display(), err);
+                return Self::default();
+            }
+        };
+        match serde_json::from_str::<Self>(&json) {
+            Ok(mut recent) => {
+                recent.normalize();
+                recent
+            }
+            Err(err) => {
+                eprintln!("[RECENT WARN] Ignoring malformed recent projects list: {}", err);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|err| err.to_string())?;
+        std::fs::write(path, json).map_err(|err| err.to_string())
+    }
+
+    pub fn entries(&self) -> &[RecentProject] {
+        &self.entries
+    }
+
+    /// Move `project_path` to the top of the list, stamped `opened_at`.
+    pub fn record(&mut self, project_path: &Path, opened_at: DateTime<Utc>) {
+        let path = absolute_project_path(project_path);
+        self.entries.retain(|entry| entry.path != path);
+        self.entries.push(RecentProject {
+            path,
+            last_opened: opened_at,
+        });
+        self.normalize();
+    }
+
+    /// Forget `project_path`, e.g. after the project was deleted.
+    pub fn remove(&mut self, project_path: &Path) -> bool {
+        let path = absolute_project_path(project_path);
+        let before = self.entries.len();
+        self.entries.retain(|entry| entry.path != path);
+        self.entries.len() != before
+    }
+
+    /// Drop entries whose folder no longer exists. Returns true if any were removed.
+    pub fn prune_missing(&mut self) -> bool {
+        let before = self.entries.len();
+        self.entries.retain(|entry| entry.path.is_dir());
+        self.entries.len() != before
+    }
+
+    /// Newest first, one entry per folder, capped at `MAX_RECENT_PROJECTS`.
+    fn normalize(&mut self) {
+        self.entries.sort_by(|a, b| b.last_opened.cmp(&a.last_opened));
+        let mut seen = Vec::with_capacity(self.entries.len());
+        self.entries.retain(|entry| {
+            if seen.contains(&entry.path) {
+                return false;
+            }
+            seen.push(entry.path.clone());
+            true
+        });
+        self.entries.truncate(MAX_RECENT_PROJECTS);
+    }
+}
+
+/// Record `project_path` as just opened in the user's list, pruning folders
+/// that have gone away while at it.
+pub fn record_recent_project(project_path: &Path) {
+    let list_path = crate::core::paths::recent_projects_path();
+    let mut recent = RecentProjects::load(&list_path);
+    recent.prune_missing();
+    recent.record(project_path, Utc::now());
+    if let Err(err) = recent.save(&list_path) {
+        eprintln!("[RECENT WARN] Failed to save {}: {}", list_path.display(), err);
+    }
+}
+
+/// Remove `project_path` from the user's list.
+pub fn forget_recent_project(project_path: &Path) {
+    let list_path = crate::core::paths::recent_projects_path();
+    let mut recent = RecentProjects::load(&list_path);
+    if recent.remove(project_path) {
+        if let Err(err) = recent.save(&list_path) {
+            eprintln!("[RECENT WARN] Failed to save {}: {}", list_path.display(), err);
+        }
+    }
+}
+
+/// Relative paths are resolved against the working directory so the same
+/// folder is only listed once however it was reached.
+fn absolute_project_path(path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir()
+            .map(|dir| dir.join(path))
+            .unwrap_or_else(|_| path.to_path_buf())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use uuid::Uuid;
+
+    fn at(seconds: i64) -> DateTime<Utc> {
+        Utc.timestamp_opt(seconds, 0).unwrap()
+    }
+
+    #[test]
+    fn test_recent_projects_newest_first_and_deduplicated() {
+        let mut recent = RecentProjects::default();
+        recent.record(Path::new("/projects/a"), at(100));
+        recent.record(Path::new("/elsewhere/b"), at(200));
+        recent.record(Path::new("/projects/c"), at(150));
+        let order: Vec<_> = recent.entries().iter().map(|entry| entry.display_name()).collect();
+        assert_eq!(order, vec!["b", "c", "a"]);
+
+        // Reopening moves a project to the top instead of listing it twice
+        recent.record(Path::new("/projects/a/"), at(300));
+        assert_eq!(recent.entries().len(), 3);
+        assert_eq!(recent.entries()[0].display_name(), "a");
+        assert_eq!(recent.entries()[0].last_opened, at(300));
+
+        // The list is capped, dropping the oldest
+        for index in 0..MAX_RECENT_PROJECTS as i64 {
+            recent.record(&PathBuf::from(format!("/many/{}", index)), at(1000 + index));
+        }
+        assert_eq!(recent.entries().len(), MAX_RECENT_PROJECTS);
+        assert!(recent.entries().iter().all(|entry| entry.path.parent().unwrap().ends_with("many")));
+    }
+
+    #[test]
+    fn test_recent_projects_prune_missing_and_round_trip() {
+        let root = std::env::temp_dir().join(format!("nla-recent-{}", Uuid::new_v4()));
+        let kept = root.join("kept");
+        std::fs::create_dir_all(&kept).unwrap();
+
+        let mut recent = RecentProjects::default();
+        recent.record(&kept, at(100));
+        recent.record(&root.join("deleted"), at(200));
+        assert!(recent.prune_missing());
+        assert_eq!(recent.entries().len(), 1);
+        assert_eq!(recent.entries()[0].path, kept);
+        assert!(!recent.prune_missing());
+
+        // Saved and reloaded lists keep their order and timestamps
+        recent.record(&root, at(300));
+        let list_path = root.join("recent.json");
+        recent.save(&list_path).unwrap();
+        assert_eq!(RecentProjects::load(&list_path), recent);
+
+        assert!(recent.remove(&kept));
+        assert_eq!(recent.entries().len(), 1);
+        // A missing file is an empty list
+        assert!(RecentProjects::load(&root.join("missing.json")).entries().is_empty());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -1,14 +1,23 @@
-use std::path::PathBuf;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::sync::Arc;
 use tokio::sync::Semaphore;
 use uuid::Uuid;
 use crate::state::Asset;
+use image::codecs::gif::GifDecoder;
+use image::codecs::webp::WebPDecoder;
 use image::imageops::FilterType;
-use image::{DynamicImage, ImageFormat, GenericImageView};
+use image::{AnimationDecoder, DynamicImage, ImageFormat, GenericImageView};
 
 const THUMBNAIL_INTERVAL_SECONDS: f64 = 1.0;
 const THUMBNAIL_HEIGHT: u32 = 120;
+/// Animated GIF/WebP thumbnails cover at least this much of the looping
+/// animation, so tiles along a long image clip keep following it.
+const ANIMATED_THUMBNAIL_SPAN_SECONDS: f64 = 30.0;
+/// Length given to frames with no (or a near-zero) delay, as browsers do.
+const DEFAULT_ANIMATION_FRAME_SECONDS: f64 = 0.1;
 
 /// Manages the generation of thumbnails for assets
 #[derive(Debug)]
@@ -52,7 +61,13 @@ impl Thumbnailer {
 
         let (absolute_source_path, source_kind) = match &asset.kind {
             crate::state::AssetKind::Video { path } => {
-                (self.project_root.join(path), SourceKind::Video)
+                // The proxy decodes much faster and is still far larger than a thumbnail
+                let proxy = asset
+                    .proxy
+                    .as_ref()
+                    .map(|proxy| self.project_root.join(&proxy.path))
+                    .filter(|proxy| proxy.is_file());
+                (proxy.unwrap_or_else(|| self.project_root.join(path)), SourceKind::Video)
             }
             crate::state::AssetKind::Image { path } => {
                 (self.project_root.join(path), SourceKind::Still)
@@ -150,13 +165,7 @@ impl Thumbnailer {
         let asset_id = asset.id.to_string();
         let output_dir = self.cache_root.join(&asset_id);
 
-        if !force
-            && output_dir.exists()
-            && output_dir
-                .read_dir()
-                .map(|mut i| i.next().is_some())
-                .unwrap_or(false)
-        {
+        if !force && has_thumbnails(&output_dir) {
             return Some(output_dir);
         }
 
@@ -202,7 +211,7 @@ impl Thumbnailer {
                     }
                 }
                 SourceKind::Still => {
-                    if let Err(err) = generate_still_thumbnail(&source, &out) {
+                    if let Err(err) = generate_image_thumbnails(&source, &out) {
                         println!(
                             "Failed to generate image thumbnail for {}: {}",
                             asset_id, err
@@ -223,7 +232,115 @@ enum SourceKind {
     Still,
 }
 
-fn generate_still_thumbnail(source: &PathBuf, out_dir: &PathBuf) -> Result<(), String> {
+/// Thumbnails for an image asset: one per interval across the animation for
+/// animated GIF/WebP, otherwise (or if the animation can't be decoded) a
+/// single thumbnail of the first frame.
+/// Whether an earlier run left thumbnails in `dir`.
+fn has_thumbnails(dir: &Path) -> bool {
+    dir.read_dir()
+        .map(|mut entries| entries.next().is_some())
+        .unwrap_or(false)
+}
+
+fn generate_image_thumbnails(source: &Path, out_dir: &Path) -> Result<(), String> {
+    match decode_animation(source) {
+        Ok(Some(animation)) => {
+            if let Err(err) = write_animation_thumbnails(&animation, out_dir) {
+                println!(
+                    "Thumbnailer Warning: falling back to the first frame of {:?}: {}",
+                    source, err
+                );
+            } else {
+                return Ok(());
+            }
+        }
+        Ok(None) => {}
+        Err(err) => println!(
+            "Thumbnailer Warning: falling back to the first frame of {:?}: {}",
+            source, err
+        ),
+    }
+    generate_still_thumbnail(source, out_dir)
+}
+
+/// Frames of an animated image, already scaled to thumbnail size.
+struct Animation {
+    frames: Vec<DynamicImage>,
+    /// When each frame starts, in seconds from the start of the loop.
+    frame_starts: Vec<f64>,
+    duration: f64,
+}
+
+/// Decode every frame of an animated GIF or WebP. `None` for other formats
+/// and for files with a single frame.
+fn decode_animation(source: &Path) -> Result<Option<Animation>, String> {
+    let extension = source
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase());
+    let open = || File::open(source).map(BufReader::new).map_err(|err| err.to_string());
+    let decoded = match extension.as_deref() {
+        Some("gif") => GifDecoder::new(open()?)
+            .map_err(|err| err.to_string())?
+            .into_frames(),
+        Some("webp") => {
+            let decoder = WebPDecoder::new(open()?).map_err(|err| err.to_string())?;
+            if !decoder.has_animation() {
+                return Ok(None);
+            }
+            decoder.into_frames()
+        }
+        _ => return Ok(None),
+    };
+
+    let mut frames = Vec::new();
+    let mut frame_starts = Vec::new();
+    let mut duration = 0.0;
+    for frame in decoded {
+        let frame = frame.map_err(|err| err.to_string())?;
+        let (numer, denom) = frame.delay().numer_denom_ms();
+        let mut seconds = numer as f64 / denom.max(1) as f64 / 1000.0;
+        if seconds <= 0.01 {
+            seconds = DEFAULT_ANIMATION_FRAME_SECONDS;
+        }
+        let image = DynamicImage::ImageRgba8(frame.into_buffer());
+        frames.push(resize_to_height(image, THUMBNAIL_HEIGHT));
+        frame_starts.push(duration);
+        duration += seconds;
+    }
+    if frames.len() < 2 {
+        return Ok(None);
+    }
+    Ok(Some(Animation {
+        frames,
+        frame_starts,
+        duration,
+    }))
+}
+
+/// Index of the frame showing at `time`, looping the animation.
+fn animation_frame_at(frame_starts: &[f64], duration: f64, time: f64) -> usize {
+    let looped = if duration > 0.0 { time.rem_euclid(duration) } else { 0.0 };
+    frame_starts
+        .partition_point(|start| *start <= looped)
+        .saturating_sub(1)
+}
+
+fn write_animation_thumbnails(animation: &Animation, out_dir: &Path) -> Result<(), String> {
+    let span = animation.duration.max(ANIMATED_THUMBNAIL_SPAN_SECONDS);
+    let count = (span / THUMBNAIL_INTERVAL_SECONDS).ceil().max(1.0) as usize;
+    for index in 0..count {
+        let time = index as f64 * THUMBNAIL_INTERVAL_SECONDS;
+        let frame_index = animation_frame_at(&animation.frame_starts, animation.duration, time);
+        let output_path = out_dir.join(format!("thumb_{:04}.jpg", index + 1));
+        animation.frames[frame_index]
+            .save_with_format(output_path, ImageFormat::Jpeg)
+            .map_err(|err| err.to_string())?;
+    }
+    Ok(())
+}
+
+fn generate_still_thumbnail(source: &Path, out_dir: &Path) -> Result<(), String> {
     let image = image::open(source).map_err(|err| err.to_string())?;
     let resized = resize_to_height(image, THUMBNAIL_HEIGHT);
     let output_path = out_dir.join("thumb_0001.jpg");
@@ -273,3 +390,112 @@ fn resolve_generative_source(
 
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::codecs::gif::GifEncoder;
+    use image::{Delay, Frame, Rgba, RgbaImage};
+
+    fn thumbnail_color(path: &Path) -> [u8; 3] {
+        let image = image::open(path).unwrap().to_rgb8();
+        let pixel = image.get_pixel(image.width() / 2, image.height() / 2);
+        [pixel[0], pixel[1], pixel[2]]
+    }
+
+    fn is_red(color: [u8; 3]) -> bool {
+        color[0] > 200 && color[2] < 60
+    }
+
+    fn is_blue(color: [u8; 3]) -> bool {
+        color[2] > 200 && color[0] < 60
+    }
+
+    #[test]
+    fn test_animation_frame_at_loops() {
+        let starts = [0.0, 0.5, 1.5];
+        assert_eq!(animation_frame_at(&starts, 2.0, 0.0), 0);
+        assert_eq!(animation_frame_at(&starts, 2.0, 0.5), 1);
+        assert_eq!(animation_frame_at(&starts, 2.0, 1.9), 2);
+        assert_eq!(animation_frame_at(&starts, 2.0, 2.6), 1);
+    }
+
+    #[test]
+    fn test_animated_gif_thumbnails_follow_the_animation() {
+        let root = std::env::temp_dir().join(format!("nla-thumbs-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&root).unwrap();
+        let source = root.join("blink.gif");
+        {
+            // Red for a second, then blue for a second
+            let mut encoder = GifEncoder::new(File::create(&source).unwrap());
+            for color in [[255, 0, 0, 255], [0, 0, 255, 255]] {
+                let buffer = RgbaImage::from_pixel(16, 16, Rgba(color));
+                let delay = Delay::from_numer_denom_ms(1000, 1);
+                encoder.encode_frame(Frame::from_parts(buffer, 0, 0, delay)).unwrap();
+            }
+        }
+
+        let thumbnailer = Thumbnailer::new(root.clone());
+        let asset_id = Uuid::new_v4();
+        let out = thumbnailer.cache_root.join(asset_id.to_string());
+        std::fs::create_dir_all(&out).unwrap();
+        generate_image_thumbnails(&source, &out).unwrap();
+
+        let at = |time: f64| thumbnailer.get_thumbnail_path(asset_id, time).unwrap();
+        assert_ne!(at(0.0), at(1.0));
+        assert!(is_red(thumbnail_color(&at(0.0))));
+        assert!(is_blue(thumbnail_color(&at(1.0))));
+        // The animation loops across the clip
+        assert!(is_red(thumbnail_color(&at(2.0))));
+        assert!(is_blue(thumbnail_color(&at(3.5))));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[tokio::test]
+    async fn test_force_regenerates_existing_thumbnails() {
+        let root = std::env::temp_dir().join(format!("nla-thumbs-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&root).unwrap();
+        let source = root.join("still.png");
+        let paint = |color| RgbaImage::from_pixel(16, 16, Rgba(color)).save(&source).unwrap();
+        paint([255, 0, 0, 255]);
+
+        let thumbnailer = Thumbnailer::new(root.clone());
+        let asset = Asset::new_image("still", PathBuf::from("still.png"));
+        thumbnailer.generate(&asset, false).await.unwrap();
+        let thumb = thumbnailer.get_thumbnail_path(asset.id, 0.0).unwrap();
+        assert!(is_red(thumbnail_color(&thumb)));
+
+        // The source changes; existing thumbnails are kept unless forced
+        paint([0, 0, 255, 255]);
+        thumbnailer.generate(&asset, false).await.unwrap();
+        assert!(is_red(thumbnail_color(&thumb)));
+        thumbnailer.generate(&asset, true).await.unwrap();
+        let thumb = thumbnailer.get_thumbnail_path(asset.id, 0.0).unwrap();
+        assert!(is_blue(thumbnail_color(&thumb)));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_static_image_has_one_thumbnail() {
+        let root = std::env::temp_dir().join(format!("nla-thumbs-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&root).unwrap();
+        let source = root.join("still.png");
+        RgbaImage::from_pixel(16, 16, Rgba([255, 0, 0, 255]))
+            .save(&source)
+            .unwrap();
+
+        let thumbnailer = Thumbnailer::new(root.clone());
+        let asset_id = Uuid::new_v4();
+        let out = thumbnailer.cache_root.join(asset_id.to_string());
+        std::fs::create_dir_all(&out).unwrap();
+        generate_image_thumbnails(&source, &out).unwrap();
+
+        let first = thumbnailer.get_thumbnail_path(asset_id, 0.0).unwrap();
+        assert_eq!(thumbnailer.get_thumbnail_path(asset_id, 3.0), Some(first.clone()));
+        assert!(is_red(thumbnail_color(&first)));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,113 @@
+//! Time formatting for the ruler and the current-time readout.
+
+use crate::state::TimeFormat;
+
+/// Format `seconds` for display at the given frame rate.
+pub fn format_time(seconds: f64, fps: f64, format: TimeFormat) -> String {
+    let seconds = if seconds.is_finite() { seconds.max(0.0) } else { 0.0 };
+    let fps = fps.max(1.0);
+    match format {
+        TimeFormat::Seconds => format_seconds(seconds),
+        TimeFormat::Frames => format!("{}", (seconds * fps).round() as u64),
+        TimeFormat::Timecode => format_timecode(seconds, fps),
+    }
+}
+
+/// `M:SS`, with tenths only when the time is not on a whole second.
+fn format_seconds(seconds: f64) -> String {
+    let tenths = (seconds * 10.0).round() as u64;
+    let whole = tenths / 10;
+    let minutes = whole / 60;
+    let secs = whole % 60;
+    if tenths % 10 == 0 {
+        format!("{}:{:02}", minutes, secs)
+    } else {
+        format!("{}:{:02}.{}", minutes, secs, tenths % 10)
+    }
+}
+
+/// Frame numbers skipped at the start of each minute for NTSC drop-frame
+/// rates (29.97 and 59.94), or `None` for non-drop rates.
+fn drop_frames_per_minute(fps: f64) -> Option<u64> {
+    if (fps - 30000.0 / 1001.0).abs() < 0.01 {
+        Some(2)
+    } else if (fps - 60000.0 / 1001.0).abs() < 0.01 {
+        Some(4)
+    } else {
+        None
+    }
+}
+
+/// SMPTE `HH:MM:SS:FF`. Drop-frame rates use `;` before the frame field and
+/// skip frame labels so the readout stays in step with wall-clock time.
+fn format_timecode(seconds: f64, fps: f64) -> String {
+    let nominal_fps = fps.round().max(1.0) as u64;
+    let mut frame = (seconds * fps).round() as u64;
+    let separator = match drop_frames_per_minute(fps) {
+        Some(drop) => {
+            let frames_per_minute = nominal_fps * 60 - drop;
+            let frames_per_ten_minutes = frames_per_minute * 10 + drop;
+            let tens = frame / frames_per_ten_minutes;
+            let remainder = frame % frames_per_ten_minutes;
+            frame += drop * 9 * tens;
+            if remainder > drop {
+                frame += drop * ((remainder - drop) / frames_per_minute);
+            }
+            ';'
+        }
+        None => ':',
+    };
+
+    let frames = frame % nominal_fps;
+    let total_seconds = frame / nominal_fps;
+    format!(
+        "{:02}:{:02}:{:02}{}{:02}",
+        total_seconds / 3600,
+        (total_seconds / 60) % 60,
+        total_seconds % 60,
+        separator,
+        frames
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NTSC_30: f64 = 30000.0 / 1001.0;
+    const NTSC_60: f64 = 60000.0 / 1001.0;
+
+    fn timecode(seconds: f64, fps: f64) -> String {
+        format_time(seconds, fps, TimeFormat::Timecode)
+    }
+
+    #[test]
+    fn test_integer_rate_timecode() {
+        assert_eq!(timecode(1.5, 24.0), "00:00:01:12");
+        assert_eq!(timecode(61.0, 30.0), "00:01:01:00");
+        assert_eq!(timecode(3661.5, 60.0), "01:01:01:30");
+        assert_eq!(timecode(59.0 + 23.0 / 24.0, 24.0), "00:00:59:23");
+    }
+
+    #[test]
+    fn test_drop_frame_timecode() {
+        // Frame 1800 at 29.97 is the first frame of minute one, labelled ;02
+        assert_eq!(timecode(1799.0 / NTSC_30, NTSC_30), "00:00:59;29");
+        assert_eq!(timecode(1800.0 / NTSC_30, NTSC_30), "00:01:00;02");
+        // Every tenth minute keeps its frame labels
+        assert_eq!(timecode(17982.0 / NTSC_30, NTSC_30), "00:10:00;00");
+        // An hour of 29.97 runs 107892 frames
+        assert_eq!(timecode(107892.0 / NTSC_30, NTSC_30), "01:00:00;00");
+
+        assert_eq!(timecode(3600.0 / NTSC_60, NTSC_60), "00:01:00;04");
+        assert_eq!(timecode(35964.0 / NTSC_60, NTSC_60), "00:10:00;00");
+    }
+
+    #[test]
+    fn test_seconds_and_frames() {
+        assert_eq!(format_time(65.0, 30.0, TimeFormat::Seconds), "1:05");
+        assert_eq!(format_time(0.5, 30.0, TimeFormat::Seconds), "0:00.5");
+        assert_eq!(format_time(2.0, 24.0, TimeFormat::Frames), "48");
+        assert_eq!(format_time(1.0, NTSC_30, TimeFormat::Frames), "30");
+    }
+}
@@ -1,5 +1,7 @@
 use uuid::Uuid;
 
+use crate::state::{Clip, Marker};
+
 /// Category of snap target used for tie-breaking.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum SnapTargetKind {
@@ -67,6 +69,20 @@ impl SnapTarget {
     }
 }
 
+/// Start and end targets for every clip whose edge passes `in_view`.
+/// Placeholders count like any other clip.
+pub fn clip_edge_targets(clips: &[Clip], fps: f64, in_view: impl Fn(f64) -> bool) -> Vec<SnapTarget> {
+    let mut targets = Vec::new();
+    for clip in clips {
+        for edge in [clip.start_time, clip.end_time()] {
+            if in_view(edge) {
+                targets.push(SnapTarget::clip_edge(frames_from_seconds(edge, fps).round(), clip.id));
+            }
+        }
+    }
+    targets
+}
+
 /// Result of a snap query in frame units.
 #[derive(Clone, Copy, Debug)]
 pub struct SnapMatch {
@@ -76,6 +92,80 @@ pub struct SnapMatch {
     pub target: SnapTarget,
 }
 
+/// Where a dragged clip edge lands, in frame units.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EdgeDrag {
+    pub start_frames: f64,
+    pub duration_frames: f64,
+    /// Frame of the snap target the dragged edge ended up on, if any.
+    pub snapped_frame: Option<f64>,
+}
+
+/// Slack for frame positions computed from seconds, so a bound sitting on a
+/// frame boundary isn't pushed to the next one.
+const FRAME_BOUND_EPSILON: f64 = 1e-6;
+
+/// Drag a clip's left edge to `start_frames` while its end stays at
+/// `end_frames`. The edge snaps to the nearest target within
+/// `threshold_frames`, lands on a whole frame, and never goes before
+/// `min_start_frames` (the first source frame) or leaves the clip shorter
+/// than `min_duration_frames`.
+pub fn drag_left_edge(
+    start_frames: f64,
+    end_frames: f64,
+    min_start_frames: f64,
+    min_duration_frames: f64,
+    targets: &[SnapTarget],
+    threshold_frames: f64,
+) -> EdgeDrag {
+    let mut start = start_frames;
+    let mut snapped_frame = None;
+    if let Some(hit) = best_snap_delta_frames(&[start], targets, threshold_frames) {
+        start += hit.delta_frames;
+        snapped_frame = Some(hit.target.frame);
+    }
+    let earliest = (min_start_frames - FRAME_BOUND_EPSILON).ceil().max(0.0);
+    let start = start
+        .round()
+        .max(earliest)
+        .min(end_frames - min_duration_frames)
+        .max(0.0);
+    EdgeDrag {
+        start_frames: start,
+        duration_frames: end_frames - start,
+        snapped_frame: snapped_frame.filter(|frame| (start - frame).abs() <= 0.5),
+    }
+}
+
+/// Drag a clip's right edge to `end_frames` while its start stays at
+/// `start_frames`. Snaps like [`drag_left_edge`], and keeps the duration
+/// between `min_duration_frames` and `max_duration_frames` (what's left of
+/// the source after the trim).
+pub fn drag_right_edge(
+    start_frames: f64,
+    end_frames: f64,
+    max_duration_frames: Option<f64>,
+    min_duration_frames: f64,
+    targets: &[SnapTarget],
+    threshold_frames: f64,
+) -> EdgeDrag {
+    let mut end = end_frames;
+    let mut snapped_frame = None;
+    if let Some(hit) = best_snap_delta_frames(&[end], targets, threshold_frames) {
+        end += hit.delta_frames;
+        snapped_frame = Some(hit.target.frame);
+    }
+    let mut duration = (end.round() - start_frames).max(min_duration_frames);
+    if let Some(max_duration_frames) = max_duration_frames {
+        duration = duration.min((max_duration_frames + FRAME_BOUND_EPSILON).floor());
+    }
+    EdgeDrag {
+        start_frames,
+        duration_frames: duration,
+        snapped_frame: snapped_frame.filter(|frame| (start_frames + duration - frame).abs() <= 0.5),
+    }
+}
+
 /// Convert seconds to frame units using the given fps.
 pub fn frames_from_seconds(time_seconds: f64, fps: f64) -> f64 {
     time_seconds * fps.max(1.0)
@@ -93,6 +183,79 @@ pub fn snap_time_to_frame(time_seconds: f64, fps: f64) -> f64 {
     (time_seconds * fps).round() / fps
 }
 
+/// Move `time_seconds` by whole frames, clamped to `[0, duration_seconds]`.
+///
+/// Works on integer frame indices so repeated steps at fractional rates
+/// (e.g. 29.97) land on exact frame boundaries instead of drifting.
+pub fn step_time_by_frames(time_seconds: f64, frames: i64, fps: f64, duration_seconds: f64) -> f64 {
+    let fps = fps.max(1.0);
+    let duration_seconds = duration_seconds.max(0.0);
+    // Small epsilon so a duration sitting on a frame boundary keeps that frame.
+    let last_frame = (duration_seconds * fps + 1e-6).floor() as i64;
+    let current_frame = (time_seconds * fps).round() as i64;
+    let target_frame = current_frame.saturating_add(frames).clamp(0, last_frame.max(0));
+    (target_frame as f64 / fps).min(duration_seconds)
+}
+
+/// Shift a group of start times by whole frames, keeping their relative offsets.
+///
+/// Each start is quantized to its nearest frame first. A leftward nudge that
+/// would push the earliest start below zero is shortened so it lands on zero.
+pub fn nudge_start_times(starts: &[f64], frames: i64, fps: f64) -> Vec<f64> {
+    let start_frames: Vec<i64> = starts
+        .iter()
+        .map(|start| frames_from_seconds(*start, fps).round() as i64)
+        .collect();
+    let Some(earliest) = start_frames.iter().copied().min() else {
+        return Vec::new();
+    };
+    let delta = frames.max(-earliest.max(0));
+    start_frames
+        .iter()
+        .map(|frame| seconds_from_frames((frame + delta) as f64, fps).max(0.0))
+        .collect()
+}
+
+/// Markers closer than this to the playhead count as sitting on it.
+const MARKER_JUMP_EPSILON: f64 = 1e-6;
+
+/// Time of the first marker after `current_seconds`. A marker exactly at the
+/// playhead is skipped so repeated jumps keep moving forward.
+pub fn next_marker_time(markers: &[Marker], current_seconds: f64) -> Option<f64> {
+    markers
+        .iter()
+        .map(|marker| marker.time)
+        .filter(|time| *time > current_seconds + MARKER_JUMP_EPSILON)
+        .min_by(|a, b| a.total_cmp(b))
+}
+
+/// Time of the last marker before `current_seconds`, skipping one exactly at
+/// the playhead.
+pub fn prev_marker_time(markers: &[Marker], current_seconds: f64) -> Option<f64> {
+    markers
+        .iter()
+        .map(|marker| marker.time)
+        .filter(|time| *time < current_seconds - MARKER_JUMP_EPSILON)
+        .max_by(|a, b| a.total_cmp(b))
+}
+
+/// Fade length after dragging a fade handle by `delta_px`, snapped to whole
+/// frames. Fade-in handles grow to the right, fade-out handles to the left.
+pub fn fade_seconds_from_drag(
+    start_fade_seconds: f64,
+    delta_px: f64,
+    zoom: f64,
+    fps: f64,
+    fade_out: bool,
+) -> f64 {
+    if zoom <= 0.0 {
+        return start_fade_seconds.max(0.0);
+    }
+    let delta_px = if fade_out { -delta_px } else { delta_px };
+    let delta_seconds = delta_px / zoom;
+    snap_time_to_frame((start_fade_seconds + delta_seconds).max(0.0), fps)
+}
+
 /// Find the best snap delta between sources and targets within a threshold.
 pub fn best_snap_delta_frames(
     sources_frames: &[f64],
@@ -131,3 +294,155 @@ pub fn best_snap_delta_frames(
 
     best_match
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step_clamps_to_range() {
+        assert_eq!(step_time_by_frames(0.0, -1, 30.0, 10.0), 0.0);
+        assert_eq!(step_time_by_frames(10.0, 1, 30.0, 10.0), 10.0);
+        assert_eq!(step_time_by_frames(9.99, 10, 30.0, 10.0), 10.0);
+        assert!((step_time_by_frames(1.0, 1, 30.0, 10.0) - 31.0 / 30.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_nudge_clamps_at_zero() {
+        assert_eq!(nudge_start_times(&[0.0], -1, 30.0), vec![0.0]);
+        assert_eq!(nudge_start_times(&[1.0 / 30.0], -5, 30.0), vec![0.0]);
+        assert_eq!(nudge_start_times(&[0.0], 1, 30.0), vec![1.0 / 30.0]);
+    }
+
+    #[test]
+    fn test_nudge_moves_group_together() {
+        let starts = [2.0 / 30.0, 1.0, 3.5];
+        let nudged = nudge_start_times(&starts, -3, 30.0);
+        // The earliest clip stops at zero and the rest keep their spacing
+        assert_eq!(nudged, vec![0.0, 28.0 / 30.0, 103.0 / 30.0]);
+
+        let nudged = nudge_start_times(&starts, 1, 30.0);
+        assert_eq!(nudged, vec![3.0 / 30.0, 31.0 / 30.0, 106.0 / 30.0]);
+    }
+
+    #[test]
+    fn test_fade_drag_direction_and_snap() {
+        // 100 px/s: dragging the fade-in handle 50 px right adds half a second
+        assert_eq!(fade_seconds_from_drag(0.0, 50.0, 100.0, 30.0, false), 0.5);
+        // The fade-out handle grows when dragged left
+        assert_eq!(fade_seconds_from_drag(1.0, -50.0, 100.0, 30.0, true), 1.5);
+        assert_eq!(fade_seconds_from_drag(1.0, 50.0, 100.0, 30.0, true), 0.5);
+        // Never negative, and lands on a frame boundary
+        assert_eq!(fade_seconds_from_drag(0.2, -500.0, 100.0, 30.0, false), 0.0);
+        assert_eq!(fade_seconds_from_drag(0.0, 1.0, 100.0, 30.0, false), 0.0);
+        assert_eq!(fade_seconds_from_drag(0.0, 2.0, 100.0, 30.0, false), 1.0 / 30.0);
+    }
+
+    #[test]
+    fn test_step_does_not_drift_at_fractional_fps() {
+        let fps = 30000.0 / 1001.0;
+        let mut time = 0.0;
+        for _ in 0..1000 {
+            time = step_time_by_frames(time, 1, fps, 3600.0);
+        }
+        assert_eq!(time, 1000.0 / fps);
+        for _ in 0..100 {
+            time = step_time_by_frames(time, -10, fps, 3600.0);
+        }
+        assert_eq!(time, 0.0);
+    }
+
+    fn edge(frame: f64) -> SnapTarget {
+        SnapTarget::clip_edge(frame, Uuid::new_v4())
+    }
+
+    #[test]
+    fn test_left_edge_drag_snaps_and_stops_at_source_start() {
+        // Clip covers frames 30..90; the source starts 20 frames before the clip
+        let targets = [edge(7.0), edge(47.0)];
+        let dragged = drag_left_edge(45.4, 90.0, 10.0, 3.0, &targets, 3.0);
+        assert_eq!(dragged.start_frames, 47.0);
+        assert_eq!(dragged.duration_frames, 43.0);
+        assert_eq!(dragged.snapped_frame, Some(47.0));
+
+        // Snapping disabled: lands on the nearest whole frame
+        let dragged = drag_left_edge(45.4, 90.0, 10.0, 3.0, &targets, 0.0);
+        assert_eq!((dragged.start_frames, dragged.snapped_frame), (45.0, None));
+
+        // The snap target sits before the first source frame, so the edge stops there
+        let dragged = drag_left_edge(8.0, 90.0, 10.0, 3.0, &targets, 3.0);
+        assert_eq!(dragged.start_frames, 10.0);
+        assert_eq!(dragged.duration_frames, 80.0);
+        assert_eq!(dragged.snapped_frame, None);
+
+        // A bound a hair over a frame boundary still allows that frame
+        assert_eq!(drag_left_edge(0.0, 90.0, 10.0 + 1e-9, 3.0, &[], 0.0).start_frames, 10.0);
+        // Dragging past the end keeps the minimum length
+        assert_eq!(drag_left_edge(120.0, 90.0, 10.0, 3.0, &[], 0.0).start_frames, 87.0);
+    }
+
+    #[test]
+    fn test_right_edge_drag_snaps_within_source() {
+        let targets = [edge(62.0), edge(140.0)];
+        let dragged = drag_right_edge(30.0, 60.6, Some(100.0), 3.0, &targets, 3.0);
+        assert_eq!(dragged.duration_frames, 32.0);
+        assert_eq!(dragged.snapped_frame, Some(62.0));
+
+        // Past the end of the source the edge stops short of the target
+        let dragged = drag_right_edge(30.0, 139.0, Some(100.0), 3.0, &targets, 3.0);
+        assert_eq!(dragged.duration_frames, 100.0);
+        assert_eq!(dragged.snapped_frame, None);
+
+        // Stills have no source limit
+        let dragged = drag_right_edge(30.0, 139.0, None, 3.0, &targets, 3.0);
+        assert_eq!(dragged.duration_frames, 110.0);
+        assert_eq!(drag_right_edge(30.0, 10.0, None, 3.0, &[], 0.0).duration_frames, 3.0);
+    }
+
+    #[test]
+    fn test_placeholders_are_snap_targets() {
+        let track_id = Uuid::new_v4();
+        let media = Clip::new(Uuid::new_v4(), track_id, 0.0, 1.0);
+        let placeholder = Clip::placeholder(track_id, 2.0, 1.5, Some("Intro".to_string()));
+        let clips = vec![media.clone(), placeholder.clone()];
+
+        let targets = clip_edge_targets(&clips, 30.0, |_| true);
+        let frames: Vec<(f64, Option<Uuid>)> = targets.iter().map(|target| (target.frame, target.clip_id)).collect();
+        assert_eq!(
+            frames,
+            vec![
+                (0.0, Some(media.id)),
+                (30.0, Some(media.id)),
+                (60.0, Some(placeholder.id)),
+                (105.0, Some(placeholder.id)),
+            ]
+        );
+        // A clip dragged near the placeholder's end snaps to it
+        let hit = best_snap_delta_frames(&[103.0], &targets, 3.0).unwrap();
+        assert_eq!(hit.target.clip_id, Some(placeholder.id));
+
+        // Edges out of view are left out
+        assert_eq!(clip_edge_targets(&clips, 30.0, |time| time < 2.5).len(), 3);
+    }
+
+    #[test]
+    fn test_marker_jumps() {
+        assert_eq!(next_marker_time(&[], 1.0), None);
+        assert_eq!(prev_marker_time(&[], 1.0), None);
+
+        let markers = vec![Marker::new(4.0), Marker::new(1.0), Marker::new(2.5)];
+        assert_eq!(next_marker_time(&markers, 0.0), Some(1.0));
+        assert_eq!(next_marker_time(&markers, 1.5), Some(2.5));
+        assert_eq!(prev_marker_time(&markers, 3.0), Some(2.5));
+        assert_eq!(prev_marker_time(&markers, 10.0), Some(4.0));
+
+        // Nothing past the last marker (or before the first)
+        assert_eq!(next_marker_time(&markers, 4.5), None);
+        assert_eq!(prev_marker_time(&markers, 0.5), None);
+
+        // A marker on the playhead is skipped in both directions
+        assert_eq!(next_marker_time(&markers, 2.5), Some(4.0));
+        assert_eq!(prev_marker_time(&markers, 2.5), Some(1.0));
+        assert_eq!(next_marker_time(&markers, 4.0), None);
+    }
+}
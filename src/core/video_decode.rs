@@ -207,6 +207,41 @@ impl VideoDecodeWorker {
     }
 }
 
+/// Result of checking whether a video file can be decoded in-process.
+#[derive(Clone, Debug, PartialEq)]
+pub enum VideoCodecSupport {
+    Supported,
+    /// The container opened but no usable decoder exists for its video codec.
+    Unsupported { codec: String },
+    /// The file could not be opened or has no video stream.
+    Unreadable(String),
+}
+
+/// Open the file's best video stream and try to create a decoder for it.
+pub fn probe_video_codec_support(path: &Path) -> VideoCodecSupport {
+    let _ = ffmpeg::init();
+    let input = match ffmpeg::format::input(path) {
+        Ok(input) => input,
+        Err(err) => return VideoCodecSupport::Unreadable(err.to_string()),
+    };
+    let Some(stream) = input.streams().best(ffmpeg::media::Type::Video) else {
+        return VideoCodecSupport::Unreadable("No video stream found".to_string());
+    };
+    let context = match ffmpeg::codec::context::Context::from_parameters(stream.parameters()) {
+        Ok(context) => context,
+        Err(err) => return VideoCodecSupport::Unreadable(err.to_string()),
+    };
+    let codec_id = context.id();
+    let codec = codec_id.name().to_string();
+    if ffmpeg::codec::decoder::find(codec_id).is_none() {
+        return VideoCodecSupport::Unsupported { codec };
+    }
+    match context.decoder().video() {
+        Ok(_) => VideoCodecSupport::Supported,
+        Err(_) => VideoCodecSupport::Unsupported { codec },
+    }
+}
+
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 struct DecoderKey {
     path: PathBuf,
@@ -0,0 +1,298 @@
+//! User keybinding overrides loaded from `keymap.json`.
+//!
+//! The file maps action names to one or more key specs:
+//!
+//! ```json
+//! {
+//!     "PlayPause": "K",
+//!     "TimelineZoomIn": ["Ctrl+=", "+"]
+//! }
+//! ```
+//!
+//! A spec is `+`-separated modifiers (`Ctrl`, `Cmd`, `Shift`, `Alt`) followed by
+//! a key: a single character or a key name such as `Space`, `Delete`, or
+//! `ArrowLeft`. An action listed here loses its default bindings. A key listed
+//! under more than one action is ignored for all of them.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use dioxus::prelude::Key;
+use serde::Deserialize;
+
+use super::HotkeyAction;
+
+const KEYMAP_FILE_NAME: &str = "keymap.json";
+
+/// A single key plus the modifiers that must be held with it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    /// Lowercased character or key name (`"k"`, `"space"`, `"arrowleft"`).
+    key: String,
+    /// Ctrl on Windows/Linux, Cmd on macOS.
+    ctrl: bool,
+    shift: bool,
+    alt: bool,
+}
+
+impl KeyBinding {
+    /// Parse a spec such as `"Ctrl+Shift+Z"` or `"Space"`.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let spec = spec.trim();
+        if spec.is_empty() {
+            return Err("empty key spec".to_string());
+        }
+        // A trailing "+" is the plus key itself ("+" or "Ctrl++").
+        let (modifiers, key) = if spec == "+" {
+            ("", "+")
+        } else if let Some(prefix) = spec.strip_suffix("++") {
+            (prefix, "+")
+        } else {
+            match spec.rsplit_once('+') {
+                Some((prefix, key)) => (prefix, key),
+                None => ("", spec),
+            }
+        };
+
+        let mut binding = Self {
+            key: normalize_key_name(key),
+            ctrl: false,
+            shift: false,
+            alt: false,
+        };
+        if binding.key.is_empty() {
+            return Err(format!("missing key in \"{}\"", spec));
+        }
+        for modifier in modifiers.split('+').filter(|part| !part.trim().is_empty()) {
+            match modifier.trim().to_ascii_lowercase().as_str() {
+                "ctrl" | "control" | "cmd" | "meta" | "mod" => binding.ctrl = true,
+                "shift" => binding.shift = true,
+                "alt" | "option" => binding.alt = true,
+                other => return Err(format!("unknown modifier \"{}\" in \"{}\"", other, spec)),
+            }
+        }
+        Ok(binding)
+    }
+
+    fn matches(&self, key: &Key, shift: bool, ctrl: bool, alt: bool, meta: bool) -> bool {
+        if self.key != key_event_name(key) || self.ctrl != (ctrl || meta) || self.alt != alt {
+            return false;
+        }
+        // Symbols like "+" already imply whatever shift the layout needs.
+        let shift_sensitive = self.shift
+            || self.key.chars().count() > 1
+            || self.key.chars().all(|c| c.is_alphanumeric());
+        !shift_sensitive || self.shift == shift
+    }
+
+    pub fn has_command_modifier(&self) -> bool {
+        self.ctrl
+    }
+}
+
+/// Keybinding overrides keyed by action.
+#[derive(Debug, Clone, Default)]
+pub struct KeyMap {
+    bindings: HashMap<HotkeyAction, Vec<KeyBinding>>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum KeySpecs {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyMap {
+    /// Location of the user keymap in the app config folder.
+    pub fn user_path() -> PathBuf {
+        crate::core::paths::app_config_root().join(KEYMAP_FILE_NAME)
+    }
+
+    /// Load the user keymap, or an empty one when the file is missing or unreadable.
+    pub fn load_user() -> Self {
+        Self::load(&Self::user_path())
+    }
+
+    pub fn load(path: &Path) -> Self {
+        match std::fs::read_to_string(path) {
+            Ok(json) => Self::from_json(&json),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Self::default(),
+            Err(err) => {
+                eprintln!("[HOTKEYS WARN] Failed to read {}: {}", path.display(), err);
+                Self::default()
+            }
+        }
+    }
+
+    /// Parse keymap JSON. Bad entries are logged and skipped so the affected
+    /// actions keep their default bindings, as are keys bound to several
+    /// actions, since a key press could only resolve to one of them.
+    pub fn from_json(json: &str) -> Self {
+        let entries: HashMap<String, serde_json::Value> = match serde_json::from_str(json) {
+            Ok(entries) => entries,
+            Err(err) => {
+                eprintln!("[HOTKEYS WARN] Ignoring malformed keymap: {}", err);
+                return Self::default();
+            }
+        };
+
+        let mut parsed_entries = Vec::new();
+        for (name, value) in entries {
+            let Ok(action) =
+                serde_json::from_value::<HotkeyAction>(serde_json::Value::String(name.clone()))
+            else {
+                eprintln!("[HOTKEYS WARN] Unknown action \"{}\" in keymap", name);
+                continue;
+            };
+            let specs = match serde_json::from_value::<KeySpecs>(value) {
+                Ok(KeySpecs::One(spec)) => vec![spec],
+                Ok(KeySpecs::Many(specs)) => specs,
+                Err(_) => {
+                    eprintln!("[HOTKEYS WARN] Keymap entry \"{}\" must be a string or list", name);
+                    continue;
+                }
+            };
+            let parsed: Result<Vec<_>, _> = specs
+                .iter()
+                .map(|spec| KeyBinding::parse(spec).map(|binding| (spec.trim().to_string(), binding)))
+                .collect();
+            match parsed {
+                Ok(action_bindings) => parsed_entries.push((action, name, action_bindings)),
+                Err(err) => {
+                    eprintln!("[HOTKEYS WARN] Keeping default for \"{}\": {}", name, err);
+                }
+            }
+        }
+
+        let mut owners: HashMap<&KeyBinding, HashSet<HotkeyAction>> = HashMap::new();
+        for (action, _, action_bindings) in parsed_entries.iter() {
+            for (_, binding) in action_bindings.iter() {
+                owners.entry(binding).or_default().insert(*action);
+            }
+        }
+        let shared: HashSet<KeyBinding> = owners
+            .into_iter()
+            .filter(|(_, actions)| actions.len() > 1)
+            .map(|(binding, _)| binding.clone())
+            .collect();
+
+        let mut bindings = HashMap::new();
+        for (action, name, action_bindings) in parsed_entries {
+            let mut kept = Vec::new();
+            for (spec, binding) in action_bindings {
+                if shared.contains(&binding) {
+                    eprintln!(
+                        "[HOTKEYS WARN] Ignoring \"{}\" for \"{}\": bound to more than one action",
+                        spec, name
+                    );
+                } else {
+                    kept.push(binding);
+                }
+            }
+            if kept.is_empty() {
+                eprintln!("[HOTKEYS WARN] Keeping default for \"{}\": no usable keys", name);
+                continue;
+            }
+            bindings.insert(action, kept);
+        }
+        Self { bindings }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bindings.is_empty()
+    }
+
+    /// Whether the user replaced the default bindings for `action`.
+    pub fn overrides(&self, action: HotkeyAction) -> bool {
+        self.bindings.contains_key(&action)
+    }
+
+    /// The overridden action bound to this key event, with the binding that matched.
+    pub fn lookup(
+        &self,
+        key: &Key,
+        shift: bool,
+        ctrl: bool,
+        alt: bool,
+        meta: bool,
+    ) -> Option<(HotkeyAction, &KeyBinding)> {
+        self.bindings.iter().find_map(|(action, bindings)| {
+            bindings
+                .iter()
+                .find(|binding| binding.matches(key, shift, ctrl, alt, meta))
+                .map(|binding| (*action, binding))
+        })
+    }
+}
+
+fn normalize_key_name(name: &str) -> String {
+    let lower = name.trim().to_lowercase();
+    match lower.as_str() {
+        "space" | "spacebar" => "space".to_string(),
+        "del" => "delete".to_string(),
+        "esc" => "escape".to_string(),
+        "left" => "arrowleft".to_string(),
+        "right" => "arrowright".to_string(),
+        "up" => "arrowup".to_string(),
+        "down" => "arrowdown".to_string(),
+        "plus" => "+".to_string(),
+        "minus" => "-".to_string(),
+        _ => lower,
+    }
+}
+
+fn key_event_name(key: &Key) -> String {
+    match key {
+        Key::Character(c) if c == " " => "space".to_string(),
+        Key::Character(c) => c.to_lowercase(),
+        other => other.to_string().to_lowercase(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_specs() {
+        let binding = KeyBinding::parse("Ctrl+Shift+Z").unwrap();
+        assert!(binding.matches(&Key::Character("Z".to_string()), true, true, false, false));
+        assert!(!binding.matches(&Key::Character("z".to_string()), false, true, false, false));
+
+        let binding = KeyBinding::parse("Ctrl++").unwrap();
+        assert!(binding.matches(&Key::Character("+".to_string()), true, true, false, false));
+
+        assert!(KeyBinding::parse("Hyper+K").is_err());
+        assert!(KeyBinding::parse("Ctrl+").is_err());
+    }
+
+    #[test]
+    fn test_keys_bound_to_several_actions_are_ignored() {
+        let keymap = KeyMap::from_json(
+            r#"{ "PlayPause": ["K", "Ctrl+P"], "TimelineZoomIn": "ctrl+p", "TimelineZoomOut": "P" }"#,
+        );
+        let ctrl_p = Key::Character("p".to_string());
+        assert!(keymap.lookup(&ctrl_p, false, true, false, false).is_none());
+        // PlayPause keeps its other key; ZoomIn has none left and keeps its defaults
+        assert!(keymap.overrides(HotkeyAction::PlayPause));
+        assert!(!keymap.overrides(HotkeyAction::TimelineZoomIn));
+        let k = Key::Character("k".to_string());
+        assert_eq!(
+            keymap.lookup(&k, false, false, false, false).map(|(action, _)| action),
+            Some(HotkeyAction::PlayPause)
+        );
+        let p = Key::Character("p".to_string());
+        assert_eq!(
+            keymap.lookup(&p, false, false, false, false).map(|(action, _)| action),
+            Some(HotkeyAction::TimelineZoomOut)
+        );
+    }
+
+    #[test]
+    fn test_malformed_entry_keeps_default() {
+        let keymap = KeyMap::from_json(r#"{ "TimelineZoomIn": 5, "TimelineZoomOut": "Hyper+-" }"#);
+        assert!(keymap.is_empty());
+        assert!(KeyMap::from_json("not json").is_empty());
+    }
+}
@@ -7,6 +7,7 @@
 //! - **HotkeyAction**: Enum of all possible actions that can be triggered by hotkeys
 //! - **HotkeyContext**: Determines which hotkeys are active based on app state
 //! - **handle_hotkey()**: Main dispatch function that maps key events to actions
+//! - **KeyMap**: User overrides from `keymap.json`, consulted before the defaults
 //!
 //! # Adding New Hotkeys
 //!
@@ -14,13 +15,19 @@
 //! 2. Add the key binding in `key_to_action()`
 //! 3. Handle the action in the App component's hotkey handler
 
+mod keymap;
+
+pub use keymap::KeyMap;
+
 use dioxus::prelude::Key;
+use serde::Deserialize;
 
 /// All possible actions that can be triggered by hotkeys.
 /// 
 /// Each variant represents a semantic action, not a key binding.
 /// This decouples "what key was pressed" from "what should happen".
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Variant names double as the action names in `keymap.json`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
 pub enum HotkeyAction {
     // ═══════════════════════════════════════════════════════════════
     // Timeline Zoom
@@ -29,26 +36,104 @@ pub enum HotkeyAction {
     TimelineZoomIn,
     /// Zoom out on the timeline (decrease pixels per second)
     TimelineZoomOut,
+    /// Zoom so the whole project fits the timeline viewport
+    TimelineZoomFit,
+    /// Zoom to frame the selected clips
+    TimelineZoomSelection,
+    /// Turn playhead-follow scrolling on or off.
+    ToggleFollowPlayhead,
+
+    // ═══════════════════════════════════════════════════════════════
+    // Project
+    // ═══════════════════════════════════════════════════════════════
     /// Save the current project.
     SaveProject,
-    /// Toggle playback.
-    PlayPause,
+    /// Save a copy of the project into a new folder and switch to it.
+    SaveProjectAs,
 
     // ═══════════════════════════════════════════════════════════════
-    // Playback (future)
+    // Generation
+    // ═══════════════════════════════════════════════════════════════
+    /// Jump to the prompt (or Generate button) of the selected generative clip.
+    FocusGenerativeControls,
+    /// Queue a generation for the selected generative clip.
+    Generate,
+
     // ═══════════════════════════════════════════════════════════════
-    // PlayPause,
+    // Selection
+    // ═══════════════════════════════════════════════════════════════
+    /// Select every clip on the timeline.
+    SelectAll,
+    /// Clear the clip, track, and marker selection.
+    DeselectAll,
+
+    // ═══════════════════════════════════════════════════════════════
+    // Editing
+    // ═══════════════════════════════════════════════════════════════
+    /// Remove the selected clips (and markers) from the timeline.
+    DeleteSelection,
+    /// Revert the last timeline edit.
+    Undo,
+    /// Re-apply the last reverted edit.
+    Redo,
+    /// Copy the selected clips to the clip clipboard.
+    CopySelection,
+    /// Paste clipboard clips at the playhead.
+    Paste,
+    /// Duplicate the selected clips right after themselves.
+    DuplicateSelection,
+    /// Group the selected clips so they select and move as one.
+    GroupSelection,
+    /// Dissolve the groups of the selected clips.
+    UngroupSelection,
+    /// Move the selected clips one frame earlier.
+    NudgeLeft,
+    /// Move the selected clips one frame later.
+    NudgeRight,
+    /// Drop a marker at the playhead.
+    AddMarker,
+
+    // ═══════════════════════════════════════════════════════════════
+    // Playback / Navigation
+    // ═══════════════════════════════════════════════════════════════
+    /// Toggle playback.
+    PlayPause,
+    /// Move the playhead one frame later.
+    StepForward,
+    /// Move the playhead one frame earlier.
+    StepBackward,
+    /// Move the playhead ten frames later.
+    StepForwardTenFrames,
+    /// Move the playhead ten frames earlier.
+    StepBackwardTenFrames,
+    /// Move the playhead to the next marker.
+    NextMarker,
+    /// Move the playhead to the previous marker.
+    PrevMarker,
     // SeekStart,
     // SeekEnd,
-    // StepForward,
-    // StepBackward,
 
     // ═══════════════════════════════════════════════════════════════
-    // Selection (future)
+    // Tracks
+    // ═══════════════════════════════════════════════════════════════
+    /// Mute or unmute the selected track.
+    ToggleTrackMute,
+    /// Solo or unsolo the selected track.
+    ToggleTrackSolo,
+
+    // ═══════════════════════════════════════════════════════════════
+    // Assets Panel
     // ═══════════════════════════════════════════════════════════════
-    // DeleteSelection,
-    // SelectAll,
-    // DeselectAll,
+    /// Highlight the asset above the current one.
+    AssetPrevious,
+    /// Highlight the asset below the current one.
+    AssetNext,
+    /// Highlight the first asset in the list.
+    AssetFirst,
+    /// Highlight the last asset in the list.
+    AssetLast,
+    /// Add the highlighted asset to the timeline at the playhead.
+    AddAssetToTimeline,
 }
 
 /// Context information that affects which hotkeys are active.
@@ -61,13 +146,20 @@ pub enum HotkeyAction {
 #[allow(dead_code)]
 pub struct HotkeyContext {
     /// Whether the timeline panel is visible (not collapsed)
-    #[allow(dead_code)]
     pub timeline_visible: bool,
     /// Whether any clips are selected
-    #[allow(dead_code)]
     pub has_selection: bool,
     /// Whether an input field has focus (should suppress most hotkeys)
     pub input_focused: bool,
+    /// Whether the primary selected clip is a generative clip
+    pub generative_selected: bool,
+    /// Whether a modal, dialog, or menu is covering the editor
+    pub modal_open: bool,
+    /// Whether a timeline track is selected
+    pub track_selected: bool,
+    /// Whether the assets panel was the last thing clicked, so arrows, Home,
+    /// End and Enter navigate its list
+    pub assets_focused: bool,
 }
 
 /// Result of processing a key event.
@@ -81,6 +173,71 @@ pub enum HotkeyResult {
     Suppressed,
 }
 
+/// Like [`handle_hotkey`], but user bindings from `keymap` win, and actions the
+/// keymap rebinds no longer respond to their default keys.
+pub fn handle_hotkey_with_keymap(
+    key: &Key,
+    shift: bool,
+    ctrl: bool,
+    alt: bool,
+    meta: bool,
+    context: &HotkeyContext,
+    keymap: &KeyMap,
+) -> HotkeyResult {
+    if keymap.is_empty() {
+        return handle_hotkey(key, shift, ctrl, alt, meta, context);
+    }
+
+    if let Some((action, binding)) = keymap.lookup(key, shift, ctrl, alt, meta) {
+        if context.input_focused
+            && !(binding.has_command_modifier() && runs_in_inputs(action))
+        {
+            return HotkeyResult::Suppressed;
+        }
+        if action_available(action, context) {
+            return HotkeyResult::Action(action);
+        }
+    }
+
+    match handle_hotkey(key, shift, ctrl, alt, meta, context) {
+        HotkeyResult::Action(action) if keymap.overrides(action) => HotkeyResult::NoMatch,
+        result => result,
+    }
+}
+
+/// Actions whose modifier shortcut should fire even while typing in a field.
+fn runs_in_inputs(action: HotkeyAction) -> bool {
+    action == HotkeyAction::Generate
+}
+
+/// Context requirements for an action, independent of which key triggered it.
+fn action_available(action: HotkeyAction, context: &HotkeyContext) -> bool {
+    match action {
+        HotkeyAction::Generate | HotkeyAction::FocusGenerativeControls => {
+            context.generative_selected && !context.modal_open
+        }
+        HotkeyAction::DeleteSelection
+        | HotkeyAction::CopySelection
+        | HotkeyAction::DuplicateSelection
+        | HotkeyAction::GroupSelection
+        | HotkeyAction::UngroupSelection
+        | HotkeyAction::NudgeLeft
+        | HotkeyAction::NudgeRight => context.has_selection,
+        HotkeyAction::ToggleTrackMute | HotkeyAction::ToggleTrackSolo => context.track_selected,
+        HotkeyAction::SelectAll
+        | HotkeyAction::ToggleFollowPlayhead
+        | HotkeyAction::TimelineZoomFit => context.timeline_visible,
+        HotkeyAction::TimelineZoomSelection => context.timeline_visible && context.has_selection,
+        HotkeyAction::DeselectAll | HotkeyAction::AddMarker => !context.modal_open,
+        HotkeyAction::AssetPrevious
+        | HotkeyAction::AssetNext
+        | HotkeyAction::AssetFirst
+        | HotkeyAction::AssetLast
+        | HotkeyAction::AddAssetToTimeline => context.assets_focused && !context.modal_open,
+        _ => true,
+    }
+}
+
 /// Maps a key event to an action, considering the current context.
 ///
 /// # Arguments
@@ -94,12 +251,22 @@ pub enum HotkeyResult {
 /// * `HotkeyResult::Suppressed` if input is focused
 pub fn handle_hotkey(
     key: &Key,
-    _shift: bool,
+    shift: bool,
     ctrl: bool,
-    _alt: bool,
+    alt: bool,
     meta: bool,
     context: &HotkeyContext,
 ) -> HotkeyResult {
+    // Ctrl+Enter is the one shortcut that runs from an input field, so the
+    // prompt can be submitted without leaving it (but not from a modal's fields)
+    if (ctrl || meta)
+        && *key == Key::Enter
+        && context.generative_selected
+        && !context.modal_open
+    {
+        return HotkeyResult::Action(HotkeyAction::Generate);
+    }
+
     // Suppress hotkeys when typing in an input field
     if context.input_focused {
         return HotkeyResult::Suppressed;
@@ -112,27 +279,149 @@ pub fn handle_hotkey(
     // Timeline zoom: Numpad +/- (produces "+" and "-" characters)
     // Also handles regular +/- for convenience
     match key {
+        Key::Character(c) if (ctrl || meta) && shift && (c == "s" || c == "S") => {
+            return HotkeyResult::Action(HotkeyAction::SaveProjectAs);
+        }
         Key::Character(c) if (ctrl || meta) && (c == "s" || c == "S") => {
             return HotkeyResult::Action(HotkeyAction::SaveProject);
         }
         Key::Character(c) if c == "+" => return HotkeyResult::Action(HotkeyAction::TimelineZoomIn),
         Key::Character(c) if c == "-" => return HotkeyResult::Action(HotkeyAction::TimelineZoomOut),
         Key::Character(c) if c == " " => return HotkeyResult::Action(HotkeyAction::PlayPause),
+        Key::Character(c)
+            if context.timeline_visible && !(ctrl || meta) && (c == "f" || c == "F") =>
+        {
+            return HotkeyResult::Action(HotkeyAction::ToggleFollowPlayhead);
+        }
+        // Backslash fits the whole project; Z frames the selected clips
+        Key::Character(c) if context.timeline_visible && !(ctrl || meta) && c == "\\" => {
+            return HotkeyResult::Action(HotkeyAction::TimelineZoomFit);
+        }
+        Key::Character(c)
+            if context.timeline_visible
+                && context.has_selection
+                && !(ctrl || meta || shift)
+                && (c == "z" || c == "Z") =>
+        {
+            return HotkeyResult::Action(HotkeyAction::TimelineZoomSelection);
+        }
+        Key::Escape if !context.modal_open => return HotkeyResult::Action(HotkeyAction::DeselectAll),
         _ => {}
     }
 
+    // Assets panel list navigation; Up/Down, Home, End and Enter aren't
+    // bound anywhere else, so this only has to stay out of modals
+    if context.assets_focused && !context.modal_open && !(ctrl || meta || alt || shift) {
+        match key {
+            Key::ArrowUp => return HotkeyResult::Action(HotkeyAction::AssetPrevious),
+            Key::ArrowDown => return HotkeyResult::Action(HotkeyAction::AssetNext),
+            Key::Home => return HotkeyResult::Action(HotkeyAction::AssetFirst),
+            Key::End => return HotkeyResult::Action(HotkeyAction::AssetLast),
+            Key::Enter => return HotkeyResult::Action(HotkeyAction::AddAssetToTimeline),
+            _ => {}
+        }
+    }
+
+    // Shift+M drops a marker at the playhead (plain M is track mute)
+    if shift && !(ctrl || meta) && !context.modal_open {
+        if let Key::Character(c) = key {
+            if c == "m" || c == "M" {
+                return HotkeyResult::Action(HotkeyAction::AddMarker);
+            }
+        }
+    }
+
+    // Track mute/solo: M and Shift+S on the selected track
+    if context.track_selected && !(ctrl || meta) {
+        match key {
+            Key::Character(c) if c == "m" || c == "M" => {
+                return HotkeyResult::Action(HotkeyAction::ToggleTrackMute);
+            }
+            Key::Character(c) if shift && (c == "s" || c == "S") => {
+                return HotkeyResult::Action(HotkeyAction::ToggleTrackSolo);
+            }
+            _ => {}
+        }
+    }
+
+    // Clip nudging: , and . (or Alt+arrows) move the selection one frame
+    if context.has_selection && !(ctrl || meta) {
+        match key {
+            Key::Character(c) if c == "," => return HotkeyResult::Action(HotkeyAction::NudgeLeft),
+            Key::Character(c) if c == "." => return HotkeyResult::Action(HotkeyAction::NudgeRight),
+            Key::ArrowLeft if alt => return HotkeyResult::Action(HotkeyAction::NudgeLeft),
+            Key::ArrowRight if alt => return HotkeyResult::Action(HotkeyAction::NudgeRight),
+            _ => {}
+        }
+    }
+
+    // Frame stepping: arrows move one frame, Shift+arrows move ten
+    if !(ctrl || meta) {
+        match key {
+            Key::ArrowRight if shift => return HotkeyResult::Action(HotkeyAction::StepForwardTenFrames),
+            Key::ArrowLeft if shift => return HotkeyResult::Action(HotkeyAction::StepBackwardTenFrames),
+            Key::ArrowRight => return HotkeyResult::Action(HotkeyAction::StepForward),
+            Key::ArrowLeft => return HotkeyResult::Action(HotkeyAction::StepBackward),
+            _ => {}
+        }
+    }
+
+    // Ctrl+A/Z/Y/C/V are handled after the input check so fields keep their
+    // native select-all, text undo, and clipboard. Ctrl+arrows jump between
+    // markers, which fields would use for word navigation.
+    if ctrl || meta {
+        match key {
+            Key::ArrowRight => return HotkeyResult::Action(HotkeyAction::NextMarker),
+            Key::ArrowLeft => return HotkeyResult::Action(HotkeyAction::PrevMarker),
+            _ => {}
+        }
+        if let Key::Character(c) = key {
+            match c.as_str() {
+                "a" | "A" if context.timeline_visible => {
+                    return HotkeyResult::Action(HotkeyAction::SelectAll);
+                }
+                "z" | "Z" if shift => return HotkeyResult::Action(HotkeyAction::Redo),
+                "z" | "Z" => return HotkeyResult::Action(HotkeyAction::Undo),
+                "y" | "Y" => return HotkeyResult::Action(HotkeyAction::Redo),
+                "c" | "C" if context.has_selection => {
+                    return HotkeyResult::Action(HotkeyAction::CopySelection);
+                }
+                "v" | "V" => return HotkeyResult::Action(HotkeyAction::Paste),
+                "d" | "D" if context.has_selection => {
+                    return HotkeyResult::Action(HotkeyAction::DuplicateSelection);
+                }
+                "g" | "G" if context.has_selection && shift => {
+                    return HotkeyResult::Action(HotkeyAction::UngroupSelection);
+                }
+                "g" | "G" if context.has_selection => {
+                    return HotkeyResult::Action(HotkeyAction::GroupSelection);
+                }
+                _ => {}
+            }
+        }
+    }
+
     // ═══════════════════════════════════════════════════════════════
     // Context-Specific Hotkeys
     // ═══════════════════════════════════════════════════════════════
     
-    // (Future: Add context-aware hotkeys here)
-    // Example:
-    // if context.has_selection {
-    //     match key {
-    //         Key::Delete => return HotkeyResult::Action(HotkeyAction::DeleteSelection),
-    //         _ => {}
-    //     }
-    // }
+    if context.generative_selected && !(ctrl || meta) {
+        match key {
+            Key::Character(c) if c == "g" || c == "G" => {
+                return HotkeyResult::Action(HotkeyAction::FocusGenerativeControls);
+            }
+            _ => {}
+        }
+    }
+
+    if context.has_selection && !(ctrl || meta) {
+        match key {
+            Key::Delete | Key::Backspace => {
+                return HotkeyResult::Action(HotkeyAction::DeleteSelection);
+            }
+            _ => {}
+        }
+    }
 
     HotkeyResult::NoMatch
 }
@@ -162,6 +451,26 @@ mod tests {
         assert!(matches!(result, HotkeyResult::Action(HotkeyAction::SaveProject)));
     }
 
+    #[test]
+    fn test_ctrl_shift_s_saves_project_as() {
+        let ctx = HotkeyContext::default();
+        let result = handle_hotkey(&Key::Character("S".to_string()), true, true, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::SaveProjectAs)));
+    }
+
+    #[test]
+    fn test_only_generate_runs_from_input() {
+        let ctx = HotkeyContext {
+            input_focused: true,
+            generative_selected: true,
+            ..HotkeyContext::default()
+        };
+        let result = handle_hotkey(&Key::Character("s".to_string()), false, true, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Suppressed));
+        let result = handle_hotkey(&Key::Enter, false, true, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::Generate)));
+    }
+
     #[test]
     fn test_space_toggles_playback() {
         let ctx = HotkeyContext::default();
@@ -169,6 +478,40 @@ mod tests {
         assert!(matches!(result, HotkeyResult::Action(HotkeyAction::PlayPause)));
     }
 
+    #[test]
+    fn test_g_focuses_generative_controls() {
+        let ctx = HotkeyContext {
+            generative_selected: true,
+            ..Default::default()
+        };
+        let result = handle_hotkey(&Key::Character("g".to_string()), false, false, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::FocusGenerativeControls)));
+
+        let result = handle_hotkey(
+            &Key::Character("g".to_string()),
+            false,
+            false,
+            false,
+            false,
+            &HotkeyContext::default(),
+        );
+        assert!(matches!(result, HotkeyResult::NoMatch));
+    }
+
+    #[test]
+    fn test_ctrl_enter_generates_from_input() {
+        let ctx = HotkeyContext {
+            input_focused: true,
+            generative_selected: true,
+            ..Default::default()
+        };
+        let result = handle_hotkey(&Key::Enter, false, true, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::Generate)));
+
+        let result = handle_hotkey(&Key::Character("g".to_string()), false, false, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Suppressed));
+    }
+
     #[test]
     fn test_suppressed_when_input_focused() {
         let ctx = HotkeyContext {
@@ -178,5 +521,418 @@ mod tests {
         let result = handle_hotkey(&Key::Character("+".to_string()), false, false, false, false, &ctx);
         assert!(matches!(result, HotkeyResult::Suppressed));
     }
-}
 
+    #[test]
+    fn test_delete_removes_selection() {
+        let ctx = HotkeyContext {
+            has_selection: true,
+            ..Default::default()
+        };
+        for key in [Key::Delete, Key::Backspace] {
+            let result = handle_hotkey(&key, false, false, false, false, &ctx);
+            assert!(matches!(result, HotkeyResult::Action(HotkeyAction::DeleteSelection)));
+        }
+
+        let result = handle_hotkey(&Key::Delete, false, false, false, false, &HotkeyContext::default());
+        assert!(matches!(result, HotkeyResult::NoMatch));
+    }
+
+    #[test]
+    fn test_ctrl_a_selects_all() {
+        let ctx = HotkeyContext {
+            timeline_visible: true,
+            ..Default::default()
+        };
+        let result = handle_hotkey(&Key::Character("a".to_string()), false, true, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::SelectAll)));
+        let result = handle_hotkey(&Key::Character("a".to_string()), false, false, false, true, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::SelectAll)));
+
+        // Nothing to select into while the timeline is collapsed
+        let result = handle_hotkey(
+            &Key::Character("a".to_string()),
+            false,
+            true,
+            false,
+            false,
+            &HotkeyContext::default(),
+        );
+        assert!(matches!(result, HotkeyResult::NoMatch));
+    }
+
+    #[test]
+    fn test_arrows_step_frames() {
+        let ctx = HotkeyContext::default();
+        let result = handle_hotkey(&Key::ArrowRight, false, false, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::StepForward)));
+        let result = handle_hotkey(&Key::ArrowLeft, false, false, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::StepBackward)));
+        let result = handle_hotkey(&Key::ArrowRight, true, false, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::StepForwardTenFrames)));
+        let result = handle_hotkey(&Key::ArrowLeft, true, false, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::StepBackwardTenFrames)));
+    }
+
+    #[test]
+    fn test_ctrl_arrows_jump_markers() {
+        let ctx = HotkeyContext::default();
+        let result = handle_hotkey(&Key::ArrowRight, false, true, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::NextMarker)));
+        let result = handle_hotkey(&Key::ArrowLeft, false, false, false, true, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::PrevMarker)));
+
+        // Word navigation in text fields is left alone
+        let ctx = HotkeyContext {
+            input_focused: true,
+            ..Default::default()
+        };
+        let result = handle_hotkey(&Key::ArrowLeft, false, true, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Suppressed));
+    }
+
+    #[test]
+    fn test_ctrl_z_undo_redo() {
+        let ctx = HotkeyContext::default();
+        let result = handle_hotkey(&Key::Character("z".to_string()), false, true, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::Undo)));
+        let result = handle_hotkey(&Key::Character("Z".to_string()), true, false, false, true, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::Redo)));
+        let result = handle_hotkey(&Key::Character("y".to_string()), false, true, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::Redo)));
+
+        // Text fields keep their own undo
+        let ctx = HotkeyContext {
+            input_focused: true,
+            ..Default::default()
+        };
+        let result = handle_hotkey(&Key::Character("z".to_string()), false, true, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Suppressed));
+    }
+
+    #[test]
+    fn test_keymap_overrides_zoom_in() {
+        let keymap = KeyMap::from_json(r#"{ "TimelineZoomIn": "Ctrl+=" }"#);
+        let ctx = HotkeyContext::default();
+
+        let result = handle_hotkey_with_keymap(
+            &Key::Character("=".to_string()),
+            false,
+            true,
+            false,
+            false,
+            &ctx,
+            &keymap,
+        );
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::TimelineZoomIn)));
+
+        // The default "+" binding is replaced, while other defaults stay put
+        let result = handle_hotkey_with_keymap(
+            &Key::Character("+".to_string()),
+            false,
+            false,
+            false,
+            false,
+            &ctx,
+            &keymap,
+        );
+        assert!(matches!(result, HotkeyResult::NoMatch));
+        let result = handle_hotkey_with_keymap(
+            &Key::Character("-".to_string()),
+            false,
+            false,
+            false,
+            false,
+            &ctx,
+            &keymap,
+        );
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::TimelineZoomOut)));
+    }
+
+    #[test]
+    fn test_keymap_ignores_unknown_actions() {
+        let keymap = KeyMap::from_json(r#"{ "NotAnAction": "Space" }"#);
+        let result = handle_hotkey_with_keymap(
+            &Key::Character(" ".to_string()),
+            false,
+            false,
+            false,
+            false,
+            &HotkeyContext::default(),
+            &keymap,
+        );
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::PlayPause)));
+    }
+
+    #[test]
+    fn test_ctrl_c_v_copy_paste() {
+        let ctx = HotkeyContext {
+            has_selection: true,
+            ..Default::default()
+        };
+        let result = handle_hotkey(&Key::Character("c".to_string()), false, true, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::CopySelection)));
+        let result = handle_hotkey(&Key::Character("v".to_string()), false, false, false, true, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::Paste)));
+
+        // Copying with nothing selected does nothing
+        let result = handle_hotkey(
+            &Key::Character("c".to_string()),
+            false,
+            true,
+            false,
+            false,
+            &HotkeyContext::default(),
+        );
+        assert!(matches!(result, HotkeyResult::NoMatch));
+    }
+
+    #[test]
+    fn test_ctrl_d_duplicates_selection() {
+        let ctx = HotkeyContext {
+            has_selection: true,
+            ..Default::default()
+        };
+        let result = handle_hotkey(&Key::Character("d".to_string()), false, true, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::DuplicateSelection)));
+    }
+
+    #[test]
+    fn test_ctrl_g_groups_selection() {
+        let ctx = HotkeyContext {
+            has_selection: true,
+            generative_selected: true,
+            ..Default::default()
+        };
+        let result = handle_hotkey(&Key::Character("g".to_string()), false, true, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::GroupSelection)));
+        let result = handle_hotkey(&Key::Character("G".to_string()), true, true, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::UngroupSelection)));
+
+        // Plain G still focuses the generative controls
+        let result = handle_hotkey(&Key::Character("g".to_string()), false, false, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::FocusGenerativeControls)));
+    }
+
+    #[test]
+    fn test_nudge_keys_need_selection() {
+        let ctx = HotkeyContext {
+            has_selection: true,
+            ..Default::default()
+        };
+        let result = handle_hotkey(&Key::Character(",".to_string()), false, false, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::NudgeLeft)));
+        let result = handle_hotkey(&Key::ArrowRight, false, false, true, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::NudgeRight)));
+
+        let result = handle_hotkey(
+            &Key::Character(".".to_string()),
+            false,
+            false,
+            false,
+            false,
+            &HotkeyContext::default(),
+        );
+        assert!(matches!(result, HotkeyResult::NoMatch));
+    }
+
+    #[test]
+    fn test_track_mute_solo_keys() {
+        let ctx = HotkeyContext {
+            track_selected: true,
+            ..Default::default()
+        };
+        let result = handle_hotkey(&Key::Character("m".to_string()), false, false, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::ToggleTrackMute)));
+        let result = handle_hotkey(&Key::Character("S".to_string()), true, false, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::ToggleTrackSolo)));
+
+        // Plain S and Ctrl+S keep their meaning
+        let result = handle_hotkey(&Key::Character("s".to_string()), false, false, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::NoMatch));
+        let result = handle_hotkey(&Key::Character("s".to_string()), false, true, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::SaveProject)));
+
+        let result = handle_hotkey(
+            &Key::Character("m".to_string()),
+            false,
+            false,
+            false,
+            false,
+            &HotkeyContext::default(),
+        );
+        assert!(matches!(result, HotkeyResult::NoMatch));
+    }
+
+    #[test]
+    fn test_shift_m_adds_marker() {
+        let ctx = HotkeyContext {
+            track_selected: true,
+            ..Default::default()
+        };
+        let result = handle_hotkey(&Key::Character("M".to_string()), true, false, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::AddMarker)));
+        let result = handle_hotkey(
+            &Key::Character("M".to_string()),
+            true,
+            false,
+            false,
+            false,
+            &HotkeyContext::default(),
+        );
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::AddMarker)));
+
+        // Plain M still mutes the selected track
+        let result = handle_hotkey(&Key::Character("m".to_string()), false, false, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::ToggleTrackMute)));
+    }
+
+    #[test]
+    fn test_shift_m_no_match_when_modal_open() {
+        let ctx = HotkeyContext {
+            modal_open: true,
+            ..Default::default()
+        };
+        let result = handle_hotkey(&Key::Character("M".to_string()), true, false, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::NoMatch));
+        assert!(!action_available(HotkeyAction::AddMarker, &ctx));
+    }
+
+    #[test]
+    fn test_follow_playhead_key() {
+        let ctx = HotkeyContext {
+            timeline_visible: true,
+            ..Default::default()
+        };
+        let result = handle_hotkey(&Key::Character("f".to_string()), false, false, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::ToggleFollowPlayhead)));
+
+        let result = handle_hotkey(&Key::Character("f".to_string()), false, true, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::NoMatch));
+
+        let result = handle_hotkey(
+            &Key::Character("f".to_string()),
+            false,
+            false,
+            false,
+            false,
+            &HotkeyContext::default(),
+        );
+        assert!(matches!(result, HotkeyResult::NoMatch));
+    }
+
+    #[test]
+    fn test_escape_deselects_all() {
+        let ctx = HotkeyContext::default();
+        let result = handle_hotkey(&Key::Escape, false, false, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::DeselectAll)));
+    }
+
+    #[test]
+    fn test_escape_no_match_when_modal_open() {
+        let ctx = HotkeyContext {
+            modal_open: true,
+            ..Default::default()
+        };
+        let result = handle_hotkey(&Key::Escape, false, false, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::NoMatch));
+    }
+
+    #[test]
+    fn test_ctrl_enter_no_match_when_modal_open() {
+        let ctx = HotkeyContext {
+            generative_selected: true,
+            modal_open: true,
+            ..Default::default()
+        };
+        let result = handle_hotkey(&Key::Enter, false, true, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::NoMatch));
+        let typing = HotkeyContext {
+            input_focused: true,
+            ..ctx
+        };
+        let result = handle_hotkey(&Key::Enter, false, true, false, false, &typing);
+        assert!(matches!(result, HotkeyResult::Suppressed));
+        assert!(!action_available(HotkeyAction::Generate, &ctx));
+    }
+
+    #[test]
+    fn test_delete_suppressed_when_input_focused() {
+        let ctx = HotkeyContext {
+            input_focused: true,
+            has_selection: true,
+            ..Default::default()
+        };
+        for key in [Key::Delete, Key::Backspace] {
+            let result = handle_hotkey(&key, false, false, false, false, &ctx);
+            assert!(matches!(result, HotkeyResult::Suppressed));
+        }
+    }
+
+    #[test]
+    fn test_zoom_fit_and_selection_keys() {
+        let ctx = HotkeyContext {
+            timeline_visible: true,
+            ..Default::default()
+        };
+        let result = handle_hotkey(&Key::Character("\\".to_string()), false, false, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::TimelineZoomFit)));
+
+        // Z needs a selection to frame, and Ctrl+Z stays undo
+        let result = handle_hotkey(&Key::Character("z".to_string()), false, false, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::NoMatch));
+        let ctx = HotkeyContext {
+            has_selection: true,
+            ..ctx
+        };
+        let result = handle_hotkey(&Key::Character("z".to_string()), false, false, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::TimelineZoomSelection)));
+        let result = handle_hotkey(&Key::Character("z".to_string()), false, true, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::Undo)));
+    }
+
+    #[test]
+    fn test_assets_panel_navigation_keys() {
+        let ctx = HotkeyContext {
+            assets_focused: true,
+            ..Default::default()
+        };
+        let expected = [
+            (Key::ArrowUp, HotkeyAction::AssetPrevious),
+            (Key::ArrowDown, HotkeyAction::AssetNext),
+            (Key::Home, HotkeyAction::AssetFirst),
+            (Key::End, HotkeyAction::AssetLast),
+            (Key::Enter, HotkeyAction::AddAssetToTimeline),
+        ];
+        for (key, action) in expected.iter() {
+            let result = handle_hotkey(key, false, false, false, false, &ctx);
+            assert!(matches!(result, HotkeyResult::Action(found) if found == *action));
+        }
+        // Left/Right still step frames
+        let result = handle_hotkey(&Key::ArrowRight, false, false, false, false, &ctx);
+        assert!(matches!(result, HotkeyResult::Action(HotkeyAction::StepForward)));
+
+        // Typing in a field (e.g. renaming an asset) suppresses navigation
+        let typing = HotkeyContext {
+            input_focused: true,
+            ..ctx.clone()
+        };
+        for (key, _) in expected.iter() {
+            let result = handle_hotkey(key, false, false, false, false, &typing);
+            assert!(matches!(result, HotkeyResult::Suppressed));
+        }
+
+        // Without panel focus, or under a modal, the keys do nothing
+        for context in [
+            HotkeyContext::default(),
+            HotkeyContext {
+                modal_open: true,
+                ..ctx.clone()
+            },
+        ] {
+            let result = handle_hotkey(&Key::ArrowDown, false, false, false, false, &context);
+            assert!(matches!(result, HotkeyResult::NoMatch));
+            let result = handle_hotkey(&Key::Enter, false, false, false, false, &context);
+            assert!(matches!(result, HotkeyResult::NoMatch));
+        }
+    }
+}
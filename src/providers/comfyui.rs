@@ -4,9 +4,11 @@ use std::path::{Path, PathBuf};
 use std::time::Duration;
 use uuid::Uuid;
 
+use crate::core::generation::{apply_binding_transform, GenerationError};
 use crate::core::paths;
+use crate::providers::request_error;
 use crate::state::{
-    input_value_as_bool, input_value_as_f64, input_value_as_i64, BindingTransform, ManifestInput,
+    input_value_as_bool, input_value_as_f64, input_value_as_i64, ManifestInput,
     NodeSelector, ProviderInputType, ProviderManifest, ProviderOutputType,
 };
 
@@ -156,7 +158,8 @@ pub async fn check_health(base_url: &str) -> Result<(), String> {
     }
 }
 
-/// Submits a ComfyUI workflow and downloads the first output matching the output type.
+/// Submits a ComfyUI workflow and downloads the outputs matching the output type.
+/// Returns one entry per image when the manifest's output `index` is unset.
 pub async fn generate_output(
     base_url: &str,
     workflow_path: &Path,
@@ -164,7 +167,7 @@ pub async fn generate_output(
     manifest_path: Option<&Path>,
     output_type: ProviderOutputType,
     progress_tx: Option<tokio::sync::mpsc::UnboundedSender<ComfyUiProgress>>,
-) -> Result<ComfyUiOutput, String> {
+) -> Result<Vec<ComfyUiOutput>, GenerationError> {
     let mut workflow = load_workflow(workflow_path)?;
     let total_nodes = workflow.as_object().map(|map| map.len()).unwrap_or(0);
     let (output_node_id, output_key, output_index) = if let Some(path) = manifest_path {
@@ -172,10 +175,9 @@ pub async fn generate_output(
         let (manifest_inputs, output_selector) = match manifest {
             ProviderManifest::ComfyUi { inputs, output, .. } => (inputs, output),
             _ => {
-                return Err(
-                    "Provider manifest adapter_type must be comfy_ui for ComfyUI providers."
-                        .to_string(),
-                )
+                return Err(GenerationError::permanent(
+                    "Provider manifest adapter_type must be comfy_ui for ComfyUI providers.",
+                ))
             }
         };
         apply_manifest_inputs(&mut workflow, inputs, &manifest_inputs)?;
@@ -191,10 +193,10 @@ pub async fn generate_output(
             (
                 Some(OUTPUT_NODE_ID.to_string()),
                 Some(DEFAULT_OUTPUT_KEY.to_string()),
-                None,
+                Some(0),
             )
         } else {
-            (None, None, None)
+            (None, None, Some(0))
         }
     };
 
@@ -212,29 +214,32 @@ pub async fn generate_output(
     if let Some(task) = ws_task {
         task.abort();
     }
-    let output_ref = find_output_ref(
+    let output_refs = find_output_refs(
         &outputs,
         output_node_id.as_deref(),
         output_key.as_deref(),
         output_index,
         output_type,
-    )
-    .ok_or_else(|| {
-        format!(
+    );
+    if output_refs.is_empty() {
+        return Err(GenerationError::permanent(format!(
             "ComfyUI history did not include {} outputs. This can happen when cached \
 results are returned for identical inputs; try changing the seed or using batch seed offsets.",
             output_type_label(output_type)
-        )
-    })?;
-    let bytes = download_output(&client, base_url, &output_ref).await?;
-
-    let extension = Path::new(&output_ref.filename)
-        .extension()
-        .and_then(|ext| ext.to_str())
-        .unwrap_or_else(|| default_extension_for_output(output_type))
-        .to_string();
+        )));
+    }
 
-    Ok(ComfyUiOutput { bytes, extension })
+    let mut results = Vec::with_capacity(output_refs.len());
+    for output_ref in output_refs.iter() {
+        let bytes = download_output(&client, base_url, output_ref).await?;
+        let extension = Path::new(&output_ref.filename)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or_else(|| default_extension_for_output(output_type))
+            .to_string();
+        results.push(ComfyUiOutput { bytes, extension });
+    }
+    Ok(results)
 }
 
 fn load_workflow(path: &Path) -> Result<Value, String> {
@@ -271,10 +276,17 @@ fn apply_manifest_inputs(
             continue;
         };
         let node_id = resolve_node_id(workflow, &manifest_input.bind.selector)?;
-        let mut resolved = apply_binding_transform(value, manifest_input.bind.transform.as_ref())?;
-        resolved = coerce_manifest_value(&resolved, &manifest_input.input_type).map_err(|err| {
+        // Coerce to the declared type first so transforms see what the user
+        // entered; the transform result goes to the node as-is.
+        let coerced = coerce_manifest_value(value, &manifest_input.input_type).map_err(|err| {
             format!("Input {}: {}", manifest_input.name, err)
         })?;
+        let mut resolved = apply_binding_transform(&coerced, manifest_input.bind.transform.as_ref())
+            .map_err(|err| format!("Input {}: {}", manifest_input.name, err))?;
+        // Legacy scale/clamp on integer inputs still feeds the node an integer.
+        if manifest_input.input_type == ProviderInputType::Integer && resolved.is_f64() {
+            resolved = coerce_manifest_value(&resolved, &ProviderInputType::Integer)?;
+        }
         set_workflow_input(
             workflow,
             &node_id,
@@ -421,24 +433,6 @@ fn set_workflow_input(
     Ok(())
 }
 
-fn apply_binding_transform(
-    value: &Value,
-    transform: Option<&BindingTransform>,
-) -> Result<Value, String> {
-    let Some(transform) = transform else {
-        return Ok(value.clone());
-    };
-    let number = input_value_as_f64(value)
-        .ok_or_else(|| "Expected numeric input for transform.".to_string())?;
-    let adjusted = match transform {
-        BindingTransform::Clamp { min, max } => number.clamp(*min, *max),
-        BindingTransform::Scale { factor } => number * factor,
-    };
-    serde_json::Number::from_f64(adjusted)
-        .map(Value::Number)
-        .ok_or_else(|| "Transformed value is not a valid number.".to_string())
-}
-
 fn coerce_manifest_value(
     value: &Value,
     input_type: &ProviderInputType,
@@ -524,38 +518,38 @@ async fn submit_prompt(
     client: &reqwest::Client,
     base_url: &str,
     workflow: &Value,
-) -> Result<String, String> {
+) -> Result<String, GenerationError> {
     let url = format!("{}/prompt", base_url.trim_end_matches('/'));
     let response = client
         .post(url)
         .json(&serde_json::json!({ "prompt": workflow }))
         .send()
         .await
-        .map_err(|err| format!("Failed to submit prompt: {}", err))?;
+        .map_err(|err| request_error("Failed to submit prompt", err))?;
     let status = response.status();
-    let payload: Value = response
-        .json()
-        .await
-        .map_err(|err| format!("Failed to parse prompt response: {}", err))?;
     if !status.is_success() {
-        return Err(format!(
-            "ComfyUI rejected prompt ({}): {}",
-            status,
-            payload
+        let body = response.text().await.unwrap_or_default();
+        return Err(GenerationError::from_status(
+            status.as_u16(),
+            format!("ComfyUI rejected prompt ({}): {}", status, body),
         ));
     }
+    let payload: Value = response
+        .json()
+        .await
+        .map_err(|err| request_error("Failed to parse prompt response", err))?;
     payload
         .get("prompt_id")
         .and_then(|value| value.as_str())
         .map(|value| value.to_string())
-        .ok_or_else(|| "ComfyUI response missing prompt_id".to_string())
+        .ok_or_else(|| GenerationError::permanent("ComfyUI response missing prompt_id"))
 }
 
 async fn poll_history(
     client: &reqwest::Client,
     base_url: &str,
     prompt_id: &str,
-) -> Result<Value, String> {
+) -> Result<Value, GenerationError> {
     let url = format!(
         "{}/history/{}",
         base_url.trim_end_matches('/'),
@@ -566,11 +560,18 @@ async fn poll_history(
             .get(&url)
             .send()
             .await
-            .map_err(|err| format!("Failed to query history: {}", err))?;
+            .map_err(|err| request_error("Failed to query history", err))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(GenerationError::from_status(
+                status.as_u16(),
+                format!("ComfyUI history query failed: {}", status),
+            ));
+        }
         let payload: Value = response
             .json()
             .await
-            .map_err(|err| format!("Failed to parse history: {}", err))?;
+            .map_err(|err| request_error("Failed to parse history", err))?;
 
         if let Some(outputs) = extract_outputs(&payload, prompt_id) {
             return Ok(outputs.clone());
@@ -579,7 +580,7 @@ async fn poll_history(
         tokio::time::sleep(Duration::from_millis(500)).await;
     }
 
-    Err("Timed out waiting for ComfyUI output.".to_string())
+    Err(GenerationError::transient("Timed out waiting for ComfyUI output."))
 }
 
 fn build_ws_url(base_url: &str, client_id: &str) -> String {
@@ -736,107 +737,98 @@ struct OutputRef {
     kind: String,
 }
 
-fn find_output_ref(
+/// Outputs selected by `index`: `Some(i)` picks the i-th item of the output
+/// list, `None` takes every item so a batch becomes one version per image.
+fn find_output_refs(
     outputs: &Value,
     output_node_id: Option<&str>,
     output_key: Option<&str>,
     index: Option<u32>,
     output_type: ProviderOutputType,
-) -> Option<OutputRef> {
-    if let Some(node_id) = output_node_id {
-        if let Some(output) = outputs.get(node_id) {
-            if let Some(key) = output_key {
-                if let Some(item) = extract_output_ref(output, key, index, Some(output_type)) {
-                    return Some(item);
-                }
-            }
-            if let Some(item) = extract_output_ref_any(output, output_type, index) {
-                return Some(item);
-            }
-        }
-    } else if let Some(output) = outputs.get(OUTPUT_NODE_ID) {
+) -> Vec<OutputRef> {
+    let node_output = outputs.get(output_node_id.unwrap_or(OUTPUT_NODE_ID));
+    if let Some(output) = node_output {
         if let Some(key) = output_key {
-            if let Some(item) = extract_output_ref(output, key, index, Some(output_type)) {
-                return Some(item);
+            let items = extract_output_refs(output, key, index, output_type);
+            if !items.is_empty() {
+                return items;
             }
         }
-        if let Some(item) = extract_output_ref_any(output, output_type, index) {
-            return Some(item);
+        let items = extract_output_refs_any(output, output_type, index);
+        if !items.is_empty() {
+            return items;
         }
     }
 
+    let Some(map) = outputs.as_object() else {
+        return Vec::new();
+    };
     if let Some(key) = output_key {
-        if let Some(item) = find_output_by_key(outputs, key, index, output_type) {
-            return Some(item);
+        for output in map.values() {
+            let items = extract_output_refs(output, key, index, output_type);
+            if !items.is_empty() {
+                return items;
+            }
         }
     }
-
-    find_output_any(outputs, output_type, index)
-}
-
-fn find_output_by_key(
-    outputs: &Value,
-    output_key: &str,
-    index: Option<u32>,
-    output_type: ProviderOutputType,
-) -> Option<OutputRef> {
-    outputs.as_object().and_then(|map| {
-        map.values()
-            .find_map(|value| extract_output_ref(value, output_key, index, Some(output_type)))
-    })
-}
-
-fn find_output_any(
-    outputs: &Value,
-    output_type: ProviderOutputType,
-    index: Option<u32>,
-) -> Option<OutputRef> {
-    outputs
-        .as_object()
-        .and_then(|map| map.values().find_map(|value| {
-            extract_output_ref_any(value, output_type, index)
-        }))
+    for output in map.values() {
+        let items = extract_output_refs_any(output, output_type, index);
+        if !items.is_empty() {
+            return items;
+        }
+    }
+    Vec::new()
 }
 
-fn extract_output_ref(
+fn extract_output_refs(
     output: &Value,
     output_key: &str,
     index: Option<u32>,
-    output_type: Option<ProviderOutputType>,
-) -> Option<OutputRef> {
-    let items = output.get(output_key)?.as_array()?;
-    let item = extract_output_item(items, index)?;
-    if let Some(output_type) = output_type {
-        if !output_matches_type(&item.filename, output_type) {
-            return None;
-        }
-    }
-    Some(item)
+    output_type: ProviderOutputType,
+) -> Vec<OutputRef> {
+    let Some(items) = output.get(output_key).and_then(|value| value.as_array()) else {
+        return Vec::new();
+    };
+    extract_output_items(items, index, output_type)
 }
 
-fn extract_output_ref_any(
+fn extract_output_refs_any(
     output: &Value,
     output_type: ProviderOutputType,
     index: Option<u32>,
-) -> Option<OutputRef> {
-    let output_obj = output.as_object()?;
+) -> Vec<OutputRef> {
+    let Some(output_obj) = output.as_object() else {
+        return Vec::new();
+    };
     for value in output_obj.values() {
         let Some(items) = value.as_array() else {
             continue;
         };
-        let Some(item) = extract_output_item(items, index) else {
-            continue;
-        };
-        if output_matches_type(&item.filename, output_type) {
-            return Some(item);
+        let refs = extract_output_items(items, index, output_type);
+        if !refs.is_empty() {
+            return refs;
         }
     }
-    None
+    Vec::new()
+}
+
+fn extract_output_items(
+    items: &[Value],
+    index: Option<u32>,
+    output_type: ProviderOutputType,
+) -> Vec<OutputRef> {
+    let selected: Vec<&Value> = match index {
+        Some(idx) => items.get(idx as usize).into_iter().collect(),
+        None => items.iter().collect(),
+    };
+    selected
+        .into_iter()
+        .filter_map(parse_output_item)
+        .filter(|item| output_matches_type(&item.filename, output_type))
+        .collect()
 }
 
-fn extract_output_item(items: &[Value], index: Option<u32>) -> Option<OutputRef> {
-    let idx = index.unwrap_or(0) as usize;
-    let item = items.get(idx)?;
+fn parse_output_item(item: &Value) -> Option<OutputRef> {
     let filename = item.get("filename")?.as_str()?.to_string();
     let subfolder = item
         .get("subfolder")
@@ -855,7 +847,7 @@ fn extract_output_item(items: &[Value], index: Option<u32>) -> Option<OutputRef>
     })
 }
 
-fn output_matches_type(filename: &str, output_type: ProviderOutputType) -> bool {
+pub(crate) fn output_matches_type(filename: &str, output_type: ProviderOutputType) -> bool {
     let Some(ext) = output_extension(filename) else {
         return false;
     };
@@ -881,7 +873,7 @@ fn output_type_label(output_type: ProviderOutputType) -> &'static str {
     }
 }
 
-fn default_extension_for_output(output_type: ProviderOutputType) -> &'static str {
+pub(crate) fn default_extension_for_output(output_type: ProviderOutputType) -> &'static str {
     match output_type {
         ProviderOutputType::Image => "png",
         ProviderOutputType::Video => "mp4",
@@ -901,7 +893,7 @@ async fn download_output(
     client: &reqwest::Client,
     base_url: &str,
     output: &OutputRef,
-) -> Result<Vec<u8>, String> {
+) -> Result<Vec<u8>, GenerationError> {
     let url = format!(
         "{}/view?filename={}&subfolder={}&type={}",
         base_url.trim_end_matches('/'),
@@ -913,14 +905,75 @@ async fn download_output(
         .get(url)
         .send()
         .await
-        .map_err(|err| format!("Failed to download output: {}", err))?;
+        .map_err(|err| request_error("Failed to download output", err))?;
     let status = response.status();
     if !status.is_success() {
-        return Err(format!("ComfyUI output download failed: {}", status));
+        return Err(GenerationError::from_status(
+            status.as_u16(),
+            format!("ComfyUI output download failed: {}", status),
+        ));
     }
     response
         .bytes()
         .await
         .map(|bytes| bytes.to_vec())
-        .map_err(|err| format!("Failed to read output bytes: {}", err))
+        .map_err(|err| request_error("Failed to read output bytes", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn history() -> Value {
+        json!({
+            "9": {
+                "images": [
+                    { "filename": "out_00001_.png", "subfolder": "", "type": "output" },
+                    { "filename": "out_00002_.png", "subfolder": "", "type": "output" },
+                    { "filename": "out_00003_.png", "subfolder": "batch", "type": "output" }
+                ]
+            },
+            "12": {
+                "images": [{ "filename": "preview_00001_.png", "subfolder": "", "type": "temp" }]
+            }
+        })
+    }
+
+    fn filenames(refs: &[OutputRef]) -> Vec<&str> {
+        refs.iter().map(|item| item.filename.as_str()).collect()
+    }
+
+    #[test]
+    fn test_output_index_selects_single_item() {
+        let refs = find_output_refs(&history(), Some("9"), Some("images"), Some(1), ProviderOutputType::Image);
+        assert_eq!(filenames(&refs), vec!["out_00002_.png"]);
+
+        let refs = find_output_refs(&history(), Some("9"), Some("images"), Some(2), ProviderOutputType::Image);
+        assert_eq!(refs[0].subfolder, "batch");
+
+        // Items of the wrong media type are never selected
+        let refs = find_output_refs(&history(), Some("9"), Some("images"), Some(0), ProviderOutputType::Video);
+        assert!(refs.is_empty());
+    }
+
+    #[test]
+    fn test_unset_index_returns_whole_batch() {
+        let refs = find_output_refs(&history(), Some("9"), Some("images"), None, ProviderOutputType::Image);
+        assert_eq!(
+            filenames(&refs),
+            vec!["out_00001_.png", "out_00002_.png", "out_00003_.png"]
+        );
+
+        let mixed = json!({
+            "5": {
+                "gifs": [
+                    { "filename": "clip_00001.mp4", "subfolder": "", "type": "output" },
+                    { "filename": "clip_00001.png", "subfolder": "", "type": "output" }
+                ]
+            }
+        });
+        let refs = find_output_refs(&mixed, Some("5"), Some("gifs"), None, ProviderOutputType::Video);
+        assert_eq!(filenames(&refs), vec!["clip_00001.mp4"]);
+    }
 }
@@ -0,0 +1,126 @@
+//! Environment variables the user agreed to let HTTP providers read, kept in
+//! `provider_env.json` in the app config folder.
+//!
+//! Approvals live with the user rather than in the provider file, so a shared
+//! `.nlaprovider` can't arrive pre-approved.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::http::env_vars_read;
+use crate::state::{ProviderConnection, ProviderEntry};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProviderEnvApprovals {
+    #[serde(default)]
+    approved: HashMap<Uuid, Vec<String>>,
+}
+
+impl ProviderEnvApprovals {
+    /// Load the user's approvals, or none when the file is missing or unreadable.
+    pub fn load_user() -> Self {
+        Self::load(&crate::core::paths::provider_env_approvals_path())
+    }
+
+    pub fn load(path: &Path) -> Self {
+        let json = match std::fs::read_to_string(path) {
+            Ok(json) => json,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Self::default(),
+            Err(err) => {
+                eprintln!("[PROVIDER WARN] Failed to read {}: {}", path.display(), err);
+                return Self::default();
+            }
+        };
+        serde_json::from_str(&json).unwrap_or_else(|err| {
+            eprintln!("[PROVIDER WARN] Ignoring malformed env approvals: {}", err);
+            Self::default()
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|err| err.to_string())?;
+        std::fs::write(path, json).map_err(|err| err.to_string())
+    }
+
+    /// Variables `provider` reads that the user hasn't approved for it yet.
+    /// Empty for providers that read none.
+    pub fn unapproved(&self, provider: &ProviderEntry) -> Vec<String> {
+        let approved = self.approved.get(&provider.id);
+        provider_env_vars(provider)
+            .into_iter()
+            .filter(|var| !approved.is_some_and(|approved| approved.contains(var)))
+            .collect()
+    }
+
+    pub fn approve(&mut self, provider_id: Uuid, vars: &[String]) {
+        let approved = self.approved.entry(provider_id).or_default();
+        for var in vars {
+            if !approved.contains(var) {
+                approved.push(var.clone());
+            }
+        }
+        approved.sort();
+    }
+}
+
+/// Environment variables a provider's requests reference.
+pub fn provider_env_vars(provider: &ProviderEntry) -> Vec<String> {
+    match &provider.connection {
+        ProviderConnection::Http(config) => env_vars_read(config),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::ProviderOutputType;
+
+    fn http_provider(url: &str) -> ProviderEntry {
+        let config = serde_json::from_value(serde_json::json!({
+            "request": { "url": url, "headers": { "Authorization": "Bearer {{env:NLA_TOKEN}}" } },
+            "response_path": "output"
+        }))
+        .unwrap();
+        ProviderEntry::new("Hosted", ProviderOutputType::Image, ProviderConnection::Http(config))
+    }
+
+    #[test]
+    fn test_new_variables_need_approval() {
+        let mut approvals = ProviderEnvApprovals::default();
+        let provider = http_provider("https://api.example.com/run");
+        assert_eq!(approvals.unapproved(&provider), vec!["NLA_TOKEN"]);
+
+        approvals.approve(provider.id, &["NLA_TOKEN".to_string()]);
+        assert!(approvals.unapproved(&provider).is_empty());
+
+        // Reading another variable later asks again, for that one only
+        let mut edited = http_provider("https://{{env:NLA_HOST}}/run");
+        edited.id = provider.id;
+        assert_eq!(approvals.unapproved(&edited), vec!["NLA_HOST"]);
+
+        // Approval is per provider
+        let other = http_provider("https://api.example.com/run");
+        assert_eq!(approvals.unapproved(&other), vec!["NLA_TOKEN"]);
+    }
+
+    #[test]
+    fn test_approvals_round_trip() {
+        let path = std::env::temp_dir().join(format!("nla-provider-env-{}.json", Uuid::new_v4()));
+        assert_eq!(ProviderEnvApprovals::load(&path), ProviderEnvApprovals::default());
+        let mut approvals = ProviderEnvApprovals::default();
+        let id = Uuid::new_v4();
+        approvals.approve(id, &["NLA_B".to_string(), "NLA_A".to_string()]);
+        approvals.save(&path).unwrap();
+        let loaded = ProviderEnvApprovals::load(&path);
+        assert_eq!(loaded, approvals);
+        assert_eq!(loaded.approved[&id], vec!["NLA_A", "NLA_B"]);
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,181 @@
+//! Short-lived cache of provider health checks so repeated generate clicks and
+//! the providers modal don't probe the same endpoint every time.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::core::generation::provider_endpoint_key;
+use crate::providers::{comfyui, http};
+use crate::state::ProviderConnection;
+
+/// Outcome of a health check that did not fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProviderHealth {
+    Online,
+    /// The adapter has no health endpoint, so the provider is assumed online.
+    Unchecked,
+}
+
+/// How long a health result is reused before the endpoint is probed again.
+pub const HEALTH_CACHE_TTL: Duration = Duration::from_secs(10);
+
+/// Health results keyed by the endpoint they were checked against. Callers
+/// pass `now` so expiry can be tested without sleeping.
+pub struct HealthCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (Instant, Result<ProviderHealth, String>)>>,
+}
+
+impl HealthCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Cached result for `key`, if it was stored less than the TTL ago.
+    pub fn get(&self, key: &str, now: Instant) -> Option<Result<ProviderHealth, String>> {
+        let entries = self.entries.lock().ok()?;
+        let (checked_at, result) = entries.get(key)?;
+        if now.saturating_duration_since(*checked_at) < self.ttl {
+            Some(result.clone())
+        } else {
+            None
+        }
+    }
+
+    pub fn insert(&self, key: &str, result: Result<ProviderHealth, String>, now: Instant) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.insert(key.to_string(), (now, result));
+        }
+    }
+
+    pub fn clear(&self) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.clear();
+        }
+    }
+}
+
+fn health_cache() -> &'static HealthCache {
+    static CACHE: OnceLock<HealthCache> = OnceLock::new();
+    CACHE.get_or_init(|| HealthCache::new(HEALTH_CACHE_TTL))
+}
+
+/// What a health check actually probes. Editing a provider's URL yields a new
+/// key, so a stale result for the old address is never reused.
+pub fn health_cache_key(connection: &ProviderConnection) -> String {
+    match connection {
+        ProviderConnection::Http(config) => match config.health_url.as_deref() {
+            Some(url) if !url.trim().is_empty() => url.trim().to_ascii_lowercase(),
+            _ => provider_endpoint_key(connection),
+        },
+        _ => provider_endpoint_key(connection),
+    }
+}
+
+/// Health check that reuses a result from the last `HEALTH_CACHE_TTL`.
+pub async fn check_provider_health(
+    connection: &ProviderConnection,
+) -> Result<ProviderHealth, String> {
+    let key = health_cache_key(connection);
+    if let Some(result) = health_cache().get(&key, Instant::now()) {
+        return result;
+    }
+    refresh_provider_health(connection).await
+}
+
+/// Probe the endpoint now and store the result for later cached checks.
+pub async fn refresh_provider_health(
+    connection: &ProviderConnection,
+) -> Result<ProviderHealth, String> {
+    let result = match connection {
+        ProviderConnection::ComfyUi { base_url, .. } => comfyui::check_health(base_url).await,
+        ProviderConnection::Http(config) => http::check_health(config).await,
+        ProviderConnection::CustomHttp { .. } => return Ok(ProviderHealth::Unchecked),
+    }
+    .map(|()| ProviderHealth::Online);
+    health_cache().insert(&health_cache_key(connection), result.clone(), Instant::now());
+    result
+}
+
+/// Drop every cached result, e.g. from a manual refresh.
+pub fn invalidate_provider_health() {
+    health_cache().clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_hit_within_ttl() {
+        let cache = HealthCache::new(Duration::from_secs(10));
+        let start = Instant::now();
+        assert_eq!(cache.get("http://127.0.0.1:8188", start), None);
+
+        cache.insert("http://127.0.0.1:8188", Ok(ProviderHealth::Online), start);
+        cache.insert("http://10.0.0.2:8188", Err("Connection failed".to_string()), start);
+        let later = start + Duration::from_secs(9);
+        assert_eq!(cache.get("http://127.0.0.1:8188", later), Some(Ok(ProviderHealth::Online)));
+        assert_eq!(
+            cache.get("http://10.0.0.2:8188", later),
+            Some(Err("Connection failed".to_string()))
+        );
+        // A different URL is a different entry
+        assert_eq!(cache.get("http://127.0.0.1:8189", later), None);
+    }
+
+    #[test]
+    fn test_cache_entry_expires_after_ttl() {
+        let cache = HealthCache::new(Duration::from_secs(10));
+        let start = Instant::now();
+        cache.insert("http://127.0.0.1:8188", Ok(ProviderHealth::Online), start);
+        assert_eq!(cache.get("http://127.0.0.1:8188", start + Duration::from_secs(10)), None);
+
+        // Re-checking refreshes the timestamp
+        cache.insert(
+            "http://127.0.0.1:8188",
+            Ok(ProviderHealth::Online),
+            start + Duration::from_secs(10),
+        );
+        assert_eq!(
+            cache.get("http://127.0.0.1:8188", start + Duration::from_secs(15)),
+            Some(Ok(ProviderHealth::Online))
+        );
+
+        cache.clear();
+        assert_eq!(cache.get("http://127.0.0.1:8188", start + Duration::from_secs(15)), None);
+    }
+
+    #[test]
+    fn test_key_follows_url_changes() {
+        let comfy = |base_url: &str| ProviderConnection::ComfyUi {
+            base_url: base_url.to_string(),
+            workflow_path: None,
+            manifest_path: None,
+        };
+        assert_eq!(
+            health_cache_key(&comfy("http://127.0.0.1:8188/")),
+            health_cache_key(&comfy("http://127.0.0.1:8188"))
+        );
+        assert_ne!(
+            health_cache_key(&comfy("http://127.0.0.1:8188")),
+            health_cache_key(&comfy("http://127.0.0.1:8190"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_adapters_without_health_endpoint_are_unchecked() {
+        let connection = ProviderConnection::CustomHttp {
+            base_url: "http://127.0.0.1:9".to_string(),
+            api_key: None,
+        };
+        assert_eq!(
+            refresh_provider_health(&connection).await,
+            Ok(ProviderHealth::Unchecked)
+        );
+    }
+}
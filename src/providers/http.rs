@@ -0,0 +1,563 @@
+//! Generic templated HTTP adapter for hosted generation APIs.
+//!
+//! A request template is filled from the job inputs and sent once. Synchronous
+//! APIs answer with the media URL directly; queued APIs answer with a status
+//! URL that is polled until the job reports done.
+
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::core::generation::GenerationError;
+use crate::state::{
+    input_value_as_string, HttpProviderConfig, HttpRequestTemplate, ProviderConnection,
+    ProviderEntry, ProviderManifest, ProviderOutputType,
+};
+
+use super::comfyui::{default_extension_for_output, output_matches_type};
+use super::request_error;
+
+/// Environment variables a template may read must start with this, so a
+/// shared manifest can't send unrelated secrets (cloud keys, tokens) to its URL.
+pub const ENV_VAR_PREFIX: &str = "NLA_";
+
+#[derive(Debug, Clone)]
+pub struct HttpOutput {
+    pub bytes: Vec<u8>,
+    pub extension: String,
+}
+
+/// Build a provider entry from an `http` manifest.
+pub fn entry_from_manifest(manifest: ProviderManifest) -> Result<ProviderEntry, String> {
+    let ProviderManifest::Http {
+        name,
+        output_type,
+        inputs,
+        connection,
+        ..
+    } = manifest
+    else {
+        return Err("Provider manifest adapter_type must be http.".to_string());
+    };
+    let mut entry = ProviderEntry::new(
+        name.unwrap_or_else(|| "HTTP Provider".to_string()),
+        output_type,
+        ProviderConnection::Http(connection),
+    );
+    entry.inputs = inputs;
+    Ok(entry)
+}
+
+/// Check that the API host answers. Without a `health_url` any HTTP response
+/// from the request origin counts, since most APIs reject bare requests.
+pub async fn check_health(config: &HttpProviderConfig) -> Result<(), String> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(3))
+        .build()
+        .map_err(|err| format!("Failed to build HTTP client: {}", err))?;
+    let empty = HashMap::new();
+    match config.health_url.as_deref() {
+        Some(url) => {
+            let url = substitute_string(url, &empty);
+            let response = client
+                .get(url)
+                .send()
+                .await
+                .map_err(|err| format!("Connection failed: {}", err))?;
+            if response.status().is_success() {
+                Ok(())
+            } else {
+                Err(format!("Health check failed ({})", response.status()))
+            }
+        }
+        None => {
+            let url = substitute_string(&config.request.url, &empty);
+            let origin = url_origin(&url)
+                .ok_or_else(|| format!("Provider URL is not absolute: {}", url))?;
+            client
+                .get(origin)
+                .send()
+                .await
+                .map(|_| ())
+                .map_err(|err| format!("Connection failed: {}", err))
+        }
+    }
+}
+
+/// Submit the templated request, follow the job if it is queued, and download
+/// the media it points at.
+pub async fn generate_output(
+    config: &HttpProviderConfig,
+    inputs: &HashMap<String, Value>,
+    output_type: ProviderOutputType,
+) -> Result<HttpOutput, GenerationError> {
+    let client = reqwest::Client::new();
+    let headers = render_headers(&config.request, inputs);
+    let submitted = submit_request(&client, &config.request, &headers, inputs).await?;
+
+    let result = match config.poll.as_ref() {
+        Some(poll) => {
+            let status_url = extract_json_path(&submitted, &poll.url_path)
+                .and_then(Value::as_str)
+                .ok_or_else(|| format!("Response missing status URL at '{}'.", poll.url_path))?
+                .to_string();
+            let deadline = Instant::now() + Duration::from_secs(poll.timeout_secs);
+            loop {
+                let payload = get_json(&client, &status_url, &headers).await?;
+                let status = extract_json_path(&payload, &poll.status_path)
+                    .and_then(input_value_as_string)
+                    .unwrap_or_default();
+                if value_in(&status, &poll.done_values) {
+                    break payload;
+                }
+                if value_in(&status, &poll.failed_values) {
+                    let detail = payload
+                        .get("error")
+                        .filter(|error| !error.is_null())
+                        .map(|error| error.to_string())
+                        .unwrap_or(status);
+                    return Err(GenerationError::permanent(format!(
+                        "Provider job failed: {}",
+                        detail
+                    )));
+                }
+                if Instant::now() >= deadline {
+                    return Err(GenerationError::transient("Timed out waiting for provider output."));
+                }
+                tokio::time::sleep(Duration::from_millis(poll.interval_ms.max(100))).await;
+            }
+        }
+        None => submitted,
+    };
+
+    let media_url = extract_json_path(&result, &config.response_path)
+        .and_then(Value::as_str)
+        .ok_or_else(|| format!("Response missing media URL at '{}'.", config.response_path))?
+        .to_string();
+    if media_url.starts_with("data:") {
+        return Err(GenerationError::permanent(
+            "Inline data URLs are not supported; ask the API for a hosted URL.",
+        ));
+    }
+    download_media(&client, &media_url, output_type).await
+}
+
+/// Whether `{{env:NAME}}` may resolve `name`; see [`ENV_VAR_PREFIX`].
+pub fn env_var_allowed(name: &str) -> bool {
+    name.len() > ENV_VAR_PREFIX.len() && name.starts_with(ENV_VAR_PREFIX)
+}
+
+/// Every environment variable the config's templates reference, sorted, so
+/// the user can see what a provider reads before it runs.
+pub fn env_vars_read(config: &HttpProviderConfig) -> Vec<String> {
+    let mut texts: Vec<&str> = vec![config.request.url.as_str()];
+    texts.extend(config.request.headers.values().map(String::as_str));
+    texts.extend(config.health_url.as_deref());
+    let mut body_strings = Vec::new();
+    if let Some(body) = config.request.body.as_ref() {
+        collect_strings(body, &mut body_strings);
+    }
+    texts.extend(body_strings);
+
+    let mut vars: Vec<String> = texts
+        .into_iter()
+        .flat_map(placeholders)
+        .filter_map(|name| name.strip_prefix("env:").map(|var| var.trim().to_string()))
+        .collect();
+    vars.sort();
+    vars.dedup();
+    vars
+}
+
+fn collect_strings<'a>(value: &'a Value, out: &mut Vec<&'a str>) {
+    match value {
+        Value::String(text) => out.push(text),
+        Value::Array(items) => items.iter().for_each(|item| collect_strings(item, out)),
+        Value::Object(map) => map.values().for_each(|item| collect_strings(item, out)),
+        _ => {}
+    }
+}
+
+/// Trimmed names of the `{{...}}` placeholders in `text`.
+fn placeholders(text: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        names.push(rest[start + 2..start + 2 + len].trim());
+        rest = &rest[start + 2 + len + 2..];
+    }
+    names
+}
+
+/// Fill `{{name}}` and `{{env:NAME}}` placeholders in a body template. A string
+/// that is exactly one placeholder takes the input's JSON value (so numbers stay
+/// numbers); placeholders inside longer strings are spliced in as text.
+pub fn substitute_template(template: &Value, inputs: &HashMap<String, Value>) -> Value {
+    match template {
+        Value::String(text) => match whole_placeholder(text) {
+            Some(name) => lookup(name, inputs).unwrap_or(Value::Null),
+            None => Value::String(substitute_string(text, inputs)),
+        },
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| substitute_template(item, inputs))
+                .collect(),
+        ),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| (key.clone(), substitute_template(value, inputs)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// Splice placeholders into a string. Missing values become empty text.
+pub fn substitute_string(text: &str, inputs: &HashMap<String, Value>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        let name = rest[start + 2..start + 2 + len].trim();
+        if let Some(value) = lookup(name, inputs) {
+            out.push_str(&input_value_as_string(&value).unwrap_or_else(|| value.to_string()));
+        }
+        rest = &rest[start + 2 + len + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Walk a dotted path with optional indices, e.g. `data.images[0].url` or
+/// `output.0`.
+pub fn extract_json_path<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    let mut current = value;
+    for segment in path.split('.').filter(|segment| !segment.is_empty()) {
+        let (key, indices) = match segment.find('[') {
+            Some(pos) => (&segment[..pos], &segment[pos..]),
+            None => (segment, ""),
+        };
+        if !key.is_empty() {
+            current = match (current, key.parse::<usize>()) {
+                (Value::Array(items), Ok(index)) => items.get(index)?,
+                _ => current.get(key)?,
+            };
+        }
+        for index in indices.split('[').filter(|part| !part.is_empty()) {
+            let index = index.strip_suffix(']')?.trim().parse::<usize>().ok()?;
+            current = current.get(index)?;
+        }
+    }
+    Some(current)
+}
+
+fn whole_placeholder(text: &str) -> Option<&str> {
+    let inner = text.trim().strip_prefix("{{")?.strip_suffix("}}")?;
+    if inner.contains("{{") || inner.contains("}}") {
+        None
+    } else {
+        Some(inner.trim())
+    }
+}
+
+fn lookup(name: &str, inputs: &HashMap<String, Value>) -> Option<Value> {
+    match name.strip_prefix("env:") {
+        Some(var) => {
+            let var = var.trim();
+            if !env_var_allowed(var) {
+                return None;
+            }
+            std::env::var(var).ok().map(Value::String)
+        }
+        None => inputs.get(name).cloned(),
+    }
+}
+
+fn value_in(value: &str, candidates: &[String]) -> bool {
+    candidates
+        .iter()
+        .any(|candidate| candidate.eq_ignore_ascii_case(value))
+}
+
+fn url_origin(url: &str) -> Option<String> {
+    let (scheme, rest) = url.split_once("://")?;
+    let host = rest.split(['/', '?', '#']).next().filter(|host| !host.is_empty())?;
+    Some(format!("{}://{}/", scheme, host))
+}
+
+fn render_headers(
+    request: &HttpRequestTemplate,
+    inputs: &HashMap<String, Value>,
+) -> Vec<(String, String)> {
+    request
+        .headers
+        .iter()
+        .map(|(name, value)| (name.clone(), substitute_string(value, inputs)))
+        .collect()
+}
+
+fn with_headers(
+    mut builder: reqwest::RequestBuilder,
+    headers: &[(String, String)],
+) -> reqwest::RequestBuilder {
+    for (name, value) in headers {
+        builder = builder.header(name.as_str(), value.as_str());
+    }
+    builder
+}
+
+async fn submit_request(
+    client: &reqwest::Client,
+    request: &HttpRequestTemplate,
+    headers: &[(String, String)],
+    inputs: &HashMap<String, Value>,
+) -> Result<Value, GenerationError> {
+    let method = reqwest::Method::from_bytes(request.method.trim().to_uppercase().as_bytes())
+        .map_err(|_| format!("Invalid HTTP method: {}", request.method))?;
+    let url = substitute_string(&request.url, inputs);
+    let mut builder = with_headers(client.request(method, url), headers);
+    if let Some(body) = request.body.as_ref() {
+        builder = builder.json(&substitute_template(body, inputs));
+    }
+    let response = builder
+        .send()
+        .await
+        .map_err(|err| request_error("Failed to submit request", err))?;
+    read_json(response).await
+}
+
+async fn get_json(
+    client: &reqwest::Client,
+    url: &str,
+    headers: &[(String, String)],
+) -> Result<Value, GenerationError> {
+    let response = with_headers(client.get(url), headers)
+        .send()
+        .await
+        .map_err(|err| request_error("Failed to query job status", err))?;
+    read_json(response).await
+}
+
+async fn read_json(response: reqwest::Response) -> Result<Value, GenerationError> {
+    let status = response.status();
+    let text = response
+        .text()
+        .await
+        .map_err(|err| request_error("Failed to read response", err))?;
+    if !status.is_success() {
+        return Err(GenerationError::from_status(
+            status.as_u16(),
+            format!("Provider rejected request ({}): {}", status, text.trim()),
+        ));
+    }
+    serde_json::from_str(&text)
+        .map_err(|err| GenerationError::permanent(format!("Failed to parse response: {}", err)))
+}
+
+async fn download_media(
+    client: &reqwest::Client,
+    url: &str,
+    output_type: ProviderOutputType,
+) -> Result<HttpOutput, GenerationError> {
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|err| request_error("Failed to download output", err))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(GenerationError::from_status(
+            status.as_u16(),
+            format!("Provider output download failed: {}", status),
+        ));
+    }
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.split(';').next().unwrap_or(value).trim().to_string());
+    let bytes = response
+        .bytes()
+        .await
+        .map(|bytes| bytes.to_vec())
+        .map_err(|err| request_error("Failed to read output bytes", err))?;
+    Ok(HttpOutput {
+        bytes,
+        extension: media_extension(url, content_type.as_deref(), output_type),
+    })
+}
+
+/// Pick a file extension from the URL path, then the content type, then the
+/// output type's default.
+fn media_extension(url: &str, content_type: Option<&str>, output_type: ProviderOutputType) -> String {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    if output_matches_type(file_name, output_type) {
+        if let Some(ext) = Path::new(file_name).extension().and_then(|ext| ext.to_str()) {
+            return ext.to_ascii_lowercase();
+        }
+    }
+    content_type
+        .and_then(mime_guess::get_mime_extensions_str)
+        .and_then(|exts| {
+            exts.iter()
+                .find(|ext| output_matches_type(&format!("x.{}", ext), output_type))
+        })
+        .map(|ext| ext.to_string())
+        .unwrap_or_else(|| default_extension_for_output(output_type).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn inputs() -> HashMap<String, Value> {
+        HashMap::from([
+            ("prompt".to_string(), json!("a red fox")),
+            ("steps".to_string(), json!(30)),
+            ("seed".to_string(), json!(42)),
+        ])
+    }
+
+    #[test]
+    fn test_body_template_substitution() {
+        let template = json!({
+            "version": "abc123",
+            "input": {
+                "prompt": "{{prompt}}",
+                "num_inference_steps": "{{ steps }}",
+                "caption": "{{prompt}}, seed {{seed}}",
+                "negative": "{{negative}}",
+                "tags": ["{{prompt}}", 7]
+            }
+        });
+        let body = substitute_template(&template, &inputs());
+        assert_eq!(
+            body,
+            json!({
+                "version": "abc123",
+                "input": {
+                    "prompt": "a red fox",
+                    "num_inference_steps": 30,
+                    "caption": "a red fox, seed 42",
+                    "negative": null,
+                    "tags": ["a red fox", 7]
+                }
+            })
+        );
+        assert_eq!(
+            substitute_string("https://api.example.com/{{missing}}/run?s={{seed}}", &inputs()),
+            "https://api.example.com//run?s=42"
+        );
+        assert_eq!(substitute_string("Token {{unterminated", &inputs()), "Token {{unterminated");
+    }
+
+    #[test]
+    fn test_response_path_extraction() {
+        let payload = json!({
+            "status": "succeeded",
+            "output": ["https://cdn.example.com/out.png"],
+            "data": { "images": [{ "url": "https://cdn.example.com/a.webp" }] },
+            "urls": { "get": "https://api.example.com/predictions/1" }
+        });
+        let text = |path: &str| extract_json_path(&payload, path).and_then(Value::as_str);
+        assert_eq!(text("output[0]"), Some("https://cdn.example.com/out.png"));
+        assert_eq!(text("output.0"), Some("https://cdn.example.com/out.png"));
+        assert_eq!(text("data.images[0].url"), Some("https://cdn.example.com/a.webp"));
+        assert_eq!(text("urls.get"), Some("https://api.example.com/predictions/1"));
+        assert_eq!(text("output[1]"), None);
+        assert_eq!(text("data.missing.url"), None);
+    }
+
+    #[test]
+    fn test_media_extension_and_origin() {
+        assert_eq!(
+            media_extension("https://cdn.example.com/out.webp?sig=1", None, ProviderOutputType::Image),
+            "webp"
+        );
+        assert_eq!(
+            media_extension("https://cdn.example.com/file/123", Some("video/mp4"), ProviderOutputType::Video),
+            "mp4"
+        );
+        assert_eq!(
+            media_extension("https://cdn.example.com/file/123", None, ProviderOutputType::Image),
+            "png"
+        );
+        assert_eq!(
+            url_origin("https://api.replicate.com/v1/predictions").as_deref(),
+            Some("https://api.replicate.com/")
+        );
+        assert_eq!(url_origin("/relative/path"), None);
+    }
+
+    #[test]
+    fn test_http_manifest_builds_entry() {
+        let manifest: ProviderManifest = serde_json::from_value(json!({
+            "adapter_type": "http",
+            "schema_version": 1,
+            "name": "Replicate SDXL",
+            "output_type": "image",
+            "inputs": [
+                { "name": "prompt", "label": "Prompt", "input_type": { "type": "text" }, "required": true }
+            ],
+            "connection": {
+                "request": {
+                    "url": "https://api.replicate.com/v1/predictions",
+                    "headers": { "Authorization": "Bearer {{env:NLA_REPLICATE_API_TOKEN}}" },
+                    "body": { "input": { "prompt": "{{prompt}}" } }
+                },
+                "poll": { "url_path": "urls.get", "status_path": "status" },
+                "response_path": "output[0]"
+            }
+        }))
+        .expect("manifest parses");
+        let entry = entry_from_manifest(manifest).expect("http manifest");
+        assert_eq!(entry.name, "Replicate SDXL");
+        assert_eq!(entry.inputs.len(), 1);
+        let ProviderConnection::Http(config) = entry.connection else {
+            panic!("expected http connection");
+        };
+        assert_eq!(config.request.method, "POST");
+        let poll = config.poll.expect("poll config");
+        assert_eq!(poll.done_values, vec!["succeeded", "completed"]);
+        assert_eq!(poll.interval_ms, 1000);
+        assert_eq!(env_vars_read(&config), vec!["NLA_REPLICATE_API_TOKEN"]);
+    }
+
+    #[test]
+    fn test_env_placeholders_need_the_prefix() {
+        std::env::set_var("NLA_TEST_HTTP_TOKEN", "allowed");
+        std::env::set_var("TEST_HTTP_SECRET", "secret");
+        assert_eq!(substitute_string("Bearer {{env:NLA_TEST_HTTP_TOKEN}}", &inputs()), "Bearer allowed");
+        // Anything without the prefix resolves to nothing
+        assert_eq!(substitute_string("Bearer {{env:TEST_HTTP_SECRET}}", &inputs()), "Bearer ");
+        assert_eq!(
+            substitute_template(&json!({ "key": "{{env:TEST_HTTP_SECRET}}" }), &inputs()),
+            json!({ "key": null })
+        );
+        assert!(!env_var_allowed("NLA_"));
+        assert!(!env_var_allowed("PATH"));
+
+        let config: HttpProviderConfig = serde_json::from_value(json!({
+            "request": {
+                "url": "https://{{env:NLA_HOST}}/run?k={{ env:AWS_SECRET_ACCESS_KEY }}",
+                "headers": { "X-Key": "{{env:NLA_KEY}}" },
+                "body": { "nested": ["{{prompt}}", { "token": "{{env:NLA_KEY}}" }] }
+            },
+            "response_path": "output",
+            "health_url": "https://{{env:NLA_HOST}}/health"
+        }))
+        .unwrap();
+        // Every referenced variable is listed, including ones that won't resolve
+        assert_eq!(env_vars_read(&config), vec!["AWS_SECRET_ACCESS_KEY", "NLA_HOST", "NLA_KEY"]);
+    }
+}
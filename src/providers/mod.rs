@@ -1 +1,19 @@
 pub mod comfyui;
+pub mod http;
+pub mod health;
+pub mod env_approvals;
+
+use crate::core::generation::GenerationError;
+
+/// Classify a failed request by its error kind: connection failures, timeouts
+/// and interrupted bodies are worth retrying, malformed responses are not.
+pub(crate) fn request_error(context: &str, err: reqwest::Error) -> GenerationError {
+    let message = format!("{}: {}", context, err);
+    match err.status() {
+        Some(status) => GenerationError::from_status(status.as_u16(), message),
+        None if err.is_connect() || err.is_timeout() || err.is_request() || err.is_body() => {
+            GenerationError::transient(message)
+        }
+        None => GenerationError::permanent(message),
+    }
+}
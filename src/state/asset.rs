@@ -2,10 +2,14 @@
 //!
 //! Assets represent content in the project - both imported files and generative assets.
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::path::PathBuf;
 use uuid::Uuid;
 
+use super::TrackType;
+
 /// The kind of asset - either a simple file reference or a generative asset
 /// The kind of asset - either a simple file reference or a generative asset
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -88,6 +92,93 @@ impl AssetKind {
     }
 }
 
+/// Kind of asset an imported media file becomes, decided by its extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportKind {
+    Video,
+    Audio,
+    Image,
+}
+
+impl ImportKind {
+    /// `None` for file types the project can't import
+    pub fn from_path(path: &std::path::Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_lowercase();
+        match ext.as_str() {
+            "mp4" | "mov" | "avi" | "mkv" | "webm" => Some(ImportKind::Video),
+            "mp3" | "wav" | "ogg" | "flac" => Some(ImportKind::Audio),
+            "png" | "jpg" | "jpeg" | "gif" | "webp" => Some(ImportKind::Image),
+            _ => None,
+        }
+    }
+
+    /// Project subfolder imported files of this kind are copied into
+    pub fn subfolder(self) -> &'static str {
+        match self {
+            ImportKind::Video => "video",
+            ImportKind::Audio => "audio",
+            ImportKind::Image => "images",
+        }
+    }
+
+    pub fn is_visual(self) -> bool {
+        matches!(self, ImportKind::Video | ImportKind::Image)
+    }
+
+    /// Whether files of this kind can be placed on a track of `track_type`
+    pub fn fits_track(self, track_type: TrackType) -> bool {
+        match track_type {
+            TrackType::Video => self.is_visual(),
+            TrackType::Audio => self == ImportKind::Audio,
+            TrackType::Marker => false,
+        }
+    }
+}
+
+/// A problem that keeps an asset from being used as-is
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type")]
+pub enum AssetIssue {
+    /// The video codec can't be decoded; a transcode can make it usable
+    UnsupportedCodec {
+        /// Codec name reported by FFmpeg
+        codec: String,
+    },
+    /// A transcode to a supported format is running
+    Transcoding,
+    /// The asset can't be used (unreadable source or failed transcode)
+    Offline {
+        /// User-facing reason
+        reason: String,
+    },
+    /// The source file isn't where the project expects it; relinking points
+    /// the asset at its new location
+    Missing,
+}
+
+impl AssetIssue {
+    /// Short label for asset lists.
+    pub fn label(&self) -> String {
+        match self {
+            AssetIssue::UnsupportedCodec { codec } => format!("Unsupported codec ({})", codec),
+            AssetIssue::Transcoding => "Transcoding...".to_string(),
+            AssetIssue::Offline { reason } => format!("Offline: {}", reason),
+            AssetIssue::Missing => "Missing file".to_string(),
+        }
+    }
+}
+
+/// A downscaled copy of a video asset, used for the preview instead of the
+/// full-resolution original
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AssetProxy {
+    /// Path relative to project root
+    pub path: PathBuf,
+    /// Pixel size of the proxy
+    pub width: u32,
+    pub height: u32,
+}
+
 /// An asset in the project
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Asset {
@@ -100,6 +191,25 @@ pub struct Asset {
     pub duration_seconds: Option<f64>,
     /// The type and location of this asset
     pub kind: AssetKind,
+    /// Set when the asset can't be decoded or is offline
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issue: Option<AssetIssue>,
+    /// Pixel width of the source media, once probed (image and video files only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_width: Option<u32>,
+    /// Pixel height of the source media, once probed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_height: Option<u32>,
+    /// Low-resolution copy for smoother editing, once generated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<AssetProxy>,
+    /// Preview from the proxy while the project's proxies are turned on
+    #[serde(default)]
+    pub use_proxy: bool,
+    /// When the asset was imported or created; `None` for assets saved
+    /// before this was recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
 }
 
 #[allow(dead_code)]
@@ -110,6 +220,12 @@ impl Asset {
             id: Uuid::new_v4(),
             name: name.into(),
             duration_seconds: None,
+            issue: None,
+            source_width: None,
+            source_height: None,
+            proxy: None,
+            use_proxy: false,
+            created_at: Some(Utc::now()),
             kind: AssetKind::Video { path },
         }
     }
@@ -120,6 +236,12 @@ impl Asset {
             id: Uuid::new_v4(),
             name: name.into(),
             duration_seconds: None,
+            issue: None,
+            source_width: None,
+            source_height: None,
+            proxy: None,
+            use_proxy: false,
+            created_at: Some(Utc::now()),
             kind: AssetKind::Image { path },
         }
     }
@@ -130,6 +252,12 @@ impl Asset {
             id: Uuid::new_v4(),
             name: name.into(),
             duration_seconds: None,
+            issue: None,
+            source_width: None,
+            source_height: None,
+            proxy: None,
+            use_proxy: false,
+            created_at: Some(Utc::now()),
             kind: AssetKind::Audio { path },
         }
     }
@@ -146,6 +274,12 @@ impl Asset {
             id: Uuid::new_v4(),
             name: name.into(),
             duration_seconds,
+            issue: None,
+            source_width: None,
+            source_height: None,
+            proxy: None,
+            use_proxy: false,
+            created_at: Some(Utc::now()),
             kind: AssetKind::GenerativeVideo {
                 folder,
                 active_version: None,
@@ -161,6 +295,12 @@ impl Asset {
             id: Uuid::new_v4(),
             name: name.into(),
             duration_seconds: None,
+            issue: None,
+            source_width: None,
+            source_height: None,
+            proxy: None,
+            use_proxy: false,
+            created_at: Some(Utc::now()),
             kind: AssetKind::GenerativeImage {
                 folder,
                 active_version: None,
@@ -174,6 +314,12 @@ impl Asset {
             id: Uuid::new_v4(),
             name: name.into(),
             duration_seconds: None,
+            issue: None,
+            source_width: None,
+            source_height: None,
+            proxy: None,
+            use_proxy: false,
+            created_at: Some(Utc::now()),
             kind: AssetKind::GenerativeAudio {
                 folder,
                 active_version: None,
@@ -201,6 +347,26 @@ impl Asset {
         self.kind.is_visual()
     }
 
+    /// The media file or generative folder this asset points at, as stored
+    /// (relative to the project root unless it lives outside the project).
+    pub fn stored_path(&self) -> &std::path::Path {
+        match &self.kind {
+            AssetKind::Video { path } | AssetKind::Image { path } | AssetKind::Audio { path } => path,
+            AssetKind::GenerativeVideo { folder, .. }
+            | AssetKind::GenerativeImage { folder, .. }
+            | AssetKind::GenerativeAudio { folder, .. } => folder,
+        }
+    }
+
+    /// Probed source size in pixels. Generative assets never cache one since
+    /// each version can differ.
+    pub fn source_dimensions(&self) -> Option<(u32, u32)> {
+        match (self.source_width, self.source_height) {
+            (Some(width), Some(height)) if width > 0 && height > 0 => Some((width, height)),
+            _ => None,
+        }
+    }
+
     /// Check if this asset can be placed on an audio track
     pub fn is_audio(&self) -> bool {
         self.kind.is_audio()
@@ -220,6 +386,26 @@ impl Asset {
     pub fn set_duration_seconds(&mut self, duration_seconds: Option<f64>) {
         self.duration_seconds = duration_seconds;
     }
+
+    /// Check if this asset is marked offline
+    pub fn is_offline(&self) -> bool {
+        matches!(self.issue, Some(AssetIssue::Offline { .. }))
+    }
+
+    /// Check if this asset's source file was not found
+    pub fn is_missing(&self) -> bool {
+        matches!(self.issue, Some(AssetIssue::Missing))
+    }
+
+    /// The media file of an imported (non-generative) asset, as stored.
+    pub fn source_file(&self) -> Option<&std::path::Path> {
+        match &self.kind {
+            AssetKind::Video { path } | AssetKind::Image { path } | AssetKind::Audio { path } => {
+                Some(path)
+            }
+            _ => None,
+        }
+    }
 }
 
 pub const DEFAULT_GENERATIVE_VIDEO_FPS: f64 = 16.0;
@@ -250,10 +436,96 @@ pub fn asset_display_name(asset: &Asset) -> String {
     asset.name.clone()
 }
 
+/// Order of the assets panel list. Every order is ascending, and assets that
+/// compare equal keep their project order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetSort {
+    /// Display name, case-insensitive
+    Name,
+    /// Oldest first, which is also project order
+    #[default]
+    DateAdded,
+    /// Imported video, image, audio, then generative video, image, audio
+    Type,
+    /// Shortest first; assets without a duration (images) go last
+    Duration,
+}
+
+impl AssetSort {
+    pub const ALL: [AssetSort; 4] = [Self::Name, Self::DateAdded, Self::Type, Self::Duration];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::DateAdded => "date_added",
+            Self::Type => "type",
+            Self::Duration => "duration",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|value| value.key() == key)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Name => "Name",
+            Self::DateAdded => "Date Added",
+            Self::Type => "Type",
+            Self::Duration => "Duration",
+        }
+    }
+
+    pub fn compare(self, a: &Asset, b: &Asset) -> Ordering {
+        match self {
+            Self::Name => asset_display_name(a)
+                .to_lowercase()
+                .cmp(&asset_display_name(b).to_lowercase()),
+            // Undated assets predate the field, so they sort first
+            Self::DateAdded => a.created_at.cmp(&b.created_at),
+            Self::Type => kind_rank(&a.kind).cmp(&kind_rank(&b.kind)),
+            Self::Duration => match (a.duration_seconds, b.duration_seconds) {
+                (Some(a), Some(b)) => a.total_cmp(&b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            },
+        }
+    }
+}
+
+fn kind_rank(kind: &AssetKind) -> u8 {
+    match kind {
+        AssetKind::Video { .. } => 0,
+        AssetKind::Image { .. } => 1,
+        AssetKind::Audio { .. } => 2,
+        AssetKind::GenerativeVideo { .. } => 3,
+        AssetKind::GenerativeImage { .. } => 4,
+        AssetKind::GenerativeAudio { .. } => 5,
+    }
+}
+
+/// Stable sort of `assets` by `sort`.
+pub fn sort_assets(assets: &mut [&Asset], sort: AssetSort) {
+    assets.sort_by(|a, b| sort.compare(a, b));
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_import_kind_fits_track() {
+        assert!(ImportKind::Video.fits_track(TrackType::Video));
+        assert!(ImportKind::Image.fits_track(TrackType::Video));
+        assert!(!ImportKind::Audio.fits_track(TrackType::Video));
+        assert!(ImportKind::Audio.fits_track(TrackType::Audio));
+        assert!(!ImportKind::Video.fits_track(TrackType::Audio));
+        assert!(!ImportKind::Image.fits_track(TrackType::Audio));
+        assert!(!ImportKind::Audio.fits_track(TrackType::Marker));
+    }
+
     #[test]
     fn test_asset_creation() {
         let video = Asset::new_video("My Video", PathBuf::from("video/clip.mp4"));
@@ -280,4 +552,91 @@ mod tests {
         assert_eq!(asset.id, parsed.id);
         assert_eq!(asset.name, parsed.name);
     }
+
+    #[test]
+    fn test_asset_issue_serialization() {
+        let mut asset = Asset::new_video("Clip", PathBuf::from("video/clip.mkv"));
+        let json = serde_json::to_string(&asset).unwrap();
+        assert!(!json.contains("issue"));
+
+        asset.issue = Some(AssetIssue::UnsupportedCodec {
+            codec: "prores".to_string(),
+        });
+        let json = serde_json::to_string(&asset).unwrap();
+        let parsed: Asset = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.issue, asset.issue);
+        assert!(!parsed.is_offline());
+    }
+
+    fn names(assets: &[&Asset]) -> Vec<String> {
+        assets.iter().map(|asset| asset.name.clone()).collect()
+    }
+
+    #[test]
+    fn test_asset_sort_by_name_and_type() {
+        let video = Asset::new_video("clip b", PathBuf::from("video/b.mp4"));
+        let image = Asset::new_image("Clip A", PathBuf::from("images/a.png"));
+        let audio = Asset::new_audio("clip b", PathBuf::from("audio/b.wav"));
+        let gen_image = Asset::new_generative_image("Backdrop", PathBuf::from("generated/image/a"));
+
+        let mut assets = vec![&gen_image, &video, &image, &audio];
+        sort_assets(&mut assets, AssetSort::Name);
+        // Case-insensitive; the two "clip b" keep their original order
+        assert_eq!(names(&assets), vec!["Backdrop", "Clip A", "clip b", "clip b"]);
+        assert_eq!(assets[2].id, video.id);
+        assert_eq!(assets[3].id, audio.id);
+
+        let mut assets = vec![&gen_image, &audio, &image, &video];
+        sort_assets(&mut assets, AssetSort::Type);
+        let ids: Vec<Uuid> = assets.iter().map(|asset| asset.id).collect();
+        assert_eq!(ids, vec![video.id, image.id, audio.id, gen_image.id]);
+    }
+
+    #[test]
+    fn test_asset_sort_by_date_added() {
+        let mut first = Asset::new_video("First", PathBuf::from("video/a.mp4"));
+        let mut second = Asset::new_video("Second", PathBuf::from("video/b.mp4"));
+        let mut legacy_a = Asset::new_video("Legacy A", PathBuf::from("video/c.mp4"));
+        let mut legacy_b = Asset::new_video("Legacy B", PathBuf::from("video/d.mp4"));
+        let base = Utc::now();
+        first.created_at = Some(base);
+        second.created_at = Some(base + chrono::Duration::seconds(5));
+        legacy_a.created_at = None;
+        legacy_b.created_at = None;
+
+        let mut assets = vec![&second, &legacy_a, &first, &legacy_b];
+        sort_assets(&mut assets, AssetSort::DateAdded);
+        assert_eq!(names(&assets), vec!["Legacy A", "Legacy B", "First", "Second"]);
+
+        // Older projects load without the field
+        let mut json = serde_json::to_value(&first).unwrap();
+        json.as_object_mut().unwrap().remove("created_at");
+        let parsed: Asset = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.created_at, None);
+    }
+
+    #[test]
+    fn test_asset_sort_by_duration() {
+        let mut long = Asset::new_video("Long", PathBuf::from("video/a.mp4"));
+        let mut short = Asset::new_audio("Short", PathBuf::from("audio/a.wav"));
+        let mut tie = Asset::new_video("Tie", PathBuf::from("video/b.mp4"));
+        let still = Asset::new_image("Still", PathBuf::from("images/a.png"));
+        let hollow = Asset::new_generative_image("Hollow", PathBuf::from("generated/image/a"));
+        long.duration_seconds = Some(30.0);
+        short.duration_seconds = Some(2.5);
+        tie.duration_seconds = Some(2.5);
+
+        let mut assets = vec![&still, &long, &tie, &hollow, &short];
+        sort_assets(&mut assets, AssetSort::Duration);
+        assert_eq!(names(&assets), vec!["Tie", "Short", "Long", "Still", "Hollow"]);
+    }
+
+    #[test]
+    fn test_asset_sort_keys_round_trip() {
+        for sort in AssetSort::ALL {
+            assert_eq!(AssetSort::from_key(sort.key()), Some(sort));
+        }
+        assert_eq!(AssetSort::from_key("size"), None);
+        assert_eq!(AssetSort::default(), AssetSort::DateAdded);
+    }
 }
@@ -0,0 +1,387 @@
+#![allow(dead_code)]
+
+use serde::{Deserialize, Serialize};
+
+/// Output container for an export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportContainer {
+    #[default]
+    Mp4,
+    Mov,
+    Webm,
+}
+
+impl ExportContainer {
+    pub const ALL: [ExportContainer; 3] = [Self::Mp4, Self::Mov, Self::Webm];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Mp4 => "mp4",
+            Self::Mov => "mov",
+            Self::Webm => "webm",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|value| value.key() == key)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Mp4 => "MP4",
+            Self::Mov => "QuickTime (MOV)",
+            Self::Webm => "WebM",
+        }
+    }
+
+    /// File extension without the dot.
+    pub fn extension(self) -> &'static str {
+        self.key()
+    }
+}
+
+/// Video encoder for an export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportVideoCodec {
+    #[default]
+    H264,
+    H265,
+    Vp9,
+    ProRes,
+}
+
+impl ExportVideoCodec {
+    pub const ALL: [ExportVideoCodec; 4] = [Self::H264, Self::H265, Self::Vp9, Self::ProRes];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::H264 => "h264",
+            Self::H265 => "h265",
+            Self::Vp9 => "vp9",
+            Self::ProRes => "prores",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|value| value.key() == key)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::H264 => "H.264",
+            Self::H265 => "H.265 / HEVC",
+            Self::Vp9 => "VP9",
+            Self::ProRes => "ProRes 422",
+        }
+    }
+
+    fn encoder(self) -> &'static str {
+        match self {
+            Self::H264 => "libx264",
+            Self::H265 => "libx265",
+            Self::Vp9 => "libvpx-vp9",
+            Self::ProRes => "prores_ks",
+        }
+    }
+
+    fn pixel_format(self) -> &'static str {
+        match self {
+            Self::ProRes => "yuv422p10le",
+            _ => "yuv420p",
+        }
+    }
+
+    /// Highest CRF the encoder accepts, or `None` when it has no CRF mode.
+    pub fn max_crf(self) -> Option<u8> {
+        match self {
+            Self::H264 | Self::H265 => Some(51),
+            Self::Vp9 => Some(63),
+            Self::ProRes => None,
+        }
+    }
+}
+
+/// Audio encoder for an export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportAudioCodec {
+    #[default]
+    Aac,
+    Opus,
+    Pcm,
+}
+
+impl ExportAudioCodec {
+    pub const ALL: [ExportAudioCodec; 3] = [Self::Aac, Self::Opus, Self::Pcm];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Aac => "aac",
+            Self::Opus => "opus",
+            Self::Pcm => "pcm",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|value| value.key() == key)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Aac => "AAC",
+            Self::Opus => "Opus",
+            Self::Pcm => "PCM (uncompressed)",
+        }
+    }
+
+    fn encoder(self) -> &'static str {
+        match self {
+            Self::Aac => "aac",
+            Self::Opus => "libopus",
+            Self::Pcm => "pcm_s16le",
+        }
+    }
+
+    /// PCM has no bitrate setting.
+    pub fn uses_bitrate(self) -> bool {
+        !matches!(self, Self::Pcm)
+    }
+}
+
+/// How the video encoder trades size for quality.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "mode", content = "value", rename_all = "lowercase")]
+pub enum ExportRateControl {
+    /// Constant quality; lower is better.
+    Crf(u8),
+    /// Target average bitrate in kbit/s.
+    Bitrate(u32),
+}
+
+impl Default for ExportRateControl {
+    fn default() -> Self {
+        Self::Crf(20)
+    }
+}
+
+/// Encoder configuration for a timeline export, saved with the project.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportOptions {
+    pub container: ExportContainer,
+    pub video_codec: ExportVideoCodec,
+    pub rate_control: ExportRateControl,
+    pub audio_codec: ExportAudioCodec,
+    /// Audio bitrate in kbit/s (ignored for PCM).
+    pub audio_bitrate_kbps: u32,
+}
+
+impl Default for ExportOptions {
+    /// Widely compatible H.264/AAC MP4.
+    fn default() -> Self {
+        Self {
+            container: ExportContainer::Mp4,
+            video_codec: ExportVideoCodec::H264,
+            rate_control: ExportRateControl::default(),
+            audio_codec: ExportAudioCodec::Aac,
+            audio_bitrate_kbps: 192,
+        }
+    }
+}
+
+impl ExportOptions {
+    /// Check that the container, codecs, and rates work together.
+    pub fn validate(&self) -> Result<(), String> {
+        match self.container {
+            ExportContainer::Webm => {
+                if self.video_codec != ExportVideoCodec::Vp9 {
+                    return Err("WebM exports need the VP9 video codec.".to_string());
+                }
+                if self.audio_codec != ExportAudioCodec::Opus {
+                    return Err("WebM exports need the Opus audio codec.".to_string());
+                }
+            }
+            ExportContainer::Mp4 => {
+                if self.video_codec == ExportVideoCodec::ProRes {
+                    return Err("ProRes needs a MOV container.".to_string());
+                }
+                if self.video_codec == ExportVideoCodec::Vp9 {
+                    return Err("VP9 needs a WebM container.".to_string());
+                }
+                if self.audio_codec == ExportAudioCodec::Pcm {
+                    return Err("PCM audio needs a MOV container.".to_string());
+                }
+            }
+            ExportContainer::Mov => {
+                if self.video_codec == ExportVideoCodec::Vp9 {
+                    return Err("VP9 needs a WebM container.".to_string());
+                }
+            }
+        }
+        if self.audio_codec == ExportAudioCodec::Opus && self.container != ExportContainer::Webm {
+            return Err("Opus audio needs a WebM container.".to_string());
+        }
+
+        match (self.rate_control, self.video_codec.max_crf()) {
+            (ExportRateControl::Crf(crf), Some(max)) if crf > max => {
+                return Err(format!("CRF for {} must be 0-{}.", self.video_codec.label(), max));
+            }
+            (ExportRateControl::Bitrate(0), _) => {
+                return Err("Video bitrate must be greater than zero.".to_string());
+            }
+            _ => {}
+        }
+        if self.audio_codec.uses_bitrate() && self.audio_bitrate_kbps == 0 {
+            return Err("Audio bitrate must be greater than zero.".to_string());
+        }
+        Ok(())
+    }
+
+    /// Encoder flags for ffmpeg, placed after the inputs and before the output path.
+    pub fn ffmpeg_codec_args(&self) -> Vec<String> {
+        let mut args: Vec<String> = vec![
+            "-c:v".into(),
+            self.video_codec.encoder().into(),
+            "-pix_fmt".into(),
+            self.video_codec.pixel_format().into(),
+        ];
+
+        match self.video_codec {
+            // ProRes quality is set by profile, not rate control.
+            ExportVideoCodec::ProRes => {
+                args.extend(["-profile:v".into(), "2".into()]);
+            }
+            _ => match self.rate_control {
+                ExportRateControl::Crf(crf) => {
+                    args.extend(["-crf".into(), crf.to_string()]);
+                    // libvpx only honours CRF as constant quality with a zero bitrate.
+                    if self.video_codec == ExportVideoCodec::Vp9 {
+                        args.extend(["-b:v".into(), "0".into()]);
+                    }
+                }
+                ExportRateControl::Bitrate(kbps) => {
+                    args.extend(["-b:v".into(), format!("{}k", kbps)]);
+                }
+            },
+        }
+
+        if self.video_codec == ExportVideoCodec::H265 {
+            // Lets Apple players recognise HEVC in MP4/MOV.
+            args.extend(["-tag:v".into(), "hvc1".into()]);
+        }
+
+        args.extend(["-c:a".into(), self.audio_codec.encoder().into()]);
+        if self.audio_codec.uses_bitrate() {
+            args.extend(["-b:a".into(), format!("{}k", self.audio_bitrate_kbps)]);
+        }
+
+        if self.container == ExportContainer::Mp4 {
+            args.extend(["-movflags".into(), "+faststart".into()]);
+        }
+        args
+    }
+}
+
+/// A named set of export options.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExportPreset {
+    pub name: &'static str,
+    pub options: ExportOptions,
+}
+
+/// Built-in presets; the first one is the default.
+pub fn export_presets() -> Vec<ExportPreset> {
+    vec![
+        ExportPreset {
+            name: "H.264 MP4 (Default)",
+            options: ExportOptions::default(),
+        },
+        ExportPreset {
+            name: "YouTube 1080p",
+            options: ExportOptions {
+                rate_control: ExportRateControl::Bitrate(12_000),
+                audio_bitrate_kbps: 384,
+                ..ExportOptions::default()
+            },
+        },
+        ExportPreset {
+            name: "Instagram",
+            options: ExportOptions {
+                rate_control: ExportRateControl::Bitrate(3_500),
+                audio_bitrate_kbps: 128,
+                ..ExportOptions::default()
+            },
+        },
+        ExportPreset {
+            name: "ProRes 422 (Editing)",
+            options: ExportOptions {
+                container: ExportContainer::Mov,
+                video_codec: ExportVideoCodec::ProRes,
+                rate_control: ExportRateControl::default(),
+                audio_codec: ExportAudioCodec::Pcm,
+                audio_bitrate_kbps: 0,
+            },
+        },
+        ExportPreset {
+            name: "Web (VP9 WebM)",
+            options: ExportOptions {
+                container: ExportContainer::Webm,
+                video_codec: ExportVideoCodec::Vp9,
+                rate_control: ExportRateControl::Crf(32),
+                audio_codec: ExportAudioCodec::Opus,
+                audio_bitrate_kbps: 128,
+            },
+        },
+    ]
+}
+
+/// Name of the preset matching `options` exactly, if any.
+pub fn matching_export_preset(options: &ExportOptions) -> Option<&'static str> {
+    export_presets()
+        .into_iter()
+        .find(|preset| preset.options == *options)
+        .map(|preset| preset.name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets_are_valid() {
+        for preset in export_presets() {
+            assert!(preset.options.validate().is_ok(), "{}", preset.name);
+        }
+    }
+
+    #[test]
+    fn test_webm_requires_vp9_opus() {
+        let options = ExportOptions {
+            container: ExportContainer::Webm,
+            ..ExportOptions::default()
+        };
+        assert!(options.validate().is_err());
+
+        let options = ExportOptions {
+            container: ExportContainer::Webm,
+            video_codec: ExportVideoCodec::Vp9,
+            audio_codec: ExportAudioCodec::Opus,
+            ..ExportOptions::default()
+        };
+        assert!(options.validate().is_ok());
+    }
+
+    #[test]
+    fn test_default_ffmpeg_args() {
+        let args = ExportOptions::default().ffmpeg_codec_args();
+        assert_eq!(
+            args,
+            vec![
+                "-c:v", "libx264", "-pix_fmt", "yuv420p", "-crf", "20", "-c:a", "aac", "-b:a",
+                "192k", "-movflags", "+faststart",
+            ]
+        );
+    }
+}
@@ -19,12 +19,14 @@ pub enum InputValue {
 }
 
 /// Strategy for adjusting seeds across batch generations.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum SeedStrategy {
     Keep,
     Increment,
     Random,
+    /// Use these seeds in order, starting over when the batch is longer.
+    FixedList(Vec<i64>),
 }
 
 impl Default for SeedStrategy {
@@ -39,16 +41,54 @@ impl SeedStrategy {
             SeedStrategy::Keep => "keep",
             SeedStrategy::Increment => "increment",
             SeedStrategy::Random => "random",
+            SeedStrategy::FixedList(_) => "fixed_list",
         }
     }
 
+    /// "fixed_list" starts with no seeds; callers carry over any existing list.
     pub fn from_str(value: &str) -> Self {
         match value.trim().to_ascii_lowercase().as_str() {
             "keep" => SeedStrategy::Keep,
             "random" => SeedStrategy::Random,
+            "fixed_list" => SeedStrategy::FixedList(Vec::new()),
             _ => SeedStrategy::Increment,
         }
     }
+
+    /// The strategy a batch actually runs: an empty fixed list increments.
+    pub fn resolved(&self) -> SeedStrategy {
+        match self {
+            SeedStrategy::FixedList(seeds) if seeds.is_empty() => SeedStrategy::Increment,
+            strategy => strategy.clone(),
+        }
+    }
+
+    /// Seed for the `index`-th job of a batch starting at `seed_base`, or
+    /// `None` to keep the configured value.
+    pub fn seed_at(&self, seed_base: Option<i64>, index: u32, random: impl FnOnce() -> i64) -> Option<i64> {
+        match self {
+            SeedStrategy::Keep => None,
+            SeedStrategy::Random => Some(random()),
+            SeedStrategy::FixedList(seeds) if !seeds.is_empty() => {
+                Some(seeds[index as usize % seeds.len()])
+            }
+            SeedStrategy::Increment | SeedStrategy::FixedList(_) => {
+                Some(seed_base.unwrap_or(0) + index as i64)
+            }
+        }
+    }
+}
+
+/// Seeds typed as a comma or whitespace separated list; anything that isn't
+/// an integer is dropped.
+pub fn parse_seed_list(text: &str) -> Vec<i64> {
+    text.split(|c: char| c == ',' || c.is_whitespace())
+        .filter_map(|part| part.trim().parse::<i64>().ok())
+        .collect()
+}
+
+pub fn format_seed_list(seeds: &[i64]) -> String {
+    seeds.iter().map(|seed| seed.to_string()).collect::<Vec<_>>().join(", ")
 }
 
 /// Batch generation settings stored per generative asset.
@@ -60,6 +100,9 @@ pub struct BatchSettings {
     pub seed_strategy: SeedStrategy,
     #[serde(default)]
     pub seed_field: Option<String>,
+    /// Numeric inputs drawn at random per job, see `ProviderInputField::randomization_bounds`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub randomized_inputs: Vec<String>,
 }
 
 impl Default for BatchSettings {
@@ -68,6 +111,7 @@ impl Default for BatchSettings {
             count: default_batch_count(),
             seed_strategy: SeedStrategy::default(),
             seed_field: None,
+            randomized_inputs: Vec::new(),
         }
     }
 }
@@ -83,6 +127,12 @@ pub struct GenerationRecord {
     pub timestamp: DateTime<Utc>,
     pub provider_id: Uuid,
     pub inputs_snapshot: HashMap<String, InputValue>,
+    /// Position of this version within a multi-output run (0-based).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_index: Option<u32>,
+    /// Number of outputs the run produced; set only when it was more than one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_count: Option<u32>,
 }
 
 /// Persistent config stored in `generated/.../config.json`.
@@ -254,6 +304,89 @@ pub fn next_generative_index(
     max_index + 1
 }
 
+/// Which pinned version an A/B comparison is showing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareSide {
+    A,
+    B,
+}
+
+/// A/B comparison between two versions of one generative asset. While it
+/// runs, the asset's active version follows the shown side; finishing hands
+/// back the version that was active before so it can be restored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VersionCompare {
+    pub asset_id: Uuid,
+    pub a: Option<String>,
+    pub b: Option<String>,
+    pub showing: CompareSide,
+    restore_version: Option<String>,
+}
+
+impl VersionCompare {
+    /// Start comparing with the active version pinned as A.
+    pub fn new(asset_id: Uuid, active_version: Option<String>) -> Self {
+        Self {
+            asset_id,
+            a: active_version.clone(),
+            b: None,
+            showing: CompareSide::A,
+            restore_version: active_version,
+        }
+    }
+
+    /// Pin `version` as A and show it.
+    pub fn pin_a(&mut self, version: &str) -> Option<&str> {
+        self.a = Some(version.to_string());
+        self.showing = CompareSide::A;
+        self.shown_version()
+    }
+
+    /// Pin `version` as B and show it.
+    pub fn pin_b(&mut self, version: &str) -> Option<&str> {
+        self.b = Some(version.to_string());
+        self.showing = CompareSide::B;
+        self.shown_version()
+    }
+
+    /// Both sides are pinned to different versions.
+    pub fn is_ready(&self) -> bool {
+        matches!((&self.a, &self.b), (Some(a), Some(b)) if a != b)
+    }
+
+    pub fn shown_version(&self) -> Option<&str> {
+        match self.showing {
+            CompareSide::A => self.a.as_deref(),
+            CompareSide::B => self.b.as_deref(),
+        }
+    }
+
+    /// Show the other side. Does nothing until both sides are pinned.
+    pub fn toggle(&mut self) -> Option<&str> {
+        if self.is_ready() {
+            self.showing = match self.showing {
+                CompareSide::A => CompareSide::B,
+                CompareSide::B => CompareSide::A,
+            };
+        }
+        self.shown_version()
+    }
+
+    /// Exchange the pins, keeping the same version on screen.
+    pub fn swap(&mut self) {
+        std::mem::swap(&mut self.a, &mut self.b);
+        self.showing = match self.showing {
+            CompareSide::A => CompareSide::B,
+            CompareSide::B => CompareSide::A,
+        };
+    }
+
+    /// The version to make active again once the comparison ends.
+    pub fn finish(self) -> Option<String> {
+        self.restore_version
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GenerationJobStatus {
     Queued,
@@ -275,6 +408,8 @@ pub struct GenerationJob {
     pub output_type: ProviderOutputType,
     pub asset_id: Uuid,
     pub clip_id: Uuid,
+    /// Shared by jobs queued together for several selected clips.
+    pub batch_id: Option<Uuid>,
     pub asset_label: String,
     pub folder_path: PathBuf,
     pub inputs: HashMap<String, serde_json::Value>,
@@ -282,3 +417,52 @@ pub struct GenerationJob {
     pub version: Option<String>,
     pub error: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_compare_pins_toggles_and_swaps() {
+        let mut compare = VersionCompare::new(Uuid::new_v4(), Some("v3".to_string()));
+        assert_eq!(compare.a.as_deref(), Some("v3"));
+        assert!(!compare.is_ready());
+        // Toggling before B is pinned keeps showing A
+        assert_eq!(compare.toggle(), Some("v3"));
+        assert_eq!(compare.showing, CompareSide::A);
+
+        assert_eq!(compare.pin_b("v5"), Some("v5"));
+        assert!(compare.is_ready());
+        assert_eq!(compare.toggle(), Some("v3"));
+        assert_eq!(compare.toggle(), Some("v5"));
+
+        // Swapping exchanges the labels but not what is on screen
+        compare.swap();
+        assert_eq!((compare.a.as_deref(), compare.b.as_deref()), (Some("v5"), Some("v3")));
+        assert_eq!(compare.showing, CompareSide::A);
+        assert_eq!(compare.shown_version(), Some("v5"));
+
+        assert_eq!(compare.pin_a("v1"), Some("v1"));
+        assert_eq!(compare.toggle(), Some("v3"));
+        // Pinning the same version on both sides isn't a comparison
+        compare.pin_b("v1");
+        assert!(!compare.is_ready());
+        assert_eq!(compare.toggle(), Some("v1"));
+    }
+
+    #[test]
+    fn test_version_compare_restores_active_version() {
+        let mut compare = VersionCompare::new(Uuid::new_v4(), Some("v2".to_string()));
+        compare.pin_a("v4");
+        compare.pin_b("v7");
+        compare.toggle();
+        compare.swap();
+        assert_eq!(compare.finish(), Some("v2".to_string()));
+
+        // An asset without an active version goes back to none
+        let mut compare = VersionCompare::new(Uuid::new_v4(), None);
+        compare.pin_a("v1");
+        compare.pin_b("v2");
+        assert_eq!(compare.finish(), None);
+    }
+}
@@ -0,0 +1,278 @@
+//! Undo/redo history for timeline edits.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use super::{Clip, Marker, Project, Track};
+
+/// Default number of undo steps kept in memory.
+pub const DEFAULT_HISTORY_CAPACITY: usize = 100;
+/// Edits sharing a coalesce key within this window collapse into one step.
+const COALESCE_WINDOW: Duration = Duration::from_millis(750);
+
+/// The undoable part of a project: tracks, clips, and markers.
+///
+/// Assets and generative configs are left out so undoing a clip edit never
+/// rolls back a generation that finished in the meantime.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectSnapshot {
+    tracks: Vec<Track>,
+    clips: Vec<Clip>,
+    markers: Vec<Marker>,
+}
+
+impl ProjectSnapshot {
+    pub fn capture(project: &Project) -> Self {
+        Self {
+            tracks: project.tracks.clone(),
+            clips: project.clips.clone(),
+            markers: project.markers.clone(),
+        }
+    }
+
+    fn restore(self, project: &mut Project) {
+        project.tracks = self.tracks;
+        project.clips = self.clips;
+        project.markers = self.markers;
+        // Assets deleted since the snapshot can't come back; drop their clips.
+        // Placeholders have no asset and always stay.
+        let assets = &project.assets;
+        project.clips.retain(|clip| {
+            clip.placeholder || assets.iter().any(|asset| asset.id == clip.asset_id)
+        });
+    }
+}
+
+/// Bounded undo/redo stacks of project snapshots.
+#[derive(Debug, Clone)]
+pub struct History {
+    undo_stack: VecDeque<ProjectSnapshot>,
+    redo_stack: Vec<ProjectSnapshot>,
+    capacity: usize,
+    last_key: Option<String>,
+    last_push_at: Option<Instant>,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_CAPACITY)
+    }
+}
+
+impl History {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            undo_stack: VecDeque::new(),
+            redo_stack: Vec::new(),
+            capacity: capacity.max(1),
+            last_key: None,
+            last_push_at: None,
+        }
+    }
+
+    /// Record the project state before an edit.
+    pub fn push(&mut self, project: &Project) {
+        self.push_coalesced(project, None);
+    }
+
+    /// Record the project state before an edit, merging with the previous step
+    /// when `key` matches it and the edits arrive in quick succession (e.g. a
+    /// clip drag or a scrubbed field).
+    pub fn push_coalesced(&mut self, project: &Project, key: Option<String>) {
+        let now = Instant::now();
+        let coalesce = key.is_some()
+            && key == self.last_key
+            && self
+                .last_push_at
+                .is_some_and(|last| now.duration_since(last) < COALESCE_WINDOW);
+        self.last_push_at = Some(now);
+        // Pushed right before the edit, so this is where it gets counted
+        project.mark_edited();
+        if coalesce {
+            return;
+        }
+        self.last_key = key;
+        self.record(ProjectSnapshot::capture(project));
+    }
+
+    /// Record a snapshot taken before an edit that might not have changed
+    /// anything. Call it only once the edit did, so no-op edits leave no step.
+    pub fn push_snapshot(&mut self, snapshot: ProjectSnapshot, project: &Project) {
+        project.mark_edited();
+        self.break_coalescing();
+        self.record(snapshot);
+    }
+
+    fn record(&mut self, snapshot: ProjectSnapshot) {
+        self.undo_stack.push_back(snapshot);
+        while self.undo_stack.len() > self.capacity {
+            self.undo_stack.pop_front();
+        }
+        self.redo_stack.clear();
+    }
+
+    /// Step back one edit. Returns false when there is nothing to undo.
+    pub fn undo(&mut self, project: &mut Project) -> bool {
+        let Some(snapshot) = self.undo_stack.pop_back() else {
+            return false;
+        };
+        self.redo_stack.push(ProjectSnapshot::capture(project));
+        snapshot.restore(project);
+        project.mark_edited();
+        self.break_coalescing();
+        true
+    }
+
+    /// Re-apply the last undone edit. Returns false when there is nothing to redo.
+    pub fn redo(&mut self, project: &mut Project) -> bool {
+        let Some(snapshot) = self.redo_stack.pop() else {
+            return false;
+        };
+        self.undo_stack.push_back(ProjectSnapshot::capture(project));
+        snapshot.restore(project);
+        project.mark_edited();
+        self.break_coalescing();
+        true
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Forget all steps (e.g. after opening a different project).
+    pub fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.break_coalescing();
+    }
+
+    fn break_coalescing(&mut self) {
+        self.last_key = None;
+        self.last_push_at = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::Asset;
+    use std::path::PathBuf;
+    use uuid::Uuid;
+
+    fn project_with_clip() -> (Project, Uuid) {
+        let mut project = Project::default();
+        let track_id = project.tracks[0].id;
+        let asset_id = project.add_asset(Asset::new_video("clip", PathBuf::from("video/clip.mp4")));
+        let clip_id = project.add_clip(Clip::new(asset_id, track_id, 0.0, 2.0));
+        (project, clip_id)
+    }
+
+    #[test]
+    fn test_undo_clip_delete_and_redo() {
+        let (mut project, clip_id) = project_with_clip();
+        let mut history = History::default();
+
+        history.push(&project);
+        project.remove_clip(clip_id);
+        assert!(project.clips.is_empty());
+
+        assert!(history.undo(&mut project));
+        assert!(project.clips.iter().any(|clip| clip.id == clip_id));
+        assert!(history.can_redo());
+
+        assert!(history.redo(&mut project));
+        assert!(project.clips.is_empty());
+        assert!(!history.redo(&mut project));
+    }
+
+    #[test]
+    fn test_undo_skips_clips_of_deleted_assets() {
+        let (mut project, clip_id) = project_with_clip();
+        let asset_id = project.clips[0].asset_id;
+        let mut history = History::default();
+        history.push(&project);
+        project.move_clip(clip_id, 4.0);
+        project.remove_asset(asset_id);
+
+        assert!(history.undo(&mut project));
+        assert!(project.clips.is_empty());
+    }
+
+    #[test]
+    fn test_undo_keeps_placeholders() {
+        let (mut project, clip_id) = project_with_clip();
+        let track_id = project.tracks[0].id;
+        let placeholder_id = project
+            .add_placeholder(track_id, 5.0, 2.0, Some("B-roll".to_string()))
+            .unwrap();
+        let mut history = History::default();
+        history.push(&project);
+        project.move_clip(clip_id, 8.0);
+
+        assert!(history.undo(&mut project));
+        assert!(project.clips.iter().any(|clip| clip.id == placeholder_id));
+        assert!(history.redo(&mut project));
+        assert!(project.clips.iter().any(|clip| clip.id == placeholder_id));
+    }
+
+    #[test]
+    fn test_snapshot_pushed_after_the_edit() {
+        let (mut project, clip_id) = project_with_clip();
+        let mut history = History::default();
+        let before = ProjectSnapshot::capture(&project);
+        project.remove_clip(clip_id);
+        history.push_snapshot(before, &project);
+
+        assert!(history.undo(&mut project));
+        assert!(project.clips.iter().any(|clip| clip.id == clip_id));
+    }
+
+    #[test]
+    fn test_coalesced_edits_share_one_step() {
+        let (mut project, clip_id) = project_with_clip();
+        let mut history = History::default();
+        let key = Some(format!("move:{}", clip_id));
+        for step in 1..=200 {
+            history.push_coalesced(&project, key.clone());
+            project.move_clip(clip_id, step as f64 * 0.01);
+        }
+
+        assert!(history.undo(&mut project));
+        assert_eq!(project.clips[0].start_time, 0.0);
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn test_capacity_drops_oldest() {
+        let (mut project, clip_id) = project_with_clip();
+        let mut history = History::new(3);
+        for step in 1..=5 {
+            history.push(&project);
+            project.move_clip(clip_id, step as f64);
+        }
+        let mut undone = 0;
+        while history.undo(&mut project) {
+            undone += 1;
+        }
+        assert_eq!(undone, 3);
+        assert_eq!(project.clips[0].start_time, 2.0);
+    }
+
+    #[test]
+    fn test_new_edit_clears_redo() {
+        let (mut project, clip_id) = project_with_clip();
+        let mut history = History::default();
+        history.push(&project);
+        project.move_clip(clip_id, 1.0);
+        history.undo(&mut project);
+        assert!(history.can_redo());
+
+        history.push(&project);
+        project.move_clip(clip_id, 3.0);
+        assert!(!history.can_redo());
+    }
+}
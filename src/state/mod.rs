@@ -6,12 +6,16 @@
 //! - Clip: Media clips placed on tracks
 //! - Asset: Project assets (imported files and generative assets)
 //! - Marker: Point-in-time annotations
+//! - ExportOptions: Encoder settings and presets for timeline export
+//! - History: Undo/redo snapshots of timeline edits
 
 mod project;
 mod asset;
 mod selection;
 mod providers;
 mod generative;
+mod export;
+mod history;
 
 pub use project::*;
 pub use asset::*;
@@ -20,3 +24,6 @@ pub use selection::*;
 pub use providers::*;
 #[allow(unused_imports)]
 pub use generative::*;
+#[allow(unused_imports)]
+pub use export::*;
+pub use history::*;
@@ -0,0 +1,236 @@
+//! Re-placing a track's clips after an edit.
+
+use uuid::Uuid;
+
+use super::Clip;
+
+/// How a track reacts when its clips are inserted, moved, resized or removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArrangeMode {
+    /// Clips stay exactly where they were put.
+    #[default]
+    Free,
+    /// Clips are packed end to end from the start of the track, in start
+    /// order, so the track never has gaps or overlaps.
+    Magnetic,
+}
+
+/// How `resolve_overlaps` separates clips that overlap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverlapResolution {
+    /// Push later clips down the track until they start where the earlier
+    /// one ends.
+    Ripple,
+    /// Shorten earlier clips so they end where the later one starts.
+    Trim,
+}
+
+/// Clips closer than this count as touching, not overlapping.
+const OVERLAP_EPSILON: f64 = 1e-6;
+
+/// Pairs of clips (all on one track) whose time ranges overlap, the one that
+/// starts first listed first. Clips that only share an edge don't overlap.
+pub fn find_overlaps(clips: &[&Clip]) -> Vec<(Uuid, Uuid)> {
+    let mut sorted: Vec<&Clip> = clips.to_vec();
+    sorted.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+    let mut overlaps = Vec::new();
+    for (index, earlier) in sorted.iter().enumerate() {
+        for later in &sorted[index + 1..] {
+            if later.start_time >= earlier.end_time() - OVERLAP_EPSILON {
+                break;
+            }
+            overlaps.push((earlier.id, later.id));
+        }
+    }
+    overlaps
+}
+
+/// Separate overlapping `clips` (all on one track). Trimming falls back to a
+/// ripple for a clip that starts together with the next one, since trimming
+/// it would leave nothing. Returns true if any clip changed.
+pub fn resolve_overlaps(clips: &mut [&mut Clip], resolution: OverlapResolution) -> bool {
+    let mut order: Vec<usize> = (0..clips.len()).collect();
+    order.sort_by(|&a, &b| clips[a].start_time.total_cmp(&clips[b].start_time));
+    let mut changed = false;
+    for position in 1..order.len() {
+        let later = order[position];
+        // Everything before `later` is already separated, so only the
+        // clip right before it can reach into it
+        let earlier = order[position - 1];
+        let earlier_end = clips[earlier].end_time();
+        if clips[later].start_time >= earlier_end - OVERLAP_EPSILON {
+            continue;
+        }
+        let trimmed = clips[later].start_time - clips[earlier].start_time;
+        if resolution == OverlapResolution::Trim && trimmed > OVERLAP_EPSILON {
+            clips[earlier].duration = trimmed;
+        } else {
+            clips[later].start_time = earlier_end;
+        }
+        changed = true;
+    }
+    changed
+}
+
+/// Re-place `clips` (all on one track) after an edit to the `changed` ones.
+///
+/// In magnetic mode a changed clip sorts ahead of an unchanged one with the
+/// same start, and one dropped inside another clip lands right after it, so
+/// an insertion pushes everything after it down the track. Free mode leaves
+/// the clips alone. Returns true if any clip moved.
+pub fn arrange_track(clips: &mut [&mut Clip], changed: &[Uuid], mode: ArrangeMode) -> bool {
+    if mode == ArrangeMode::Free {
+        return false;
+    }
+    let mut order: Vec<usize> = (0..clips.len()).collect();
+    order.sort_by(|&a, &b| {
+        clips[a]
+            .start_time
+            .total_cmp(&clips[b].start_time)
+            .then_with(|| changed.contains(&clips[b].id).cmp(&changed.contains(&clips[a].id)))
+    });
+    let mut cursor = 0.0;
+    let mut moved = false;
+    for index in order {
+        let clip = &mut clips[index];
+        if (clip.start_time - cursor).abs() > 1e-9 {
+            clip.start_time = cursor;
+            moved = true;
+        }
+        cursor += clip.duration.max(0.0);
+    }
+    moved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::Project;
+
+    fn starts(project: &Project, track_id: Uuid) -> Vec<f64> {
+        let mut starts: Vec<f64> = project
+            .clips_on_track(track_id)
+            .iter()
+            .map(|clip| clip.start_time)
+            .collect();
+        starts.sort_by(|a, b| a.total_cmp(b));
+        starts
+    }
+
+    fn clip_at(start: f64, duration: f64) -> Clip {
+        Clip::new(Uuid::new_v4(), Uuid::new_v4(), start, duration)
+    }
+
+    #[test]
+    fn test_find_overlaps() {
+        let a = clip_at(0.0, 2.0);
+        let b = clip_at(1.5, 2.0);
+        // Touches b's end exactly: no overlap
+        let c = clip_at(3.5, 1.0);
+        // Fully inside d
+        let d = clip_at(10.0, 5.0);
+        let e = clip_at(11.0, 1.0);
+        let overlaps = find_overlaps(&[&c, &e, &a, &d, &b]);
+        assert_eq!(overlaps, vec![(a.id, b.id), (d.id, e.id)]);
+
+        assert!(find_overlaps(&[&a, &clip_at(2.0, 1.0)]).is_empty());
+        assert!(find_overlaps(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_resolve_overlaps_ripples_or_trims() {
+        let mut clips = vec![clip_at(0.0, 2.0), clip_at(1.5, 2.0), clip_at(10.0, 5.0), clip_at(11.0, 1.0)];
+        let mut refs: Vec<&mut Clip> = clips.iter_mut().collect();
+        assert!(resolve_overlaps(&mut refs, OverlapResolution::Ripple));
+        let starts: Vec<f64> = clips.iter().map(|clip| clip.start_time).collect();
+        assert_eq!(starts, vec![0.0, 2.0, 10.0, 15.0]);
+
+        let mut clips = vec![clip_at(0.0, 2.0), clip_at(1.5, 2.0), clip_at(10.0, 5.0), clip_at(10.0, 1.0)];
+        let mut refs: Vec<&mut Clip> = clips.iter_mut().collect();
+        assert!(resolve_overlaps(&mut refs, OverlapResolution::Trim));
+        assert_eq!(clips[0].duration, 1.5);
+        assert_eq!(clips[1].start_time, 1.5);
+        // Clips starting together can't be trimmed apart, so the later one moves
+        assert_eq!((clips[2].duration, clips[3].start_time), (5.0, 15.0));
+        let refs: Vec<&Clip> = clips.iter().collect();
+        assert!(find_overlaps(&refs).is_empty());
+
+        let mut refs: Vec<&mut Clip> = clips.iter_mut().collect();
+        assert!(!resolve_overlaps(&mut refs, OverlapResolution::Trim));
+    }
+
+    #[test]
+    fn test_magnetic_insert_pushes_later_clips() {
+        let mut project = Project::default();
+        project.settings.magnetic_timeline = true;
+        let track_id = project.tracks[0].id;
+        let first = project.add_clip(Clip::new(Uuid::new_v4(), track_id, 0.0, 2.0));
+        let second = project.add_clip(Clip::new(Uuid::new_v4(), track_id, 2.0, 3.0));
+
+        // Dropped inside the first clip: it lands after it and the second moves down
+        let inserted = project.add_clip(Clip::new(Uuid::new_v4(), track_id, 1.0, 1.5));
+        assert!(project.arrange_tracks(&[track_id], &[inserted]));
+        let start_of = |id: Uuid| project.clips.iter().find(|clip| clip.id == id).unwrap().start_time;
+        assert_eq!(start_of(first), 0.0);
+        assert_eq!(start_of(inserted), 2.0);
+        assert_eq!(start_of(second), 3.5);
+
+        // Dropped on an existing start, the new clip goes first
+        let mut clips: Vec<Clip> = vec![
+            Clip::new(Uuid::new_v4(), track_id, 0.0, 1.0),
+            Clip::new(Uuid::new_v4(), track_id, 4.0, 1.0),
+        ];
+        let dropped = Clip::new(Uuid::new_v4(), track_id, 0.0, 2.0);
+        let dropped_id = dropped.id;
+        clips.push(dropped);
+        let mut refs: Vec<&mut Clip> = clips.iter_mut().collect();
+        assert!(arrange_track(&mut refs, &[dropped_id], ArrangeMode::Magnetic));
+        let placed: Vec<f64> = clips.iter().map(|clip| clip.start_time).collect();
+        assert_eq!(placed, vec![2.0, 3.0, 0.0]);
+    }
+
+    #[test]
+    fn test_placeholders_ripple_like_clips() {
+        let mut project = Project::default();
+        project.settings.magnetic_timeline = true;
+        let track_id = project.tracks[0].id;
+        let first = project.add_clip(Clip::new(Uuid::new_v4(), track_id, 0.0, 2.0));
+        let later = project.add_clip(Clip::new(Uuid::new_v4(), track_id, 2.0, 1.0));
+
+        // A placeholder dropped at the seam pushes the later clip down
+        let placeholder = project.add_placeholder(track_id, 2.0, 4.0, None).unwrap();
+        assert!(project.arrange_tracks(&[track_id], &[placeholder]));
+        assert_eq!(starts(&project, track_id), vec![0.0, 2.0, 6.0]);
+
+        // and overlaps with it are found and resolved like any other
+        project.clips.iter_mut().find(|clip| clip.id == first).unwrap().duration = 3.0;
+        let refs: Vec<&Clip> = project.clips_on_track(track_id);
+        assert_eq!(find_overlaps(&refs), vec![(first, placeholder)]);
+        let mut clips: Vec<&mut Clip> = project.clips.iter_mut().collect();
+        assert!(resolve_overlaps(&mut clips, OverlapResolution::Ripple));
+        assert_eq!(starts(&project, track_id), vec![0.0, 3.0, 7.0]);
+        let later_start = project.clips.iter().find(|clip| clip.id == later).unwrap().start_time;
+        assert_eq!(later_start, 7.0);
+    }
+
+    #[test]
+    fn test_magnetic_delete_closes_gap_and_free_mode_leaves_it() {
+        for magnetic in [true, false] {
+            let mut project = Project::default();
+            project.settings.magnetic_timeline = magnetic;
+            let track_id = project.tracks[0].id;
+            let other_track = project.tracks[1].id;
+            project.add_clip(Clip::new(Uuid::new_v4(), track_id, 0.0, 2.0));
+            let middle = project.add_clip(Clip::new(Uuid::new_v4(), track_id, 2.0, 1.0));
+            project.add_clip(Clip::new(Uuid::new_v4(), track_id, 3.0, 2.0));
+            project.add_clip(Clip::new(Uuid::new_v4(), other_track, 6.0, 1.0));
+
+            assert!(project.remove_clip(middle));
+            assert_eq!(project.arrange_tracks(&[track_id], &[]), magnetic);
+            let expected = if magnetic { vec![0.0, 2.0] } else { vec![0.0, 3.0] };
+            assert_eq!(starts(&project, track_id), expected);
+            // Other tracks are never touched
+            assert_eq!(starts(&project, other_track), vec![6.0]);
+        }
+    }
+}
@@ -1,6 +1,62 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+/// Smallest scale factor a clip transform may use.
+pub const MIN_CLIP_SCALE: f32 = 0.01;
+/// Largest scale factor a clip transform may use; anything past this is a typo.
+pub const MAX_CLIP_SCALE: f32 = 100.0;
+/// Slowest playback rate a clip may use.
+pub const MIN_CLIP_SPEED: f64 = 0.1;
+/// Fastest playback rate a clip may use.
+pub const MAX_CLIP_SPEED: f64 = 16.0;
+
+/// How a visual clip's pixels combine with the layers below it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BlendMode {
+    #[default]
+    Normal,
+    Add,
+    Multiply,
+    Screen,
+    Overlay,
+}
+
+impl BlendMode {
+    pub const ALL: [BlendMode; 5] = [
+        BlendMode::Normal,
+        BlendMode::Add,
+        BlendMode::Multiply,
+        BlendMode::Screen,
+        BlendMode::Overlay,
+    ];
+
+    /// Stable identifier, matching the serialized form.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BlendMode::Normal => "normal",
+            BlendMode::Add => "add",
+            BlendMode::Multiply => "multiply",
+            BlendMode::Screen => "screen",
+            BlendMode::Overlay => "overlay",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            BlendMode::Normal => "Normal",
+            BlendMode::Add => "Add",
+            BlendMode::Multiply => "Multiply",
+            BlendMode::Screen => "Screen",
+            BlendMode::Overlay => "Overlay",
+        }
+    }
+
+    pub fn from_id(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|mode| mode.as_str() == value)
+    }
+}
+
 /// Transform controls for a visual clip.
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct ClipTransform {
@@ -16,6 +72,48 @@ pub struct ClipTransform {
     pub rotation_deg: f32,
     /// Opacity from 0.0 (transparent) to 1.0 (opaque).
     pub opacity: f32,
+    /// Blend mode used when compositing over lower tracks.
+    #[serde(default)]
+    pub blend_mode: BlendMode,
+}
+
+/// How a source is sized against the project frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameFit {
+    /// Whole source visible, letterboxed on the short axis.
+    Fit,
+    /// Frame fully covered, cropping the long axis.
+    Fill,
+}
+
+/// Uniform scale that fits or fills a `source` (width, height) inside the
+/// project `frame`. Scale 1.0 shows the source at its native pixel size.
+pub fn frame_fit_scale(source: (u32, u32), frame: (u32, u32), mode: FrameFit) -> Option<f32> {
+    if source.0 == 0 || source.1 == 0 || frame.0 == 0 || frame.1 == 0 {
+        return None;
+    }
+    let ratio_x = frame.0 as f32 / source.0 as f32;
+    let ratio_y = frame.1 as f32 / source.1 as f32;
+    let scale = match mode {
+        FrameFit::Fit => ratio_x.min(ratio_y),
+        FrameFit::Fill => ratio_x.max(ratio_y),
+    };
+    Some(scale.clamp(MIN_CLIP_SCALE, MAX_CLIP_SCALE))
+}
+
+impl ClipTransform {
+    /// Centered copy of this transform scaled to fit or fill the frame.
+    /// Rotation, opacity, and blend mode are kept.
+    pub fn fitted_to_frame(self, source: (u32, u32), frame: (u32, u32), mode: FrameFit) -> Option<Self> {
+        let scale = frame_fit_scale(source, frame, mode)?;
+        Some(Self {
+            position_x: 0.0,
+            position_y: 0.0,
+            scale_x: scale,
+            scale_y: scale,
+            ..self
+        })
+    }
 }
 
 impl Default for ClipTransform {
@@ -27,6 +125,7 @@ impl Default for ClipTransform {
             scale_y: 1.0,
             rotation_deg: 0.0,
             opacity: 1.0,
+            blend_mode: BlendMode::Normal,
         }
     }
 }
@@ -56,6 +155,47 @@ pub struct Clip {
     /// Transform applied when compositing this clip.
     #[serde(default)]
     pub transform: ClipTransform,
+    /// Source time (seconds) held for the whole clip; `None` plays normally.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub freeze_at: Option<f64>,
+    /// Play the trimmed source backward over the clip's duration.
+    #[serde(default)]
+    pub reversed: bool,
+    /// Playback rate: 2.0 plays the source twice as fast, covering twice as
+    /// much of it in the same timeline duration.
+    #[serde(default = "default_speed")]
+    pub speed: f64,
+    /// Reserves time on the track without media, e.g. for a generation that
+    /// hasn't finished. Placeholders have no asset and never composite or play.
+    #[serde(default)]
+    pub placeholder: bool,
+    /// Repeat the source from the trim point to fill the clip's duration.
+    #[serde(default)]
+    pub loop_to_duration: bool,
+    /// Source seconds in one loop cycle, starting at `trim_in_seconds`.
+    #[serde(default)]
+    pub loop_source_seconds: f64,
+    /// Fade-in length in seconds (opacity for visuals, gain for audio).
+    #[serde(default)]
+    pub fade_in_seconds: f64,
+    /// Fade-out length in seconds.
+    #[serde(default)]
+    pub fade_out_seconds: f64,
+    /// Scale playback and the waveform so the loudest peak in the trimmed range
+    /// hits the normalize target.
+    #[serde(default)]
+    pub normalize: bool,
+    /// Clips sharing a link group move, trim, and delete together (e.g. a
+    /// video clip and its audio).
+    #[serde(default)]
+    pub link_group: Option<Uuid>,
+    /// Clips sharing a group are selected and moved as one block. Unlike links,
+    /// trims and deletes stay per clip.
+    #[serde(default)]
+    pub group_id: Option<Uuid>,
+    /// Timeline color for this clip; `None` follows the track type.
+    #[serde(default)]
+    pub color: Option<[u8; 3]>,
 }
 
 impl Clip {
@@ -72,6 +212,50 @@ impl Clip {
             volume: 1.0,
             label: None,
             transform: ClipTransform::default(),
+            freeze_at: None,
+            reversed: false,
+            speed: 1.0,
+            placeholder: false,
+            loop_to_duration: false,
+            loop_source_seconds: 0.0,
+            fade_in_seconds: 0.0,
+            fade_out_seconds: 0.0,
+            normalize: false,
+            link_group: None,
+            group_id: None,
+            color: None,
+        }
+    }
+
+    /// A placeholder reserving `duration` seconds on `track_id`.
+    pub fn placeholder(track_id: Uuid, start_time: f64, duration: f64, label: Option<String>) -> Self {
+        let mut clip = Self::new(Uuid::nil(), track_id, start_time, duration);
+        clip.placeholder = true;
+        clip.label = label.filter(|label| !label.trim().is_empty());
+        clip
+    }
+
+    /// Name shown on a placeholder, or `None` for clips with media.
+    pub fn placeholder_label(&self) -> Option<String> {
+        if !self.placeholder {
+            return None;
+        }
+        Some(
+            self.label
+                .as_deref()
+                .map(str::trim)
+                .filter(|label| !label.is_empty())
+                .unwrap_or("Placeholder")
+                .to_string(),
+        )
+    }
+
+    /// CSS color the clip is drawn in: its own color when set, otherwise
+    /// `type_color`, the default for its track type.
+    pub fn resolved_color(&self, type_color: &str) -> String {
+        match self.color {
+            Some([r, g, b]) => format!("#{:02x}{:02x}{:02x}", r, g, b),
+            None => type_color.to_string(),
         }
     }
 
@@ -80,6 +264,102 @@ impl Clip {
         self.start_time + self.duration
     }
 
+    /// Playback rate, kept within `MIN_CLIP_SPEED..=MAX_CLIP_SPEED`.
+    pub fn playback_speed(&self) -> f64 {
+        if self.speed.is_finite() {
+            self.speed.clamp(MIN_CLIP_SPEED, MAX_CLIP_SPEED)
+        } else {
+            1.0
+        }
+    }
+
+    /// Source seconds in one loop cycle, or `None` when the clip doesn't loop.
+    pub fn loop_length(&self) -> Option<f64> {
+        (self.loop_to_duration && self.loop_source_seconds > 0.0).then_some(self.loop_source_seconds)
+    }
+
+    /// Seconds of source media the clip plays through. A looping clip only
+    /// reads one cycle, however many times it repeats.
+    pub fn source_duration(&self) -> f64 {
+        let played = self.duration.max(0.0) * self.playback_speed();
+        match self.loop_length() {
+            Some(cycle) => played.min(cycle),
+            None => played,
+        }
+    }
+
+    /// Timeline seconds it takes to play `source_seconds` of media.
+    pub fn timeline_duration_for_source(&self, source_seconds: f64) -> f64 {
+        source_seconds / self.playback_speed()
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.freeze_at.is_some()
+    }
+
+    /// Source media time (seconds) shown at `timeline_time`.
+    ///
+    /// The clip plays `trim_in_seconds..trim_in_seconds + source_duration()`.
+    /// Forward clips start at the trim point; reversed clips start at the end
+    /// of that range and walk back at the same speed. Frozen clips ignore the
+    /// timeline position. Looping clips wrap the offset into one cycle, so the
+    /// last cycle may stop partway through.
+    pub fn source_time_at(&self, timeline_time: f64) -> f64 {
+        if let Some(freeze_at) = self.freeze_at {
+            return freeze_at.max(0.0);
+        }
+        let local_time = (timeline_time - self.start_time).clamp(0.0, self.duration.max(0.0));
+        let mut source_offset = local_time * self.playback_speed();
+        if let Some(cycle) = self.loop_length() {
+            source_offset = source_offset.rem_euclid(cycle);
+        }
+        if self.reversed {
+            return (self.trim_in_seconds + self.source_duration() - source_offset).max(0.0);
+        }
+        (self.trim_in_seconds + source_offset).max(0.0)
+    }
+
+    /// Fade lengths clamped so they never add up to more than the clip. The
+    /// fade-in wins when a resize leaves too little room for both.
+    pub fn clamped_fades(&self) -> (f64, f64) {
+        let duration = self.duration.max(0.0);
+        let fade_in = self.fade_in_seconds.clamp(0.0, duration);
+        let fade_out = self.fade_out_seconds.clamp(0.0, duration - fade_in);
+        (fade_in, fade_out)
+    }
+
+    /// Set the fade-in, leaving room for the existing fade-out.
+    pub fn set_fade_in(&mut self, seconds: f64) {
+        let max = (self.duration - self.clamped_fades().1).max(0.0);
+        self.fade_in_seconds = seconds.clamp(0.0, max);
+    }
+
+    /// Set the fade-out, leaving room for the existing fade-in.
+    pub fn set_fade_out(&mut self, seconds: f64) {
+        let max = (self.duration - self.clamped_fades().0).max(0.0);
+        self.fade_out_seconds = seconds.clamp(0.0, max);
+    }
+
+    /// Fade multiplier (0.0..=1.0) at `timeline_time`.
+    pub fn fade_gain_at(&self, timeline_time: f64) -> f32 {
+        let (fade_in, fade_out) = self.clamped_fades();
+        let mut gain = 1.0_f64;
+        if fade_in > 0.0 {
+            gain = gain.min((timeline_time - self.start_time) / fade_in);
+        }
+        if fade_out > 0.0 {
+            gain = gain.min((self.end_time() - timeline_time) / fade_out);
+        }
+        gain.clamp(0.0, 1.0) as f32
+    }
+
+    /// The clip's transform with fades applied to its opacity.
+    pub fn transform_at(&self, timeline_time: f64) -> ClipTransform {
+        let mut transform = self.transform;
+        transform.opacity *= self.fade_gain_at(timeline_time);
+        transform
+    }
+
     /// Check if this clip overlaps with a time range
     #[allow(dead_code)]
     pub fn overlaps(&self, start: f64, end: f64) -> bool {
@@ -90,3 +370,7 @@ impl Clip {
 fn default_volume() -> f32 {
     1.0
 }
+
+fn default_speed() -> f64 {
+    1.0
+}
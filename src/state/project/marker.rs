@@ -9,10 +9,13 @@ pub struct Marker {
     /// Time position in seconds
     pub time: f64,
     /// Optional label
+    #[serde(default)]
     pub label: Option<String>,
     /// Optional description
+    #[serde(default)]
     pub description: Option<String>,
     /// Optional color (hex string, e.g., "#f97316")
+    #[serde(default)]
     pub color: Option<String>,
 }
 
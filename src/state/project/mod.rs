@@ -5,12 +5,18 @@
 mod project;
 mod track;
 mod clip;
+mod arrange;
 mod marker;
 mod settings;
 mod persistence;
 
 pub use project::Project;
-pub use track::{Track, TrackType};
-pub use clip::{Clip, ClipTransform};
+pub use track::{default_waveform_color, Track, TrackType};
+pub use clip::{
+    frame_fit_scale, BlendMode, Clip, ClipTransform, FrameFit, MAX_CLIP_SCALE, MAX_CLIP_SPEED,
+    MIN_CLIP_SCALE, MIN_CLIP_SPEED,
+};
+pub use arrange::{arrange_track, find_overlaps, resolve_overlaps, ArrangeMode, OverlapResolution};
 pub use marker::Marker;
-pub use settings::ProjectSettings;
+pub use settings::{PreviewGuides, ProjectSettings, ResampleQuality, TimeFormat};
+pub use persistence::{portable_path, read_generative_configs, resolve_project_path};
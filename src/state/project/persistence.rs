@@ -1,9 +1,10 @@
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs;
 use std::io;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
-use crate::state::{Asset, AssetKind, GenerativeConfig};
+use crate::state::{Asset, AssetIssue, AssetKind, GenerativeConfig, ImportKind, InputValue};
 use super::{Project, ProjectSettings};
 
 impl Project {
@@ -33,22 +34,52 @@ impl Project {
         fs::create_dir_all(folder.join("generated/audio"))?;
         fs::create_dir_all(folder.join("exports"))?;
 
-        // Write project.json
-        let json = serde_json::to_string_pretty(self)?;
+        // Write project.json, with paths inside the folder stored relative to it
+        let json = match self.with_portable_paths(folder) {
+            Some(portable) => serde_json::to_string_pretty(&portable)?,
+            None => serde_json::to_string_pretty(self)?,
+        };
         fs::write(folder.join("project.json"), json)?;
         self.save_generative_configs()?;
+        if self.project_path.as_deref() == Some(folder) {
+            self.saved_revision.set(Some(self.revision.get()));
+        }
 
         Ok(())
     }
 
+    /// Whether the project was edited since it was last saved or loaded. A
+    /// project that was never saved or loaded has nothing to lose.
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.saved_revision
+            .get()
+            .is_some_and(|saved| saved != self.revision.get())
+    }
+
+    /// Count an edit towards [`Project::has_unsaved_changes`]. The editing
+    /// methods call this themselves; code that assigns fields directly, and
+    /// every undo step, has to call it too.
+    pub fn mark_edited(&self) {
+        self.revision.set(self.revision.get().wrapping_add(1));
+    }
+
     /// Load a project from a folder
     pub fn load(folder: &Path) -> io::Result<Self> {
+        let mut project = Self::load_manifest(folder)?;
+        project.load_generative_configs();
+        Ok(project)
+    }
+
+    /// Load only `project.json`, leaving generative configs for the caller to stream in
+    /// with [`read_generative_configs`] and [`Project::apply_generative_configs`].
+    pub fn load_manifest(folder: &Path) -> io::Result<Self> {
         let project_file = folder.join("project.json");
         let json = fs::read_to_string(&project_file)?;
         let mut project: Project = serde_json::from_str(&json)?;
         project.project_path = Some(folder.to_path_buf());
-        project.load_generative_configs();
         project.ensure_generative_video_durations();
+        project.flag_missing_sources();
+        project.saved_revision.set(Some(project.revision.get()));
         Ok(project)
     }
 
@@ -83,6 +114,197 @@ impl Project {
         Ok(())
     }
 
+    /// Copy the whole project folder (media, generative folders, caches) to
+    /// `folder` and save this project there as `name`. Absolute paths that
+    /// pointed into the old folder are rewritten to the new one. Returns the
+    /// copy; `self` keeps pointing at the original. `on_progress` gets
+    /// (bytes copied, total bytes).
+    pub fn duplicate_to(
+        &self,
+        folder: &Path,
+        name: impl Into<String>,
+        on_progress: impl FnMut(u64, u64),
+    ) -> io::Result<Self> {
+        let old_root = self.project_path.clone().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, "Project path not set")
+        })?;
+        copy_project_folder(&old_root, folder, on_progress)?;
+
+        let mut copy = self.clone();
+        copy.rebase_paths(&old_root, folder);
+        copy.name = name.into();
+        copy.project_path = Some(folder.to_path_buf());
+        copy.save_to(folder)?;
+        Ok(copy)
+    }
+
+    /// A copy with absolute asset paths inside `project_root` made relative,
+    /// or `None` when there are none to rewrite.
+    fn with_portable_paths(&self, project_root: &Path) -> Option<Self> {
+        let needs_rewrite = self
+            .assets
+            .iter()
+            .any(|asset| portable_path(asset.stored_path(), project_root).is_some());
+        if !needs_rewrite {
+            return None;
+        }
+        let mut portable = self.clone();
+        for asset in portable.assets.iter_mut() {
+            let path = asset_path_mut(asset);
+            if let Some(relative) = portable_path(path, project_root) {
+                *path = relative;
+            }
+        }
+        Some(portable)
+    }
+
+    /// Rewrite absolute asset paths and path-like generative inputs under
+    /// `old_root` to point under `new_root`. Relative paths are left alone;
+    /// they already follow the project folder.
+    pub fn rebase_paths(&mut self, old_root: &Path, new_root: &Path) {
+        for asset in self.assets.iter_mut() {
+            let path = asset_path_mut(asset);
+            if let Some(rebased) = rebase_path(path, old_root, new_root) {
+                *path = rebased;
+            }
+        }
+        for config in self.generative_configs.values_mut() {
+            rebase_input_values(config.inputs.values_mut(), old_root, new_root);
+            for record in config.versions.iter_mut() {
+                rebase_input_values(record.inputs_snapshot.values_mut(), old_root, new_root);
+            }
+        }
+    }
+
+    /// Flag imported assets whose source file no longer exists as
+    /// [`AssetIssue::Missing`], and clear the flag from ones that are back.
+    /// Returns the ids of the missing assets.
+    pub fn flag_missing_sources(&mut self) -> Vec<Uuid> {
+        let Some(project_root) = self.project_path.clone() else {
+            return Vec::new();
+        };
+        let mut missing = Vec::new();
+        for asset in self.assets.iter_mut() {
+            let Some(path) = asset.source_file() else {
+                continue;
+            };
+            if !resolve_project_path(path, &project_root).is_file() {
+                asset.issue = Some(AssetIssue::Missing);
+                missing.push(asset.id);
+            } else if asset.is_missing() {
+                asset.issue = None;
+            }
+        }
+        missing
+    }
+
+    /// Delete what the asset keeps on disk: its generative folder and, with
+    /// `include_sources`, an imported asset's source file and proxy. Only
+    /// paths inside the project folder are removed; files linked from
+    /// elsewhere are left alone. Call before removing the asset.
+    pub fn delete_asset_files(&self, id: Uuid, include_sources: bool) -> io::Result<()> {
+        let Some(root) = self.project_path.as_deref() else {
+            return Ok(());
+        };
+        let asset = self
+            .find_asset(id)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Asset not found"))?;
+        if let Some(folder) = generative_folder_for_asset(asset) {
+            if let Some(folder) = path_inside_project(folder, root) {
+                if folder.is_dir() {
+                    fs::remove_dir_all(&folder)?;
+                }
+            }
+        }
+        if include_sources {
+            let owned = asset
+                .source_file()
+                .into_iter()
+                .chain(asset.proxy.as_ref().map(|proxy| proxy.path.as_path()));
+            for path in owned {
+                if let Some(path) = path_inside_project(path, root) {
+                    if path.is_file() {
+                        fs::remove_file(&path)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Ids of assets flagged as missing.
+    pub fn missing_asset_ids(&self) -> Vec<Uuid> {
+        self.assets
+            .iter()
+            .filter(|asset| asset.is_missing())
+            .map(|asset| asset.id)
+            .collect()
+    }
+
+    /// Point an imported asset at `path`; every clip using the asset follows.
+    /// The file must be the same kind of media. Cached duration, size and
+    /// proxy are cleared so they are made again.
+    pub fn relink_asset(&mut self, id: Uuid, path: &Path) -> Result<(), String> {
+        if !path.is_file() {
+            return Err(format!("{} doesn't exist", path.display()));
+        }
+        let stored = self
+            .project_path
+            .as_deref()
+            .and_then(|root| portable_path(path, root))
+            .unwrap_or_else(|| path.to_path_buf());
+        let asset = self
+            .assets
+            .iter_mut()
+            .find(|asset| asset.id == id)
+            .ok_or_else(|| "Asset not found".to_string())?;
+        let (expected, noun) = match asset.kind {
+            AssetKind::Video { .. } => (ImportKind::Video, "video"),
+            AssetKind::Image { .. } => (ImportKind::Image, "image"),
+            AssetKind::Audio { .. } => (ImportKind::Audio, "audio"),
+            _ => return Err("Only imported media can be relinked".to_string()),
+        };
+        if ImportKind::from_path(path) != Some(expected) {
+            return Err(format!("{} isn't a supported {} file", path.display(), noun));
+        }
+        *asset_path_mut(asset) = stored;
+        asset.issue = None;
+        asset.duration_seconds = None;
+        asset.source_width = None;
+        asset.source_height = None;
+        asset.proxy = None;
+        self.mark_edited();
+        Ok(())
+    }
+
+    /// Search `folder` (and below) for files named like each missing asset's
+    /// source and relink the ones found. Returns the relinked ids.
+    pub fn relink_missing_from_folder(&mut self, folder: &Path) -> Vec<Uuid> {
+        let wanted: Vec<(Uuid, String)> = self
+            .assets
+            .iter()
+            .filter(|asset| asset.is_missing())
+            .filter_map(|asset| {
+                let name = asset.source_file()?.file_name()?;
+                Some((asset.id, name.to_string_lossy().to_lowercase()))
+            })
+            .collect();
+        if wanted.is_empty() {
+            return Vec::new();
+        }
+        let names: HashSet<String> = wanted.iter().map(|(_, name)| name.clone()).collect();
+        let found = find_files_named(folder, &names);
+        wanted
+            .into_iter()
+            .filter(|(id, name)| {
+                found
+                    .get(name)
+                    .is_some_and(|path| self.relink_asset(*id, path).is_ok())
+            })
+            .map(|(id, _)| id)
+            .collect()
+    }
+
     pub fn set_generative_provider_id(
         &mut self,
         asset_id: Uuid,
@@ -136,34 +358,52 @@ impl Project {
         true
     }
 
+    /// Point a generative asset at `version` for display only. The config keeps
+    /// its active version, so nothing is saved and a reload shows that one.
+    pub fn show_generative_version(&mut self, asset_id: Uuid, version: Option<String>) -> bool {
+        let Some(asset) = self.assets.iter_mut().find(|asset| asset.id == asset_id) else {
+            return false;
+        };
+        match &mut asset.kind {
+            AssetKind::GenerativeVideo { active_version, .. }
+            | AssetKind::GenerativeImage { active_version, .. }
+            | AssetKind::GenerativeAudio { active_version, .. } => {
+                *active_version = version;
+                true
+            }
+            _ => false,
+        }
+    }
+
     pub fn load_generative_configs(&mut self) {
         let Some(project_root) = self.project_path.clone() else {
             return;
         };
 
         self.generative_configs.clear();
-        for asset in self.assets.iter_mut() {
-            let (folder, active_version) = match &mut asset.kind {
-                AssetKind::GenerativeVideo {
-                    folder,
-                    active_version,
-                    ..
-                }
-                | AssetKind::GenerativeImage {
-                    folder,
-                    active_version,
+        let configs = read_generative_configs(&project_root, &self.assets);
+        self.apply_generative_configs(configs);
+    }
+
+    /// Install configs read off-thread. Assets that already have a config (e.g. edited
+    /// while the rest were loading) keep theirs.
+    pub fn apply_generative_configs(&mut self, configs: Vec<(Uuid, GenerativeConfig)>) {
+        for (asset_id, config) in configs {
+            if self.generative_configs.contains_key(&asset_id) {
+                continue;
+            }
+            let Some(asset) = self.assets.iter_mut().find(|asset| asset.id == asset_id) else {
+                continue;
+            };
+            match &mut asset.kind {
+                AssetKind::GenerativeVideo { active_version, .. }
+                | AssetKind::GenerativeImage { active_version, .. }
+                | AssetKind::GenerativeAudio { active_version, .. } => {
+                    *active_version = config.active_version.clone();
                 }
-                | AssetKind::GenerativeAudio {
-                    folder,
-                    active_version,
-                } => (folder, active_version),
                 _ => continue,
-            };
-
-            let folder_path = project_root.join(folder);
-            let config = GenerativeConfig::load(&folder_path).unwrap_or_default();
-            *active_version = config.active_version.clone();
-            self.generative_configs.insert(asset.id, config);
+            }
+            self.generative_configs.insert(asset_id, config);
         }
     }
 
@@ -204,7 +444,7 @@ impl Project {
     }
 }
 
-fn generative_folder_for_asset(asset: &Asset) -> Option<&std::path::PathBuf> {
+fn generative_folder_for_asset(asset: &Asset) -> Option<&PathBuf> {
     match &asset.kind {
         AssetKind::GenerativeVideo { folder, .. }
         | AssetKind::GenerativeImage { folder, .. }
@@ -212,3 +452,574 @@ fn generative_folder_for_asset(asset: &Asset) -> Option<&std::path::PathBuf> {
         _ => None,
     }
 }
+
+/// Read the generative config for every generative asset; safe to call off the UI thread.
+pub fn read_generative_configs(project_root: &Path, assets: &[Asset]) -> Vec<(Uuid, GenerativeConfig)> {
+    assets
+        .iter()
+        .filter_map(|asset| {
+            let folder = generative_folder_for_asset(asset)?;
+            let config = GenerativeConfig::load(&project_root.join(folder)).unwrap_or_default();
+            Some((asset.id, config))
+        })
+        .collect()
+}
+
+fn asset_path_mut(asset: &mut Asset) -> &mut PathBuf {
+    match &mut asset.kind {
+        AssetKind::Video { path } | AssetKind::Image { path } | AssetKind::Audio { path } => path,
+        AssetKind::GenerativeVideo { folder, .. }
+        | AssetKind::GenerativeImage { folder, .. }
+        | AssetKind::GenerativeAudio { folder, .. } => folder,
+    }
+}
+
+/// How `path` should be stored so the project folder can be moved: relative to
+/// `project_root` when it lies inside it. `None` when it should stay as is
+/// (already relative, or outside the project).
+pub fn portable_path(path: &Path, project_root: &Path) -> Option<PathBuf> {
+    if !path.is_absolute() {
+        return None;
+    }
+    let relative = path.strip_prefix(project_root).ok()?;
+    if relative.as_os_str().is_empty() {
+        return None;
+    }
+    Some(relative.to_path_buf())
+}
+
+/// Absolute location of a stored project path: relative paths are resolved
+/// against `project_root`, absolute ones (outside the project) are kept.
+pub fn resolve_project_path(path: &Path, project_root: &Path) -> PathBuf {
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        project_root.join(path)
+    }
+}
+
+/// Absolute location of a stored path if it lies strictly inside
+/// `project_root`; `None` for outside paths and ones that climb out with `..`.
+fn path_inside_project(path: &Path, project_root: &Path) -> Option<PathBuf> {
+    let relative = if path.is_absolute() {
+        path.strip_prefix(project_root).ok()?
+    } else {
+        path
+    };
+    let inside = relative
+        .components()
+        .all(|component| matches!(component, std::path::Component::Normal(_)));
+    if !inside || relative.as_os_str().is_empty() {
+        return None;
+    }
+    Some(project_root.join(relative))
+}
+
+/// `path` moved from under `old_root` to the same place under `new_root`.
+/// `None` when `path` is relative or lies outside `old_root`.
+pub fn rebase_path(path: &Path, old_root: &Path, new_root: &Path) -> Option<PathBuf> {
+    if !path.is_absolute() {
+        return None;
+    }
+    let relative = path.strip_prefix(old_root).ok()?;
+    Some(new_root.join(relative))
+}
+
+/// Rebase literal string inputs that hold an absolute path into the old project.
+fn rebase_input_values<'a>(
+    values: impl Iterator<Item = &'a mut InputValue>,
+    old_root: &Path,
+    new_root: &Path,
+) {
+    for value in values {
+        let InputValue::Literal { value } = value else {
+            continue;
+        };
+        let Some(text) = value.as_str() else {
+            continue;
+        };
+        if let Some(rebased) = rebase_path(Path::new(text), old_root, new_root) {
+            *value = serde_json::Value::String(rebased.to_string_lossy().to_string());
+        }
+    }
+}
+
+/// Recursively copy `source` into `dest`, which must not exist yet or be empty.
+/// Files are copied in chunks so `on_progress` (bytes copied, total bytes)
+/// keeps moving through large generated videos. The copy is staged next to
+/// `dest` and renamed into place, so a failed copy leaves nothing behind.
+pub fn copy_project_folder(
+    source: &Path,
+    dest: &Path,
+    on_progress: impl FnMut(u64, u64),
+) -> io::Result<()> {
+    let source = fs::canonicalize(source)?;
+    let dest = canonicalize_new_path(dest)?;
+    if dest.starts_with(&source) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Can't copy a project into its own folder",
+        ));
+    }
+    let dest_exists = dest.exists();
+    if dest_exists && fs::read_dir(&dest)?.next().is_some() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists and isn't empty", dest.display()),
+        ));
+    }
+
+    let file_name = dest.file_name().ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidInput, "Destination has no folder name")
+    })?;
+    let staging = dest.with_file_name(format!(
+        ".{}.copying-{}",
+        file_name.to_string_lossy(),
+        Uuid::new_v4()
+    ));
+    let result = copy_files(&source, &staging, on_progress).and_then(|_| {
+        if dest_exists {
+            fs::remove_dir(&dest)?;
+        }
+        fs::rename(&staging, &dest)
+    });
+    if result.is_err() {
+        let _ = fs::remove_dir_all(&staging);
+    }
+    result
+}
+
+/// `path` with its existing ancestors resolved, for paths that may not exist
+/// yet.
+fn canonicalize_new_path(path: &Path) -> io::Result<PathBuf> {
+    if path.exists() {
+        return fs::canonicalize(path);
+    }
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir()?.join(path)
+    };
+    let mut missing = Vec::new();
+    let mut existing = absolute.as_path();
+    while !existing.exists() {
+        match (existing.file_name(), existing.parent()) {
+            (Some(name), Some(parent)) => {
+                missing.push(name.to_os_string());
+                existing = parent;
+            }
+            _ => break,
+        }
+    }
+    let mut resolved = fs::canonicalize(existing)?;
+    resolved.extend(missing.iter().rev());
+    Ok(resolved)
+}
+
+fn copy_files(
+    source: &Path,
+    dest: &Path,
+    mut on_progress: impl FnMut(u64, u64),
+) -> io::Result<()> {
+    let mut files = Vec::new();
+    collect_files(source, Path::new(""), &mut files)?;
+    let total: u64 = files.iter().map(|(_, size)| size).sum();
+    let mut copied = 0_u64;
+    on_progress(copied, total);
+
+    fs::create_dir_all(dest)?;
+    let mut buffer = vec![0_u8; COPY_CHUNK_BYTES];
+    for (relative, _) in files {
+        let target = dest.join(&relative);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut reader = fs::File::open(source.join(&relative))?;
+        let mut writer = fs::File::create(&target)?;
+        loop {
+            let read = io::Read::read(&mut reader, &mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            io::Write::write_all(&mut writer, &buffer[..read])?;
+            copied += read as u64;
+            on_progress(copied, total);
+        }
+    }
+    Ok(())
+}
+
+const COPY_CHUNK_BYTES: usize = 1024 * 1024;
+
+/// How many folders below the chosen one a relink search looks.
+const RELINK_SEARCH_DEPTH: usize = 8;
+
+/// The shallowest file under `folder` for each of `names` (lowercase file
+/// names), keyed by name.
+fn find_files_named(folder: &Path, names: &HashSet<String>) -> HashMap<String, PathBuf> {
+    let mut found = HashMap::new();
+    let mut pending = VecDeque::from([(folder.to_path_buf(), 0)]);
+    while let Some((dir, depth)) = pending.pop_front() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                if depth < RELINK_SEARCH_DEPTH {
+                    pending.push_back((entry.path(), depth + 1));
+                }
+            } else if file_type.is_file() {
+                let name = entry.file_name().to_string_lossy().to_lowercase();
+                if names.contains(&name) {
+                    found.entry(name).or_insert_with(|| entry.path());
+                }
+            }
+        }
+        if found.len() == names.len() {
+            break;
+        }
+    }
+    found
+}
+
+/// Every file under `root`/`relative` as (path relative to `root`, size).
+/// Empty folders are recreated by `save_to`, so only files are listed.
+fn collect_files(root: &Path, relative: &Path, files: &mut Vec<(PathBuf, u64)>) -> io::Result<()> {
+    for entry in fs::read_dir(root.join(relative))? {
+        let entry = entry?;
+        let entry_relative = relative.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect_files(root, &entry_relative, files)?;
+        } else if file_type.is_file() {
+            files.push((entry_relative, entry.metadata()?.len()));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{Clip, GenerationRecord};
+
+    #[test]
+    fn test_rebase_path_moves_old_root_references() {
+        let old_root = std::env::temp_dir().join("nla-old").join("My Project");
+        let new_root = std::env::temp_dir().join("nla-new").join("My Project copy");
+
+        let inside = old_root.join("generated").join("video").join("gen_001");
+        assert_eq!(
+            rebase_path(&inside, &old_root, &new_root),
+            Some(new_root.join("generated").join("video").join("gen_001"))
+        );
+        // Relative paths already follow the project folder
+        assert_eq!(rebase_path(Path::new("video/clip.mp4"), &old_root, &new_root), None);
+        // Files outside the project stay where they are
+        let outside = std::env::temp_dir().join("elsewhere").join("clip.mp4");
+        assert_eq!(rebase_path(&outside, &old_root, &new_root), None);
+        // A sibling whose name merely starts with the root isn't inside it
+        let sibling = std::env::temp_dir().join("nla-old").join("My Project 2").join("a.png");
+        assert_eq!(rebase_path(&sibling, &old_root, &new_root), None);
+    }
+
+    #[test]
+    fn test_paths_inside_root_are_stored_relative() {
+        let root = std::env::temp_dir().join("nla-portable").join("Project");
+        let inside = root.join("video").join("clip.mp4");
+        assert_eq!(portable_path(&inside, &root), Some(PathBuf::from("video").join("clip.mp4")));
+        assert_eq!(resolve_project_path(Path::new("video/clip.mp4"), &root), root.join("video/clip.mp4"));
+
+        // Outside the root the absolute path is kept and resolves to itself
+        let outside = std::env::temp_dir().join("nla-portable").join("Other").join("clip.mp4");
+        assert_eq!(portable_path(&outside, &root), None);
+        assert_eq!(resolve_project_path(&outside, &root), outside);
+        // Already-relative paths and the root itself are left alone
+        assert_eq!(portable_path(Path::new("video/clip.mp4"), &root), None);
+        assert_eq!(portable_path(&root, &root), None);
+    }
+
+    #[test]
+    fn test_saved_project_round_trips_portable_paths() {
+        let base = std::env::temp_dir().join(format!("nla-portable-{}", Uuid::new_v4()));
+        let root = base.join("project");
+        let inside = root.join("images").join("still.png");
+        let outside = base.join("shared").join("music.wav");
+
+        let mut project = Project::create_in(&root, "Portable").unwrap();
+        let inside_id = project.add_asset(Asset::new_image("still", inside.clone()));
+        let outside_id = project.add_asset(Asset::new_audio("music", outside.clone()));
+        project.save().unwrap();
+
+        // project.json holds the inside path relative and the outside one absolute
+        let json = fs::read_to_string(root.join("project.json")).unwrap();
+        let stored: Project = serde_json::from_str(&json).unwrap();
+        match &stored.find_asset(inside_id).unwrap().kind {
+            AssetKind::Image { path } => assert_eq!(path, &PathBuf::from("images").join("still.png")),
+            other => panic!("unexpected kind {:?}", other),
+        }
+        match &stored.find_asset(outside_id).unwrap().kind {
+            AssetKind::Audio { path } => assert_eq!(path, &outside),
+            other => panic!("unexpected kind {:?}", other),
+        }
+        // Saving doesn't touch the in-memory project
+        assert_eq!(project.find_asset(inside_id).unwrap().stored_path(), inside.as_path());
+
+        // After moving the folder, both resolve to absolute paths from the new root
+        let moved = base.join("moved");
+        fs::rename(&root, &moved).unwrap();
+        let loaded = Project::load(&moved).unwrap();
+        let resolve = |id| resolve_project_path(loaded.find_asset(id).unwrap().stored_path(), &moved);
+        assert_eq!(resolve(inside_id), moved.join("images").join("still.png"));
+        assert_eq!(resolve(outside_id), outside);
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn test_unsaved_changes_set_on_edit_and_cleared_on_save() {
+        let root = std::env::temp_dir().join(format!("nla-dirty-{}", Uuid::new_v4()));
+        // Nothing to lose before the project is ever saved
+        let mut project = Project::default();
+        project.name = "Draft".to_string();
+        assert!(!project.has_unsaved_changes());
+
+        let mut project = Project::create_in(&root, "Dirty").unwrap();
+        assert!(!project.has_unsaved_changes());
+        let track_id = project.tracks[0].id;
+        let clip_id = project.add_clip(Clip::new(Uuid::new_v4(), track_id, 0.0, 2.0));
+        assert!(project.has_unsaved_changes());
+        project.add_marker_at(1.0, 30.0).unwrap();
+        project.save().unwrap();
+        assert!(!project.has_unsaved_changes());
+
+        // Edits that change nothing leave it clean
+        assert!(!project.remove_clip(Uuid::new_v4()));
+        assert!(project.add_marker_at(1.0, 30.0).is_none());
+        assert!(!project.fill_placeholder(clip_id, Uuid::new_v4()));
+        assert!(!project.rename_asset(Uuid::new_v4(), "Missing"));
+        assert!(!project.has_unsaved_changes());
+
+        // Direct field edits count once they're marked
+        project.name = "Renamed".to_string();
+        assert!(!project.has_unsaved_changes());
+        project.mark_edited();
+        assert!(project.has_unsaved_changes());
+        project.save().unwrap();
+
+        // A freshly loaded project starts clean
+        project.remove_clip(clip_id);
+        let loaded = Project::load(&root).unwrap();
+        assert!(!loaded.has_unsaved_changes());
+        assert!(project.has_unsaved_changes());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_duplicate_copies_folder_and_rewrites_paths() {
+        let base = std::env::temp_dir().join(format!("nla-duplicate-{}", Uuid::new_v4()));
+        let old_root = base.join("original");
+        let new_root = base.join("copy");
+
+        let mut project = Project::create_in(&old_root, "Original").unwrap();
+        fs::write(old_root.join("video").join("clip.mp4"), vec![7_u8; 3000]).unwrap();
+        let absolute_image = old_root.join("images").join("still.png");
+        fs::write(&absolute_image, b"png").unwrap();
+        project.add_asset(Asset::new_video("clip", PathBuf::from("video/clip.mp4")));
+        let image_id = project.add_asset(Asset::new_image("still", absolute_image.clone()));
+        let generative = Asset::new_generative_image("gen", old_root.join("generated/image/gen_001"));
+        let generative_id = project.add_asset(generative);
+        let literal = |path: &Path| InputValue::Literal {
+            value: serde_json::Value::String(path.to_string_lossy().to_string()),
+        };
+        let mut inputs = HashMap::new();
+        inputs.insert("mask".to_string(), literal(&absolute_image));
+        project.generative_configs.insert(
+            generative_id,
+            GenerativeConfig {
+                inputs: inputs.clone(),
+                versions: vec![GenerationRecord {
+                    version: "v1".to_string(),
+                    timestamp: chrono::Utc::now(),
+                    provider_id: Uuid::new_v4(),
+                    inputs_snapshot: inputs,
+                    output_index: None,
+                    output_count: None,
+                }],
+                ..GenerativeConfig::default()
+            },
+        );
+        project.save().unwrap();
+
+        let mut progress = Vec::new();
+        let copy = project
+            .duplicate_to(&new_root, "Copy", |copied, total| progress.push((copied, total)))
+            .unwrap();
+
+        assert_eq!(copy.name, "Copy");
+        assert_eq!(copy.project_path.as_deref(), Some(new_root.as_path()));
+        assert_eq!(fs::read(new_root.join("video").join("clip.mp4")).unwrap().len(), 3000);
+        let (copied, total) = *progress.last().unwrap();
+        assert_eq!(copied, total);
+        assert!(total >= 3003);
+
+        let rebased_image = new_root.join("images").join("still.png");
+        match &copy.find_asset(image_id).unwrap().kind {
+            AssetKind::Image { path } => assert_eq!(path, &rebased_image),
+            other => panic!("unexpected kind {:?}", other),
+        }
+        let config = &copy.generative_configs[&generative_id];
+        assert_eq!(config.inputs["mask"], literal(&rebased_image));
+        assert_eq!(config.versions[0].inputs_snapshot["mask"], literal(&rebased_image));
+
+        // The saved copy loads with the rewritten paths and the original is untouched
+        let reloaded = Project::load(&new_root).unwrap();
+        assert_eq!(reloaded.name, "Copy");
+        let reloaded_image = reloaded.find_asset(image_id).unwrap().stored_path();
+        assert_eq!(resolve_project_path(reloaded_image, &new_root), rebased_image);
+        assert_eq!(project.project_path.as_deref(), Some(old_root.as_path()));
+
+        // Copying again onto the now non-empty folder is refused
+        assert!(project.duplicate_to(&new_root, "Again", |_, _| {}).is_err());
+        assert!(project.duplicate_to(&old_root.join("nested"), "Nested", |_, _| {}).is_err());
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn test_failed_copy_leaves_no_destination() {
+        let base = std::env::temp_dir().join(format!("nla-copy-{}", Uuid::new_v4()));
+        let source = base.join("source");
+        fs::create_dir_all(source.join("media")).unwrap();
+        fs::write(source.join("a.bin"), vec![1_u8; 64]).unwrap();
+        fs::write(source.join("media").join("b.bin"), vec![2_u8; 64]).unwrap();
+
+        // Naming the source through `..` doesn't hide that dest is inside it
+        let roundabout = source.join("media").join("..");
+        assert!(copy_project_folder(&roundabout, &source.join("inside"), |_, _| {}).is_err());
+        assert!(!source.join("inside").exists());
+
+        // A file vanishing mid-copy fails the copy without leaving a partial one
+        let dest = base.join("copy");
+        let missing = source.join("media").join("b.bin");
+        let result = copy_project_folder(&source, &dest, |_, _| {
+            let _ = fs::remove_file(&missing);
+        });
+        assert!(result.is_err());
+        let leftovers: Vec<_> = fs::read_dir(&base)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(leftovers, vec![std::ffi::OsString::from("source")]);
+
+        copy_project_folder(&source, &dest, |_, _| {}).unwrap();
+        assert_eq!(fs::read(dest.join("a.bin")).unwrap(), vec![1_u8; 64]);
+        assert_eq!(fs::read_dir(&base).unwrap().count(), 2);
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn test_load_flags_missing_sources() {
+        let root = std::env::temp_dir().join(format!("nla-missing-{}", Uuid::new_v4()));
+        let mut project = Project::create_in(&root, "Missing").unwrap();
+        fs::write(root.join("video").join("here.mp4"), b"mp4").unwrap();
+        let present = project.add_asset(Asset::new_video("here", PathBuf::from("video/here.mp4")));
+        let gone = project.add_asset(Asset::new_video("gone", PathBuf::from("video/gone.mp4")));
+        // Generative folders are filled in later, so they are never flagged
+        project.add_asset(Asset::new_generative_image("gen", PathBuf::from("generated/image/gen_001")));
+        project.save().unwrap();
+
+        let mut loaded = Project::load(&root).unwrap();
+        assert_eq!(loaded.missing_asset_ids(), vec![gone]);
+        assert_eq!(loaded.find_asset(gone).unwrap().issue, Some(AssetIssue::Missing));
+        assert!(loaded.find_asset(present).unwrap().issue.is_none());
+
+        // Once the file is back the flag clears
+        fs::write(root.join("video").join("gone.mp4"), b"mp4").unwrap();
+        assert!(loaded.flag_missing_sources().is_empty());
+        assert!(loaded.find_asset(gone).unwrap().issue.is_none());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_delete_asset_files_stays_inside_the_project() {
+        let base = std::env::temp_dir().join(format!("nla-delete-files-{}", Uuid::new_v4()));
+        let root = base.join("project");
+        let mut project = Project::create_in(&root, "Delete").unwrap();
+        let outside = base.join("outside.mp4");
+        fs::write(&outside, b"mp4").unwrap();
+        fs::write(root.join("video").join("inside.mp4"), b"mp4").unwrap();
+        let gen_folder = root.join("generated").join("image").join("gen_001");
+        fs::create_dir_all(&gen_folder).unwrap();
+        fs::write(gen_folder.join("v1.png"), b"png").unwrap();
+
+        let inside = project.add_asset(Asset::new_video("inside", PathBuf::from("video/inside.mp4")));
+        let linked = project.add_asset(Asset::new_video("linked", outside.clone()));
+        let escaping = project.add_asset(Asset::new_video("escaping", PathBuf::from("../outside.mp4")));
+        let generated = project.add_asset(Asset::new_generative_image(
+            "gen",
+            PathBuf::from("generated/image/gen_001"),
+        ));
+
+        // Generative folders go; imported sources need `include_sources`
+        project.delete_asset_files(generated, false).unwrap();
+        project.delete_asset_files(inside, false).unwrap();
+        assert!(!gen_folder.exists());
+        assert!(root.join("video").join("inside.mp4").is_file());
+
+        project.delete_asset_files(inside, true).unwrap();
+        assert!(!root.join("video").join("inside.mp4").exists());
+
+        // Files outside the project folder are never deleted
+        project.delete_asset_files(linked, true).unwrap();
+        project.delete_asset_files(escaping, true).unwrap();
+        assert!(outside.is_file());
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn test_relink_updates_every_clip_using_the_asset() {
+        let base = std::env::temp_dir().join(format!("nla-relink-{}", Uuid::new_v4()));
+        let root = base.join("project");
+        let mut project = Project::create_in(&root, "Relink").unwrap();
+        let asset_id = project.add_asset(Asset::new_video("shot", PathBuf::from("video/shot.mp4")));
+        let other_id = project.add_asset(Asset::new_audio("music", PathBuf::from("audio/music.wav")));
+        let track_id = project.tracks[0].id;
+        project.add_clip(Clip::new(asset_id, track_id, 0.0, 2.0));
+        project.add_clip(Clip::new(asset_id, track_id, 5.0, 2.0));
+        assert_eq!(project.flag_missing_sources(), vec![asset_id, other_id]);
+
+        // The file moved inside the project: stored relative again
+        let moved = root.join("video").join("renamed.mp4");
+        fs::write(&moved, b"mp4").unwrap();
+        assert!(project.relink_asset(asset_id, &root.join("video").join("nope.mp4")).is_err());
+        let image = root.join("images").join("still.png");
+        fs::write(&image, b"png").unwrap();
+        assert!(project.relink_asset(asset_id, &image).is_err());
+        project.relink_asset(asset_id, &moved).unwrap();
+        let asset = project.find_asset(asset_id).unwrap();
+        assert!(asset.issue.is_none());
+        assert_eq!(asset.stored_path(), Path::new("video").join("renamed.mp4"));
+        for clip in project.clips.iter() {
+            let clip_asset = project.find_asset(clip.asset_id).unwrap();
+            assert_eq!(resolve_project_path(clip_asset.stored_path(), &root), moved);
+        }
+
+        // Searching a folder matches file names, in any case and any depth
+        let library = base.join("library").join("sounds");
+        fs::create_dir_all(&library).unwrap();
+        fs::write(library.join("Music.WAV"), b"wav").unwrap();
+        assert_eq!(project.relink_missing_from_folder(&base.join("library")), vec![other_id]);
+        assert_eq!(project.find_asset(other_id).unwrap().stored_path(), library.join("Music.WAV"));
+        assert!(project.missing_asset_ids().is_empty());
+
+        let _ = fs::remove_dir_all(&base);
+    }
+}
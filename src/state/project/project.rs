@@ -1,12 +1,18 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
-use crate::state::{generative_video_duration_seconds, Asset, AssetKind, GenerativeConfig};
-use super::{Clip, ClipTransform, Marker, ProjectSettings, Track, TrackType};
+use crate::state::{
+    generative_video_duration_seconds, Asset, AssetIssue, AssetKind, AssetProxy, ExportOptions, GenerativeConfig,
+    ImportKind, InputValue,
+};
+use super::{
+    arrange_track, find_overlaps, resolve_overlaps, ArrangeMode, Clip, ClipTransform, OverlapResolution, Marker, ProjectSettings, Track, TrackType, MAX_CLIP_SPEED, MIN_CLIP_SPEED,
+};
 
 /// The main project container
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,7 +30,11 @@ pub struct Project {
     /// All clips placed on tracks
     pub clips: Vec<Clip>,
     /// All markers
+    #[serde(default)]
     pub markers: Vec<Marker>,
+    /// Encoder settings used when exporting the timeline
+    #[serde(default)]
+    pub export_options: ExportOptions,
     
     /// Path to the project folder (not serialized - set on load)
     #[serde(skip)]
@@ -32,6 +42,13 @@ pub struct Project {
     /// In-memory generative configs keyed by asset id.
     #[serde(skip)]
     pub generative_configs: HashMap<Uuid, GenerativeConfig>,
+    /// Bumped by every edit to what `project.json` stores.
+    #[serde(skip)]
+    pub(super) revision: Cell<u64>,
+    /// `revision` when the project was last saved to or loaded from
+    /// `project_path`; `None` until either happens.
+    #[serde(skip)]
+    pub(super) saved_revision: Cell<Option<u64>>,
 }
 
 impl Default for Project {
@@ -48,8 +65,11 @@ impl Default for Project {
             assets: Vec::new(),
             clips: Vec::new(),
             markers: Vec::new(),
+            export_options: ExportOptions::default(),
             project_path: None,
             generative_configs: HashMap::new(),
+            revision: Cell::new(0),
+            saved_revision: Cell::new(None),
         }
     }
 }
@@ -102,6 +122,69 @@ impl Project {
         false
     }
 
+    /// Remember the probed pixel size of a file-backed image or video asset.
+    pub fn set_asset_dimensions(&mut self, id: Uuid, width: u32, height: u32) -> bool {
+        let Some(asset) = self.assets.iter_mut().find(|a| a.id == id) else {
+            return false;
+        };
+        if asset.is_generative() || !asset.is_visual() {
+            return false;
+        }
+        asset.source_width = Some(width);
+        asset.source_height = Some(height);
+        true
+    }
+
+    /// Set or clear the issue flag for an asset
+    pub fn set_asset_issue(&mut self, id: Uuid, issue: Option<AssetIssue>) -> bool {
+        if let Some(asset) = self.assets.iter_mut().find(|a| a.id == id) {
+            asset.issue = issue;
+            return true;
+        }
+        false
+    }
+
+    /// Set or clear the proxy of a video asset.
+    pub fn set_asset_proxy(&mut self, id: Uuid, proxy: Option<AssetProxy>) -> bool {
+        let Some(asset) = self.assets.iter_mut().find(|a| a.id == id) else {
+            return false;
+        };
+        if !matches!(asset.kind, AssetKind::Video { .. }) {
+            return false;
+        }
+        asset.proxy = proxy;
+        true
+    }
+
+    /// Choose whether an asset previews from its proxy.
+    pub fn set_asset_use_proxy(&mut self, id: Uuid, use_proxy: bool) -> bool {
+        let Some(asset) = self.assets.iter_mut().find(|a| a.id == id) else {
+            return false;
+        };
+        asset.use_proxy = use_proxy;
+        self.mark_edited();
+        true
+    }
+
+    /// Point a video asset at a new file (e.g. a transcoded copy).
+    /// Clears the issue flag, cached duration and size, and the proxy of the
+    /// old file so they are re-derived.
+    pub fn replace_video_source(&mut self, id: Uuid, path: PathBuf) -> bool {
+        let Some(asset) = self.assets.iter_mut().find(|a| a.id == id) else {
+            return false;
+        };
+        let AssetKind::Video { path: current } = &mut asset.kind else {
+            return false;
+        };
+        *current = path;
+        asset.issue = None;
+        asset.duration_seconds = None;
+        asset.source_width = None;
+        asset.source_height = None;
+        asset.proxy = None;
+        true
+    }
+
     /// Get the cached duration (in seconds) for an asset
     pub fn asset_duration_seconds(&self, id: Uuid) -> Option<f64> {
         self.find_asset(id).and_then(|asset| asset.duration_seconds)
@@ -153,6 +236,7 @@ impl Project {
 
     /// Add a new video track
     pub fn add_video_track(&mut self) -> Uuid {
+        self.mark_edited();
         let count = self.tracks.iter().filter(|t| t.track_type == TrackType::Video).count();
         let track = Track::new(format!("Video {}", count + 1), TrackType::Video);
         let id = track.id;
@@ -162,6 +246,7 @@ impl Project {
 
     /// Add a new audio track
     pub fn add_audio_track(&mut self) -> Uuid {
+        self.mark_edited();
         let count = self.tracks.iter().filter(|t| t.track_type == TrackType::Audio).count();
         let track = Track::new(format!("Audio {}", count + 1), TrackType::Audio);
         let id = track.id;
@@ -184,11 +269,16 @@ impl Project {
         // Remove the track
         let len = self.tracks.len();
         self.tracks.retain(|t| t.id != id);
-        self.tracks.len() < len
+        let removed = self.tracks.len() < len;
+        if removed {
+            self.mark_edited();
+        }
+        removed
     }
 
     /// Add an asset to the project
     pub fn add_asset(&mut self, asset: Asset) -> Uuid {
+        self.mark_edited();
         let id = asset.id;
         let is_generative = asset.is_generative();
         self.assets.push(asset);
@@ -213,12 +303,10 @@ impl Project {
             .unwrap_or("")
             .to_lowercase();
         
-        let (subfolder, is_video, is_audio, _is_image) = match ext.as_str() {
-            "mp4" | "mov" | "avi" | "mkv" | "webm" => ("video", true, false, false),
-            "mp3" | "wav" | "ogg" | "flac" => ("audio", false, true, false),
-            "png" | "jpg" | "jpeg" | "gif" | "webp" => ("images", false, false, true),
-            _ => return Err(io::Error::new(io::ErrorKind::InvalidInput, "Unsupported file type")),
-        };
+        let kind = ImportKind::from_path(source_path).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "Unsupported file type")
+        })?;
+        let subfolder = kind.subfolder();
 
         // 2. Determine target filename with collision handling
         let file_stem = source_path.file_stem()
@@ -248,12 +336,10 @@ impl Project {
         let relative_path = PathBuf::from(subfolder).join(&target_filename);
         let name = file_stem.to_string(); // Use original filename as display name
 
-        let asset = if is_video {
-            Asset::new_video(name, relative_path)
-        } else if is_audio {
-            Asset::new_audio(name, relative_path)
-        } else {
-            Asset::new_image(name, relative_path)
+        let asset = match kind {
+            ImportKind::Video => Asset::new_video(name, relative_path),
+            ImportKind::Audio => Asset::new_audio(name, relative_path),
+            ImportKind::Image => Asset::new_image(name, relative_path),
         };
 
         Ok(self.add_asset(asset))
@@ -262,13 +348,42 @@ impl Project {
     /// Remove an asset by ID (also removes any clips using this asset)
     pub fn remove_asset(&mut self, id: Uuid) -> bool {
         // Remove any clips that reference this asset
+        let clip_count = self.clips.len();
         self.clips.retain(|c| c.asset_id != id);
         
         // Remove the asset
         let len = self.assets.len();
         self.assets.retain(|a| a.id != id);
         self.generative_configs.remove(&id);
-        self.assets.len() < len
+        let removed = self.assets.len() < len;
+        if removed || self.clips.len() < clip_count {
+            self.mark_edited();
+        }
+        removed
+    }
+
+    /// Ids of assets no clip uses, in project order. An asset another
+    /// generative asset takes as an input still counts as used.
+    pub fn unused_assets(&self) -> Vec<Uuid> {
+        let referenced: HashSet<Uuid> = self
+            .clips
+            .iter()
+            .map(|clip| clip.asset_id)
+            .chain(
+                self.generative_configs
+                    .values()
+                    .flat_map(|config| config.inputs.values())
+                    .filter_map(|value| match value {
+                        InputValue::AssetRef { asset_id } => Some(*asset_id),
+                        InputValue::Literal { .. } => None,
+                    }),
+            )
+            .collect();
+        self.assets
+            .iter()
+            .filter(|asset| !referenced.contains(&asset.id))
+            .map(|asset| asset.id)
+            .collect()
     }
 
     /// Rename an asset by ID.
@@ -276,6 +391,7 @@ impl Project {
         let name = name.into();
         if let Some(asset) = self.assets.iter_mut().find(|asset| asset.id == id) {
             asset.name = name;
+            self.mark_edited();
             return true;
         }
         false
@@ -283,6 +399,7 @@ impl Project {
 
     /// Add a clip to the project
     pub fn add_clip(&mut self, clip: Clip) -> Uuid {
+        self.mark_edited();
         let id = clip.id;
         self.clips.push(clip);
         id
@@ -311,17 +428,279 @@ impl Project {
         Some(self.add_clip(clip))
     }
 
+    /// Paste copies of `clips` with fresh ids so the earliest one starts at `time`,
+    /// keeping their relative offsets. Each copy lands on `target_track` when its
+    /// type fits, otherwise on its original track (or the first track of that type).
+    /// Returns the new clip ids.
+    pub fn paste_clips(&mut self, clips: &[Clip], time: f64, target_track: Option<Uuid>) -> Vec<Uuid> {
+        let Some(earliest) = clips.iter().map(|clip| clip.start_time).reduce(f64::min) else {
+            return Vec::new();
+        };
+        let mut base = time.max(0.0);
+        if self.settings.clamp_pasted_clips {
+            let span = clips
+                .iter()
+                .map(|clip| clip.end_time() - earliest)
+                .fold(0.0, f64::max);
+            base = base.min(self.settings.duration_seconds - span).max(0.0);
+        }
+
+        // Copies pasted together stay linked (and grouped) with each other, not with the originals
+        let mut link_groups: HashMap<Uuid, Uuid> = HashMap::new();
+        let mut pasted = Vec::with_capacity(clips.len());
+        for clip in clips {
+            let Some(track_id) = self.paste_track_for(clip, target_track) else {
+                continue;
+            };
+            let mut copy = clip.clone();
+            copy.id = Uuid::new_v4();
+            copy.track_id = track_id;
+            copy.link_group = clip
+                .link_group
+                .map(|group| *link_groups.entry(group).or_insert_with(Uuid::new_v4));
+            copy.group_id = clip
+                .group_id
+                .map(|group| *link_groups.entry(group).or_insert_with(Uuid::new_v4));
+            copy.start_time = base + (clip.start_time - earliest);
+            pasted.push(self.add_clip(copy));
+        }
+        pasted
+    }
+
+    /// Duplicate a clip right after itself on the same track. Unless the project
+    /// allows overlap, the copy slides forward to the first gap it fits in.
+    /// Returns the new clip id.
+    pub fn duplicate_clip(&mut self, id: Uuid) -> Option<Uuid> {
+        let original = self.clips.iter().find(|clip| clip.id == id)?.clone();
+        let mut start = original.end_time();
+        if !self.settings.allow_duplicate_overlap {
+            // Each blocker pushes the candidate to its end, so this terminates.
+            while let Some(blocker_end) = self
+                .clips
+                .iter()
+                .filter(|clip| clip.track_id == original.track_id)
+                .filter(|clip| clip.start_time < start + original.duration - 1e-9)
+                .filter(|clip| clip.end_time() > start + 1e-9)
+                .map(|clip| clip.end_time())
+                .reduce(f64::max)
+            {
+                start = blocker_end;
+            }
+        }
+
+        let mut copy = original;
+        copy.id = Uuid::new_v4();
+        copy.start_time = start;
+        copy.link_group = None;
+        copy.group_id = None;
+        Some(self.add_clip(copy))
+    }
+
+    fn paste_track_for(&self, clip: &Clip, target_track: Option<Uuid>) -> Option<Uuid> {
+        let track_type = match self.find_track(clip.track_id) {
+            Some(track) => track.track_type,
+            None => {
+                let asset = self.find_asset(clip.asset_id)?;
+                if asset.is_audio() {
+                    TrackType::Audio
+                } else {
+                    TrackType::Video
+                }
+            }
+        };
+        if let Some(target) = target_track.and_then(|id| self.find_track(id)) {
+            if target.track_type == track_type {
+                return Some(target.id);
+            }
+        }
+        if self.find_track(clip.track_id).is_some() {
+            return Some(clip.track_id);
+        }
+        self.tracks
+            .iter()
+            .find(|track| track.track_type == track_type)
+            .map(|track| track.id)
+    }
+
     /// Update a clip label by ID (per-instance display name).
     pub fn set_clip_label(&mut self, id: Uuid, label: Option<String>) -> bool {
         if let Some(clip) = self.clips.iter_mut().find(|clip| clip.id == id) {
             clip.label = label;
+            self.mark_edited();
             return true;
         }
         false
     }
 
+    /// Freeze a clip from `time` to its end on the frame shown at `time`.
+    ///
+    /// Inside the clip this splits it and freezes the tail; at or before the start the
+    /// whole clip is frozen. Returns the id of the frozen clip.
+    pub fn freeze_clip_from(&mut self, id: Uuid, time: f64) -> Option<Uuid> {
+        const MIN_SPLIT_SECONDS: f64 = 1.0 / 240.0;
+        let index = self.clips.iter().position(|clip| clip.id == id)?;
+        let clip = &mut self.clips[index];
+        if time >= clip.end_time() - MIN_SPLIT_SECONDS {
+            return None;
+        }
+        let hold_time = clip.source_time_at(time.max(clip.start_time));
+        if time <= clip.start_time + MIN_SPLIT_SECONDS {
+            clip.freeze_at = Some(hold_time);
+            let clip_id = clip.id;
+            self.mark_edited();
+            return Some(clip_id);
+        }
+
+        let head_duration = time - clip.start_time;
+        let mut tail = clip.clone();
+        tail.id = Uuid::new_v4();
+        tail.start_time = time;
+        tail.duration = clip.duration - head_duration;
+        if clip.is_frozen() || clip.loop_length().is_some() {
+            // Holding one frame or repeating a cycle; nothing to re-trim.
+        } else if clip.reversed {
+            // A reversed head plays the end of the source range.
+            clip.trim_in_seconds += tail.source_duration();
+        } else {
+            tail.trim_in_seconds = clip.trim_in_seconds + head_duration * clip.playback_speed();
+        }
+        tail.freeze_at = Some(hold_time);
+        // The linked partner stays with the head
+        tail.link_group = None;
+        // The cut sits between the fades: the head keeps the fade-in, the tail the fade-out.
+        tail.fade_in_seconds = 0.0;
+        clip.fade_out_seconds = 0.0;
+        clip.duration = head_duration;
+        let tail_id = tail.id;
+        self.clips.insert(index + 1, tail);
+        self.mark_edited();
+        Some(tail_id)
+    }
+
+    /// Reserve `duration` seconds at `start_time` on a video or audio track.
+    pub fn add_placeholder(
+        &mut self,
+        track_id: Uuid,
+        start_time: f64,
+        duration: f64,
+        label: Option<String>,
+    ) -> Option<Uuid> {
+        let track = self.find_track(track_id)?;
+        if track.track_type == TrackType::Marker {
+            return None;
+        }
+        let clip = Clip::placeholder(track_id, start_time.max(0.0), duration.max(0.1), label);
+        Some(self.add_clip(clip))
+    }
+
+    /// The placeholder on `track_id` covering `time`, if any.
+    pub fn placeholder_at(&self, track_id: Uuid, time: f64) -> Option<Uuid> {
+        self.clips
+            .iter()
+            .find(|clip| {
+                clip.placeholder
+                    && clip.track_id == track_id
+                    && time >= clip.start_time
+                    && time < clip.end_time()
+            })
+            .map(|clip| clip.id)
+    }
+
+    /// Put `asset_id` in a placeholder's slot. Media shorter than the slot
+    /// is cut to its own length; the slot's start and label are kept.
+    pub fn fill_placeholder(&mut self, clip_id: Uuid, asset_id: Uuid) -> bool {
+        let Some(asset) = self.find_asset(asset_id) else {
+            return false;
+        };
+        let media_duration = asset
+            .duration_seconds
+            .filter(|duration| *duration > 0.0 && (asset.is_video() || asset.is_audio()));
+        let Some(clip) = self
+            .clips
+            .iter_mut()
+            .find(|clip| clip.id == clip_id && clip.placeholder)
+        else {
+            return false;
+        };
+        clip.asset_id = asset_id;
+        clip.placeholder = false;
+        if let Some(media_duration) = media_duration {
+            clip.duration = clip.duration.min(media_duration);
+        }
+        self.mark_edited();
+        true
+    }
+
+    /// Set a clip's fade lengths, clamped so they fit inside the clip.
+    pub fn set_clip_fades(&mut self, id: Uuid, fade_in: f64, fade_out: f64) -> bool {
+        let Some(clip) = self.clips.iter_mut().find(|clip| clip.id == id) else {
+            return false;
+        };
+        clip.set_fade_in(fade_in);
+        clip.set_fade_out(fade_out);
+        self.mark_edited();
+        true
+    }
+
+    /// Change a clip's playback speed, keeping the source range it plays and
+    /// scaling its duration to match. The duration is capped so the clip never
+    /// runs past the end of its source.
+    pub fn set_clip_speed(&mut self, id: Uuid, speed: f64) -> bool {
+        let Some(index) = self.clips.iter().position(|clip| clip.id == id) else {
+            return false;
+        };
+        let source_length = self
+            .asset_duration_seconds(self.clips[index].asset_id)
+            .filter(|duration| *duration > 0.0);
+        let clip = &mut self.clips[index];
+        let source_duration = clip.source_duration();
+        clip.speed = speed.clamp(MIN_CLIP_SPEED, MAX_CLIP_SPEED);
+        if clip.loop_length().is_some() {
+            // The loop still fills the same stretch of timeline
+            self.mark_edited();
+            return true;
+        }
+        let mut duration = clip.timeline_duration_for_source(source_duration);
+        if let Some(source_length) = source_length {
+            let remaining = (source_length - clip.trim_in_seconds).max(0.0);
+            duration = duration.min(clip.timeline_duration_for_source(remaining));
+        }
+        clip.duration = duration.max(0.0);
+        self.mark_edited();
+        true
+    }
+
+    /// Turn looping on or off for a clip. Turning it on loops the source the
+    /// clip currently plays; turning it off shortens the clip to what the
+    /// source can cover from the trim point.
+    pub fn set_clip_loop(&mut self, id: Uuid, enabled: bool) -> bool {
+        let Some(index) = self.clips.iter().position(|clip| clip.id == id) else {
+            return false;
+        };
+        let remaining = self
+            .asset_duration_seconds(self.clips[index].asset_id)
+            .filter(|duration| *duration > 0.0)
+            .map(|duration| (duration - self.clips[index].trim_in_seconds.max(0.0)).max(0.0));
+        let clip = &mut self.clips[index];
+        if enabled {
+            if clip.loop_length().is_none() {
+                let mut cycle = clip.source_duration();
+                if let Some(remaining) = remaining {
+                    cycle = cycle.min(remaining);
+                }
+                clip.loop_source_seconds = cycle;
+            }
+        } else if let Some(remaining) = remaining {
+            clip.duration = clip.duration.min(clip.timeline_duration_for_source(remaining));
+        }
+        clip.loop_to_duration = enabled;
+        self.mark_edited();
+        true
+    }
+
     /// Add a marker to the project
     pub fn add_marker(&mut self, marker: Marker) -> Uuid {
+        self.mark_edited();
         let id = marker.id;
         self.markers.push(marker);
         // Keep markers sorted by time
@@ -329,11 +708,33 @@ impl Project {
         id
     }
 
+    /// Marker sitting on the same frame as `time` (seconds) at `fps`, if any.
+    pub fn marker_on_frame(&self, time: f64, fps: f64) -> Option<Uuid> {
+        let fps = fps.max(1.0);
+        let frame = (time.max(0.0) * fps).round();
+        self.markers
+            .iter()
+            .find(|marker| (marker.time * fps).round() == frame)
+            .map(|marker| marker.id)
+    }
+
+    /// Drop a marker at `time` snapped to the nearest frame. Returns `None`
+    /// when a marker already sits on that frame.
+    pub fn add_marker_at(&mut self, time: f64, fps: f64) -> Option<Uuid> {
+        if self.marker_on_frame(time, fps).is_some() {
+            return None;
+        }
+        let fps = fps.max(1.0);
+        let snapped = (time.max(0.0) * fps).round() / fps;
+        Some(self.add_marker(Marker::new(snapped)))
+    }
+
     /// Move a marker to a new time (seconds), keeping the list sorted.
     pub fn move_marker(&mut self, id: Uuid, new_time: f64) -> bool {
         if let Some(marker) = self.markers.iter_mut().find(|marker| marker.id == id) {
             marker.time = new_time.max(0.0);
             self.markers.sort_by(|a, b| a.time.partial_cmp(&b.time).unwrap());
+            self.mark_edited();
             return true;
         }
         false
@@ -343,6 +744,7 @@ impl Project {
     pub fn set_marker_label(&mut self, id: Uuid, label: Option<String>) -> bool {
         if let Some(marker) = self.markers.iter_mut().find(|marker| marker.id == id) {
             marker.label = label.filter(|value| !value.trim().is_empty());
+            self.mark_edited();
             return true;
         }
         false
@@ -352,6 +754,7 @@ impl Project {
     pub fn set_marker_description(&mut self, id: Uuid, description: Option<String>) -> bool {
         if let Some(marker) = self.markers.iter_mut().find(|marker| marker.id == id) {
             marker.description = description.filter(|value| !value.trim().is_empty());
+            self.mark_edited();
             return true;
         }
         false
@@ -361,6 +764,7 @@ impl Project {
     pub fn set_marker_color(&mut self, id: Uuid, color: Option<String>) -> bool {
         if let Some(marker) = self.markers.iter_mut().find(|marker| marker.id == id) {
             marker.color = color.filter(|value| !value.trim().is_empty());
+            self.mark_edited();
             return true;
         }
         false
@@ -370,62 +774,416 @@ impl Project {
     pub fn remove_clip(&mut self, id: Uuid) -> bool {
         let len = self.clips.len();
         self.clips.retain(|c| c.id != id);
-        self.clips.len() < len
+        let removed = self.clips.len() < len;
+        if removed {
+            self.mark_edited();
+        }
+        removed
     }
 
     /// Move a clip to a new start time
     pub fn move_clip(&mut self, id: Uuid, new_start_time: f64) -> bool {
         if let Some(clip) = self.clips.iter_mut().find(|c| c.id == id) {
             clip.start_time = new_start_time.max(0.0);
+            self.mark_edited();
             return true;
         }
         false
     }
 
     /// Resize a clip (change start and/or duration)
+    ///
+    /// For video and audio, moving the start trims the source by the same
+    /// amount so the frames that stay on screen don't shift. The start can't
+    /// move before the first source frame, nor the end past the last.
     pub fn resize_clip(&mut self, id: Uuid, new_start: f64, new_duration: f64) -> bool {
         if let Some(clip) = self.clips.iter_mut().find(|c| c.id == id) {
             let old_start = clip.start_time;
-            let start_time = new_start.max(0.0);
+            let mut start_time = new_start.max(0.0);
             let mut duration = new_duration.max(0.1);  // Minimum 0.1 second
 
+            // Looping clips repeat their cycle to any length and keep it when
+            // trimmed from the start
+            if clip.loop_length().is_some() {
+                clip.start_time = start_time;
+                clip.duration = duration;
+                self.mark_edited();
+                return true;
+            }
+
             let asset = self.assets.iter().find(|a| a.id == clip.asset_id);
             let max_duration = asset.and_then(|a| a.duration_seconds).filter(|d| *d > 0.0);
+            let speed = clip.playback_speed();
 
             if let Some(max_duration) = max_duration {
-                duration = duration.min(max_duration);
+                duration = duration.min(max_duration / speed);
             }
 
-            if let Some(asset) = asset {
-                if (asset.is_video() || asset.is_audio()) && (start_time - old_start).abs() > f64::EPSILON {
-                    let delta = start_time - old_start;
+            if let Some(asset) = asset.filter(|asset| asset.is_video() || asset.is_audio()) {
+                if (start_time - old_start).abs() > f64::EPSILON {
+                    // Stop at the first source frame, keeping the requested end
+                    let earliest_start = (old_start - clip.trim_in_seconds.max(0.0) / speed).max(0.0);
+                    if start_time < earliest_start {
+                        duration = (start_time + duration - earliest_start).max(0.1);
+                        start_time = earliest_start;
+                    }
+                    // Trimming the start moves through the source at the clip's speed
+                    let delta = (start_time - old_start) * speed;
                     clip.trim_in_seconds = (clip.trim_in_seconds + delta).max(0.0);
 
                     if let Some(max_duration) = max_duration {
-                        let max_trim_in = (max_duration - duration).max(0.0);
+                        let max_trim_in = (max_duration - duration * speed).max(0.0);
                         if clip.trim_in_seconds > max_trim_in {
                             clip.trim_in_seconds = max_trim_in;
                         }
                     }
                 }
+                if let Some(max_duration) = max_duration {
+                    let remaining = (max_duration - clip.trim_in_seconds).max(0.0);
+                    duration = duration.min(remaining / speed);
+                }
             }
 
             clip.start_time = start_time;
             clip.duration = duration;
+            self.mark_edited();
             return true;
         }
         false
     }
 
+    /// How edited tracks are re-placed, from the project settings.
+    pub fn arrange_mode(&self) -> ArrangeMode {
+        if self.settings.magnetic_timeline {
+            ArrangeMode::Magnetic
+        } else {
+            ArrangeMode::Free
+        }
+    }
+
+    /// Tracks holding any of `clip_ids`, each listed once.
+    pub fn clip_track_ids(&self, clip_ids: &[Uuid]) -> Vec<Uuid> {
+        let mut track_ids = Vec::new();
+        for clip in self.clips.iter().filter(|clip| clip_ids.contains(&clip.id)) {
+            if !track_ids.contains(&clip.track_id) {
+                track_ids.push(clip.track_id);
+            }
+        }
+        track_ids
+    }
+
+    /// Re-place the clips on `track_ids` after an edit to the `changed`
+    /// clips, following `arrange_mode`. Returns true if any clip moved.
+    pub fn arrange_tracks(&mut self, track_ids: &[Uuid], changed: &[Uuid]) -> bool {
+        let mode = self.arrange_mode();
+        let mut moved = false;
+        for track_id in track_ids {
+            let mut clips: Vec<&mut Clip> = self
+                .clips
+                .iter_mut()
+                .filter(|clip| clip.track_id == *track_id)
+                .collect();
+            moved |= arrange_track(&mut clips, changed, mode);
+        }
+        if moved {
+            self.mark_edited();
+        }
+        moved
+    }
+
+    /// Overlapping clip pairs on every track.
+    pub fn clip_overlaps(&self) -> Vec<(Uuid, Uuid)> {
+        self.tracks
+            .iter()
+            .flat_map(|track| find_overlaps(&self.clips_on_track(track.id)))
+            .collect()
+    }
+
+    /// Separate overlapping clips on every track. Returns true if any changed.
+    pub fn resolve_clip_overlaps(&mut self, resolution: OverlapResolution) -> bool {
+        let track_ids: Vec<Uuid> = self.tracks.iter().map(|track| track.id).collect();
+        let mut changed = false;
+        for track_id in track_ids {
+            let mut clips: Vec<&mut Clip> = self
+                .clips
+                .iter_mut()
+                .filter(|clip| clip.track_id == track_id)
+                .collect();
+            changed |= resolve_overlaps(&mut clips, resolution);
+        }
+        if changed {
+            self.mark_edited();
+        }
+        changed
+    }
+
+    /// Ids of the clips linked with `id`, including `id` itself. Empty when the
+    /// clip doesn't exist.
+    pub fn linked_clip_ids(&self, id: Uuid) -> Vec<Uuid> {
+        let Some(clip) = self.clips.iter().find(|clip| clip.id == id) else {
+            return Vec::new();
+        };
+        match clip.link_group {
+            Some(group) => self
+                .clips
+                .iter()
+                .filter(|clip| clip.link_group == Some(group))
+                .map(|clip| clip.id)
+                .collect(),
+            None => vec![id],
+        }
+    }
+
+    /// Link clips so moves, trims, and deletes apply to all of them. Clips that
+    /// are already linked bring their whole group along. Returns the group id,
+    /// or `None` when fewer than two clips would be linked.
+    pub fn link_clips(&mut self, ids: &[Uuid]) -> Option<Uuid> {
+        let mut members: Vec<Uuid> = Vec::new();
+        for id in ids {
+            for linked in self.linked_clip_ids(*id) {
+                if !members.contains(&linked) {
+                    members.push(linked);
+                }
+            }
+        }
+        if members.len() < 2 {
+            return None;
+        }
+        let group = Uuid::new_v4();
+        for clip in self.clips.iter_mut().filter(|clip| members.contains(&clip.id)) {
+            clip.link_group = Some(group);
+        }
+        self.mark_edited();
+        Some(group)
+    }
+
+    /// Take a clip out of its link group. A single partner left behind is
+    /// unlinked as well.
+    pub fn unlink_clip(&mut self, id: Uuid) -> bool {
+        let Some(group) = self
+            .clips
+            .iter()
+            .find(|clip| clip.id == id)
+            .and_then(|clip| clip.link_group)
+        else {
+            return false;
+        };
+        let remaining = self
+            .clips
+            .iter()
+            .filter(|clip| clip.id != id && clip.link_group == Some(group))
+            .count();
+        for clip in self.clips.iter_mut() {
+            if clip.id == id || (remaining < 2 && clip.link_group == Some(group)) {
+                clip.link_group = None;
+            }
+        }
+        self.mark_edited();
+        true
+    }
+
+    /// Ids of the clips grouped with `id`, including `id` itself. Empty when
+    /// the clip doesn't exist.
+    pub fn grouped_clip_ids(&self, id: Uuid) -> Vec<Uuid> {
+        let Some(clip) = self.clips.iter().find(|clip| clip.id == id) else {
+            return Vec::new();
+        };
+        match clip.group_id {
+            Some(group) => self
+                .clips
+                .iter()
+                .filter(|clip| clip.group_id == Some(group))
+                .map(|clip| clip.id)
+                .collect(),
+            None => vec![id],
+        }
+    }
+
+    /// Clips to select when `id` is clicked: `id` first (the primary), then
+    /// the rest of its group.
+    pub fn clip_selection_for(&self, id: Uuid) -> Vec<Uuid> {
+        let mut ids = vec![id];
+        ids.extend(self.grouped_clip_ids(id).into_iter().filter(|other| *other != id));
+        ids
+    }
+
+    /// Put clips into one new group, merging any groups they already belong
+    /// to. Returns the group id, or `None` when fewer than two clips would be grouped.
+    pub fn group_clips(&mut self, ids: &[Uuid]) -> Option<Uuid> {
+        let mut members: Vec<Uuid> = Vec::new();
+        for id in ids {
+            for grouped in self.grouped_clip_ids(*id) {
+                if !members.contains(&grouped) {
+                    members.push(grouped);
+                }
+            }
+        }
+        if members.len() < 2 {
+            return None;
+        }
+        let group = Uuid::new_v4();
+        for clip in self.clips.iter_mut().filter(|clip| members.contains(&clip.id)) {
+            clip.group_id = Some(group);
+        }
+        self.mark_edited();
+        Some(group)
+    }
+
+    /// Dissolve the groups of the given clips. Returns false when none were grouped.
+    pub fn ungroup_clips(&mut self, ids: &[Uuid]) -> bool {
+        let groups: Vec<Uuid> = self
+            .clips
+            .iter()
+            .filter(|clip| ids.contains(&clip.id))
+            .filter_map(|clip| clip.group_id)
+            .collect();
+        if groups.is_empty() {
+            return false;
+        }
+        for clip in self.clips.iter_mut() {
+            if clip.group_id.is_some_and(|group| groups.contains(&group)) {
+                clip.group_id = None;
+            }
+        }
+        self.mark_edited();
+        true
+    }
+
+    /// Set (or with `None`, clear) the timeline color of the given clips.
+    /// Returns false when none of them changed.
+    pub fn set_clip_color(&mut self, ids: &[Uuid], color: Option<[u8; 3]>) -> bool {
+        let mut changed = false;
+        for clip in self.clips.iter_mut().filter(|clip| ids.contains(&clip.id)) {
+            if clip.color != color {
+                clip.color = color;
+                changed = true;
+            }
+        }
+        if changed {
+            self.mark_edited();
+        }
+        changed
+    }
+
+    /// Every clip that moves when `id` is dragged: its group and link
+    /// partners, followed transitively.
+    fn clips_moving_with(&self, id: Uuid) -> Vec<Uuid> {
+        let mut members = self.linked_clip_ids(id);
+        let mut index = 0;
+        while index < members.len() {
+            let member = members[index];
+            for other in self
+                .grouped_clip_ids(member)
+                .into_iter()
+                .chain(self.linked_clip_ids(member))
+            {
+                if !members.contains(&other) {
+                    members.push(other);
+                }
+            }
+            index += 1;
+        }
+        members
+    }
+
+    /// Move a clip to `new_start_time` and shift its linked and grouped clips
+    /// by the same amount, keeping their offsets. The shift is limited so no
+    /// clip in the block starts before zero.
+    pub fn move_clip_with_partners(&mut self, id: Uuid, new_start_time: f64) -> bool {
+        let Some(start) = self.clips.iter().find(|clip| clip.id == id).map(|clip| clip.start_time) else {
+            return false;
+        };
+        let moving = self.clips_moving_with(id);
+        let earliest = self
+            .clips
+            .iter()
+            .filter(|clip| moving.contains(&clip.id))
+            .map(|clip| clip.start_time)
+            .fold(f64::INFINITY, f64::min);
+        let delta = (new_start_time - start).max(-earliest.max(0.0));
+        for clip in self.clips.iter_mut().filter(|clip| moving.contains(&clip.id)) {
+            clip.start_time = (clip.start_time + delta).max(0.0);
+        }
+        self.mark_edited();
+        true
+    }
+
+    /// Resize a clip and move the same edges of its linked clips by the same
+    /// amounts, so trimming a video clip trims its audio too.
+    pub fn resize_linked_clips(&mut self, id: Uuid, new_start: f64, new_duration: f64) -> bool {
+        let Some((old_start, old_duration)) = self
+            .clips
+            .iter()
+            .find(|clip| clip.id == id)
+            .map(|clip| (clip.start_time, clip.duration))
+        else {
+            return false;
+        };
+        if !self.resize_clip(id, new_start, new_duration) {
+            return false;
+        }
+        // Use what the resize actually applied after its own clamping
+        let Some((start, duration)) = self
+            .clips
+            .iter()
+            .find(|clip| clip.id == id)
+            .map(|clip| (clip.start_time, clip.duration))
+        else {
+            return false;
+        };
+        let start_delta = start - old_start;
+        let duration_delta = duration - old_duration;
+        for partner in self.linked_clip_ids(id) {
+            if partner == id {
+                continue;
+            }
+            let Some((partner_start, partner_duration)) = self
+                .clips
+                .iter()
+                .find(|clip| clip.id == partner)
+                .map(|clip| (clip.start_time, clip.duration))
+            else {
+                continue;
+            };
+            self.resize_clip(partner, partner_start + start_delta, partner_duration + duration_delta);
+        }
+        true
+    }
+
+    /// Remove a clip together with its linked clips. Returns the removed ids.
+    pub fn remove_linked_clips(&mut self, id: Uuid) -> Vec<Uuid> {
+        let linked = self.linked_clip_ids(id);
+        if !linked.is_empty() {
+            self.clips.retain(|clip| !linked.contains(&clip.id));
+            self.mark_edited();
+        }
+        linked
+    }
+
     /// Update the transform for a clip.
     pub fn set_clip_transform(&mut self, id: Uuid, transform: ClipTransform) -> bool {
         if let Some(clip) = self.clips.iter_mut().find(|c| c.id == id) {
             clip.transform = transform;
+            self.mark_edited();
             return true;
         }
         false
     }
 
+    /// Restore a clip's transform to the default. Returns true when it changed
+    /// (so the preview needs a redraw).
+    pub fn reset_clip_transform(&mut self, id: Uuid) -> bool {
+        let Some(clip) = self.clips.iter_mut().find(|c| c.id == id) else {
+            return false;
+        };
+        if clip.transform == ClipTransform::default() {
+            return false;
+        }
+        clip.transform = ClipTransform::default();
+        self.mark_edited();
+        true
+    }
+
     /// Move a clip to the nearest compatible track above or below.
     pub fn move_clip_to_adjacent_track(&mut self, id: Uuid, direction: i32) -> bool {
         if direction == 0 {
@@ -462,6 +1220,7 @@ impl Project {
             let track = &self.tracks[index as usize];
             if track.track_type == target_track_type {
                 self.clips[clip_index].track_id = track.id;
+                self.mark_edited();
                 return true;
             }
             index += direction.signum();
@@ -474,7 +1233,11 @@ impl Project {
     pub fn remove_marker(&mut self, id: Uuid) -> bool {
         let len = self.markers.len();
         self.markers.retain(|m| m.id != id);
-        self.markers.len() < len
+        let removed = self.markers.len() < len;
+        if removed {
+            self.mark_edited();
+        }
+        removed
     }
 
     /// Move a track up in the list (visually higher)
@@ -482,6 +1245,7 @@ impl Project {
         if let Some(index) = self.tracks.iter().position(|t| t.id == id) {
             if index > 0 {
                 self.tracks.swap(index, index - 1);
+                self.mark_edited();
                 return true;
             }
         }
@@ -493,16 +1257,99 @@ impl Project {
         if let Some(index) = self.tracks.iter().position(|t| t.id == id) {
             if index < self.tracks.len() - 1 {
                 self.tracks.swap(index, index + 1);
+                self.mark_edited();
                 return true;
             }
         }
         false
     }
+
+    /// Move a track to `index` in the list. The target is clamped to the span of
+    /// tracks sharing its type, so video and audio tracks stay grouped and a drop
+    /// past the top or bottom lands at the end of that group. Returns false if
+    /// the track is missing or doesn't move.
+    pub fn reorder_track(&mut self, id: Uuid, index: usize) -> bool {
+        let Some(from) = self.tracks.iter().position(|t| t.id == id) else {
+            return false;
+        };
+        let track_type = self.tracks[from].track_type;
+        let same_type: Vec<usize> = self
+            .tracks
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| t.track_type == track_type)
+            .map(|(i, _)| i)
+            .collect();
+        let first = same_type.first().copied().unwrap_or(from);
+        let last = same_type.last().copied().unwrap_or(from);
+        let to = index.clamp(first, last);
+        if to == from {
+            return false;
+        }
+        let track = self.tracks.remove(from);
+        self.tracks.insert(to, track);
+        self.mark_edited();
+        true
+    }
+
+    /// Apply `update` to a track. Returns false if there is no such track.
+    pub fn update_track(&mut self, track_id: Uuid, update: impl FnOnce(&mut Track)) -> bool {
+        let Some(track) = self.tracks.iter_mut().find(|track| track.id == track_id) else {
+            return false;
+        };
+        update(track);
+        self.mark_edited();
+        true
+    }
+
+    /// Toggle a track between collapsed and expanded. Returns false if the track is missing.
+    pub fn toggle_track_collapsed(&mut self, id: Uuid) -> bool {
+        match self.tracks.iter_mut().find(|t| t.id == id) {
+            Some(track) => {
+                track.collapsed = !track.collapsed;
+                self.mark_edited();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Toggle a track's mute flag. Returns the new state, or `None` if the track is missing.
+    pub fn toggle_track_mute(&mut self, id: Uuid) -> Option<bool> {
+        let track = self.tracks.iter_mut().find(|t| t.id == id)?;
+        track.muted = !track.muted;
+        let muted = track.muted;
+        self.mark_edited();
+        Some(muted)
+    }
+
+    /// Toggle a track's solo flag. Returns the new state, or `None` if the track is missing.
+    pub fn toggle_track_solo(&mut self, id: Uuid) -> Option<bool> {
+        let track = self.tracks.iter_mut().find(|t| t.id == id)?;
+        track.soloed = !track.soloed;
+        let soloed = track.soloed;
+        self.mark_edited();
+        Some(soloed)
+    }
+
+    /// Whether clips on a track should render and play. Mute always wins; while
+    /// any track is soloed, unsoloed tracks are silenced without touching their
+    /// own mute flag.
+    pub fn track_is_active(&self, id: Uuid) -> bool {
+        let Some(track) = self.find_track(id) else {
+            return false;
+        };
+        if track.muted {
+            return false;
+        }
+        track.soloed || !self.tracks.iter().any(|t| t.soloed)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::state::{default_waveform_color, frame_fit_scale, Asset, BlendMode, FrameFit};
 
     #[test]
     fn test_default_project() {
@@ -513,6 +1360,42 @@ mod tests {
         assert_eq!(project.tracks[2].track_type, TrackType::Marker);
     }
 
+    #[test]
+    fn test_unused_assets_excludes_assets_on_the_timeline() {
+        let mut project = Project::new("Unused");
+        let video_track = project.tracks[0].id;
+        let audio_track = project.tracks[1].id;
+        let used_video = project.add_asset(Asset::new_video("used", PathBuf::from("video/used.mp4")));
+        let spare_video = project.add_asset(Asset::new_video("spare", PathBuf::from("video/spare.mp4")));
+        let used_audio = project.add_asset(Asset::new_audio("music", PathBuf::from("audio/music.wav")));
+        let spare_gen = project.add_asset(Asset::new_generative_image(
+            "Gen Image 1",
+            PathBuf::from("generated/image/a"),
+        ));
+        let input_image = project.add_asset(Asset::new_image("ref", PathBuf::from("images/ref.png")));
+        assert_eq!(project.unused_assets().len(), 5);
+
+        // Used twice still counts once
+        project.add_clip(Clip::new(used_video, video_track, 0.0, 2.0));
+        project.add_clip(Clip::new(used_video, video_track, 4.0, 2.0));
+        project.add_clip(Clip::new(used_audio, audio_track, 0.0, 5.0));
+        // Feeding a generative asset keeps an asset even without a clip
+        project
+            .generative_configs
+            .entry(spare_gen)
+            .or_default()
+            .inputs
+            .insert("image".to_string(), InputValue::AssetRef { asset_id: input_image });
+        assert_eq!(project.unused_assets(), vec![spare_video, spare_gen]);
+
+        project.remove_asset(spare_gen);
+        assert_eq!(project.unused_assets(), vec![spare_video, input_image]);
+
+        project.clips.clear();
+        project.remove_asset(spare_video);
+        assert_eq!(project.unused_assets(), vec![used_video, used_audio, input_image]);
+    }
+
     #[test]
     fn test_clip_overlap() {
         let clip = Clip::new(Uuid::new_v4(), Uuid::new_v4(), 5.0, 10.0);
@@ -532,6 +1415,21 @@ mod tests {
         assert_eq!(project.tracks.len(), parsed.tracks.len());
     }
 
+    #[test]
+    fn test_background_color_round_trip() {
+        let mut project = Project::default();
+        assert_eq!(project.settings.background_color, [0, 0, 0, 255]);
+        project.settings.background_color = [0, 177, 64, 128];
+        let json = serde_json::to_string(&project).unwrap();
+        let parsed: Project = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.settings.background_color, [0, 177, 64, 128]);
+
+        // Settings saved before the field existed load as opaque black
+        let old = r#"{ "width": 1280, "height": 720, "fps": 30.0 }"#;
+        let settings: ProjectSettings = serde_json::from_str(old).unwrap();
+        assert_eq!(settings.background_color, [0, 0, 0, 255]);
+    }
+
     #[test]
     fn test_add_tracks() {
         let mut project = Project::default();
@@ -545,4 +1443,756 @@ mod tests {
         assert_eq!(project.tracks.len(), initial_count + 2);
         assert_eq!(project.tracks.last().unwrap().name, "Audio 2");
     }
+
+    #[test]
+    fn test_track_collapse_persists() {
+        let mut project = Project::default();
+        let track_id = project.tracks[0].id;
+        assert!(project.toggle_track_collapsed(track_id));
+        assert!(!project.toggle_track_collapsed(Uuid::new_v4()));
+
+        let json = serde_json::to_string(&project).unwrap();
+        let parsed: Project = serde_json::from_str(&json).unwrap();
+        assert!(parsed.tracks[0].collapsed);
+        assert!(!parsed.tracks[1].collapsed);
+    }
+
+    #[test]
+    fn test_reorder_track_within_type() {
+        let mut project = Project::default();
+        let video_1 = project.tracks[0].id;
+        let video_2 = Track::new("Video 2", TrackType::Video);
+        let video_3 = Track::new("Video 3", TrackType::Video);
+        let (video_2, video_3) = {
+            let ids = (video_2.id, video_3.id);
+            project.tracks.insert(1, video_2);
+            project.tracks.insert(2, video_3);
+            ids
+        };
+        let ids = |project: &Project| project.tracks.iter().map(|t| t.id).collect::<Vec<_>>();
+        assert_eq!(ids(&project)[..3], [video_1, video_2, video_3]);
+
+        assert!(project.reorder_track(video_3, 0));
+        assert_eq!(ids(&project)[..3], [video_3, video_1, video_2]);
+
+        // Dragging past the bottom stops at the last video track
+        assert!(project.reorder_track(video_3, 99));
+        assert_eq!(ids(&project)[..3], [video_1, video_2, video_3]);
+        assert_eq!(project.tracks[3].track_type, TrackType::Audio);
+
+        // Audio can't be dropped among the video tracks
+        let audio = project.tracks[3].id;
+        assert!(!project.reorder_track(audio, 0));
+        assert_eq!(project.tracks[3].id, audio);
+        assert!(!project.reorder_track(Uuid::new_v4(), 0));
+    }
+
+    #[test]
+    fn test_track_mute_solo() {
+        let mut project = Project::default();
+        let video = project.tracks[0].id;
+        let audio = project.tracks[1].id;
+        assert!(project.track_is_active(video));
+
+        assert_eq!(project.toggle_track_mute(video), Some(true));
+        assert!(!project.track_is_active(video));
+        assert!(project.track_is_active(audio));
+
+        // Soloing audio silences video implicitly; unmuting video stays silenced
+        assert_eq!(project.toggle_track_solo(audio), Some(true));
+        assert_eq!(project.toggle_track_mute(video), Some(false));
+        assert!(!project.track_is_active(video));
+        assert!(!project.tracks[0].muted);
+        assert!(project.track_is_active(audio));
+
+        // A muted solo track stays silent
+        project.toggle_track_mute(audio);
+        assert!(!project.track_is_active(audio));
+        project.toggle_track_mute(audio);
+
+        assert_eq!(project.toggle_track_solo(audio), Some(false));
+        assert!(project.track_is_active(video));
+        assert_eq!(project.toggle_track_solo(Uuid::new_v4()), None);
+    }
+
+    #[test]
+    fn test_track_mute_solo_serde_defaults() {
+        let json = r#"{ "id": "6f1c2d4e-8a9b-4c3d-9e2f-1a2b3c4d5e6f", "name": "Video 1", "track_type": "Video" }"#;
+        let track: Track = serde_json::from_str(json).unwrap();
+        assert!(!track.muted);
+        assert!(!track.soloed);
+
+        let mut track = track;
+        track.muted = true;
+        track.soloed = true;
+        let parsed: Track = serde_json::from_str(&serde_json::to_string(&track).unwrap()).unwrap();
+        assert!(parsed.muted);
+        assert!(parsed.soloed);
+    }
+
+    #[test]
+    fn test_track_waveform_color_defaults() {
+        let json = r#"{ "id": "6f1c2d4e-8a9b-4c3d-9e2f-1a2b3c4d5e6f", "name": "Audio 1", "track_type": "Audio" }"#;
+        let mut track: Track = serde_json::from_str(json).unwrap();
+        assert_eq!(track.waveform_color, None);
+        assert_eq!(track.resolved_waveform_color(), default_waveform_color(TrackType::Audio));
+        assert_ne!(
+            default_waveform_color(TrackType::Audio),
+            default_waveform_color(TrackType::Video)
+        );
+        assert_eq!(
+            Track::default_video().resolved_waveform_color(),
+            default_waveform_color(TrackType::Video)
+        );
+
+        track.waveform_color = Some([255, 0, 128]);
+        let parsed: Track = serde_json::from_str(&serde_json::to_string(&track).unwrap()).unwrap();
+        assert_eq!(parsed.resolved_waveform_color(), [255, 0, 128]);
+    }
+
+    #[test]
+    fn test_freeze_clip_tail() {
+        let mut project = Project::default();
+        let track_id = project.tracks[0].id;
+        let mut clip = Clip::new(Uuid::new_v4(), track_id, 2.0, 4.0);
+        clip.trim_in_seconds = 1.0;
+        let clip_id = project.add_clip(clip);
+
+        let frozen_id = project.freeze_clip_from(clip_id, 3.5).unwrap();
+        assert_ne!(frozen_id, clip_id);
+        let head = project.clips.iter().find(|c| c.id == clip_id).unwrap();
+        let tail = project.clips.iter().find(|c| c.id == frozen_id).unwrap();
+        assert!((head.duration - 1.5).abs() < 1e-9);
+        assert!(!head.is_frozen());
+        assert!((tail.start_time - 3.5).abs() < 1e-9);
+        assert!((tail.end_time() - 6.0).abs() < 1e-9);
+        assert!(tail.is_frozen());
+        // Every frame of the tail shows the source frame at the split point.
+        assert!((tail.source_time_at(3.5) - 2.5).abs() < 1e-9);
+        assert!((tail.source_time_at(5.9) - 2.5).abs() < 1e-9);
+
+        // Past the end there is nothing to freeze.
+        assert!(project.freeze_clip_from(frozen_id, 6.0).is_none());
+    }
+
+    #[test]
+    fn test_paste_clips_offsets_with_new_ids() {
+        let mut project = Project::default();
+        let video_track = project.tracks[0].id;
+        let audio_track = project.tracks[1].id;
+        let mut first = Clip::new(Uuid::new_v4(), video_track, 2.0, 1.0);
+        first.transform.scale_x = 2.0;
+        let second = Clip::new(Uuid::new_v4(), audio_track, 3.5, 2.0);
+        let copied = vec![first.clone(), second.clone()];
+
+        let pasted = project.paste_clips(&copied, 10.0, None);
+        assert_eq!(pasted.len(), 2);
+        assert!(pasted.iter().all(|id| *id != first.id && *id != second.id));
+        assert_ne!(pasted[0], pasted[1]);
+
+        let a = project.clips.iter().find(|c| c.id == pasted[0]).unwrap();
+        let b = project.clips.iter().find(|c| c.id == pasted[1]).unwrap();
+        assert_eq!(a.start_time, 10.0);
+        assert_eq!(b.start_time, 11.5);
+        assert_eq!(a.asset_id, first.asset_id);
+        assert_eq!(a.transform, first.transform);
+        assert_eq!(a.track_id, video_track);
+        assert_eq!(b.track_id, audio_track);
+
+        // A selected track only takes clips of its own type
+        let second_video = project.add_video_track();
+        let pasted = project.paste_clips(&copied, 0.0, Some(second_video));
+        let a = project.clips.iter().find(|c| c.id == pasted[0]).unwrap();
+        let b = project.clips.iter().find(|c| c.id == pasted[1]).unwrap();
+        assert_eq!(a.track_id, second_video);
+        assert_eq!(b.track_id, audio_track);
+    }
+
+    #[test]
+    fn test_paste_clamps_to_duration() {
+        let mut project = Project::default();
+        project.settings.duration_seconds = 20.0;
+        project.settings.clamp_pasted_clips = true;
+        let track_id = project.tracks[0].id;
+        let copied = vec![Clip::new(Uuid::new_v4(), track_id, 0.0, 4.0)];
+
+        let pasted = project.paste_clips(&copied, 18.0, None);
+        let clip = project.clips.iter().find(|c| c.id == pasted[0]).unwrap();
+        assert_eq!(clip.start_time, 16.0);
+    }
+
+    #[test]
+    fn test_duplicate_clip_after_original() {
+        let mut project = Project::default();
+        let track_id = project.tracks[0].id;
+        let mut clip = Clip::new(Uuid::new_v4(), track_id, 1.0, 2.0);
+        clip.trim_in_seconds = 0.5;
+        clip.volume = 0.4;
+        clip.transform.rotation_deg = 15.0;
+        let clip_id = project.add_clip(clip.clone());
+
+        let dup_id = project.duplicate_clip(clip_id).unwrap();
+        let dup = project.clips.iter().find(|c| c.id == dup_id).unwrap();
+        assert_ne!(dup_id, clip_id);
+        assert_eq!(dup.track_id, track_id);
+        assert_eq!(dup.start_time, 3.0);
+        assert_eq!(dup.duration, 2.0);
+        assert_eq!(dup.trim_in_seconds, 0.5);
+        assert_eq!(dup.volume, 0.4);
+        assert_eq!(dup.transform, clip.transform);
+
+        // The next duplicate skips past the occupied spot and the clip after it
+        project.add_clip(Clip::new(Uuid::new_v4(), track_id, 5.5, 1.0));
+        let next_id = project.duplicate_clip(clip_id).unwrap();
+        let next = project.clips.iter().find(|c| c.id == next_id).unwrap();
+        assert_eq!(next.start_time, 6.5);
+
+        project.settings.allow_duplicate_overlap = true;
+        let overlap_id = project.duplicate_clip(clip_id).unwrap();
+        let overlap = project.clips.iter().find(|c| c.id == overlap_id).unwrap();
+        assert_eq!(overlap.start_time, 3.0);
+    }
+
+    #[test]
+    fn test_clip_fades_default_and_clamp() {
+        let json = r#"{
+            "id": "6f1c2d4e-8a9b-4c3d-9e2f-1a2b3c4d5e6f",
+            "asset_id": "7f1c2d4e-8a9b-4c3d-9e2f-1a2b3c4d5e6f",
+            "track_id": "8f1c2d4e-8a9b-4c3d-9e2f-1a2b3c4d5e6f",
+            "start_time": 1.0,
+            "duration": 4.0
+        }"#;
+        let mut clip: Clip = serde_json::from_str(json).unwrap();
+        assert_eq!(clip.fade_in_seconds, 0.0);
+        assert_eq!(clip.fade_out_seconds, 0.0);
+        assert_eq!(clip.fade_gain_at(1.0), 1.0);
+
+        clip.set_fade_in(1.0);
+        clip.set_fade_out(10.0);
+        assert_eq!(clip.fade_out_seconds, 3.0);
+        clip.set_fade_in(2.0);
+        assert_eq!(clip.fade_in_seconds, 1.0);
+        assert_eq!(clip.fade_gain_at(1.5), 0.5);
+        assert_eq!(clip.transform_at(1.5).opacity, 0.5);
+
+        // Shortening the clip clamps the fades it already has
+        clip.duration = 2.0;
+        assert_eq!(clip.clamped_fades(), (1.0, 1.0));
+    }
+
+    #[test]
+    fn test_source_time_follows_clip_speed() {
+        let mut clip = Clip::new(Uuid::new_v4(), Uuid::new_v4(), 2.0, 4.0);
+        clip.trim_in_seconds = 1.0;
+        for (speed, at_3, at_6) in [(0.5, 1.5, 3.0), (1.0, 2.0, 5.0), (2.0, 3.0, 9.0)] {
+            clip.speed = speed;
+            assert!((clip.source_time_at(2.0) - 1.0).abs() < 1e-9);
+            assert!((clip.source_time_at(3.0) - at_3).abs() < 1e-9);
+            assert!((clip.source_time_at(6.0) - at_6).abs() < 1e-9);
+            assert!((clip.source_duration() - 4.0 * speed).abs() < 1e-9);
+        }
+
+        // Reversed clips run the same sped-up range from the end
+        clip.reversed = true;
+        assert!((clip.source_time_at(2.0) - 9.0).abs() < 1e-9);
+        assert!((clip.source_time_at(3.0) - 7.0).abs() < 1e-9);
+        assert!((clip.source_time_at(6.0) - 1.0).abs() < 1e-9);
+
+        // Out-of-range speeds are clamped
+        clip.speed = 100.0;
+        assert!((clip.playback_speed() - MAX_CLIP_SPEED).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_set_clip_speed_keeps_source_range() {
+        let mut project = Project::default();
+        let mut asset = Asset::new_video("clip.mp4", PathBuf::from("video/clip.mp4"));
+        asset.duration_seconds = Some(10.0);
+        let asset_id = project.add_asset(asset);
+        let mut clip = Clip::new(asset_id, project.tracks[0].id, 0.0, 4.0);
+        clip.trim_in_seconds = 2.0;
+        let clip_id = project.add_clip(clip);
+
+        // Doubling the speed halves the duration over the same source seconds
+        assert!(project.set_clip_speed(clip_id, 2.0));
+        let clip = project.clips.iter().find(|c| c.id == clip_id).unwrap();
+        assert!((clip.duration - 2.0).abs() < 1e-9);
+        assert!((clip.source_time_at(clip.end_time()) - 6.0).abs() < 1e-9);
+
+        // Slowing down stretches it back out
+        assert!(project.set_clip_speed(clip_id, 0.5));
+        let clip = project.clips.iter().find(|c| c.id == clip_id).unwrap();
+        assert!((clip.duration - 8.0).abs() < 1e-9);
+
+        // Speeding up a clip that already reaches the end of its source
+        // can't run past it
+        project.clips.iter_mut().find(|c| c.id == clip_id).unwrap().duration = 20.0;
+        assert!(project.set_clip_speed(clip_id, 1.0));
+        let clip = project.clips.iter().find(|c| c.id == clip_id).unwrap();
+        assert!((clip.duration - 8.0).abs() < 1e-9);
+        assert!(!project.set_clip_speed(Uuid::new_v4(), 2.0));
+    }
+
+    #[test]
+    fn test_frozen_time_survives_trim_and_speed() {
+        let mut project = Project::default();
+        let mut clip = Clip::new(Uuid::new_v4(), project.tracks[0].id, 2.0, 4.0);
+        clip.trim_in_seconds = 1.0;
+        clip.speed = 2.0;
+        let clip_id = project.add_clip(clip);
+
+        // Freezing at the start holds the whole clip on its first frame
+        assert_eq!(project.freeze_clip_from(clip_id, 2.0), Some(clip_id));
+        assert!(project.set_clip_speed(clip_id, 0.5));
+        let clip = project.clips.iter_mut().find(|c| c.id == clip_id).unwrap();
+        clip.trim_in_seconds = 3.0;
+        assert_eq!(clip.freeze_at, Some(1.0));
+        // Every timeline offset, inside the clip or not, shows the held frame
+        for time in [0.0, 2.0, 3.0, 4.5, 5.99, 10.0] {
+            assert!((clip.source_time_at(time) - 1.0).abs() < 1e-9);
+        }
+
+        let parsed: Clip = serde_json::from_str(&serde_json::to_string(&*clip).unwrap()).unwrap();
+        assert_eq!(parsed.freeze_at, Some(1.0));
+    }
+
+    #[test]
+    fn test_clip_color_overrides_track_type_default() {
+        let json = r#"{
+            "id": "6f1c2d4e-8a9b-4c3d-9e2f-1a2b3c4d5e6f",
+            "asset_id": "0b7e4f2a-3c1d-4e5f-8a9b-0c1d2e3f4a5b",
+            "track_id": "9a8b7c6d-5e4f-4a3b-8c2d-1e0f9a8b7c6d",
+            "start_time": 0.0,
+            "duration": 2.0
+        }"#;
+        let clip: Clip = serde_json::from_str(json).unwrap();
+        assert_eq!(clip.color, None);
+        assert_eq!(clip.resolved_color("#22c55e"), "#22c55e");
+
+        let mut project = Project::default();
+        let track_id = project.tracks[0].id;
+        let hero = project.add_clip(Clip::new(Uuid::new_v4(), track_id, 0.0, 2.0));
+        let other = project.add_clip(Clip::new(Uuid::new_v4(), track_id, 2.0, 2.0));
+        assert!(project.set_clip_color(&[hero], Some([249, 115, 22])));
+        // Setting the same color again is not a change
+        assert!(!project.set_clip_color(&[hero], Some([249, 115, 22])));
+
+        let parsed: Project = serde_json::from_str(&serde_json::to_string(&project).unwrap()).unwrap();
+        let color_of = |id: Uuid| {
+            parsed
+                .clips
+                .iter()
+                .find(|clip| clip.id == id)
+                .unwrap()
+                .resolved_color("#22c55e")
+        };
+        assert_eq!(color_of(hero), "#f97316");
+        assert_eq!(color_of(other), "#22c55e");
+
+        assert!(project.set_clip_color(&[hero, other], None));
+        assert!(project.clips.iter().all(|clip| clip.color.is_none()));
+    }
+
+    #[test]
+    fn test_placeholder_metadata() {
+        let track_id = Uuid::new_v4();
+        let placeholder = Clip::placeholder(track_id, 1.0, 3.0, None);
+        assert!(placeholder.placeholder);
+        assert!(placeholder.asset_id.is_nil());
+        assert_eq!(placeholder.placeholder_label().as_deref(), Some("Placeholder"));
+        let named = Clip::placeholder(track_id, 1.0, 3.0, Some("Intro shot".to_string()));
+        assert_eq!(named.placeholder_label().as_deref(), Some("Intro shot"));
+        let blank = Clip::placeholder(track_id, 1.0, 3.0, Some("  ".to_string()));
+        assert_eq!(blank.placeholder_label().as_deref(), Some("Placeholder"));
+        assert_eq!(Clip::new(Uuid::new_v4(), track_id, 0.0, 1.0).placeholder_label(), None);
+
+        // Projects saved before placeholders existed load as ordinary clips
+        let mut value = serde_json::to_value(Clip::new(Uuid::new_v4(), track_id, 0.0, 1.0)).unwrap();
+        value.as_object_mut().unwrap().remove("placeholder");
+        let clip: Clip = serde_json::from_value(value).unwrap();
+        assert!(!clip.placeholder);
+    }
+
+    #[test]
+    fn test_fill_placeholder_with_media() {
+        let mut project = Project::default();
+        let track_id = project.tracks[0].id;
+        let marker_track = project.tracks[2].id;
+        assert_eq!(project.add_placeholder(marker_track, 0.0, 5.0, None), None);
+
+        let placeholder_id = project.add_placeholder(track_id, 2.0, 5.0, None).unwrap();
+        assert_eq!(project.placeholder_at(track_id, 4.0), Some(placeholder_id));
+        assert_eq!(project.placeholder_at(track_id, 7.0), None);
+        assert_eq!(project.placeholder_at(project.tracks[1].id, 4.0), None);
+
+        let mut asset = Asset::new_video("shot.mp4", PathBuf::from("video/shot.mp4"));
+        asset.duration_seconds = Some(3.0);
+        let asset_id = project.add_asset(asset);
+        assert!(!project.fill_placeholder(placeholder_id, Uuid::new_v4()));
+        assert!(project.fill_placeholder(placeholder_id, asset_id));
+        let clip = project.clips.iter().find(|c| c.id == placeholder_id).unwrap();
+        assert!(!clip.placeholder);
+        assert_eq!(clip.asset_id, asset_id);
+        assert_eq!((clip.start_time, clip.duration), (2.0, 3.0));
+        // Only placeholders can be filled
+        assert!(!project.fill_placeholder(placeholder_id, asset_id));
+    }
+
+    #[test]
+    fn test_looping_source_time_wraps_each_cycle() {
+        // A 2s cycle starting 1s into the source, filling 5s: two full loops and a half
+        let mut clip = Clip::new(Uuid::new_v4(), Uuid::new_v4(), 10.0, 5.0);
+        clip.trim_in_seconds = 1.0;
+        clip.loop_to_duration = true;
+        clip.loop_source_seconds = 2.0;
+        for (time, expected) in [(10.0, 1.0), (11.5, 2.5), (12.0, 1.0), (13.25, 2.25), (14.0, 1.0), (14.9, 1.9)] {
+            assert!((clip.source_time_at(time) - expected).abs() < 1e-9, "{}", time);
+        }
+        assert!((clip.source_duration() - 2.0).abs() < 1e-9);
+
+        // At double speed each cycle takes 1s of timeline
+        clip.speed = 2.0;
+        assert!((clip.source_time_at(10.5) - 2.0).abs() < 1e-9);
+        assert!((clip.source_time_at(11.25) - 1.5).abs() < 1e-9);
+
+        // Reversed loops run each cycle back to front
+        clip.speed = 1.0;
+        clip.reversed = true;
+        assert!((clip.source_time_at(10.0) - 3.0).abs() < 1e-9);
+        assert!((clip.source_time_at(12.5) - 2.5).abs() < 1e-9);
+        assert!((clip.source_time_at(14.5) - 2.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_set_clip_loop_fills_and_restores_duration() {
+        let mut project = Project::default();
+        let mut asset = Asset::new_video("loop.mp4", PathBuf::from("video/loop.mp4"));
+        asset.duration_seconds = Some(3.0);
+        let asset_id = project.add_asset(asset);
+        let mut clip = Clip::new(asset_id, project.tracks[0].id, 0.0, 2.0);
+        clip.trim_in_seconds = 0.5;
+        let clip_id = project.add_clip(clip);
+
+        assert!(project.set_clip_loop(clip_id, true));
+        // Looping clips stretch past the end of their source
+        assert!(project.resize_clip(clip_id, 0.0, 7.0));
+        let clip = project.clips.iter().find(|c| c.id == clip_id).unwrap();
+        assert_eq!(clip.loop_length(), Some(2.0));
+        assert!((clip.duration - 7.0).abs() < 1e-9);
+        assert!((clip.source_time_at(6.5) - 1.0).abs() < 1e-9);
+
+        // Turning it off leaves what the source can cover from the trim point
+        assert!(project.set_clip_loop(clip_id, false));
+        let clip = project.clips.iter().find(|c| c.id == clip_id).unwrap();
+        assert_eq!(clip.loop_length(), None);
+        assert!((clip.duration - 2.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_left_trim_keeps_source_frames_in_place() {
+        let mut project = Project::default();
+        let mut asset = Asset::new_video("trim.mp4", PathBuf::from("video/trim.mp4"));
+        asset.duration_seconds = Some(10.0);
+        let asset_id = project.add_asset(asset);
+        let mut clip = Clip::new(asset_id, project.tracks[0].id, 4.0, 4.0);
+        clip.trim_in_seconds = 2.0;
+        let clip_id = project.add_clip(clip);
+        let clip_of = |project: &Project| project.clips.iter().find(|c| c.id == clip_id).unwrap().clone();
+
+        // Trimming in from the left: the frame at 6s still shows source 4s
+        assert!(project.resize_clip(clip_id, 5.0, 3.0));
+        let clip = clip_of(&project);
+        assert!((clip.trim_in_seconds - 3.0).abs() < 1e-9);
+        assert!((clip.start_time - clip.trim_in_seconds - 2.0).abs() < 1e-9);
+        assert!((clip.source_time_at(6.0) - 4.0).abs() < 1e-9);
+
+        // Extending left past the first source frame stops there, end unchanged
+        assert!(project.resize_clip(clip_id, 0.5, 7.5));
+        let clip = clip_of(&project);
+        assert!((clip.start_time - 2.0).abs() < 1e-9);
+        assert!(clip.trim_in_seconds.abs() < 1e-9);
+        assert!((clip.end_time() - 8.0).abs() < 1e-9);
+        assert!((clip.source_time_at(6.0) - 4.0).abs() < 1e-9);
+
+        // The right edge stops at the last source frame
+        assert!(project.resize_clip(clip_id, 2.0, 20.0));
+        assert!((clip_of(&project).duration - 10.0).abs() < 1e-9);
+
+        // At double speed the trim moves two source seconds per timeline second
+        assert!(project.set_clip_speed(clip_id, 2.0));
+        assert!(project.resize_clip(clip_id, 3.0, 4.0));
+        let clip = clip_of(&project);
+        assert!((clip.trim_in_seconds - 2.0).abs() < 1e-9);
+        assert!((clip.source_time_at(5.0) - 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_reversed_source_time_without_trim() {
+        let mut clip = Clip::new(Uuid::new_v4(), Uuid::new_v4(), 1.0, 3.0);
+        clip.reversed = true;
+        assert!((clip.source_time_at(1.0) - 3.0).abs() < 1e-9);
+        assert!((clip.source_time_at(2.5) - 1.5).abs() < 1e-9);
+        assert!((clip.source_time_at(4.0) - 0.0).abs() < 1e-9);
+        // Times outside the clip hold its first and last frames
+        assert!((clip.source_time_at(0.0) - 3.0).abs() < 1e-9);
+        assert!((clip.source_time_at(9.0) - 0.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_reversed_source_time() {
+        let mut clip = Clip::new(Uuid::new_v4(), Uuid::new_v4(), 2.0, 4.0);
+        clip.trim_in_seconds = 1.0;
+        clip.reversed = true;
+        assert!((clip.source_time_at(2.0) - 5.0).abs() < 1e-9);
+        assert!((clip.source_time_at(5.0) - 2.0).abs() < 1e-9);
+
+        // Freezing keeps the frame that was on screen at the split.
+        let mut project = Project::default();
+        clip.track_id = project.tracks[0].id;
+        let clip_id = project.add_clip(clip);
+        let frozen_id = project.freeze_clip_from(clip_id, 3.0).unwrap();
+        let head = project.clips.iter().find(|c| c.id == clip_id).unwrap();
+        let tail = project.clips.iter().find(|c| c.id == frozen_id).unwrap();
+        assert!((head.source_time_at(2.0) - 5.0).abs() < 1e-9);
+        assert!((head.source_time_at(3.0) - 4.0).abs() < 1e-9);
+        assert!((tail.source_time_at(4.0) - 4.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_add_marker_at_snaps_and_skips_duplicates() {
+        let mut project = Project::default();
+        let id = project.add_marker_at(2.51, 10.0).unwrap();
+        let marker = project.markers.iter().find(|marker| marker.id == id).unwrap();
+        assert!((marker.time - 2.5).abs() < 1e-9);
+        assert_eq!(marker.label, None);
+        assert_eq!(marker.color, None);
+
+        // Same frame: no second marker
+        assert_eq!(project.add_marker_at(2.49, 10.0), None);
+        assert_eq!(project.marker_on_frame(2.5, 10.0), Some(id));
+
+        // Markers stay sorted by time
+        let early = project.add_marker_at(1.0, 10.0).unwrap();
+        assert_eq!(project.markers[0].id, early);
+        assert_eq!(project.markers.len(), 2);
+    }
+
+    #[test]
+    fn test_marker_fields_default_on_old_projects() {
+        let old = r#"{ "id": "6f1c2a52-63a9-4b43-9d39-8d5fd9d2c0a1", "time": 3.0 }"#;
+        let marker: Marker = serde_json::from_str(old).unwrap();
+        assert_eq!(marker.time, 3.0);
+        assert_eq!(marker.label, None);
+        assert_eq!(marker.description, None);
+        assert_eq!(marker.color, None);
+
+        // Projects saved without a markers list load with none
+        let mut json = serde_json::to_value(Project::default()).unwrap();
+        json.as_object_mut().unwrap().remove("markers");
+        let parsed: Project = serde_json::from_value(json).unwrap();
+        assert!(parsed.markers.is_empty());
+    }
+
+    #[test]
+    fn test_linked_clips_move_and_trim_together() {
+        let mut project = Project::default();
+        let video_track = project.tracks[0].id;
+        let audio_track = project.tracks[1].id;
+        let asset_id = Uuid::new_v4();
+        let video = project.add_clip(Clip::new(asset_id, video_track, 2.0, 5.0));
+        let audio = project.add_clip(Clip::new(asset_id, audio_track, 2.5, 5.0));
+        let other = project.add_clip(Clip::new(asset_id, audio_track, 10.0, 1.0));
+        assert!(project.link_clips(&[video, audio]).is_some());
+        assert_eq!(project.linked_clip_ids(other), vec![other]);
+
+        let start_of = |project: &Project, id: Uuid| {
+            project.clips.iter().find(|clip| clip.id == id).unwrap().start_time
+        };
+
+        // Moving either one shifts its partner by the same delta
+        assert!(project.move_clip_with_partners(video, 3.0));
+        assert!((start_of(&project, audio) - 3.5).abs() < 1e-9);
+        assert!(project.move_clip_with_partners(audio, 4.5));
+        assert!((start_of(&project, video) - 4.0).abs() < 1e-9);
+        assert!((start_of(&project, other) - 10.0).abs() < 1e-9);
+
+        // The group can't be pushed before zero
+        assert!(project.move_clip_with_partners(audio, 0.0));
+        assert!((start_of(&project, video) - 0.0).abs() < 1e-9);
+        assert!((start_of(&project, audio) - 0.5).abs() < 1e-9);
+
+        // Trimming the end trims the partner's end too
+        assert!(project.resize_linked_clips(video, 0.0, 4.0));
+        let partner = project.clips.iter().find(|clip| clip.id == audio).unwrap();
+        assert!((partner.duration - 4.0).abs() < 1e-9);
+
+        // Deleting one removes the group
+        let removed = project.remove_linked_clips(audio);
+        assert_eq!(removed.len(), 2);
+        assert_eq!(project.clips.len(), 1);
+    }
+
+    #[test]
+    fn test_unlink_stops_propagation() {
+        let mut project = Project::default();
+        let video_track = project.tracks[0].id;
+        let audio_track = project.tracks[1].id;
+        let asset_id = Uuid::new_v4();
+        let video = project.add_clip(Clip::new(asset_id, video_track, 2.0, 5.0));
+        let audio = project.add_clip(Clip::new(asset_id, audio_track, 2.0, 5.0));
+        project.link_clips(&[video, audio]).unwrap();
+
+        assert!(project.unlink_clip(video));
+        // The lone partner is unlinked as well
+        assert!(project.clips.iter().all(|clip| clip.link_group.is_none()));
+        assert!(project.move_clip_with_partners(video, 6.0));
+        let audio_start = project.clips.iter().find(|clip| clip.id == audio).unwrap().start_time;
+        assert!((audio_start - 2.0).abs() < 1e-9);
+        assert!(!project.unlink_clip(video));
+    }
+
+    #[test]
+    fn test_pasted_links_are_remapped() {
+        let mut project = Project::default();
+        let video_track = project.tracks[0].id;
+        let audio_track = project.tracks[1].id;
+        let asset_id = Uuid::new_v4();
+        let video = project.add_clip(Clip::new(asset_id, video_track, 0.0, 2.0));
+        let audio = project.add_clip(Clip::new(asset_id, audio_track, 0.0, 2.0));
+        let group = project.link_clips(&[video, audio]).unwrap();
+
+        let copied = project.clips.clone();
+        let pasted = project.paste_clips(&copied, 5.0, None);
+        assert_eq!(pasted.len(), 2);
+        let pasted_group = project.clips.iter().find(|clip| clip.id == pasted[0]).unwrap().link_group;
+        assert!(pasted_group.is_some());
+        assert_ne!(pasted_group, Some(group));
+        assert_eq!(project.linked_clip_ids(pasted[0]).len(), 2);
+        assert_eq!(project.linked_clip_ids(video).len(), 2);
+    }
+
+    #[test]
+    fn test_group_selection_expands() {
+        let mut project = Project::default();
+        let video_track = project.tracks[0].id;
+        let audio_track = project.tracks[1].id;
+        let asset_id = Uuid::new_v4();
+        let a = project.add_clip(Clip::new(asset_id, video_track, 0.0, 2.0));
+        let b = project.add_clip(Clip::new(asset_id, audio_track, 4.0, 2.0));
+        let c = project.add_clip(Clip::new(asset_id, video_track, 8.0, 2.0));
+        assert_eq!(project.group_clips(&[a]), None);
+        assert!(project.group_clips(&[a, b]).is_some());
+
+        // The clicked clip stays primary and brings its group along
+        assert_eq!(project.clip_selection_for(b), vec![b, a]);
+        assert_eq!(project.clip_selection_for(c), vec![c]);
+
+        assert!(project.ungroup_clips(&[a]));
+        assert_eq!(project.clip_selection_for(b), vec![b]);
+        assert!(!project.ungroup_clips(&[a, b]));
+    }
+
+    #[test]
+    fn test_grouped_move_keeps_offsets() {
+        let mut project = Project::default();
+        let video_track = project.tracks[0].id;
+        let audio_track = project.tracks[1].id;
+        let asset_id = Uuid::new_v4();
+        let a = project.add_clip(Clip::new(asset_id, video_track, 1.0, 2.0));
+        let b = project.add_clip(Clip::new(asset_id, audio_track, 4.0, 2.0));
+        let c = project.add_clip(Clip::new(asset_id, video_track, 8.0, 2.0));
+        project.group_clips(&[a, b]).unwrap();
+        let start_of = |project: &Project, id: Uuid| {
+            project.clips.iter().find(|clip| clip.id == id).unwrap().start_time
+        };
+
+        assert!(project.move_clip_with_partners(b, 6.0));
+        assert!((start_of(&project, a) - 3.0).abs() < 1e-9);
+        assert!((start_of(&project, b) - 6.0).abs() < 1e-9);
+        assert!((start_of(&project, c) - 8.0).abs() < 1e-9);
+
+        // A clip linked to a group member moves with the block too
+        let d = project.add_clip(Clip::new(asset_id, audio_track, 3.0, 2.0));
+        project.link_clips(&[a, d]).unwrap();
+        assert!(project.move_clip_with_partners(b, 5.0));
+        assert!((start_of(&project, a) - 2.0).abs() < 1e-9);
+        assert!((start_of(&project, d) - 2.0).abs() < 1e-9);
+
+        // Moving left stops when the earliest clip reaches zero
+        assert!(project.move_clip_with_partners(b, 0.0));
+        assert!((start_of(&project, a) - 0.0).abs() < 1e-9);
+        assert!((start_of(&project, b) - 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_reset_clip_transform() {
+        let mut project = Project::default();
+        let track_id = project.tracks[0].id;
+        let clip_id = project.add_clip(Clip::new(Uuid::new_v4(), track_id, 0.0, 1.0));
+        project.set_clip_transform(
+            clip_id,
+            ClipTransform {
+                position_x: 40.0,
+                scale_y: 2.5,
+                rotation_deg: 15.0,
+                opacity: 0.4,
+                blend_mode: BlendMode::Screen,
+                ..ClipTransform::default()
+            },
+        );
+
+        // A change asks for a redraw; resetting an untouched transform doesn't
+        assert!(project.reset_clip_transform(clip_id));
+        let clip = project.clips.iter().find(|clip| clip.id == clip_id).unwrap();
+        assert_eq!(clip.transform, ClipTransform::default());
+        assert!(!project.reset_clip_transform(clip_id));
+        assert!(!project.reset_clip_transform(Uuid::new_v4()));
+    }
+
+    #[test]
+    fn test_fit_and_fill_scale_across_aspects() {
+        let frame = (1920, 1080);
+        // A 4:3 source fits to the frame height and fills to its width
+        assert_eq!(frame_fit_scale((1440, 1080), frame, FrameFit::Fit), Some(1.0));
+        let fill = frame_fit_scale((1440, 1080), frame, FrameFit::Fill).unwrap();
+        assert!((fill - 1920.0 / 1440.0).abs() < 1e-6);
+
+        // A tall 9:16 source letterboxes against the height and crops to the width
+        let fit = frame_fit_scale((1080, 1920), frame, FrameFit::Fit).unwrap();
+        assert!((fit - 1080.0 / 1920.0).abs() < 1e-6);
+        assert_eq!(frame_fit_scale((1080, 1920), frame, FrameFit::Fill), Some(1920.0 / 1080.0));
+
+        // Matching aspect ratios agree, and zero sizes have no answer
+        assert_eq!(frame_fit_scale((960, 540), frame, FrameFit::Fit), Some(2.0));
+        assert_eq!(frame_fit_scale((960, 540), frame, FrameFit::Fill), Some(2.0));
+        assert_eq!(frame_fit_scale((0, 540), frame, FrameFit::Fit), None);
+
+        // Applying a preset recenters but keeps rotation and opacity
+        let transform = ClipTransform {
+            position_x: 30.0,
+            rotation_deg: 45.0,
+            opacity: 0.5,
+            ..ClipTransform::default()
+        };
+        let fitted = transform.fitted_to_frame((1440, 1080), frame, FrameFit::Fill).unwrap();
+        assert_eq!(fitted.position_x, 0.0);
+        assert_eq!(fitted.scale_x, fitted.scale_y);
+        assert_eq!(fitted.rotation_deg, 45.0);
+        assert_eq!(fitted.opacity, 0.5);
+    }
+
+    #[test]
+    fn test_asset_dimensions_only_cached_for_files() {
+        let mut project = Project::default();
+        let image = Asset::new_image("still.png", PathBuf::from("media/still.png"));
+        let image_id = image.id;
+        project.add_asset(image);
+        assert!(project.set_asset_dimensions(image_id, 640, 480));
+        assert_eq!(project.find_asset(image_id).unwrap().source_dimensions(), Some((640, 480)));
+
+        let audio = Asset::new_audio("voice.wav", PathBuf::from("media/voice.wav"));
+        let audio_id = audio.id;
+        project.add_asset(audio);
+        assert!(!project.set_asset_dimensions(audio_id, 640, 480));
+    }
 }
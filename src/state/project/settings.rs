@@ -18,12 +18,160 @@ pub struct ProjectSettings {
     /// Preview downsample height in pixels
     #[serde(default = "default_preview_max_height")]
     pub preview_max_height: u32,
+    /// Waveform peak resolution (peaks per second at the finest level)
+    #[serde(default = "default_waveform_peaks_per_second")]
+    pub waveform_peaks_per_second: f64,
+    /// Shift pasted clips back so they end inside `duration_seconds`
+    /// instead of extending the timeline.
+    #[serde(default)]
+    pub clamp_pasted_clips: bool,
+    /// Place duplicates right after the original even if that overlaps
+    /// the next clip, instead of sliding them to the next free gap.
+    #[serde(default)]
+    pub allow_duplicate_overlap: bool,
+    /// Pack each edited track end to end so moves, inserts and deletes
+    /// never leave gaps or overlaps.
+    #[serde(default)]
+    pub magnetic_timeline: bool,
+    /// How the ruler and current-time readout label time
+    #[serde(default)]
+    pub time_format: TimeFormat,
+    /// Attempts per generation job before a transient failure is final
+    #[serde(default = "default_generation_max_attempts")]
+    pub generation_max_attempts: u8,
+    /// Generation jobs allowed to run at once across all providers
+    #[serde(default = "default_generation_max_concurrent")]
+    pub generation_max_concurrent: u8,
+    /// Memory budget for decoded preview frames, in megabytes
+    #[serde(default = "default_preview_cache_mb")]
+    pub preview_cache_mb: u32,
+    /// Composite the preview on the CPU even when the GPU surface is available
+    #[serde(default)]
+    pub force_cpu_preview: bool,
+    /// Preview assets from their low-resolution proxies where they have one
+    /// (exports always use the originals)
+    #[serde(default)]
+    pub use_proxies: bool,
+    /// RGBA color the composite is cleared to before drawing layers
+    #[serde(default = "default_background_color")]
+    pub background_color: [u8; 4],
+    /// Overlay guides shown over the preview (view only, never exported)
+    #[serde(default)]
+    pub preview_guides: PreviewGuides,
+    /// Order of the assets panel list (view only)
+    #[serde(default)]
+    pub asset_sort: crate::state::AssetSort,
+    /// Resampler used when decoding audio for playback and waveforms
+    /// (WAV and video exports always use `Sinc`)
+    #[serde(default)]
+    pub preview_resample_quality: ResampleQuality,
+}
+
+/// Interpolation used when converting decoded audio to the target sample rate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResampleQuality {
+    /// Cheapest; good enough for scrubbing and waveform peaks
+    Linear,
+    /// Short cubic kernel
+    Cubic,
+    /// Windowed-sinc filter, FFmpeg's default and what decoding always used
+    #[default]
+    Sinc,
+}
+
+impl ResampleQuality {
+    pub const ALL: [ResampleQuality; 3] = [
+        ResampleQuality::Linear,
+        ResampleQuality::Cubic,
+        ResampleQuality::Sinc,
+    ];
+
+    /// Stable identifier, matching the serialized form.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ResampleQuality::Linear => "linear",
+            ResampleQuality::Cubic => "cubic",
+            ResampleQuality::Sinc => "sinc",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ResampleQuality::Linear => "Linear (fast)",
+            ResampleQuality::Cubic => "Cubic",
+            ResampleQuality::Sinc => "Sinc (best)",
+        }
+    }
+
+    pub fn from_id(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|quality| quality.as_str() == value)
+    }
+}
+
+/// Which overlay guides the preview draws.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PreviewGuides {
+    /// Title-safe box (90% of the frame)
+    pub title_safe: bool,
+    /// Action-safe box (93% of the frame)
+    pub action_safe: bool,
+    /// Rule-of-thirds grid
+    pub thirds: bool,
+    /// Cross at the frame center
+    pub center: bool,
+}
+
+impl PreviewGuides {
+    pub fn any(&self) -> bool {
+        self.title_safe || self.action_safe || self.thirds || self.center
+    }
+}
+
+/// Time display style for the timeline ruler and readout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TimeFormat {
+    /// Minutes and seconds (`1:05`)
+    #[default]
+    Seconds,
+    /// SMPTE timecode (`00:01:05:12`), drop-frame at 29.97/59.94
+    Timecode,
+    /// Absolute frame number
+    Frames,
+}
+
+impl TimeFormat {
+    /// The next format when cycling through them from the readout.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Seconds => Self::Timecode,
+            Self::Timecode => Self::Frames,
+            Self::Frames => Self::Seconds,
+        }
+    }
 }
 
 fn default_project_duration_seconds() -> f64 {
     60.0
 }
 
+fn default_generation_max_attempts() -> u8 {
+    4
+}
+
+fn default_generation_max_concurrent() -> u8 {
+    1
+}
+
+fn default_preview_cache_mb() -> u32 {
+    8 * 1024
+}
+
+fn default_background_color() -> [u8; 4] {
+    [0, 0, 0, 255]
+}
+
 fn default_preview_max_width() -> u32 {
     960
 }
@@ -32,6 +180,11 @@ fn default_preview_max_height() -> u32 {
     540
 }
 
+fn default_waveform_peaks_per_second() -> f64 {
+    // 256-sample blocks at 48 kHz.
+    187.5
+}
+
 impl Default for ProjectSettings {
     fn default() -> Self {
         Self {
@@ -41,6 +194,27 @@ impl Default for ProjectSettings {
             duration_seconds: default_project_duration_seconds(),
             preview_max_width: default_preview_max_width(),
             preview_max_height: default_preview_max_height(),
+            waveform_peaks_per_second: default_waveform_peaks_per_second(),
+            clamp_pasted_clips: false,
+            allow_duplicate_overlap: false,
+            magnetic_timeline: false,
+            time_format: TimeFormat::default(),
+            generation_max_attempts: default_generation_max_attempts(),
+            generation_max_concurrent: default_generation_max_concurrent(),
+            preview_cache_mb: default_preview_cache_mb(),
+            force_cpu_preview: false,
+            use_proxies: false,
+            background_color: default_background_color(),
+            preview_guides: PreviewGuides::default(),
+            asset_sort: crate::state::AssetSort::default(),
+            preview_resample_quality: ResampleQuality::default(),
         }
     }
 }
+
+impl ProjectSettings {
+    /// `preview_cache_mb` in bytes.
+    pub fn preview_cache_budget_bytes(&self) -> usize {
+        self.preview_cache_mb as usize * 1024 * 1024
+    }
+}
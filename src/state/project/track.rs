@@ -24,6 +24,18 @@ pub struct Track {
     /// Track volume (applies to audio playback for audio/video clips).
     #[serde(default = "default_volume")]
     pub volume: f32,
+    /// Collapsed tracks render as a thin strip with plain clip bars.
+    #[serde(default)]
+    pub collapsed: bool,
+    /// Muted tracks neither render nor play.
+    #[serde(default)]
+    pub muted: bool,
+    /// While any track is soloed, only soloed tracks render and play.
+    #[serde(default)]
+    pub soloed: bool,
+    /// Waveform color for clips on this track; `None` follows the track type.
+    #[serde(default)]
+    pub waveform_color: Option<[u8; 3]>,
 }
 
 impl Track {
@@ -34,9 +46,19 @@ impl Track {
             name: name.into(),
             track_type,
             volume: 1.0,
+            collapsed: false,
+            muted: false,
+            soloed: false,
+            waveform_color: None,
         }
     }
 
+    /// Color waveforms on this track are drawn in.
+    pub fn resolved_waveform_color(&self) -> [u8; 3] {
+        self.waveform_color
+            .unwrap_or_else(|| default_waveform_color(self.track_type))
+    }
+
     /// Create the default video track
     pub fn default_video() -> Self {
         Self::new("Video 1", TrackType::Video)
@@ -53,6 +75,15 @@ impl Track {
     }
 }
 
+/// Default waveform color: a light tint of the track type's accent.
+pub fn default_waveform_color(track_type: TrackType) -> [u8; 3] {
+    match track_type {
+        TrackType::Video => [134, 239, 172],
+        TrackType::Audio => [147, 197, 253],
+        TrackType::Marker => [160, 160, 160],
+    }
+}
+
 fn default_volume() -> f32 {
     1.0
 }
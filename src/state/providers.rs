@@ -4,6 +4,7 @@
 //! Providers describe external generation backends (ComfyUI, APIs, etc.).
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use uuid::Uuid;
 
 /// The output media type produced by a provider entry.
@@ -41,6 +42,36 @@ pub struct ProviderInputField {
     pub default: Option<serde_json::Value>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub ui: Option<InputUi>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub random_range: Option<RandomRange>,
+}
+
+impl ProviderInputField {
+    /// Min, max, and step a batch samples this input from when it is flagged
+    /// to randomize. `None` for non-numeric inputs or when no bound is known.
+    pub fn randomization_bounds(&self) -> Option<(f64, f64, Option<f64>)> {
+        if !matches!(self.input_type, ProviderInputType::Integer | ProviderInputType::Number) {
+            return None;
+        }
+        let ui = self.ui.as_ref();
+        let range = self.random_range.as_ref();
+        let min = range.and_then(|range| range.min).or_else(|| ui.and_then(|ui| ui.min))?;
+        let max = range.and_then(|range| range.max).or_else(|| ui.and_then(|ui| ui.max))?;
+        if !min.is_finite() || !max.is_finite() || min > max {
+            return None;
+        }
+        Some((min, max, ui.and_then(|ui| ui.step)))
+    }
+}
+
+/// Range a numeric input is randomized within across a batch. Missing bounds
+/// fall back to the input's `ui` min/max; the `ui` step still applies.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RandomRange {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
 }
 
 /// Connection configuration for a provider entry.
@@ -55,6 +86,93 @@ pub enum ProviderConnection {
         manifest_path: Option<String>,
     },
     CustomHttp { base_url: String, api_key: Option<String> },
+    /// Templated JSON API (Replicate/Fal style), see `providers::http`.
+    Http(HttpProviderConfig),
+}
+
+/// Request template and response mapping for a generic HTTP provider.
+///
+/// The URL, header values, and body strings may reference inputs as
+/// `{{name}}` and environment variables as `{{env:NAME}}` (only `NLA_`
+/// prefixed ones resolve).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HttpProviderConfig {
+    pub request: HttpRequestTemplate,
+    /// Present for APIs that queue the job and return a status URL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub poll: Option<HttpPollConfig>,
+    /// Path to the generated media URL in the final response, e.g. `output[0]`.
+    pub response_path: String,
+    /// Checked by the health probe; defaults to the request URL's origin.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health_url: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HttpRequestTemplate {
+    #[serde(default = "default_http_method")]
+    pub method: String,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<serde_json::Value>,
+}
+
+fn default_http_method() -> String {
+    "POST".to_string()
+}
+
+/// How to follow a queued job until it finishes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HttpPollConfig {
+    /// Path to the status URL in the submit response, e.g. `urls.get`.
+    pub url_path: String,
+    /// Path to the job status in each poll response.
+    pub status_path: String,
+    #[serde(default = "default_http_done_values")]
+    pub done_values: Vec<String>,
+    #[serde(default = "default_http_failed_values")]
+    pub failed_values: Vec<String>,
+    #[serde(default = "default_http_poll_interval_ms")]
+    pub interval_ms: u64,
+    #[serde(default = "default_http_poll_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_http_done_values() -> Vec<String> {
+    vec!["succeeded".to_string(), "completed".to_string()]
+}
+
+fn default_http_failed_values() -> Vec<String> {
+    vec!["failed".to_string(), "canceled".to_string(), "error".to_string()]
+}
+
+fn default_http_poll_interval_ms() -> u64 {
+    1000
+}
+
+fn default_http_poll_timeout_secs() -> u64 {
+    600
+}
+
+/// Where a provider entry was loaded from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ProviderScope {
+    /// Shared across every project on this machine.
+    #[default]
+    Global,
+    /// Stored inside the project folder and travels with it.
+    Project,
+}
+
+impl ProviderScope {
+    pub fn label(self) -> &'static str {
+        match self {
+            ProviderScope::Global => "Global",
+            ProviderScope::Project => "Project",
+        }
+    }
 }
 
 /// A configured provider entry stored on disk.
@@ -66,6 +184,17 @@ pub struct ProviderEntry {
     #[serde(default)]
     pub inputs: Vec<ProviderInputField>,
     pub connection: ProviderConnection,
+    /// Optional group shown in provider lists; blank means uncategorized.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub favorite: bool,
+    /// Jobs this provider's endpoint may run at once; unset means one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent: Option<u32>,
+    /// Set by the provider store when loading; not part of the file format.
+    #[serde(skip)]
+    pub scope: ProviderScope,
 }
 
 impl ProviderEntry {
@@ -80,8 +209,52 @@ impl ProviderEntry {
             output_type,
             inputs: Vec::new(),
             connection,
+            category: None,
+            favorite: false,
+            max_concurrent: None,
+            scope: ProviderScope::Global,
+        }
+    }
+
+    pub fn category_label(&self) -> &str {
+        provider_category_label(self.category.as_deref())
+    }
+}
+
+pub const UNCATEGORIZED_PROVIDER_CATEGORY: &str = "Uncategorized";
+
+pub fn provider_category_label(category: Option<&str>) -> &str {
+    category
+        .map(str::trim)
+        .filter(|category| !category.is_empty())
+        .unwrap_or(UNCATEGORIZED_PROVIDER_CATEGORY)
+}
+
+/// Group providers by category for display.
+///
+/// Categories sort alphabetically with "Uncategorized" last; inside a group favorites
+/// come first, then names alphabetically.
+pub fn group_providers_by_category(entries: &[ProviderEntry]) -> Vec<(String, Vec<ProviderEntry>)> {
+    let mut groups: Vec<(String, Vec<ProviderEntry>)> = Vec::new();
+    for entry in entries {
+        let label = entry.category_label();
+        match groups.iter_mut().find(|(category, _)| category == label) {
+            Some((_, members)) => members.push(entry.clone()),
+            None => groups.push((label.to_string(), vec![entry.clone()])),
         }
     }
+    groups.sort_by(|(a, _), (b, _)| {
+        (a == UNCATEGORIZED_PROVIDER_CATEGORY, a.to_lowercase())
+            .cmp(&(b == UNCATEGORIZED_PROVIDER_CATEGORY, b.to_lowercase()))
+    });
+    for (_, members) in groups.iter_mut() {
+        members.sort_by(|a, b| {
+            b.favorite
+                .cmp(&a.favorite)
+                .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+        });
+    }
+    groups
 }
 
 pub fn input_value_as_string(value: &serde_json::Value) -> Option<String> {
@@ -138,6 +311,15 @@ pub enum ProviderManifest {
         inputs: Vec<CustomHttpInput>,
         output: CustomHttpOutput,
     },
+    Http {
+        schema_version: u32,
+        #[serde(default)]
+        name: Option<String>,
+        output_type: ProviderOutputType,
+        #[serde(default)]
+        inputs: Vec<ProviderInputField>,
+        connection: HttpProviderConfig,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -158,6 +340,8 @@ pub struct ManifestInput {
     pub default: Option<serde_json::Value>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub ui: Option<InputUi>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub random_range: Option<RandomRange>,
     pub bind: InputBinding,
 }
 
@@ -205,11 +389,23 @@ pub struct ComfyOutputSelector {
     pub index: Option<u32>,
 }
 
+/// Adjustment applied to an input value before it is written into the
+/// workflow node, e.g. seconds * fps -> frame count.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum BindingTransform {
     Clamp { min: f64, max: f64 },
-    Scale { factor: f64 },
+    #[serde(alias = "scale")]
+    Multiply { factor: f64 },
+    Offset { amount: f64 },
+    /// Round to the nearest integer.
+    Round,
+    /// Text with `{value}` replaced by the input.
+    Format { template: String },
+    /// RFC 6901 pointer into a JSON input (objects or JSON text).
+    JsonPointer { pointer: String },
+    /// Apply each step in order.
+    Chain { steps: Vec<BindingTransform> },
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
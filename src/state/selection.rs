@@ -33,6 +33,12 @@ impl SelectionState {
         self.clip_ids.push(clip_id);
     }
 
+    /// Replace the selection with the given clips.
+    pub fn select_clips(&mut self, clip_ids: Vec<Uuid>) {
+        self.clear();
+        self.clip_ids = clip_ids;
+    }
+
     /// Remove a clip from selection, if present.
     pub fn remove_clip(&mut self, clip_id: Uuid) {
         self.clip_ids.retain(|id| *id != clip_id);
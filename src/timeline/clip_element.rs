@@ -1,45 +1,97 @@
 use dioxus::prelude::*;
 use std::collections::HashMap;
 use std::fs;
+use std::cell::RefCell;
 use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::Arc;
 use std::time::Instant;
 
 use crate::constants::{
     ACCENT_VIDEO,
     BG_ELEVATED,
+    BG_SURFACE,
     BORDER_ACCENT,
     BORDER_DEFAULT,
     BORDER_SUBTLE,
+    TEXT_MUTED,
     TEXT_PRIMARY,
     TIMELINE_SNAP_THRESHOLD_PX,
 };
-use crate::core::timeline_snap::{best_snap_delta_frames, frames_from_seconds, seconds_from_frames, SnapTarget};
-use crate::core::audio::cache::{cache_matches_source, load_peak_cache, peak_cache_path, PeakCache};
-use crate::core::audio::waveform::{build_and_store_peak_cache, resolve_audio_source, PeakBuildConfig};
+use crate::core::timeline_snap::{
+    best_snap_delta_frames, drag_left_edge, drag_right_edge, fade_seconds_from_drag,
+    frames_from_seconds, seconds_from_frames, SnapTarget,
+};
+use crate::core::audio::cache::{load_peak_cache, peak_cache_path, shared_peak_caches, PeakCache};
+use crate::core::audio::normalize::clip_normalize_gain_from_cache;
+use crate::core::audio::waveform::{
+    load_shared_peak_cache, resolve_audio_source, run_peak_cache_build, PeakBuildConfig,
+    PeakBuildProgress,
+};
 
 use image::codecs::bmp::BmpEncoder;
 use image::{ColorType, ImageEncoder};
 
-use super::{MAX_THUMB_TILES, MIN_CLIP_WIDTH_FLOOR_PX, MIN_CLIP_WIDTH_PX, MIN_CLIP_WIDTH_SCALE, THUMB_TILE_WIDTH_PX};
+use super::thumb_tiles::{
+    resolve_thumb_tile_urls, thumb_tile_layout, ThumbTileKey, ThumbTileRequest, ThumbTileRequests,
+    ThumbTileStrip, THUMB_TILE_DEBOUNCE_MS,
+};
+use futures_util::StreamExt;
+use super::{
+    truncate_middle, CLIP_LABEL_CHAR_WIDTH_PX, CLIP_LABEL_MIN_WIDTH_PX, MIN_CLIP_WIDTH_FLOOR_PX,
+    MIN_CLIP_WIDTH_PX, MIN_CLIP_WIDTH_SCALE, THUMB_TILE_WIDTH_PX,
+};
+
+/// Colors offered in the clip context menu for color-coding clips.
+const CLIP_COLOR_SWATCHES: [(&str, [u8; 3]); 8] = [
+    ("Red", [239, 68, 68]),
+    ("Orange", [249, 115, 22]),
+    ("Yellow", [234, 179, 8]),
+    ("Green", [34, 197, 94]),
+    ("Teal", [20, 184, 166]),
+    ("Blue", [59, 130, 246]),
+    ("Purple", [168, 85, 247]),
+    ("Pink", [236, 72, 153]),
+];
 
 /// Interactive clip element with drag, resize, and context menu support
 #[component]
 pub(crate) fn ClipElement(
     clip: crate::state::Clip,
+    // Clips linked or grouped with this one, which move along with it
+    partner_clip_ids: Vec<uuid::Uuid>,
     assets: Vec<crate::state::Asset>,
     thumbnailer: std::sync::Arc<crate::core::thumbnailer::Thumbnailer>,
     thumbnail_cache_buster: u64,
+    // Bumps every few seconds so strips retry while thumbnails are generating
+    thumbnail_refresh_tick: u64,
     clip_cache_buckets: std::sync::Arc<HashMap<uuid::Uuid, Vec<bool>>>,
     project_root: Option<std::path::PathBuf>,
     audio_waveform_cache_buster: Signal<u64>,
+    waveform_peaks_per_second: f64,
+    resample_quality: crate::state::ResampleQuality,
     zoom: f64,
     fps: f64,
-    clip_color: &'static str,
+    clip_color: String,
+    waveform_color: [u8; 3],
+    collapsed: bool,
     on_delete: EventHandler<uuid::Uuid>,
     on_move: EventHandler<(uuid::Uuid, f64)>,
     on_resize: EventHandler<(uuid::Uuid, f64, f64)>,  // (id, new_start, new_duration)
     on_move_track: EventHandler<(uuid::Uuid, i32)>,
     is_selected: bool,
+    // Shares time with another clip on the same track
+    overlapping: bool,
+    current_time: f64,
+    on_freeze_from: EventHandler<(uuid::Uuid, f64)>,
+    // Link this clip with the current selection
+    on_link: EventHandler<uuid::Uuid>,
+    on_unlink: EventHandler<uuid::Uuid>,
+    // Group every selected clip
+    on_group_selection: EventHandler<()>,
+    on_ungroup: EventHandler<uuid::Uuid>,
+    on_set_color: EventHandler<(uuid::Uuid, Option<[u8; 3]>)>,
+    on_fade: EventHandler<(uuid::Uuid, f64, f64)>,  // (id, fade_in, fade_out)
     on_select: EventHandler<uuid::Uuid>,
     on_snap_preview: EventHandler<Option<f64>>,
     snap_targets: std::sync::Arc<Vec<SnapTarget>>,
@@ -52,6 +104,7 @@ pub(crate) fn ClipElement(
     let mut drag_start_duration = use_signal(|| 0.0);
     let mut drag_start_end_time = use_signal(|| 0.0);
     let mut drag_start_offset = use_signal(|| 0.0);
+    let mut drag_start_fade = use_signal(|| 0.0);
     let fps = fps.max(1.0);
 
     let left = (clip.start_time * zoom) as i32;
@@ -63,7 +116,12 @@ pub(crate) fn ClipElement(
     let filtered_snap_targets: Vec<SnapTarget> = snap_targets
         .iter()
         .copied()
-        .filter(|target| target.clip_id != Some(clip_id))
+        // Partners move along with this clip, so their edges can't be snap targets
+        .filter(|target| {
+            target
+                .clip_id
+                .map_or(true, |id| id != clip_id && !partner_clip_ids.contains(&id))
+        })
         .collect();
     let cache_buckets = clip_cache_buckets
         .get(&clip.id)
@@ -86,64 +144,173 @@ pub(crate) fn ClipElement(
         .filter(|label| !label.is_empty())
         .map(|label| label.to_string())
         .unwrap_or_else(|| asset_name.clone());
-    let display_name = match asset.and_then(|asset| asset.active_version()) {
-        Some(version) => format!("{} ({})", base_name, version),
-        None => base_name,
+    let placeholder_label = clip.placeholder_label();
+    let is_placeholder = placeholder_label.is_some();
+    let active_version = asset.and_then(|asset| asset.active_version());
+    let label_tooltip = match &placeholder_label {
+        Some(label) => label.clone(),
+        None => {
+            let mut lines = vec![base_name.clone(), format!("Asset: {}", asset_name)];
+            if let Some(version) = active_version {
+                lines.push(format!("Version: {}", version));
+            }
+            lines.join("\n")
+        }
+    };
+    let display_name = match placeholder_label {
+        Some(label) => label,
+        None => match active_version {
+            Some(version) => format!("{} ({})", base_name, version),
+            None => base_name,
+        },
     };
     let is_generative = asset.map(|a| a.is_generative()).unwrap_or(false);
     let is_visual = asset.map(|a| a.is_visual()).unwrap_or(false);
     let is_audio = asset.map(|a| a.is_audio()).unwrap_or(false);
+    let is_frozen = clip.is_frozen();
+    let is_linked = clip.link_group.is_some();
+    let is_grouped = clip.group_id.is_some();
+    let custom_color = clip.color;
+    let is_reversed = clip.reversed && !is_frozen;
+    let speed_badge = if is_frozen || (clip.playback_speed() - 1.0).abs() < 1e-6 {
+        String::new()
+    } else {
+        format!("{}x ", (clip.playback_speed() * 100.0).round() / 100.0)
+    };
+    // Peaks are built front to back; mirror the strip for reversed clips.
+    let waveform_flip = if is_reversed { "scaleX(-1)" } else { "none" };
+    let can_freeze = asset.map(|a| a.is_video()).unwrap_or(false)
+        && current_time >= clip.start_time
+        && current_time < clip.end_time();
+    // Looping clips keep their cycle when trimmed and can stretch to any length
+    let is_looping = clip.loop_length().is_some();
+    // Badges take roughly two characters each; the name gets what's left of
+    // the body after its 10px side padding and the 9px color bar.
+    let badge_chars = 2 * [is_generative, is_frozen, is_reversed, is_looping, is_linked, overlapping]
+        .iter()
+        .filter(|shown| **shown)
+        .count()
+        + speed_badge.chars().count();
+    let show_label = clip_width_f >= CLIP_LABEL_MIN_WIDTH_PX;
+    let label_chars = ((clip_width_f - 29.0) / CLIP_LABEL_CHAR_WIDTH_PX).floor().max(0.0) as usize;
+    let display_name = truncate_middle(&display_name, label_chars.saturating_sub(badge_chars));
     let has_source_trim = asset
         .map(|a| a.is_video() || a.is_audio())
-        .unwrap_or(false);
+        .unwrap_or(false)
+        && !is_looping;
     let trim_in_seconds = clip.trim_in_seconds.max(0.0);
     let max_duration = asset.and_then(|a| {
-        if a.is_video() || a.is_audio() {
+        if (a.is_video() || a.is_audio()) && !is_looping {
             a.duration_seconds.filter(|duration| *duration > 0.0)
         } else {
             None
         }
     });
-    let available_duration = max_duration.map(|duration| (duration - trim_in_seconds).max(0.0));
+    // Timeline seconds left in the source after the trim, at the clip's speed
+    let available_duration = max_duration
+        .map(|duration| clip.timeline_duration_for_source((duration - trim_in_seconds).max(0.0)));
+    let trim_in_timeline_seconds = clip.timeline_duration_for_source(trim_in_seconds);
     
-    let first_thumb_url = if is_visual {
-        thumbnailer.get_thumbnail_path(clip.asset_id, trim_in_seconds).map(|p| {
-            let url = crate::utils::get_local_file_url(&p);
-            format!("{}?v={}", url, thumbnail_cache_buster)
-        })
+    // Tile URLs resolve in the background; the last finished strip stays up
+    // until a newer one is ready.
+    let mut thumb_strip = use_signal(|| None::<ThumbTileStrip>);
+    let thumb_task = use_coroutine({
+        let thumbnailer = thumbnailer.clone();
+        move |mut rx: UnboundedReceiver<Option<ThumbTileRequest>>| {
+            let thumbnailer = thumbnailer.clone();
+            async move {
+                let requests = ThumbTileRequests::default();
+                let mut next = rx.next().await;
+                while let Some(request) = next.take() {
+                    let ticket = requests.begin();
+                    let Some(ThumbTileRequest { layout, key, refresh_tick }) = request else {
+                        thumb_strip.set(None);
+                        next = rx.next().await;
+                        continue;
+                    };
+                    let stale = thumb_strip.peek().as_ref().map_or(true, |strip| {
+                        strip.key != key || (!strip.complete && strip.refresh_tick != refresh_tick)
+                    });
+                    if !stale {
+                        next = rx.next().await;
+                        continue;
+                    }
+                    // A newer layout during the debounce replaces this one
+                    tokio::select! {
+                        newer = rx.next() => {
+                            next = newer;
+                            continue;
+                        }
+                        _ = tokio::time::sleep(std::time::Duration::from_millis(THUMB_TILE_DEBOUNCE_MS)) => {}
+                    }
+                    let tile_width = layout.tile_width;
+                    let asset_id = key.asset_id;
+                    let cache_buster = key.cache_buster;
+                    let thumbnailer = thumbnailer.clone();
+                    let worker_ticket = ticket.clone();
+                    let resolving = tokio::task::spawn_blocking(move || {
+                        resolve_thumb_tile_urls(
+                            &thumbnailer,
+                            asset_id,
+                            &layout.source_times,
+                            cache_buster,
+                            &worker_ticket,
+                        )
+                    });
+                    tokio::select! {
+                        newer = rx.next() => {
+                            // The ticket is superseded, so the worker stops early
+                            requests.cancel();
+                            next = newer;
+                            continue;
+                        }
+                        resolved = resolving => {
+                            if let Ok(Some((urls, complete))) = resolved {
+                                thumb_strip.set(Some(ThumbTileStrip {
+                                    key,
+                                    tile_width,
+                                    urls,
+                                    complete,
+                                    refresh_tick,
+                                }));
+                            }
+                        }
+                    }
+                    next = rx.next().await;
+                }
+            }
+        }
+    });
+    // Only a changed layout (or refresh tick) is sent to the tile task
+    let thumb_sent = use_hook(|| Rc::new(RefCell::new(None::<Option<(ThumbTileKey, u64)>>)));
+    let thumb_layout = if is_visual && !collapsed {
+        thumb_tile_layout(&clip, clip_width_f, zoom)
     } else {
         None
     };
-    
-    let mut thumb_tiles: Vec<String> = Vec::new();
-    let mut tile_width = THUMB_TILE_WIDTH_PX;
-    
-    if let Some(fallback_url) = first_thumb_url.clone() {
-        if clip_width > 40 {
-            let estimated_tiles = (clip_width_f / tile_width).ceil() as usize;
-            if estimated_tiles > MAX_THUMB_TILES {
-                tile_width = (clip_width_f / MAX_THUMB_TILES as f64).ceil();
-            }
-            let tile_count = (clip_width_f / tile_width).ceil() as usize;
-            let tile_count = tile_count.max(1);
-            let tile_time = tile_width / zoom;
-            
-            for i in 0..tile_count {
-                let time_in_clip = (i as f64 * tile_time).min(clip.duration.max(0.0));
-                let time = trim_in_seconds + time_in_clip;
-                let url = thumbnailer
-                    .get_thumbnail_path(clip.asset_id, time)
-                    .map(|p| {
-                        let url = crate::utils::get_local_file_url(&p);
-                        format!("{}?v={}", url, thumbnail_cache_buster)
-                    })
-                    .unwrap_or_else(|| fallback_url.clone());
-                thumb_tiles.push(url);
-            }
-        }
+    let thumb_request = thumb_layout.map(|layout| ThumbTileRequest {
+        key: ThumbTileKey::new(clip.asset_id, thumbnail_cache_buster, &layout),
+        layout,
+        refresh_tick: thumbnail_refresh_tick,
+    });
+    let sent = thumb_request
+        .as_ref()
+        .map(|request| (request.key.clone(), request.refresh_tick));
+    if thumb_sent.borrow().as_ref() != Some(&sent) {
+        *thumb_sent.borrow_mut() = Some(sent);
+        thumb_task.send(thumb_request);
     }
-    
-    let border_style = if is_generative {
+    let (thumb_tiles, tile_width) = thumb_strip
+        .read()
+        .as_ref()
+        .map(|strip| (strip.urls.clone(), strip.tile_width))
+        .unwrap_or_else(|| (Vec::new(), THUMB_TILE_WIDTH_PX));
+
+    let border_style = if overlapping {
+        "1px solid #ef4444".to_string()
+    } else if is_placeholder {
+        format!("1px dashed {}", TEXT_MUTED)
+    } else if is_generative {
         format!("1px dashed {}", clip_color)
     } else {
         format!("1px solid {}", clip_color)
@@ -154,11 +321,15 @@ pub(crate) fn ClipElement(
         "none".to_string()
     };
 
-    let mut waveform_cache = use_signal(|| None::<PeakCache>);
+    let mut waveform_cache = use_signal(|| None::<Arc<PeakCache>>);
     let mut waveform_building = use_signal(|| false);
     let waveform_cache_buster = audio_waveform_cache_buster;
     let mut waveform_last_buster = use_signal(|| 0_u64);
     let mut waveform_bitmap_cache = use_signal(|| None::<(WaveformKey, String)>);
+    // While a build runs: (progress fraction, partial update count).
+    let mut waveform_progress = use_signal(|| None::<(Option<f32>, u32)>);
+    // Bumped per build and per cache reload so a stale build can't publish
+    let mut waveform_build_seq = use_signal(|| 0_u64);
 
     let waveform_buster_value = waveform_cache_buster();
     if is_audio {
@@ -166,30 +337,26 @@ pub(crate) fn ClipElement(
             let asset_id = asset.id;
             let cache_path = peak_cache_path(&project_root, asset_id);
             let source_path = resolve_audio_source(&project_root, &asset);
+            let peak_config = PeakBuildConfig::with_peaks_per_second(waveform_peaks_per_second)
+                .with_resample_quality(resample_quality);
 
             if waveform_last_buster() != waveform_buster_value {
-                let mut loaded = None;
-                if let Some(source_path) = source_path.as_ref() {
-                    if cache_path.exists() {
-                        match load_peak_cache(&cache_path)
-                            .and_then(|cache| {
-                                if cache_matches_source(&cache, source_path)? {
-                                    Ok(Some(cache))
-                                } else {
-                                    Ok(None)
-                                }
-                            }) {
-                            Ok(cache) => {
-                                loaded = cache;
-                            }
-                            Err(_err) => {}
-                        }
-                    }
-                }
+                let loaded = source_path.as_ref().and_then(|source_path| {
+                    load_shared_peak_cache(
+                        shared_peak_caches(),
+                        asset_id,
+                        &cache_path,
+                        source_path,
+                        &peak_config,
+                    )
+                    .ok()
+                    .flatten()
+                });
                 waveform_cache.set(loaded);
                 if waveform_bitmap_cache().is_some() {
                     waveform_bitmap_cache.set(None);
                 }
+                waveform_build_seq.set(waveform_build_seq.peek().wrapping_add(1));
                 waveform_last_buster.set(waveform_buster_value);
             }
 
@@ -201,28 +368,70 @@ pub(crate) fn ClipElement(
                     let mut waveform_cache_buster = waveform_cache_buster.clone();
                     let project_root_for_build = project_root.clone();
                     let source_path_for_build = source_path.clone();
+                    let source_path_for_lookup = source_path.clone();
+                    let (progress_tx, mut progress_rx) =
+                        tokio::sync::mpsc::unbounded_channel::<PeakBuildProgress>();
+                    waveform_progress.set(Some((Some(0.0), 0)));
+                    let build_seq = waveform_build_seq.peek().wrapping_add(1);
+                    waveform_build_seq.set(build_seq);
                     spawn(async move {
-                        let build_result = tokio::task::spawn_blocking(move || {
-                            build_and_store_peak_cache(
-                                &project_root_for_build,
-                                asset_id,
-                                &source_path_for_build,
-                                PeakBuildConfig::default(),
-                            )
-                        })
-                        .await
-                        .ok()
-                        .and_then(|res| res.ok());
+                        let build = run_peak_cache_build(
+                            project_root_for_build.clone(),
+                            asset_id,
+                            source_path_for_build,
+                            peak_config,
+                            Some(progress_tx),
+                        );
+                        tokio::pin!(build);
+                        let mut last_partial = None::<Instant>;
+                        let build_result = loop {
+                            tokio::select! {
+                                result = &mut build => break result.ok(),
+                                Some(progress) = progress_rx.recv() => {
+                                    if *waveform_build_seq.peek() != build_seq {
+                                        continue;
+                                    }
+                                    let updates = waveform_progress
+                                        .peek()
+                                        .map(|(_, updates)| updates)
+                                        .unwrap_or(0);
+                                    // Every partial redraw writes a strip bitmap, so only
+                                    // the progress bar follows each tick
+                                    if last_partial.is_some_and(|at| {
+                                        at.elapsed() < std::time::Duration::from_millis(WAVEFORM_PARTIAL_REDRAW_MS)
+                                    }) {
+                                        waveform_progress.set(Some((progress.fraction, updates)));
+                                        continue;
+                                    }
+                                    last_partial = Some(Instant::now());
+                                    waveform_cache.set(Some(Arc::new(progress.partial)));
+                                    waveform_progress.set(Some((progress.fraction, updates + 1)));
+                                }
+                            }
+                        };
 
                         waveform_building.set(false);
+                        waveform_progress.set(None);
+                        let _ = fs::remove_dir_all(waveform_partial_strips_dir(
+                            &project_root_for_build,
+                            asset_id,
+                        ));
+                        if *waveform_build_seq.peek() != build_seq {
+                            return;
+                        }
                         if let Some(cache_path) = build_result {
-                            let load_path = cache_path.clone();
-                            if let Ok(cache) =
-                                tokio::task::spawn_blocking(move || load_peak_cache(&load_path))
+                            // The build registered its peaks; disk is the fallback
+                            let cached = shared_peak_caches()
+                                .get_current(asset_id, &source_path_for_lookup);
+                            let loaded = match cached {
+                                Some(cache) => Ok(cache),
+                                None => tokio::task::spawn_blocking(move || load_peak_cache(&cache_path))
                                     .await
                                     .ok()
                                     .unwrap_or_else(|| Err("Waveform cache load failed".to_string()))
-                            {
+                                    .map(Arc::new),
+                            };
+                            if let Ok(cache) = loaded {
                                 waveform_cache.set(Some(cache));
                                 waveform_cache_buster
                                     .set(waveform_cache_buster() + 1);
@@ -243,14 +452,33 @@ pub(crate) fn ClipElement(
     let current_start = clip.start_time;
     let current_duration = clip.duration;
     let current_end = current_start + current_duration;
+    let (fade_in, fade_out) = clip.clamped_fades();
+    let fade_in_px = fade_in * zoom;
+    let fade_out_px = fade_out * zoom;
+    // Fade handles sit inside the resize handles and only on clips wide enough to grab.
+    let show_fade_handles = !collapsed && (is_visual || is_audio) && clip_width > 36;
     
     let is_active = drag_mode().is_some();
     let cursor_style = match drag_mode() {
-        Some("resize-left") | Some("resize-right") => "ew-resize",
+        Some("resize-left") | Some("resize-right") | Some("fade-in") | Some("fade-out") => "ew-resize",
         Some("move") => "grabbing",
         _ => "grab",
     };
     let z_index = if is_active { "100" } else { "1" };
+    // Collapsed tracks draw clips as plain colored bars.
+    let (clip_height, clip_background) = if collapsed {
+        (9, clip_color.clone())
+    } else if is_placeholder {
+        // Hatched so reserved time reads as empty
+        (
+            32,
+            format!(
+                "repeating-linear-gradient(135deg, {BG_ELEVATED} 0 6px, {BG_SURFACE} 6px 12px)"
+            ),
+        )
+    } else {
+        (32, BG_ELEVATED.to_string())
+    };
     let snap_targets = filtered_snap_targets.clone();
     
     rsx! {
@@ -261,8 +489,8 @@ pub(crate) fn ClipElement(
                 left: {left}px;
                 top: 2px;
                 width: {clip_width}px;
-                height: 32px;
-                background-color: {BG_ELEVATED};
+                height: {clip_height}px;
+                background: {clip_background};
                 border: {border_style};
                 box-shadow: {selection_ring};
                 border-radius: 4px;
@@ -300,7 +528,7 @@ pub(crate) fn ClipElement(
                 }
             }
 
-            if is_audio {
+            if is_audio && !collapsed {
                 {
                     let mut waveform_url = String::new();
                     if let Some(cache) = waveform_cache().as_ref() {
@@ -309,18 +537,24 @@ pub(crate) fn ClipElement(
                             .first()
                             .map(|level| level.block_size.max(1))
                             .unwrap_or(1);
-                        let max_columns = ((clip.duration.max(0.0) * cache.sample_rate as f64)
+                        let max_columns = ((clip.source_duration() * cache.sample_rate as f64)
                             / base_block as f64)
                             .ceil() as usize;
                         let mut render_width = clip_width.max(1) as usize;
                         render_width = render_width.min(WAVEFORM_MAX_WIDTH_PX).min(max_columns.max(1));
 
+                        let partial_updates = waveform_progress()
+                            .map(|(_, updates)| updates)
+                            .unwrap_or(0);
                         let key = WaveformKey {
                             buster: waveform_buster_value,
+                            partial: partial_updates,
                             width: render_width,
                             zoom_bits: zoom.to_bits(),
                             trim_bits: trim_in_seconds.to_bits(),
-                            duration_bits: clip.duration.to_bits(),
+                            duration_bits: clip.source_duration().to_bits(),
+                            color: waveform_color,
+                            normalize: clip.normalize,
                         };
 
                         let mut needs_rebuild = true;
@@ -345,12 +579,18 @@ pub(crate) fn ClipElement(
                                     waveform_url = bmp_url.clone();
                                     waveform_bitmap_cache.set(Some((key, bmp_url)));
                                 } else {
+                                    let display_gain = if clip.normalize {
+                                        clip_normalize_gain_from_cache(&clip, cache).unwrap_or(1.0)
+                                    } else {
+                                        1.0
+                                    };
                                     let columns_start = Instant::now();
                                     let columns = waveform_columns_for_clip(
                                         cache,
-                                        clip.duration,
+                                        clip.source_duration(),
                                         trim_in_seconds,
                                         render_width,
+                                        display_gain,
                                     );
                                     let columns_elapsed = columns_start.elapsed();
 
@@ -359,6 +599,7 @@ pub(crate) fn ClipElement(
                                         &columns,
                                         render_width,
                                         WAVEFORM_BMP_HEIGHT_PX,
+                                        waveform_color,
                                     );
                                     let bitmap_elapsed = bitmap_start.elapsed();
 
@@ -393,7 +634,7 @@ pub(crate) fn ClipElement(
                                     position: absolute; left: 0; right: 0; top: 0; bottom: 0;
                                     width: 100%; height: 100%;
                                     pointer-events: none; z-index: 0;
-                                    opacity: 0.6;
+                                    opacity: 0.6; transform: {waveform_flip};
                                 ",
                                 src: "{waveform_url}",
                                 draggable: "false",
@@ -405,7 +646,23 @@ pub(crate) fn ClipElement(
                 }
             }
 
-            if !cache_buckets.is_empty() {
+            if let Some((fraction, _)) = waveform_progress() {
+                {
+                    let percent = fraction.map(|value| value * 100.0).unwrap_or(0.0);
+                    rsx! {
+                        div {
+                            style: "
+                                position: absolute; left: 0; top: 0;
+                                width: {percent}%; height: 2px;
+                                background-color: {BORDER_ACCENT}; opacity: 0.8;
+                                pointer-events: none; z-index: 2;
+                            ",
+                        }
+                    }
+                }
+            }
+
+            if !cache_buckets.is_empty() && !collapsed {
                 div {
                     style: "
                         position: absolute; left: 0; right: 0; bottom: 0;
@@ -500,30 +757,105 @@ pub(crate) fn ClipElement(
                 },
                 
                 // Foreground Content Container (Text + Indicator)
-                div {
-                    style: "
-                        display: flex; align-items: center; width: 100%;
-                        min-width: 0; overflow: hidden;
-                        z-index: 1; position: relative;
-                    ",
-                    // Color indicator bar
+                if !collapsed && show_label {
                     div {
-                        style: "width: 3px; height: 20px; border-radius: 2px; background-color: {clip_color}; flex-shrink: 0; margin-right: 6px;",
-                    }
-                    // Clip name with text shadow for readability over image
-                    span {
                         style: "
-                            font-size: 10px; color: {TEXT_PRIMARY}; 
-                            white-space: nowrap; overflow: hidden; text-overflow: ellipsis;
-                            flex: 1; min-width: 0;
-                            text-shadow: 0 1px 2px rgba(0,0,0,0.8);
+                            display: flex; align-items: center; width: 100%;
+                            min-width: 0; overflow: hidden;
+                            z-index: 1; position: relative;
                         ",
-                        if is_generative { "✨ " } else { "" }
-                        "{display_name}"
+                        // Color indicator bar
+                        div {
+                            style: "width: 3px; height: 20px; border-radius: 2px; background-color: {clip_color}; flex-shrink: 0; margin-right: 6px;",
+                        }
+                        // Clip name with text shadow for readability over image
+                        span {
+                            title: "{label_tooltip}",
+                            style: "
+                                font-size: 10px; color: {TEXT_PRIMARY}; 
+                                white-space: nowrap; overflow: hidden; text-overflow: ellipsis;
+                                flex: 1; min-width: 0;
+                                text-shadow: 0 1px 2px rgba(0,0,0,0.8);
+                            ",
+                            if is_generative { "✨ " } else { "" }
+                            if is_frozen { "❄ " } else { "" }
+                            if is_reversed { "⇆ " } else { "" }
+                            if is_looping { "🔁 " } else { "" }
+                            "{speed_badge}"
+                            if is_linked { "🔗 " } else { "" }
+                            if overlapping {
+                                span {
+                                    style: "color: #ef4444;",
+                                    title: "Overlaps another clip on this track",
+                                    "⚠ "
+                                }
+                            }
+                            "{display_name}"
+                        }
                     }
                 }
             }
             
+            // Fade ramps and their corner handles
+            if show_fade_handles {
+                if fade_in_px > 0.0 {
+                    div {
+                        style: "
+                            position: absolute; left: 0; top: 0; bottom: 0; width: {fade_in_px}px;
+                            background: rgba(0, 0, 0, 0.35);
+                            clip-path: polygon(0 0, 100% 0, 0 100%);
+                            pointer-events: none; z-index: 2; border-radius: 4px 0 0 4px;
+                        ",
+                    }
+                }
+                if fade_out_px > 0.0 {
+                    div {
+                        style: "
+                            position: absolute; right: 0; top: 0; bottom: 0; width: {fade_out_px}px;
+                            background: rgba(0, 0, 0, 0.35);
+                            clip-path: polygon(0 0, 100% 0, 100% 100%);
+                            pointer-events: none; z-index: 2; border-radius: 0 4px 4px 0;
+                        ",
+                    }
+                }
+                div {
+                    style: "
+                        position: absolute; left: {fade_in_px + 6.0}px; top: 0;
+                        width: 0; height: 0; cursor: ew-resize; z-index: 11;
+                        border-top: 7px solid rgba(255, 255, 255, 0.75);
+                        border-right: 7px solid transparent;
+                        transform: translateX(-50%);
+                    ",
+                    title: "Drag to fade in",
+                    onmousedown: move |e| {
+                        e.prevent_default();
+                        e.stop_propagation();
+                        on_select.call(clip_id);
+                        drag_mode.set(Some("fade-in"));
+                        drag_start_x.set(e.client_coordinates().x);
+                        drag_start_fade.set(fade_in);
+                    },
+                }
+                div {
+                    style: "
+                        position: absolute; right: {fade_out_px + 6.0}px; top: 0;
+                        width: 0; height: 0; cursor: ew-resize; z-index: 11;
+                        border-top: 7px solid rgba(255, 255, 255, 0.75);
+                        border-left: 7px solid transparent;
+                        transform: translateX(50%);
+                    ",
+                    title: "Drag to fade out",
+                    onmousedown: move |e| {
+                        e.prevent_default();
+                        e.stop_propagation();
+                        on_select.call(clip_id);
+                        drag_mode.set(Some("fade-out"));
+                        drag_start_x.set(e.client_coordinates().x);
+                        drag_start_fade.set(fade_out);
+                    },
+                }
+            }
+
             // Right resize handle
             div {
                 class: "resize-handle-right",
@@ -663,101 +995,66 @@ pub(crate) fn ClipElement(
                         Some("resize-left") => {
                             // Moving left edge: keep right edge fixed while clamping to source duration
                             let end_frames = frames_from_seconds(drag_start_end_time(), fps).round();
+                            // The first source frame stays put while the edge moves
                             let min_start_frames = if has_source_trim {
                                 frames_from_seconds(
-                                    (current_start - trim_in_seconds).max(0.0),
+                                    (current_start - trim_in_timeline_seconds).max(0.0),
                                     fps,
                                 )
-                                .round()
                             } else {
                                 0.0
                             };
-                            let mut new_start_frames =
-                                frames_from_seconds(drag_start_time(), fps).round() + delta_frames;
-                            let mut snap_target_frame = None;
-                            if snap_enabled {
-                                if let Some(hit) = best_snap_delta_frames(
-                                    &[new_start_frames],
-                                    &snap_targets,
-                                    snap_threshold_frames,
-                                ) {
-                                    new_start_frames += hit.delta_frames;
-                                    snap_target_frame = Some(hit.target.frame);
-                                }
-                            }
-                            new_start_frames = new_start_frames.max(min_start_frames);
-                            let mut new_duration_frames = end_frames - new_start_frames;
-                            if let Some(max_duration) = max_duration {
-                                let max_duration_frames =
-                                    frames_from_seconds(max_duration, fps).round();
-                                if new_duration_frames > max_duration_frames {
-                                    new_duration_frames = max_duration_frames;
-                                    new_start_frames = end_frames - new_duration_frames;
-                                }
-                            }
-                            if new_duration_frames < min_duration_frames {
-                                new_duration_frames = min_duration_frames;
-                                new_start_frames = end_frames - new_duration_frames;
-                            }
-                            let snapped_start_frames = new_start_frames.round().max(0.0);
-                            let snapped_start = seconds_from_frames(snapped_start_frames, fps);
-                            let snapped_duration_frames =
-                                (end_frames - snapped_start_frames).max(min_duration_frames);
-                            let snapped_duration =
-                                seconds_from_frames(snapped_duration_frames, fps);
-                            on_resize.call((clip_id, snapped_start, snapped_duration));
-                            let mut snap_preview = None;
-                            if snap_enabled {
-                                if let Some(target_frame) = snap_target_frame {
-                                    if (snapped_start_frames - target_frame).abs() <= 0.5 {
-                                        snap_preview = Some(seconds_from_frames(target_frame, fps));
-                                    }
-                                }
-                            }
-                            on_snap_preview.call(snap_preview);
+                            let dragged = drag_left_edge(
+                                frames_from_seconds(drag_start_time(), fps).round() + delta_frames,
+                                end_frames,
+                                min_start_frames,
+                                min_duration_frames,
+                                &snap_targets,
+                                if snap_enabled { snap_threshold_frames } else { 0.0 },
+                            );
+                            on_resize.call((
+                                clip_id,
+                                seconds_from_frames(dragged.start_frames, fps),
+                                seconds_from_frames(dragged.duration_frames, fps),
+                            ));
+                            on_snap_preview
+                                .call(dragged.snapped_frame.map(|frame| seconds_from_frames(frame, fps)));
                         }
                         Some("resize-right") => {
                             // Moving right edge: only changes duration, clamped to source duration
                             let start_frames = frames_from_seconds(drag_start_time(), fps).round();
-                            let mut new_end_frames = start_frames
-                                + frames_from_seconds(drag_start_duration(), fps).round()
-                                + delta_frames;
-                            let mut snap_target_frame = None;
-                            if snap_enabled {
-                                if let Some(hit) = best_snap_delta_frames(
-                                    &[new_end_frames],
-                                    &snap_targets,
-                                    snap_threshold_frames,
-                                ) {
-                                    new_end_frames += hit.delta_frames;
-                                    snap_target_frame = Some(hit.target.frame);
-                                }
-                            }
-                            let mut new_duration_frames =
-                                (new_end_frames - start_frames).max(min_duration_frames);
-                            if let Some(available_duration) = available_duration {
-                                let available_frames =
-                                    frames_from_seconds(available_duration, fps).round();
-                                if new_duration_frames > available_frames {
-                                    new_duration_frames = available_frames;
-                                }
-                            }
-                            let snapped_duration_frames =
-                                new_duration_frames.round().max(min_duration_frames);
-                            let snapped_duration =
-                                seconds_from_frames(snapped_duration_frames, fps);
-                            let snapped_start = seconds_from_frames(start_frames, fps);
-                            on_resize.call((clip_id, snapped_start, snapped_duration));
-                            let mut snap_preview = None;
-                            if snap_enabled {
-                                if let Some(target_frame) = snap_target_frame {
-                                    let snapped_end_frames = start_frames + snapped_duration_frames;
-                                    if (snapped_end_frames - target_frame).abs() <= 0.5 {
-                                        snap_preview = Some(seconds_from_frames(target_frame, fps));
-                                    }
-                                }
+                            let dragged = drag_right_edge(
+                                start_frames,
+                                start_frames
+                                    + frames_from_seconds(drag_start_duration(), fps).round()
+                                    + delta_frames,
+                                available_duration.map(|duration| frames_from_seconds(duration, fps)),
+                                min_duration_frames,
+                                &snap_targets,
+                                if snap_enabled { snap_threshold_frames } else { 0.0 },
+                            );
+                            on_resize.call((
+                                clip_id,
+                                seconds_from_frames(start_frames, fps),
+                                seconds_from_frames(dragged.duration_frames, fps),
+                            ));
+                            on_snap_preview
+                                .call(dragged.snapped_frame.map(|frame| seconds_from_frames(frame, fps)));
+                        }
+                        Some(mode @ ("fade-in" | "fade-out")) => {
+                            let is_fade_out = mode == "fade-out";
+                            let fade = fade_seconds_from_drag(
+                                drag_start_fade(),
+                                delta_x,
+                                zoom,
+                                fps,
+                                is_fade_out,
+                            );
+                            if is_fade_out {
+                                on_fade.call((clip_id, fade_in, fade));
+                            } else {
+                                on_fade.call((clip_id, fade, fade_out));
                             }
-                            on_snap_preview.call(snap_preview);
                         }
                         _ => {
                             on_snap_preview.call(None);
@@ -816,6 +1113,104 @@ pub(crate) fn ClipElement(
                     },
                     "Move Down"
                 }
+                if is_linked {
+                    div {
+                        style: "
+                            padding: 6px 12px; color: {TEXT_PRIMARY}; cursor: pointer;
+                            transition: background-color 0.1s ease;
+                        ",
+                        onclick: move |_| {
+                            on_unlink.call(clip_id);
+                            show_menu.set(false);
+                        },
+                        "Unlink"
+                    }
+                } else if !is_selected {
+                    div {
+                        style: "
+                            padding: 6px 12px; color: {TEXT_PRIMARY}; cursor: pointer;
+                            transition: background-color 0.1s ease;
+                        ",
+                        onclick: move |_| {
+                            on_link.call(clip_id);
+                            show_menu.set(false);
+                        },
+                        "🔗 Link with Selection"
+                    }
+                }
+                if is_grouped {
+                    div {
+                        style: "
+                            padding: 6px 12px; color: {TEXT_PRIMARY}; cursor: pointer;
+                            transition: background-color 0.1s ease;
+                        ",
+                        onclick: move |_| {
+                            on_ungroup.call(clip_id);
+                            show_menu.set(false);
+                        },
+                        "Ungroup"
+                    }
+                } else if is_selected {
+                    div {
+                        style: "
+                            padding: 6px 12px; color: {TEXT_PRIMARY}; cursor: pointer;
+                            transition: background-color 0.1s ease;
+                        ",
+                        onclick: move |_| {
+                            on_group_selection.call(());
+                            show_menu.set(false);
+                        },
+                        "Group Selection"
+                    }
+                }
+                div {
+                    style: "display: flex; align-items: center; gap: 4px; padding: 6px 12px;",
+                    for (name, rgb) in CLIP_COLOR_SWATCHES {
+                        {
+                            let [r, g, b] = rgb;
+                            let ring = if custom_color == Some(rgb) { TEXT_PRIMARY } else { "transparent" };
+                            rsx! {
+                                div {
+                                    key: "{name}",
+                                    title: "{name}",
+                                    style: "
+                                        width: 12px; height: 12px; border-radius: 3px; cursor: pointer;
+                                        background-color: rgb({r}, {g}, {b});
+                                        box-shadow: 0 0 0 1px {ring};
+                                    ",
+                                    onclick: move |_| {
+                                        on_set_color.call((clip_id, Some(rgb)));
+                                        show_menu.set(false);
+                                    },
+                                }
+                            }
+                        }
+                    }
+                    if custom_color.is_some() {
+                        span {
+                            style: "margin-left: 4px; color: {TEXT_MUTED}; cursor: pointer;",
+                            title: "Use the track color",
+                            onclick: move |_| {
+                                on_set_color.call((clip_id, None));
+                                show_menu.set(false);
+                            },
+                            "Default"
+                        }
+                    }
+                }
+                if can_freeze {
+                    div {
+                        style: "
+                            padding: 6px 12px; color: {TEXT_PRIMARY}; cursor: pointer;
+                            transition: background-color 0.1s ease;
+                        ",
+                        onclick: move |_| {
+                            on_freeze_from.call((clip_id, current_time));
+                            show_menu.set(false);
+                        },
+                        "❄ Freeze From Playhead"
+                    }
+                }
                 div {
                     style: "height: 1px; background-color: {BORDER_SUBTLE}; margin: 4px 0;",
                 }
@@ -838,10 +1233,14 @@ pub(crate) fn ClipElement(
 #[derive(Clone, Copy, Debug, PartialEq)]
 struct WaveformKey {
     buster: u64,
+    /// Partial update count while a build is streaming (0 once complete).
+    partial: u32,
     width: usize,
     zoom_bits: u64,
     trim_bits: u64,
     duration_bits: u64,
+    color: [u8; 3],
+    normalize: bool,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -851,14 +1250,16 @@ struct WaveColumn {
 }
 
 const WAVEFORM_BMP_HEIGHT_PX: usize = 32;
+/// Minimum gap between partial waveform redraws while peaks build.
+const WAVEFORM_PARTIAL_REDRAW_MS: u64 = 250;
 const WAVEFORM_MAX_WIDTH_PX: usize = 60_000;
-const WAVEFORM_PIXEL_VALUE: u8 = 160;
 
 fn waveform_columns_for_clip(
     cache: &PeakCache,
-    clip_duration: f64,
+    source_duration: f64,
     trim_in_seconds: f64,
     width_px: usize,
+    gain: f32,
 ) -> Vec<WaveColumn> {
     let levels = &cache.levels;
     if levels.is_empty() || width_px == 0 {
@@ -868,22 +1269,24 @@ fn waveform_columns_for_clip(
     let sample_rate = cache.sample_rate as f64;
     let level = &levels[0];
 
-    let clip_duration = clip_duration.max(0.0);
+    let source_duration = source_duration.max(0.0);
     let trim_in_seconds = trim_in_seconds.max(0.0);
     let start_frame = (trim_in_seconds * sample_rate).floor() as usize;
-    let end_frame = ((trim_in_seconds + clip_duration) * sample_rate).ceil() as usize;
+    let end_frame = ((trim_in_seconds + source_duration) * sample_rate).ceil() as usize;
     if level.block_size == 0 {
         return Vec::new();
     }
     let start_index = start_frame / level.block_size;
-    let end_index = (end_frame / level.block_size).min(level.peaks.len());
+    let requested_end = end_frame / level.block_size;
+    let end_index = requested_end.min(level.peaks.len());
     if start_index >= end_index {
         return Vec::new();
     }
 
+    // Step over the requested range so partial caches fill only their decoded prefix.
     let slice = &level.peaks[start_index..end_index];
     let width = width_px.max(1);
-    let step = slice.len() as f64 / width as f64;
+    let step = (requested_end - start_index) as f64 / width as f64;
     let height = 32.0_f32;
     let center = height / 2.0;
     let amp = (height - 6.0) / 2.0;
@@ -902,8 +1305,8 @@ fn waveform_columns_for_clip(
             min = min.min(peak.min_l.min(peak.min_r));
             max = max.max(peak.max_l.max(peak.max_r));
         }
-        let min = min as f32 / i16::MAX as f32;
-        let max = max as f32 / i16::MAX as f32;
+        let min = min as f32 / i16::MAX as f32 * gain;
+        let max = max as f32 / i16::MAX as f32 * gain;
         columns.push(WaveColumn {
             y_top: center - max * amp,
             y_bottom: center - min * amp,
@@ -917,11 +1320,12 @@ fn waveform_bitmap_from_columns(
     columns: &[WaveColumn],
     width: usize,
     height: usize,
+    color: [u8; 3],
 ) -> Vec<u8> {
     if columns.is_empty() || width == 0 || height == 0 {
         return Vec::new();
     }
-    let mut buffer = vec![0_u8; width * height];
+    let mut buffer = vec![0_u8; width * height * 3];
     let height_f = height as f32;
     let max_y = height.saturating_sub(1) as i32;
 
@@ -936,9 +1340,9 @@ fn waveform_bitmap_from_columns(
         }
         y_top = y_top.clamp(0, max_y);
         y_bottom = y_bottom.clamp(0, max_y);
-        let base = x;
         for y in y_top..=y_bottom {
-            buffer[y as usize * width + base] = WAVEFORM_PIXEL_VALUE;
+            let offset = (y as usize * width + x) * 3;
+            buffer[offset..offset + 3].copy_from_slice(&color);
         }
     }
 
@@ -952,9 +1356,22 @@ fn waveform_bmp_cache_path(
     height: usize,
 ) -> PathBuf {
     let file_name = format!(
-        "w{}_h{}_z{:x}_t{:x}_d{:x}_b{:x}.bmp",
-        key.width, height, key.zoom_bits, key.trim_bits, key.duration_bits, key.buster
+        "w{}_h{}_z{:x}_t{:x}_d{:x}_b{:x}_c{:02x}{:02x}{:02x}{}.bmp",
+        key.width,
+        height,
+        key.zoom_bits,
+        key.trim_bits,
+        key.duration_bits,
+        key.buster,
+        key.color[0],
+        key.color[1],
+        key.color[2],
+        if key.normalize { "_n" } else { "" }
     );
+    if key.partial > 0 {
+        return waveform_partial_strips_dir(project_root, asset_id)
+            .join(format!("p{}_{}", key.partial, file_name));
+    }
     project_root
         .join(".cache")
         .join("audio")
@@ -963,6 +1380,16 @@ fn waveform_bmp_cache_path(
         .join(file_name)
 }
 
+/// Scratch folder for strips drawn from in-progress peak builds.
+fn waveform_partial_strips_dir(project_root: &Path, asset_id: uuid::Uuid) -> PathBuf {
+    project_root
+        .join(".cache")
+        .join("audio")
+        .join("waveform_strips")
+        .join(asset_id.to_string())
+        .join("partial")
+}
+
 fn write_waveform_bmp(
     path: &Path,
     asset_id: uuid::Uuid,
@@ -981,7 +1408,7 @@ fn write_waveform_bmp(
     let mut bmp_bytes = Vec::new();
     let bmp_encode_start = Instant::now();
     let bmp_result = BmpEncoder::new(&mut bmp_bytes)
-        .write_image(bitmap, width as u32, height as u32, ColorType::L8.into());
+        .write_image(bitmap, width as u32, height as u32, ColorType::Rgb8.into());
     let bmp_encode_ms = bmp_encode_start.elapsed().as_millis();
 
     if bmp_result.is_err() {
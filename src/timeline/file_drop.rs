@@ -0,0 +1,92 @@
+//! Dropping media files from the OS onto timeline tracks.
+//!
+//! The drop overlay covers the whole track area, so its element coordinates
+//! map straight to a track row and a timeline time.
+
+use uuid::Uuid;
+
+use crate::core::timeline_snap::snap_time_to_frame;
+use crate::state::Track;
+
+use super::track_height_px;
+
+/// Track under `y` pixels from the top of the track rows. Each row is its
+/// height plus a one pixel border.
+pub(crate) fn drop_track_at(tracks: &[Track], y: f64) -> Option<Uuid> {
+    if y < 0.0 {
+        return None;
+    }
+    let mut top = 0.0;
+    for track in tracks {
+        let bottom = top + track_height_px(track.collapsed) + 1.0;
+        if y < bottom {
+            return Some(track.id);
+        }
+        top = bottom;
+    }
+    None
+}
+
+/// Timeline time at `x` pixels into the track content, quantized to frames.
+pub(crate) fn drop_time_at(x: f64, zoom: f64, fps: f64) -> f64 {
+    if zoom <= 0.0 {
+        return 0.0;
+    }
+    snap_time_to_frame((x / zoom).max(0.0), fps)
+}
+
+/// Start times for clips laid end to end from `start`, each on a frame. A clip
+/// that ends between frames pushes the next one to the following frame so
+/// they never overlap.
+pub(crate) fn sequential_clip_starts(start: f64, durations: &[f64], fps: f64) -> Vec<f64> {
+    let fps = fps.max(1.0);
+    let mut starts = Vec::with_capacity(durations.len());
+    let mut next = snap_time_to_frame(start.max(0.0), fps);
+    for duration in durations {
+        starts.push(next);
+        // Small epsilon so an end already on a frame isn't bumped a frame later
+        next = ((next + duration.max(0.0)) * fps - 1e-6).ceil().max(0.0) / fps;
+    }
+    starts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::TrackType;
+
+    #[test]
+    fn test_drop_position_maps_to_track_and_time() {
+        let video = Track::new("Video 1".to_string(), TrackType::Video);
+        let mut collapsed = Track::new("Video 2".to_string(), TrackType::Video);
+        collapsed.collapsed = true;
+        let audio = Track::new("Audio 1".to_string(), TrackType::Audio);
+        let tracks = vec![video.clone(), collapsed.clone(), audio.clone()];
+
+        // 37 px video row, 15 px collapsed row, then the audio row
+        assert_eq!(drop_track_at(&tracks, 0.0), Some(video.id));
+        assert_eq!(drop_track_at(&tracks, 36.5), Some(video.id));
+        assert_eq!(drop_track_at(&tracks, 40.0), Some(collapsed.id));
+        assert_eq!(drop_track_at(&tracks, 52.0), Some(audio.id));
+        assert_eq!(drop_track_at(&tracks, 200.0), None);
+        assert_eq!(drop_track_at(&tracks, -1.0), None);
+
+        // 100 px/s at 30 fps: 123 px is 1.23 s, which lands on frame 37
+        assert!((drop_time_at(123.0, 100.0, 30.0) - 37.0 / 30.0).abs() < 1e-9);
+        assert_eq!(drop_time_at(-20.0, 100.0, 30.0), 0.0);
+    }
+
+    #[test]
+    fn test_dropped_files_are_laid_out_sequentially() {
+        let starts = sequential_clip_starts(1.0, &[2.0, 0.5, 3.0], 30.0);
+        assert_eq!(starts, vec![1.0, 3.0, 3.5]);
+
+        // A clip ending between frames pushes the next one to the following frame
+        let starts = sequential_clip_starts(0.0, &[0.51, 1.0], 10.0);
+        assert!((starts[1] - 0.6).abs() < 1e-9, "{:?}", starts);
+        // The first start is quantized too
+        let starts = sequential_clip_starts(0.52, &[1.0], 10.0);
+        assert!((starts[0] - 0.5).abs() < 1e-9, "{:?}", starts);
+        assert!(sequential_clip_starts(0.0, &[], 30.0).is_empty());
+    }
+}
@@ -1,6 +1,9 @@
 use dioxus::prelude::*;
 
-use crate::constants::{ACCENT_MARKER, BG_SURFACE, BORDER_DEFAULT, TEXT_DIM, TEXT_PRIMARY, TIMELINE_SNAP_THRESHOLD_PX};
+use crate::constants::{
+    ACCENT_MARKER, BG_BASE, BG_SURFACE, BORDER_DEFAULT, BORDER_SUBTLE, MARKER_COLORS, TEXT_DIM,
+    TEXT_MUTED, TEXT_PRIMARY, TIMELINE_SNAP_THRESHOLD_PX,
+};
 use crate::core::timeline_snap::{best_snap_delta_frames, frames_from_seconds, seconds_from_frames, SnapTarget};
 
 #[component]
@@ -14,6 +17,10 @@ pub fn MarkerElement(
     on_select: EventHandler<uuid::Uuid>,
     on_move: EventHandler<(uuid::Uuid, f64)>,
     on_delete: EventHandler<uuid::Uuid>,
+    // New label (None clears it)
+    on_rename: EventHandler<(uuid::Uuid, Option<String>)>,
+    // New hex color (None restores the default)
+    on_recolor: EventHandler<(uuid::Uuid, Option<String>)>,
     on_snap_preview: EventHandler<Option<f64>>,
     snap_targets: std::sync::Arc<Vec<SnapTarget>>,
 ) -> Element {
@@ -33,6 +40,9 @@ pub fn MarkerElement(
     let mut drag_start_time = use_signal(|| marker_time);
     let mut show_menu = use_signal(|| false);
     let mut menu_pos = use_signal(|| (0.0, 0.0));
+    let mut rename_draft = use_signal(|| None::<String>);
+    let current_label = marker.label.clone().unwrap_or_default();
+    let has_custom_color = marker.color.is_some();
 
     let filtered_snap_targets: Vec<SnapTarget> = snap_targets
         .iter()
@@ -68,6 +78,8 @@ pub fn MarkerElement(
                 e.stop_propagation();
                 let coords = e.client_coordinates();
                 menu_pos.set((coords.x, coords.y));
+                rename_draft.set(None);
+                on_select.call(marker_id);
                 show_menu.set(true);
             },
 
@@ -199,6 +211,92 @@ pub fn MarkerElement(
                     font-size: 12px;
                 ",
                 oncontextmenu: move |e| e.prevent_default(),
+                if let Some(draft) = rename_draft() {
+                    div {
+                        style: "padding: 4px 8px;",
+                        input {
+                            r#type: "text",
+                            value: "{draft}",
+                            placeholder: "Marker label",
+                            autofocus: true,
+                            style: "
+                                width: 100%; box-sizing: border-box; padding: 4px 6px;
+                                font-size: 12px; color: {TEXT_PRIMARY};
+                                background-color: {BG_BASE};
+                                border: 1px solid {BORDER_DEFAULT}; border-radius: 4px;
+                                outline: none;
+                            ",
+                            oninput: move |e| rename_draft.set(Some(e.value())),
+                            onkeydown: move |e| {
+                                e.stop_propagation();
+                                match e.key() {
+                                    Key::Enter => {
+                                        let label = rename_draft().unwrap_or_default();
+                                        let label = (!label.trim().is_empty()).then_some(label);
+                                        on_rename.call((marker_id, label));
+                                        rename_draft.set(None);
+                                        show_menu.set(false);
+                                    }
+                                    Key::Escape => {
+                                        rename_draft.set(None);
+                                        show_menu.set(false);
+                                    }
+                                    _ => {}
+                                }
+                            },
+                        }
+                    }
+                } else {
+                    div {
+                        style: "
+                            padding: 6px 12px; color: {TEXT_PRIMARY}; cursor: pointer;
+                            transition: background-color 0.1s ease;
+                        ",
+                        onclick: move |e| {
+                            e.stop_propagation();
+                            rename_draft.set(Some(current_label.clone()));
+                        },
+                        "Rename..."
+                    }
+                }
+                div {
+                    style: "
+                        display: flex; align-items: center; gap: 6px;
+                        padding: 6px 12px;
+                        border-top: 1px solid {BORDER_SUBTLE};
+                        border-bottom: 1px solid {BORDER_SUBTLE};
+                    ",
+                    for swatch in MARKER_COLORS {
+                        div {
+                            key: "{swatch}",
+                            title: "{swatch}",
+                            style: "
+                                width: 14px; height: 14px; border-radius: 3px; cursor: pointer;
+                                background-color: {swatch};
+                                border: 1px solid {if swatch.eq_ignore_ascii_case(marker_color) { TEXT_PRIMARY } else { BORDER_DEFAULT }};
+                            ",
+                            onclick: move |_| {
+                                // The first swatch is the default color, so store it as unset
+                                let color = (swatch != ACCENT_MARKER).then(|| swatch.to_string());
+                                on_recolor.call((marker_id, color));
+                                show_menu.set(false);
+                            },
+                        }
+                    }
+                }
+                if has_custom_color {
+                    div {
+                        style: "
+                            padding: 6px 12px; color: {TEXT_MUTED}; cursor: pointer;
+                            transition: background-color 0.1s ease;
+                        ",
+                        onclick: move |_| {
+                            on_recolor.call((marker_id, None));
+                            show_menu.set(false);
+                        },
+                        "Default Color"
+                    }
+                }
                 div {
                     style: "
                         padding: 6px 12px;
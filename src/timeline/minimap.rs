@@ -0,0 +1,154 @@
+use dioxus::prelude::*;
+
+use crate::constants::{
+    ACCENT_AUDIO, ACCENT_MARKER, ACCENT_VIDEO, BG_BASE, BORDER_DEFAULT, BORDER_STRONG,
+};
+use crate::state::{Clip, Track, TrackType};
+
+const MINIMAP_ROW_HEIGHT_PX: f64 = 3.0;
+const MINIMAP_ROW_GAP_PX: f64 = 1.0;
+const MINIMAP_MIN_HEIGHT_PX: f64 = 14.0;
+const MINIMAP_MAX_HEIGHT_PX: f64 = 40.0;
+
+/// Height of the overview strip for a given number of tracks.
+fn minimap_height_px(track_count: usize) -> f64 {
+    let rows = track_count as f64 * (MINIMAP_ROW_HEIGHT_PX + MINIMAP_ROW_GAP_PX) + 4.0;
+    rows.clamp(MINIMAP_MIN_HEIGHT_PX, MINIMAP_MAX_HEIGHT_PX)
+}
+
+/// Scroll offset that centers the main view on minimap position `x`, clamped
+/// so the view never runs past either end of the content.
+fn scroll_offset_for_minimap_x(
+    x: f64,
+    minimap_width: f64,
+    duration: f64,
+    zoom: f64,
+    viewport_width: f64,
+) -> f64 {
+    let time = (x / minimap_width.max(1.0)).clamp(0.0, 1.0) * duration;
+    let max_scroll = (duration * zoom - viewport_width).max(0.0);
+    let offset = time * zoom - viewport_width * 0.5;
+    if offset.is_finite() {
+        offset.clamp(0.0, max_scroll)
+    } else {
+        0.0
+    }
+}
+
+/// Whole-project overview under the tracks: clip blocks per track plus a
+/// draggable rectangle for the part the main timeline is showing.
+#[component]
+pub(crate) fn TimelineMinimap(
+    tracks: Vec<Track>,
+    clips: Vec<Clip>,
+    duration: f64,
+    zoom: f64,
+    scroll_offset: f64,
+    current_time: f64,
+    viewport_width: f64,  // width of the scrollable track area
+    on_scroll: EventHandler<f64>,
+) -> Element {
+    let mut dragging = use_signal(|| false);
+    let duration = duration.max(0.01);
+    let width = viewport_width.max(1.0);
+    let height = minimap_height_px(tracks.len());
+    let px_per_second = width / duration;
+
+    let content_width = (duration * zoom).max(1.0);
+    let view_left = (scroll_offset / content_width * width).clamp(0.0, width);
+    let view_width = (viewport_width / content_width * width).clamp(4.0, width);
+    let playhead_left = (current_time.clamp(0.0, duration) * px_per_second).min(width - 1.0);
+
+    let row_stride = MINIMAP_ROW_HEIGHT_PX + MINIMAP_ROW_GAP_PX;
+    let mut blocks = Vec::new();
+    for (row, track) in tracks.iter().enumerate() {
+        let color = match track.track_type {
+            TrackType::Video => ACCENT_VIDEO,
+            TrackType::Audio => ACCENT_AUDIO,
+            TrackType::Marker => ACCENT_MARKER,
+        };
+        let top = 2.0 + row as f64 * row_stride;
+        if top + MINIMAP_ROW_HEIGHT_PX > height {
+            break;
+        }
+        for clip in clips.iter().filter(|clip| clip.track_id == track.id) {
+            let left = clip.start_time * px_per_second;
+            let block_width = (clip.duration * px_per_second).max(1.0);
+            blocks.push((clip.id, left, top, block_width, color));
+        }
+    }
+
+    let seek_to = move |x: f64| {
+        on_scroll.call(scroll_offset_for_minimap_x(
+            x,
+            width,
+            duration,
+            zoom,
+            viewport_width,
+        ));
+    };
+
+    rsx! {
+        div {
+            style: "
+                position: relative;
+                width: {width}px;
+                height: {height}px;
+                background-color: {BG_BASE};
+                border-top: 1px solid {BORDER_DEFAULT};
+                overflow: hidden;
+                cursor: grab;
+            ",
+            onmousedown: move |e| {
+                e.prevent_default();
+                e.stop_propagation();
+                dragging.set(true);
+                seek_to(e.element_coordinates().x);
+            },
+            onmousemove: move |e| {
+                if dragging() {
+                    seek_to(e.element_coordinates().x);
+                }
+            },
+            onmouseup: move |_| dragging.set(false),
+            onmouseleave: move |_| dragging.set(false),
+
+            for (clip_id, left, top, block_width, color) in blocks {
+                div {
+                    key: "{clip_id}",
+                    style: "
+                        position: absolute;
+                        left: {left}px; top: {top}px;
+                        width: {block_width}px; height: {MINIMAP_ROW_HEIGHT_PX}px;
+                        background-color: {color};
+                        opacity: 0.75;
+                        pointer-events: none;
+                    ",
+                }
+            }
+
+            // Visible range of the main timeline
+            div {
+                style: "
+                    position: absolute;
+                    left: {view_left}px; top: 0;
+                    width: {view_width}px; height: 100%;
+                    box-sizing: border-box;
+                    border: 1px solid {BORDER_STRONG};
+                    background-color: rgba(255, 255, 255, 0.06);
+                    pointer-events: none;
+                ",
+            }
+
+            div {
+                style: "
+                    position: absolute;
+                    left: {playhead_left}px; top: 0;
+                    width: 1px; height: 100%;
+                    background-color: #ef4444;
+                    pointer-events: none;
+                ",
+            }
+        }
+    }
+}
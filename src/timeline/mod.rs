@@ -9,16 +9,58 @@ mod track_label;
 mod track_row;
 mod clip_element;
 mod marker_element;
+mod minimap;
+mod thumb_tiles;
+mod file_drop;
 
 pub use panel::TimelinePanel;
+pub(crate) use file_drop::sequential_clip_starts;
+pub(crate) use ruler::ruler_time_at;
 
-use crate::constants::{TIMELINE_MAX_PX_PER_FRAME, TIMELINE_MIN_ZOOM_FLOOR};
+use crate::constants::{
+    TIMELINE_FOLLOW_MARGIN_PX, TIMELINE_MAX_PX_PER_FRAME, TIMELINE_MIN_ZOOM_FLOOR,
+    TIMELINE_ZOOM_SELECTION_PADDING_PX,
+};
 
 pub(crate) const THUMB_TILE_WIDTH_PX: f64 = 60.0;
 pub(crate) const MAX_THUMB_TILES: usize = 120;
 pub(crate) const MIN_CLIP_WIDTH_PX: f64 = 20.0;
 pub(crate) const MIN_CLIP_WIDTH_FLOOR_PX: f64 = 2.0;
 pub(crate) const MIN_CLIP_WIDTH_SCALE: f64 = 0.2;
+pub(crate) const TRACK_HEIGHT_PX: f64 = 36.0;
+pub(crate) const COLLAPSED_TRACK_HEIGHT_PX: f64 = 14.0;
+/// Clips narrower than this show no label at all.
+pub(crate) const CLIP_LABEL_MIN_WIDTH_PX: f64 = 32.0;
+/// Rough width of one character of the 10px clip label font.
+pub(crate) const CLIP_LABEL_CHAR_WIDTH_PX: f64 = 6.0;
+
+pub(crate) fn track_height_px(collapsed: bool) -> f64 {
+    if collapsed {
+        COLLAPSED_TRACK_HEIGHT_PX
+    } else {
+        TRACK_HEIGHT_PX
+    }
+}
+
+/// Shorten `s` to at most `max_chars` characters by replacing its middle with
+/// an ellipsis, so both the start and the end (often a take number or
+/// version) stay readable.
+pub(crate) fn truncate_middle(s: &str, max_chars: usize) -> String {
+    let count = s.chars().count();
+    if count <= max_chars {
+        return s.to_string();
+    }
+    if max_chars == 0 {
+        return String::new();
+    }
+    let kept = max_chars - 1;
+    let head = kept.div_ceil(2);
+    let tail = kept / 2;
+    let mut truncated: String = s.chars().take(head).collect();
+    truncated.push('…');
+    truncated.extend(s.chars().skip(count - tail));
+    truncated
+}
 
 pub fn timeline_zoom_bounds(duration: f64, viewport_width: Option<f64>, fps: f64) -> (f64, f64) {
     let duration = duration.max(0.01);
@@ -27,3 +69,194 @@ pub fn timeline_zoom_bounds(duration: f64, viewport_width: Option<f64>, fps: f64
     let max_zoom = (fps.max(1.0) * TIMELINE_MAX_PX_PER_FRAME).max(min_zoom);
     (min_zoom, max_zoom)
 }
+
+/// Scroll offset that keeps `focus_time` at the same viewport pixel when the
+/// zoom changes from `old_zoom` to `new_zoom`. Clamped to the scrollable range
+/// when the viewport width is known.
+pub fn adjust_scroll_for_zoom(
+    old_zoom: f64,
+    new_zoom: f64,
+    focus_time: f64,
+    scroll_offset: f64,
+    viewport_width: Option<f64>,
+    duration: f64,
+) -> f64 {
+    let focus_x = focus_time * old_zoom - scroll_offset;
+    let mut next = focus_time * new_zoom - focus_x;
+    if !next.is_finite() {
+        next = 0.0;
+    }
+    let max_scroll = viewport_width
+        .map(|width| (duration * new_zoom - width).max(0.0))
+        .unwrap_or(f64::INFINITY);
+    next.clamp(0.0, max_scroll)
+}
+
+/// Time to keep fixed while zooming from the keyboard or buttons: the playhead
+/// when it's on screen, otherwise the middle of the view.
+pub fn zoom_focus_time(
+    current_time: f64,
+    zoom: f64,
+    scroll_offset: f64,
+    viewport_width: Option<f64>,
+) -> f64 {
+    let Some(width) = viewport_width.filter(|width| *width > 0.0) else {
+        return current_time;
+    };
+    let playhead_x = current_time * zoom - scroll_offset;
+    if (0.0..=width).contains(&playhead_x) || zoom <= 0.0 {
+        current_time
+    } else {
+        (scroll_offset + width * 0.5) / zoom
+    }
+}
+
+/// Zoom that shows the whole project (0..duration) in the viewport.
+pub fn timeline_zoom_to_fit(duration: f64, viewport_width: Option<f64>, fps: f64) -> f64 {
+    let (min_zoom, max_zoom) = timeline_zoom_bounds(duration, viewport_width, fps);
+    let viewport_width = viewport_width.unwrap_or(600.0).max(1.0);
+    (viewport_width / duration.max(0.01)).clamp(min_zoom, max_zoom)
+}
+
+/// Zoom and scroll offset that frame `start..end` with a little padding on
+/// each side. Spans too short for the max zoom are centered instead.
+pub fn timeline_zoom_to_span(
+    start: f64,
+    end: f64,
+    duration: f64,
+    viewport_width: Option<f64>,
+    fps: f64,
+) -> (f64, f64) {
+    let (min_zoom, max_zoom) = timeline_zoom_bounds(duration, viewport_width, fps);
+    let width = viewport_width.unwrap_or(600.0).max(1.0);
+    let padding = TIMELINE_ZOOM_SELECTION_PADDING_PX.min(width * 0.25);
+    let span = (end - start).max(0.0);
+    let zoom = if span > 0.0 {
+        ((width - padding * 2.0) / span).clamp(min_zoom, max_zoom)
+    } else {
+        max_zoom
+    };
+    let center = (start + end) * 0.5;
+    let max_scroll = (duration * zoom - width).max(0.0);
+    let scroll = (center * zoom - width * 0.5).clamp(0.0, max_scroll);
+    (zoom, scroll)
+}
+
+/// Scroll offset that keeps a playing playhead on screen. When the playhead
+/// nears the right edge (or has left the view entirely) the view pages forward
+/// so the playhead lands just inside the left edge. Returns `None` when the
+/// view can stay where it is.
+pub fn playhead_follow_scroll(
+    playhead_x: f64,
+    scroll_offset: f64,
+    viewport_width: f64,
+    content_width: f64,
+) -> Option<f64> {
+    if viewport_width <= 0.0 || !playhead_x.is_finite() {
+        return None;
+    }
+    // At high zoom a page is only a few frames wide; keep the margin to a
+    // fraction of the view so every page still advances.
+    let margin = TIMELINE_FOLLOW_MARGIN_PX.min(viewport_width * 0.1);
+    if playhead_x >= scroll_offset && playhead_x <= scroll_offset + viewport_width - margin {
+        return None;
+    }
+    let max_scroll = (content_width - viewport_width).max(0.0);
+    let next = (playhead_x - margin).clamp(0.0, max_scroll);
+    if (next - scroll_offset).abs() < 0.5 {
+        None
+    } else {
+        Some(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_middle() {
+        // Short strings come back unchanged
+        assert_eq!(truncate_middle("Hero shot", 20), "Hero shot");
+        assert_eq!(truncate_middle("Hero shot", 9), "Hero shot");
+        assert_eq!(truncate_middle("", 0), "");
+
+        // Long ones keep both ends
+        let truncated = truncate_middle("Interview_take_04_final (v3)", 12);
+        assert_eq!(truncated, "Interv… (v3)");
+        assert_eq!(truncated.chars().count(), 12);
+        assert_eq!(truncate_middle("abcdefgh", 4), "ab…h");
+        // Counted in characters, not bytes
+        assert_eq!(truncate_middle("ééééé", 3), "é…é");
+
+        // Zero and one character widths
+        assert_eq!(truncate_middle("abc", 0), "");
+        assert_eq!(truncate_middle("abc", 1), "…");
+        assert_eq!(truncate_middle("a", 1), "a");
+    }
+
+    #[test]
+    fn test_zoom_keeps_focus_time_in_place() {
+        // 12 s sits 200 px into a view scrolled to 1000 px at 100 px/s
+        let scroll = adjust_scroll_for_zoom(100.0, 250.0, 12.0, 1000.0, Some(800.0), 60.0);
+        assert!((12.0 * 250.0 - scroll - 200.0).abs() < 1e-9);
+
+        // Zooming back out returns to the original offset
+        let back = adjust_scroll_for_zoom(250.0, 100.0, 12.0, scroll, Some(800.0), 60.0);
+        assert!((back - 1000.0).abs() < 1e-9);
+
+        // The result never scrolls before the start or past the end
+        let scroll = adjust_scroll_for_zoom(100.0, 20.0, 1.0, 0.0, Some(800.0), 60.0);
+        assert_eq!(scroll, 0.0);
+        let scroll = adjust_scroll_for_zoom(100.0, 50.0, 59.0, 5200.0, Some(800.0), 60.0);
+        assert_eq!(scroll, 60.0 * 50.0 - 800.0);
+    }
+
+    #[test]
+    fn test_zoom_focus_falls_back_to_view_center() {
+        // Playhead on screen
+        assert_eq!(zoom_focus_time(5.0, 100.0, 0.0, Some(800.0)), 5.0);
+        // Playhead scrolled out of view: the center of the view is kept instead
+        assert_eq!(zoom_focus_time(1.0, 100.0, 2000.0, Some(800.0)), 24.0);
+        assert_eq!(zoom_focus_time(1.0, 100.0, 2000.0, None), 1.0);
+    }
+
+    #[test]
+    fn test_zoom_to_fit_stays_in_bounds() {
+        // 60 s across 1200 px is 20 px per second
+        let zoom = timeline_zoom_to_fit(60.0, Some(1200.0), 30.0);
+        assert!((zoom - 20.0).abs() < 1e-9);
+        let (min_zoom, max_zoom) = timeline_zoom_bounds(60.0, Some(1200.0), 30.0);
+        assert!(zoom >= min_zoom && zoom <= max_zoom);
+
+        // A very short project can't zoom past the per-frame limit
+        let zoom = timeline_zoom_to_fit(0.05, Some(1200.0), 30.0);
+        assert_eq!(zoom, 30.0 * TIMELINE_MAX_PX_PER_FRAME);
+
+        // A very long one never drops below the floor
+        let zoom = timeline_zoom_to_fit(1.0e6, Some(1200.0), 30.0);
+        assert_eq!(zoom, TIMELINE_MIN_ZOOM_FLOOR);
+    }
+
+    #[test]
+    fn test_zoom_to_selection_frames_span() {
+        let width = 1000.0;
+        let (zoom, scroll) = timeline_zoom_to_span(10.0, 20.0, 120.0, Some(width), 30.0);
+        let padding = TIMELINE_ZOOM_SELECTION_PADDING_PX;
+        assert!((zoom - (width - padding * 2.0) / 10.0).abs() < 1e-9);
+        // The span starts and ends one padding inside the viewport
+        assert!((10.0 * zoom - scroll - padding).abs() < 1e-6);
+        assert!((scroll + width - 20.0 * zoom - padding).abs() < 1e-6);
+
+        // A single frame hits the max zoom and is centered
+        let (min_zoom, max_zoom) = timeline_zoom_bounds(120.0, Some(width), 30.0);
+        let (zoom, scroll) = timeline_zoom_to_span(5.0, 5.0 + 1.0 / 30.0, 120.0, Some(width), 30.0);
+        assert_eq!(zoom, max_zoom);
+        assert!(zoom >= min_zoom);
+        assert!(((5.0 + 1.0 / 60.0) * zoom - scroll - width * 0.5).abs() < 1e-6);
+
+        // A span at the very start can't scroll before zero
+        let (_, scroll) = timeline_zoom_to_span(0.0, 0.5, 120.0, Some(width), 30.0);
+        assert_eq!(scroll, 0.0);
+    }
+}
@@ -4,14 +4,18 @@ use std::collections::HashMap;
 use crate::constants::{
     BG_ELEVATED, BG_SURFACE,
     BORDER_DEFAULT, BORDER_SUBTLE,
-    TEXT_DIM, TEXT_MUTED,
+    TEXT_DIM, TEXT_MUTED, TEXT_SECONDARY,
     ACCENT_AUDIO, ACCENT_MARKER, ACCENT_VIDEO,
 };
-use crate::state::{Track, TrackType};
+use crate::state::{TimeFormat, Track, TrackType};
+use crate::core::audio::meter::MeterChannel;
+use crate::core::timecode::format_time;
 use crate::core::timeline_snap::{snap_time_to_frame, SnapTarget};
 
-use super::playback_controls::PlaybackBtn;
-use super::ruler::TimeRuler;
+use super::file_drop::{drop_time_at, drop_track_at};
+use super::minimap::TimelineMinimap;
+use super::playback_controls::{OutputMeter, PlaybackBtn};
+use super::ruler::{ruler_time_at, TimeRuler};
 use super::track_label::TrackLabel;
 use super::track_row::TrackRow;
 
@@ -34,6 +38,9 @@ pub fn TimelinePanel(
     clip_cache_buckets: std::sync::Arc<HashMap<uuid::Uuid, Vec<bool>>>,
     project_root: Option<std::path::PathBuf>,
     audio_waveform_cache_buster: Signal<u64>,
+    waveform_peaks_per_second: f64,
+    resample_quality: crate::state::ResampleQuality,
+    output_meter: Signal<[MeterChannel; 2]>,
     // Timeline state
     current_time: f64,
     duration: f64,
@@ -43,11 +50,18 @@ pub fn TimelinePanel(
     max_zoom: f64,
     is_playing: bool,
     scroll_offset: f64,
+    viewport_width: Option<f64>,
+    follow_playhead: bool,
+    magnetic_timeline: bool,
+    time_format: TimeFormat,
     // Callbacks
     on_seek: EventHandler<f64>,
     on_zoom_change: EventHandler<f64>,
     on_play_pause: EventHandler<MouseEvent>,
     on_scroll: EventHandler<f64>,
+    on_toggle_follow: EventHandler<MouseEvent>,
+    on_toggle_magnetic: EventHandler<MouseEvent>,
+    on_time_format_change: EventHandler<TimeFormat>,
     on_seek_start: EventHandler<MouseEvent>,
     on_seek_end: EventHandler<MouseEvent>,
     is_seeking: bool,
@@ -57,29 +71,50 @@ pub fn TimelinePanel(
     on_track_context_menu: EventHandler<(f64, f64, uuid::Uuid)>,  // (x, y, track_id)
     selected_tracks: Vec<uuid::Uuid>,
     on_track_select: EventHandler<uuid::Uuid>,
+    on_track_toggle_collapse: EventHandler<uuid::Uuid>,
+    on_track_reorder: EventHandler<(uuid::Uuid, usize)>, // (track_id, new_index)
     // Clip operations
     on_clip_delete: EventHandler<uuid::Uuid>,
     on_clip_move: EventHandler<(uuid::Uuid, f64)>,  // (clip_id, new_start_time)
     on_clip_resize: EventHandler<(uuid::Uuid, f64, f64)>,  // (clip_id, new_start, new_duration)
     on_clip_move_track: EventHandler<(uuid::Uuid, i32)>, // (clip_id, direction)
+    on_clip_freeze_from: EventHandler<(uuid::Uuid, f64)>, // (clip_id, time)
+    on_clip_link: EventHandler<uuid::Uuid>,
+    on_clip_unlink: EventHandler<uuid::Uuid>,
+    on_group_selection: EventHandler<()>,
+    on_clip_ungroup: EventHandler<uuid::Uuid>,
+    on_clip_set_color: EventHandler<(uuid::Uuid, Option<[u8; 3]>)>,
+    on_clip_fade: EventHandler<(uuid::Uuid, f64, f64)>, // (clip_id, fade_in, fade_out)
     selected_clips: Vec<uuid::Uuid>,
     on_clip_select: EventHandler<uuid::Uuid>,
     on_marker_add: EventHandler<f64>,
     on_marker_move: EventHandler<(uuid::Uuid, f64)>,
     on_marker_delete: EventHandler<uuid::Uuid>,
+    on_marker_rename: EventHandler<(uuid::Uuid, Option<String>)>,
+    on_marker_recolor: EventHandler<(uuid::Uuid, Option<String>)>,
     selected_markers: Vec<uuid::Uuid>,
     on_marker_select: EventHandler<uuid::Uuid>,
     snap_targets: std::sync::Arc<Vec<SnapTarget>>,
     // Asset Drag & Drop
     dragged_asset: Option<uuid::Uuid>,
     on_asset_drop: EventHandler<(uuid::Uuid, f64, uuid::Uuid)>, // (track_id, time, asset_id)
+    // Files dragged in from the OS
+    on_files_drop: EventHandler<(uuid::Uuid, f64, Vec<std::path::PathBuf>)>, // (track_id, time, paths)
     // Selection
     on_deselect_all: EventHandler<MouseEvent>,
 ) -> Element {
     let _ = thumbnail_refresh_tick;
     let fps = fps.max(1.0);
-    let fps_i = fps.round().max(1.0) as u64;
     let mut snap_indicator_time = use_signal(|| None::<f64>);
+    let mut show_minimap = use_signal(|| true);
+    // Track being dragged by its label handle, with its starting index
+    let mut dragging_track = use_signal(|| None::<(uuid::Uuid, usize)>);
+    let mut track_drop_index = use_signal(|| None::<usize>);
+    // OS files are being dragged over the track rows
+    let mut file_drop_active = use_signal(|| false);
+    let minimap_color = if show_minimap() { TEXT_SECONDARY } else { TEXT_MUTED };
+    let follow_color = if follow_playhead { TEXT_SECONDARY } else { TEXT_MUTED };
+    let magnetic_color = if magnetic_timeline { TEXT_SECONDARY } else { TEXT_MUTED };
     let icon = if collapsed { "▲" } else { "▼" };
     let play_icon = if is_playing { "⏸" } else { "▶" };
     
@@ -90,19 +125,7 @@ pub fn TimelinePanel(
     let header_cursor = if collapsed { "pointer" } else { "default" };
     let header_class = if collapsed { "collapsed-rail" } else { "" };
     
-    // Format time as HH:MM:SS:FF using project fps.
-    let format_time = |t: f64| -> String {
-        let total_frames = (t * fps).round().max(0.0) as u64;
-        let frames = total_frames % fps_i.max(1);
-        let total_seconds = total_frames / fps_i.max(1);
-        let seconds = total_seconds % 60;
-        let total_minutes = total_seconds / 60;
-        let minutes = total_minutes % 60;
-        let hours = total_minutes / 60;
-        format!("{:02}:{:02}:{:02}:{:02}", hours, minutes, seconds, frames)
-    };
-    
-    let timecode = format_time(current_time);
+    let timecode = format_time(current_time, fps, time_format);
     let zoom_label = if (zoom - min_zoom).abs() <= 0.5 {
         "Fit".to_string()
     } else if (zoom - max_zoom).abs() <= 0.5 {
@@ -127,6 +150,12 @@ pub fn TimelinePanel(
     // Constants
     let ruler_height = 24;
     let track_label_width = 140;
+    let any_soloed = tracks.iter().any(|t| t.soloed);
+    let muted_tracks = tracks.iter().filter(|t| t.muted).count();
+    let audio_silenced = tracks
+        .iter()
+        .filter(|t| t.track_type != TrackType::Marker)
+        .all(|t| t.muted || (any_soloed && !t.soloed));
 
     rsx! {
         {
@@ -198,6 +227,27 @@ pub fn TimelinePanel(
                             onclick: move |_| on_zoom_change.call(max_zoom),
                             "Frames"
                         }
+                        button {
+                            class: "collapse-btn",
+                            style: "padding: 0 6px; height: 20px; border: none; border-radius: 3px; background: transparent; color: {minimap_color}; font-size: 10px; cursor: pointer; display: flex; align-items: center; justify-content: center;",
+                            title: "Toggle overview",
+                            onclick: move |_| show_minimap.toggle(),
+                            "Map"
+                        }
+                        button {
+                            class: "collapse-btn",
+                            style: "padding: 0 6px; height: 20px; border: none; border-radius: 3px; background: transparent; color: {follow_color}; font-size: 10px; cursor: pointer; display: flex; align-items: center; justify-content: center;",
+                            title: "Follow playhead during playback (F)",
+                            onclick: move |e| on_toggle_follow.call(e),
+                            "Follow"
+                        }
+                        button {
+                            class: "collapse-btn",
+                            style: "padding: 0 6px; height: 20px; border: none; border-radius: 3px; background: transparent; color: {magnetic_color}; font-size: 10px; cursor: pointer; display: flex; align-items: center; justify-content: center;",
+                            title: "Magnetic timeline: edited tracks close their gaps and push clips aside instead of overlapping",
+                            onclick: move |e| on_toggle_magnetic.call(e),
+                            "Magnetic"
+                        }
                     }
                 }
                 
@@ -234,13 +284,21 @@ pub fn TimelinePanel(
                         icon: "⏭",
                         on_click: move |_| on_seek.call(duration),
                     }
+                    OutputMeter {
+                        levels: output_meter,
+                        muted_tracks: muted_tracks,
+                        any_soloed: any_soloed,
+                        silenced: audio_silenced,
+                    }
                 }
 
                 // Right: Timecode + collapse button
                 div {
                     style: "display: flex; align-items: center; gap: 12px;",
                     span { 
-                        style: "font-family: 'SF Mono', Consolas, monospace; font-size: 11px; color: {TEXT_DIM};", 
+                        style: "font-family: 'SF Mono', Consolas, monospace; font-size: 11px; color: {TEXT_DIM}; cursor: pointer; user-select: none;", 
+                        title: "Click to switch time format",
+                        onclick: move |_| on_time_format_change.call(time_format.next()),
                         "{timecode}" 
                     }
                     button {
@@ -299,7 +357,19 @@ pub fn TimelinePanel(
                             // Existing track labels
                             div {
                                 style: "flex: 1;",
-                                for track in tracks.iter() {
+                                // Dropping anywhere over the labels commits a track drag
+                                onmouseup: move |_| {
+                                    if let (Some((id, _)), Some(index)) = (dragging_track(), track_drop_index()) {
+                                        on_track_reorder.call((id, index));
+                                    }
+                                    dragging_track.set(None);
+                                    track_drop_index.set(None);
+                                },
+                                onmouseleave: move |_| {
+                                    dragging_track.set(None);
+                                    track_drop_index.set(None);
+                                },
+                                for (index, track) in tracks.iter().enumerate() {
                                     {
                                         let color = match track.track_type {
                                             TrackType::Video => ACCENT_VIDEO,
@@ -307,6 +377,11 @@ pub fn TimelinePanel(
                                             TrackType::Marker => ACCENT_MARKER,
                                         };
                                         let tid = track.id;
+                                        let drop_edge = match (dragging_track(), track_drop_index()) {
+                                            (Some((_, from)), Some(to)) if to == index && to < from => Some(true),
+                                            (Some((_, from)), Some(to)) if to == index && to > from => Some(false),
+                                            _ => None,
+                                        };
                                         rsx! {
                                             TrackLabel { 
                                                 key: "{track.id}",
@@ -314,8 +389,24 @@ pub fn TimelinePanel(
                                                 color: color,
                                                 track_id: tid,
                                                 selected: selected_tracks.contains(&tid),
+                                                collapsed: track.collapsed,
+                                                muted: track.muted,
+                                                soloed: track.soloed,
+                                                active: !track.muted && (track.soloed || !any_soloed),
+                                                drop_above: drop_edge == Some(true),
+                                                drop_below: drop_edge == Some(false),
+                                                on_drag_start: move |id| {
+                                                    dragging_track.set(Some((id, index)));
+                                                    track_drop_index.set(Some(index));
+                                                },
+                                                on_drag_over: move |_| {
+                                                    if dragging_track().is_some() {
+                                                        track_drop_index.set(Some(index));
+                                                    }
+                                                },
                                                 on_select: move |id| on_track_select.call(id),
                                                 on_context_menu: move |data| on_track_context_menu.call(data),
+                                                on_toggle_collapse: move |id| on_track_toggle_collapse.call(id),
                                             }
                                         }
                                     }
@@ -400,10 +491,8 @@ pub fn TimelinePanel(
                                     // element_coordinates gives position relative to this ruler element
                                     // which is in scroll space (content coordinates)
                                     let x = e.element_coordinates().x;
-                                    let t = (x / zoom).clamp(0.0, duration);
                                     // Snap to frame and seek immediately
-                                    let snapped = snap_time_to_frame(t, fps).clamp(0.0, duration);
-                                    on_seek.call(snapped);
+                                    on_seek.call(ruler_time_at(x, zoom, 0.0, duration, fps));
                                     // Start drag mode so continued mouse movement continues seeking
                                     on_seek_start.call(e);
                                 },
@@ -414,6 +503,7 @@ pub fn TimelinePanel(
                                     zoom: zoom,
                                     scroll_offset: 0.0,  // No offset - we're in scroll space
                                     fps: fps,
+                                    time_format: time_format,
                                 }
                                 
                                 // Playhead indicator on ruler (in scroll space)
@@ -464,6 +554,11 @@ pub fn TimelinePanel(
                                     flex-direction: column;
                                     position: relative;
                                 ",
+                                ondragenter: move |e: DragEvent| {
+                                    if !e.files().is_empty() {
+                                        file_drop_active.set(true);
+                                    }
+                                },
                                 
                                 for track in tracks.iter() {
                                     TrackRow { 
@@ -471,14 +566,19 @@ pub fn TimelinePanel(
                                         width: content_width,
                                         track_id: track.id,
                                         track_type: track.track_type.clone(),
+                                        waveform_color: track.resolved_waveform_color(),
+                                        collapsed: track.collapsed,
                                         clips: clips.clone(),
                                         markers: markers.clone(),
                                         assets: assets.clone(),
                                         thumbnailer: thumbnailer.clone(),
                                         thumbnail_cache_buster: thumbnail_cache_buster,
+                                        thumbnail_refresh_tick: thumbnail_refresh_tick,
                                         clip_cache_buckets: clip_cache_buckets.clone(),
                                         project_root: project_root.clone(),
                                         audio_waveform_cache_buster: audio_waveform_cache_buster,
+                                        waveform_peaks_per_second: waveform_peaks_per_second,
+                                        resample_quality: resample_quality,
                                         zoom: zoom,
                                         fps: fps,
                                         duration: duration,
@@ -487,6 +587,13 @@ pub fn TimelinePanel(
                                         on_clip_move: move |(id, time)| on_clip_move.call((id, time)),
                                         on_clip_resize: move |(id, start, dur)| on_clip_resize.call((id, start, dur)),
                                         on_clip_move_track: move |(id, direction)| on_clip_move_track.call((id, direction)),
+                                        on_clip_freeze_from: move |(id, time)| on_clip_freeze_from.call((id, time)),
+                                        on_clip_link: move |id| on_clip_link.call(id),
+                                        on_clip_unlink: move |id| on_clip_unlink.call(id),
+                                        on_group_selection: move |_| on_group_selection.call(()),
+                                        on_clip_ungroup: move |id| on_clip_ungroup.call(id),
+                                        on_clip_set_color: move |(id, color)| on_clip_set_color.call((id, color)),
+                                        on_clip_fade: move |(id, fade_in, fade_out)| on_clip_fade.call((id, fade_in, fade_out)),
                                         selected_clips: selected_clips.clone(),
                                         on_clip_select: move |id| on_clip_select.call(id),
                                         on_snap_preview: move |time| snap_indicator_time.set(time),
@@ -494,6 +601,8 @@ pub fn TimelinePanel(
                                         on_marker_add: move |time| on_marker_add.call(time),
                                         on_marker_move: move |(id, time)| on_marker_move.call((id, time)),
                                         on_marker_delete: move |id| on_marker_delete.call(id),
+                                        on_marker_rename: move |(id, label)| on_marker_rename.call((id, label)),
+                                        on_marker_recolor: move |(id, color)| on_marker_recolor.call((id, color)),
                                         selected_markers: selected_markers.clone(),
                                         on_marker_select: move |id| on_marker_select.call(id),
                                         dragged_asset: dragged_asset,
@@ -502,6 +611,41 @@ pub fn TimelinePanel(
                                     }
                                 }
                                 
+                                // Drop target for OS files; covers the rows so the
+                                // drop coordinates are relative to the track area
+                                if file_drop_active() {
+                                    div {
+                                        style: "
+                                            position: absolute;
+                                            inset: 0;
+                                            background-color: rgba(59, 130, 246, 0.08);
+                                            outline: 1px dashed rgba(59, 130, 246, 0.6);
+                                            outline-offset: -1px;
+                                            z-index: 20;
+                                        ",
+                                        ondragover: move |e: DragEvent| e.prevent_default(),
+                                        ondragleave: move |_| file_drop_active.set(false),
+                                        ondrop: {
+                                            let tracks = tracks.clone();
+                                            move |e: DragEvent| {
+                                                e.prevent_default();
+                                                file_drop_active.set(false);
+                                                let paths: Vec<std::path::PathBuf> =
+                                                    e.files().iter().map(|file| file.path()).collect();
+                                                if paths.is_empty() {
+                                                    return;
+                                                }
+                                                let coords = e.element_coordinates();
+                                                let Some(track_id) = drop_track_at(&tracks, coords.y) else {
+                                                    return;
+                                                };
+                                                let time = drop_time_at(coords.x, zoom, fps);
+                                                on_files_drop.call((track_id, time, paths));
+                                            }
+                                        },
+                                    }
+                                }
+
                                 if let Some(snap_pos) = snap_indicator_pos {
                                     div {
                                         style: "
@@ -534,6 +678,28 @@ pub fn TimelinePanel(
                         }
                     }
                 }
+
+                // Overview of the whole project for navigating long timelines
+                if show_minimap() {
+                    if let Some(viewport_width) = viewport_width {
+                        div {
+                            style: "display: flex; flex-shrink: 0; background-color: {BG_ELEVATED};",
+                            div {
+                                style: "width: {track_label_width}px; min-width: {track_label_width}px; border-right: 1px solid {BORDER_DEFAULT}; border-top: 1px solid {BORDER_DEFAULT};",
+                            }
+                            TimelineMinimap {
+                                tracks: tracks.clone(),
+                                clips: clips.clone(),
+                                duration: duration,
+                                zoom: zoom,
+                                scroll_offset: scroll_offset,
+                                current_time: current_time,
+                                viewport_width: viewport_width,
+                                on_scroll: move |offset: f64| on_scroll.call(offset),
+                            }
+                        }
+                    }
+                }
             }
         }
     }
@@ -1,5 +1,6 @@
 use dioxus::prelude::*;
 use crate::constants::{BG_HOVER, TEXT_MUTED};
+use crate::core::audio::meter::{meter_fraction, MeterChannel};
 
 /// Playback button
 #[component]
@@ -19,3 +20,74 @@ pub(crate) fn PlaybackBtn(
     }
 }
 
+
+/// Master output meter with mute/solo state beside the transport buttons
+#[component]
+pub(crate) fn OutputMeter(
+    levels: Signal<[MeterChannel; 2]>,
+    muted_tracks: usize,
+    any_soloed: bool,
+    silenced: bool,  // no audio-capable track is audible
+) -> Element {
+    let channels = levels();
+    let clipping = channels.iter().any(|channel| channel.is_clipping());
+    let clip_color = if clipping { "#ef4444" } else { BG_HOVER };
+    let bar_opacity = if silenced { 0.35 } else { 1.0 };
+    let meter_title = if silenced {
+        "Output level (all audio tracks are muted)"
+    } else {
+        "Output level"
+    };
+    rsx! {
+        div {
+            style: "display: flex; align-items: center; gap: 6px; margin-left: 8px;",
+            div {
+                style: "display: flex; flex-direction: column; gap: 2px; width: 64px; opacity: {bar_opacity};",
+                title: "{meter_title}",
+                for (index, channel) in channels.iter().enumerate() {
+                    {
+                        let level_pct = meter_fraction(channel.level) * 100.0;
+                        let peak_pct = meter_fraction(channel.peak) * 100.0;
+                        let peak_color = if channel.is_clipping() { "#ef4444" } else { TEXT_MUTED };
+                        rsx! {
+                            div {
+                                key: "{index}",
+                                style: "position: relative; height: 3px; border-radius: 1px; background-color: {BG_HOVER}; overflow: hidden;",
+                                div {
+                                    style: "
+                                        position: absolute; left: 0; top: 0; bottom: 0; width: {level_pct}%;
+                                        background: linear-gradient(90deg, #22c55e 0%, #22c55e 70%, #facc15 90%, #ef4444 100%);
+                                        background-size: 64px 100%;
+                                    ",
+                                }
+                                if channel.peak > 0.0 {
+                                    div {
+                                        style: "position: absolute; top: 0; bottom: 0; left: calc({peak_pct}% - 1px); width: 1px; background-color: {peak_color};",
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            div {
+                style: "width: 6px; height: 6px; border-radius: 50%; background-color: {clip_color};",
+                title: "Clipping",
+            }
+            if muted_tracks > 0 {
+                span {
+                    style: "font-size: 9px; font-weight: 600; color: #f87171;",
+                    title: "Muted tracks",
+                    "M{muted_tracks}"
+                }
+            }
+            if any_soloed {
+                span {
+                    style: "font-size: 9px; font-weight: 600; color: #facc15;",
+                    title: "Solo active",
+                    "S"
+                }
+            }
+        }
+    }
+}
@@ -1,10 +1,31 @@
 use dioxus::prelude::*;
 use crate::constants::{BORDER_STRONG, BORDER_SUBTLE, TEXT_DIM};
+use crate::core::timecode::format_time;
+use crate::core::timeline_snap::snap_time_to_frame;
+use crate::state::TimeFormat;
+
+/// Playhead time for a click `x` pixels into the ruler's visible area,
+/// quantized to a frame and clamped to `[0, duration]`. Pass a zero
+/// `scroll_offset` when `x` is already in scroll (content) space.
+pub(crate) fn ruler_time_at(x: f64, zoom: f64, scroll_offset: f64, duration: f64, fps: f64) -> f64 {
+    if zoom <= 0.0 || !x.is_finite() {
+        return 0.0;
+    }
+    let duration = duration.max(0.0);
+    let time = ((x + scroll_offset) / zoom).clamp(0.0, duration);
+    snap_time_to_frame(time, fps.max(1.0)).clamp(0.0, duration)
+}
 
 /// Time ruler with tick marks and labels
 /// All elements here use pointer-events: none so clicks pass through to parent
 #[component]
-pub(crate) fn TimeRuler(duration: f64, zoom: f64, scroll_offset: f64, fps: f64) -> Element {
+pub(crate) fn TimeRuler(
+    duration: f64,
+    zoom: f64,
+    scroll_offset: f64,
+    fps: f64,
+    time_format: TimeFormat,
+) -> Element {
     let _ = scroll_offset;
     let fps = fps.max(1.0);
     let fps_i = fps.round().max(1.0) as i32;
@@ -83,9 +104,7 @@ pub(crate) fn TimeRuler(duration: f64, zoom: f64, scroll_offset: f64, fps: f64)
                 {
                     let t = i as f64 * seconds_per_major_tick;
                     let x = t * zoom;
-                    let minutes = t as i32 / 60;
-                    let seconds = t as i32 % 60;
-                    let label = format!("{}:{:02}", minutes, seconds);
+                    let label = format_time(t, fps, time_format);
                     
                     if x <= content_width + 50.0 {
                         rsx! {
@@ -142,3 +161,21 @@ pub(crate) fn TimeRuler(duration: f64, zoom: f64, scroll_offset: f64, fps: f64)
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ruler_pixel_to_frame_time() {
+        // 250 px into a view scrolled 1000 px at 100 px/s is 12.5 s
+        assert_eq!(ruler_time_at(250.0, 100.0, 1000.0, 60.0, 30.0), 12.5);
+
+        // Times between frames round to the nearest frame at 24 fps
+        let time = ruler_time_at(104.0, 100.0, 0.0, 60.0, 24.0);
+        assert!((time - 25.0 / 24.0).abs() < 1e-9, "{}", time);
+
+        // Clicks past either end stay inside the project
+        assert_eq!(ruler_time_at(-40.0, 100.0, 0.0, 60.0, 30.0), 0.0);
+        assert_eq!(ruler_time_at(9000.0, 100.0, 0.0, 60.0, 30.0), 60.0);
+    }
+}
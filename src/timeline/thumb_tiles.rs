@@ -0,0 +1,214 @@
+//! Thumbnail tiles for visual clips on the timeline.
+//!
+//! Working out which cached thumbnail each tile shows touches the disk once per
+//! tile, so it runs on the blocking pool instead of during render. Each clip
+//! keeps a `ThumbTileRequests` counter; starting a request supersedes the ones
+//! before it, so rapid zoom or trim changes only resolve the latest layout.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::core::thumbnailer::Thumbnailer;
+use crate::state::Clip;
+
+use super::{MAX_THUMB_TILES, THUMB_TILE_WIDTH_PX};
+
+/// Quiet period before a tile request starts, so a zoom drag resolves once.
+pub(crate) const THUMB_TILE_DEBOUNCE_MS: u64 = 60;
+/// Clips narrower than this show no tiles.
+const MIN_TILED_CLIP_WIDTH_PX: f64 = 40.0;
+
+/// Tile width and the source time each tile shows.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ThumbTileLayout {
+    pub tile_width: f64,
+    pub source_times: Vec<f64>,
+}
+
+/// Everything a resolved tile strip depends on. A new key means the strip is
+/// stale and has to be resolved again.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ThumbTileKey {
+    pub asset_id: Uuid,
+    pub cache_buster: u64,
+    pub tile_width_bits: u64,
+    pub source_time_bits: Vec<u64>,
+}
+
+impl ThumbTileKey {
+    pub fn new(asset_id: Uuid, cache_buster: u64, layout: &ThumbTileLayout) -> Self {
+        Self {
+            asset_id,
+            cache_buster,
+            tile_width_bits: layout.tile_width.to_bits(),
+            source_time_bits: layout.source_times.iter().map(|time| time.to_bits()).collect(),
+        }
+    }
+}
+
+/// A layout for the tile task to resolve, with the refresh tick it was laid
+/// out on.
+pub(crate) struct ThumbTileRequest {
+    pub layout: ThumbTileLayout,
+    pub key: ThumbTileKey,
+    pub refresh_tick: u64,
+}
+
+/// Resolved tile URLs for a key.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ThumbTileStrip {
+    pub key: ThumbTileKey,
+    pub tile_width: f64,
+    pub urls: Vec<String>,
+    /// False while the asset has no thumbnails on disk yet; the strip is then
+    /// retried on the next refresh tick.
+    pub complete: bool,
+    pub refresh_tick: u64,
+}
+
+/// Split a clip `clip_width` pixels wide into tiles, at most `MAX_THUMB_TILES`
+/// of them. `None` when the clip is too narrow to show any.
+pub(crate) fn thumb_tile_layout(clip: &Clip, clip_width: f64, zoom: f64) -> Option<ThumbTileLayout> {
+    if clip_width <= MIN_TILED_CLIP_WIDTH_PX || zoom <= 0.0 {
+        return None;
+    }
+    let mut tile_width = THUMB_TILE_WIDTH_PX;
+    if (clip_width / tile_width).ceil() as usize > MAX_THUMB_TILES {
+        tile_width = (clip_width / MAX_THUMB_TILES as f64).ceil();
+    }
+    let tile_count = ((clip_width / tile_width).ceil() as usize).max(1);
+    let tile_time = tile_width / zoom;
+    let source_times = (0..tile_count)
+        .map(|index| {
+            let time_in_clip = (index as f64 * tile_time).min(clip.duration.max(0.0));
+            clip.source_time_at(clip.start_time + time_in_clip)
+        })
+        .collect();
+    Some(ThumbTileLayout {
+        tile_width,
+        source_times,
+    })
+}
+
+/// Hands out request tickets for one clip's tile strip.
+#[derive(Clone, Default)]
+pub(crate) struct ThumbTileRequests {
+    generation: Arc<AtomicU64>,
+}
+
+impl ThumbTileRequests {
+    /// Start a request, superseding every earlier one.
+    pub fn begin(&self) -> ThumbTileTicket {
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        ThumbTileTicket {
+            generation,
+            current: Arc::clone(&self.generation),
+        }
+    }
+
+    /// Supersede any request in flight without starting a new one.
+    pub fn cancel(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// One tile request. It stops doing work once a newer request has begun.
+#[derive(Clone)]
+pub(crate) struct ThumbTileTicket {
+    generation: u64,
+    current: Arc<AtomicU64>,
+}
+
+impl ThumbTileTicket {
+    pub fn is_current(&self) -> bool {
+        self.current.load(Ordering::SeqCst) == self.generation
+    }
+}
+
+/// Look up the thumbnail URL for every tile. Tiles without their own
+/// thumbnail reuse the first tile's. Returns `None` once `ticket` is
+/// superseded; the boolean is false when no thumbnail exists at all.
+pub(crate) fn resolve_thumb_tile_urls(
+    thumbnailer: &Thumbnailer,
+    asset_id: Uuid,
+    source_times: &[f64],
+    cache_buster: u64,
+    ticket: &ThumbTileTicket,
+) -> Option<(Vec<String>, bool)> {
+    let to_url = |path: std::path::PathBuf| {
+        format!("{}?v={}", crate::utils::get_local_file_url(&path), cache_buster)
+    };
+    let Some(fallback) = source_times
+        .first()
+        .and_then(|time| thumbnailer.get_thumbnail_path(asset_id, *time))
+        .map(to_url)
+    else {
+        return ticket.is_current().then(|| (Vec::new(), false));
+    };
+    let mut urls = Vec::with_capacity(source_times.len());
+    for (index, time) in source_times.iter().enumerate() {
+        if !ticket.is_current() {
+            return None;
+        }
+        let url = if index == 0 {
+            fallback.clone()
+        } else {
+            thumbnailer
+                .get_thumbnail_path(asset_id, *time)
+                .map(to_url)
+                .unwrap_or_else(|| fallback.clone())
+        };
+        urls.push(url);
+    }
+    ticket.is_current().then_some((urls, true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tile_times_follow_the_clip() {
+        let mut clip = Clip::new(Uuid::new_v4(), Uuid::new_v4(), 10.0, 4.0);
+        clip.trim_in_seconds = 2.0;
+
+        // 4 s at 60 px/s is 240 px: four 60 px tiles, one second apart
+        let layout = thumb_tile_layout(&clip, 240.0, 60.0).unwrap();
+        assert_eq!(layout.tile_width, THUMB_TILE_WIDTH_PX);
+        assert_eq!(layout.source_times, vec![2.0, 3.0, 4.0, 5.0]);
+
+        // Very wide clips widen the tiles instead of exceeding the cap
+        let layout = thumb_tile_layout(&clip, 60_000.0, 15_000.0).unwrap();
+        assert!(layout.source_times.len() <= MAX_THUMB_TILES);
+        assert!(layout.tile_width > THUMB_TILE_WIDTH_PX);
+
+        // Narrow clips get no tiles
+        assert_eq!(thumb_tile_layout(&clip, 30.0, 60.0), None);
+    }
+
+    #[test]
+    fn test_newer_tile_request_supersedes_older() {
+        let requests = ThumbTileRequests::default();
+        let first = requests.begin();
+        assert!(first.is_current());
+
+        // A zoom change starts a second request; the first must stop
+        let second = requests.begin();
+        assert!(!first.is_current());
+        assert!(second.is_current());
+
+        // Cancelling (e.g. the clip scrolled away) stops the latest one too
+        requests.cancel();
+        assert!(!second.is_current());
+
+        // Clones share the counter, so a request started elsewhere still wins
+        let shared = requests.clone();
+        let third = shared.begin();
+        assert!(third.is_current());
+        let fourth = requests.begin();
+        assert!(!third.is_current());
+        assert!(fourth.is_current());
+    }
+}
@@ -1,5 +1,9 @@
 use dioxus::prelude::*;
-use crate::constants::{BG_HOVER, BORDER_SUBTLE, TEXT_SECONDARY};
+use crate::constants::{
+    ACCENT_PRIMARY, BG_HOVER, BORDER_SUBTLE, TEXT_DIM, TEXT_MUTED, TEXT_SECONDARY,
+};
+
+use super::track_height_px;
 
 /// Track label in the sidebar
 #[component]
@@ -8,26 +12,92 @@ pub fn TrackLabel(
     color: &'static str,
     track_id: uuid::Uuid,
     selected: bool,
+    collapsed: bool,
+    muted: bool,
+    soloed: bool,
+    active: bool,  // false when silenced by mute or another track's solo
+    drop_above: bool,  // drop indicators while a track is being dragged
+    drop_below: bool,
+    on_drag_start: EventHandler<uuid::Uuid>,
+    on_drag_over: EventHandler<uuid::Uuid>,
     on_select: EventHandler<uuid::Uuid>,
     on_context_menu: EventHandler<(f64, f64, uuid::Uuid)>,
+    on_toggle_collapse: EventHandler<uuid::Uuid>,
 ) -> Element {
     let bg = if selected { BG_HOVER } else { "transparent" };
+    let name_color = if active { TEXT_SECONDARY } else { TEXT_DIM };
+    let height = track_height_px(collapsed);
+    let drop_shadow = if drop_above {
+        format!("box-shadow: inset 0 2px 0 {};", ACCENT_PRIMARY)
+    } else if drop_below {
+        format!("box-shadow: inset 0 -2px 0 {};", ACCENT_PRIMARY)
+    } else {
+        String::new()
+    };
+    let (chevron, chevron_title, font_size, bar_height) = if collapsed {
+        ("▸", "Expand track", 10, 8)
+    } else {
+        ("▾", "Collapse track", 12, 16)
+    };
     rsx! {
         div {
             style: "
-                display: flex; align-items: center; gap: 10px; height: 36px; 
-                padding: 0 12px; border-bottom: 1px solid {BORDER_SUBTLE}; 
-                font-size: 12px; color: {TEXT_SECONDARY}; cursor: pointer;
+                display: flex; align-items: center; gap: 8px; height: {height}px; 
+                padding: 0 12px 0 4px; border-bottom: 1px solid {BORDER_SUBTLE}; 
+                font-size: {font_size}px; color: {TEXT_SECONDARY}; cursor: pointer;
                 background-color: {bg};
+                {drop_shadow}
             ",
             onclick: move |_| on_select.call(track_id),
+            onmouseenter: move |_| on_drag_over.call(track_id),
             oncontextmenu: move |e| {
                 e.prevent_default();
                 let coords = e.client_coordinates();
                 on_context_menu.call((coords.x, coords.y, track_id));
             },
-            div { style: "width: 3px; height: 16px; border-radius: 2px; background-color: {color};" }
-            span { "{name}" }
+            span {
+                style: "color: {TEXT_DIM}; font-size: 10px; cursor: grab; user-select: none;",
+                title: "Drag to reorder",
+                onmousedown: move |e| {
+                    e.prevent_default();
+                    e.stop_propagation();
+                    on_drag_start.call(track_id);
+                },
+                onclick: move |e| e.stop_propagation(),
+                "⠿"
+            }
+            button {
+                style: "
+                    width: 16px; height: 100%; padding: 0; border: none;
+                    background: transparent; color: {TEXT_MUTED};
+                    font-size: 10px; line-height: 1; cursor: pointer;
+                ",
+                title: "{chevron_title}",
+                onclick: move |e| {
+                    e.stop_propagation();
+                    on_toggle_collapse.call(track_id);
+                },
+                "{chevron}"
+            }
+            div { style: "width: 3px; height: {bar_height}px; border-radius: 2px; background-color: {color};" }
+            span {
+                style: "flex: 1; min-width: 0; white-space: nowrap; overflow: hidden; text-overflow: ellipsis; color: {name_color};",
+                "{name}"
+            }
+            if muted {
+                span {
+                    style: "font-size: 9px; font-weight: 600; color: #f87171;",
+                    title: "Muted (M)",
+                    "M"
+                }
+            }
+            if soloed {
+                span {
+                    style: "font-size: 9px; font-weight: 600; color: #facc15;",
+                    title: "Soloed (Shift+S)",
+                    "S"
+                }
+            }
         }
     }
 }
@@ -13,10 +13,11 @@ use crate::constants::{
     TEXT_PRIMARY,
 };
 use crate::core::timeline_snap::{snap_time_to_frame, SnapTarget};
-use crate::state::TrackType;
+use crate::state::{find_overlaps, TrackType};
 
 use super::clip_element::ClipElement;
 use super::marker_element::MarkerElement;
+use super::track_height_px;
 
 /// Track row content area
 #[component]
@@ -24,14 +25,19 @@ pub fn TrackRow(
     width: i32,
     track_id: uuid::Uuid,
     track_type: TrackType,
+    waveform_color: [u8; 3],
+    collapsed: bool,
     clips: Vec<crate::state::Clip>,
     markers: Vec<crate::state::Marker>,
     assets: Vec<crate::state::Asset>,
     thumbnailer: std::sync::Arc<crate::core::thumbnailer::Thumbnailer>,
     thumbnail_cache_buster: u64,
+    thumbnail_refresh_tick: u64,
     clip_cache_buckets: std::sync::Arc<HashMap<uuid::Uuid, Vec<bool>>>,
     project_root: Option<std::path::PathBuf>,
     audio_waveform_cache_buster: Signal<u64>,
+    waveform_peaks_per_second: f64,
+    resample_quality: crate::state::ResampleQuality,
     zoom: f64,  // pixels per second
     fps: f64,
     duration: f64,
@@ -40,6 +46,13 @@ pub fn TrackRow(
     on_clip_move: EventHandler<(uuid::Uuid, f64)>,  // (clip_id, new_start_time)
     on_clip_resize: EventHandler<(uuid::Uuid, f64, f64)>,  // (clip_id, new_start, new_duration)
     on_clip_move_track: EventHandler<(uuid::Uuid, i32)>,
+    on_clip_freeze_from: EventHandler<(uuid::Uuid, f64)>,
+    on_clip_link: EventHandler<uuid::Uuid>,
+    on_clip_unlink: EventHandler<uuid::Uuid>,
+    on_group_selection: EventHandler<()>,
+    on_clip_ungroup: EventHandler<uuid::Uuid>,
+    on_clip_set_color: EventHandler<(uuid::Uuid, Option<[u8; 3]>)>,
+    on_clip_fade: EventHandler<(uuid::Uuid, f64, f64)>,  // (clip_id, fade_in, fade_out)
     selected_clips: Vec<uuid::Uuid>,
     on_clip_select: EventHandler<uuid::Uuid>,
     on_snap_preview: EventHandler<Option<f64>>,
@@ -47,6 +60,8 @@ pub fn TrackRow(
     on_marker_add: EventHandler<f64>,
     on_marker_move: EventHandler<(uuid::Uuid, f64)>,
     on_marker_delete: EventHandler<uuid::Uuid>,
+    on_marker_rename: EventHandler<(uuid::Uuid, Option<String>)>,
+    on_marker_recolor: EventHandler<(uuid::Uuid, Option<String>)>,
     selected_markers: Vec<uuid::Uuid>,
     on_marker_select: EventHandler<uuid::Uuid>,
     dragged_asset: Option<uuid::Uuid>,
@@ -56,10 +71,15 @@ pub fn TrackRow(
     let fps = fps.max(1.0);
     let mut show_marker_menu = use_signal(|| false);
     let mut marker_menu_pos = use_signal(|| (0.0, 0.0));
+    let mut marker_menu_time = use_signal(|| 0.0);
     // Filter clips for this track
     let track_clips: Vec<_> = clips.iter()
         .filter(|c| c.track_id == track_id)
         .collect();
+    let overlapping: Vec<uuid::Uuid> = find_overlaps(&track_clips)
+        .into_iter()
+        .flat_map(|(a, b)| [a, b])
+        .collect();
 
     let track_markers: Vec<_> = if track_type == TrackType::Marker {
         markers
@@ -86,11 +106,12 @@ pub fn TrackRow(
     } else { false };
     
     let bg_color = if can_drop { BG_HOVER } else { BG_BASE };
+    let row_height = track_height_px(collapsed);
     
     rsx! {
         div { 
             style: "
-                height: 36px; min-width: {width}px; 
+                height: {row_height}px; min-width: {width}px; 
                 border-bottom: 1px solid {BORDER_SUBTLE}; 
                 background-color: {bg_color};
                 position: relative;
@@ -101,6 +122,7 @@ pub fn TrackRow(
                 if track_type == TrackType::Marker {
                     let coords = e.client_coordinates();
                     marker_menu_pos.set((coords.x, coords.y));
+                    marker_menu_time.set((e.element_coordinates().x / zoom).max(0.0));
                     show_marker_menu.set(true);
                 }
             },
@@ -131,20 +153,43 @@ pub fn TrackRow(
                 ClipElement {
                     key: "{clip.id}",
                     clip: (*clip).clone(),
+                    partner_clip_ids: clips
+                        .iter()
+                        .filter(|other| other.id != clip.id)
+                        .filter(|other| {
+                            (clip.link_group.is_some() && other.link_group == clip.link_group)
+                                || (clip.group_id.is_some() && other.group_id == clip.group_id)
+                        })
+                        .map(|other| other.id)
+                        .collect::<Vec<_>>(),
                     assets: assets.clone(),
                     thumbnailer: thumbnailer.clone(),
                     thumbnail_cache_buster: thumbnail_cache_buster,
+                    thumbnail_refresh_tick: thumbnail_refresh_tick,
                     clip_cache_buckets: clip_cache_buckets.clone(),
                     project_root: project_root.clone(),
                     audio_waveform_cache_buster: audio_waveform_cache_buster,
+                    waveform_peaks_per_second: waveform_peaks_per_second,
+                    resample_quality: resample_quality,
                     zoom: zoom,
                     fps: fps,
-                    clip_color: clip_color,
+                    clip_color: clip.resolved_color(clip_color),
+                    waveform_color: waveform_color,
+                    collapsed: collapsed,
                     on_delete: move |id| on_clip_delete.call(id),
                     on_move: move |(id, time)| on_clip_move.call((id, time)),
                     on_resize: move |(id, start, dur)| on_clip_resize.call((id, start, dur)),
                     on_move_track: move |(id, direction)| on_clip_move_track.call((id, direction)),
                     is_selected: selected_clips.contains(&clip.id),
+                    overlapping: overlapping.contains(&clip.id),
+                    current_time: current_time,
+                    on_freeze_from: move |(id, time)| on_clip_freeze_from.call((id, time)),
+                    on_link: move |id| on_clip_link.call(id),
+                    on_unlink: move |id| on_clip_unlink.call(id),
+                    on_group_selection: move |_| on_group_selection.call(()),
+                    on_ungroup: move |id| on_clip_ungroup.call(id),
+                    on_set_color: move |(id, color)| on_clip_set_color.call((id, color)),
+                    on_fade: move |(id, fade_in, fade_out)| on_clip_fade.call((id, fade_in, fade_out)),
                     on_select: move |id| on_clip_select.call(id),
                     on_snap_preview: move |time| on_snap_preview.call(time),
                     snap_targets: snap_targets.clone(),
@@ -163,6 +208,8 @@ pub fn TrackRow(
                     on_select: move |id| on_marker_select.call(id),
                     on_move: move |(id, time)| on_marker_move.call((id, time)),
                     on_delete: move |id| on_marker_delete.call(id),
+                    on_rename: move |(id, label)| on_marker_rename.call((id, label)),
+                    on_recolor: move |(id, color)| on_marker_recolor.call((id, color)),
                     on_snap_preview: move |time| on_snap_preview.call(time),
                     snap_targets: snap_targets.clone(),
                 }
@@ -202,6 +249,17 @@ pub fn TrackRow(
                         },
                         "Add Marker at Playhead"
                     }
+                    div {
+                        style: "
+                            padding: 6px 12px; color: {TEXT_PRIMARY}; cursor: pointer;
+                            transition: background-color 0.1s ease;
+                        ",
+                        onclick: move |_| {
+                            on_marker_add.call(marker_menu_time());
+                            show_marker_menu.set(false);
+                        },
+                        "Add Marker Here"
+                    }
                 }
             }
         }